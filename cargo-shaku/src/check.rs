@@ -0,0 +1,446 @@
+//! Checks on the module graph built from a scan
+
+use crate::scan::{Dependency, ModuleDecl, Scan, SubmoduleDecl};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Build the graph of every module found in the scan, and check it for
+/// unresolved dependencies, cycles, and unused components.
+pub fn check(scan: &Scan) -> Report {
+    let mut report = Report {
+        modules: Vec::new(),
+        issues: Vec::new(),
+    };
+
+    for error in scan.parse_errors() {
+        report.warning(format!("could not parse {}", error));
+    }
+
+    for module in scan.modules() {
+        let graph = ModuleGraph::new(scan, module);
+        graph.check_unresolved(&mut report);
+        graph.check_cycles(&mut report);
+        report.modules.push(graph);
+    }
+
+    check_unused(scan, &mut report);
+
+    report
+}
+
+/// The result of checking the modules. The `Display` impl prints the graph
+/// followed by the issues.
+#[derive(Debug)]
+pub struct Report {
+    modules: Vec<ModuleGraph>,
+    issues: Vec<Issue>,
+}
+
+impl Report {
+    /// The graphs of the modules which were checked
+    pub fn modules(&self) -> &[ModuleGraph] {
+        &self.modules
+    }
+
+    /// The issues found
+    pub fn issues(&self) -> &[Issue] {
+        &self.issues
+    }
+
+    /// Check if any errors were found (warnings are ignored)
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == Severity::Error)
+    }
+
+    fn error(&mut self, message: String) {
+        self.issues.push(Issue {
+            severity: Severity::Error,
+            message,
+        });
+    }
+
+    fn warning(&mut self, message: String) {
+        self.issues.push(Issue {
+            severity: Severity::Warning,
+            message,
+        });
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for module in &self.modules {
+            writeln!(f, "{}", module)?;
+        }
+
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+
+        let errors = self
+            .issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .count();
+
+        write!(
+            f,
+            "Checked {} module(s): {} error(s), {} warning(s)",
+            self.modules.len(),
+            errors,
+            self.issues.len() - errors
+        )
+    }
+}
+
+/// How severe an issue is. Only errors fail the check.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single issue found while checking
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Error => write!(f, "error: {}", self.message),
+            Severity::Warning => write!(f, "warning: {}", self.message),
+        }
+    }
+}
+
+/// The kind of a service
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ServiceKind {
+    Component,
+    Provider,
+}
+
+impl fmt::Display for ServiceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceKind::Component => write!(f, "component"),
+            ServiceKind::Provider => write!(f, "provider"),
+        }
+    }
+}
+
+/// A service implemented in a module
+#[derive(Debug, Clone)]
+pub struct GraphService {
+    /// The implementation type, as listed in the module
+    pub ty: String,
+    pub kind: ServiceKind,
+    /// The interface, if the implementation was found
+    pub interface: Option<String>,
+    pub dependencies: Vec<Dependency>,
+}
+
+/// The services of a single module
+#[derive(Debug, Clone)]
+pub struct ModuleGraph {
+    pub name: String,
+    pub services: Vec<GraphService>,
+    pub submodules: Vec<SubmoduleDecl>,
+}
+
+impl ModuleGraph {
+    fn new(scan: &Scan, module: &ModuleDecl) -> Self {
+        let mut graph = ModuleGraph {
+            name: module.name.clone(),
+            services: Vec::new(),
+            submodules: Vec::new(),
+        };
+        graph.add_module(scan, module, &mut HashSet::new());
+        graph
+    }
+
+    /// Add the services of the module. Extended modules are added first, so
+    /// their services can be overridden.
+    fn add_module<'a>(
+        &mut self,
+        scan: &'a Scan,
+        module: &'a ModuleDecl,
+        seen: &mut HashSet<&'a str>,
+    ) {
+        if !seen.insert(&module.name) {
+            return;
+        }
+
+        if let Some(base) = &module.extends {
+            if let Some(base) = scan.modules().iter().find(|other| &other.name == base) {
+                self.add_module(scan, base, seen);
+            }
+        }
+
+        let components = module
+            .components
+            .iter()
+            .map(|ty| (ty, ServiceKind::Component));
+        let providers = module
+            .providers
+            .iter()
+            .map(|ty| (ty, ServiceKind::Provider));
+
+        for (ty, kind) in components.chain(providers) {
+            let service = scan.services().get(service_key(ty));
+            let service = GraphService {
+                ty: ty.clone(),
+                kind,
+                interface: service.map(|service| service.interface.clone()),
+                dependencies: service
+                    .map(|service| service.dependencies.clone())
+                    .unwrap_or_default(),
+            };
+
+            // Remove overridden services from the extended module
+            if let Some(interface) = &service.interface {
+                self.services.retain(|existing| {
+                    existing.kind != kind || existing.interface.as_ref() != Some(interface)
+                });
+            }
+
+            self.services.push(service);
+        }
+
+        self.submodules.extend(module.submodules.iter().cloned());
+    }
+
+    /// Check if the module provides the interface
+    fn resolves(&self, dependency: &Dependency) -> bool {
+        let kind = if dependency.provided {
+            ServiceKind::Provider
+        } else {
+            ServiceKind::Component
+        };
+        let local = self.services.iter().any(|service| {
+            service.kind == kind && service.interface.as_ref() == Some(&dependency.interface)
+        });
+        let from_submodule = self.submodules.iter().any(|submodule| {
+            let list = match kind {
+                ServiceKind::Component => &submodule.components,
+                ServiceKind::Provider => &submodule.providers,
+            };
+            let factory = kind == ServiceKind::Component
+                && submodule
+                    .providers
+                    .iter()
+                    .any(|provider| dependency.interface == format!("Factory<{}>", provider));
+
+            list.contains(&dependency.interface) || factory
+        });
+
+        local || from_submodule
+    }
+
+    fn check_unresolved(&self, report: &mut Report) {
+        // Unknown services could provide anything
+        if self
+            .services
+            .iter()
+            .any(|service| service.interface.is_none())
+        {
+            return;
+        }
+
+        for service in &self.services {
+            for dependency in &service.dependencies {
+                if !self.resolves(dependency) {
+                    report.error(format!(
+                        "`{}` in `{}` depends on {} `{}`, which the module does not provide",
+                        service.ty,
+                        self.name,
+                        dependency_kind(dependency),
+                        dependency.interface
+                    ));
+                }
+            }
+        }
+    }
+
+    /// The indices of the services in this module which satisfy the service's
+    /// dependencies
+    fn edges(&self, service: &GraphService) -> Vec<usize> {
+        service
+            .dependencies
+            .iter()
+            .flat_map(|dependency| {
+                self.services
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, other)| {
+                        other.interface.as_ref() == Some(&dependency.interface)
+                            && (other.kind == ServiceKind::Provider) == dependency.provided
+                    })
+                    .map(|(i, _)| i)
+            })
+            .collect()
+    }
+
+    fn check_cycles(&self, report: &mut Report) {
+        let edges: Vec<Vec<usize>> = self
+            .services
+            .iter()
+            .map(|service| self.edges(service))
+            .collect();
+        let mut state = vec![VisitState::New; self.services.len()];
+        let mut stack = Vec::new();
+        let mut cycles = Vec::new();
+
+        for start in 0..self.services.len() {
+            find_cycles(start, &edges, &mut state, &mut stack, &mut cycles);
+        }
+
+        for cycle in cycles {
+            let path: Vec<&str> = cycle
+                .iter()
+                .chain(cycle.first())
+                .map(|&i| self.services[i].ty.as_str())
+                .collect();
+
+            report.error(format!(
+                "dependency cycle in `{}`: {}",
+                self.name,
+                path.join(" -> ")
+            ));
+        }
+    }
+}
+
+impl fmt::Display for ModuleGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        for service in &self.services {
+            let interface = service.interface.as_deref().unwrap_or("<unknown>");
+            write!(f, "\n  {} {}: {}", service.kind, service.ty, interface)?;
+
+            for dependency in &service.dependencies {
+                write!(
+                    f,
+                    "\n    depends on {} {}",
+                    dependency_kind(dependency),
+                    dependency.interface
+                )?;
+            }
+        }
+
+        for submodule in &self.submodules {
+            write!(f, "\n  use {}", submodule.name)?;
+
+            for component in &submodule.components {
+                write!(f, "\n    component {}", component)?;
+            }
+
+            for provider in &submodule.providers {
+                write!(f, "\n    provider {}", provider)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum VisitState {
+    New,
+    InProgress,
+    Done,
+}
+
+/// Depth-first search which records each cycle once
+fn find_cycles(
+    node: usize,
+    edges: &[Vec<usize>],
+    state: &mut [VisitState],
+    stack: &mut Vec<usize>,
+    cycles: &mut Vec<Vec<usize>>,
+) {
+    if state[node] != VisitState::New {
+        return;
+    }
+
+    state[node] = VisitState::InProgress;
+    stack.push(node);
+
+    for &next in &edges[node] {
+        match state[next] {
+            VisitState::New => find_cycles(next, edges, state, stack, cycles),
+            VisitState::InProgress => {
+                let start = stack.iter().position(|&i| i == next).unwrap();
+                cycles.push(stack[start..].to_vec());
+            }
+            VisitState::Done => {}
+        }
+    }
+
+    stack.pop();
+    state[node] = VisitState::Done;
+}
+
+/// Warn about components which are not injected by any service in a module or
+/// exposed to another module
+fn check_unused(scan: &Scan, report: &mut Report) {
+    let mut used: HashSet<&str> = HashSet::new();
+
+    for service in report.modules.iter().flat_map(|module| &module.services) {
+        used.extend(
+            service
+                .dependencies
+                .iter()
+                .filter(|dependency| !dependency.provided)
+                .map(|dependency| dependency.interface.as_str()),
+        );
+    }
+
+    for module in scan.modules() {
+        for submodule in &module.submodules {
+            used.extend(submodule.components.iter().map(String::as_str));
+        }
+    }
+
+    let mut warned: HashSet<&str> = HashSet::new();
+
+    for module in &report.modules {
+        for service in &module.services {
+            let interface = match (&service.interface, service.kind) {
+                (Some(interface), ServiceKind::Component) => interface,
+                _ => continue,
+            };
+
+            if used.contains(interface.as_str()) || !warned.insert(&service.ty) {
+                continue;
+            }
+
+            report.issues.push(Issue {
+                severity: Severity::Warning,
+                message: format!(
+                    "component `{}` (`{}`) is not injected by any service or used by another module",
+                    service.ty, interface
+                ),
+            });
+        }
+    }
+}
+
+fn dependency_kind(dependency: &Dependency) -> &'static str {
+    if dependency.provided {
+        "provided"
+    } else {
+        "component"
+    }
+}
+
+/// The key of a listed service type (without the generics)
+fn service_key(ty: &str) -> &str {
+    ty.split('<').next().unwrap_or(ty)
+}
@@ -0,0 +1,41 @@
+//! Checks the wiring of [shaku] modules without compiling the crate. This is
+//! the library behind the `cargo shaku check` subcommand.
+//!
+//! The sources are scanned for `module!` invocations and the `Component` /
+//! `Provider` implementations they use, and the resulting module graph is
+//! checked for unresolved dependencies, dependency cycles, and unused
+//! components.
+//!
+//! # Example
+//! ```
+//! let mut scan = cargo_shaku::Scan::new();
+//! scan.scan_source(
+//!     "lib.rs",
+//!     r#"
+//!     #[derive(Component)]
+//!     #[shaku(interface = Foo)]
+//!     struct FooImpl {
+//!         #[shaku(inject)]
+//!         bar: Arc<dyn Bar>,
+//!     }
+//!
+//!     module! {
+//!         MyModule {
+//!             components = [FooImpl],
+//!             providers = []
+//!         }
+//!     }
+//!     "#,
+//! );
+//!
+//! let report = cargo_shaku::check(&scan);
+//! assert!(report.has_errors());
+//! ```
+//!
+//! [shaku]: https://crates.io/crates/shaku
+
+mod check;
+mod scan;
+
+pub use crate::check::*;
+pub use crate::scan::*;
@@ -0,0 +1,49 @@
+use std::env;
+use std::path::PathBuf;
+use std::process;
+
+const USAGE: &str = "\
+Check the wiring of shaku modules
+
+Usage: cargo shaku check [CRATE_DIR]
+
+CRATE_DIR defaults to the current directory. Its `src` directory is scanned if
+it exists, otherwise the directory itself is scanned.";
+
+fn main() {
+    let mut args = env::args().skip(1).peekable();
+
+    // Cargo passes the subcommand name as the first argument
+    if args.peek().map(String::as_str) == Some("shaku") {
+        args.next();
+    }
+
+    match args.next().as_deref() {
+        Some("check") => {}
+        Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            return;
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    }
+
+    let crate_dir = PathBuf::from(args.next().unwrap_or_else(|| ".".to_string()));
+    let src_dir = crate_dir.join("src");
+    let scan_dir = if src_dir.is_dir() { src_dir } else { crate_dir };
+
+    let mut scan = cargo_shaku::Scan::new();
+    if let Err(error) = scan.scan_dir(&scan_dir) {
+        eprintln!("error: failed to read {}: {}", scan_dir.display(), error);
+        process::exit(2);
+    }
+
+    let report = cargo_shaku::check(&scan);
+    println!("{}", report);
+
+    if report.has_errors() {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,509 @@
+//! Scanning of source files for services and `module!` invocations
+
+use proc_macro2::TokenTree;
+use quote::ToTokens;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use syn::parse::{Parse, ParseStream};
+use syn::visit::{self, Visit};
+use syn::visit_mut::{self, VisitMut};
+use syn::{
+    Attribute, Fields, GenericArgument, Ident, ImplItem, ItemImpl, ItemStruct, Macro,
+    PathArguments, Type, TypeParamBound, TypeTraitObject, WherePredicate,
+};
+
+mod kw {
+    syn::custom_keyword!(components);
+    syn::custom_keyword!(providers);
+    syn::custom_keyword!(extends);
+}
+
+/// Everything found while scanning the sources of a crate
+#[derive(Debug, Default)]
+pub struct Scan {
+    services: HashMap<String, Service>,
+    modules: Vec<ModuleDecl>,
+    parse_errors: Vec<String>,
+}
+
+impl Scan {
+    /// Create an empty scan
+    pub fn new() -> Self {
+        Scan::default()
+    }
+
+    /// Scan every `.rs` file in the directory (recursively)
+    pub fn scan_dir(&mut self, dir: &Path) -> io::Result<()> {
+        let mut files = Vec::new();
+        collect_rust_files(dir, &mut files)?;
+        files.sort();
+
+        for file in files {
+            let source = fs::read_to_string(&file)?;
+            self.scan_source(&file.display().to_string(), &source);
+        }
+
+        Ok(())
+    }
+
+    /// Scan the source code of a single file. The name is used in error messages.
+    pub fn scan_source(&mut self, name: &str, source: &str) {
+        let file = match syn::parse_file(source) {
+            Ok(file) => file,
+            Err(error) => {
+                self.parse_errors.push(format!("{}: {}", name, error));
+                return;
+            }
+        };
+
+        let mut visitor = Visitor { scan: self, name };
+        visitor.visit_file(&file);
+    }
+
+    /// The services (components and providers) found, by type name
+    pub fn services(&self) -> &HashMap<String, Service> {
+        &self.services
+    }
+
+    /// The modules found
+    pub fn modules(&self) -> &[ModuleDecl] {
+        &self.modules
+    }
+
+    /// Files or `module!` invocations which could not be parsed
+    pub fn parse_errors(&self) -> &[String] {
+        &self.parse_errors
+    }
+}
+
+/// A component or provider implementation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Service {
+    /// The interface the service implements
+    pub interface: String,
+    /// The interfaces the service injects
+    pub dependencies: Vec<Dependency>,
+}
+
+/// An interface injected into a service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub interface: String,
+    /// If the interface is injected with `#[shaku(provide)]` / `HasProvider`
+    pub provided: bool,
+}
+
+/// A `module!` invocation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleDecl {
+    pub name: String,
+    pub extends: Option<String>,
+    pub components: Vec<String>,
+    pub providers: Vec<String>,
+    pub submodules: Vec<SubmoduleDecl>,
+}
+
+/// A submodule used by a module, and the services it exposes to the module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmoduleDecl {
+    pub name: String,
+    pub components: Vec<String>,
+    pub providers: Vec<String>,
+}
+
+struct Visitor<'a> {
+    scan: &'a mut Scan,
+    name: &'a str,
+}
+
+impl<'ast> Visit<'ast> for Visitor<'_> {
+    fn visit_item_struct(&mut self, item: &'ast ItemStruct) {
+        if let Some(service) = service_from_struct(item) {
+            self.scan.services.insert(item.ident.to_string(), service);
+        }
+
+        visit::visit_item_struct(self, item);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        if let Some((name, service)) = service_from_impl(item) {
+            self.scan.services.insert(name, service);
+        }
+
+        visit::visit_item_impl(self, item);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let is_module_macro = mac
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "module");
+
+        if is_module_macro {
+            match mac.parse_body::<ModuleDecl>() {
+                Ok(module) => self.scan.modules.push(module),
+                Err(error) => self
+                    .scan
+                    .parse_errors
+                    .push(format!("{}: module!: {}", self.name, error)),
+            }
+        }
+
+        visit::visit_macro(self, mac);
+    }
+}
+
+fn collect_rust_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            collect_rust_files(&path, files)?;
+        } else if path.extension().map_or(false, |ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Get a comparable name for a type. Paths are shortened to their last
+/// segment and `dyn` is removed, since the macros allow leaving it out.
+pub fn type_name(ty: &Type) -> String {
+    let mut ty = ty.clone();
+    ShortenPaths.visit_type_mut(&mut ty);
+
+    ty.to_token_stream().to_string().replace(' ', "")
+}
+
+struct ShortenPaths;
+
+impl VisitMut for ShortenPaths {
+    fn visit_type_trait_object_mut(&mut self, trait_object: &mut TypeTraitObject) {
+        trait_object.dyn_token = None;
+        visit_mut::visit_type_trait_object_mut(self, trait_object);
+    }
+
+    fn visit_path_mut(&mut self, path: &mut syn::Path) {
+        if let Some(last) = path.segments.pop() {
+            path.segments.clear();
+            path.segments.push(last.into_value());
+        }
+        path.leading_colon = None;
+        visit_mut::visit_path_mut(self, path);
+    }
+}
+
+/// Get the name used to look up the service of a type listed in a module
+/// (the last path segment, without generics)
+pub fn service_key(ty: &Type) -> Option<String> {
+    if let Type::Path(type_path) = ty {
+        type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+    } else {
+        None
+    }
+}
+
+/// Read a `#[derive(Component)]` or `#[derive(Provider)]` struct
+fn service_from_struct(item: &ItemStruct) -> Option<Service> {
+    if !item.attrs.iter().any(is_service_derive) {
+        return None;
+    }
+
+    let interface = item
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("shaku"))
+        .filter_map(|attr| attr.parse_args_with(parse_interface).ok())
+        .find_map(|interface| interface)?;
+
+    let generic_params: Vec<&Ident> = item.generics.type_params().map(|p| &p.ident).collect();
+    let fields = match &item.fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+    let dependencies = fields
+        .into_iter()
+        .filter_map(|field| {
+            let provided = field_flags(&field.attrs, "provide");
+
+            if !provided && !field_flags(&field.attrs, "inject") {
+                return None;
+            }
+
+            let inner = inner_type(&field.ty)?;
+
+            if is_generic_param(inner, &generic_params) {
+                return None;
+            }
+
+            Some(Dependency {
+                interface: type_name(inner),
+                provided,
+            })
+        })
+        .collect();
+
+    // Providers can use their own type as the interface
+    let interface = match &interface {
+        Type::Path(type_path) if type_path.path.is_ident("Self") => item.ident.to_string(),
+        interface => type_name(interface),
+    };
+
+    Some(Service {
+        interface,
+        dependencies,
+    })
+}
+
+fn is_service_derive(attr: &Attribute) -> bool {
+    attr.path.is_ident("derive")
+        && attr_idents(attr)
+            .iter()
+            .any(|ident| ident == "Component" || ident == "Provider")
+}
+
+/// Check if a field has a `#[shaku(flag)]` attribute
+fn field_flags(attrs: &[Attribute], flag: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("shaku"))
+        .any(|attr| attr_idents(attr).iter().any(|ident| ident == flag))
+}
+
+/// The top level identifiers inside an attribute's parentheses
+fn attr_idents(attr: &Attribute) -> Vec<Ident> {
+    let group = match attr.tokens.clone().into_iter().next() {
+        Some(TokenTree::Group(group)) => group,
+        _ => return Vec::new(),
+    };
+
+    group
+        .stream()
+        .into_iter()
+        .filter_map(|token| match token {
+            TokenTree::Ident(ident) => Some(ident),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Find `interface = ?` inside `#[shaku(...)]`, skipping the other entries
+fn parse_interface(input: ParseStream<'_>) -> syn::Result<Option<Type>> {
+    let mut interface = None;
+
+    while !input.is_empty() {
+        let key: Ident = input.parse()?;
+
+        if key == "interface" && input.peek(syn::Token![=]) {
+            input.parse::<syn::Token![=]>()?;
+            interface = Some(input.parse()?);
+        }
+
+        // Skip the rest of the entry
+        while !input.is_empty() && !input.peek(syn::Token![,]) {
+            input.parse::<TokenTree>()?;
+        }
+
+        if !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+        }
+    }
+
+    Ok(interface)
+}
+
+/// Get `T` from `Arc<T>` or `Box<T>`
+fn inner_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(type_path) => type_path.path.segments.last()?,
+        _ => return None,
+    };
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => {
+            arguments.args.iter().find_map(|argument| match argument {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn is_generic_param(ty: &Type, params: &[&Ident]) -> bool {
+    if let Type::Path(type_path) = ty {
+        params
+            .iter()
+            .any(|param| type_path.qself.is_none() && type_path.path.is_ident(*param))
+    } else {
+        false
+    }
+}
+
+/// Read a manual `impl Component<M> for ?` or `impl Provider<M> for ?`. The
+/// dependencies are read from `HasComponent`/`HasProvider` bounds.
+fn service_from_impl(item: &ItemImpl) -> Option<(String, Service)> {
+    let (_, trait_path, _) = item.trait_.as_ref()?;
+    let trait_name = trait_path.segments.last()?.ident.to_string();
+
+    if trait_name != "Component" && trait_name != "Provider" {
+        return None;
+    }
+
+    let name = service_key(&item.self_ty)?;
+    let interface = item.items.iter().find_map(|impl_item| match impl_item {
+        ImplItem::Type(ty) if ty.ident == "Interface" => Some(type_name(&ty.ty)),
+        _ => None,
+    })?;
+
+    let param_bounds = item
+        .generics
+        .type_params()
+        .flat_map(|param| param.bounds.iter());
+    let where_bounds = item
+        .generics
+        .where_clause
+        .iter()
+        .flat_map(|where_clause| where_clause.predicates.iter())
+        .filter_map(|predicate| match predicate {
+            WherePredicate::Type(predicate) => Some(predicate.bounds.iter()),
+            _ => None,
+        })
+        .flatten();
+    let dependencies = param_bounds
+        .chain(where_bounds)
+        .filter_map(dependency_from_bound)
+        .collect();
+
+    Some((
+        name,
+        Service {
+            interface,
+            dependencies,
+        },
+    ))
+}
+
+fn dependency_from_bound(bound: &TypeParamBound) -> Option<Dependency> {
+    let segment = match bound {
+        TypeParamBound::Trait(bound) => bound.path.segments.last()?,
+        _ => return None,
+    };
+    let provided = match segment.ident.to_string().as_str() {
+        "HasComponent" => false,
+        "HasProvider" => true,
+        _ => return None,
+    };
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => {
+            arguments.args.iter().find_map(|argument| match argument {
+                GenericArgument::Type(ty) => Some(Dependency {
+                    interface: type_name(ty),
+                    provided,
+                }),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+impl Parse for ModuleDecl {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        input.call(Attribute::parse_outer)?;
+        input.parse::<syn::Visibility>()?;
+        let name: Ident = input.parse()?;
+        input.parse::<syn::Generics>()?;
+
+        let extends = if input.peek(kw::extends) {
+            input.parse::<kw::extends>()?;
+            Some(service_key(&input.parse()?).unwrap_or_default())
+        } else {
+            None
+        };
+
+        input.parse::<Option<syn::WhereClause>>()?;
+
+        if input.peek(syn::Token![:]) {
+            input.parse::<syn::Token![:]>()?;
+            input.parse::<syn::Path>()?;
+        }
+
+        let content;
+        syn::braced!(content in input);
+        let (components, providers) = parse_services(&content)?;
+        let mut submodules = Vec::new();
+
+        while !content.is_empty() {
+            content.parse::<syn::Token![,]>()?;
+
+            if content.is_empty() {
+                break;
+            }
+
+            content.parse::<syn::Token![use]>()?;
+            let submodule: Type = content.parse()?;
+            let services;
+            syn::braced!(services in content);
+            let (components, providers) = parse_services(&services)?;
+            services.parse::<Option<syn::Token![,]>>()?;
+
+            submodules.push(SubmoduleDecl {
+                name: type_name(&submodule),
+                components,
+                providers,
+            });
+        }
+
+        Ok(ModuleDecl {
+            name: name.to_string(),
+            extends,
+            components,
+            providers,
+            submodules,
+        })
+    }
+}
+
+/// Parse `components = [...], providers = [...]`
+fn parse_services(input: ParseStream<'_>) -> syn::Result<(Vec<String>, Vec<String>)> {
+    input.parse::<kw::components>()?;
+    input.parse::<syn::Token![=]>()?;
+    let components = parse_service_list(input)?;
+    input.parse::<syn::Token![,]>()?;
+    input.parse::<kw::providers>()?;
+    input.parse::<syn::Token![=]>()?;
+    let providers = parse_service_list(input)?;
+
+    Ok((components, providers))
+}
+
+fn parse_service_list(input: ParseStream<'_>) -> syn::Result<Vec<String>> {
+    let content;
+    syn::bracketed!(content in input);
+    let items = content.parse_terminated::<ServiceItem, syn::Token![,]>(ServiceItem::parse)?;
+
+    Ok(items.into_iter().map(|item| item.0).collect())
+}
+
+/// A service in a module's list, ex. `#[lazy] FooImpl`
+struct ServiceItem(String);
+
+impl Parse for ServiceItem {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        input.call(Attribute::parse_outer)?;
+        let ty: Type = input.parse()?;
+
+        Ok(ServiceItem(type_name(&ty)))
+    }
+}
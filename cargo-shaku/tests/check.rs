@@ -0,0 +1,244 @@
+//! Checking the module graph of scanned sources
+
+use cargo_shaku::{check, Report, Scan, Severity};
+
+const SERVICES: &str = r#"
+use shaku::{Component, Interface, Module, Provider, HasComponent};
+use std::sync::Arc;
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+
+#[derive(Component)]
+#[shaku(interface = dyn Repository)]
+struct RepositoryImpl {
+    #[shaku(inject)]
+    logger: Arc<dyn Logger>,
+}
+
+#[derive(Provider)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn Repository>,
+    #[shaku(provide)]
+    connection: Box<crate::db::Connection>,
+}
+
+struct Connection;
+
+impl<M: Module + HasComponent<dyn Logger>> Provider<M> for Connection {
+    type Interface = Connection;
+
+    fn provide(module: &M) -> Result<Box<Connection>, Box<dyn Error>> {
+        Ok(Box::new(Connection))
+    }
+}
+"#;
+
+fn check_source(source: &str) -> Report {
+    let mut scan = Scan::new();
+    scan.scan_source("services.rs", SERVICES);
+    scan.scan_source("modules.rs", source);
+
+    check(&scan)
+}
+
+fn messages(report: &Report, severity: Severity) -> Vec<&str> {
+    report
+        .issues()
+        .iter()
+        .filter(|issue| issue.severity == severity)
+        .map(|issue| issue.message.as_str())
+        .collect()
+}
+
+/// A fully wired module has no issues, and the graph lists its services
+#[test]
+fn wired_module() {
+    let report = check_source(
+        r#"
+        module! {
+            MyModule {
+                components = [LoggerImpl, #[lazy] RepositoryImpl],
+                providers = [ServiceImpl, Connection]
+            }
+        }
+        "#,
+    );
+
+    assert!(report.issues().is_empty(), "{}", report);
+
+    let module = &report.modules()[0];
+    assert_eq!(module.name, "MyModule");
+    assert_eq!(module.services.len(), 4);
+    assert_eq!(module.services[1].interface.as_deref(), Some("Repository"));
+    assert_eq!(module.services[1].dependencies[0].interface, "Logger");
+    assert_eq!(module.services[3].dependencies[0].interface, "Logger");
+}
+
+/// Dependencies which the module does not provide are errors
+#[test]
+fn unresolved_dependency() {
+    let report = check_source(
+        r#"
+        module! {
+            MyModule {
+                components = [RepositoryImpl],
+                providers = [ServiceImpl]
+            }
+        }
+        "#,
+    );
+
+    assert!(report.has_errors());
+    assert_eq!(
+        messages(&report, Severity::Error),
+        vec![
+            "`RepositoryImpl` in `MyModule` depends on component `Logger`, \
+             which the module does not provide",
+            "`ServiceImpl` in `MyModule` depends on provided `Connection`, \
+             which the module does not provide",
+        ]
+    );
+}
+
+/// Dependencies can be resolved by submodules
+#[test]
+fn submodule_dependency() {
+    let report = check_source(
+        r#"
+        module! {
+            MyModule {
+                components = [RepositoryImpl],
+                providers = [ServiceImpl],
+
+                use LoggingModule {
+                    components = [dyn Logger],
+                    providers = [Connection]
+                }
+            }
+        }
+        "#,
+    );
+
+    assert!(!report.has_errors(), "{}", report);
+}
+
+/// Dependency cycles are errors
+#[test]
+fn dependency_cycle() {
+    let mut scan = Scan::new();
+    scan.scan_source(
+        "cycle.rs",
+        r#"
+        #[derive(Component)]
+        #[shaku(interface = A)]
+        struct AImpl {
+            #[shaku(inject)]
+            b: Arc<dyn B>,
+        }
+
+        #[derive(Component)]
+        #[shaku(interface = B)]
+        struct BImpl {
+            #[shaku(inject)]
+            a: Arc<dyn A>,
+        }
+
+        module! {
+            CycleModule {
+                components = [AImpl, BImpl],
+                providers = []
+            }
+        }
+        "#,
+    );
+    let report = check(&scan);
+
+    assert_eq!(
+        messages(&report, Severity::Error),
+        vec!["dependency cycle in `CycleModule`: AImpl -> BImpl -> AImpl"]
+    );
+}
+
+/// Components which nothing injects are warnings, which don't fail the check
+#[test]
+fn unused_component() {
+    let report = check_source(
+        r#"
+        module! {
+            MyModule {
+                components = [LoggerImpl, RepositoryImpl],
+                providers = []
+            }
+        }
+        "#,
+    );
+
+    assert!(!report.has_errors(), "{}", report);
+    assert_eq!(
+        messages(&report, Severity::Warning),
+        vec![
+            "component `RepositoryImpl` (`Repository`) is not injected by any service \
+             or used by another module"
+        ]
+    );
+}
+
+/// Services of an extended module are inherited, and can be overridden
+#[test]
+fn extended_module() {
+    let report = check_source(
+        r#"
+        #[derive(Component)]
+        #[shaku(interface = Logger)]
+        struct TestLogger;
+
+        module! {
+            #[shaku(extendable)]
+            BaseModule {
+                components = [LoggerImpl],
+                providers = []
+            }
+        }
+
+        module! {
+            TestModule extends BaseModule {
+                components = [TestLogger, RepositoryImpl],
+                providers = []
+            }
+        }
+        "#,
+    );
+
+    let module = &report.modules()[1];
+    let services: Vec<&str> = module
+        .services
+        .iter()
+        .map(|service| service.ty.as_str())
+        .collect();
+
+    assert!(!report.has_errors(), "{}", report);
+    assert_eq!(services, vec!["TestLogger", "RepositoryImpl"]);
+}
+
+/// Modules using services which weren't found are not checked for
+/// unresolved dependencies
+#[test]
+fn unknown_service() {
+    let report = check_source(
+        r#"
+        module! {
+            MyModule {
+                components = [RepositoryImpl, other_crate::ExternalLogger],
+                providers = []
+            }
+        }
+        "#,
+    );
+
+    assert!(!report.has_errors(), "{}", report);
+    assert_eq!(report.modules()[0].services[1].interface, None);
+}
@@ -0,0 +1,52 @@
+//! The application only uses the auth services through the interfaces crate. The implementation
+//! is chosen when the root module is built.
+
+use multi_crate_interfaces::{AuthModule, Session};
+use shaku::{module, HasProvider, Provider};
+use std::collections::HashMap;
+
+trait LoginHandler {
+    fn handle(&mut self, user: &str, password: &str) -> String;
+}
+
+#[derive(Provider)]
+#[shaku(interface = LoginHandler)]
+struct LoginHandlerImpl {
+    #[shaku(provide)]
+    session: Box<dyn Session>,
+}
+
+impl LoginHandler for LoginHandlerImpl {
+    fn handle(&mut self, user: &str, password: &str) -> String {
+        if self.session.login(user, password) {
+            format!("Welcome, {}!", user)
+        } else {
+            format!("Invalid password for {}", user)
+        }
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [LoginHandlerImpl],
+
+        use dyn AuthModule {
+            components = [],
+            providers = [dyn Session]
+        }
+    }
+}
+
+fn main() {
+    let mut passwords = HashMap::new();
+    passwords.insert("admin".to_string(), "hunter2".to_string());
+
+    let auth_module = multi_crate_auth::auth_module(passwords);
+    let root_module = RootModule::builder(auth_module).build();
+
+    for password in &["hunter2", "password"] {
+        let mut handler: Box<dyn LoginHandler> = root_module.provide().unwrap();
+        println!("{}", handler.handle("admin", password));
+    }
+}
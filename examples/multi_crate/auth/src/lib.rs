@@ -0,0 +1,80 @@
+//! An implementation of the auth services
+
+use multi_crate_interfaces::{AuthManager, AuthModule, CredentialStore, Session};
+use shaku::{module, Component, HasComponent, Module, Provider};
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+
+#[derive(Component)]
+#[shaku(interface = CredentialStore)]
+struct InMemoryCredentialStore {
+    passwords: HashMap<String, String>,
+}
+
+impl CredentialStore for InMemoryCredentialStore {
+    fn password(&self, user: &str) -> Option<String> {
+        self.passwords.get(user).cloned()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = AuthManager)]
+struct AuthManagerImpl {
+    #[shaku(inject)]
+    credentials: Arc<dyn CredentialStore>,
+}
+
+impl AuthManager for AuthManagerImpl {
+    fn login(&self, user: &str, password: &str) -> bool {
+        self.credentials.password(user).as_deref() == Some(password)
+    }
+}
+
+struct SessionImpl {
+    auth_manager: Arc<dyn AuthManager>,
+    user: Option<String>,
+}
+
+impl<M: Module + HasComponent<dyn AuthManager>> Provider<M> for SessionImpl {
+    type Interface = dyn Session;
+
+    fn provide(module: &M) -> Result<Box<dyn Session>, Box<dyn Error>> {
+        Ok(Box::new(SessionImpl {
+            auth_manager: module.resolve(),
+            user: None,
+        }))
+    }
+}
+
+impl Session for SessionImpl {
+    fn login(&mut self, user: &str, password: &str) -> bool {
+        if self.auth_manager.login(user, password) {
+            self.user = Some(user.to_string());
+        }
+
+        self.user.is_some()
+    }
+
+    fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+}
+
+module! {
+    AuthModuleImpl: AuthModule {
+        components = [InMemoryCredentialStore, AuthManagerImpl],
+        providers = [SessionImpl]
+    }
+}
+
+/// Build the auth module with the given users and passwords
+pub fn auth_module(passwords: HashMap<String, String>) -> Arc<dyn AuthModule> {
+    Arc::new(
+        AuthModuleImpl::builder()
+            .with_component_parameters::<InMemoryCredentialStore>(
+                InMemoryCredentialStoreParameters { passwords },
+            )
+            .build(),
+    )
+}
@@ -0,0 +1,28 @@
+//! The service traits and module interfaces. Crates which use the services only need to depend on
+//! this crate, not on the implementations.
+
+use shaku::{module_interface, Interface};
+
+/// Checks user credentials
+pub trait AuthManager: Interface {
+    fn login(&self, user: &str, password: &str) -> bool;
+}
+
+/// Stores the users' passwords
+pub trait CredentialStore: Interface {
+    fn password(&self, user: &str) -> Option<String>;
+}
+
+/// A user's session, which is created per request
+pub trait Session {
+    fn login(&mut self, user: &str, password: &str) -> bool;
+    fn user(&self) -> Option<&str>;
+}
+
+module_interface! {
+    /// The services exposed by the auth implementation
+    pub trait AuthModule {
+        components = [dyn AuthManager, dyn CredentialStore],
+        providers = [dyn Session]
+    }
+}
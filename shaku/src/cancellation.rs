@@ -0,0 +1,112 @@
+//! Cooperative cancellation of async module builds
+
+use std::fmt::{self, Debug};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::Waker;
+
+/// Cancels an async module build (see [`BuildAsync::with_cancellation`]). The token is cheap to
+/// clone, and all clones cancel the same builds. Cancelling drops the pending fetch (ex. a network
+/// request to a secrets manager), and the build returns [`ModuleBuildError::Cancelled`].
+///
+/// # Example
+/// ```
+/// use shaku::{module, CancellationToken, ModuleBuildError};
+/// # use std::future::Future;
+/// # use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+///
+/// module! {
+///     MyModule {
+///         components = [],
+///         providers = []
+///     }
+/// }
+/// #
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     fn noop_raw_waker() -> RawWaker {
+/// #         fn clone(_: *const ()) -> RawWaker { noop_raw_waker() }
+/// #         fn noop(_: *const ()) {}
+/// #         static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+/// #         RawWaker::new(std::ptr::null(), &VTABLE)
+/// #     }
+/// #     let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+/// #     let mut context = Context::from_waker(&waker);
+/// #     let mut future = Box::pin(future);
+/// #     loop {
+/// #         if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+///
+/// # fn main() {
+/// let token = CancellationToken::new();
+/// let build = MyModule::builder()
+///     .build_async()
+///     .with_cancellation(token.clone());
+///
+/// // ex. on shutdown
+/// token.cancel();
+///
+/// match block_on(build) {
+///     Err(ModuleBuildError::Cancelled { completed }) => assert!(completed.is_empty()),
+///     _ => panic!("The build was not cancelled"),
+/// }
+/// # }
+/// ```
+///
+/// [`BuildAsync::with_cancellation`]: struct.BuildAsync.html#method.with_cancellation
+/// [`ModuleBuildError::Cancelled`]: enum.ModuleBuildError.html#variant.Cancelled
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<CancellationState>,
+}
+
+#[derive(Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    /// The tasks to wake once cancelled
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl CancellationToken {
+    /// Create a token which is not cancelled
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    /// Cancel the builds using this token. Their tasks are woken, so they return right away.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+
+        let wakers: Vec<Waker> = self.inner.wakers.lock().unwrap().drain(..).collect();
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Check if the token was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Wake the task once the token is cancelled (right away if it already is)
+    pub(crate) fn register(&self, waker: &Waker) {
+        let mut wakers = self.inner.wakers.lock().unwrap();
+
+        if self.is_cancelled() {
+            drop(wakers);
+            waker.wake_by_ref();
+        } else if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+}
+
+impl Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
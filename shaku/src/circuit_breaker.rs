@@ -0,0 +1,227 @@
+//! Failing fast when a provider keeps failing
+
+use crate::{Component, HasComponent, Module, ModuleBuildContext, Provider};
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Configures when a [`CircuitBreaker`] trips, and for how long.
+///
+/// [`CircuitBreaker`]: struct.CircuitBreaker.html
+pub trait CircuitBreakerPolicy: 'static {
+    /// The number of consecutive failures after which the breaker opens
+    const FAILURE_THRESHOLD: u32;
+
+    /// How long the breaker stays open before letting calls through again
+    const COOLDOWN: Duration;
+}
+
+/// Tracks the failures of a provider. After [`FAILURE_THRESHOLD`] consecutive failures the breaker
+/// opens, and calls fail immediately with [`CircuitOpenError`] for the [`COOLDOWN`]. Afterwards,
+/// calls are let through again. A success closes the breaker, while a failure opens it for another
+/// cooldown.
+///
+/// The breaker is a component, so its state is stored in the module. Add it to the module's
+/// components alongside the [`CircuitBreakerProvider`] which uses it.
+///
+/// [`FAILURE_THRESHOLD`]: trait.CircuitBreakerPolicy.html#associatedconstant.FAILURE_THRESHOLD
+/// [`COOLDOWN`]: trait.CircuitBreakerPolicy.html#associatedconstant.COOLDOWN
+/// [`CircuitOpenError`]: struct.CircuitOpenError.html
+/// [`CircuitBreakerProvider`]: struct.CircuitBreakerProvider.html
+pub struct CircuitBreaker<B> {
+    state: Mutex<BreakerState>,
+    _policy: PhantomData<fn() -> B>,
+}
+
+#[derive(Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl<B: CircuitBreakerPolicy> CircuitBreaker<B> {
+    /// Create a closed breaker
+    pub fn new() -> Self {
+        CircuitBreaker {
+            state: Mutex::new(BreakerState::default()),
+            _policy: PhantomData,
+        }
+    }
+
+    /// Check if the breaker is open, meaning calls currently fail fast
+    pub fn is_open(&self) -> bool {
+        self.remaining_cooldown().is_some()
+    }
+
+    /// Call `provide` unless the breaker is open, and record the result
+    pub fn call<T>(
+        &self,
+        provide: impl FnOnce() -> Result<T, Box<dyn Error>>,
+    ) -> Result<T, Box<dyn Error>> {
+        if let Some(retry_after) = self.remaining_cooldown() {
+            return Err(Box::new(CircuitOpenError { retry_after }));
+        }
+
+        // The lock is not held while providing, since the provider may be slow
+        let result = provide();
+        let mut state = self.state.lock().unwrap();
+
+        match result {
+            Ok(_) => {
+                state.consecutive_failures = 0;
+                state.opened_at = None;
+            }
+            Err(_) => {
+                state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+
+                if state.consecutive_failures >= B::FAILURE_THRESHOLD {
+                    state.opened_at = Some(Instant::now());
+                }
+            }
+        }
+
+        result
+    }
+
+    fn remaining_cooldown(&self) -> Option<Duration> {
+        let state = self.state.lock().unwrap();
+        let elapsed = state.opened_at?.elapsed();
+
+        if elapsed < B::COOLDOWN {
+            Some(B::COOLDOWN - elapsed)
+        } else {
+            None
+        }
+    }
+}
+
+impl<B: CircuitBreakerPolicy> Default for CircuitBreaker<B> {
+    fn default() -> Self {
+        CircuitBreaker::new()
+    }
+}
+
+impl<B> Debug for CircuitBreaker<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+
+        f.debug_struct("CircuitBreaker")
+            .field("consecutive_failures", &state.consecutive_failures)
+            .field("opened_at", &state.opened_at)
+            .finish()
+    }
+}
+
+impl<M: Module, B: CircuitBreakerPolicy> Component<M> for CircuitBreaker<B> {
+    type Interface = CircuitBreaker<B>;
+    type Parameters = ();
+
+    fn build(_: &mut ModuleBuildContext<M>, _: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(CircuitBreaker::new())
+    }
+}
+
+/// Returned instead of calling the provider while its [`CircuitBreaker`] is open
+///
+/// [`CircuitBreaker`]: struct.CircuitBreaker.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitOpenError {
+    retry_after: Duration,
+}
+
+impl CircuitOpenError {
+    /// The time until the breaker lets calls through again
+    pub fn retry_after(&self) -> Duration {
+        self.retry_after
+    }
+}
+
+impl fmt::Display for CircuitOpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The circuit breaker is open, retry after {:?}",
+            self.retry_after
+        )
+    }
+}
+
+impl Error for CircuitOpenError {}
+
+/// Wraps a provider so it fails fast after repeated failures, using the [`CircuitBreaker`]
+/// component with policy `B`. The module must also contain the `CircuitBreaker<B>` component.
+/// Providers which share a policy type share the breaker.
+///
+/// # Example
+/// ```
+/// use shaku::{
+///     module, CircuitBreaker, CircuitBreakerPolicy, CircuitBreakerProvider, HasProvider, Module,
+///     Provider,
+/// };
+/// use std::error::Error;
+/// use std::time::Duration;
+///
+/// struct Connection;
+///
+/// impl<M: Module> Provider<M> for Connection {
+///     type Interface = Connection;
+///
+///     fn provide(_: &M) -> Result<Box<Connection>, Box<dyn Error>> {
+///         Err("connection refused".into())
+///     }
+/// }
+///
+/// struct ConnectionBreaker;
+///
+/// impl CircuitBreakerPolicy for ConnectionBreaker {
+///     const FAILURE_THRESHOLD: u32 = 5;
+///     const COOLDOWN: Duration = Duration::from_secs(30);
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [CircuitBreaker<ConnectionBreaker>],
+///         providers = [CircuitBreakerProvider<Connection, ConnectionBreaker>]
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+///
+/// for _ in 0..5 {
+///     assert!(HasProvider::<Connection>::provide(&module).is_err());
+/// }
+///
+/// // The connection is not attempted anymore
+/// let error = HasProvider::<Connection>::provide(&module).err().unwrap();
+/// assert!(error.to_string().starts_with("The circuit breaker is open"));
+/// # }
+/// ```
+///
+/// [`CircuitBreaker`]: struct.CircuitBreaker.html
+pub struct CircuitBreakerProvider<P, B> {
+    _provider: PhantomData<P>,
+    _policy: PhantomData<B>,
+}
+
+impl<M, P, B> Provider<M> for CircuitBreakerProvider<P, B>
+where
+    M: Module + HasComponent<CircuitBreaker<B>>,
+    P: Provider<M>,
+    B: CircuitBreakerPolicy,
+{
+    type Interface = P::Interface;
+
+    fn provide(module: &M) -> Result<Box<Self::Interface>, Box<dyn Error>> {
+        let breaker: &CircuitBreaker<B> = module.resolve_ref();
+        breaker.call(|| P::provide(module))
+    }
+}
+
+impl<P, B> Debug for CircuitBreakerProvider<P, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreakerProvider").finish()
+    }
+}
@@ -3,15 +3,17 @@
 use crate::module::ModuleInterface;
 use crate::Module;
 use crate::ModuleBuildContext;
-use std::any::Any;
-use std::sync::Arc;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::{type_name, Any};
 
 /// Components provide a service by implementing an interface. They may use
 /// other components as dependencies.
 ///
 /// This trait is normally derived, but if the `derive` feature is turned off
 /// then it will need to be implemented manually.
-pub trait Component<M: Module>: Interface {
+pub trait Component<M: Module>: LocalInterface {
     /// The trait/interface which this component implements
     type Interface: Interface + ?Sized;
 
@@ -23,6 +25,18 @@ pub trait Component<M: Module>: Interface {
     #[cfg(not(feature = "thread_safe"))]
     type Parameters: Default;
 
+    /// If the parameters are empty (ex. `()`), so the component can be built with the default
+    /// parameters without looking up the parameters which were set. The `Component` derive sets
+    /// this for components without parameters. The default is false.
+    const EMPTY_PARAMETERS: bool = false;
+
+    /// If the component is tied to the module it was built in (ex. it holds a module handle, or
+    /// registers itself with other components after it is built), so `RebuildModule::rebuild`
+    /// always builds it again instead of reusing it. The `Component` derive sets this for
+    /// components with `#[shaku(inject_module)]`, `#[shaku(reconfigurable)]`, subscriptions, a
+    /// health check, or lifecycle hooks. The default is false.
+    const ALWAYS_REBUILD: bool = false;
+
     /// Use the build context and parameters to create the component. Other
     /// components can be resolved by adding a [`HasComponent`] bound to the
     /// `M` generic, then calling [`M::build_component`].
@@ -31,25 +45,79 @@ pub trait Component<M: Module>: Interface {
     /// [`M::build_component`]: trait.HasComponent.html#tymethod.build_component
     fn build(context: &mut ModuleBuildContext<M>, params: Self::Parameters)
         -> Box<Self::Interface>;
+
+    /// Build the component into the shared pointer which is stored in the module. The default
+    /// implementation calls [`build`]. Components which need a handle to their concrete type
+    /// after they are built (ex. [`Reconfigurable`] components) can build the `Arc` themselves.
+    ///
+    /// [`build`]: #tymethod.build
+    /// [`Reconfigurable`]: trait.Reconfigurable.html
+    fn build_shared(
+        context: &mut ModuleBuildContext<M>,
+        params: Self::Parameters,
+    ) -> Arc<Self::Interface> {
+        Arc::from(Self::build(context, params))
+    }
+
+    /// Called after the component has been built and stored in the build
+    /// context. This can be used to wire the shared component into other
+    /// components (ex. subscribing it to an event bus). The default
+    /// implementation does nothing.
+    ///
+    /// This is not called for components which were overridden.
+    #[allow(unused_variables)]
+    fn post_build(context: &mut ModuleBuildContext<M>, component: &Arc<Self::Interface>) {}
+
+    /// The type names of the interfaces which are built before this component
+    /// (its injected components and `#[shaku(build_after = ?)]` components).
+    /// This is used to report the module's build order, and to find services
+    /// which are unreachable from the module interface. The default
+    /// implementation returns no dependencies.
+    fn dependencies() -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// The keys of the fields which are read from a parameter source (see
+    /// `#[shaku(from_source = "key")]`). The default implementation returns no keys.
+    fn parameter_sources() -> Vec<&'static str> {
+        Vec::new()
+    }
 }
 
+trait_alias!(
+    /// Interfaces must be `'static` in order to be stored in a module (hence the `Any`
+    /// requirement), and must be [`ThreadSafe`] so the module can be shared between threads.
+    ///
+    /// Interface traits should inherit this trait (ex. `trait Logger: Interface {}`) so that the
+    /// trait objects (ex. `dyn Logger`) also have these bounds.
+    ///
+    /// [`ThreadSafe`]: trait.ThreadSafe.html
+    pub Interface = Any + ThreadSafe
+);
+
 #[cfg(not(feature = "thread_safe"))]
 trait_alias!(
-    /// Interfaces must be `'static` in order to be stored in a module
-    /// (hence the `Any` requirement).
+    /// Requires `Send + Sync` if the `thread_safe` feature is turned on.
     ///
-    /// The `thread_safe` feature is turned off, so interfaces do not need to
-    /// implement `Send` or `Sync`.
-    pub Interface = Any
+    /// The `thread_safe` feature is turned off, so this trait has no requirements.
+    pub ThreadSafe =
 );
 #[cfg(feature = "thread_safe")]
 trait_alias!(
-    /// Interfaces must be `'static` in order to be stored in a module
-    /// (hence the `Any` requirement).
+    /// Requires `Send + Sync` if the `thread_safe` feature is turned on.
+    ///
+    /// The `thread_safe` feature is turned on, so this trait requires `Send` and `Sync`.
+    pub ThreadSafe = Send + Sync
+);
+
+trait_alias!(
+    /// Like [`Interface`], but never requires `Send` or `Sync`, even if the `thread_safe` feature
+    /// is turned on. Components which implement a local interface are stored in a module via
+    /// [`ThreadBound`].
     ///
-    /// The `thread_safe` feature is turned on, which requires interfaces to
-    /// also implement `Send` and `Sync`.
-    pub Interface = Any + Send + Sync
+    /// [`Interface`]: trait.Interface.html
+    /// [`ThreadBound`]: struct.ThreadBound.html
+    pub LocalInterface = Any
 );
 
 /// The type signature of [`Component::build`] without the parameters. This is
@@ -135,4 +203,135 @@ pub trait HasComponent<I: Interface + ?Sized>: ModuleInterface {
     /// # }
     /// ```
     fn resolve_ref(&self) -> &I;
+
+    /// Get a mutable reference to the component, if the module is its only owner (ie. no other
+    /// component or caller holds an `Arc` of it). Lazy components are not supported, so this
+    /// always returns `None` for them. The default implementation returns `None`.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, Interface, HasComponent};
+    /// # use std::sync::Arc;
+    /// #
+    /// # trait Foo: Interface { fn set(&mut self, value: usize); }
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Foo)]
+    /// # struct FooImpl { #[shaku(default)] value: usize }
+    /// # impl Foo for FooImpl { fn set(&mut self, value: usize) { self.value = value; } }
+    /// #
+    /// # module! {
+    /// #     TestModule {
+    /// #         components = [FooImpl],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # let mut module = TestModule::builder().build();
+    /// #
+    /// let foo: &mut dyn Foo = module.resolve_mut().unwrap();
+    /// foo.set(5);
+    /// # }
+    /// ```
+    fn resolve_mut(&mut self) -> Option<&mut I> {
+        None
+    }
+
+    /// The type name of the interface (ex. `dyn my_crate::Foo`), for logging and error messages in
+    /// code which is generic over the module and interface. Call it via
+    /// `HasComponent::<I>::interface_name(&module)` if the module has several components.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, Interface, HasComponent};
+    /// #
+    /// # trait Foo: Interface {}
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Foo)]
+    /// # struct FooImpl;
+    /// # impl Foo for FooImpl {}
+    /// #
+    /// # module! {
+    /// #     TestModule {
+    /// #         components = [FooImpl],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    /// #
+    /// fn resolve_logged<I: Interface + ?Sized, M: HasComponent<I> + ?Sized>(module: &M) -> &I {
+    ///     println!("Resolving {}", module.interface_name());
+    ///     module.resolve_ref()
+    /// }
+    ///
+    /// # fn main() {
+    /// # let module = TestModule::builder().build();
+    /// #
+    /// let foo: &dyn Foo = resolve_logged(&module);
+    /// assert!(HasComponent::<dyn Foo>::interface_name(&module).ends_with("Foo"));
+    /// # }
+    /// ```
+    fn interface_name(&self) -> &'static str {
+        type_name::<I>()
+    }
+}
+
+/// Forwards to the shared module, so generic code over modules also accepts `Arc<M>`
+impl<I: Interface + ?Sized, M: HasComponent<I> + ?Sized> HasComponent<I> for Arc<M>
+where
+    Arc<M>: ModuleInterface,
+{
+    fn build_component(_context: &mut ModuleBuildContext<Self>) -> Arc<I>
+    where
+        Self: Module + Sized,
+    {
+        unreachable!("Arc<M> is never a module")
+    }
+
+    fn resolve(&self) -> Arc<I> {
+        M::resolve(&**self)
+    }
+
+    fn resolve_ref(&self) -> &I {
+        M::resolve_ref(&**self)
+    }
+
+    fn resolve_mut(&mut self) -> Option<&mut I> {
+        Arc::get_mut(self).and_then(M::resolve_mut)
+    }
+}
+
+/// Forwards to the boxed module, so generic code over modules also accepts `Box<M>`
+impl<I: Interface + ?Sized, M: HasComponent<I> + ?Sized> HasComponent<I> for Box<M>
+where
+    Box<M>: ModuleInterface,
+{
+    fn build_component(_context: &mut ModuleBuildContext<Self>) -> Arc<I>
+    where
+        Self: Module + Sized,
+    {
+        unreachable!("Box<M> is never a module")
+    }
+
+    fn resolve(&self) -> Arc<I> {
+        M::resolve(&**self)
+    }
+
+    fn resolve_ref(&self) -> &I {
+        M::resolve_ref(&**self)
+    }
+
+    fn resolve_mut(&mut self) -> Option<&mut I> {
+        M::resolve_mut(&mut **self)
+    }
+}
+
+/// Creates an interface implementation from a closure. This is implemented for `dyn Trait` by the
+/// `closure_interface` attribute, and used by [`ModuleBuilder::with_component_closure`].
+///
+/// [`ModuleBuilder::with_component_closure`]: struct.ModuleBuilder.html#method.with_component_closure
+pub trait FromClosure<F>: Interface {
+    /// Wrap the closure in an implementation of the interface
+    fn from_closure(closure: F) -> Box<Self>;
 }
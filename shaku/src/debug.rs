@@ -0,0 +1,301 @@
+//! Tools for inspecting the wiring of built modules.
+//!
+//! Modules created by the [`module`] macro can record a [`ModuleGraph`] while they are built: the
+//! implementation of each service, whether it was overridden, and the component parameters. The
+//! graph is only recorded if the module is built after calling [`ModuleBuilder::record_graph`].
+//! Two graphs can be compared via [`diff`], ex. to check that a blue/green deployment only changes
+//! the expected configuration, or that a refactor did not change the module's wiring.
+//!
+//! # Example
+//! ```
+//! use shaku::{debug, module, Component, Interface};
+//!
+//! trait Database: Interface {}
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = Database, parameters_derive(Debug))]
+//! struct DatabaseImpl {
+//!     url: String,
+//! }
+//! impl Database for DatabaseImpl {}
+//!
+//! module! {
+//!     AppModule {
+//!         components = [DatabaseImpl],
+//!         providers = []
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let build = |url: &str| {
+//!     AppModule::builder()
+//!         .record_graph()
+//!         .with_component_parameters::<DatabaseImpl>(DatabaseImplParameters {
+//!             url: url.to_string(),
+//!         })
+//!         .build()
+//! };
+//! let blue = build("postgres://blue");
+//! let green = build("postgres://green");
+//!
+//! let diff = debug::diff(&blue, &green);
+//! assert!(diff.added.is_empty() && diff.removed.is_empty());
+//! assert_eq!(diff.changed.len(), 1);
+//! assert!(diff.changed[0].parameters_changed());
+//! assert!(debug::diff(&blue, &build("postgres://blue")).is_empty());
+//! # }
+//! ```
+//!
+//! [`module`]: ../macro.module.html
+//! [`ModuleGraph`]: struct.ModuleGraph.html
+//! [`ModuleBuilder::record_graph`]: ../struct.ModuleBuilder.html#method.record_graph
+//! [`diff`]: fn.diff.html
+
+use crate::ServiceKind;
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+
+/// The wiring and configuration of a built module. Modules created by the [`module`] macro
+/// expose it via [`GraphSource`].
+///
+/// [`module`]: ../macro.module.html
+/// [`GraphSource`]: trait.GraphSource.html
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleGraph {
+    services: Vec<GraphService>,
+}
+
+impl ModuleGraph {
+    /// Create a graph without any services
+    pub fn new() -> Self {
+        ModuleGraph::default()
+    }
+
+    /// Add a service to the graph
+    pub fn push(&mut self, service: GraphService) {
+        self.services.push(service);
+    }
+
+    /// The services of the module, in registration order
+    pub fn services(&self) -> &[GraphService] {
+        &self.services
+    }
+
+    /// Find the service of the kind with the interface
+    pub fn service(&self, kind: ServiceKind, interface: &str) -> Option<&GraphService> {
+        self.services
+            .iter()
+            .find(|service| service.kind == kind && service.interface == interface)
+    }
+}
+
+/// A service of a [`ModuleGraph`]
+///
+/// [`ModuleGraph`]: struct.ModuleGraph.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphService {
+    /// The type name of the service's interface
+    pub interface: &'static str,
+    /// The kind of service
+    pub kind: ServiceKind,
+    /// The type name of the implementation (the component or provider), or of the submodule for
+    /// services from submodules
+    pub implementation: &'static str,
+    /// If the service comes from a submodule
+    pub from_submodule: bool,
+    /// If the service was overridden while building the module
+    pub overridden: bool,
+    /// The `Debug` output of the component's parameters, if they were set and implement `Debug`
+    pub parameters: Option<String>,
+}
+
+impl fmt::Display for GraphService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} `{}` ", self.kind, self.interface)?;
+
+        if self.from_submodule {
+            write!(f, "from the submodule `{}`", self.implementation)?;
+        } else {
+            write!(f, "via `{}`", self.implementation)?;
+        }
+        if self.overridden {
+            write!(f, " (overridden)")?;
+        }
+        if let Some(parameters) = &self.parameters {
+            write!(f, " with {}", parameters)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A service which exists in both graphs of a [`GraphDiff`], but is implemented or configured
+/// differently
+///
+/// [`GraphDiff`]: struct.GraphDiff.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceChange {
+    /// The service in the first graph
+    pub before: GraphService,
+    /// The service in the second graph
+    pub after: GraphService,
+}
+
+impl ServiceChange {
+    /// If the service has a different implementation, or was overridden in only one graph
+    pub fn implementation_changed(&self) -> bool {
+        self.before.implementation != self.after.implementation
+            || self.before.from_submodule != self.after.from_submodule
+            || self.before.overridden != self.after.overridden
+    }
+
+    /// If the component parameters differ. Parameters are compared by their `Debug` output, so
+    /// parameters which don't implement `Debug` never differ.
+    pub fn parameters_changed(&self) -> bool {
+        self.before.parameters != self.after.parameters
+    }
+}
+
+/// The differences between two module graphs. See [`diff`].
+///
+/// [`diff`]: fn.diff.html
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GraphDiff {
+    /// Services which are only in the second graph
+    pub added: Vec<GraphService>,
+    /// Services which are only in the first graph
+    pub removed: Vec<GraphService>,
+    /// Services which are in both graphs, but differ
+    pub changed: Vec<ServiceChange>,
+}
+
+impl GraphDiff {
+    /// Returns true if the graphs have the same wiring and configuration
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for GraphDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for service in &self.removed {
+            writeln!(f, "- {}", service)?;
+        }
+        for service in &self.added {
+            writeln!(f, "+ {}", service)?;
+        }
+        for change in &self.changed {
+            writeln!(f, "~ {}", change.before)?;
+            writeln!(f, "  {}", change.after)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Provides the [`ModuleGraph`] to compare via [`diff`]. Implemented by modules created by the
+/// [`module`] macro, and by the graphs themselves (ex. graphs which were stored by a previous
+/// deployment).
+///
+/// # Panics
+/// Modules panic if their graph was not recorded (see [`ModuleBuilder::record_graph`]).
+///
+/// [`ModuleGraph`]: struct.ModuleGraph.html
+/// [`ModuleBuilder::record_graph`]: ../struct.ModuleBuilder.html#method.record_graph
+/// [`diff`]: fn.diff.html
+/// [`module`]: ../macro.module.html
+pub trait GraphSource {
+    /// The graph of the module
+    fn module_graph(&self) -> &ModuleGraph;
+}
+
+impl GraphSource for ModuleGraph {
+    fn module_graph(&self) -> &ModuleGraph {
+        self
+    }
+}
+
+/// Compare the wiring and configuration of two modules. Services are matched by their kind and
+/// interface.
+///
+/// # Panics
+/// Panics if the graph of a module was not recorded (see [`GraphSource`]).
+///
+/// [`GraphSource`]: trait.GraphSource.html
+pub fn diff<A: GraphSource + ?Sized, B: GraphSource + ?Sized>(a: &A, b: &B) -> GraphDiff {
+    let (a, b) = (a.module_graph(), b.module_graph());
+    let mut diff = GraphDiff::default();
+
+    for before in a.services() {
+        match b.service(before.kind, before.interface) {
+            Some(after) if after != before => diff.changed.push(ServiceChange {
+                before: before.clone(),
+                after: after.clone(),
+            }),
+            Some(_) => {}
+            None => diff.removed.push(before.clone()),
+        }
+    }
+
+    for after in b.services() {
+        if a.service(after.kind, after.interface).is_none() {
+            diff.added.push(after.clone());
+        }
+    }
+
+    diff
+}
+
+/// The recorded graph of a module. Used by the `module` macro to implement [`GraphSource`].
+///
+/// [`GraphSource`]: trait.GraphSource.html
+#[doc(hidden)]
+pub fn recorded_graph<'a>(graph: &'a Option<Arc<ModuleGraph>>, module: &str) -> &'a ModuleGraph {
+    match graph {
+        Some(graph) => graph,
+        None => panic!(
+            "The graph of {} was not recorded. Build the module via `builder().record_graph()`.",
+            module
+        ),
+    }
+}
+
+/// The parameters of a component, if they were set while building the module. The parameters are
+/// only recorded in the graph if they implement `Debug`, which is checked via the
+/// [`DebugParameters`] and [`SkipDebugParameters`] traits (like the module fingerprint).
+///
+/// [`DebugParameters`]: trait.DebugParameters.html
+/// [`SkipDebugParameters`]: trait.SkipDebugParameters.html
+#[doc(hidden)]
+pub struct ParametersDebug<'a, P>(pub Option<&'a P>);
+
+/// Formats component parameters which implement `Debug`. See [`ParametersDebug`].
+///
+/// [`ParametersDebug`]: struct.ParametersDebug.html
+#[doc(hidden)]
+pub trait DebugParameters {
+    fn debug_parameters(&self) -> Option<String>;
+}
+
+/// Skips component parameters which don't implement `Debug`. See [`ParametersDebug`].
+///
+/// [`ParametersDebug`]: struct.ParametersDebug.html
+#[doc(hidden)]
+pub trait SkipDebugParameters {
+    fn debug_parameters(&self) -> Option<String>;
+}
+
+impl<'a, P: Debug> DebugParameters for ParametersDebug<'a, P> {
+    fn debug_parameters(&self) -> Option<String> {
+        self.0.map(|parameters| format!("{:?}", parameters))
+    }
+}
+
+impl<'a, 'b, P> SkipDebugParameters for &'b ParametersDebug<'a, P> {
+    fn debug_parameters(&self) -> Option<String> {
+        None
+    }
+}
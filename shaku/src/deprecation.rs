@@ -0,0 +1,115 @@
+//! Reporting of deprecated component resolution
+
+use core::fmt;
+#[cfg(feature = "std")]
+use once_cell::sync::Lazy;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::sync::{Mutex, RwLock};
+
+/// A deprecated component was resolved from a module. Components are marked as deprecated in the
+/// [`module`] macro via `#[deprecated]` or `#[deprecated = "note"]`.
+///
+/// [`module`]: macro.module.html
+#[derive(Clone, Debug)]
+pub struct DeprecatedResolution {
+    module: &'static str,
+    interface: &'static str,
+    note: Option<&'static str>,
+}
+
+impl DeprecatedResolution {
+    /// The name of the module which the component was resolved from
+    pub fn module(&self) -> &'static str {
+        self.module
+    }
+
+    /// The deprecated interface, ex. `dyn PaymentsV1`
+    pub fn interface(&self) -> &'static str {
+        self.interface
+    }
+
+    /// The note given in `#[deprecated = "note"]`, if any
+    pub fn note(&self) -> Option<&'static str> {
+        self.note
+    }
+}
+
+impl fmt::Display for DeprecatedResolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is deprecated in module `{}`",
+            self.interface, self.module
+        )?;
+
+        if let Some(note) = self.note {
+            write!(f, ": {}", note)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A function which is called when a deprecated component is resolved. See
+/// [`set_deprecation_handler`].
+///
+/// [`set_deprecation_handler`]: fn.set_deprecation_handler.html
+pub type DeprecationHandler = fn(&DeprecatedResolution);
+
+#[cfg(feature = "std")]
+static HANDLER: Lazy<RwLock<Option<DeprecationHandler>>> = Lazy::new(|| RwLock::new(None));
+#[cfg(feature = "std")]
+static REPORTED: Lazy<Mutex<HashSet<(&'static str, &'static str)>>> =
+    Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Set the function which is called the first time each deprecated component is resolved (via
+/// [`HasComponent::resolve`] or [`HasComponent::resolve_ref`]). Until a handler is set, deprecated
+/// resolutions are not reported. The handler can, for example, log a warning or panic in tests.
+///
+/// [`HasComponent::resolve`]: trait.HasComponent.html#tymethod.resolve
+/// [`HasComponent::resolve_ref`]: trait.HasComponent.html#tymethod.resolve_ref
+#[cfg(feature = "std")]
+pub fn set_deprecation_handler(handler: DeprecationHandler) {
+    *HANDLER.write().unwrap() = Some(handler);
+}
+
+/// Called by the generated [`HasComponent`] impls of deprecated components
+///
+/// [`HasComponent`]: trait.HasComponent.html
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub fn report_deprecated_resolution(
+    module: &'static str,
+    interface: &'static str,
+    note: Option<&'static str>,
+) {
+    let handler = match *HANDLER.read().unwrap() {
+        Some(handler) => handler,
+        None => return,
+    };
+
+    if !REPORTED.lock().unwrap().insert((module, interface)) {
+        return;
+    }
+
+    handler(&DeprecatedResolution {
+        module,
+        interface,
+        note,
+    });
+}
+
+/// Called by the generated [`HasComponent`] impls of deprecated components
+///
+/// [`HasComponent`]: trait.HasComponent.html
+#[doc(hidden)]
+#[cfg(not(feature = "std"))]
+#[inline(always)]
+pub fn report_deprecated_resolution(
+    _module: &'static str,
+    _interface: &'static str,
+    _note: Option<&'static str>,
+) {
+}
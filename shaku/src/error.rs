@@ -0,0 +1,15 @@
+//! The error type returned by providers
+
+#[cfg(feature = "std")]
+pub use std::error::Error;
+
+#[cfg(not(feature = "std"))]
+use core::fmt::{Debug, Display};
+
+/// The error returned by providers, as `Box<dyn Error>`. With the `std` feature this is
+/// `std::error::Error`. Without std, any type which implements `Debug` and `Display` is an error.
+#[cfg(not(feature = "std"))]
+pub trait Error: Debug + Display {}
+
+#[cfg(not(feature = "std"))]
+impl<T: Debug + Display + ?Sized> Error for T {}
@@ -0,0 +1,262 @@
+use crate::{Component, Interface, Module, ModuleBuildContext};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A value stored in a [`TypedCache`]
+///
+/// [`TypedCache`]: trait.TypedCache.html
+pub type CachedValue = Arc<dyn Any + Send + Sync>;
+
+/// An in-process cache shared by the components of a module. Entries are keyed by their type and
+/// a string key, so different components can use the same keys for different types without
+/// conflicts.
+///
+/// The methods of this trait work with type-erased values. Use the typed methods on
+/// `dyn TypedCache` instead, ex. [`get`] and [`insert`].
+///
+/// [`get`]: #method.get
+/// [`insert`]: #method.insert
+pub trait TypedCache: Interface {
+    /// Get the entry, unless it is missing or expired
+    fn get_value(&self, type_id: TypeId, key: &str) -> Option<CachedValue>;
+
+    /// Add or replace an entry. If `ttl` is `None`, the cache's default TTL is used.
+    fn insert_value(&self, type_id: TypeId, key: String, value: CachedValue, ttl: Option<Duration>);
+
+    /// Remove the entry, returning true if it was present
+    fn remove_value(&self, type_id: TypeId, key: &str) -> bool;
+
+    /// Remove every entry
+    fn clear(&self);
+
+    /// The number of entries, including expired entries which were not evicted yet
+    fn len(&self) -> usize;
+
+    /// Check if the cache has no entries
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl dyn TypedCache {
+    /// Get the value of type `T` stored under the key
+    pub fn get<T: Any + Send + Sync>(&self, key: &str) -> Option<Arc<T>> {
+        self.get_value(TypeId::of::<T>(), key)
+            .and_then(|value| value.downcast().ok())
+    }
+
+    /// Store the value under the key, using the cache's default TTL
+    pub fn insert<T: Any + Send + Sync>(&self, key: impl Into<String>, value: T) {
+        self.insert_value(TypeId::of::<T>(), key.into(), Arc::new(value), None);
+    }
+
+    /// Store the value under the key, expiring after `ttl`
+    pub fn insert_with_ttl<T: Any + Send + Sync>(
+        &self,
+        key: impl Into<String>,
+        value: T,
+        ttl: Duration,
+    ) {
+        self.insert_value(TypeId::of::<T>(), key.into(), Arc::new(value), Some(ttl));
+    }
+
+    /// Get the value of type `T` stored under the key, or create and store it
+    pub fn get_or_insert_with<T: Any + Send + Sync>(
+        &self,
+        key: &str,
+        create: impl FnOnce() -> T,
+    ) -> Arc<T> {
+        if let Some(value) = self.get(key) {
+            return value;
+        }
+
+        let value = Arc::new(create());
+        self.insert_value(TypeId::of::<T>(), key.to_string(), value.clone(), None);
+        value
+    }
+
+    /// Remove the value of type `T` stored under the key, returning true if it was present
+    pub fn remove<T: Any + Send + Sync>(&self, key: &str) -> bool {
+        self.remove_value(TypeId::of::<T>(), key)
+    }
+}
+
+/// A [`TypedCache`] which stores entries in memory. Expired entries are evicted when they are
+/// accessed, or when the cache is full. If the cache is still full, the oldest entry is evicted.
+///
+/// The default TTL and the maximum number of entries are set via [`InMemoryCacheParameters`].
+///
+/// # Example
+/// ```
+/// use shaku::extras::{InMemoryCache, InMemoryCacheParameters, TypedCache};
+/// use shaku::{module, HasComponent};
+/// use std::time::Duration;
+///
+/// module! {
+///     MyModule {
+///         components = [InMemoryCache],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder()
+///     .with_component_parameters::<InMemoryCache>(InMemoryCacheParameters {
+///         default_ttl: Some(Duration::from_secs(60)),
+///         max_entries: Some(1000),
+///     })
+///     .build();
+/// let cache: &dyn TypedCache = module.resolve_ref();
+///
+/// cache.insert("answer", 42u32);
+/// cache.insert("answer", "forty-two".to_string());
+///
+/// assert_eq!(cache.get::<u32>("answer").as_deref(), Some(&42));
+/// assert_eq!(cache.get::<String>("answer").as_deref().map(String::as_str), Some("forty-two"));
+/// # }
+/// ```
+///
+/// [`TypedCache`]: trait.TypedCache.html
+/// [`InMemoryCacheParameters`]: struct.InMemoryCacheParameters.html
+pub struct InMemoryCache {
+    entries: Mutex<Entries>,
+    default_ttl: Option<Duration>,
+    max_entries: Option<usize>,
+}
+
+#[derive(Default)]
+struct Entries {
+    map: HashMap<(TypeId, String), Entry>,
+    /// Incremented on every insert, to find the oldest entry
+    next_sequence: u64,
+}
+
+struct Entry {
+    value: CachedValue,
+    expires_at: Option<Instant>,
+    sequence: u64,
+}
+
+impl Entry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= now)
+    }
+}
+
+impl InMemoryCache {
+    /// Create an empty cache
+    pub fn new(params: InMemoryCacheParameters) -> Self {
+        InMemoryCache {
+            entries: Mutex::new(Entries::default()),
+            default_ttl: params.default_ttl,
+            max_entries: params.max_entries,
+        }
+    }
+}
+
+impl TypedCache for InMemoryCache {
+    fn get_value(&self, type_id: TypeId, key: &str) -> Option<CachedValue> {
+        let mut entries = self.entries.lock().unwrap();
+        let map_key = (type_id, key.to_string());
+
+        if entries.map.get(&map_key)?.is_expired(Instant::now()) {
+            entries.map.remove(&map_key);
+            return None;
+        }
+
+        entries.map.get(&map_key).map(|entry| entry.value.clone())
+    }
+
+    fn insert_value(
+        &self,
+        type_id: TypeId,
+        key: String,
+        value: CachedValue,
+        ttl: Option<Duration>,
+    ) {
+        let mut entries = self.entries.lock().unwrap();
+        let now = Instant::now();
+        let map_key = (type_id, key);
+
+        if let Some(max_entries) = self.max_entries {
+            if !entries.map.contains_key(&map_key) && entries.map.len() >= max_entries {
+                entries.map.retain(|_, entry| !entry.is_expired(now));
+            }
+
+            while !entries.map.contains_key(&map_key) && entries.map.len() >= max_entries {
+                let oldest = match entries.map.iter().min_by_key(|(_, entry)| entry.sequence) {
+                    Some((oldest, _)) => oldest.clone(),
+                    None => break,
+                };
+                entries.map.remove(&oldest);
+            }
+
+            if max_entries == 0 {
+                return;
+            }
+        }
+
+        let sequence = entries.next_sequence;
+        entries.next_sequence += 1;
+        entries.map.insert(
+            map_key,
+            Entry {
+                value,
+                expires_at: ttl.or(self.default_ttl).map(|ttl| now + ttl),
+                sequence,
+            },
+        );
+    }
+
+    fn remove_value(&self, type_id: TypeId, key: &str) -> bool {
+        self.entries
+            .lock()
+            .unwrap()
+            .map
+            .remove(&(type_id, key.to_string()))
+            .is_some()
+    }
+
+    fn clear(&self) {
+        self.entries.lock().unwrap().map.clear();
+    }
+
+    fn len(&self) -> usize {
+        self.entries.lock().unwrap().map.len()
+    }
+}
+
+impl fmt::Debug for InMemoryCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemoryCache")
+            .field("len", &self.len())
+            .field("default_ttl", &self.default_ttl)
+            .field("max_entries", &self.max_entries)
+            .finish()
+    }
+}
+
+/// Parameters for [`InMemoryCache`]
+///
+/// [`InMemoryCache`]: struct.InMemoryCache.html
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryCacheParameters {
+    /// How long entries are kept if they are inserted without a TTL. If not set, these entries
+    /// don't expire.
+    pub default_ttl: Option<Duration>,
+    /// The maximum number of entries. If not set, the cache is unbounded.
+    pub max_entries: Option<usize>,
+}
+
+impl<M: Module> Component<M> for InMemoryCache {
+    type Interface = dyn TypedCache;
+    type Parameters = InMemoryCacheParameters;
+
+    fn build(_: &mut ModuleBuildContext<M>, params: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(InMemoryCache::new(params))
+    }
+}
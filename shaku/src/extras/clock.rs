@@ -0,0 +1,158 @@
+use crate::{Component, HasComponent, Interface, Module, ModuleBuildContext, ModuleBuilder};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// The source of the current time. Components which read the time can inject `Arc<dyn Clock>`
+/// instead of calling `SystemTime::now`, so tests can control the time with a [`TestClock`].
+///
+/// [`TestClock`]: struct.TestClock.html
+pub trait Clock: Interface {
+    /// The current wall-clock time
+    fn now(&self) -> SystemTime;
+
+    /// The current monotonic time, for measuring durations
+    fn instant(&self) -> Instant;
+}
+
+/// A [`Clock`] which reads the system time. Register it with a module like any other component.
+///
+/// # Example
+/// ```
+/// use shaku::extras::{Clock, SystemClock, TestClock, TestClockBuilder};
+/// use shaku::{module, Component, HasComponent, Interface};
+/// use std::sync::Arc;
+/// use std::time::{Duration, SystemTime};
+///
+/// trait Session: Interface {
+///     fn is_expired(&self, created: SystemTime) -> bool;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Session)]
+/// struct SessionImpl {
+///     #[shaku(inject)]
+///     clock: Arc<dyn Clock>,
+/// }
+///
+/// impl Session for SessionImpl {
+///     fn is_expired(&self, created: SystemTime) -> bool {
+///         self.clock.now() > created + Duration::from_secs(60)
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [SystemClock, SessionImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// // In tests, replace the system clock and advance the time manually
+/// let clock = TestClock::new();
+/// let module = MyModule::builder().with_test_clock(&clock).build();
+/// let session: &dyn Session = module.resolve_ref();
+/// let created = clock.now();
+///
+/// assert!(!session.is_expired(created));
+/// clock.advance(Duration::from_secs(61));
+/// assert!(session.is_expired(created));
+/// # }
+/// ```
+///
+/// [`Clock`]: trait.Clock.html
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+impl<M: Module> Component<M> for SystemClock {
+    type Interface = dyn Clock;
+    type Parameters = ();
+
+    fn build(_: &mut ModuleBuildContext<M>, _: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(SystemClock)
+    }
+}
+
+/// A [`Clock`] which only moves forward when advanced. Clones share the same time, so a test can
+/// keep a clone to advance the clock used by the module. Use
+/// [`TestClockBuilder::with_test_clock`] to replace the module's clock.
+///
+/// [`Clock`]: trait.Clock.html
+/// [`TestClockBuilder::with_test_clock`]: trait.TestClockBuilder.html#tymethod.with_test_clock
+#[derive(Clone, Debug)]
+pub struct TestClock {
+    start: SystemTime,
+    start_instant: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl TestClock {
+    /// Create a clock which starts at the current system time
+    pub fn new() -> Self {
+        Self::starting_at(SystemTime::now())
+    }
+
+    /// Create a clock which starts at the given time
+    pub fn starting_at(start: SystemTime) -> Self {
+        TestClock {
+            start,
+            start_instant: Instant::now(),
+            elapsed: Arc::new(Mutex::new(Duration::from_secs(0))),
+        }
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+    }
+
+    /// The time the clock has been advanced by
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> SystemTime {
+        self.start + self.elapsed()
+    }
+
+    fn instant(&self) -> Instant {
+        self.start_instant + self.elapsed()
+    }
+}
+
+/// Replaces the [`Clock`] of a module with a [`TestClock`] while building it. This is implemented
+/// for the builder of every module which contains a clock.
+///
+/// [`Clock`]: trait.Clock.html
+/// [`TestClock`]: struct.TestClock.html
+pub trait TestClockBuilder {
+    /// Replace the module's [`Clock`] with the test clock. The clock is shared, so advancing the
+    /// passed in clock also advances the module's clock.
+    ///
+    /// [`Clock`]: trait.Clock.html
+    fn with_test_clock(self, clock: &TestClock) -> Self;
+}
+
+impl<M: Module + HasComponent<dyn Clock>> TestClockBuilder for ModuleBuilder<M> {
+    fn with_test_clock(self, clock: &TestClock) -> Self {
+        self.with_component_override::<dyn Clock>(Box::new(clock.clone()))
+    }
+}
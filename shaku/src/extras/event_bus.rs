@@ -0,0 +1,139 @@
+use crate::{Component, Interface, Module, ModuleBuildContext};
+use std::sync::{Arc, RwLock};
+
+/// Receives events published on an [`EventBus`].
+///
+/// Components can be subscribed automatically by deriving `Component` with
+/// `#[shaku(interface = MyInterface, subscribe = MyEvent)]`. In that case the
+/// interface trait must also inherit `Subscriber<MyEvent>`.
+///
+/// [`EventBus`]: struct.EventBus.html
+pub trait Subscriber<E>: Interface {
+    /// Handle an event published on the bus.
+    fn on(&self, event: &E);
+}
+
+impl<E, T: Subscriber<E> + ?Sized> Subscriber<E> for Arc<T>
+where
+    Arc<T>: Interface,
+{
+    fn on(&self, event: &E) {
+        T::on(&**self, event)
+    }
+}
+
+/// A publish/subscribe component which dispatches events of type `E` to every
+/// [`Subscriber`] registered with it.
+///
+/// Register it with a module like any other component. The interface is the
+/// bus itself, so it is resolved as `Arc<EventBus<E>>`.
+///
+/// # Example
+/// ```
+/// use shaku::extras::{EventBus, Subscriber};
+/// use shaku::{module, Component, HasComponent, Interface};
+/// use std::sync::{Arc, Mutex};
+///
+/// enum AppEvent {
+///     UserCreated(String),
+/// }
+///
+/// trait AuditLog: Interface + Subscriber<AppEvent> {
+///     fn entries(&self) -> Vec<String>;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = AuditLog, subscribe = AppEvent)]
+/// struct AuditLogImpl {
+///     #[shaku(default)]
+///     entries: Mutex<Vec<String>>,
+/// }
+///
+/// impl AuditLog for AuditLogImpl {
+///     fn entries(&self) -> Vec<String> {
+///         self.entries.lock().unwrap().clone()
+///     }
+/// }
+///
+/// impl Subscriber<AppEvent> for AuditLogImpl {
+///     fn on(&self, event: &AppEvent) {
+///         match event {
+///             AppEvent::UserCreated(name) => self.entries.lock().unwrap().push(name.clone()),
+///         }
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [EventBus<AppEvent>, AuditLogImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let bus: &EventBus<AppEvent> = module.resolve_ref();
+/// bus.publish(&AppEvent::UserCreated("alice".to_string()));
+///
+/// let audit_log: &dyn AuditLog = module.resolve_ref();
+/// assert_eq!(audit_log.entries(), vec!["alice".to_string()]);
+/// # }
+/// ```
+///
+/// [`Subscriber`]: trait.Subscriber.html
+pub struct EventBus<E: 'static> {
+    subscribers: RwLock<Vec<Arc<dyn Subscriber<E>>>>,
+}
+
+impl<E: 'static> EventBus<E> {
+    /// Create an event bus with no subscribers
+    pub fn new() -> Self {
+        EventBus {
+            subscribers: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Add a subscriber to the bus. It will receive every event published
+    /// after this call.
+    pub fn subscribe(&self, subscriber: Arc<dyn Subscriber<E>>) {
+        self.subscribers.write().unwrap().push(subscriber);
+    }
+
+    /// Send an event to all subscribers, in the order they subscribed.
+    ///
+    /// Subscribers are allowed to publish or subscribe while handling an
+    /// event. Subscribers added during a publish will not receive the event
+    /// currently being published.
+    pub fn publish(&self, event: &E) {
+        let subscribers = self.subscribers.read().unwrap().clone();
+
+        for subscriber in subscribers {
+            subscriber.on(event);
+        }
+    }
+
+    /// The number of subscribers registered with the bus
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.read().unwrap().len()
+    }
+}
+
+impl<E: 'static> Default for EventBus<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: Module, E: 'static> Component<M> for EventBus<E>
+where
+    EventBus<E>: Interface,
+{
+    type Interface = EventBus<E>;
+    type Parameters = ();
+    // Reusing the bus would keep the subscriptions of the components which were rebuilt
+    const ALWAYS_REBUILD: bool = true;
+
+    fn build(_: &mut ModuleBuildContext<M>, _: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(EventBus::new())
+    }
+}
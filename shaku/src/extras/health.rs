@@ -0,0 +1,220 @@
+use crate::{Component, HasComponent, Interface, Module, ModuleBuildContext};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+/// A component which can report on its health (ex. a database pool checking its connections).
+///
+/// Components can be registered with the module's [`HealthRegistry`] automatically by deriving
+/// `Component` with `#[shaku(interface = MyInterface, health_check)]`. In that case the interface
+/// trait must also inherit `HealthCheck`.
+///
+/// Checks are run synchronously whenever a report is requested, so they should be quick.
+///
+/// [`HealthRegistry`]: struct.HealthRegistry.html
+pub trait HealthCheck: Interface {
+    /// The name of the check in the report. Defaults to the type name of the component.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Check the health of the component
+    fn check(&self) -> HealthStatus;
+}
+
+impl<T: HealthCheck + ?Sized> HealthCheck for Arc<T>
+where
+    Arc<T>: Interface,
+{
+    fn name(&self) -> &str {
+        T::name(&**self)
+    }
+
+    fn check(&self) -> HealthStatus {
+        T::check(&**self)
+    }
+}
+
+/// The result of a single [`HealthCheck`]
+///
+/// [`HealthCheck`]: trait.HealthCheck.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HealthStatus {
+    /// Working as expected
+    Healthy,
+    /// Working, but with reduced functionality or performance
+    Degraded(String),
+    /// Not working
+    Unhealthy(String),
+}
+
+impl fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HealthStatus::Healthy => write!(f, "healthy"),
+            HealthStatus::Degraded(reason) => write!(f, "degraded ({})", reason),
+            HealthStatus::Unhealthy(reason) => write!(f, "unhealthy ({})", reason),
+        }
+    }
+}
+
+/// A component which collects every [`HealthCheck`] registered with it, and aggregates their
+/// results into a [`HealthReport`].
+///
+/// Register it with a module like any other component. The interface is the registry itself, so
+/// it is resolved as `Arc<HealthRegistry>`. Modules containing the registry can create a report
+/// via [`ModuleHealth::health`].
+///
+/// # Example
+/// ```
+/// use shaku::extras::{HealthCheck, HealthRegistry, HealthStatus, ModuleHealth};
+/// use shaku::{module, Component, Interface};
+///
+/// trait Database: Interface + HealthCheck {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Database, health_check)]
+/// struct DatabaseImpl;
+///
+/// impl Database for DatabaseImpl {}
+///
+/// impl HealthCheck for DatabaseImpl {
+///     fn name(&self) -> &str {
+///         "database"
+///     }
+///
+///     fn check(&self) -> HealthStatus {
+///         HealthStatus::Degraded("high latency".to_string())
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [HealthRegistry, DatabaseImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let report = module.health();
+///
+/// assert!(report.is_healthy());
+/// assert_eq!(report.to_string(), "healthy\n- database: degraded (high latency)");
+/// # }
+/// ```
+///
+/// [`HealthCheck`]: trait.HealthCheck.html
+/// [`HealthReport`]: struct.HealthReport.html
+/// [`ModuleHealth::health`]: trait.ModuleHealth.html#tymethod.health
+pub struct HealthRegistry {
+    checks: RwLock<Vec<Arc<dyn HealthCheck>>>,
+}
+
+impl HealthRegistry {
+    /// Create a registry with no checks
+    pub fn new() -> Self {
+        HealthRegistry {
+            checks: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Add a check to the registry
+    pub fn register(&self, check: Arc<dyn HealthCheck>) {
+        self.checks.write().unwrap().push(check);
+    }
+
+    /// Run every check, in the order they were registered
+    pub fn check(&self) -> HealthReport {
+        let checks = self.checks.read().unwrap().clone();
+
+        HealthReport {
+            checks: checks
+                .iter()
+                .map(|check| (check.name().to_string(), check.check()))
+                .collect(),
+        }
+    }
+
+    /// The number of checks registered with the registry
+    pub fn check_count(&self) -> usize {
+        self.checks.read().unwrap().len()
+    }
+}
+
+impl Default for HealthRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: Module> Component<M> for HealthRegistry {
+    type Interface = HealthRegistry;
+    type Parameters = ();
+    // Reusing the registry would keep the checks of the components which were rebuilt
+    const ALWAYS_REBUILD: bool = true;
+
+    fn build(_: &mut ModuleBuildContext<M>, _: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(HealthRegistry::new())
+    }
+}
+
+/// The results of the checks in a [`HealthRegistry`]
+///
+/// [`HealthRegistry`]: struct.HealthRegistry.html
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HealthReport {
+    checks: Vec<(String, HealthStatus)>,
+}
+
+impl HealthReport {
+    /// The name and status of each check
+    pub fn checks(&self) -> &[(String, HealthStatus)] {
+        &self.checks
+    }
+
+    /// Check if no check is unhealthy. Degraded checks still count as healthy.
+    pub fn is_healthy(&self) -> bool {
+        for (_, status) in &self.checks {
+            if let HealthStatus::Unhealthy(_) = status {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl fmt::Display for HealthReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_healthy() {
+            write!(f, "healthy")?;
+        } else {
+            write!(f, "unhealthy")?;
+        }
+
+        for (name, status) in &self.checks {
+            write!(f, "\n- {}: {}", name, status)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates a [`HealthReport`] from a module's [`HealthRegistry`]. This is implemented for every
+/// module which contains the registry.
+///
+/// [`HealthReport`]: struct.HealthReport.html
+/// [`HealthRegistry`]: struct.HealthRegistry.html
+pub trait ModuleHealth {
+    /// Run the checks registered with the module's [`HealthRegistry`]
+    ///
+    /// [`HealthRegistry`]: struct.HealthRegistry.html
+    fn health(&self) -> HealthReport;
+}
+
+impl<M: HasComponent<HealthRegistry> + ?Sized> ModuleHealth for M {
+    fn health(&self) -> HealthReport {
+        let registry: &HealthRegistry = self.resolve_ref();
+        registry.check()
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{Component, Error, HasComponent, Interface, Module, ModuleBuildContext, Provider};
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use reqwest::{Client, IntoUrl, Method, RequestBuilder};
+use std::fmt;
+use std::time::Duration;
+
+/// A shared HTTP client. Components and providers can inject `Arc<dyn HttpClient>` instead of
+/// creating their own `reqwest::Client`, so every request of the module goes through one
+/// connection pool.
+pub trait HttpClient: Interface {
+    /// The shared client. Cloning it is cheap, and the clone uses the same connection pool.
+    fn client(&self) -> &Client;
+}
+
+/// An [`HttpClient`] which is configured via its component parameters. The client is built once,
+/// when the module is built, so its connections are reused by every component and every provided
+/// [`AuthenticatedClient`].
+///
+/// This component is enabled by the `http_client` feature.
+///
+/// # Example
+/// ```
+/// use shaku::extras::{
+///     AuthenticatedClient, HttpClient, PooledHttpClient, PooledHttpClientParameters,
+///     StaticCredentials, StaticCredentialsParameters,
+/// };
+/// use shaku::{module, HasProvider};
+/// use std::time::Duration;
+///
+/// module! {
+///     ApiModule {
+///         components = [PooledHttpClient, StaticCredentials],
+///         providers = [AuthenticatedClient]
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = ApiModule::builder()
+///     .with_component_parameters::<PooledHttpClient>(PooledHttpClientParameters {
+///         timeout: Some(Duration::from_secs(5)),
+///         user_agent: Some("my-service/1.0".to_string()),
+///         ..Default::default()
+///     })
+///     .with_component_parameters::<StaticCredentials>(StaticCredentialsParameters {
+///         authorization: "Bearer secret-token".to_string(),
+///     })
+///     .build();
+///
+/// let client: Box<AuthenticatedClient> = module.provide().unwrap();
+/// let request = client.get("https://example.com/users").build().unwrap();
+/// assert!(request.headers().contains_key("authorization"));
+/// # }
+/// ```
+///
+/// [`HttpClient`]: trait.HttpClient.html
+/// [`AuthenticatedClient`]: struct.AuthenticatedClient.html
+#[derive(Clone, Debug)]
+pub struct PooledHttpClient {
+    client: Client,
+}
+
+impl PooledHttpClient {
+    /// Share an existing client
+    pub fn new(client: Client) -> Self {
+        PooledHttpClient { client }
+    }
+}
+
+impl HttpClient for PooledHttpClient {
+    fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+/// Parameters for [`PooledHttpClient`]. Settings which are not set use reqwest's defaults.
+///
+/// [`PooledHttpClient`]: struct.PooledHttpClient.html
+#[derive(Default)]
+pub struct PooledHttpClientParameters {
+    /// Use this client instead of building one. The other settings are ignored.
+    pub client: Option<Client>,
+    /// The timeout of each request, from connecting until the response body is read
+    pub timeout: Option<Duration>,
+    /// The timeout for connecting to a host
+    pub connect_timeout: Option<Duration>,
+    /// How long idle connections are kept in the pool
+    pub pool_idle_timeout: Option<Duration>,
+    /// The maximum number of idle connections kept in the pool for each host
+    pub pool_max_idle_per_host: Option<usize>,
+    /// The `User-Agent` header of each request
+    pub user_agent: Option<String>,
+}
+
+impl<M: Module> Component<M> for PooledHttpClient {
+    type Interface = dyn HttpClient;
+    type Parameters = PooledHttpClientParameters;
+
+    fn build(_: &mut ModuleBuildContext<M>, params: Self::Parameters) -> Box<Self::Interface> {
+        if let Some(client) = params.client {
+            return Box::new(PooledHttpClient::new(client));
+        }
+
+        let mut builder = Client::builder();
+        if let Some(timeout) = params.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = params.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(pool_idle_timeout) = params.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(pool_idle_timeout);
+        }
+        if let Some(pool_max_idle_per_host) = params.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(pool_max_idle_per_host);
+        }
+        if let Some(user_agent) = params.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+
+        let client = builder
+            .build()
+            .expect("Failed to build the client of PooledHttpClient");
+
+        Box::new(PooledHttpClient::new(client))
+    }
+}
+
+/// The credentials which an [`AuthenticatedClient`] sends with each request. This is read each
+/// time a client is provided, so implementations can refresh expiring tokens.
+///
+/// [`AuthenticatedClient`]: struct.AuthenticatedClient.html
+pub trait Credentials: Interface {
+    /// The value of the `Authorization` header (ex. `Bearer <token>`)
+    fn authorization(&self) -> Result<String, Box<dyn Error>>;
+}
+
+/// [`Credentials`] which never change, set via the component parameters
+///
+/// [`Credentials`]: trait.Credentials.html
+pub struct StaticCredentials {
+    authorization: String,
+}
+
+impl Credentials for StaticCredentials {
+    fn authorization(&self) -> Result<String, Box<dyn Error>> {
+        Ok(self.authorization.clone())
+    }
+}
+
+impl fmt::Debug for StaticCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticCredentials")
+            .field("authorization", &format_args!("***"))
+            .finish()
+    }
+}
+
+/// Parameters for [`StaticCredentials`]
+///
+/// [`StaticCredentials`]: struct.StaticCredentials.html
+#[derive(Default)]
+pub struct StaticCredentialsParameters {
+    /// The value of the `Authorization` header (ex. `Bearer <token>`)
+    pub authorization: String,
+}
+
+impl<M: Module> Component<M> for StaticCredentials {
+    type Interface = dyn Credentials;
+    type Parameters = StaticCredentialsParameters;
+
+    fn build(_: &mut ModuleBuildContext<M>, params: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(StaticCredentials {
+            authorization: params.authorization,
+        })
+    }
+}
+
+/// A provided HTTP client which adds the module's [`Credentials`] to each request. Each provided
+/// client shares the connection pool of the module's [`HttpClient`], so a client can be provided
+/// per request (ex. with the credentials of the current user) without opening new connections.
+///
+/// See [`PooledHttpClient`] for an example.
+///
+/// [`Credentials`]: trait.Credentials.html
+/// [`HttpClient`]: trait.HttpClient.html
+/// [`PooledHttpClient`]: struct.PooledHttpClient.html
+#[derive(Clone)]
+pub struct AuthenticatedClient {
+    client: Client,
+    authorization: HeaderValue,
+}
+
+impl AuthenticatedClient {
+    /// Start a request with the credentials set
+    pub fn request<U: IntoUrl>(&self, method: Method, url: U) -> RequestBuilder {
+        self.client
+            .request(method, url)
+            .header(AUTHORIZATION, self.authorization.clone())
+    }
+
+    /// Start a `GET` request with the credentials set
+    pub fn get<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::GET, url)
+    }
+
+    /// Start a `POST` request with the credentials set
+    pub fn post<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::POST, url)
+    }
+
+    /// Start a `PUT` request with the credentials set
+    pub fn put<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::PUT, url)
+    }
+
+    /// Start a `DELETE` request with the credentials set
+    pub fn delete<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::DELETE, url)
+    }
+
+    /// The shared client, without the credentials
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+impl fmt::Debug for AuthenticatedClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthenticatedClient")
+            .field("client", &self.client)
+            .field("authorization", &format_args!("***"))
+            .finish()
+    }
+}
+
+impl<M> Provider<M> for AuthenticatedClient
+where
+    M: Module + HasComponent<dyn HttpClient> + HasComponent<dyn Credentials>,
+{
+    type Interface = AuthenticatedClient;
+
+    fn provide(module: &M) -> Result<Box<Self::Interface>, Box<dyn Error>> {
+        let http_client: &dyn HttpClient = module.resolve_ref();
+        let credentials: &dyn Credentials = module.resolve_ref();
+
+        let mut authorization = HeaderValue::from_str(&credentials.authorization()?)?;
+        authorization.set_sensitive(true);
+
+        Ok(Box::new(AuthenticatedClient {
+            client: http_client.client().clone(),
+            authorization,
+        }))
+    }
+
+    fn dependencies() -> Vec<&'static str> {
+        vec![
+            std::any::type_name::<dyn HttpClient>(),
+            std::any::type_name::<dyn Credentials>(),
+        ]
+    }
+}
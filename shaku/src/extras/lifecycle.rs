@@ -0,0 +1,347 @@
+use crate::{BoxFuture, Component, Error, HasComponent, Interface, Module, ModuleBuildContext};
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// A component which does asynchronous work when the application starts or shuts down (ex. a
+/// server draining its connections, or a queue consumer committing its offsets).
+///
+/// Components can be registered with the module's [`LifecycleRegistry`] automatically by deriving
+/// `Component` with `#[shaku(interface = MyInterface, lifecycle)]`. In that case the interface
+/// trait must also inherit `Stoppable`. The hooks return a [`BoxFuture`], so they can be
+/// implemented with `async fn` via [`async_interface!`].
+///
+/// [`LifecycleRegistry`]: struct.LifecycleRegistry.html
+/// [`BoxFuture`]: ../type.BoxFuture.html
+/// [`async_interface!`]: ../macro.async_interface.html
+pub trait Stoppable: Interface {
+    /// The name of the component in errors. Defaults to the type name of the component.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Start the component. The default implementation does nothing.
+    fn start(&self) -> BoxFuture<'_, Result<(), Box<dyn Error>>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Stop the component
+    fn stop(&self) -> BoxFuture<'_, Result<(), Box<dyn Error>>>;
+}
+
+impl<T: Stoppable + ?Sized> Stoppable for Arc<T>
+where
+    Arc<T>: Interface,
+{
+    fn name(&self) -> &str {
+        T::name(&**self)
+    }
+
+    fn start(&self) -> BoxFuture<'_, Result<(), Box<dyn Error>>> {
+        T::start(&**self)
+    }
+
+    fn stop(&self) -> BoxFuture<'_, Result<(), Box<dyn Error>>> {
+        T::stop(&**self)
+    }
+}
+
+/// Why a lifecycle hook of a [`Stoppable`] component failed
+///
+/// [`Stoppable`]: trait.Stoppable.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LifecycleFailure {
+    /// The hook returned an error
+    Failed(String),
+    /// The hook did not finish within the registry's timeout
+    TimedOut(Duration),
+}
+
+impl fmt::Display for LifecycleFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifecycleFailure::Failed(reason) => write!(f, "failed ({})", reason),
+            LifecycleFailure::TimedOut(timeout) => write!(f, "timed out after {:?}", timeout),
+        }
+    }
+}
+
+/// The components of a [`LifecycleRegistry`] which failed to start or stop
+///
+/// [`LifecycleRegistry`]: struct.LifecycleRegistry.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LifecycleError {
+    action: &'static str,
+    failures: Vec<(String, LifecycleFailure)>,
+}
+
+impl LifecycleError {
+    /// The name of each component which failed, and why
+    pub fn failures(&self) -> &[(String, LifecycleFailure)] {
+        &self.failures
+    }
+}
+
+impl fmt::Display for LifecycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of the components failed to {}",
+            self.failures.len(),
+            self.action
+        )?;
+
+        for (name, failure) in &self.failures {
+            write!(f, "\n- {}: {}", name, failure)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for LifecycleError {}
+
+/// Run a lifecycle hook, and report an error or timeout as a failure
+async fn run_hook(
+    hook: BoxFuture<'_, Result<(), Box<dyn Error>>>,
+    timeout: Duration,
+) -> Result<(), LifecycleFailure> {
+    match tokio::time::timeout(timeout, hook).await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(error)) => Err(LifecycleFailure::Failed(error.to_string())),
+        Err(_) => Err(LifecycleFailure::TimedOut(timeout)),
+    }
+}
+
+/// A component which collects every [`Stoppable`] component registered with it, and runs their
+/// lifecycle hooks.
+///
+/// Register it with a module like any other component. The interface is the registry itself, so
+/// it is resolved as `Arc<LifecycleRegistry>`. Modules containing the registry can be started and
+/// shut down via [`ModuleLifecycle`]. Each hook must finish within the timeout set via
+/// [`LifecycleRegistryParameters`], which requires a tokio runtime with the time driver enabled.
+///
+/// This component is enabled by the `lifecycle` feature.
+///
+/// # Example
+/// ```
+/// use shaku::extras::{LifecycleRegistry, ModuleLifecycle, Stoppable};
+/// use shaku::{async_interface, module, Component, Error, Interface};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use tokio::runtime::Builder;
+///
+/// trait Server: Interface + Stoppable {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Server, lifecycle)]
+/// struct ServerImpl {
+///     #[shaku(default)]
+///     running: AtomicBool,
+/// }
+///
+/// impl Server for ServerImpl {}
+///
+/// async_interface! {
+///     impl Stoppable for ServerImpl {
+///         async fn start(&self) -> Result<(), Box<dyn Error>> {
+///             self.running.store(true, Ordering::SeqCst);
+///             Ok(())
+///         }
+///
+///         async fn stop(&self) -> Result<(), Box<dyn Error>> {
+///             self.running.store(false, Ordering::SeqCst);
+///             Ok(())
+///         }
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [LifecycleRegistry, ServerImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let runtime = Builder::new_current_thread().enable_time().build().unwrap();
+/// let module = MyModule::builder().build();
+///
+/// runtime.block_on(async {
+///     module.startup().await.unwrap();
+///     // Serve requests until the application exits, ex. via `shutdown_on_ctrl_c`
+///     module.shutdown().await.unwrap();
+/// });
+/// # }
+/// ```
+///
+/// [`Stoppable`]: trait.Stoppable.html
+/// [`ModuleLifecycle`]: trait.ModuleLifecycle.html
+/// [`LifecycleRegistryParameters`]: struct.LifecycleRegistryParameters.html
+pub struct LifecycleRegistry {
+    components: RwLock<Vec<Arc<dyn Stoppable>>>,
+    timeout: Duration,
+}
+
+impl LifecycleRegistry {
+    /// The default time each hook has to finish
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Create a registry with no components, whose hooks each have `timeout` to finish
+    pub fn new(timeout: Duration) -> Self {
+        LifecycleRegistry {
+            components: RwLock::new(Vec::new()),
+            timeout,
+        }
+    }
+
+    /// Add a component to the registry
+    pub fn register(&self, component: Arc<dyn Stoppable>) {
+        self.components.write().unwrap().push(component);
+    }
+
+    /// The number of components registered with the registry
+    pub fn component_count(&self) -> usize {
+        self.components.read().unwrap().len()
+    }
+
+    /// The time each hook has to finish
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Start every component, in the order they were registered. Components are registered after
+    /// their dependencies are built, so dependencies are started first. Starting stops at the
+    /// first component which fails.
+    pub async fn start(&self) -> Result<(), LifecycleError> {
+        let components = self.components.read().unwrap().clone();
+
+        for component in &components {
+            if let Err(failure) = run_hook(component.start(), self.timeout).await {
+                return Err(LifecycleError {
+                    action: "start",
+                    failures: vec![(component.name().to_string(), failure)],
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stop every component, in the reverse order they were registered, so components are
+    /// stopped before their dependencies. Every component is stopped even if some fail, and the
+    /// failures are reported together.
+    pub async fn stop(&self) -> Result<(), LifecycleError> {
+        let components = self.components.read().unwrap().clone();
+        let mut failures = Vec::new();
+
+        for component in components.iter().rev() {
+            if let Err(failure) = run_hook(component.stop(), self.timeout).await {
+                failures.push((component.name().to_string(), failure));
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(LifecycleError {
+                action: "stop",
+                failures,
+            })
+        }
+    }
+}
+
+impl Default for LifecycleRegistry {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TIMEOUT)
+    }
+}
+
+impl fmt::Debug for LifecycleRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LifecycleRegistry")
+            .field("components", &self.component_count())
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// Parameters for [`LifecycleRegistry`]
+///
+/// [`LifecycleRegistry`]: struct.LifecycleRegistry.html
+pub struct LifecycleRegistryParameters {
+    /// The time each hook has to finish. Defaults to
+    /// [`LifecycleRegistry::DEFAULT_TIMEOUT`].
+    ///
+    /// [`LifecycleRegistry::DEFAULT_TIMEOUT`]: struct.LifecycleRegistry.html#associatedconstant.DEFAULT_TIMEOUT
+    pub timeout: Duration,
+}
+
+impl Default for LifecycleRegistryParameters {
+    fn default() -> Self {
+        LifecycleRegistryParameters {
+            timeout: LifecycleRegistry::DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl<M: Module> Component<M> for LifecycleRegistry {
+    type Interface = LifecycleRegistry;
+    type Parameters = LifecycleRegistryParameters;
+    // Reusing the registry would keep the components which were rebuilt
+    const ALWAYS_REBUILD: bool = true;
+
+    fn build(_: &mut ModuleBuildContext<M>, params: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(LifecycleRegistry::new(params.timeout))
+    }
+}
+
+/// Starts and shuts down the components registered with a module's [`LifecycleRegistry`]. This is
+/// implemented for every module which contains the registry.
+///
+/// [`LifecycleRegistry`]: struct.LifecycleRegistry.html
+pub trait ModuleLifecycle {
+    /// Start the components of the module. See [`LifecycleRegistry::start`].
+    ///
+    /// [`LifecycleRegistry::start`]: struct.LifecycleRegistry.html#method.start
+    fn startup(&self) -> BoxFuture<'_, Result<(), LifecycleError>>;
+
+    /// Stop the components of the module. See [`LifecycleRegistry::stop`].
+    ///
+    /// [`LifecycleRegistry::stop`]: struct.LifecycleRegistry.html#method.stop
+    fn shutdown(&self) -> BoxFuture<'_, Result<(), LifecycleError>>;
+}
+
+impl<M: HasComponent<LifecycleRegistry> + ?Sized> ModuleLifecycle for M {
+    fn startup(&self) -> BoxFuture<'_, Result<(), LifecycleError>> {
+        let registry: &LifecycleRegistry = self.resolve_ref();
+        Box::pin(registry.start())
+    }
+
+    fn shutdown(&self) -> BoxFuture<'_, Result<(), LifecycleError>> {
+        let registry: &LifecycleRegistry = self.resolve_ref();
+        Box::pin(registry.stop())
+    }
+}
+
+/// Wait for `signal` to finish, then shut down the module's components
+pub async fn shutdown_on<M, S>(module: &M, signal: S) -> Result<(), LifecycleError>
+where
+    M: ModuleLifecycle + ?Sized,
+    S: Future<Output = ()>,
+{
+    signal.await;
+    module.shutdown().await
+}
+
+/// Wait for ctrl-c, then shut down the module's components. If the signal can't be listened for,
+/// the components are shut down right away.
+pub async fn shutdown_on_ctrl_c<M: ModuleLifecycle + ?Sized>(
+    module: &M,
+) -> Result<(), LifecycleError> {
+    shutdown_on(module, async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+    .await
+}
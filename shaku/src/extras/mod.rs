@@ -0,0 +1,38 @@
+//! Optional utilities built on top of shaku's components and providers.
+//!
+//! These are enabled with the `extras` feature.
+
+mod cache;
+mod clock;
+mod event_bus;
+mod health;
+#[cfg(feature = "http_client")]
+mod http_client;
+#[cfg(feature = "lifecycle")]
+mod lifecycle;
+#[cfg(feature = "random")]
+mod random;
+#[cfg(feature = "tokio_runtime")]
+mod runtime_handle;
+
+pub use self::cache::{CachedValue, InMemoryCache, InMemoryCacheParameters, TypedCache};
+pub use self::clock::{Clock, SystemClock, TestClock, TestClockBuilder};
+pub use self::event_bus::{EventBus, Subscriber};
+pub use self::health::{HealthCheck, HealthRegistry, HealthReport, HealthStatus, ModuleHealth};
+#[cfg(feature = "http_client")]
+pub use self::http_client::{
+    AuthenticatedClient, Credentials, HttpClient, PooledHttpClient, PooledHttpClientParameters,
+    StaticCredentials, StaticCredentialsParameters,
+};
+#[cfg(feature = "lifecycle")]
+pub use self::lifecycle::{
+    shutdown_on, shutdown_on_ctrl_c, LifecycleError, LifecycleFailure, LifecycleRegistry,
+    LifecycleRegistryParameters, ModuleLifecycle, Stoppable,
+};
+#[cfg(feature = "random")]
+pub use self::random::{
+    IdGenerator, RandomSource, SeededRandom, SeededRandomParameters, SequentialIdGenerator,
+    ThreadRandom, UuidV4Generator, UuidV7Generator,
+};
+#[cfg(feature = "tokio_runtime")]
+pub use self::runtime_handle::{RuntimeHandle, RuntimeHandleParameters, SpawnedTask, Spawner};
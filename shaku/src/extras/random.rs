@@ -0,0 +1,229 @@
+use crate::{Component, Interface, Module, ModuleBuildContext};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Generates unique IDs. Components can inject `Arc<dyn IdGenerator>` instead of calling
+/// `Uuid::new_v4` directly, so tests can use a [`SequentialIdGenerator`].
+///
+/// [`SequentialIdGenerator`]: struct.SequentialIdGenerator.html
+pub trait IdGenerator: Interface {
+    /// Generate a new ID
+    fn generate(&self) -> Uuid;
+}
+
+/// An [`IdGenerator`] which generates random (version 4) UUIDs
+///
+/// [`IdGenerator`]: trait.IdGenerator.html
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UuidV4Generator;
+
+impl IdGenerator for UuidV4Generator {
+    fn generate(&self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+impl<M: Module> Component<M> for UuidV4Generator {
+    type Interface = dyn IdGenerator;
+    type Parameters = ();
+
+    fn build(_: &mut ModuleBuildContext<M>, _: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(UuidV4Generator)
+    }
+}
+
+/// An [`IdGenerator`] which generates time-ordered (version 7) UUIDs
+///
+/// [`IdGenerator`]: trait.IdGenerator.html
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UuidV7Generator;
+
+impl IdGenerator for UuidV7Generator {
+    fn generate(&self) -> Uuid {
+        Uuid::now_v7()
+    }
+}
+
+impl<M: Module> Component<M> for UuidV7Generator {
+    type Interface = dyn IdGenerator;
+    type Parameters = ();
+
+    fn build(_: &mut ModuleBuildContext<M>, _: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(UuidV7Generator)
+    }
+}
+
+/// A deterministic [`IdGenerator`] for tests. IDs are numbered, starting at
+/// `00000000-0000-0000-0000-000000000001`.
+///
+/// # Example
+/// ```
+/// use shaku::extras::{IdGenerator, SequentialIdGenerator, UuidV4Generator};
+/// use shaku::{module, HasComponent};
+/// use uuid::Uuid;
+///
+/// module! {
+///     MyModule {
+///         components = [UuidV4Generator],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder()
+///     .with_component_override::<dyn IdGenerator>(Box::new(SequentialIdGenerator::new()))
+///     .build();
+/// let ids: &dyn IdGenerator = module.resolve_ref();
+///
+/// assert_eq!(ids.generate(), Uuid::from_u128(1));
+/// assert_eq!(ids.generate(), Uuid::from_u128(2));
+/// # }
+/// ```
+///
+/// [`IdGenerator`]: trait.IdGenerator.html
+#[derive(Debug, Default)]
+pub struct SequentialIdGenerator {
+    last: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    /// Create a generator whose first ID is 1
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn generate(&self) -> Uuid {
+        Uuid::from_u128(u128::from(self.last.fetch_add(1, Ordering::SeqCst) + 1))
+    }
+}
+
+impl<M: Module> Component<M> for SequentialIdGenerator {
+    type Interface = dyn IdGenerator;
+    type Parameters = ();
+
+    fn build(_: &mut ModuleBuildContext<M>, _: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(SequentialIdGenerator::new())
+    }
+}
+
+/// A source of random numbers. Components can inject `Arc<dyn RandomSource>` instead of using
+/// `rand::thread_rng` directly, so tests can use a [`SeededRandom`].
+///
+/// [`SeededRandom`]: struct.SeededRandom.html
+pub trait RandomSource: Interface {
+    /// Generate a random `u32`
+    fn next_u32(&self) -> u32;
+
+    /// Generate a random `u64`
+    fn next_u64(&self) -> u64;
+
+    /// Fill the buffer with random bytes
+    fn fill_bytes(&self, dest: &mut [u8]);
+}
+
+/// A [`RandomSource`] which uses the thread-local random number generator
+///
+/// [`RandomSource`]: trait.RandomSource.html
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ThreadRandom;
+
+impl RandomSource for ThreadRandom {
+    fn next_u32(&self) -> u32 {
+        rand::thread_rng().next_u32()
+    }
+
+    fn next_u64(&self) -> u64 {
+        rand::thread_rng().next_u64()
+    }
+
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        rand::thread_rng().fill_bytes(dest)
+    }
+}
+
+impl<M: Module> Component<M> for ThreadRandom {
+    type Interface = dyn RandomSource;
+    type Parameters = ();
+
+    fn build(_: &mut ModuleBuildContext<M>, _: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(ThreadRandom)
+    }
+}
+
+/// A deterministic [`RandomSource`] for tests. Sources with the same seed generate the same
+/// numbers.
+///
+/// As a component, the seed is set via its parameters (it defaults to 0):
+///
+/// ```
+/// use shaku::extras::{RandomSource, SeededRandom, SeededRandomParameters};
+/// use shaku::{module, HasComponent};
+///
+/// module! {
+///     MyModule {
+///         components = [SeededRandom],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder()
+///     .with_component_parameters::<SeededRandom>(SeededRandomParameters { seed: 42 })
+///     .build();
+/// let random: &dyn RandomSource = module.resolve_ref();
+///
+/// assert_eq!(random.next_u64(), SeededRandom::new(42).next_u64());
+/// # }
+/// ```
+///
+/// [`RandomSource`]: trait.RandomSource.html
+#[derive(Debug)]
+pub struct SeededRandom {
+    rng: Mutex<StdRng>,
+}
+
+impl SeededRandom {
+    /// Create a source from the seed
+    pub fn new(seed: u64) -> Self {
+        SeededRandom {
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl RandomSource for SeededRandom {
+    fn next_u32(&self) -> u32 {
+        self.rng.lock().unwrap().next_u32()
+    }
+
+    fn next_u64(&self) -> u64 {
+        self.rng.lock().unwrap().next_u64()
+    }
+
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        self.rng.lock().unwrap().fill_bytes(dest)
+    }
+}
+
+/// Parameters for [`SeededRandom`]
+///
+/// [`SeededRandom`]: struct.SeededRandom.html
+#[derive(Debug, Default)]
+pub struct SeededRandomParameters {
+    /// The seed of the random number generator
+    pub seed: u64,
+}
+
+impl<M: Module> Component<M> for SeededRandom {
+    type Interface = dyn RandomSource;
+    type Parameters = SeededRandomParameters;
+
+    fn build(_: &mut ModuleBuildContext<M>, params: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(SeededRandom::new(params.seed))
+    }
+}
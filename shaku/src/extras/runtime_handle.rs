@@ -0,0 +1,120 @@
+use crate::{Component, Interface, Module, ModuleBuildContext};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use tokio::runtime::Handle;
+
+/// A task which can be passed to a [`Spawner`]
+///
+/// [`Spawner`]: trait.Spawner.html
+pub type SpawnedTask = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Spawns background tasks. Components which spawn tasks can inject `Arc<dyn Spawner>` instead of
+/// using a global runtime handle, so tests can substitute their own implementation.
+pub trait Spawner: Interface {
+    /// Run the task in the background
+    fn spawn(&self, task: SpawnedTask);
+}
+
+/// A [`Spawner`] which spawns tasks on a tokio runtime.
+///
+/// By default, the handle of the runtime which the module is built in is used, so the module must
+/// be built within a tokio context (ex. in an `async fn`). Otherwise, pass the handle in via the
+/// component parameters. Building the module panics if no handle is available.
+///
+/// This component is enabled by the `tokio_runtime` feature.
+///
+/// # Example
+/// ```
+/// use shaku::extras::{RuntimeHandle, RuntimeHandleParameters, Spawner};
+/// use shaku::{module, HasComponent};
+/// use std::sync::mpsc::channel;
+/// use tokio::runtime::Builder;
+///
+/// module! {
+///     MyModule {
+///         components = [RuntimeHandle],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let runtime = Builder::new_current_thread().build().unwrap();
+/// let module = MyModule::builder()
+///     .with_component_parameters::<RuntimeHandle>(RuntimeHandleParameters {
+///         handle: Some(runtime.handle().clone()),
+///     })
+///     .build();
+///
+/// let spawner: &dyn Spawner = module.resolve_ref();
+/// let (sender, receiver) = channel();
+/// spawner.spawn(Box::pin(async move {
+///     sender.send("Ran in the background").unwrap();
+/// }));
+///
+/// // The task runs once the runtime is driven
+/// let message = runtime.block_on(async {
+///     loop {
+///         if let Ok(message) = receiver.try_recv() {
+///             return message;
+///         }
+///
+///         tokio::task::yield_now().await;
+///     }
+/// });
+/// assert_eq!(message, "Ran in the background");
+/// # }
+/// ```
+///
+/// [`Spawner`]: trait.Spawner.html
+pub struct RuntimeHandle {
+    handle: Handle,
+}
+
+impl RuntimeHandle {
+    /// Create a spawner for the runtime
+    pub fn new(handle: Handle) -> Self {
+        RuntimeHandle { handle }
+    }
+
+    /// The handle of the runtime which tasks are spawned on
+    pub fn handle(&self) -> &Handle {
+        &self.handle
+    }
+}
+
+impl Spawner for RuntimeHandle {
+    fn spawn(&self, task: SpawnedTask) {
+        self.handle.spawn(task);
+    }
+}
+
+impl fmt::Debug for RuntimeHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RuntimeHandle").finish()
+    }
+}
+
+/// Parameters for [`RuntimeHandle`]
+///
+/// [`RuntimeHandle`]: struct.RuntimeHandle.html
+#[derive(Default)]
+pub struct RuntimeHandleParameters {
+    /// The runtime to spawn tasks on. If not set, the runtime which the module is built in is
+    /// used.
+    pub handle: Option<Handle>,
+}
+
+impl<M: Module> Component<M> for RuntimeHandle {
+    type Interface = dyn Spawner;
+    type Parameters = RuntimeHandleParameters;
+
+    fn build(_: &mut ModuleBuildContext<M>, params: Self::Parameters) -> Box<Self::Interface> {
+        let handle = params.handle.or_else(|| Handle::try_current().ok()).expect(
+            "RuntimeHandle requires a tokio runtime. Build the module within a tokio \
+                 context, or set RuntimeHandleParameters::handle",
+        );
+
+        Box::new(RuntimeHandle::new(handle))
+    }
+}
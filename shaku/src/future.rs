@@ -0,0 +1,21 @@
+//! The future type returned by async interface methods
+
+use alloc::boxed::Box;
+use core::future::Future;
+use core::pin::Pin;
+
+/// The future returned by methods declared with [`async_interface`]. Boxing the future keeps the
+/// interface object safe, so it can be used as a trait object (ex. `Arc<dyn Trait>`). When the
+/// `thread_safe` feature is on, the future is `Send`.
+///
+/// [`async_interface`]: macro.async_interface.html
+#[cfg(feature = "thread_safe")]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// The future returned by methods declared with [`async_interface`]. Boxing the future keeps the
+/// interface object safe, so it can be used as a trait object (ex. `Rc<dyn Trait>`). When the
+/// `thread_safe` feature is on, the future is `Send`.
+///
+/// [`async_interface`]: macro.async_interface.html
+#[cfg(not(feature = "thread_safe"))]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
@@ -0,0 +1,107 @@
+//! A lazily built module which can be stored in a `static`
+
+use crate::{HasComponent, HasProvider, Interface, Module};
+use once_cell::sync::Lazy;
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+/// A module which is built the first time it is used, so it can be stored in a `static`. This is
+/// useful for applications which only need one module, instead of passing the module (or its
+/// components) through every layer. The module is built at most once, even if it is first used by
+/// multiple threads at the same time.
+///
+/// This requires the `thread_safe` feature, since a `static` must be shareable between threads.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, Global, Interface};
+/// use std::sync::Arc;
+///
+/// trait Logger: Interface {
+///     fn log(&self, content: &str);
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Logger)]
+/// struct ConsoleLogger;
+///
+/// impl Logger for ConsoleLogger {
+///     fn log(&self, content: &str) {
+///         println!("{}", content);
+///     }
+/// }
+///
+/// module! {
+///     AppModule {
+///         components = [ConsoleLogger],
+///         providers = []
+///     }
+/// }
+///
+/// static APP: Global<AppModule> = Global::new(|| AppModule::builder().build());
+///
+/// fn main() {
+///     let logger: &dyn Logger = APP.resolve_ref();
+///     logger.log("Hello from a global module");
+///
+///     let logger: Arc<dyn Logger> = APP.resolve();
+/// }
+/// ```
+pub struct Global<M, F = fn() -> M> {
+    module: Lazy<M, F>,
+}
+
+// The const fn can't have trait bounds on the minimum supported Rust version
+impl<M, F> Global<M, F> {
+    /// Create a global module which is built by `build` when it is first used
+    pub const fn new(build: F) -> Self {
+        Global {
+            module: Lazy::new(build),
+        }
+    }
+}
+
+impl<M: Module, F: FnOnce() -> M> Global<M, F> {
+    /// Get the module, building it if this is the first use
+    pub fn get(&self) -> &M {
+        &self.module
+    }
+
+    /// Resolve a component from the module. See [`HasComponent::resolve`].
+    ///
+    /// [`HasComponent::resolve`]: trait.HasComponent.html#tymethod.resolve
+    pub fn resolve<I: Interface + ?Sized>(&self) -> Arc<I>
+    where
+        M: HasComponent<I>,
+    {
+        self.get().resolve()
+    }
+
+    /// Get a reference to a component from the module. See [`HasComponent::resolve_ref`].
+    ///
+    /// [`HasComponent::resolve_ref`]: trait.HasComponent.html#tymethod.resolve_ref
+    pub fn resolve_ref<I: Interface + ?Sized>(&self) -> &I
+    where
+        M: HasComponent<I>,
+    {
+        self.get().resolve_ref()
+    }
+
+    /// Create a service using the module's provider. See [`HasProvider::provide`].
+    ///
+    /// [`HasProvider::provide`]: trait.HasProvider.html#tymethod.provide
+    pub fn provide<I: ?Sized>(&self) -> Result<Box<I>, Box<dyn Error>>
+    where
+        M: HasProvider<I>,
+    {
+        self.get().provide()
+    }
+}
+
+impl<M, F> Debug for Global<M, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Checking if the module was built requires a newer once_cell than the minimum supported
+        f.write_str("Global { .. }")
+    }
+}
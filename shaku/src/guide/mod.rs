@@ -6,6 +6,8 @@
 //!   lifetimes.
 //! - [Getting started with submodules][submodule guide]: Learn how to organize and abstract
 //!   components into multiple modules.
+//! - [Splitting modules across crates][multiple crates guide]: Learn how to split interfaces and
+//!   implementations into separate crates.
 //!
 //! ## Structure your application
 //! Start with your application's structs and traits. Use `Arc<dyn T>` for dependencies.
@@ -48,6 +50,11 @@
 //! the `thread_safe` feature. The [`Interface`] trait acts as a trait alias for these bounds, and is
 //! automatically implemented on types which implement the bounds.
 //!
+//! If a component can't be made thread safe (ex. it uses `Rc` or `Cell`), its interface can inherit
+//! [`LocalInterface`] instead, and the component can be marked with `#[shaku(thread_bound)]`. The
+//! component is then resolved as `Arc<ThreadBound<dyn Trait>>`, and can only be used on the thread
+//! which built it. See [`ThreadBound`] for more details.
+//!
 //! In our example, the two interface traits would become:
 //!
 //! ```
@@ -62,6 +69,13 @@
 //! }
 //! ```
 //!
+//! Components are stored as trait objects (ex. `Arc<dyn Logger>`), so interface traits must be
+//! [object safe]. The derive macros check this first, so an object-unsafe interface is reported at
+//! `#[shaku(interface = ...)]`. The usual causes are generic methods, which can be moved to another
+//! trait or take a trait object instead, and `async fn`, which [`async_interface!`] supports.
+//!
+//! [object safe]: https://doc.rust-lang.org/reference/items/traits.html#object-safety
+//!
 //! ## Implement Component
 //! A component is a struct that implements an [`Interface`] trait. In our example, we have 2
 //! components:
@@ -88,6 +102,14 @@
 //! struct LoggerImpl;
 //! ```
 //!
+//! The interface can also be generic or bind associated types, ex.
+//! `#[shaku(interface = Repository<Entity = User>)]` (the `dyn` is optional). The component is then
+//! resolved as `Arc<dyn Repository<Entity = User>>`.
+//!
+//! Simple value-like services (ex. a configuration struct) don't need a trait at all. With
+//! `#[shaku(interface = Self)]` (or the component's own type, ex. `#[shaku(interface = Config)]`)
+//! the struct itself is the interface, and the component is resolved as `Arc<Config>`.
+//!
 //! ## Express dependencies
 //! Components can depend on other components. In our example, `DateLoggerImpl` requires an `Logger`
 //! component.
@@ -284,6 +306,62 @@
 //! # }
 //! ```
 //!
+//! Traits can be derived on the generated parameters struct by listing them in the component's
+//! attribute, ex. `#[shaku(interface = DateLogger, parameters_derive(Clone, Debug))]`.
+//!
+//! Built modules implement `ModuleFingerprint`, whose `fingerprint()` hashes the implementations
+//! of the module's services (and whether they were overridden) along with the parameters which
+//! implement `Hash`, ex. via `parameters_derive(Hash)`. The fingerprint is stable between runs, so
+//! it can be used as a cache key or to detect configuration changes in snapshot tests. The
+//! fingerprint is only recorded if the module is built via
+//! `MyModule::builder().record_fingerprint()`, since hashing the parameters slows down the build.
+//!
+//! Parameters which hold credentials can be marked with `#[shaku(secret)]`. The parameters struct
+//! then gets a generated `Debug` impl (replacing `parameters_derive(Debug)`) which prints `***`
+//! instead of the secret values, so logging the parameters doesn't leak them.
+//!
+//! Parameters can also be closures, boxed as `Box<dyn Fn(..) + Send + Sync>` (the `Send + Sync`
+//! is only required by the `thread_safe` feature, so without it a plain `Box<dyn Fn(..)>` which
+//! captures ex. an `Rc` works too). Closures have no default value, so wrap the parameter in
+//! [`NoDefault`], ex. `format_fn: NoDefault<Box<dyn Fn(i32) -> String + Send + Sync>>`. This also
+//! works when implementing [`Component`] manually, where the `Parameters` type must implement
+//! `Default`.
+//!
+//! Components (and providers) can also be tuple structs, with the same attributes on each field
+//! (ex. `struct DateLoggerImpl(#[shaku(inject)] Arc<dyn Logger>, String, usize);`). The generated
+//! parameters struct is then a tuple struct with just the parameter fields, in order (ex.
+//! `DateLoggerImplParameters("Jan 26".to_string(), 2020)`).
+//!
+//! If the interface is implemented by one of the component's parameters (ex. a client type from
+//! another crate), use `#[shaku(delegate = <field>)]` on the component. The module then stores
+//! that field as the component, and the component derefs to it. The field must be a parameter
+//! (not injected or provided), and its type must implement the interface.
+//!
+//! When migrating to a new version of an interface, the component can implement the new version and
+//! generate an adapter for the old version via `#[shaku(adapter(<old interface> as <adapter>))]`,
+//! ex. `#[shaku(interface = PaymentsV2, adapter(PaymentsV1 as PaymentsV1Adapter))]`. The adapter
+//! is a component which wraps the new interface (and derefs to it), so you only need to implement
+//! the old interface on it using the new one. Add both to the module, optionally marking the
+//! adapter with `#[deprecated]` (see the [module macro]).
+//!
+//! Components are built in the order they are listed in the module, after the components they
+//! depend on. `MyModule::build_order()` (from the `ModuleBuildOrder` trait) lists the components
+//! in that order. If a component relies on the side effects of another component without
+//! injecting it (ex. a logger which must be initialized first), pin the order via
+//! `#[shaku(build_after = <other component>)]`, ex.
+//! `#[shaku(interface = Database, build_after = LoggerImpl)]`. The other component must also be in
+//! the module.
+//!
+//! Components which provide services dynamically at runtime (ex. orchestrators) can receive a
+//! handle to their module via `#[shaku(inject_module)] module: ModuleHandle<dyn MyModuleTrait>`.
+//! The handle is set once the module is built via [`ModuleBuilder::build_arc`]. See
+//! [`ModuleHandle`] for an example.
+//!
+//! Components can also own a service from a provider via `#[shaku(provide)] parser: Box<dyn
+//! Parser>`. The provider is called once when the component is built, which needs the finished
+//! module, so these components must be `#[lazy]` and the module built via
+//! [`ModuleBuilder::build_arc`].
+//!
 //! ## Resolve components
 //! Once you created the module, you can resolve the components using the module's [`HasComponent`]
 //! methods.
@@ -340,6 +418,9 @@
 //! # }
 //! ```
 //!
+//! To resolve several components at once, use [`ResolveMany`], ex.
+//! `let (logger, date_logger): (Arc<dyn Logger>, Arc<dyn DateLogger>) = module.resolve_many();`.
+//!
 //! ## Overriding components
 //! Although shaku is a compile time DI library, you can override the implementation of a service
 //! during the module build. This can be useful during testing, for example using an in-memory
@@ -407,6 +488,13 @@
 //! # }
 //! ```
 //!
+//! If the interface has a [mockall] mock (ex. via `#[cfg_attr(test, mockall::automock)]` on the
+//! trait), turn on the `mock` feature and add `mock` to the attribute of one of the interface's
+//! components, ex. `#[shaku(interface = Logger, mock)]`. In tests, the mock (ex. `MockLogger`) is
+//! then a component as well. It takes the mock itself as its parameters, so a test module can list
+//! `MockLogger` in its components, and pass in a mock with its expectations set up via
+//! `with_component_parameters::<MockLogger>(mock)`.
+//!
 //! ## The full example
 //! ```
 //! use shaku::{module, Component, Interface, HasComponent};
@@ -469,17 +557,27 @@
 //!
 //! [provider guide]: provider/index.html
 //! [submodule guide]: submodules/index.html
+//! [multiple crates guide]: multiple_crates/index.html
 //! [`Interface`]: ../trait.Interface.html
+//! [`LocalInterface`]: ../trait.LocalInterface.html
+//! [`ThreadBound`]: ../struct.ThreadBound.html
+//! [`async_interface!`]: ../macro.async_interface.html
 //! [`Component`]: ../trait.Component.html
 //! [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
 //! [`HasComponent`]: ../trait.HasComponent.html
 //! [`HasComponent::build_component`]: ../trait.HasComponent.html#tymethod.build_component
+//! [`ModuleBuilder::build_arc`]: ../struct.ModuleBuilder.html#method.build_arc
+//! [`ModuleHandle`]: ../struct.ModuleHandle.html
+//! [`NoDefault`]: ../struct.NoDefault.html
+//! [`ResolveMany`]: ../trait.ResolveMany.html
 //! [`Module`]: ../trait.Module.html
 //! [module macro]: ../macro.module.html
 //! [`ModuleBuilder::with_submodules`]: ../struct.ModuleBuilder.html#method.with_submodules
 //! [`ModuleBuilder::build`]: ../struct.ModuleBuilder.html#method.build
 //! [`with_component_parameters`]: ../struct.ModuleBuilder.html#method.with_component_parameters
 //! [`with_component_override`]: ../struct.ModuleBuilder.html#method.with_component_override
+//! [mockall]: https://crates.io/crates/mockall
 
+pub mod multiple_crates;
 pub mod provider;
 pub mod submodules;
@@ -0,0 +1,161 @@
+//! # Splitting modules across crates
+//! This guide assumes you have already read the [submodules getting started guide].
+//!
+//! Large applications are often split into a Cargo workspace with multiple crates. With shaku, a
+//! common layout is:
+//!
+//! - An interface crate, which contains the service traits and the module interface traits. It only
+//!   depends on `shaku`.
+//! - One or more implementation crates, which contain the components, providers, and modules. They
+//!   depend on the interface crate.
+//! - The application crate, which uses the module interfaces and picks the implementations when
+//!   building its modules.
+//!
+//! Code which uses the services only depends on the interface crate, so implementations can be
+//! swapped (ex. for a fake during tests) without changing or recompiling that code.
+//!
+//! A full example is available in the [`examples/multi_crate`] directory of the repository.
+//!
+//! ## The interface crate
+//! The [`module_interface`] macro declares a module interface trait from the list of services it
+//! exposes. This is the same as writing the [`HasComponent`] and [`HasProvider`] supertraits by hand.
+//!
+//! ```rust
+//! mod auth_interface {
+//!     use shaku::{module_interface, Interface};
+//!
+//!     pub trait AuthManager: Interface {
+//!         fn is_valid(&self, token: &str) -> bool;
+//!     }
+//!
+//!     pub trait Session {
+//!         fn user(&self) -> String;
+//!     }
+//!
+//!     module_interface! {
+//!         pub trait AuthModule {
+//!             components = [dyn AuthManager],
+//!             providers = [dyn Session]
+//!         }
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! ## The implementation crate
+//! The implementation crate implements the services and the module interface via the
+//! [`module`][module macro] macro. The module does not need to be public if the crate exposes a
+//! function which builds it.
+//!
+//! ```rust
+//! # mod auth_interface {
+//! #     use shaku::{module_interface, Interface};
+//! #     pub trait AuthManager: Interface { fn is_valid(&self, token: &str) -> bool; }
+//! #     pub trait Session { fn user(&self) -> String; }
+//! #     module_interface! {
+//! #         pub trait AuthModule { components = [dyn AuthManager], providers = [dyn Session] }
+//! #     }
+//! # }
+//! mod auth_impl {
+//!     use crate::auth_interface::{AuthManager, AuthModule, Session};
+//!     use shaku::{module, Component, Provider};
+//!     use std::sync::Arc;
+//!
+//!     #[derive(Component)]
+//!     #[shaku(interface = AuthManager)]
+//!     struct AuthManagerImpl;
+//!     impl AuthManager for AuthManagerImpl {
+//!         fn is_valid(&self, token: &str) -> bool {
+//!             token == "secret"
+//!         }
+//!     }
+//!
+//!     #[derive(Provider)]
+//!     #[shaku(interface = Session)]
+//!     struct SessionImpl;
+//!     impl Session for SessionImpl {
+//!         fn user(&self) -> String {
+//!             "admin".to_string()
+//!         }
+//!     }
+//!
+//!     module! {
+//!         AuthModuleImpl: AuthModule {
+//!             components = [AuthManagerImpl],
+//!             providers = [SessionImpl]
+//!         }
+//!     }
+//!
+//!     pub fn auth_module() -> Arc<dyn AuthModule> {
+//!         Arc::new(AuthModuleImpl::builder().build())
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let auth_module = auth_impl::auth_module();
+//! # }
+//! ```
+//!
+//! ## The application crate
+//! The application uses the module interface as a submodule (see the [submodules getting started
+//! guide]), and passes in the implementation when building its module.
+//!
+//! ```rust
+//! # mod auth_interface {
+//! #     use shaku::{module_interface, Interface};
+//! #     pub trait AuthManager: Interface { fn is_valid(&self, token: &str) -> bool; }
+//! #     pub trait Session { fn user(&self) -> String; }
+//! #     module_interface! {
+//! #         pub trait AuthModule { components = [dyn AuthManager], providers = [dyn Session] }
+//! #     }
+//! # }
+//! # mod auth_impl {
+//! #     use crate::auth_interface::{AuthManager, AuthModule, Session};
+//! #     use shaku::{module, Component, Provider};
+//! #     use std::sync::Arc;
+//! #     #[derive(Component)]
+//! #     #[shaku(interface = AuthManager)]
+//! #     struct AuthManagerImpl;
+//! #     impl AuthManager for AuthManagerImpl {
+//! #         fn is_valid(&self, token: &str) -> bool { token == "secret" }
+//! #     }
+//! #     #[derive(Provider)]
+//! #     #[shaku(interface = Session)]
+//! #     struct SessionImpl;
+//! #     impl Session for SessionImpl { fn user(&self) -> String { "admin".to_string() } }
+//! #     module! {
+//! #         AuthModuleImpl: AuthModule { components = [AuthManagerImpl], providers = [SessionImpl] }
+//! #     }
+//! #     pub fn auth_module() -> Arc<dyn AuthModule> { Arc::new(AuthModuleImpl::builder().build()) }
+//! # }
+//! use auth_interface::{AuthManager, AuthModule, Session};
+//! use shaku::{module, HasComponent, HasProvider};
+//!
+//! module! {
+//!     RootModule {
+//!         components = [],
+//!         providers = [],
+//!
+//!         use dyn AuthModule {
+//!             components = [dyn AuthManager],
+//!             providers = [dyn Session]
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let root_module = RootModule::builder(auth_impl::auth_module()).build();
+//!
+//! let auth_manager: &dyn AuthManager = root_module.resolve_ref();
+//! let session: Box<dyn Session> = root_module.provide().unwrap();
+//! assert!(auth_manager.is_valid("secret"));
+//! assert_eq!(session.user(), "admin");
+//! # }
+//! ```
+//!
+//! [submodules getting started guide]: ../submodules/index.html
+//! [`examples/multi_crate`]: https://github.com/AzureMarker/shaku/tree/master/examples/multi_crate
+//! [`module_interface`]: ../../macro.module_interface.html
+//! [`HasComponent`]: ../../trait.HasComponent.html
+//! [`HasProvider`]: ../../trait.HasProvider.html
+//! [module macro]: ../../macro.module.html
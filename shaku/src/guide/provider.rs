@@ -136,6 +136,12 @@
 //! }
 //! ```
 //!
+//! Providers don't have parameters, but fields with a default (`#[shaku(default)]` or
+//! `#[shaku(default = ...)]`) are set to the default each time a service is provided. This is
+//! useful for `PhantomData` markers in generic providers, ex. `struct Exporter<T>` with
+//! `#[shaku(interface = Export<T>)]`. The type parameters of a generic provider must be `'static`,
+//! which the derive macro adds to the impl's where clause.
+//!
 //! ### Manually implement Provider
 //! Sometimes you have to manually implement provider when it's not as simple as constructing a new
 //! service directly from existing ones. This is the case for `DBConnection`, as it comes from a
@@ -167,6 +173,11 @@
 //! services, you should use traits for decoupling, but sometimes you just need to pass around a
 //! concrete data structure or connection type.
 //!
+//! The derive macro supports this too: use `#[shaku(interface = Self)]` (or the provider's own
+//! type, ex. `#[shaku(interface = Scoped<T>)]` for a generic provider) to provide the struct
+//! itself instead of a trait object. Concrete interfaces, generic or not, are listed in modules
+//! and module interfaces just like traits (ex. `providers = [Connection<u32>]`).
+//!
 //! ## Associate with module
 //! Associating providers with a module is just like associating a service:
 //!
@@ -278,6 +289,43 @@
 //! # }
 //! ```
 //!
+//! ## Retrying failures
+//! Providers which connect to other systems can fail temporarily. A derived provider can retry such
+//! failures with `#[shaku(retry(times = 3, backoff_ms = 100))]`, which calls the provider up to
+//! four times. The delay starts at `backoff_ms` milliseconds and doubles after each failed attempt.
+//! The last error is returned if every attempt fails. Manually implemented providers can be wrapped
+//! in a [`RetryProvider`] instead.
+//!
+//! ```
+//! # use shaku::Provider;
+//! # trait Repository {}
+//! # struct DBConnection;
+//! # impl<M: shaku::Module> Provider<M> for DBConnection {
+//! #     type Interface = DBConnection;
+//! #     fn provide(_: &M) -> Result<Box<DBConnection>, Box<dyn std::error::Error>> {
+//! #         Ok(Box::new(DBConnection))
+//! #     }
+//! # }
+//! #[derive(Provider)]
+//! #[shaku(interface = Repository, retry(times = 3, backoff_ms = 100))]
+//! struct RepositoryImpl {
+//!     #[shaku(provide)]
+//!     db: Box<DBConnection>,
+//! }
+//! # impl Repository for RepositoryImpl {}
+//! ```
+//!
+//! To stop calling a provider which keeps failing, wrap it in a [`CircuitBreakerProvider`]. After a
+//! number of consecutive failures, the provider fails fast until a cooldown has passed. The
+//! breaker's state is kept in a [`CircuitBreaker`] component in the module.
+//!
+//! ## Pooling services
+//! Services which are expensive to create but cheap to reset (ex. large buffers or compiled
+//! templates) can be reused by wrapping their provider in a [`PooledProvider`]. The provided
+//! service is a [`Pooled`] guard, which [`Reset`]s the service and returns it to the module's
+//! [`ProviderPool`] component when dropped. Services provided within a [`Scope`] only return to
+//! the pool once the scope has ended.
+//!
 //! ## Overriding providers
 //! Like components, you can override the implementation of a provider during the module build.
 //! Overriding a provider is done by passing a [`Provider::provide`]-like function to
@@ -348,6 +396,9 @@
 //! # }
 //! ```
 //!
+//! In tests, the `MemoizedProvider` of the [`shaku_test`] crate makes a provider return the same
+//! instance on every call, so the test can assert on the interactions with a per-request service.
+//!
 //! ## The full example
 //! ```
 //! use shaku::{module, Component, HasComponent, HasProvider, Interface, Module, Provider};
@@ -446,3 +497,12 @@
 //! [`Provider::provide`]: ../../trait.Provider.html#tymethod.provide
 //! [`HasProvider::provide`]: ../../trait.HasProvider.html#tymethod.provide
 //! [`with_provider_override`]: ../../struct.ModuleBuilder.html#method.with_provider_override
+//! [`shaku_test`]: https://crates.io/crates/shaku_test
+//! [`RetryProvider`]: ../../struct.RetryProvider.html
+//! [`CircuitBreakerProvider`]: ../../struct.CircuitBreakerProvider.html
+//! [`CircuitBreaker`]: ../../struct.CircuitBreaker.html
+//! [`PooledProvider`]: ../../struct.PooledProvider.html
+//! [`Pooled`]: ../../struct.Pooled.html
+//! [`Reset`]: ../../trait.Reset.html
+//! [`ProviderPool`]: ../../struct.ProviderPool.html
+//! [`Scope`]: ../../struct.Scope.html
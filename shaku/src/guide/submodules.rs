@@ -104,7 +104,22 @@
 //! implement `AuthModule`. It does this by adding `: AuthModule` after the name of the module.
 //! This is shorthand for the statement `impl AuthModule for AuthModuleImpl {}`.
 //!
+//! In tests, a submodule can be replaced after the builder is created via
+//! [`with_submodule_override`], ex.
+//! `RootModule::builder(auth_module).with_submodule_override::<dyn AuthModule>(fake_auth_module)`.
+//!
+//! Services which the root module does not re-export can still be reached explicitly via
+//! [`SubmodulePath`], ex. `root_module.submodule::<dyn AuthModule>().resolve_ref()`. This is
+//! meant for diagnostics or admin tools, since the caller depends on the module tree.
+//!
+//! Each provider used from a submodule is also available as a [`Factory`] component. For example,
+//! a component in `RootModule` could inject `Arc<dyn Factory<dyn Session>>` if `AuthModule`
+//! provided `dyn Session`, and call `create` whenever it needs a new session.
+//!
 //! [getting started guide]: ../index.html
+//! [`Factory`]: ../../trait.Factory.html
 //! [`Module`]: ../../trait.Module.html
 //! [module macro]: ../../macro.module.html
 //! [`ModuleBuilder`]: ../../struct.ModuleBuilder.html
+//! [`SubmodulePath`]: ../../trait.SubmodulePath.html
+//! [`with_submodule_override`]: ../../struct.ModuleBuilder.html#method.with_submodule_override
@@ -0,0 +1,94 @@
+//! Tracing the method calls of components
+
+use crate::{Component, Interface, Module, ModuleBuildContext};
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// Wraps an implementation of the interface in one which traces each method call. This is
+/// implemented for `dyn Trait` by the `instrument_interface` attribute, and used by components
+/// marked with `#[instrument]` in a module (see [`Instrumented`]).
+///
+/// [`Instrumented`]: struct.Instrumented.html
+pub trait Instrument: Interface {
+    /// Wrap the implementation in one which traces its method calls
+    fn instrument(inner: Arc<Self>) -> Box<Self>;
+}
+
+/// The component `C`, with its interface wrapped via [`Instrument`]. The [`module`] macro
+/// registers components marked with `#[instrument]` as this component, ex.
+/// `components = [#[instrument] LoggerImpl]`.
+///
+/// The parameters are still set on `C` (ex. `with_component_parameters::<LoggerImpl>`), and the
+/// module and `C`'s dependents share the same wrapper. Overridden components are not wrapped.
+///
+/// This is enabled by the `instrument` feature.
+///
+/// # Example
+/// ```
+/// use shaku::{instrument_interface, module, Component, HasComponent, Interface};
+///
+/// #[instrument_interface]
+/// trait Greeter: Interface {
+///     fn greet(&self, name: &str) -> String;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Greeter)]
+/// struct GreeterImpl;
+///
+/// impl Greeter for GreeterImpl {
+///     fn greet(&self, name: &str) -> String {
+///         format!("Hello, {}!", name)
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [#[instrument] GreeterImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let greeter: &dyn Greeter = module.resolve_ref();
+///
+/// // Runs in a "greet" span, with "enter" and "exit" events
+/// assert_eq!(greeter.greet("Alice"), "Hello, Alice!");
+/// # }
+/// ```
+///
+/// [`Instrument`]: trait.Instrument.html
+/// [`module`]: macro.module.html
+pub struct Instrumented<C>(PhantomData<C>);
+
+impl<M: Module, C: Component<M>> Component<M> for Instrumented<C>
+where
+    C::Interface: Instrument,
+{
+    type Interface = C::Interface;
+    type Parameters = ();
+    const EMPTY_PARAMETERS: bool = true;
+    const ALWAYS_REBUILD: bool = C::ALWAYS_REBUILD;
+
+    fn build(context: &mut ModuleBuildContext<M>, _: ()) -> Box<Self::Interface> {
+        let parameters = context.take_parameters::<C>();
+        let inner = C::build_shared(context, parameters);
+
+        C::Interface::instrument(inner)
+    }
+
+    fn post_build(context: &mut ModuleBuildContext<M>, component: &Arc<Self::Interface>) {
+        C::post_build(context, component)
+    }
+
+    fn dependencies() -> Vec<&'static str> {
+        C::dependencies()
+    }
+
+    fn parameter_sources() -> Vec<&'static str> {
+        C::parameter_sources()
+    }
+}
@@ -0,0 +1,75 @@
+/// Create an interface trait for a trait which doesn't inherit [`Interface`], such as a trait from
+/// another crate. The generated trait inherits both traits and is implemented for every type
+/// which implements the foreign trait, so `dyn MyInterface` can be used as a component interface.
+///
+/// The generated trait has an `as_foreign` method, and `dyn MyInterface` derefs to the foreign
+/// trait object, so the service can be passed to code which expects the foreign trait.
+///
+/// # Example
+/// ```
+/// use shaku::{interface_adapter, module, Component, HasComponent};
+/// use std::fmt::Display;
+/// use std::sync::Arc;
+///
+/// interface_adapter!(
+///     /// A greeting, which is any displayable value
+///     dyn Display as pub Greeting
+/// );
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Greeting)]
+/// struct HelloWorld;
+///
+/// impl Display for HelloWorld {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "Hello, world!")
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [HelloWorld],
+///         providers = []
+///     }
+/// }
+///
+/// fn print(display: &dyn Display) -> String {
+///     display.to_string()
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let greeting: Arc<dyn Greeting> = module.resolve();
+///
+/// assert_eq!(greeting.to_string(), "Hello, world!");
+/// assert_eq!(print(&**greeting), "Hello, world!");
+/// # }
+/// ```
+///
+/// [`Interface`]: trait.Interface.html
+#[macro_export]
+macro_rules! interface_adapter {
+    ($(#[$attributes:meta])* dyn $foreign:path as $visibility:vis $name:ident) => {
+        $(#[$attributes])*
+        $visibility trait $name: $foreign + $crate::Interface {
+            /// Use the service as the adapted trait object
+            fn as_foreign(&self) -> &(dyn $foreign + 'static);
+        }
+
+        impl<T: $foreign + $crate::Interface> $name for T {
+            fn as_foreign(&self) -> &(dyn $foreign + 'static) {
+                self
+            }
+        }
+
+        // Newer compilers coerce to the supertrait directly, but older ones need this impl
+        #[allow(unknown_lints, deref_into_dyn_supertrait)]
+        impl ::core::ops::Deref for dyn $name {
+            type Target = dyn $foreign;
+
+            fn deref(&self) -> &Self::Target {
+                self.as_foreign()
+            }
+        }
+    };
+}
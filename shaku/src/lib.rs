@@ -9,33 +9,167 @@
 //! By default shaku is thread-safe and exposes macros, but these can be disabled by opting out of
 //! the following features:
 //!
-//! - `thread_safe`: Requires components to be `Send + Sync`
+//! - `std`: Links the standard library. Without it, shaku is `#![no_std]` and only requires
+//!   `alloc` (see [Without std](#without-std)).
+//! - `thread_safe`: Requires components and modules to be `Send + Sync` (see [`ThreadSafe`]),
+//!   unless they are stored in a [`ThreadBound`]
 //! - `derive`: Uses the `shaku_derive` crate to provide proc-macro derives of `Component` and
 //!   `Provider`, and the `module` macro.
+//! - `providers`: Allows providers in the derives and macros (and enables `derive`). Without it,
+//!   `#[derive(Provider)]`, modules which list providers, and `#[as_provider]` components are
+//!   compile errors, so modules of component-only builds don't contain any provider code.
+//!
+//! The following features are optional:
+//!
+//! - `extras`: Exposes the [`extras`] module, which contains reusable components such as an
+//!   [`EventBus`] and a [`Clock`] which can be replaced in tests.
+//! - `metrics`: Counts how often each service of a module is resolved or provided. See
+//!   [`ResolveMetrics`].
+//! - `serde_parameters`: Allows snapshotting and restoring component parameters via serde. See
+//!   [`ModuleBuilder::parameters_snapshot`].
+//! - `random`: Adds ID and random number generators (and enables `extras`), such as
+//!   [`UuidV4Generator`], with deterministic versions for tests.
+//! - `tokio_runtime`: Adds the [`RuntimeHandle`] component (and enables `extras`), which spawns
+//!   tasks on a tokio runtime.
+//! - `lifecycle`: Adds the [`LifecycleRegistry`] component (and enables `tokio_runtime`), which
+//!   runs the async startup and shutdown hooks of [`Stoppable`] components, and can shut them
+//!   down on ctrl-c.
+//! - `http_client`: Adds the [`PooledHttpClient`] component (and enables `extras`), which shares
+//!   one reqwest connection pool with the module, and the [`AuthenticatedClient`] provider.
+//!   reqwest requires a newer Rust version than shaku's minimum supported version.
+//! - `instrument`: Adds the [`instrument_interface`] attribute, which generates a wrapper that
+//!   traces the method calls of an interface with [tracing], and [`Instrumented`] components,
+//!   which install the wrapper around a component via `#[instrument]` in the [`module`] macro.
+//! - `thread_confinement`: When `thread_safe` is turned off, panics if a module's services are
+//!   used on a different thread than the one which built the module. This is meant for debug
+//!   builds, to catch modules which were moved across threads via `unsafe` code.
+//! - `usage_tracking`: Adds [`ModuleBuilder::build_with_usage_tracking`], which records the
+//!   services of a module which are used, to find registrations which are no longer needed.
+//! - `skip_cycle_checks`: Skips tracking the resolution chain while building modules in release
+//!   builds (without `debug_assertions`), which detects circular dependencies at runtime. A
+//!   circular dependency then overflows the stack instead of panicking with the chain, so this is
+//!   meant for applications whose modules are built in debug builds (ex. in tests) first. The
+//!   chain is still tracked if the build is profiled or tracks usage.
+//! - `mock`: Allows `#[shaku(mock)]` on components, which implements `Component` for the
+//!   interface's [mockall] mock in tests. See the [getting started guide].
+//!
+//! # Async interfaces
+//! Traits with `async fn` methods are not object safe, so they can't be used as interfaces
+//! directly. Wrap the trait and its implementations in [`async_interface!`], which turns each
+//! `async fn` into a method returning a [`BoxFuture`] (which is `Send` when `thread_safe` is on).
+//!
+//! # Without std
+//! Turning off the default features (and the `std` feature) makes shaku `#![no_std]`, for use on
+//! embedded targets with an allocator. Components, providers, modules and the derives keep
+//! working, but:
+//!
+//! - Every feature except `derive` and `mock` requires `std`, including `thread_safe`. Modules are
+//!   never `Send` or `Sync` without std.
+//! - Providers return `Box<dyn shaku::Error>`. With std this is [`std::error::Error`], but without
+//!   std it is any `Debug + Display` type, so errors don't convert via `?` and must be boxed
+//!   explicitly (ex. `Box::new("connection refused")`).
+//! - [`ParameterSource`], [`CancellationToken`], [`ProvideContext`], [`Scope`], [`ThreadBound`],
+//!   [`RetryPolicy`], [`CircuitBreaker`], [`ProviderPool`], [`verify`] and
+//!   [`ModuleBuilder::build_profiled`] are not available.
+//! - Deprecated components are not reported when resolved.
 //!
 //! [Rocket]: https://rocket.rs
 //! [`shaku_rocket`]: https://crates.io/crates/shaku_rocket
 //! [getting started guide]: guide/index.html
+//! [`extras`]: extras/index.html
+//! [`EventBus`]: extras/struct.EventBus.html
+//! [`Clock`]: extras/trait.Clock.html
+//! [`RuntimeHandle`]: extras/struct.RuntimeHandle.html
+//! [`LifecycleRegistry`]: extras/struct.LifecycleRegistry.html
+//! [`Stoppable`]: extras/trait.Stoppable.html
+//! [`UuidV4Generator`]: extras/struct.UuidV4Generator.html
+//! [`PooledHttpClient`]: extras/struct.PooledHttpClient.html
+//! [`AuthenticatedClient`]: extras/struct.AuthenticatedClient.html
+//! [`ResolveMetrics`]: trait.ResolveMetrics.html
+//! [mockall]: https://crates.io/crates/mockall
+//! [tracing]: https://crates.io/crates/tracing
+//! [`instrument_interface`]: attr.instrument_interface.html
+//! [`Instrumented`]: struct.Instrumented.html
+//! [`module`]: macro.module.html
+//! [`ThreadBound`]: struct.ThreadBound.html
+//! [`ThreadSafe`]: trait.ThreadSafe.html
+//! [`ModuleBuilder::parameters_snapshot`]: struct.ModuleBuilder.html#method.parameters_snapshot
+//! [`std::error::Error`]: https://doc.rust-lang.org/std/error/trait.Error.html
+//! [`ParameterSource`]: trait.ParameterSource.html
+//! [`CancellationToken`]: struct.CancellationToken.html
+//! [`ProvideContext`]: struct.ProvideContext.html
+//! [`Scope`]: struct.Scope.html
+//! [`RetryPolicy`]: struct.RetryPolicy.html
+//! [`CircuitBreaker`]: struct.CircuitBreaker.html
+//! [`ProviderPool`]: struct.ProviderPool.html
+//! [`verify`]: fn.verify.html
+//! [`ModuleBuilder::build_with_usage_tracking`]: struct.ModuleBuilder.html#method.build_with_usage_tracking
+//! [`ModuleBuilder::build_profiled`]: struct.ModuleBuilder.html#method.build_profiled
+//! [`async_interface!`]: macro.async_interface.html
+//! [`BoxFuture`]: type.BoxFuture.html
 
 // This lint is ignored because proc-macros aren't allowed in statement position
 // (at least until 1.45). Removing the main function makes rustdoc think the
 // module macro is a statement instead of top-level item.
 // This can be removed once the MSRV is at least 1.45.
 #![allow(clippy::needless_doctest_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 // Modules
 #[macro_use]
 mod trait_alias;
+#[cfg(feature = "std")]
+mod cancellation;
+#[cfg(feature = "std")]
+mod circuit_breaker;
 mod component;
+mod deprecation;
+mod error;
+mod future;
+#[cfg(feature = "instrument")]
+mod instrument;
+#[macro_use]
+mod interface_adapter;
+#[cfg(feature = "thread_safe")]
+mod global;
+mod lock;
 mod module;
+mod no_default;
+#[cfg(feature = "std")]
+mod parameter_source;
 mod parameters;
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "std")]
+mod provide_context;
 mod provider;
+mod resolve_many;
+#[cfg(feature = "std")]
+mod retry;
+#[cfg(feature = "std")]
+mod scope;
+#[cfg(feature = "std")]
+mod thread_bound;
+#[cfg(feature = "thread_confinement")]
+mod thread_confinement;
+#[cfg(feature = "std")]
+mod verify;
 
+pub mod debug;
+#[cfg(feature = "extras")]
+pub mod extras;
 pub mod guide;
 
 // Reexport proc macros
+#[cfg(all(feature = "derive", feature = "instrument"))]
+pub use shaku_derive::instrument_interface;
 #[cfg(feature = "derive")]
-pub use {shaku_derive::module, shaku_derive::Component, shaku_derive::Provider};
+pub use {
+    shaku_derive::async_interface, shaku_derive::closure_interface, shaku_derive::module,
+    shaku_derive::module_interface, shaku_derive::Component, shaku_derive::Provider,
+};
 
 // Reexport OnceCell to support lazy components
 #[doc(hidden)]
@@ -45,5 +179,37 @@ pub use once_cell::sync::OnceCell;
 #[cfg(not(feature = "thread_safe"))]
 pub use once_cell::unsync::OnceCell;
 
+// Reexport the lock used to support lazy components
+#[doc(hidden)]
+pub use crate::lock::Lock;
+
+// Reexport tracing for the instrumented interfaces, since the calling crate might not depend on it
+#[doc(hidden)]
+#[cfg(feature = "instrument")]
+pub use tracing as __tracing;
+
+// Reexport alloc types for the derives, since the calling crate might not link std
+#[doc(hidden)]
+pub mod __alloc {
+    pub use alloc::boxed::Box;
+    pub use alloc::sync::Arc;
+    pub use alloc::vec;
+    pub use alloc::vec::Vec;
+}
+
 // Expose a flat module structure
-pub use crate::{component::*, module::*, provider::*};
+#[cfg(feature = "thread_safe")]
+pub use crate::global::*;
+#[cfg(feature = "instrument")]
+pub use crate::instrument::*;
+#[cfg(feature = "thread_confinement")]
+pub use crate::thread_confinement::*;
+#[cfg(feature = "std")]
+pub use crate::{
+    cancellation::*, circuit_breaker::*, parameter_source::*, pool::*, provide_context::*,
+    retry::*, scope::*, thread_bound::*, verify::*,
+};
+pub use crate::{
+    component::*, deprecation::*, error::*, future::*, module::*, no_default::*, provider::*,
+    resolve_many::*,
+};
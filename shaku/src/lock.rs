@@ -0,0 +1,47 @@
+//! A lock which does not require std
+
+#[cfg(not(feature = "std"))]
+use core::cell::{RefCell, RefMut};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, MutexGuard};
+
+/// Guards data which is shared by a module, such as the build context of lazy components. This is
+/// a `Mutex` when the `std` feature is on. Without std modules are never thread safe (the
+/// `thread_safe` feature requires `std`), so a `RefCell` is used instead.
+#[doc(hidden)]
+pub struct Lock<T> {
+    #[cfg(feature = "std")]
+    inner: Mutex<T>,
+    #[cfg(not(feature = "std"))]
+    inner: RefCell<T>,
+}
+
+impl<T> Lock<T> {
+    /// Create the lock
+    #[cfg(feature = "std")]
+    pub fn new(value: T) -> Self {
+        Lock {
+            inner: Mutex::new(value),
+        }
+    }
+
+    /// Create the lock
+    #[cfg(not(feature = "std"))]
+    pub fn new(value: T) -> Self {
+        Lock {
+            inner: RefCell::new(value),
+        }
+    }
+
+    /// Lock the data. Panics if a previous holder of the lock panicked.
+    #[cfg(feature = "std")]
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        self.inner.lock().unwrap()
+    }
+
+    /// Lock the data. Panics if the data is already locked.
+    #[cfg(not(feature = "std"))]
+    pub fn lock(&self) -> RefMut<'_, T> {
+        self.inner.borrow_mut()
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{HasComponent, HasProvider, Interface};
+
+/// Assert that the module has a component with the interface. This does nothing at runtime; if the
+/// module does not have the component, the call fails to compile with an error pointing at the
+/// assertion. Use it in a test or a `const` item, so a refactor which drops an interface from a
+/// module fails at an explicit check instead of at a distant use of the module.
+///
+/// # Example
+/// ```
+/// use shaku::{assert_module_has, module, Component, Interface};
+///
+/// trait Logger: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Logger)]
+/// struct LoggerImpl;
+/// impl Logger for LoggerImpl {}
+///
+/// module! {
+///     MyModule {
+///         components = [LoggerImpl],
+///         providers = []
+///     }
+/// }
+///
+/// // Checked when the crate is compiled
+/// const _: fn() = assert_module_has::<MyModule, dyn Logger>;
+///
+/// # fn main() {
+/// // Or in a test
+/// assert_module_has::<MyModule, dyn Logger>();
+/// # }
+/// ```
+pub fn assert_module_has<M, I>()
+where
+    M: HasComponent<I>,
+    I: Interface + ?Sized,
+{
+}
+
+/// Assert that the module has a provider with the interface. Like [`assert_module_has`], this does
+/// nothing at runtime and fails to compile if the module does not have the provider.
+///
+/// # Example
+/// ```
+/// use shaku::{assert_module_provides, module, Provider};
+///
+/// trait Connection {}
+///
+/// #[derive(Provider)]
+/// #[shaku(interface = Connection)]
+/// struct ConnectionImpl;
+/// impl Connection for ConnectionImpl {}
+///
+/// module! {
+///     MyModule {
+///         components = [],
+///         providers = [ConnectionImpl]
+///     }
+/// }
+///
+/// const _: fn() = assert_module_provides::<MyModule, dyn Connection>;
+/// # fn main() {}
+/// ```
+///
+/// [`assert_module_has`]: fn.assert_module_has.html
+pub fn assert_module_provides<M, I>()
+where
+    M: HasProvider<I>,
+    I: ?Sized,
+{
+}
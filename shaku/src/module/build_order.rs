@@ -0,0 +1,72 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A component of a module, as the interface type name, whether the component is lazy, and the
+/// interface type names of its dependencies (see [`Component::dependencies`]).
+///
+/// [`Component::dependencies`]: trait.Component.html#method.dependencies
+#[doc(hidden)]
+pub type ComponentDependencies = (&'static str, bool, Vec<&'static str>);
+
+/// Access the order in which a module's components are built. Implemented by modules created via
+/// the [`module`] macro.
+///
+/// [`module`]: macro.module.html
+pub trait ModuleBuildOrder {
+    /// The interface type names of the components, in the order they are built by
+    /// `Module::build`. Each component is built after the components it depends on. Lazy
+    /// components are only listed if another component depends on them.
+    fn build_order() -> Vec<&'static str>;
+}
+
+/// Get the order in which the module's components are built. Components are built in the order
+/// they are listed in the module, but each component builds its dependencies first. Lazy
+/// components are only built during the module build if another component depends on them.
+///
+/// This is used by the [`ModuleBuildOrder`] impl which the [`module`] macro generates.
+///
+/// [`ModuleBuildOrder`]: trait.ModuleBuildOrder.html
+/// [`module`]: macro.module.html
+#[doc(hidden)]
+pub fn build_order(components: Vec<ComponentDependencies>) -> Vec<&'static str> {
+    let indices: BTreeMap<&'static str, usize> = components
+        .iter()
+        .enumerate()
+        .map(|(i, (interface, _, _))| (*interface, i))
+        .collect();
+    let mut visited = vec![false; components.len()];
+    let mut order = Vec::with_capacity(components.len());
+
+    for (i, (_, lazy, _)) in components.iter().enumerate() {
+        if !lazy {
+            visit(i, &components, &indices, &mut visited, &mut order);
+        }
+    }
+
+    order
+}
+
+/// Add the component to the build order after its dependencies. Dependencies which are not
+/// components of the module (ex. submodule components) are already built, so they are skipped.
+fn visit(
+    index: usize,
+    components: &[ComponentDependencies],
+    indices: &BTreeMap<&'static str, usize>,
+    visited: &mut [bool],
+    order: &mut Vec<&'static str>,
+) {
+    if visited[index] {
+        return;
+    }
+    visited[index] = true;
+
+    let (interface, _, dependencies) = &components[index];
+    for dependency in dependencies {
+        if let Some(&dependency_index) = indices.get(dependency) {
+            visit(dependency_index, components, indices, visited, order);
+        }
+    }
+
+    order.push(interface);
+}
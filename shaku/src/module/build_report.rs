@@ -0,0 +1,108 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::time::Duration;
+
+/// Timing information about a module build. See [`ModuleBuilder::build_profiled`].
+///
+/// Components are listed in the order they finished building, so dependencies are listed before
+/// the components which use them. Lazy components which were not built during module build are not
+/// included.
+///
+/// [`ModuleBuilder::build_profiled`]: struct.ModuleBuilder.html#method.build_profiled
+#[derive(Clone, Debug)]
+pub struct BuildReport {
+    pub(crate) components: Vec<ComponentBuildTiming>,
+    pub(crate) total_duration: Duration,
+}
+
+impl BuildReport {
+    /// The components which were built, along with their timing information
+    pub fn components(&self) -> &[ComponentBuildTiming] {
+        &self.components
+    }
+
+    /// The time it took to build the whole module
+    pub fn total_duration(&self) -> Duration {
+        self.total_duration
+    }
+
+    /// Sort the components by the time spent in their own constructor (excluding dependencies),
+    /// slowest first.
+    pub fn sort_by_own_duration(&mut self) {
+        self.components
+            .sort_by(|a, b| b.own_duration.cmp(&a.own_duration));
+    }
+
+    /// Sort the components by the time it took to build them (including dependencies), slowest
+    /// first.
+    pub fn sort_by_duration(&mut self) {
+        self.components.sort_by(|a, b| b.duration.cmp(&a.duration));
+    }
+
+    /// Sort the components by their dependency depth, deepest first.
+    pub fn sort_by_depth(&mut self) {
+        self.components.sort_by(|a, b| b.depth.cmp(&a.depth));
+    }
+}
+
+impl fmt::Display for BuildReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Built {} component(s) in {:?}",
+            self.components.len(),
+            self.total_duration
+        )?;
+
+        for component in &self.components {
+            writeln!(
+                f,
+                "  {} (own: {:?}, total: {:?}, depth: {})",
+                component.component_type_name,
+                component.own_duration,
+                component.duration,
+                component.depth
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Timing information about a single component build
+#[derive(Clone, Debug)]
+pub struct ComponentBuildTiming {
+    pub(crate) component_type_name: &'static str,
+    pub(crate) interface_type_name: &'static str,
+    pub(crate) duration: Duration,
+    pub(crate) own_duration: Duration,
+    pub(crate) depth: usize,
+}
+
+impl ComponentBuildTiming {
+    /// The type name of the component implementation
+    pub fn component_type_name(&self) -> &'static str {
+        self.component_type_name
+    }
+
+    /// The type name of the component's interface
+    pub fn interface_type_name(&self) -> &'static str {
+        self.interface_type_name
+    }
+
+    /// The time it took to build the component, including building its dependencies
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The time it took to build the component, excluding building its dependencies
+    pub fn own_duration(&self) -> Duration {
+        self.own_duration
+    }
+
+    /// How deep in the dependency chain the component was built. Components built directly by the
+    /// module have a depth of 0, their dependencies have a depth of 1, etc.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
@@ -0,0 +1,150 @@
+use crate::module::ComponentMap;
+use crate::{Component, Interface, Lock, Module};
+use alloc::sync::Arc;
+use core::fmt;
+
+/// A component which can receive new parameters (`P`, the component's `Parameters` type) after it
+/// was built, via [`ReconfigurableModule::apply_parameters`] on modules created by the [`module`]
+/// macro. The
+/// component stays the same instance, so any settings which can change must be kept behind
+/// interior mutability (ex. a `RwLock`).
+///
+/// Derived components opt in via `#[shaku(reconfigurable)]`, which registers the component with
+/// its module when it is built. The component must be thread safe, since the module keeps a
+/// reference to it.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, HasComponent, Interface, Reconfigurable, ReconfigurableModule};
+/// use std::sync::RwLock;
+///
+/// trait Greeter: Interface {
+///     fn greet(&self) -> String;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Greeter, reconfigurable)]
+/// struct GreeterImpl {
+///     #[shaku(default = RwLock::new("Hello".to_string()))]
+///     greeting: RwLock<String>,
+/// }
+///
+/// impl Greeter for GreeterImpl {
+///     fn greet(&self) -> String {
+///         format!("{}, world!", self.greeting.read().unwrap())
+///     }
+/// }
+///
+/// impl Reconfigurable<GreeterImplParameters> for GreeterImpl {
+///     fn reconfigure(&self, params: GreeterImplParameters) {
+///         *self.greeting.write().unwrap() = params.greeting.into_inner().unwrap();
+///     }
+/// }
+///
+/// module! {
+///     GreetingModule {
+///         components = [GreeterImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = GreetingModule::builder().build();
+/// let greeter: &dyn Greeter = module.resolve_ref();
+/// assert_eq!(greeter.greet(), "Hello, world!");
+///
+/// module.apply_parameters::<GreeterImpl>(GreeterImplParameters {
+///     greeting: RwLock::new("Goodbye".to_string()),
+/// });
+/// assert_eq!(greeter.greet(), "Goodbye, world!");
+/// # }
+/// ```
+///
+/// [`ReconfigurableModule::apply_parameters`]: trait.ReconfigurableModule.html#tymethod.apply_parameters
+/// [`module`]: macro.module.html
+pub trait Reconfigurable<P>: Interface {
+    /// Apply new parameters to the component
+    fn reconfigure(&self, params: P);
+}
+
+/// Send new parameters to the live instances of a module's [`Reconfigurable`] components.
+/// Implemented by modules created via the [`module`] macro.
+///
+/// [`Reconfigurable`]: trait.Reconfigurable.html
+/// [`module`]: macro.module.html
+pub trait ReconfigurableModule: Module + Sized {
+    /// Send new parameters to the live instance of a reconfigurable component. Returns false if
+    /// the component has not been built, ex. because it was overridden or is lazy and was not
+    /// resolved yet.
+    fn apply_parameters<C>(&self, params: C::Parameters) -> bool
+    where
+        C: Component<Self> + Reconfigurable<<C as Component<Self>>::Parameters>;
+}
+
+/// The reconfigurable components of a module, which receive the parameters passed to
+/// `ReconfigurableModule::apply_parameters`. Components register themselves while they are built via
+/// [`ModuleBuildContext::watch_parameters`]. Lazy components are registered once they are
+/// built, and clones of a module share their watcher.
+///
+/// The map of components is only allocated once a component registers, or the module needs to
+/// share the watcher with the lazy components it builds later.
+///
+/// [`ModuleBuildContext::watch_parameters`]: struct.ModuleBuildContext.html#method.watch_parameters
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct ConfigWatcher {
+    components: Option<Arc<Lock<ComponentMap>>>,
+}
+
+impl ConfigWatcher {
+    pub(crate) fn new() -> Self {
+        ConfigWatcher { components: None }
+    }
+
+    /// The registered components, allocating the map if no component registered yet
+    fn components(&mut self) -> &Arc<Lock<ComponentMap>> {
+        self.components
+            .get_or_insert_with(|| Arc::new(Lock::new(ComponentMap::new())))
+    }
+
+    /// Register the live instance of the component
+    pub(crate) fn watch<C: Reconfigurable<P>, P>(&mut self, component: &Arc<C>) {
+        self.components()
+            .lock()
+            .insert::<Arc<C>>(Arc::clone(component));
+    }
+
+    /// Clone the watcher, so that components registered on either one are visible to both
+    pub(crate) fn share(&mut self) -> ConfigWatcher {
+        self.components();
+        self.clone()
+    }
+
+    /// Send the parameters to the live instance of the component. Returns false if the component
+    /// has not been built, ex. because it was overridden or is lazy and was not resolved yet.
+    pub fn apply<M, C>(&self, params: C::Parameters) -> bool
+    where
+        M: Module,
+        C: Component<M> + Reconfigurable<<C as Component<M>>::Parameters>,
+    {
+        // Release the lock first, in case the component applies parameters to other components
+        let component = self
+            .components
+            .as_ref()
+            .and_then(|components| components.lock().get::<Arc<C>>().cloned());
+
+        match component {
+            Some(component) => {
+                component.reconfigure(params);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigWatcher").finish()
+    }
+}
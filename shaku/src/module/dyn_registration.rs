@@ -0,0 +1,119 @@
+use crate::{ComponentFn, Interface, Module, ModuleBuildContext};
+use alloc::boxed::Box;
+use core::any::{type_name, Any, TypeId};
+use core::fmt::{self, Debug};
+
+/// The build function of a [`DynRegistration`]. It returns the component as a `Box<I>`, boxed
+/// again as a `Box<dyn Any>`.
+///
+/// [`DynRegistration`]: struct.DynRegistration.html
+#[cfg(not(feature = "thread_safe"))]
+pub type DynBuildFn<M> = Box<dyn FnOnce(&mut ModuleBuildContext<M>) -> Box<dyn Any>>;
+/// The build function of a [`DynRegistration`]. It returns the component as a `Box<I>`, boxed
+/// again as a `Box<dyn Any>`.
+///
+/// [`DynRegistration`]: struct.DynRegistration.html
+#[cfg(feature = "thread_safe")]
+pub type DynBuildFn<M> =
+    Box<dyn (FnOnce(&mut ModuleBuildContext<M>) -> Box<dyn Any>) + Send + Sync>;
+
+/// A component implementation registered by its interface's type id, for wiring which is generated
+/// at build time (ex. by a `build.rs` from a schema) instead of written against each interface.
+/// Registrations are added via [`ModuleBuilder::with_registrations`], and are built like a
+/// component overridden via [`ModuleBuilder::with_component_override_fn`].
+///
+/// The build function must return the component as a `Box<I>` (where `I` is the interface with the
+/// type id), boxed again as a `Box<dyn Any>`. [`DynRegistration::new`] does this for a typed build
+/// function.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, DynBuildFn, DynRegistration, HasComponent, Interface};
+/// use std::any::{Any, TypeId};
+///
+/// trait Greeter: Interface {
+///     fn greet(&self) -> String;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Greeter)]
+/// struct DefaultGreeter;
+/// impl Greeter for DefaultGreeter {
+///     fn greet(&self) -> String {
+///         "Hello".to_string()
+///     }
+/// }
+///
+/// struct GeneratedGreeter;
+/// impl Greeter for GeneratedGreeter {
+///     fn greet(&self) -> String {
+///         "Hello from the schema".to_string()
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [DefaultGreeter],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// // ex. generated by a build script
+/// let build: DynBuildFn<MyModule> = Box::new(|_| {
+///     let component: Box<dyn Greeter> = Box::new(GeneratedGreeter);
+///     Box::new(component) as Box<dyn Any>
+/// });
+/// let registrations = vec![DynRegistration {
+///     interface: TypeId::of::<dyn Greeter>(),
+///     build,
+/// }];
+///
+/// let module = MyModule::builder().with_registrations(registrations).build();
+/// let greeter: &dyn Greeter = module.resolve_ref();
+/// assert_eq!(greeter.greet(), "Hello from the schema");
+/// # }
+/// ```
+///
+/// [`ModuleBuilder::with_registrations`]: struct.ModuleBuilder.html#method.with_registrations
+/// [`ModuleBuilder::with_component_override_fn`]: struct.ModuleBuilder.html#method.with_component_override_fn
+/// [`DynRegistration::new`]: #method.new
+pub struct DynRegistration<M: Module> {
+    /// The type id of the component's interface (ex. `TypeId::of::<dyn Greeter>()`)
+    pub interface: TypeId,
+    /// Builds the component
+    pub build: DynBuildFn<M>,
+}
+
+impl<M: Module> DynRegistration<M> {
+    /// Register a typed build function, so it can be listed with the other registrations
+    pub fn new<I: Interface + ?Sized>(build: ComponentFn<M, I>) -> Self {
+        DynRegistration {
+            interface: TypeId::of::<I>(),
+            build: Box::new(move |context| Box::new(build(context)) as Box<dyn Any>),
+        }
+    }
+}
+
+impl<M: Module> Debug for DynRegistration<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynRegistration")
+            .field("interface", &self.interface)
+            .finish()
+    }
+}
+
+/// Convert a registration's build function back into a typed component function. Panics when the
+/// component is built if the build function returned the wrong type.
+pub(crate) fn typed_build_fn<M: Module, I: Interface + ?Sized>(
+    build: DynBuildFn<M>,
+) -> ComponentFn<M, I> {
+    Box::new(move |context| match build(context).downcast::<Box<I>>() {
+        Ok(component) => *component,
+        Err(_) => panic!(
+            "The registration for {} did not build a Box<{}>",
+            type_name::<I>(),
+            type_name::<I>()
+        ),
+    })
+}
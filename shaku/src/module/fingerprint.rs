@@ -0,0 +1,106 @@
+use core::hash::{Hash, Hasher};
+
+/// Access the fingerprint of a module. Implemented by modules created via the [`module`] macro.
+///
+/// [`module`]: macro.module.html
+pub trait ModuleFingerprint {
+    /// A hash of the module's wiring and configuration: the implementation of each component and
+    /// provider, whether it was overridden, and the component parameters which implement `Hash`.
+    /// The hash is stable between runs, so it can be used as a cache key or compared in snapshot
+    /// tests.
+    ///
+    /// The fingerprint is only recorded if the module was built after calling
+    /// [`ModuleBuilder::record_fingerprint`], otherwise this returns `None`.
+    ///
+    /// [`ModuleBuilder::record_fingerprint`]: struct.ModuleBuilder.html#method.record_fingerprint
+    fn fingerprint(&self) -> Option<u64>;
+}
+
+/// Hashes the wiring and configuration of a module into its fingerprint. See
+/// [`ModuleFingerprint`].
+///
+/// [`ModuleFingerprint`]: trait.ModuleFingerprint.html
+///
+/// This is a 64-bit FNV-1a hash, which (unlike the std `DefaultHasher`) is stable between Rust
+/// versions and runs of the program. Type names are hashed instead of `TypeId`s, since `TypeId`s
+/// can change between builds.
+#[doc(hidden)]
+pub struct FingerprintHasher {
+    hash: u64,
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl FingerprintHasher {
+    /// Create a hasher with nothing hashed yet
+    pub fn new() -> Self {
+        FingerprintHasher {
+            hash: FNV_OFFSET_BASIS,
+        }
+    }
+
+    /// Hash a service of the module, as the implementation's type name and whether it was
+    /// overridden while building the module.
+    pub fn write_service(&mut self, implementation: &'static str, overridden: bool) {
+        implementation.hash(self);
+        overridden.hash(self);
+    }
+}
+
+impl Default for FingerprintHasher {
+    fn default() -> Self {
+        FingerprintHasher::new()
+    }
+}
+
+impl Hasher for FingerprintHasher {
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash ^= u64::from(*byte);
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+/// The parameters of a component, if they were set while building the module. The parameters are
+/// only part of the fingerprint if they implement `Hash`, which is checked via the
+/// [`HashParameters`] and [`SkipParameters`] traits (the impl for `&ParametersFingerprint` is only
+/// used if the parameters don't implement `Hash`).
+///
+/// [`HashParameters`]: trait.HashParameters.html
+/// [`SkipParameters`]: trait.SkipParameters.html
+#[doc(hidden)]
+pub struct ParametersFingerprint<'a, P>(pub Option<&'a P>);
+
+/// Hashes component parameters which implement `Hash`. See [`ParametersFingerprint`].
+///
+/// [`ParametersFingerprint`]: struct.ParametersFingerprint.html
+#[doc(hidden)]
+pub trait HashParameters {
+    fn hash_parameters(&self, hasher: &mut FingerprintHasher);
+}
+
+/// Skips component parameters which don't implement `Hash`. See [`ParametersFingerprint`].
+///
+/// [`ParametersFingerprint`]: struct.ParametersFingerprint.html
+#[doc(hidden)]
+pub trait SkipParameters {
+    fn hash_parameters(&self, hasher: &mut FingerprintHasher);
+}
+
+impl<'a, P: Hash> HashParameters for ParametersFingerprint<'a, P> {
+    fn hash_parameters(&self, hasher: &mut FingerprintHasher) {
+        // Parameters which were not set are hashed as `None`, since creating the default
+        // parameters may panic
+        self.0.hash(hasher);
+    }
+}
+
+impl<'a, 'b, P> SkipParameters for &'b ParametersFingerprint<'a, P> {
+    fn hash_parameters(&self, _: &mut FingerprintHasher) {}
+}
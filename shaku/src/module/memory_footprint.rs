@@ -0,0 +1,225 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem;
+
+/// Estimates the memory held by a module's components. Implemented by modules created via the
+/// [`module`] macro which are annotated with `#[shaku(memory_footprint)]`. See
+/// [`MemoryFootprint`] for an example.
+///
+/// [`module`]: macro.module.html
+/// [`MemoryFootprint`]: trait.MemoryFootprint.html
+pub trait ModuleMemoryFootprint {
+    /// Estimate the memory held by the components: the size of each component, plus the heap
+    /// usage reported by components whose interface implements [`MemoryFootprint`]. Lazy
+    /// components which have not been built are listed without a size, and the components of
+    /// submodules are not included.
+    ///
+    /// [`MemoryFootprint`]: trait.MemoryFootprint.html
+    fn memory_footprint(&self) -> MemoryReport;
+}
+
+/// Estimates the heap memory which a component holds alive, so it can be included in a module's
+/// [`MemoryReport`]. Implement this trait for a component and add it as a supertrait of the
+/// interface (ex. `trait Cache: Interface + MemoryFootprint`) to report the heap usage of the
+/// component. Components whose interface doesn't implement the trait only report their own size.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, Interface, MemoryFootprint, ModuleMemoryFootprint};
+///
+/// trait Cache: Interface + MemoryFootprint {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Cache)]
+/// struct CacheImpl {
+///     entries: Vec<u64>,
+/// }
+///
+/// impl Cache for CacheImpl {}
+///
+/// impl MemoryFootprint for CacheImpl {
+///     fn heap_size(&self) -> usize {
+///         self.entries.heap_size()
+///     }
+/// }
+///
+/// module! {
+///     #[shaku(memory_footprint)]
+///     MyModule {
+///         components = [CacheImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder()
+///     .with_component_parameters::<CacheImpl>(CacheImplParameters {
+///         entries: Vec::with_capacity(1024),
+///     })
+///     .build();
+/// let report = module.memory_footprint();
+///
+/// assert_eq!(report.components()[0].heap_size(), Some(1024 * 8));
+/// # }
+/// ```
+///
+/// [`MemoryReport`]: struct.MemoryReport.html
+pub trait MemoryFootprint {
+    /// The number of heap bytes owned by this value, not including the value itself
+    fn heap_size(&self) -> usize;
+}
+
+impl MemoryFootprint for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+/// Only counts the vector's buffer, not the heap memory owned by its elements
+impl<T> MemoryFootprint for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<T>()
+    }
+}
+
+/// The estimated memory usage of a module's components. See [`ModuleMemoryFootprint`].
+///
+/// [`ModuleMemoryFootprint`]: trait.ModuleMemoryFootprint.html
+#[derive(Clone, Debug, Default)]
+pub struct MemoryReport {
+    components: Vec<ComponentFootprint>,
+}
+
+/// The estimated memory usage of a component. See [`MemoryReport`].
+///
+/// [`MemoryReport`]: struct.MemoryReport.html
+#[derive(Clone, Debug)]
+pub struct ComponentFootprint {
+    interface: &'static str,
+    size: Option<usize>,
+    heap_size: Option<usize>,
+}
+
+impl ComponentFootprint {
+    /// The type name of the component's interface
+    pub fn interface(&self) -> &'static str {
+        self.interface
+    }
+
+    /// The size of the component value itself, or `None` if the component is lazy and has not been
+    /// built yet
+    pub fn size(&self) -> Option<usize> {
+        self.size
+    }
+
+    /// The heap usage reported via [`MemoryFootprint`], or `None` if the component's interface
+    /// doesn't implement it (or the component has not been built yet)
+    ///
+    /// [`MemoryFootprint`]: trait.MemoryFootprint.html
+    pub fn heap_size(&self) -> Option<usize> {
+        self.heap_size
+    }
+
+    /// The size of the component plus its reported heap usage
+    pub fn total(&self) -> usize {
+        self.size.unwrap_or(0) + self.heap_size.unwrap_or(0)
+    }
+}
+
+impl MemoryReport {
+    /// Create an empty report
+    #[doc(hidden)]
+    pub fn new() -> Self {
+        MemoryReport::default()
+    }
+
+    /// Add a component to the report. The size and heap size are `None` if the component has not
+    /// been built.
+    #[doc(hidden)]
+    pub fn push(&mut self, interface: &'static str, size: Option<usize>, heap_size: Option<usize>) {
+        self.components.push(ComponentFootprint {
+            interface,
+            size,
+            heap_size,
+        });
+    }
+
+    /// The components of the module, in the order they were listed in the module
+    pub fn components(&self) -> &[ComponentFootprint] {
+        &self.components
+    }
+
+    /// The estimated total memory held by the components. This is a lower bound, since components
+    /// which don't implement [`MemoryFootprint`] only count their own size.
+    ///
+    /// [`MemoryFootprint`]: trait.MemoryFootprint.html
+    pub fn total(&self) -> usize {
+        self.components.iter().map(ComponentFootprint::total).sum()
+    }
+}
+
+impl fmt::Display for MemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes in {} component(s):",
+            self.total(),
+            self.components.len()
+        )?;
+        for component in &self.components {
+            match (component.size, component.heap_size) {
+                (None, _) => write!(f, "\n- {}: not built", component.interface)?,
+                (Some(size), None) => write!(f, "\n- {}: {} bytes", component.interface, size)?,
+                (Some(size), Some(heap_size)) => write!(
+                    f,
+                    "\n- {}: {} bytes ({} inline, {} heap)",
+                    component.interface,
+                    size + heap_size,
+                    size,
+                    heap_size
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Gets the heap size of a component if its interface implements [`MemoryFootprint`]. The
+/// [`ReportHeapSize`] impl applies if it does, otherwise the [`UnknownHeapSize`] impl for
+/// `&FootprintProbe` is used.
+///
+/// [`MemoryFootprint`]: trait.MemoryFootprint.html
+/// [`ReportHeapSize`]: trait.ReportHeapSize.html
+/// [`UnknownHeapSize`]: trait.UnknownHeapSize.html
+#[doc(hidden)]
+pub struct FootprintProbe<'a, I: ?Sized>(pub &'a I);
+
+/// See [`FootprintProbe`].
+///
+/// [`FootprintProbe`]: struct.FootprintProbe.html
+#[doc(hidden)]
+pub trait ReportHeapSize {
+    fn heap_size(&self) -> Option<usize>;
+}
+
+/// See [`FootprintProbe`].
+///
+/// [`FootprintProbe`]: struct.FootprintProbe.html
+#[doc(hidden)]
+pub trait UnknownHeapSize {
+    fn heap_size(&self) -> Option<usize>;
+}
+
+impl<'a, I: MemoryFootprint + ?Sized> ReportHeapSize for FootprintProbe<'a, I> {
+    fn heap_size(&self) -> Option<usize> {
+        Some(self.0.heap_size())
+    }
+}
+
+impl<'a, 'b, I: ?Sized> UnknownHeapSize for &'b FootprintProbe<'a, I> {
+    fn heap_size(&self) -> Option<usize> {
+        None
+    }
+}
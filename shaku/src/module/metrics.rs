@@ -0,0 +1,169 @@
+use crate::ModuleServices;
+use crate::ServiceKind;
+use alloc::vec::Vec;
+use core::fmt;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Counts how often each service of a module is resolved or provided. Modules created via the
+/// [`module`] macro expose the counts via [`ResolveMetrics`]. Clones of a module share their
+/// counts.
+///
+/// [`module`]: macro.module.html
+/// [`ResolveMetrics`]: trait.ResolveMetrics.html
+#[doc(hidden)]
+#[derive(Clone, Default)]
+pub struct ResolveCounter {
+    counters: Arc<Counters>,
+}
+
+#[derive(Default)]
+struct Counters {
+    /// The services exposed by the module, in registration order
+    services: Vec<(&'static str, ServiceKind, AtomicU64)>,
+    /// The index of each service in `services`, by interface name
+    indices: HashMap<&'static str, usize>,
+}
+
+impl ResolveCounter {
+    /// Create a counter for each service exposed by the module
+    pub fn new<M: ModuleServices>() -> Self {
+        let mut counters = Counters::default();
+
+        for service in M::services().iter() {
+            let interface = service.interface_name();
+
+            if !counters.indices.contains_key(interface) {
+                counters.indices.insert(interface, counters.services.len());
+                counters
+                    .services
+                    .push((interface, service.kind(), AtomicU64::new(0)));
+            }
+        }
+
+        ResolveCounter {
+            counters: Arc::new(counters),
+        }
+    }
+
+    /// Record that the interface was resolved or provided from the module
+    pub fn record(&self, interface: &'static str) {
+        if let Some(&index) = self.counters.indices.get(interface) {
+            self.counters.services[index]
+                .2
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Get the current counts
+    pub fn metrics(&self) -> ModuleMetrics {
+        let services = self
+            .counters
+            .services
+            .iter()
+            .map(|(interface, kind, count)| (*interface, *kind, count.load(Ordering::Relaxed)))
+            .collect();
+
+        ModuleMetrics { services }
+    }
+}
+
+impl fmt::Debug for ResolveCounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolveCounter")
+            .field("services", &self.counters.services.len())
+            .finish()
+    }
+}
+
+/// Access the resolve counts of a module. Implemented by modules created via the [`module`] macro.
+///
+/// [`module`]: macro.module.html
+pub trait ResolveMetrics {
+    /// Count how often each service was resolved or provided
+    fn metrics(&self) -> ModuleMetrics;
+}
+
+/// How often each service of a module was resolved or provided, created via
+/// [`ResolveMetrics::metrics`] on modules created by the [`module`] macro. Useful to find hot
+/// services and services which are never used.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, HasComponent, Interface, ResolveMetrics};
+///
+/// trait Logger: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Logger)]
+/// struct LoggerImpl;
+/// impl Logger for LoggerImpl {}
+///
+/// module! {
+///     MyModule {
+///         components = [LoggerImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let _logger: &dyn Logger = module.resolve_ref();
+/// let _logger: &dyn Logger = module.resolve_ref();
+///
+/// let metrics = module.metrics();
+/// assert_eq!(metrics.count(std::any::type_name::<dyn Logger>()), 2);
+/// # }
+/// ```
+///
+/// [`ResolveMetrics::metrics`]: trait.ResolveMetrics.html#tymethod.metrics
+/// [`module`]: macro.module.html
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleMetrics {
+    services: Vec<(&'static str, ServiceKind, u64)>,
+}
+
+impl ModuleMetrics {
+    /// The interface type name, kind, and count of each service, in registration order
+    pub fn services(&self) -> &[(&'static str, ServiceKind, u64)] {
+        &self.services
+    }
+
+    /// How often the interface was resolved or provided. Returns 0 for unknown interfaces.
+    pub fn count(&self, interface: &str) -> u64 {
+        self.services
+            .iter()
+            .find(|(name, _, _)| *name == interface)
+            .map_or(0, |(_, _, count)| *count)
+    }
+
+    /// The total number of resolves and provides across every service
+    pub fn total(&self) -> u64 {
+        self.services.iter().map(|(_, _, count)| count).sum()
+    }
+
+    /// The type names of the interfaces which were never resolved or provided
+    pub fn unused(&self) -> Vec<&'static str> {
+        self.services
+            .iter()
+            .filter(|(_, _, count)| *count == 0)
+            .map(|(interface, _, _)| *interface)
+            .collect()
+    }
+}
+
+/// Lists the services from most to least used
+impl fmt::Display for ModuleMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut services: Vec<_> = self.services.iter().collect();
+        services.sort_by(|a, b| b.2.cmp(&a.2));
+
+        write!(f, "{} resolve(s):", self.total())?;
+        for (interface, kind, count) in services {
+            write!(f, "\n- {} {}: {}", kind, interface, count)?;
+        }
+
+        Ok(())
+    }
+}
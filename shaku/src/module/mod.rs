@@ -1,22 +1,92 @@
 //! This module handles building and resolving services.
 
+mod assertions;
+mod build_order;
+mod build_report;
+mod config_watcher;
+mod dyn_registration;
+mod fingerprint;
+mod memory_footprint;
+#[cfg(feature = "metrics")]
+mod metrics;
 mod module_build_context;
 mod module_builder;
+mod module_handle;
+mod module_services;
 mod module_traits;
+#[cfg(feature = "serde_parameters")]
+mod parameters_snapshot;
+mod reachability;
+mod rebuild;
+mod service_locator;
+mod strict_builder;
+mod superseded;
+#[cfg(not(feature = "std"))]
+mod type_map;
+mod usage_report;
 
+pub use self::assertions::{assert_module_has, assert_module_provides};
+pub use self::build_order::{build_order, ComponentDependencies, ModuleBuildOrder};
+pub use self::build_report::{BuildReport, ComponentBuildTiming};
+pub use self::config_watcher::{ConfigWatcher, Reconfigurable, ReconfigurableModule};
+pub(crate) use self::dyn_registration::typed_build_fn;
+pub use self::dyn_registration::{DynBuildFn, DynRegistration};
+pub use self::fingerprint::{
+    FingerprintHasher, HashParameters, ModuleFingerprint, ParametersFingerprint, SkipParameters,
+};
+pub use self::memory_footprint::{
+    ComponentFootprint, FootprintProbe, MemoryFootprint, MemoryReport, ModuleMemoryFootprint,
+    ReportHeapSize, UnknownHeapSize,
+};
+#[cfg(feature = "metrics")]
+pub use self::metrics::{ModuleMetrics, ResolveCounter, ResolveMetrics};
 pub use self::module_build_context::ModuleBuildContext;
-pub use self::module_builder::ModuleBuilder;
-pub use self::module_traits::{Module, ModuleInterface};
+pub use self::module_builder::{AnyOverride, ModuleBuilder};
+pub use self::module_handle::ModuleHandle;
+pub(crate) use self::module_handle::ModuleHandles;
+pub use self::module_services::{
+    ModuleServices, ServiceDescriptor, ServiceKind, ServiceRegistry, TryProvide,
+};
+pub use self::module_traits::{
+    ExportedComponent, ExportedProvider, HasSubmodule, Module, ModuleInterface, SubmodulePath,
+};
+#[cfg(feature = "serde_parameters")]
+pub use self::parameters_snapshot::{ParametersSnapshot, SnapshotError};
+pub use self::reachability::{
+    deny_unreachable_services, unreachable_services, ExportProbe, ExportsComponent,
+    ExportsProvider, NotExported, ServiceReachability,
+};
+pub use self::rebuild::{rebuilt_components, RebuildModule};
+pub use self::service_locator::{LocatedService, ServiceLocator};
+pub use self::strict_builder::{
+    ParametersComplete, ParametersMissing, ParametersSet, RequiredParameters,
+    SetComponentParameters, StrictModuleBuilder,
+};
+#[cfg(feature = "std")]
+pub use self::superseded::set_superseded_handler;
+pub use self::superseded::{report_superseded_services, SupersededHandler, SupersededService};
+#[cfg(not(feature = "usage_tracking"))]
+pub(crate) use self::usage_report::UsageTracker;
+#[cfg(feature = "usage_tracking")]
+pub use self::usage_report::{UsageReport, UsageTracker};
 
-#[cfg(not(feature = "thread_safe"))]
+#[cfg(all(feature = "std", not(feature = "thread_safe")))]
 type AnyType = dyn anymap2::any::Any;
 #[cfg(feature = "thread_safe")]
 type AnyType = dyn anymap2::any::Any + Send + Sync;
 
-#[cfg(not(feature = "thread_safe"))]
+#[cfg(all(feature = "std", not(feature = "thread_safe")))]
 type ParamAnyType = dyn anymap2::any::Any;
 #[cfg(feature = "thread_safe")]
 type ParamAnyType = dyn anymap2::any::Any + Send;
 
+#[cfg(feature = "std")]
 type ComponentMap = anymap2::Map<AnyType>;
+#[cfg(feature = "std")]
 type ParameterMap = anymap2::Map<ParamAnyType>;
+
+// anymap2 requires std, and without std modules are never thread safe
+#[cfg(not(feature = "std"))]
+type ComponentMap = self::type_map::TypeMap;
+#[cfg(not(feature = "std"))]
+type ParameterMap = self::type_map::TypeMap;
@@ -1,10 +1,18 @@
-use crate::module::{ComponentMap, ParameterMap};
+use crate::module::build_report::ComponentBuildTiming;
+use crate::module::config_watcher::{ConfigWatcher, Reconfigurable};
+use crate::module::usage_report::UsageTracker;
+use crate::module::{ComponentMap, ModuleHandles, ParameterMap};
 use crate::parameters::ComponentParameters;
-use crate::{Component, HasProvider, Provider, ProviderFn};
-use crate::{ComponentFn, Module};
-use std::any::{type_name, TypeId};
-use std::fmt::{self, Debug};
-use std::sync::Arc;
+use crate::{Component, HasProvider, ModuleProviderFn, ProvidedInterface, Provider, ProviderFn};
+use crate::{ComponentFn, HasSubmodule, Interface, Lock, Module, ModuleHandle, ModuleInterface};
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::{type_name, TypeId};
+use core::fmt::{self, Debug};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
 
 /// Builds a [`Module`] and its associated components. Build context, such as
 /// parameters and resolved components, are stored in this struct.
@@ -16,7 +24,25 @@ pub struct ModuleBuildContext<M: Module> {
     provider_overrides: ComponentMap,
     parameters: ParameterMap,
     submodules: M::Submodules,
+    data: ComponentMap,
     resolve_chain: Vec<ResolveStep>,
+    // Profiling needs a clock, so the profiler is never set without std
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    profiler: Option<Arc<Lock<BuildProfiler>>>,
+    usage: UsageTracker,
+    module_handles: ModuleHandles<M>,
+    config_watcher: ConfigWatcher,
+    record_fingerprint: bool,
+    record_graph: bool,
+}
+
+/// Collects component build timings. See `ModuleBuilder::build_profiled`.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+#[derive(Default)]
+pub(crate) struct BuildProfiler {
+    pub(crate) timings: Vec<ComponentBuildTiming>,
+    /// The time spent building dependencies of each component currently being built
+    dependency_durations: Vec<Duration>,
 }
 
 /// Tracks the current resolution chain. Used to detect circular dependencies.
@@ -36,12 +62,19 @@ impl Debug for ResolveStep {
 
 impl<M: Module> ModuleBuildContext<M> {
     /// Create the build context
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         parameters: ParameterMap,
         component_overrides: ComponentMap,
         component_fn_overrides: ComponentMap,
         provider_overrides: ComponentMap,
         submodules: M::Submodules,
+        data: ComponentMap,
+        profiler: Option<Arc<Lock<BuildProfiler>>>,
+        usage: UsageTracker,
+        module_handles: ModuleHandles<M>,
+        record_fingerprint: bool,
+        record_graph: bool,
     ) -> Self {
         ModuleBuildContext {
             resolved_components: component_overrides,
@@ -49,7 +82,14 @@ impl<M: Module> ModuleBuildContext<M> {
             provider_overrides,
             parameters,
             submodules,
+            data,
             resolve_chain: Vec::new(),
+            profiler,
+            usage,
+            module_handles,
+            config_watcher: ConfigWatcher::new(),
+            record_fingerprint,
+            record_graph,
         }
     }
 
@@ -58,9 +98,120 @@ impl<M: Module> ModuleBuildContext<M> {
         &self.submodules
     }
 
+    /// Access one of this module's submodules by its type (ex. `dyn AuthModule`), instead of
+    /// destructuring [`submodules`].
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, HasComponent, Interface, ModuleBuildContext};
+    /// # use std::sync::Arc;
+    /// #
+    /// # trait Logger: Interface {}
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Logger)]
+    /// # struct LoggerImpl;
+    /// # impl Logger for LoggerImpl {}
+    /// #
+    /// # module! {
+    /// #     LoggingModule {
+    /// #         components = [LoggerImpl],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    /// #
+    /// # module! {
+    /// #     RootModule {
+    /// #         components = [],
+    /// #         providers = [],
+    /// #
+    /// #         use LoggingModule {
+    /// #             components = [dyn Logger],
+    /// #             providers = []
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// fn logger(context: &mut ModuleBuildContext<RootModule>) -> Arc<dyn Logger> {
+    ///     context.submodule::<LoggingModule>().resolve()
+    /// }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// [`submodules`]: #method.submodules
+    pub fn submodule<S: ?Sized>(&self) -> &Arc<S>
+    where
+        M: HasSubmodule<S>,
+    {
+        M::submodule(&self.submodules)
+    }
+
+    /// Get the data of type `T` which was passed to [`ModuleBuilder::with_build_context_data`], if
+    /// any. This is useful in custom [`Component::build`] implementations.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, HasComponent, Interface, Module, ModuleBuildContext};
+    /// #
+    /// struct AppConfig {
+    ///     name: String,
+    /// }
+    ///
+    /// trait Greeter: Interface {
+    ///     fn greet(&self) -> String;
+    /// }
+    ///
+    /// struct GreeterImpl {
+    ///     app_name: String,
+    /// }
+    ///
+    /// impl Greeter for GreeterImpl {
+    ///     fn greet(&self) -> String {
+    ///         format!("Hello from {}", self.app_name)
+    ///     }
+    /// }
+    ///
+    /// impl<M: Module> Component<M> for GreeterImpl {
+    ///     type Interface = dyn Greeter;
+    ///     type Parameters = ();
+    ///
+    ///     fn build(context: &mut ModuleBuildContext<M>, _: ()) -> Box<dyn Greeter> {
+    ///         let config = context.data::<AppConfig>().expect("AppConfig is missing");
+    ///         Box::new(GreeterImpl {
+    ///             app_name: config.name.clone(),
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// module! {
+    ///     MyModule {
+    ///         components = [GreeterImpl],
+    ///         providers = []
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let module = MyModule::builder()
+    ///     .with_build_context_data(AppConfig {
+    ///         name: "my-app".to_string(),
+    ///     })
+    ///     .build();
+    ///
+    /// let greeter: &dyn Greeter = module.resolve_ref();
+    /// assert_eq!(greeter.greet(), "Hello from my-app");
+    /// # }
+    /// ```
+    ///
+    /// [`ModuleBuilder::with_build_context_data`]: struct.ModuleBuilder.html#method.with_build_context_data
+    /// [`Component::build`]: trait.Component.html#tymethod.build
+    pub fn data<T: Interface>(&self) -> Option<&T> {
+        self.data.get::<T>()
+    }
+
     /// Resolve a component by building it if it is not already resolved or
     /// overridden.
     pub fn build_component<C: Component<M>>(&mut self) -> Arc<C::Interface> {
+        self.record_dependency::<C::Interface>();
+
         // First check resolved components (which includes overridden component instances)
         self.resolved_components
             .get::<Arc<C::Interface>>()
@@ -71,6 +222,7 @@ impl<M: Module> ModuleBuildContext<M> {
                     .component_fn_overrides
                     .remove::<ComponentFn<M, C::Interface>>()?;
                 self.add_resolve_step::<C>();
+                let start = self.start_profiling();
 
                 // Build the component
                 let component = component_fn(self);
@@ -80,30 +232,45 @@ impl<M: Module> ModuleBuildContext<M> {
 
                 // Resolution was successful, pop the component off the chain
                 self.resolve_chain.pop();
+                self.finish_profiling::<C>(start);
 
                 Some(component)
             })
             // Third resolve the concrete component
             .unwrap_or_else(|| {
                 self.add_resolve_step::<C>();
+                let start = self.start_profiling();
 
                 // Build the component
-                let parameters = self
-                    .parameters
-                    .remove::<ComponentParameters<C, C::Parameters>>()
-                    .unwrap_or_default();
-                let component = C::build(self, parameters.value);
-                let component = Arc::from(component);
+                let parameters = self.take_parameters::<C>();
+                let component = C::build_shared(self, parameters);
                 self.resolved_components
                     .insert::<Arc<C::Interface>>(Arc::clone(&component));
 
                 // Resolution was successful, pop the component off the chain
                 self.resolve_chain.pop();
+                self.finish_profiling::<C>(start);
+
+                // Let the component wire itself into the rest of the module
+                C::post_build(self, &component);
 
                 component
             })
     }
 
+    /// Create a handle to the module as a module trait object (ex. `dyn MyModule`), which is set
+    /// once the module is built via [`ModuleBuilder::build_arc`]. The upcast function converts the
+    /// module into the trait object, usually via `|module| module`. Used by the `Component` derive
+    /// for fields marked with `#[shaku(inject_module)]`.
+    ///
+    /// [`ModuleBuilder::build_arc`]: struct.ModuleBuilder.html#method.build_arc
+    pub fn module_handle<T: ?Sized + ModuleInterface>(
+        &self,
+        upcast: fn(Arc<M>) -> Arc<T>,
+    ) -> ModuleHandle<T> {
+        self.module_handles.create(upcast)
+    }
+
     /// Get a provider function from the given provider impl, or an overridden
     /// one if configured during module build.
     pub fn provider_fn<P: Provider<M>>(&self) -> Arc<ProviderFn<M, P::Interface>>
@@ -116,7 +283,226 @@ impl<M: Module> ModuleBuildContext<M> {
             .unwrap_or_else(|| Arc::new(Box::new(P::provide)))
     }
 
+    /// Like [`provider_fn`], but stores the provider's own function as a plain function pointer
+    /// if it was not overridden. Used by the `module` macro.
+    ///
+    /// [`provider_fn`]: #method.provider_fn
+    #[doc(hidden)]
+    pub fn module_provider_fn<P: Provider<M>>(&self) -> ModuleProviderFn<M, P::Interface>
+    where
+        M: HasProvider<P::Interface>,
+    {
+        match self.provider_override::<P::Interface>() {
+            Some(provider_fn) => ModuleProviderFn::Override(provider_fn),
+            None => ModuleProviderFn::Provide(P::provide),
+        }
+    }
+
+    /// Get the provider override for a service of a submodule, if one was set on the builder. Used
+    /// by the `module` macro, so overrides on the module's builder also apply to the services which
+    /// the module provides via its submodules.
+    #[doc(hidden)]
+    pub fn provider_override<I: ?Sized + 'static>(&self) -> Option<Arc<ProviderFn<M, I>>> {
+        self.provider_overrides
+            .get::<Arc<ProviderFn<M, I>>>()
+            .cloned()
+    }
+
+    /// Create a service via the module's provider, to be owned by the component being built. Used by
+    /// the `Component` derive for fields marked with `#[shaku(provide)]`.
+    ///
+    /// Providers need the built module, so this only works for `#[lazy]` components of a module
+    /// built via [`ModuleBuilder::build_arc`]. The provider's injected components must not be lazy
+    /// components which are not built yet. Panics otherwise, or if the provider fails.
+    ///
+    /// [`ModuleBuilder::build_arc`]: struct.ModuleBuilder.html#method.build_arc
+    pub fn provide<I: ProvidedInterface + ?Sized>(&self) -> Box<I>
+    where
+        M: HasProvider<I>,
+    {
+        let component = self
+            .resolve_chain
+            .last()
+            .map_or("a component", |step| step.component_type_name);
+        let module = self.module_handles.module().unwrap_or_else(|| {
+            panic!(
+                "{} is provided to {} before the module is built. Components with \
+                 #[shaku(provide)] fields must be #[lazy], and the module built via \
+                 ModuleBuilder::build_arc.",
+                type_name::<I>(),
+                component
+            )
+        });
+
+        module.provide().unwrap_or_else(|error| {
+            panic!(
+                "Failed to provide {} to {}: {}",
+                type_name::<I>(),
+                component,
+                error
+            )
+        })
+    }
+
+    /// Record that the interface is injected into the component currently being built, if usage
+    /// tracking is enabled. Called by the `module` macro for components from submodules.
+    #[doc(hidden)]
+    pub fn record_dependency<I: ?Sized>(&self) {
+        if let Some(parent) = self.resolve_chain.last() {
+            self.usage
+                .record_dependency(parent.interface_type_name, type_name::<I>());
+        }
+    }
+
+    /// If the module should record its fingerprint (see `ModuleBuilder::record_fingerprint`)
+    #[doc(hidden)]
+    pub fn is_recording_fingerprint(&self) -> bool {
+        self.record_fingerprint
+    }
+
+    /// If the module should record its graph (see `ModuleBuilder::record_graph`)
+    #[doc(hidden)]
+    pub fn is_recording_graph(&self) -> bool {
+        self.record_graph
+    }
+
+    /// The parameters of the component, if they were set. Used to fingerprint the module.
+    #[doc(hidden)]
+    pub fn parameters<C: Component<M>>(&self) -> Option<&C::Parameters> {
+        self.parameters
+            .get::<ComponentParameters<C, C::Parameters>>()
+            .map(|parameters| &parameters.value)
+    }
+
+    /// Check if the component was overridden, either by an instance or a function. Used to
+    /// fingerprint the module.
+    #[doc(hidden)]
+    pub fn is_component_overridden<I: Interface + ?Sized>(&self) -> bool {
+        self.resolved_components.contains::<Arc<I>>()
+            || self.component_fn_overrides.contains::<ComponentFn<M, I>>()
+    }
+
+    /// Check if the provider was overridden. Used to fingerprint the module.
+    #[doc(hidden)]
+    pub fn is_provider_overridden<I: ProvidedInterface + ?Sized>(&self) -> bool {
+        self.provider_overrides.contains::<Arc<ProviderFn<M, I>>>()
+    }
+
+    /// Register the component so it receives the parameters passed to
+    /// `ReconfigurableModule::apply_parameters`
+    /// (see [`Reconfigurable`]). Called from [`Component::build_shared`] by components derived
+    /// with `#[shaku(reconfigurable)]`.
+    ///
+    /// [`Reconfigurable`]: trait.Reconfigurable.html
+    /// [`Component::build_shared`]: trait.Component.html#method.build_shared
+    pub fn watch_parameters<C: Reconfigurable<P>, P>(&mut self, component: &Arc<C>) {
+        self.config_watcher.watch(component);
+    }
+
+    /// The config watcher which the module should route new parameters through. Modules with
+    /// lazy components keep the build context, so they share the watcher with the components
+    /// which register later.
+    #[doc(hidden)]
+    pub fn config_watcher(&mut self, lazy_components: bool) -> ConfigWatcher {
+        if lazy_components {
+            self.config_watcher.share()
+        } else {
+            self.config_watcher.clone()
+        }
+    }
+
+    /// The usage tracker which the module should record service usage in
+    #[doc(hidden)]
+    #[cfg(feature = "usage_tracking")]
+    pub fn usage_tracker(&self) -> UsageTracker {
+        self.usage.clone()
+    }
+
+    /// Take the parameters of the component, or the default parameters if none were set. Empty
+    /// parameters (see `Component::EMPTY_PARAMETERS`) are not looked up. The flag is a constant,
+    /// so the branch is removed for each component.
+    pub(crate) fn take_parameters<C: Component<M>>(&mut self) -> C::Parameters {
+        if C::EMPTY_PARAMETERS {
+            return C::Parameters::default();
+        }
+
+        self.parameters
+            .remove::<ComponentParameters<C, C::Parameters>>()
+            .unwrap_or_default()
+            .value
+    }
+
+    /// Start timing a component build, if profiling is enabled
+    #[cfg(feature = "std")]
+    fn start_profiling(&self) -> Option<Instant> {
+        let profiler = self.profiler.as_ref()?;
+        profiler
+            .lock()
+            .dependency_durations
+            .push(Duration::default());
+
+        Some(Instant::now())
+    }
+
+    /// Start timing a component build, if profiling is enabled
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn start_profiling(&self) -> Option<()> {
+        None
+    }
+
+    /// Record the timing of a component build started via `start_profiling`
+    #[cfg(feature = "std")]
+    fn finish_profiling<C: Component<M>>(&self, start: Option<Instant>) {
+        let (profiler, start) = match (&self.profiler, start) {
+            (Some(profiler), Some(start)) => (profiler, start),
+            _ => return,
+        };
+        let duration = start.elapsed();
+        let mut profiler = profiler.lock();
+        let dependency_duration = profiler.dependency_durations.pop().unwrap_or_default();
+
+        // Count this build towards the component which depends on it
+        if let Some(parent_duration) = profiler.dependency_durations.last_mut() {
+            *parent_duration += duration;
+        }
+
+        profiler.timings.push(ComponentBuildTiming {
+            component_type_name: type_name::<C>(),
+            interface_type_name: type_name::<C::Interface>(),
+            duration,
+            own_duration: duration
+                .checked_sub(dependency_duration)
+                .unwrap_or_default(),
+            depth: self.resolve_chain.len(),
+        });
+    }
+
+    /// Record the timing of a component build started via `start_profiling`
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn finish_profiling<C: Component<M>>(&self, _start: Option<()>) {}
+
+    /// If the resolution chain is tracked, which detects circular dependencies
+    #[cfg(not(all(feature = "skip_cycle_checks", not(debug_assertions))))]
+    #[inline(always)]
+    fn tracks_resolve_chain(&self) -> bool {
+        true
+    }
+
+    /// If the resolution chain is tracked. The `skip_cycle_checks` feature skips it in release
+    /// builds, unless the build is profiled or tracks usage (which read the chain).
+    #[cfg(all(feature = "skip_cycle_checks", not(debug_assertions)))]
+    #[inline(always)]
+    fn tracks_resolve_chain(&self) -> bool {
+        self.profiler.is_some() || self.usage.is_enabled()
+    }
+
     fn add_resolve_step<C: Component<M>>(&mut self) {
+        if !self.tracks_resolve_chain() {
+            return;
+        }
+
         let step = ResolveStep {
             component_type_name: type_name::<C>(),
             component_type_id: TypeId::of::<C>(),
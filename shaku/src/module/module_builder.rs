@@ -1,10 +1,45 @@
 use crate::component::Interface;
-use crate::module::{ComponentMap, ParameterMap};
+use crate::module::module_build_context::BuildProfiler;
+#[cfg(feature = "serde_parameters")]
+use crate::module::parameters_snapshot::ParametersSnapshotter;
+use crate::module::{ComponentMap, ModuleHandles, ParameterMap};
+#[cfg(feature = "std")]
+use crate::parameter_source::ParameterValues;
 use crate::parameters::ComponentParameters;
 use crate::provider::ProviderFn;
-use crate::{Component, ComponentFn, HasComponent, HasProvider, Module, ModuleBuildContext};
-use std::marker::PhantomData;
-use std::sync::Arc;
+#[cfg(feature = "std")]
+use crate::{BuildAsync, BuildReport, ModuleBuildError, ParameterSource};
+use crate::{
+    Component, ComponentFn, DynRegistration, FromClosure, HasComponent, HasProvider, HasSubmodule,
+    Lock, Module, ModuleBuildContext, ModuleServices, ServiceKind, UsageTracker,
+};
+#[cfg(feature = "serde_parameters")]
+use crate::{ParametersSnapshot, SnapshotError};
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::{type_name, Any, TypeId};
+use core::marker::PhantomData;
+#[cfg(feature = "serde_parameters")]
+use serde::{de::DeserializeOwned, Serialize};
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// A type-erased service override for [`ModuleBuilder::with_dynamic_overrides`]. Components are
+/// overridden with an `Arc<I>`, and providers with a [`ProviderFn`].
+///
+/// [`ModuleBuilder::with_dynamic_overrides`]: struct.ModuleBuilder.html#method.with_dynamic_overrides
+/// [`ProviderFn`]: type.ProviderFn.html
+#[cfg(not(feature = "thread_safe"))]
+pub type AnyOverride = Box<dyn Any>;
+/// A type-erased service override for [`ModuleBuilder::with_dynamic_overrides`]. Components are
+/// overridden with an `Arc<I>`, and providers with a [`ProviderFn`].
+///
+/// [`ModuleBuilder::with_dynamic_overrides`]: struct.ModuleBuilder.html#method.with_dynamic_overrides
+/// [`ProviderFn`]: type.ProviderFn.html
+#[cfg(feature = "thread_safe")]
+pub type AnyOverride = Box<dyn Any + Send + Sync>;
 
 /// Builds a [`Module`]. Component parameters can be set, and both components and providers
 /// implementations can be overridden.
@@ -16,6 +51,20 @@ pub struct ModuleBuilder<M: Module> {
     component_overrides: ComponentMap,
     component_fn_overrides: ComponentMap,
     provider_overrides: ComponentMap,
+    build_context_data: ComponentMap,
+    #[cfg(feature = "serde_parameters")]
+    parameters_snapshotters: Vec<ParametersSnapshotter>,
+    #[cfg(feature = "std")]
+    parameter_sources: Vec<Arc<dyn ParameterSource>>,
+    #[cfg(feature = "std")]
+    deny_parameter_overwrites: bool,
+    /// The components (and parameter value keys) whose parameters were set more than once
+    #[cfg(feature = "std")]
+    overwritten_parameters: Vec<String>,
+    record_fingerprint: bool,
+    record_graph: bool,
+    overridden_interfaces: BTreeSet<&'static str>,
+    parameterized_components: BTreeSet<&'static str>,
     _module: PhantomData<M>,
 }
 
@@ -28,32 +77,293 @@ impl<M: Module> ModuleBuilder<M> {
             component_overrides: ComponentMap::new(),
             component_fn_overrides: ComponentMap::new(),
             provider_overrides: ComponentMap::new(),
+            build_context_data: ComponentMap::new(),
+            #[cfg(feature = "serde_parameters")]
+            parameters_snapshotters: Vec::new(),
+            #[cfg(feature = "std")]
+            parameter_sources: Vec::new(),
+            #[cfg(feature = "std")]
+            deny_parameter_overwrites: false,
+            #[cfg(feature = "std")]
+            overwritten_parameters: Vec::new(),
+            record_fingerprint: false,
+            record_graph: false,
+            overridden_interfaces: BTreeSet::new(),
+            parameterized_components: BTreeSet::new(),
             _module: PhantomData,
         }
     }
 
     /// Set the parameters of the specified component. If the parameters are not
     /// manually set, the defaults will be used.
+    ///
+    /// Setting the parameters again replaces the previous parameters, unless
+    /// [`deny_parameter_overwrites`] was called.
+    ///
+    /// [`deny_parameter_overwrites`]: #method.deny_parameter_overwrites
     pub fn with_component_parameters<C: Component<M>>(mut self, params: C::Parameters) -> Self
     where
         M: HasComponent<C::Interface>,
     {
         self.parameters
             .insert(ComponentParameters::<C, C::Parameters>::new(params));
+        self.insert_parameterized_component(type_name::<C>());
+        self
+    }
+
+    /// Record that the component's parameters were set, noting if they were already set before
+    fn insert_parameterized_component(&mut self, component_name: &'static str) {
+        let first_set = self.parameterized_components.insert(component_name);
+
+        #[cfg(feature = "std")]
+        {
+            if !first_set {
+                self.overwritten_parameters.push(component_name.to_string());
+            }
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = first_set;
+    }
+
+    /// Fail the build if the parameters of a component are set more than once, instead of
+    /// silently replacing the previous parameters. This catches large test setups where several
+    /// helpers set the same component's parameters. Parameters set via
+    /// [`with_component_parameters`], [`restore_parameters`], and [`with_parameter_value`] are
+    /// checked, including the ones set before this call.
+    ///
+    /// Setting the parameters again counts as an overwrite even if the value is the same. The
+    /// build then fails with [`ModuleBuildError::ParametersOverwritten`]: [`try_build`] and
+    /// [`build_async`] return the error, and the other build methods panic with it.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, Interface};
+    /// #
+    /// # trait Config: Interface {}
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Config)]
+    /// # struct ConfigImpl {
+    /// #     port: u16,
+    /// # }
+    /// # impl Config for ConfigImpl {}
+    /// #
+    /// # module! {
+    /// #     MyModule {
+    /// #         components = [ConfigImpl],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// let result = MyModule::builder()
+    ///     .deny_parameter_overwrites()
+    ///     .with_component_parameters::<ConfigImpl>(ConfigImplParameters { port: 80 })
+    ///     .with_component_parameters::<ConfigImpl>(ConfigImplParameters { port: 8080 })
+    ///     .try_build();
+    ///
+    /// // Fails, since the port was already set
+    /// assert!(result.is_err());
+    /// # }
+    /// ```
+    ///
+    /// [`with_component_parameters`]: #method.with_component_parameters
+    /// [`restore_parameters`]: #method.restore_parameters
+    /// [`with_parameter_value`]: #method.with_parameter_value
+    /// [`ModuleBuildError::ParametersOverwritten`]: enum.ModuleBuildError.html#variant.ParametersOverwritten
+    /// [`try_build`]: #method.try_build
+    /// [`build_async`]: #method.build_async
+    #[cfg(feature = "std")]
+    pub fn deny_parameter_overwrites(mut self) -> Self {
+        self.deny_parameter_overwrites = true;
+        self
+    }
+
+    /// Record the module's fingerprint while building it, so it can be read via
+    /// [`ModuleFingerprint::fingerprint`]. The fingerprint hashes the component parameters before
+    /// they are used up, so it is not recorded by default.
+    ///
+    /// [`ModuleFingerprint::fingerprint`]: trait.ModuleFingerprint.html#tymethod.fingerprint
+    pub fn record_fingerprint(mut self) -> Self {
+        self.record_fingerprint = true;
+        self
+    }
+
+    /// Record the module's graph while building it, so it can be inspected and compared via
+    /// `shaku::debug`. Recording the graph formats the component parameters, so it is not
+    /// recorded by default.
+    pub fn record_graph(mut self) -> Self {
+        self.record_graph = true;
+        self
+    }
+
+    /// Include the parameters of the specified component in [`parameters_snapshot`], and allow
+    /// them to be set via [`restore_parameters`].
+    ///
+    /// [`parameters_snapshot`]: #method.parameters_snapshot
+    /// [`restore_parameters`]: #method.restore_parameters
+    #[cfg(feature = "serde_parameters")]
+    pub fn with_snapshot_parameters<C: Component<M>>(mut self) -> Self
+    where
+        M: HasComponent<C::Interface>,
+        C::Parameters: Serialize + DeserializeOwned,
+    {
+        let snapshotter = ParametersSnapshotter::new::<M, C>();
+        let already_registered = self
+            .parameters_snapshotters
+            .iter()
+            .any(|registered| registered.component_name == snapshotter.component_name);
+
+        if !already_registered {
+            self.parameters_snapshotters.push(snapshotter);
+        }
+
         self
     }
 
+    /// Take a snapshot of the component parameters which have been set on this builder, for
+    /// example to log the effective configuration of the application. Only the parameters of
+    /// components registered via [`with_snapshot_parameters`] are included, and components which
+    /// use their default parameters are skipped.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, Interface};
+    /// #
+    /// # trait Foo: Interface {}
+    /// #
+    /// #[derive(Component)]
+    /// #[shaku(interface = Foo, parameters_derive(serde::Serialize, serde::Deserialize))]
+    /// struct FooImpl {
+    ///     value: usize,
+    /// }
+    /// # impl Foo for FooImpl {}
+    /// #
+    /// # module! {
+    /// #     MyModule {
+    /// #         components = [FooImpl],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    ///
+    /// # fn main() {
+    /// let snapshot = MyModule::builder()
+    ///     .with_snapshot_parameters::<FooImpl>()
+    ///     .with_component_parameters::<FooImpl>(FooImplParameters { value: 17 })
+    ///     .parameters_snapshot()
+    ///     .unwrap();
+    ///
+    /// // Later, for example in a test
+    /// let module = MyModule::builder()
+    ///     .with_snapshot_parameters::<FooImpl>()
+    ///     .restore_parameters(snapshot)
+    ///     .unwrap()
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`with_snapshot_parameters`]: #method.with_snapshot_parameters
+    #[cfg(feature = "serde_parameters")]
+    pub fn parameters_snapshot(&self) -> Result<ParametersSnapshot, SnapshotError> {
+        let mut snapshot = ParametersSnapshot::default();
+
+        for snapshotter in &self.parameters_snapshotters {
+            snapshotter.snapshot(&self.parameters, &mut snapshot)?;
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Set the component parameters contained in the snapshot. See [`parameters_snapshot`].
+    ///
+    /// Every component in the snapshot must be registered via [`with_snapshot_parameters`].
+    /// Parameters which were already set on this builder are replaced, unless
+    /// [`deny_parameter_overwrites`] was called.
+    ///
+    /// [`parameters_snapshot`]: #method.parameters_snapshot
+    /// [`with_snapshot_parameters`]: #method.with_snapshot_parameters
+    /// [`deny_parameter_overwrites`]: #method.deny_parameter_overwrites
+    #[cfg(feature = "serde_parameters")]
+    pub fn restore_parameters(
+        mut self,
+        snapshot: ParametersSnapshot,
+    ) -> Result<Self, SnapshotError> {
+        for (component_name, value) in snapshot.into_parameters() {
+            let snapshotter = self
+                .parameters_snapshotters
+                .iter()
+                .find(|snapshotter| snapshotter.component_name == component_name)
+                .ok_or(SnapshotError::UnknownComponent(component_name))?;
+
+            snapshotter.restore(&mut self.parameters, value)?;
+            let component_name = snapshotter.component_name;
+            self.insert_parameterized_component(component_name);
+        }
+
+        Ok(self)
+    }
+
     /// Override a component implementation. This method is best used when the
     /// overriding component has no injected dependencies.
     pub fn with_component_override<I: Interface + ?Sized>(mut self, component: Box<I>) -> Self
     where
         M: HasComponent<I>,
     {
-        self.component_overrides
-            .insert::<Arc<I>>(Arc::from(component));
+        self.insert_component_override::<I>(Arc::from(component));
         self
     }
 
+    /// Override a component implementation with a closure. The interface trait must be annotated
+    /// with `#[shaku::closure_interface]`, which requires it to have a single method without a
+    /// default implementation. The closure is called whenever that method is called. This is
+    /// useful for tiny adapters in tests and glue code.
+    ///
+    /// # Example
+    /// ```
+    /// use shaku::{closure_interface, module, Component, HasComponent, Interface};
+    /// use std::sync::mpsc;
+    /// use std::sync::Mutex;
+    ///
+    /// #[closure_interface]
+    /// trait EventSink: Interface {
+    ///     fn send(&self, event: &str);
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// #[shaku(interface = EventSink)]
+    /// struct NoopSink;
+    /// impl EventSink for NoopSink {
+    ///     fn send(&self, _: &str) {}
+    /// }
+    ///
+    /// module! {
+    ///     MyModule {
+    ///         components = [NoopSink],
+    ///         providers = []
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let (sender, receiver) = mpsc::channel();
+    /// let sender = Mutex::new(sender);
+    /// let module = MyModule::builder()
+    ///     .with_component_closure::<dyn EventSink, _>(move |event: &str| {
+    ///         sender.lock().unwrap().send(event.to_string()).unwrap();
+    ///     })
+    ///     .build();
+    ///
+    /// let sink: &dyn EventSink = module.resolve_ref();
+    /// sink.send("started");
+    /// assert_eq!(receiver.recv().unwrap(), "started");
+    /// # }
+    /// ```
+    pub fn with_component_closure<I: FromClosure<F> + ?Sized, F>(self, closure: F) -> Self
+    where
+        M: HasComponent<I>,
+    {
+        self.with_component_override::<I>(I::from_closure(closure))
+    }
+
     /// Override a component implementation. This method is best used when the
     /// overriding component has injected dependencies.
     pub fn with_component_override_fn<I: Interface + ?Sized>(
@@ -63,11 +373,51 @@ impl<M: Module> ModuleBuilder<M> {
     where
         M: HasComponent<I>,
     {
-        self.component_fn_overrides.insert(component_fn);
+        self.insert_component_fn_override::<I>(component_fn);
+        self
+    }
+
+    /// Add components registered by their interface's type id (see [`DynRegistration`]). This is
+    /// the target for wiring which is generated at build time. Each registration overrides the
+    /// module's component like [`with_component_override_fn`].
+    ///
+    /// # Panics
+    /// Panics if the module has no component with one of the type ids.
+    ///
+    /// [`DynRegistration`]: struct.DynRegistration.html
+    /// [`with_component_override_fn`]: #method.with_component_override_fn
+    pub fn with_registrations<R>(mut self, registrations: R) -> Self
+    where
+        M: ModuleServices,
+        R: IntoIterator<Item = DynRegistration<M>>,
+    {
+        let services = M::services();
+
+        for registration in registrations {
+            let service = services
+                .iter()
+                .find(|service| {
+                    service.interface_type_id() == registration.interface
+                        && service.kind() == ServiceKind::Component
+                })
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Unable to register {:?}: {} has no component with this interface",
+                        registration.interface,
+                        type_name::<M>()
+                    )
+                });
+
+            service.apply_registration(&mut self, registration.build);
+        }
+
         self
     }
 
-    /// Override a provider implementation.
+    /// Override a provider implementation. This also overrides providers of submodules which the
+    /// module provides (ex. `use OtherModule { providers = [dyn Repository] }`), when the service is
+    /// provided via this module. The submodule's own providers still use the submodule's provider,
+    /// so override it on the submodule's builder to change the services they provide.
     pub fn with_provider_override<I: 'static + ?Sized>(
         mut self,
         provider_fn: ProviderFn<M, I>,
@@ -75,18 +425,543 @@ impl<M: Module> ModuleBuilder<M> {
     where
         M: HasProvider<I>,
     {
+        self.insert_provider_override::<I>(provider_fn);
+        self
+    }
+
+    /// Override services by their interface's type id, with values created at runtime. This is
+    /// useful for property-based tests which generate sets of overrides, without naming each
+    /// interface statically. Components are overridden with an `Arc<I>`, and providers with a
+    /// [`ProviderFn`] (see [`AnyOverride`]).
+    ///
+    /// # Panics
+    /// Panics if the module has no service with one of the type ids, or if one of the values has
+    /// the wrong type for its service.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, AnyOverride, Component, HasComponent, Interface};
+    /// # use std::any::TypeId;
+    /// # use std::sync::Arc;
+    /// #
+    /// trait Clock: Interface {
+    ///     fn now(&self) -> u64;
+    /// }
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Clock)]
+    /// # struct SystemClock;
+    /// # impl Clock for SystemClock {
+    /// #     fn now(&self) -> u64 { 1_600_000_000 }
+    /// # }
+    ///
+    /// struct FixedClock(u64);
+    /// impl Clock for FixedClock {
+    ///     fn now(&self) -> u64 {
+    ///         self.0
+    ///     }
+    /// }
+    /// #
+    /// # module! {
+    /// #     MyModule {
+    /// #         components = [SystemClock],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    ///
+    /// # fn main() {
+    /// let clock: Arc<dyn Clock> = Arc::new(FixedClock(5));
+    /// let overrides: Vec<(TypeId, AnyOverride)> = vec![(TypeId::of::<dyn Clock>(), Box::new(clock))];
+    ///
+    /// let module = MyModule::builder().with_dynamic_overrides(overrides).build();
+    /// let clock: &dyn Clock = module.resolve_ref();
+    /// assert_eq!(clock.now(), 5);
+    /// # }
+    /// ```
+    ///
+    /// [`ProviderFn`]: type.ProviderFn.html
+    /// [`AnyOverride`]: type.AnyOverride.html
+    pub fn with_dynamic_overrides<O>(mut self, overrides: O) -> Self
+    where
+        M: ModuleServices,
+        O: IntoIterator<Item = (TypeId, AnyOverride)>,
+    {
+        let services = M::services();
+
+        for (type_id, value) in overrides {
+            let service = services
+                .iter()
+                .find(|service| service.interface_type_id() == type_id)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Unable to override {:?}: {} has no service with this interface",
+                        type_id,
+                        type_name::<M>()
+                    )
+                });
+
+            if service.apply_override(&mut self, value).is_err() {
+                panic!(
+                    "Unable to override {} {}: the override has the wrong type",
+                    service.kind(),
+                    service.interface_name()
+                );
+            }
+        }
+
+        self
+    }
+
+    /// The type names of the interfaces whose component or provider was overridden on this builder,
+    /// in alphabetical order. This is useful for frameworks which wrap the builder, to validate or
+    /// log the overrides before the module is built.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, Interface};
+    /// #
+    /// trait Clock: Interface {}
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Clock)]
+    /// # struct SystemClock;
+    /// # impl Clock for SystemClock {}
+    ///
+    /// struct FixedClock;
+    /// impl Clock for FixedClock {}
+    /// #
+    /// # module! {
+    /// #     MyModule {
+    /// #         components = [SystemClock],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    ///
+    /// # fn main() {
+    /// let builder = MyModule::builder().with_component_override::<dyn Clock>(Box::new(FixedClock));
+    /// let overridden = builder.overridden_interfaces();
+    /// assert_eq!(overridden.len(), 1);
+    /// assert!(overridden[0].contains("Clock"));
+    /// # }
+    /// ```
+    pub fn overridden_interfaces(&self) -> Vec<&'static str> {
+        self.overridden_interfaces.iter().cloned().collect()
+    }
+
+    /// The type names of the components whose parameters were set on this builder, in
+    /// alphabetical order. Like [`overridden_interfaces`], this is useful for frameworks which
+    /// wrap the builder.
+    ///
+    /// [`overridden_interfaces`]: #method.overridden_interfaces
+    pub fn parameterized_components(&self) -> Vec<&'static str> {
+        self.parameterized_components.iter().cloned().collect()
+    }
+
+    /// Check if the component's parameters were set, or the component was overridden. This is
+    /// used by the `RebuildModule` impl which the [`module`] macro generates.
+    ///
+    /// [`module`]: macro.module.html
+    #[doc(hidden)]
+    pub fn is_component_configured<C: Component<M>>(&self) -> bool
+    where
+        M: HasComponent<C::Interface>,
+    {
+        self.parameters
+            .contains::<ComponentParameters<C, C::Parameters>>()
+            || self.component_overrides.contains::<Arc<C::Interface>>()
+            || self
+                .component_fn_overrides
+                .contains::<ComponentFn<M, C::Interface>>()
+    }
+
+    /// Reuse a component from a previous build of the module, unless the component was overridden.
+    /// This is used by the `RebuildModule` impl which the [`module`] macro generates.
+    ///
+    /// [`module`]: macro.module.html
+    #[doc(hidden)]
+    pub fn reuse_component<I: Interface + ?Sized>(mut self, component: Arc<I>) -> Self
+    where
+        M: HasComponent<I>,
+    {
+        if !self.component_overrides.contains::<Arc<I>>()
+            && !self.component_fn_overrides.contains::<ComponentFn<M, I>>()
+        {
+            self.component_overrides.insert::<Arc<I>>(component);
+        }
+        self
+    }
+
+    /// Override a component with an instance which is already shared
+    pub(crate) fn insert_component_override<I: Interface + ?Sized>(&mut self, component: Arc<I>) {
+        self.component_overrides.insert::<Arc<I>>(component);
+        self.overridden_interfaces.insert(type_name::<I>());
+    }
+
+    /// Override a component with the function which builds it
+    pub(crate) fn insert_component_fn_override<I: Interface + ?Sized>(
+        &mut self,
+        component_fn: ComponentFn<M, I>,
+    ) {
+        self.component_fn_overrides.insert(component_fn);
+        self.overridden_interfaces.insert(type_name::<I>());
+    }
+
+    /// Override a provider with the provider function
+    pub(crate) fn insert_provider_override<I: 'static + ?Sized>(
+        &mut self,
+        provider_fn: ProviderFn<M, I>,
+    ) {
         self.provider_overrides.insert(Arc::new(provider_fn));
+        self.overridden_interfaces.insert(type_name::<I>());
+    }
+
+    /// Make the data available to every component build via [`ModuleBuildContext::data`]. This is
+    /// useful for startup data which many components need (ex. a config or runtime handle), instead
+    /// of passing it to each component via its parameters. Only one value is stored per type, so
+    /// setting data of the same type again replaces the previous value.
+    ///
+    /// The data is only available while this module is built, not while its submodules are built.
+    ///
+    /// [`ModuleBuildContext::data`]: struct.ModuleBuildContext.html#method.data
+    pub fn with_build_context_data<T: Interface>(mut self, data: T) -> Self {
+        self.build_context_data.insert(data);
+        self
+    }
+
+    /// Add a source for the parameters marked with `#[shaku(from_source = "key")]`. The values are
+    /// fetched by [`build_async`], which asks the sources in the order they were added.
+    ///
+    /// See [`ParameterSource`] for an example.
+    ///
+    /// [`build_async`]: #method.build_async
+    /// [`ParameterSource`]: trait.ParameterSource.html
+    #[cfg(feature = "std")]
+    pub fn with_parameter_source<S: ParameterSource>(mut self, source: S) -> Self {
+        self.parameter_sources.push(Arc::new(source));
+        self
+    }
+
+    /// Set the value of a parameter marked with `#[shaku(from_source = "key")]`, instead of
+    /// fetching it from a source. This allows building the module via [`build`], ex. in tests.
+    /// The value is parsed into the field's type via `FromStr` when the component is built.
+    ///
+    /// Setting the value of a key again replaces the previous value, unless
+    /// [`deny_parameter_overwrites`] was called.
+    ///
+    /// [`build`]: #method.build
+    /// [`deny_parameter_overwrites`]: #method.deny_parameter_overwrites
+    #[cfg(feature = "std")]
+    pub fn with_parameter_value<K: Into<String>, V: Into<String>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Self {
+        let key = key.into();
+        if self.has_parameter_value(&key) {
+            self.overwritten_parameters.push(key.clone());
+        }
+
+        self.insert_parameter_value(key, value.into());
+        self
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn insert_parameter_value(&mut self, key: String, value: String) {
+        match self.build_context_data.get_mut::<ParameterValues>() {
+            Some(values) => {
+                values.0.insert(key, value);
+            }
+            None => {
+                let mut values = ParameterValues::default();
+                values.0.insert(key, value);
+                self.build_context_data.insert(values);
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn has_parameter_value(&self, key: &str) -> bool {
+        self.build_context_data
+            .get::<ParameterValues>()
+            .map_or(false, |values| values.0.contains_key(key))
+    }
+
+    /// Replace a submodule, which was passed in when creating the builder. The submodule type must
+    /// match the type declared in the module (ex. `dyn MyModuleInterface`). This is useful for
+    /// replacing an entire submodule in tests, instead of overriding each of its services.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, module_interface, Component, HasComponent, Interface};
+    /// # use std::sync::Arc;
+    /// #
+    /// # trait Auth: Interface {
+    /// #     fn is_admin(&self) -> bool;
+    /// # }
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Auth)]
+    /// # struct AuthImpl;
+    /// # impl Auth for AuthImpl {
+    /// #     fn is_admin(&self) -> bool { false }
+    /// # }
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Auth)]
+    /// # struct FakeAuthImpl;
+    /// # impl Auth for FakeAuthImpl {
+    /// #     fn is_admin(&self) -> bool { true }
+    /// # }
+    /// #
+    /// module_interface! {
+    ///     trait AuthModule {
+    ///         components = [dyn Auth],
+    ///         providers = []
+    ///     }
+    /// }
+    /// #
+    /// # module! {
+    /// #     AuthModuleImpl: AuthModule {
+    /// #         components = [AuthImpl],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    /// #
+    /// # module! {
+    /// #     FakeAuthModule: AuthModule {
+    /// #         components = [FakeAuthImpl],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    ///
+    /// module! {
+    ///     RootModule {
+    ///         components = [],
+    ///         providers = [],
+    ///
+    ///         use dyn AuthModule {
+    ///             components = [dyn Auth],
+    ///             providers = []
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// # let auth_module: Arc<dyn AuthModule> = Arc::new(AuthModuleImpl::builder().build());
+    /// let module = RootModule::builder(auth_module)
+    ///     .with_submodule_override::<dyn AuthModule>(Arc::new(FakeAuthModule::builder().build()))
+    ///     .build();
+    ///
+    /// let auth: &dyn Auth = module.resolve_ref();
+    /// assert!(auth.is_admin());
+    /// # }
+    /// ```
+    pub fn with_submodule_override<S: ?Sized>(mut self, submodule: Arc<S>) -> Self
+    where
+        M: HasSubmodule<S>,
+    {
+        *M::submodule_mut(&mut self.submodules) = submodule;
         self
     }
 
     /// Build the module
+    ///
+    /// # Panics
+    /// Panics if parameters were overwritten after [`deny_parameter_overwrites`] was called. Use
+    /// [`try_build`] to handle the error instead.
+    ///
+    /// [`deny_parameter_overwrites`]: #method.deny_parameter_overwrites
+    /// [`try_build`]: #method.try_build
     pub fn build(self) -> M {
-        M::build(ModuleBuildContext::new(
+        self.build_with_handles(ModuleHandles::new())
+    }
+
+    /// Build the module, or fail if parameters were overwritten after
+    /// [`deny_parameter_overwrites`] was called.
+    ///
+    /// [`deny_parameter_overwrites`]: #method.deny_parameter_overwrites
+    #[cfg(feature = "std")]
+    pub fn try_build(self) -> Result<M, ModuleBuildError> {
+        self.check_parameter_overwrites()?;
+        Ok(self.build())
+    }
+
+    /// Fail if parameters were overwritten after [`deny_parameter_overwrites`] was called
+    ///
+    /// [`deny_parameter_overwrites`]: #method.deny_parameter_overwrites
+    #[cfg(feature = "std")]
+    pub(crate) fn check_parameter_overwrites(&self) -> Result<(), ModuleBuildError> {
+        if self.deny_parameter_overwrites && !self.overwritten_parameters.is_empty() {
+            return Err(ModuleBuildError::ParametersOverwritten(
+                self.overwritten_parameters.clone(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Build the module and place it in an `Arc`. Unlike [`build`], this also sets the
+    /// [`ModuleHandle`]s injected into components via `#[shaku(inject_module)]`.
+    ///
+    /// [`build`]: #method.build
+    /// [`ModuleHandle`]: struct.ModuleHandle.html
+    pub fn build_arc(self) -> Arc<M> {
+        let module_handles = ModuleHandles::new();
+        let module = Arc::new(self.build_with_handles(module_handles.clone()));
+        module_handles.ready(&module);
+
+        module
+    }
+
+    /// Fetch the values of the parameters marked with `#[shaku(from_source = "key")]` from the
+    /// [`ParameterSource`]s, then build the module. Values which were set via
+    /// [`with_parameter_value`] are not fetched. The build fails if a source fails, or if none of
+    /// the sources have a value for a key.
+    ///
+    /// [`ParameterSource`]: trait.ParameterSource.html
+    /// [`with_parameter_value`]: #method.with_parameter_value
+    #[cfg(feature = "std")]
+    pub fn build_async(mut self) -> BuildAsync<M> {
+        let sources = self.parameter_sources.drain(..).collect();
+        BuildAsync::new(self, sources)
+    }
+
+    fn build_with_handles(self, module_handles: ModuleHandles<M>) -> M {
+        M::build(self.into_context(None, UsageTracker::disabled(), module_handles))
+    }
+
+    /// Create the context which builds the module from the builder's configuration
+    fn into_context(
+        self,
+        profiler: Option<Arc<Lock<BuildProfiler>>>,
+        usage: UsageTracker,
+        module_handles: ModuleHandles<M>,
+    ) -> ModuleBuildContext<M> {
+        #[cfg(feature = "std")]
+        {
+            if let Err(error) = self.check_parameter_overwrites() {
+                panic!("{}", error);
+            }
+        }
+
+        ModuleBuildContext::new(
             self.parameters,
             self.component_overrides,
             self.component_fn_overrides,
             self.provider_overrides,
             self.submodules,
-        ))
+            self.build_context_data,
+            profiler,
+            usage,
+            module_handles,
+            self.record_fingerprint,
+            self.record_graph,
+        )
+    }
+
+    /// Build the module, recording how long each component took to build. This is useful for
+    /// finding slow component constructors which delay application startup.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, Interface};
+    /// #
+    /// # trait Foo: Interface {}
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Foo)]
+    /// # struct FooImpl;
+    /// # impl Foo for FooImpl {}
+    /// #
+    /// # module! {
+    /// #     MyModule {
+    /// #         components = [FooImpl],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// let (module, mut report) = MyModule::builder().build_profiled();
+    ///
+    /// report.sort_by_own_duration();
+    /// println!("{}", report);
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn build_profiled(self) -> (M, BuildReport) {
+        let profiler = Arc::new(Lock::new(BuildProfiler::default()));
+        let start = Instant::now();
+
+        let module = M::build(self.into_context(
+            Some(Arc::clone(&profiler)),
+            UsageTracker::disabled(),
+            ModuleHandles::new(),
+        ));
+
+        let total_duration = start.elapsed();
+        let timings = profiler.lock().timings.drain(..).collect();
+        let report = BuildReport {
+            components: timings,
+            total_duration,
+        };
+
+        (module, report)
+    }
+
+    /// Build the module, recording which of its services are used. This is useful for finding
+    /// registrations which are no longer needed, ex. by building the module this way in an
+    /// integration test and checking the [`UsageReport`] at the end.
+    ///
+    /// Only modules created via the [`module`] macro record usage.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, HasComponent, Interface};
+    /// #
+    /// # trait Foo: Interface {}
+    /// # trait Bar: Interface {}
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Foo)]
+    /// # struct FooImpl;
+    /// # impl Foo for FooImpl {}
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Bar)]
+    /// # struct BarImpl;
+    /// # impl Bar for BarImpl {}
+    /// #
+    /// # module! {
+    /// #     MyModule {
+    /// #         components = [FooImpl, BarImpl],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// let (module, usage) = MyModule::builder().build_with_usage_tracking();
+    /// let _foo: &dyn Foo = module.resolve_ref();
+    ///
+    /// let report = usage.report();
+    /// assert_eq!(report.unused_components(), [std::any::type_name::<dyn Bar>()]);
+    /// # }
+    /// ```
+    ///
+    /// [`UsageReport`]: struct.UsageReport.html
+    /// [`module`]: macro.module.html
+    #[cfg(feature = "usage_tracking")]
+    pub fn build_with_usage_tracking(self) -> (M, UsageTracker)
+    where
+        M: ModuleServices,
+    {
+        let services = M::services()
+            .iter()
+            .map(|service| (service.interface_name(), service.kind()))
+            .collect();
+        let usage = UsageTracker::enabled(services);
+
+        let module = M::build(self.into_context(None, usage.clone(), ModuleHandles::new()));
+
+        (module, usage)
     }
 }
@@ -0,0 +1,178 @@
+use crate::module::ModuleInterface;
+use crate::{Lock, OnceCell};
+use alloc::boxed::Box;
+use alloc::sync::{Arc, Weak};
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+
+/// A handle to the module which owns a component, as a module trait object (ex. `dyn MyModule`).
+/// Inject it by annotating a `ModuleHandle<dyn MyModule>` field with `#[shaku(inject_module)]`.
+/// This is useful for components which provide services dynamically at runtime.
+///
+/// The module can't be referenced while it is being built, so the handle is set once the built
+/// module is placed in an `Arc` by [`ModuleBuilder::build_arc`]. The handle only holds a weak
+/// reference, so it doesn't keep the module alive (which would be a reference cycle).
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, HasComponent, Interface, ModuleHandle};
+/// use std::sync::Arc;
+///
+/// trait Greeter: Interface {
+///     fn greet(&self) -> String;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Greeter)]
+/// struct GreeterImpl;
+/// impl Greeter for GreeterImpl {
+///     fn greet(&self) -> String {
+///         "Hello".to_string()
+///     }
+/// }
+///
+/// trait Orchestrator: Interface {
+///     fn run(&self) -> String;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Orchestrator)]
+/// struct OrchestratorImpl {
+///     #[shaku(inject_module)]
+///     module: ModuleHandle<dyn AppModule>,
+/// }
+///
+/// impl Orchestrator for OrchestratorImpl {
+///     fn run(&self) -> String {
+///         let module = self.module.get().expect("The module was dropped");
+///         let greeter: &dyn Greeter = module.resolve_ref();
+///         greeter.greet()
+///     }
+/// }
+///
+/// trait AppModule: HasComponent<dyn Greeter> {}
+///
+/// module! {
+///     MyModule: AppModule {
+///         components = [GreeterImpl, OrchestratorImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build_arc();
+/// let orchestrator: &dyn Orchestrator = module.resolve_ref();
+/// assert_eq!(orchestrator.run(), "Hello");
+/// # }
+/// ```
+///
+/// [`ModuleBuilder::build_arc`]: struct.ModuleBuilder.html#method.build_arc
+pub struct ModuleHandle<T: ?Sized> {
+    module: Arc<OnceCell<Weak<T>>>,
+}
+
+impl<T: ?Sized> ModuleHandle<T> {
+    /// Get the module. Returns `None` if the module was not built via
+    /// [`ModuleBuilder::build_arc`], or if it was dropped.
+    ///
+    /// [`ModuleBuilder::build_arc`]: struct.ModuleBuilder.html#method.build_arc
+    pub fn get(&self) -> Option<Arc<T>> {
+        self.module.get()?.upgrade()
+    }
+}
+
+impl<T: ?Sized> Clone for ModuleHandle<T> {
+    fn clone(&self) -> Self {
+        ModuleHandle {
+            module: Arc::clone(&self.module),
+        }
+    }
+}
+
+impl<T: ?Sized> Debug for ModuleHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModuleHandle")
+            .field("set", &self.module.get().is_some())
+            .finish()
+    }
+}
+
+#[cfg(not(feature = "thread_safe"))]
+type SetHandleFn<M> = Box<dyn FnOnce(&Arc<M>)>;
+#[cfg(feature = "thread_safe")]
+type SetHandleFn<M> = Box<dyn FnOnce(&Arc<M>) + Send>;
+
+/// The module handles created while building a module, which are set once the module is built via
+/// `ModuleBuilder::build_arc`. Handles created afterwards (ex. by lazy components) are set right
+/// away.
+pub(crate) struct ModuleHandles<M> {
+    state: Arc<Lock<ModuleHandlesState<M>>>,
+}
+
+struct ModuleHandlesState<M> {
+    module: Option<Weak<M>>,
+    pending: Vec<SetHandleFn<M>>,
+}
+
+impl<M: ModuleInterface> ModuleHandles<M> {
+    pub(crate) fn new() -> Self {
+        ModuleHandles {
+            state: Arc::new(Lock::new(ModuleHandlesState {
+                module: None,
+                pending: Vec::new(),
+            })),
+        }
+    }
+
+    /// Create a handle, which is set once the module is ready. The upcast function converts the
+    /// module into the handle's module trait object.
+    pub(crate) fn create<T: ?Sized + ModuleInterface>(
+        &self,
+        upcast: fn(Arc<M>) -> Arc<T>,
+    ) -> ModuleHandle<T> {
+        let handle = ModuleHandle {
+            module: Arc::new(OnceCell::new()),
+        };
+        let cell = Arc::clone(&handle.module);
+        let set_handle: SetHandleFn<M> = Box::new(move |module: &Arc<M>| {
+            let _ = cell.set(Arc::downgrade(&upcast(Arc::clone(module))));
+        });
+
+        let mut state = self.state.lock();
+        match state.module.as_ref().and_then(Weak::upgrade) {
+            Some(module) => {
+                drop(state);
+                set_handle(&module);
+            }
+            None => state.pending.push(set_handle),
+        }
+
+        handle
+    }
+
+    /// The module, if it is built and still alive
+    pub(crate) fn module(&self) -> Option<Arc<M>> {
+        self.state.lock().module.as_ref().and_then(Weak::upgrade)
+    }
+
+    /// Set the handles now that the module is built
+    pub(crate) fn ready(&self, module: &Arc<M>) {
+        let pending: Vec<SetHandleFn<M>> = {
+            let mut state = self.state.lock();
+            state.module = Some(Arc::downgrade(module));
+            state.pending.drain(..).collect()
+        };
+
+        for set_handle in pending {
+            set_handle(module);
+        }
+    }
+}
+
+impl<M> Clone for ModuleHandles<M> {
+    fn clone(&self) -> Self {
+        ModuleHandles {
+            state: Arc::clone(&self.state),
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use crate::module::service_locator::LocatedService;
+use crate::module::typed_build_fn;
+use crate::{
+    AnyOverride, DynBuildFn, Error, HasComponent, HasProvider, Interface, Module, ModuleBuilder,
+    ProviderFn,
+};
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::{type_name, Any, TypeId};
+use core::fmt;
+
+/// Describes the services (components and providers) which a module exposes. This allows tools
+/// such as [`verify`] to walk all of the services of a module at runtime.
+///
+/// This trait is implemented automatically by the [`module`] macro. Manually implemented modules
+/// can implement it by registering each `HasComponent`/`HasProvider` interface.
+///
+/// # Example
+/// ```
+/// use shaku::{Interface, HasComponent, Module, ModuleServices, ServiceRegistry};
+///
+/// trait Foo: Interface {}
+///
+/// # use shaku::{Component, ModuleBuildContext};
+/// # use std::sync::Arc;
+/// # struct FooImpl;
+/// # impl Foo for FooImpl {}
+/// # impl<M: Module> Component<M> for FooImpl {
+/// #     type Interface = dyn Foo;
+/// #     type Parameters = ();
+/// #     fn build(_: &mut ModuleBuildContext<M>, _: ()) -> Box<dyn Foo> { Box::new(FooImpl) }
+/// # }
+/// struct MyModule {
+///     foo: Arc<dyn Foo>,
+/// }
+/// #
+/// # impl Module for MyModule {
+/// #     type Submodules = ();
+/// #     fn build(mut context: ModuleBuildContext<Self>) -> Self {
+/// #         Self { foo: Self::build_component(&mut context) }
+/// #     }
+/// # }
+/// #
+/// # impl HasComponent<dyn Foo> for MyModule {
+/// #     fn build_component(context: &mut ModuleBuildContext<Self>) -> Arc<dyn Foo> {
+/// #         context.build_component::<FooImpl>()
+/// #     }
+/// #     fn resolve(&self) -> Arc<dyn Foo> { Arc::clone(&self.foo) }
+/// #     fn resolve_ref(&self) -> &dyn Foo { Arc::as_ref(&self.foo) }
+/// # }
+///
+/// impl ModuleServices for MyModule {
+///     fn register_services(services: &mut ServiceRegistry<Self>) {
+///         services.component::<dyn Foo>();
+///     }
+/// }
+/// ```
+///
+/// [`verify`]: fn.verify.html
+/// [`module`]: macro.module.html
+pub trait ModuleServices: Module + Sized {
+    /// Register each service exposed by this module
+    fn register_services(services: &mut ServiceRegistry<Self>);
+
+    /// Get the registry of services exposed by this module
+    fn services() -> ServiceRegistry<Self> {
+        let mut services = ServiceRegistry::new();
+        Self::register_services(&mut services);
+        services
+    }
+}
+
+/// Provide a service by its interface, if the module registered a provider for it in
+/// [`ModuleServices`]. This is useful when the calling code does not statically know whether the
+/// provider is registered, ex. in plugin hosts or admin tooling. If the provider is known to be
+/// registered, use [`HasProvider::provide`] instead, which is checked at compile time.
+///
+/// This trait is implemented for every module which implements [`ModuleServices`].
+///
+/// # Example
+/// ```
+/// use shaku::{module, Provider, TryProvide};
+///
+/// trait Connection {}
+/// trait Cache {}
+///
+/// #[derive(Provider)]
+/// #[shaku(interface = Connection)]
+/// struct ConnectionImpl;
+/// impl Connection for ConnectionImpl {}
+///
+/// module! {
+///     MyModule {
+///         components = [],
+///         providers = [ConnectionImpl]
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+///
+/// assert!(module.try_provide::<dyn Connection>().is_some());
+/// assert!(module.try_provide::<dyn Cache>().is_none());
+/// # }
+/// ```
+///
+/// [`ModuleServices`]: trait.ModuleServices.html
+/// [`HasProvider::provide`]: trait.HasProvider.html#tymethod.provide
+pub trait TryProvide {
+    /// Provide a service via the provider registered with the interface `I`. Returns `None` if the
+    /// module has no provider for the interface.
+    fn try_provide<I: ?Sized + 'static>(&self) -> Option<Result<Box<I>, Box<dyn Error>>>;
+}
+
+impl<M: ModuleServices> TryProvide for M {
+    fn try_provide<I: ?Sized + 'static>(&self) -> Option<Result<Box<I>, Box<dyn Error>>> {
+        let services = M::services();
+        let provide = services.iter().find_map(|service| match service.provide {
+            Some(provide) if service.interface_type_id == TypeId::of::<I>() => Some(provide),
+            _ => None,
+        })?;
+
+        Some(provide(self).map(|service| {
+            *service
+                .downcast::<Box<I>>()
+                .expect("The provided service did not match the registered interface")
+        }))
+    }
+}
+
+/// The kind of a service
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ServiceKind {
+    /// The service is a component (registered via `HasComponent`)
+    Component,
+    /// The service is provided (registered via `HasProvider`)
+    Provider,
+}
+
+impl fmt::Display for ServiceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceKind::Component => write!(f, "component"),
+            ServiceKind::Provider => write!(f, "provider"),
+        }
+    }
+}
+
+/// A list of the services exposed by a module. See [`ModuleServices`].
+///
+/// [`ModuleServices`]: trait.ModuleServices.html
+pub struct ServiceRegistry<M: Module> {
+    services: Vec<ServiceDescriptor<M>>,
+}
+
+impl<M: Module> ServiceRegistry<M> {
+    fn new() -> Self {
+        ServiceRegistry {
+            services: Vec::new(),
+        }
+    }
+
+    /// Register a component interface
+    pub fn component<I: Interface + ?Sized>(&mut self) -> &mut Self
+    where
+        M: HasComponent<I>,
+    {
+        self.services.push(ServiceDescriptor {
+            interface_name: type_name::<I>(),
+            name: None,
+            interface_type_id: TypeId::of::<I>(),
+            kind: ServiceKind::Component,
+            check: |module| {
+                HasComponent::<I>::resolve(module);
+                Ok(())
+            },
+            resolve: Some(|module| {
+                Arc::new(HasComponent::<I>::resolve(module)) as Arc<LocatedService>
+            }),
+            provide: None,
+            apply_override: |builder, value| {
+                let component = value.downcast::<Arc<I>>()?;
+                builder.insert_component_override::<I>(*component);
+                Ok(())
+            },
+            apply_registration: Some(|builder, build| {
+                builder.insert_component_fn_override::<I>(typed_build_fn(build));
+            }),
+        });
+        self
+    }
+
+    /// Register a provider interface
+    pub fn provider<I: ?Sized + 'static>(&mut self) -> &mut Self
+    where
+        M: HasProvider<I>,
+    {
+        self.services.push(ServiceDescriptor {
+            interface_name: type_name::<I>(),
+            name: None,
+            interface_type_id: TypeId::of::<I>(),
+            kind: ServiceKind::Provider,
+            check: |module| HasProvider::<I>::provide(module).map(|_| ()),
+            resolve: None,
+            provide: Some(|module| {
+                HasProvider::<I>::provide(module).map(|service| Box::new(service) as Box<dyn Any>)
+            }),
+            apply_override: |builder, value| {
+                let provider_fn = value.downcast::<ProviderFn<M, I>>()?;
+                builder.insert_provider_override::<I>(*provider_fn);
+                Ok(())
+            },
+            apply_registration: None,
+        });
+        self
+    }
+
+    /// Name the service which was registered last, ex. via `#[name = "..."]` in the [`module`]
+    /// macro. The name is a stable identifier for tools, unlike the interface's type name.
+    ///
+    /// [`module`]: macro.module.html
+    pub fn named(&mut self, name: &'static str) -> &mut Self {
+        if let Some(service) = self.services.last_mut() {
+            service.name = Some(name);
+        }
+        self
+    }
+
+    /// Iterate over the registered services
+    pub fn iter(&self) -> impl Iterator<Item = &ServiceDescriptor<M>> {
+        self.services.iter()
+    }
+
+    /// The number of registered services
+    pub fn len(&self) -> usize {
+        self.services.len()
+    }
+
+    /// Check if there are no registered services
+    pub fn is_empty(&self) -> bool {
+        self.services.is_empty()
+    }
+}
+
+/// Describes a single service exposed by a module
+pub struct ServiceDescriptor<M: Module> {
+    interface_name: &'static str,
+    name: Option<&'static str>,
+    interface_type_id: TypeId,
+    kind: ServiceKind,
+    // Only used by `verify`, which requires std
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    check: fn(&M) -> Result<(), Box<dyn Error>>,
+    /// Resolve the service as an `Arc<I>`, for components
+    resolve: Option<ResolveAnyFn<M>>,
+    /// Provide the service as a `Box<I>`, for providers
+    provide: Option<ProvideAnyFn<M>>,
+    /// Apply a type-erased override of the service to the builder. The value is returned if it
+    /// has the wrong type.
+    apply_override: ApplyOverrideFn<M>,
+    /// Build the component from a registration, for components
+    apply_registration: Option<ApplyRegistrationFn<M>>,
+}
+
+type ResolveAnyFn<M> = fn(&M) -> Arc<LocatedService>;
+type ProvideAnyFn<M> = fn(&M) -> Result<Box<dyn Any>, Box<dyn Error>>;
+type ApplyOverrideFn<M> = fn(&mut ModuleBuilder<M>, AnyOverride) -> Result<(), AnyOverride>;
+type ApplyRegistrationFn<M> = fn(&mut ModuleBuilder<M>, DynBuildFn<M>);
+
+impl<M: Module> ServiceDescriptor<M> {
+    /// The type name of the service's interface
+    pub fn interface_name(&self) -> &'static str {
+        self.interface_name
+    }
+
+    /// The name of the service, if it was named via [`ServiceRegistry::named`]
+    ///
+    /// [`ServiceRegistry::named`]: struct.ServiceRegistry.html#method.named
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// The kind of service
+    pub fn kind(&self) -> ServiceKind {
+        self.kind
+    }
+
+    /// The type id of the service's interface
+    pub(crate) fn interface_type_id(&self) -> TypeId {
+        self.interface_type_id
+    }
+
+    /// Resolve the component as an `Arc<I>`. Returns `None` for providers. See
+    /// `ServiceLocator`.
+    pub(crate) fn resolve(&self, module: &M) -> Option<Arc<LocatedService>> {
+        self.resolve.map(|resolve| resolve(module))
+    }
+
+    /// Override the service on the builder. See `ModuleBuilder::with_dynamic_overrides`.
+    pub(crate) fn apply_override(
+        &self,
+        builder: &mut ModuleBuilder<M>,
+        value: AnyOverride,
+    ) -> Result<(), AnyOverride> {
+        (self.apply_override)(builder, value)
+    }
+
+    /// Build the component from the registration's build function. Does nothing for providers. See
+    /// `ModuleBuilder::with_registrations`.
+    pub(crate) fn apply_registration(&self, builder: &mut ModuleBuilder<M>, build: DynBuildFn<M>) {
+        if let Some(apply_registration) = self.apply_registration {
+            apply_registration(builder, build);
+        }
+    }
+
+    /// Resolve (for components) or provide (for providers) the service, discarding the result.
+    #[cfg(feature = "std")]
+    pub(crate) fn check(&self, module: &M) -> Result<(), Box<dyn Error>> {
+        (self.check)(module)
+    }
+}
+
+impl<M: Module> fmt::Debug for ServiceDescriptor<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServiceDescriptor")
+            .field("interface_name", &self.interface_name)
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
@@ -1,5 +1,7 @@
-use crate::ModuleBuildContext;
-use std::any::Any;
+use crate::{Interface, ModuleBuildContext, ProvidedInterface, ThreadSafe};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::Any;
 
 /// A module represents a group of services. By implementing traits such as [`HasComponent`] on a
 /// module, service dependencies are checked at compile time. At runtime, modules hold the
@@ -42,23 +44,127 @@ pub trait Module: ModuleInterface {
     fn build(context: ModuleBuildContext<Self>) -> Self
     where
         Self: Sized;
-}
 
-#[cfg(not(feature = "thread_safe"))]
-trait_alias!(
-    /// Submodules must be `'static` in order to be stored in other modules
-    /// (hence the `Any` requirement).
+    /// The keys of the component parameters which are read from a parameter source (see
+    /// [`ModuleBuilder::build_async`]). The default implementation returns no keys.
     ///
-    /// The `thread_safe` feature is turned off, so submodules do not need to
-    /// implement `Send` or `Sync`.
-    pub ModuleInterface = Any
-);
-#[cfg(feature = "thread_safe")]
+    /// [`ModuleBuilder::build_async`]: struct.ModuleBuilder.html#method.build_async
+    fn parameter_sources() -> Vec<&'static str>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+}
+
+/// Indicates that a module uses a submodule of type `S` (ex. `dyn MyModuleInterface`). This allows
+/// accessing the submodule by type via [`ModuleBuildContext::submodule`] instead of destructuring
+/// [`ModuleBuildContext::submodules`], replacing the submodule via
+/// [`ModuleBuilder::with_submodule_override`], and reaching into the submodule of a built module
+/// via [`SubmodulePath::submodule`].
+///
+/// Implemented by the [`module`] macro for each submodule, unless the module uses multiple
+/// submodules of the same type.
+///
+/// [`ModuleBuildContext::submodule`]: struct.ModuleBuildContext.html#method.submodule
+/// [`ModuleBuildContext::submodules`]: struct.ModuleBuildContext.html#method.submodules
+/// [`ModuleBuilder::with_submodule_override`]: struct.ModuleBuilder.html#method.with_submodule_override
+/// [`SubmodulePath::submodule`]: trait.SubmodulePath.html#method.submodule
+/// [`module`]: macro.module.html
+pub trait HasSubmodule<S: ?Sized>: Module {
+    /// Get the submodule from the module's submodules
+    fn submodule(submodules: &Self::Submodules) -> &Arc<S>;
+
+    /// Get the submodule from the module's submodules, mutably
+    fn submodule_mut(submodules: &mut Self::Submodules) -> &mut Arc<S>;
+
+    /// Get the submodule from the built module
+    fn resolve_submodule(&self) -> &Arc<S>;
+}
+
+/// Reaches into the submodules of a built module by type, ex.
+/// `root.submodule::<AuthModuleImpl>()`. This allows diagnostics or admin code to resolve services
+/// which the root module does not re-export. Calls can be chained to reach nested submodules.
+///
+/// Implemented for every module. Prefer re-exporting services for regular use, since this ties the
+/// caller to the structure of the module tree.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, HasComponent, Interface, SubmodulePath};
+/// use std::sync::Arc;
+///
+/// trait TokenStore: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = TokenStore)]
+/// struct TokenStoreImpl;
+/// impl TokenStore for TokenStoreImpl {}
+///
+/// module! {
+///     AuthModule {
+///         components = [TokenStoreImpl],
+///         providers = []
+///     }
+/// }
+///
+/// module! {
+///     RootModule {
+///         components = [],
+///         providers = [],
+///
+///         use AuthModule {
+///             components = [],
+///             providers = []
+///         }
+///     }
+/// }
+///
+/// # fn main() {
+/// let auth_module = Arc::new(AuthModule::builder().build());
+/// let root_module = RootModule::builder(auth_module).build();
+///
+/// let token_store: Arc<dyn TokenStore> = root_module.submodule::<AuthModule>().resolve();
+/// # }
+/// ```
+pub trait SubmodulePath {
+    /// Get the submodule of type `S` (ex. `dyn MyModuleInterface`)
+    fn submodule<S: ?Sized>(&self) -> &Arc<S>
+    where
+        Self: HasSubmodule<S>,
+    {
+        HasSubmodule::resolve_submodule(self)
+    }
+}
+
+impl<M: Module + ?Sized> SubmodulePath for M {}
+
 trait_alias!(
-    /// Submodules must be `'static` in order to be stored in other modules
-    /// (hence the `Any` requirement).
+    /// Submodules must be `'static` in order to be stored in other modules (hence the `Any`
+    /// requirement), and must be [`ThreadSafe`] so the module can be shared between threads.
     ///
-    /// The `thread_safe` feature is turned on, which requires submodules to
-    /// also implement `Send` and `Sync`.
-    pub ModuleInterface = Any + Send + Sync
+    /// [`ThreadSafe`]: trait.ThreadSafe.html
+    pub ModuleInterface = Any + ThreadSafe
 );
+
+/// The component interfaces exposed by a module with `#[shaku(extendable)]`, by index (ex.
+/// `[(); 0]`). Modules which extend it use these to re-export its components without naming the
+/// component implementations. Implemented by the [`module`] macro.
+///
+/// The extending module is part of the trait so the interfaces are known when checking the
+/// extending module's impls for overlap, even if the extended module is from another crate.
+///
+/// [`module`]: macro.module.html
+#[doc(hidden)]
+pub trait ExportedComponent<Index, Extension>: Module {
+    type Interface: Interface + ?Sized;
+}
+
+/// The provider interfaces exposed by a module with `#[shaku(extendable)]`, by index. See
+/// [`ExportedComponent`].
+///
+/// [`ExportedComponent`]: trait.ExportedComponent.html
+#[doc(hidden)]
+pub trait ExportedProvider<Index, Extension>: Module {
+    type Interface: ProvidedInterface + ?Sized;
+}
@@ -0,0 +1,187 @@
+use crate::module::ParameterMap;
+use crate::parameters::ComponentParameters;
+use crate::{Component, Module};
+use serde::de::{Deserialize, DeserializeOwned, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use serde_json::Value;
+use std::any::type_name;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+/// A serializable copy of component parameters set on a [`ModuleBuilder`]. See
+/// [`ModuleBuilder::parameters_snapshot`] and [`ModuleBuilder::restore_parameters`].
+///
+/// The parameters are keyed by the component's type name, so a snapshot can only be restored by
+/// the same build of the application (type names are not guaranteed to be stable across compiler
+/// versions).
+///
+/// [`ModuleBuilder`]: struct.ModuleBuilder.html
+/// [`ModuleBuilder::parameters_snapshot`]: struct.ModuleBuilder.html#method.parameters_snapshot
+/// [`ModuleBuilder::restore_parameters`]: struct.ModuleBuilder.html#method.restore_parameters
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParametersSnapshot {
+    parameters: BTreeMap<String, Value>,
+}
+
+impl ParametersSnapshot {
+    /// Get the serialized parameters of a component by its type name
+    pub fn get(&self, component_name: &str) -> Option<&Value> {
+        self.parameters.get(component_name)
+    }
+
+    /// Iterate over the component type names and their serialized parameters
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.parameters
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// The number of components with parameters in the snapshot
+    pub fn len(&self) -> usize {
+        self.parameters.len()
+    }
+
+    /// Returns true if the snapshot does not contain any parameters
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+    }
+
+    pub(crate) fn into_parameters(self) -> BTreeMap<String, Value> {
+        self.parameters
+    }
+}
+
+impl Serialize for ParametersSnapshot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.parameters.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ParametersSnapshot {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(ParametersSnapshot {
+            parameters: BTreeMap::deserialize(deserializer)?,
+        })
+    }
+}
+
+/// An error which occurred while taking or restoring a [`ParametersSnapshot`].
+///
+/// [`ParametersSnapshot`]: struct.ParametersSnapshot.html
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The parameters of a component could not be serialized
+    Serialize {
+        component_name: String,
+        error: serde_json::Error,
+    },
+    /// The parameters of a component could not be deserialized
+    Deserialize {
+        component_name: String,
+        error: serde_json::Error,
+    },
+    /// The snapshot contains parameters of a component which is not registered via
+    /// [`ModuleBuilder::with_snapshot_parameters`]
+    ///
+    /// [`ModuleBuilder::with_snapshot_parameters`]: struct.ModuleBuilder.html#method.with_snapshot_parameters
+    UnknownComponent(String),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Serialize {
+                component_name,
+                error,
+            } => write!(
+                f,
+                "Failed to serialize the parameters of {}: {}",
+                component_name, error
+            ),
+            SnapshotError::Deserialize {
+                component_name,
+                error,
+            } => write!(
+                f,
+                "Failed to deserialize the parameters of {}: {}",
+                component_name, error
+            ),
+            SnapshotError::UnknownComponent(component_name) => write!(
+                f,
+                "The parameters of {} are not registered for snapshots",
+                component_name
+            ),
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SnapshotError::Serialize { error, .. } | SnapshotError::Deserialize { error, .. } => {
+                Some(error)
+            }
+            SnapshotError::UnknownComponent(_) => None,
+        }
+    }
+}
+
+/// Serializes and deserializes the parameters of a single component type
+pub(crate) struct ParametersSnapshotter {
+    pub(crate) component_name: &'static str,
+    snapshot: fn(&ParameterMap) -> Option<serde_json::Result<Value>>,
+    restore: fn(&mut ParameterMap, Value) -> serde_json::Result<()>,
+}
+
+impl ParametersSnapshotter {
+    pub(crate) fn new<M: Module, C>() -> Self
+    where
+        C: Component<M>,
+        C::Parameters: Serialize + DeserializeOwned,
+    {
+        ParametersSnapshotter {
+            component_name: type_name::<C>(),
+            snapshot: |parameters| {
+                parameters
+                    .get::<ComponentParameters<C, C::Parameters>>()
+                    .map(|parameters| serde_json::to_value(&parameters.value))
+            },
+            restore: |parameters, value| {
+                let value = serde_json::from_value(value)?;
+                parameters.insert(ComponentParameters::<C, C::Parameters>::new(value));
+                Ok(())
+            },
+        }
+    }
+
+    pub(crate) fn snapshot(
+        &self,
+        parameters: &ParameterMap,
+        snapshot: &mut ParametersSnapshot,
+    ) -> Result<(), SnapshotError> {
+        if let Some(result) = (self.snapshot)(parameters) {
+            let value = result.map_err(|error| SnapshotError::Serialize {
+                component_name: self.component_name.to_string(),
+                error,
+            })?;
+
+            snapshot
+                .parameters
+                .insert(self.component_name.to_string(), value);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn restore(
+        &self,
+        parameters: &mut ParameterMap,
+        value: Value,
+    ) -> Result<(), SnapshotError> {
+        (self.restore)(parameters, value).map_err(|error| SnapshotError::Deserialize {
+            component_name: self.component_name.to_string(),
+            error,
+        })
+    }
+}
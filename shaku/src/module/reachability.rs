@@ -0,0 +1,128 @@
+use crate::{HasComponent, HasProvider, Interface};
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// A service of a module, as the interface type name, whether the module interface exports the
+/// service, and the interface type names of its dependencies (see [`Component::dependencies`] and
+/// [`Provider::dependencies`]).
+///
+/// [`Component::dependencies`]: trait.Component.html#method.dependencies
+/// [`Provider::dependencies`]: trait.Provider.html#method.dependencies
+#[doc(hidden)]
+pub type ServiceReachability = (&'static str, bool, Vec<&'static str>);
+
+/// Get the services which are not reachable from the module interface, meaning they are not
+/// exported and no reachable service depends on them. The services are returned in the order they
+/// were given.
+///
+/// This is used by the `unreachable_services` function which the [`module`] macro generates.
+///
+/// [`module`]: macro.module.html
+#[doc(hidden)]
+pub fn unreachable_services(services: Vec<ServiceReachability>) -> Vec<&'static str> {
+    let mut reachable: BTreeSet<&'static str> = BTreeSet::new();
+    let mut pending: Vec<&'static str> = services
+        .iter()
+        .filter(|(_, exported, _)| *exported)
+        .map(|(interface, _, _)| *interface)
+        .collect();
+
+    while let Some(interface) = pending.pop() {
+        if !reachable.insert(interface) {
+            continue;
+        }
+
+        for (_, _, dependencies) in services.iter().filter(|(i, _, _)| *i == interface) {
+            pending.extend(dependencies.iter().cloned());
+        }
+    }
+
+    services
+        .into_iter()
+        .map(|(interface, _, _)| interface)
+        .filter(|interface| !reachable.contains(interface))
+        .collect()
+}
+
+/// Panic if the module has unreachable services. Used by modules with
+/// `#[shaku(deny_unused)]` when they are built.
+#[doc(hidden)]
+pub fn deny_unreachable_services(module: &'static str, unreachable: &[&'static str]) {
+    if !unreachable.is_empty() {
+        panic!(
+            "{} has services which are not reachable from its module interface: {}",
+            module,
+            unreachable.join(", ")
+        );
+    }
+}
+
+/// Checks if the module interface `M` (ex. `dyn MyModule`) exports the interface `I`. The
+/// [`ExportsComponent`] and [`ExportsProvider`] impls apply if `M` has the interface as a
+/// component or provider, otherwise the [`NotExported`] impl for `&ExportProbe` is used.
+///
+/// [`ExportsComponent`]: trait.ExportsComponent.html
+/// [`ExportsProvider`]: trait.ExportsProvider.html
+/// [`NotExported`]: trait.NotExported.html
+#[doc(hidden)]
+pub struct ExportProbe<M: ?Sized, I: ?Sized>(PhantomData<fn(&M, &I)>);
+
+impl<M: ?Sized, I: ?Sized> ExportProbe<M, I> {
+    pub fn new() -> Self {
+        ExportProbe(PhantomData)
+    }
+}
+
+impl<M: ?Sized, I: ?Sized> Default for ExportProbe<M, I> {
+    fn default() -> Self {
+        ExportProbe::new()
+    }
+}
+
+/// See [`ExportProbe`].
+///
+/// [`ExportProbe`]: struct.ExportProbe.html
+#[doc(hidden)]
+pub trait ExportsComponent {
+    fn exports_component(&self) -> bool;
+}
+
+/// See [`ExportProbe`].
+///
+/// [`ExportProbe`]: struct.ExportProbe.html
+#[doc(hidden)]
+pub trait ExportsProvider {
+    fn exports_provider(&self) -> bool;
+}
+
+/// See [`ExportProbe`].
+///
+/// [`ExportProbe`]: struct.ExportProbe.html
+#[doc(hidden)]
+pub trait NotExported {
+    fn exports_component(&self) -> bool;
+    fn exports_provider(&self) -> bool;
+}
+
+impl<M: HasComponent<I> + ?Sized, I: Interface + ?Sized> ExportsComponent for ExportProbe<M, I> {
+    fn exports_component(&self) -> bool {
+        true
+    }
+}
+
+impl<M: HasProvider<I> + ?Sized, I: ?Sized> ExportsProvider for ExportProbe<M, I> {
+    fn exports_provider(&self) -> bool {
+        true
+    }
+}
+
+impl<'a, M: ?Sized, I: ?Sized> NotExported for &'a ExportProbe<M, I> {
+    fn exports_component(&self) -> bool {
+        false
+    }
+
+    fn exports_provider(&self) -> bool {
+        false
+    }
+}
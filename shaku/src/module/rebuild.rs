@@ -0,0 +1,79 @@
+use crate::{ComponentDependencies, Module, ModuleBuilder};
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+/// Build a module again, reusing the components which are not affected by the new builder.
+/// Implemented by modules created via the [`module`] macro.
+///
+/// [`module`]: macro.module.html
+pub trait RebuildModule: Module + Sized {
+    /// Reuse the components of this module which are not affected by the builder. Use
+    /// [`rebuild`] or [`rebuild_arc`] instead.
+    ///
+    /// [`rebuild`]: #method.rebuild
+    /// [`rebuild_arc`]: #method.rebuild_arc
+    #[doc(hidden)]
+    fn reuse_components(&self, builder: ModuleBuilder<Self>) -> ModuleBuilder<Self>;
+
+    /// Build the module again, reusing the components of this module which are not affected by
+    /// the builder. A component is rebuilt if its parameters were set or it was overridden on the
+    /// builder, if it is tied to this module (see `Component::ALWAYS_REBUILD`), or if it
+    /// (transitively) depends on a rebuilt component. Lazy components which were not built yet
+    /// are built as usual.
+    ///
+    /// Like [`ModuleBuilder::build`], this does not set the module handles injected via
+    /// `#[shaku(inject_module)]`. Use [`rebuild_arc`] for modules with such components.
+    ///
+    /// [`ModuleBuilder::build`]: struct.ModuleBuilder.html#method.build
+    /// [`rebuild_arc`]: #method.rebuild_arc
+    fn rebuild(&self, builder: ModuleBuilder<Self>) -> Self {
+        self.reuse_components(builder).build()
+    }
+
+    /// Build the module again like [`rebuild`], and place it in an `Arc`. Like
+    /// [`ModuleBuilder::build_arc`], this also sets the module handles injected via
+    /// `#[shaku(inject_module)]`.
+    ///
+    /// [`rebuild`]: #method.rebuild
+    /// [`ModuleBuilder::build_arc`]: struct.ModuleBuilder.html#method.build_arc
+    fn rebuild_arc(&self, builder: ModuleBuilder<Self>) -> Arc<Self> {
+        self.reuse_components(builder).build_arc()
+    }
+}
+
+/// Get the components which must be rebuilt when the `changed` components change: the changed
+/// components and every component which transitively depends on them. The other components can be
+/// reused from the previous build.
+///
+/// This is used by the [`RebuildModule`] impl which the [`module`] macro generates.
+///
+/// [`RebuildModule`]: trait.RebuildModule.html
+/// [`module`]: macro.module.html
+#[doc(hidden)]
+pub fn rebuilt_components(
+    components: Vec<ComponentDependencies>,
+    changed: Vec<&'static str>,
+) -> BTreeSet<&'static str> {
+    let mut rebuilt: BTreeSet<&'static str> = changed.into_iter().collect();
+
+    // Keep adding dependents until a pass finds no new ones
+    loop {
+        let mut added = false;
+
+        for (interface, _, dependencies) in &components {
+            if !rebuilt.contains(interface)
+                && dependencies
+                    .iter()
+                    .any(|dependency| rebuilt.contains(dependency))
+            {
+                rebuilt.insert(interface);
+                added = true;
+            }
+        }
+
+        if !added {
+            return rebuilt;
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{ModuleServices, ThreadSafe};
+use alloc::sync::Arc;
+use core::any::{Any, TypeId};
+
+/// A type-erased component, as returned by [`ServiceLocator::get_arc`]. This is an `Arc<I>` of
+/// the component's interface `I`. Requires `Send` and `Sync` if the `thread_safe` feature is
+/// turned on.
+///
+/// [`ServiceLocator::get_arc`]: trait.ServiceLocator.html#tymethod.get_arc
+#[cfg(feature = "thread_safe")]
+pub type LocatedService = dyn Any + Send + Sync;
+/// A type-erased component, as returned by [`ServiceLocator::get_arc`]. This is an `Arc<I>` of
+/// the component's interface `I`. Requires `Send` and `Sync` if the `thread_safe` feature is
+/// turned on.
+///
+/// [`ServiceLocator::get_arc`]: trait.ServiceLocator.html#tymethod.get_arc
+#[cfg(not(feature = "thread_safe"))]
+pub type LocatedService = dyn Any;
+
+/// Look up components by the `TypeId` of their interface. This lets a module be passed to APIs
+/// which are designed around type-based lookup (ex. error reporting backends or request
+/// extensions) as a `&dyn ServiceLocator`, without those APIs depending on the module's type.
+///
+/// This trait is implemented for every module which implements [`ModuleServices`], so it covers
+/// the components registered there (including components from submodules). Providers are not
+/// located, since each provided service is a new instance; see [`TryProvide`] instead.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, Interface, ServiceLocator};
+/// use std::any::TypeId;
+/// use std::sync::Arc;
+///
+/// trait Logger: Interface {
+///     fn log(&self, message: &str) -> String;
+/// }
+/// trait Cache: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Logger)]
+/// struct LoggerImpl;
+/// impl Logger for LoggerImpl {
+///     fn log(&self, message: &str) -> String {
+///         format!("[log] {}", message)
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [LoggerImpl],
+///         providers = []
+///     }
+/// }
+///
+/// fn report(locator: &dyn ServiceLocator) -> Option<String> {
+///     let logger = locator.get::<dyn Logger>()?;
+///     Some(logger.log("something went wrong"))
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+///
+/// assert_eq!(report(&module).unwrap(), "[log] something went wrong");
+/// assert!(module.get_arc(TypeId::of::<dyn Cache>()).is_none());
+/// # }
+/// ```
+///
+/// [`ModuleServices`]: trait.ModuleServices.html
+/// [`TryProvide`]: trait.TryProvide.html
+pub trait ServiceLocator: ThreadSafe {
+    /// Resolve the component whose interface has the type id. The component is returned as an
+    /// `Arc<I>` (ex. `Arc<dyn Logger>`) behind the type-erased `Arc`. Returns `None` if the module
+    /// has no component for the interface.
+    fn get_arc(&self, interface: TypeId) -> Option<Arc<LocatedService>>;
+}
+
+impl<'a> dyn ServiceLocator + 'a {
+    /// Resolve the component which implements the interface `I`. Returns `None` if the module has
+    /// no component for the interface.
+    pub fn get<I: ?Sized + 'static>(&self) -> Option<Arc<I>> {
+        let component = self.get_arc(TypeId::of::<I>())?;
+        let component = component
+            .downcast_ref::<Arc<I>>()
+            .expect("The located component did not match the registered interface");
+
+        Some(Arc::clone(component))
+    }
+}
+
+impl<M: ModuleServices> ServiceLocator for M {
+    fn get_arc(&self, interface: TypeId) -> Option<Arc<LocatedService>> {
+        M::services()
+            .iter()
+            .filter(|service| service.interface_type_id() == interface)
+            .find_map(|service| service.resolve(self))
+    }
+}
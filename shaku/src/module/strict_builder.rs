@@ -0,0 +1,135 @@
+use crate::{Component, HasComponent, Module, ModuleBuilder};
+use core::marker::PhantomData;
+
+/// A [`ModuleBuilder`] which tracks which components still need their parameters set, so that
+/// `build` is only available once the parameters of every component with parameters that have no
+/// default (ie. no `#[shaku(default)]`) have been set. This moves the "no default value" panic to a compile time error.
+///
+/// Created via the `strict_builder` function, which the [`module`] macro generates when the module
+/// is annotated with `#[shaku(strict_builder)]`. The state `S` is a list of
+/// [`ParametersMissing`]/[`ParametersSet`], one per component.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, HasComponent, Interface};
+///
+/// trait Database: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Database)]
+/// struct DatabaseImpl {
+///     url: String,
+/// }
+/// impl Database for DatabaseImpl {}
+///
+/// module! {
+///     #[shaku(strict_builder)]
+///     MyModule {
+///         components = [DatabaseImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// // Calling build without setting the parameters would not compile
+/// let module = MyModule::strict_builder()
+///     .with_component_parameters::<DatabaseImpl>(DatabaseImplParameters {
+///         url: "postgres://localhost".to_string(),
+///     })
+///     .build();
+/// let database: &dyn Database = module.resolve_ref();
+/// # }
+/// ```
+///
+/// [`ModuleBuilder`]: struct.ModuleBuilder.html
+/// [`module`]: macro.module.html
+/// [`ParametersMissing`]: struct.ParametersMissing.html
+/// [`ParametersSet`]: struct.ParametersSet.html
+pub struct StrictModuleBuilder<M: Module, S> {
+    builder: ModuleBuilder<M>,
+    _state: PhantomData<S>,
+}
+
+impl<M: Module, S> StrictModuleBuilder<M, S> {
+    /// Used by the `strict_builder` function which the `module` macro generates
+    #[doc(hidden)]
+    pub fn new(builder: ModuleBuilder<M>) -> Self {
+        StrictModuleBuilder {
+            builder,
+            _state: PhantomData,
+        }
+    }
+
+    /// Set the parameters of the specified component. See
+    /// [`ModuleBuilder::with_component_parameters`].
+    ///
+    /// [`ModuleBuilder::with_component_parameters`]: struct.ModuleBuilder.html#method.with_component_parameters
+    pub fn with_component_parameters<C: Component<M>>(
+        self,
+        params: C::Parameters,
+    ) -> StrictModuleBuilder<M, <Self as SetComponentParameters<C>>::State>
+    where
+        M: HasComponent<C::Interface>,
+        Self: SetComponentParameters<C>,
+    {
+        StrictModuleBuilder::new(self.builder.with_component_parameters::<C>(params))
+    }
+
+    /// Configure the underlying [`ModuleBuilder`], ex. to override a component. Parameters set
+    /// this way are not tracked.
+    ///
+    /// [`ModuleBuilder`]: struct.ModuleBuilder.html
+    pub fn configure(self, configure: impl FnOnce(ModuleBuilder<M>) -> ModuleBuilder<M>) -> Self {
+        StrictModuleBuilder::new(configure(self.builder))
+    }
+}
+
+impl<M: Module, S: ParametersComplete> StrictModuleBuilder<M, S> {
+    /// Build the module. See [`ModuleBuilder::build`].
+    ///
+    /// [`ModuleBuilder::build`]: struct.ModuleBuilder.html#method.build
+    pub fn build(self) -> M {
+        self.builder.build()
+    }
+}
+
+/// Tracks whether a component's parameters must be set before the module is built via
+/// [`StrictModuleBuilder`]. The derived parameters struct implements this trait, so it only needs
+/// to be implemented for custom parameters types.
+///
+/// [`StrictModuleBuilder`]: struct.StrictModuleBuilder.html
+pub trait RequiredParameters {
+    /// [`ParametersMissing`] if the parameters have no default, otherwise [`ParametersSet`]
+    ///
+    /// [`ParametersMissing`]: struct.ParametersMissing.html
+    /// [`ParametersSet`]: struct.ParametersSet.html
+    type State;
+}
+
+impl RequiredParameters for () {
+    type State = ParametersSet;
+}
+
+/// The component's parameters have no default and have not been set yet
+#[derive(Debug)]
+pub struct ParametersMissing;
+
+/// The component's parameters have been set (or have a default)
+#[derive(Debug)]
+pub struct ParametersSet;
+
+/// Implemented when the parameters of every component have been set. The state is a list of the
+/// form `(S0, (S1, ()))`.
+pub trait ParametersComplete {}
+
+impl ParametersComplete for () {}
+
+impl<T: ParametersComplete> ParametersComplete for (ParametersSet, T) {}
+
+/// Marks the component's parameters as set. Implemented by the [`module`] macro.
+///
+/// [`module`]: macro.module.html
+#[doc(hidden)]
+pub trait SetComponentParameters<C> {
+    type State;
+}
@@ -0,0 +1,86 @@
+//! Reporting of superseded services in modules with `#[shaku(last_wins)]`
+
+use core::fmt;
+#[cfg(feature = "std")]
+use once_cell::sync::Lazy;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::sync::{Mutex, RwLock};
+
+/// A service of a module with `#[shaku(last_wins)]` was registered more than once, and the
+/// earlier registration was replaced by a later one. See the [`module`] macro.
+///
+/// [`module`]: macro.module.html
+#[derive(Clone, Debug)]
+pub struct SupersededService {
+    module: &'static str,
+    description: &'static str,
+}
+
+impl SupersededService {
+    /// The name of the module which the service was registered in
+    pub fn module(&self) -> &'static str {
+        self.module
+    }
+
+    /// Which registration was superseded, and by which registration
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+impl fmt::Display for SupersededService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.module, self.description)
+    }
+}
+
+/// A function which is called for each superseded service. See [`set_superseded_handler`].
+///
+/// [`set_superseded_handler`]: fn.set_superseded_handler.html
+pub type SupersededHandler = fn(&SupersededService);
+
+#[cfg(feature = "std")]
+static HANDLER: Lazy<RwLock<Option<SupersededHandler>>> = Lazy::new(|| RwLock::new(None));
+#[cfg(feature = "std")]
+static REPORTED: Lazy<Mutex<HashSet<&'static str>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Set the function which is called for each superseded service, the first time a module with
+/// `#[shaku(last_wins)]` is built. Until a handler is set, superseded services are not reported.
+#[cfg(feature = "std")]
+pub fn set_superseded_handler(handler: SupersededHandler) {
+    *HANDLER.write().unwrap() = Some(handler);
+}
+
+/// Report the services of a module with `#[shaku(last_wins)]` which were registered more than
+/// once, and replaced by a later registration. Called by the [`module`] macro when the module is
+/// built.
+///
+/// [`module`]: macro.module.html
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub fn report_superseded_services(module: &'static str, superseded: &[&'static str]) {
+    let handler = match *HANDLER.read().unwrap() {
+        Some(handler) => handler,
+        None => return,
+    };
+
+    if !REPORTED.lock().unwrap().insert(module) {
+        return;
+    }
+
+    for description in superseded {
+        handler(&SupersededService {
+            module,
+            description,
+        });
+    }
+}
+
+/// Report the services of a module with `#[shaku(last_wins)]` which were registered more than
+/// once. Without `std`, there is nowhere to report them.
+#[doc(hidden)]
+#[cfg(not(feature = "std"))]
+#[inline(always)]
+pub fn report_superseded_services(_module: &'static str, _superseded: &[&'static str]) {}
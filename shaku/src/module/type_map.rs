@@ -0,0 +1,37 @@
+//! A map which stores one value per type, used instead of anymap2 without std
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use core::any::{Any, TypeId};
+
+/// Stores at most one value of each type, keyed by the type's `TypeId`
+#[derive(Default)]
+pub(crate) struct TypeMap {
+    values: BTreeMap<TypeId, Box<dyn Any>>,
+}
+
+impl TypeMap {
+    pub(crate) fn new() -> Self {
+        TypeMap::default()
+    }
+
+    /// Insert the value, returning the previous value of the same type
+    pub(crate) fn insert<T: Any>(&mut self, value: T) -> Option<T> {
+        self.values
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .map(|previous| *previous.downcast::<T>().unwrap())
+    }
+
+    pub(crate) fn get<T: Any>(&self) -> Option<&T> {
+        self.values.get(&TypeId::of::<T>())?.downcast_ref::<T>()
+    }
+
+    pub(crate) fn remove<T: Any>(&mut self) -> Option<T> {
+        let value = self.values.remove(&TypeId::of::<T>())?;
+        Some(*value.downcast::<T>().unwrap())
+    }
+
+    pub(crate) fn contains<T: Any>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+}
@@ -0,0 +1,176 @@
+use crate::Lock;
+#[cfg(feature = "usage_tracking")]
+use crate::ServiceKind;
+use alloc::collections::BTreeMap;
+#[cfg(feature = "usage_tracking")]
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Records which services of a module are used. See [`ModuleBuilder::build_with_usage_tracking`].
+///
+/// Usage is only recorded by modules created via the [`module`] macro. Tracking is disabled unless
+/// the module was built with [`ModuleBuilder::build_with_usage_tracking`].
+///
+/// [`ModuleBuilder::build_with_usage_tracking`]: struct.ModuleBuilder.html#method.build_with_usage_tracking
+/// [`module`]: macro.module.html
+#[derive(Clone, Default)]
+pub struct UsageTracker {
+    usage: Option<Arc<Lock<Usage>>>,
+}
+
+#[derive(Default)]
+struct Usage {
+    /// The services exposed by the module
+    #[cfg(feature = "usage_tracking")]
+    services: Vec<(&'static str, ServiceKind)>,
+    /// Interfaces which were resolved or provided from the module
+    #[cfg(feature = "usage_tracking")]
+    used: BTreeSet<&'static str>,
+    /// The interfaces injected into each component while building the module
+    dependencies: BTreeMap<&'static str, Vec<&'static str>>,
+}
+
+impl UsageTracker {
+    /// Create a tracker which records the usage of the services
+    #[cfg(feature = "usage_tracking")]
+    pub(crate) fn enabled(services: Vec<(&'static str, ServiceKind)>) -> Self {
+        UsageTracker {
+            usage: Some(Arc::new(Lock::new(Usage {
+                services,
+                ..Usage::default()
+            }))),
+        }
+    }
+
+    /// Create a tracker which does not record anything
+    #[doc(hidden)]
+    pub fn disabled() -> Self {
+        UsageTracker::default()
+    }
+
+    /// If usage is recorded
+    #[cfg(all(feature = "skip_cycle_checks", not(debug_assertions)))]
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.usage.is_some()
+    }
+
+    /// Record that the interface was resolved or provided from the module
+    #[doc(hidden)]
+    #[cfg(feature = "usage_tracking")]
+    pub fn record_use(&self, interface: &'static str) {
+        if let Some(usage) = &self.usage {
+            usage.lock().used.insert(interface);
+        }
+    }
+
+    /// Record that the interface was injected into the component while building the module
+    pub(crate) fn record_dependency(&self, component: &'static str, interface: &'static str) {
+        if let Some(usage) = &self.usage {
+            usage
+                .lock()
+                .dependencies
+                .entry(component)
+                .or_insert_with(Vec::new)
+                .push(interface);
+        }
+    }
+
+    /// Create a report of the services which have not been used so far. A component counts as used
+    /// if it was resolved from the module, or if it was injected into a used component. A provider
+    /// counts as used if it was called.
+    #[cfg(feature = "usage_tracking")]
+    pub fn report(&self) -> UsageReport {
+        let usage = match &self.usage {
+            Some(usage) => usage.lock(),
+            None => return UsageReport::default(),
+        };
+
+        // Components injected into used components are also used
+        let mut used = usage.used.clone();
+        let mut pending: Vec<&'static str> = used.iter().cloned().collect();
+        while let Some(interface) = pending.pop() {
+            for dependency in usage.dependencies.get(interface).into_iter().flatten() {
+                if used.insert(dependency) {
+                    pending.push(dependency);
+                }
+            }
+        }
+
+        let unused = |kind: ServiceKind| {
+            usage
+                .services
+                .iter()
+                .filter(|(interface, service_kind)| {
+                    *service_kind == kind && !used.contains(interface)
+                })
+                .map(|(interface, _)| *interface)
+                .collect()
+        };
+
+        UsageReport {
+            unused_components: unused(ServiceKind::Component),
+            unused_providers: unused(ServiceKind::Provider),
+        }
+    }
+}
+
+impl fmt::Debug for UsageTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UsageTracker")
+            .field("enabled", &self.usage.is_some())
+            .finish()
+    }
+}
+
+/// The services of a module which were not used. See [`UsageTracker::report`].
+///
+/// [`UsageTracker::report`]: struct.UsageTracker.html#method.report
+#[cfg(feature = "usage_tracking")]
+#[derive(Clone, Debug, Default)]
+pub struct UsageReport {
+    unused_components: Vec<&'static str>,
+    unused_providers: Vec<&'static str>,
+}
+
+#[cfg(feature = "usage_tracking")]
+impl UsageReport {
+    /// The type names of the component interfaces which were never resolved or injected
+    pub fn unused_components(&self) -> &[&'static str] {
+        &self.unused_components
+    }
+
+    /// The type names of the provider interfaces which were never called
+    pub fn unused_providers(&self) -> &[&'static str] {
+        &self.unused_providers
+    }
+
+    /// Check if every service was used
+    pub fn is_empty(&self) -> bool {
+        self.unused_components.is_empty() && self.unused_providers.is_empty()
+    }
+}
+
+#[cfg(feature = "usage_tracking")]
+impl fmt::Display for UsageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "All services were used");
+        }
+
+        write!(
+            f,
+            "Found {} unused service(s):",
+            self.unused_components.len() + self.unused_providers.len()
+        )?;
+        for component in &self.unused_components {
+            write!(f, "\n- component {}", component)?;
+        }
+        for provider in &self.unused_providers {
+            write!(f, "\n- provider {}", provider)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+//! A parameter wrapper for types which can't implement `Default`
+
+use crate::{ParametersMissing, RequiredParameters};
+use core::any::type_name;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+/// Wraps a parameter which has no sensible default value, ex. a boxed closure. Component
+/// parameters must implement `Default`, which closures and many handles don't. `NoDefault`
+/// implements `Default` by panicking, so the parameter must be passed to the module builder (ex.
+/// via [`with_component_parameters`]). The [`StrictModuleBuilder`] checks this at compile time.
+///
+/// Derived components can use the wrapper as a field type. The field is a required parameter, so it
+/// can't have a `#[shaku(default)]`. Components which implement [`Component`] manually can use it
+/// as (or in) their `Parameters` type.
+///
+/// If the `thread_safe` feature is turned on, parameters must be `Send` and components must be
+/// `Send + Sync`, so closures should be boxed as `Box<dyn Fn(..) + Send + Sync>`. Without
+/// `thread_safe`, a plain `Box<dyn Fn(..)>` works, so the closure can capture types like `Rc`.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, HasComponent, Interface, NoDefault};
+///
+/// trait Formatter: Interface {
+///     fn format(&self, value: i32) -> String;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Formatter)]
+/// struct FormatterImpl {
+///     format_fn: NoDefault<Box<dyn Fn(i32) -> String + Send + Sync>>,
+/// }
+///
+/// impl Formatter for FormatterImpl {
+///     fn format(&self, value: i32) -> String {
+///         (self.format_fn)(value)
+///     }
+/// }
+///
+/// module! {
+///     FormatModule {
+///         components = [FormatterImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = FormatModule::builder()
+///     .with_component_parameters::<FormatterImpl>(FormatterImplParameters {
+///         format_fn: NoDefault(Box::new(|value| format!("#{}", value))),
+///     })
+///     .build();
+///
+/// let formatter: &dyn Formatter = module.resolve_ref();
+/// assert_eq!(formatter.format(5), "#5");
+/// # }
+/// ```
+///
+/// [`with_component_parameters`]: struct.ModuleBuilder.html#method.with_component_parameters
+/// [`StrictModuleBuilder`]: struct.StrictModuleBuilder.html
+/// [`Component`]: trait.Component.html
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NoDefault<T>(pub T);
+
+impl<T> NoDefault<T> {
+    /// Unwrap the value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Default for NoDefault<T> {
+    fn default() -> Self {
+        panic!(
+            "There is no default value for the parameter `{}`. Pass it to the module builder, \
+             ex. via with_component_parameters.",
+            type_name::<T>()
+        )
+    }
+}
+
+impl<T> Deref for NoDefault<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for NoDefault<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for NoDefault<T> {
+    fn from(value: T) -> Self {
+        NoDefault(value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for NoDefault<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T> RequiredParameters for NoDefault<T> {
+    type State = ParametersMissing;
+}
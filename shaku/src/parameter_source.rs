@@ -0,0 +1,353 @@
+//! Component parameters which are fetched from an external source (ex. a secrets manager) when the
+//! module is built
+
+use crate::{BoxFuture, CancellationToken, Module, ModuleBuildContext, ModuleBuilder, ThreadSafe};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// The error returned when a [`ParameterSource`] fails to fetch a value
+pub type FetchError = Box<dyn Error + Send + Sync>;
+
+/// Fetches the values of component parameters marked with `#[shaku(from_source = "key")]`, ex.
+/// from Vault or AWS Secrets Manager. Sources are added via
+/// [`ModuleBuilder::with_parameter_source`], and the values are fetched by
+/// [`ModuleBuilder::build_async`].
+///
+/// Values are fetched as strings and parsed into the field's type via `FromStr`. If a source
+/// doesn't have a value for a key, it returns `Ok(None)` and the next source is asked.
+///
+/// # Example
+/// ```
+/// use shaku::{module, BoxFuture, Component, FetchError, HasComponent, Interface, ParameterSource};
+/// use std::collections::HashMap;
+/// # use std::future::Future;
+/// # use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+///
+/// trait Database: Interface {
+///     fn password(&self) -> &str;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Database)]
+/// struct DatabaseImpl {
+///     #[shaku(from_source = "db_password")]
+///     password: String,
+/// }
+///
+/// impl Database for DatabaseImpl {
+///     fn password(&self) -> &str {
+///         &self.password
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [DatabaseImpl],
+///         providers = []
+///     }
+/// }
+///
+/// struct VaultSource {
+///     // A real source would hold a client instead
+///     secrets: HashMap<String, String>,
+/// }
+///
+/// impl ParameterSource for VaultSource {
+///     fn fetch(&self, key: &str) -> BoxFuture<'static, Result<Option<String>, FetchError>> {
+///         // A real source would send a request here
+///         self.secrets.fetch(key)
+///     }
+/// }
+/// #
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     fn noop_raw_waker() -> RawWaker {
+/// #         fn clone(_: *const ()) -> RawWaker { noop_raw_waker() }
+/// #         fn noop(_: *const ()) {}
+/// #         static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+/// #         RawWaker::new(std::ptr::null(), &VTABLE)
+/// #     }
+/// #     let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+/// #     let mut context = Context::from_waker(&waker);
+/// #     let mut future = Box::pin(future);
+/// #     loop {
+/// #         if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+///
+/// # fn main() {
+/// let mut secrets = HashMap::new();
+/// secrets.insert("db_password".to_string(), "hunter2".to_string());
+///
+/// let module = block_on(
+///     MyModule::builder()
+///         .with_parameter_source(VaultSource { secrets })
+///         .build_async(),
+/// )
+/// .unwrap();
+///
+/// let database: &dyn Database = module.resolve_ref();
+/// assert_eq!(database.password(), "hunter2");
+/// # }
+/// ```
+///
+/// [`ModuleBuilder::with_parameter_source`]: struct.ModuleBuilder.html#method.with_parameter_source
+/// [`ModuleBuilder::build_async`]: struct.ModuleBuilder.html#method.build_async
+pub trait ParameterSource: ThreadSafe + 'static {
+    /// Fetch the value of the key, or `None` if this source doesn't have a value for it
+    fn fetch(&self, key: &str) -> BoxFuture<'static, Result<Option<String>, FetchError>>;
+}
+
+/// Fixed values, ex. for tests or values read from the environment before the build
+impl ParameterSource for HashMap<String, String> {
+    fn fetch(&self, key: &str) -> BoxFuture<'static, Result<Option<String>, FetchError>> {
+        Box::pin(Ready(Some(Ok(self.get(key).cloned()))))
+    }
+}
+
+/// A future which is immediately ready (`std::future::ready` is above the MSRV)
+struct Ready<T>(Option<T>);
+
+impl<T> Unpin for Ready<T> {}
+
+impl<T> Future for Ready<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<T> {
+        Poll::Ready(self.0.take().expect("Ready polled after completion"))
+    }
+}
+
+/// An error which occurred while fetching the parameter values for [`ModuleBuilder::build_async`],
+/// or which [`ModuleBuilder::try_build`] found in the builder's configuration
+///
+/// [`ModuleBuilder::build_async`]: struct.ModuleBuilder.html#method.build_async
+/// [`ModuleBuilder::try_build`]: struct.ModuleBuilder.html#method.try_build
+#[derive(Debug)]
+pub enum ModuleBuildError {
+    /// None of the sources have a value for the key
+    MissingParameter(&'static str),
+    /// A source failed to fetch the value of the key
+    FetchParameter {
+        key: &'static str,
+        error: FetchError,
+    },
+    /// The build was cancelled via its [`CancellationToken`]. `completed` lists the keys whose
+    /// values were fetched before it was cancelled, in the order they were fetched.
+    ///
+    /// [`CancellationToken`]: struct.CancellationToken.html
+    Cancelled { completed: Vec<&'static str> },
+    /// Parameters were set more than once, which was denied via
+    /// [`ModuleBuilder::deny_parameter_overwrites`]. Lists the components (or the keys of the
+    /// parameter values) in the order they were overwritten.
+    ///
+    /// [`ModuleBuilder::deny_parameter_overwrites`]: struct.ModuleBuilder.html#method.deny_parameter_overwrites
+    ParametersOverwritten(Vec<String>),
+}
+
+impl fmt::Display for ModuleBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModuleBuildError::MissingParameter(key) => {
+                write!(f, "No parameter source has a value for `{}`", key)
+            }
+            ModuleBuildError::FetchParameter { key, error } => {
+                write!(f, "Failed to fetch the value of `{}`: {}", key, error)
+            }
+            ModuleBuildError::Cancelled { completed } => write!(
+                f,
+                "The module build was cancelled after fetching {} parameter(s)",
+                completed.len()
+            ),
+            ModuleBuildError::ParametersOverwritten(overwritten) => write!(
+                f,
+                "The parameters of `{}` were set more than once. Overwriting parameters is \
+                 denied by ModuleBuilder::deny_parameter_overwrites.",
+                overwritten.join("`, `")
+            ),
+        }
+    }
+}
+
+impl Error for ModuleBuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ModuleBuildError::FetchParameter { error, .. } => Some(&**error),
+            ModuleBuildError::MissingParameter(_)
+            | ModuleBuildError::Cancelled { .. }
+            | ModuleBuildError::ParametersOverwritten(_) => None,
+        }
+    }
+}
+
+/// The parameter values which were set or fetched, stored in the build context data
+#[derive(Default)]
+pub(crate) struct ParameterValues(pub(crate) HashMap<String, String>);
+
+/// Get the value of a field marked with `#[shaku(from_source = "key")]` while building a component.
+/// Used by the `Component` derive.
+#[doc(hidden)]
+pub fn source_parameter<M: Module, T: FromStr>(
+    context: &ModuleBuildContext<M>,
+    component: &'static str,
+    key: &'static str,
+) -> T
+where
+    T::Err: fmt::Display,
+{
+    let value = context
+        .data::<ParameterValues>()
+        .and_then(|values| values.0.get(key))
+        .unwrap_or_else(|| {
+            panic!(
+                "The parameter `{}` of {} has no value. Build the module via \
+                 ModuleBuilder::build_async, or set the value via \
+                 ModuleBuilder::with_parameter_value.",
+                key, component
+            )
+        });
+
+    value.parse().unwrap_or_else(|error| {
+        panic!(
+            "Failed to parse the parameter `{}` of {}: {}",
+            key, component, error
+        )
+    })
+}
+
+/// The future returned by [`ModuleBuilder::build_async`]. It fetches the parameter values one
+/// key at a time, asking the sources in the order they were added, then builds the module.
+///
+/// [`ModuleBuilder::build_async`]: struct.ModuleBuilder.html#method.build_async
+pub struct BuildAsync<M: Module> {
+    builder: Option<ModuleBuilder<M>>,
+    sources: Vec<Arc<dyn ParameterSource>>,
+    /// The keys which still need to be fetched
+    keys: Vec<&'static str>,
+    /// The keys which were fetched
+    completed: Vec<&'static str>,
+    /// The key being fetched, the index of the source it is fetched from, and the fetch
+    fetching: Option<(&'static str, usize, Fetch)>,
+    cancellation: Option<CancellationToken>,
+}
+
+type Fetch = BoxFuture<'static, Result<Option<String>, FetchError>>;
+
+impl<M: Module> BuildAsync<M> {
+    pub(crate) fn new(builder: ModuleBuilder<M>, sources: Vec<Arc<dyn ParameterSource>>) -> Self {
+        let mut keys = M::parameter_sources();
+        keys.reverse();
+
+        BuildAsync {
+            builder: Some(builder),
+            sources,
+            keys,
+            completed: Vec::new(),
+            fetching: None,
+            cancellation: None,
+        }
+    }
+
+    /// Abort the build once the token is cancelled. The pending fetch is dropped, and the build
+    /// returns [`ModuleBuildError::Cancelled`]. Once the values are fetched, the components are
+    /// built synchronously, so the build can't be cancelled from then on.
+    ///
+    /// See [`CancellationToken`] for an example.
+    ///
+    /// [`ModuleBuildError::Cancelled`]: enum.ModuleBuildError.html#variant.Cancelled
+    /// [`CancellationToken`]: struct.CancellationToken.html
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .map_or(false, CancellationToken::is_cancelled)
+    }
+}
+
+// The builder is never pinned, only the fetch futures (which are boxed)
+impl<M: Module> Unpin for BuildAsync<M> {}
+
+impl<M: Module> Future for BuildAsync<M> {
+    type Output = Result<M, ModuleBuildError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        loop {
+            if this.is_cancelled() {
+                this.fetching = None;
+                this.builder = None;
+                let completed = this.completed.drain(..).collect();
+                return Poll::Ready(Err(ModuleBuildError::Cancelled { completed }));
+            }
+
+            if let Some((key, source_index, fetch)) = &mut this.fetching {
+                let (key, source_index) = (*key, *source_index);
+
+                match fetch.as_mut().poll(cx) {
+                    Poll::Pending => {
+                        if let Some(token) = &this.cancellation {
+                            token.register(cx.waker());
+                        }
+                        return Poll::Pending;
+                    }
+                    Poll::Ready(Err(error)) => {
+                        this.fetching = None;
+                        return Poll::Ready(Err(ModuleBuildError::FetchParameter { key, error }));
+                    }
+                    Poll::Ready(Ok(Some(value))) => {
+                        this.fetching = None;
+                        this.completed.push(key);
+                        this.builder
+                            .as_mut()
+                            .expect("BuildAsync polled after completion")
+                            .insert_parameter_value(key.to_string(), value);
+                    }
+                    Poll::Ready(Ok(None)) => {
+                        let next_index = source_index + 1;
+                        match this.sources.get(next_index) {
+                            Some(source) => {
+                                this.fetching = Some((key, next_index, source.fetch(key)));
+                            }
+                            None => {
+                                this.fetching = None;
+                                return Poll::Ready(Err(ModuleBuildError::MissingParameter(key)));
+                            }
+                        }
+                    }
+                }
+
+                continue;
+            }
+
+            let builder = this
+                .builder
+                .as_mut()
+                .expect("BuildAsync polled after completion");
+
+            match this.keys.pop() {
+                // Values which were already set (or fetched for another component) are kept
+                Some(key) if builder.has_parameter_value(key) => {}
+                Some(key) => match this.sources.first() {
+                    Some(source) => this.fetching = Some((key, 0, source.fetch(key))),
+                    None => return Poll::Ready(Err(ModuleBuildError::MissingParameter(key))),
+                },
+                None => {
+                    let builder = this.builder.take().unwrap();
+                    return Poll::Ready(builder.try_build());
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 /// Used to store the parameters of a component. This is used instead of
 /// directly storing the parameters to avoid mixing up parameters of the same
@@ -7,9 +7,12 @@ use std::marker::PhantomData;
 /// Example: Component1 and Component2 both use String as their parameter type.
 /// If the parameter was stored directly in the parameter map, one of the
 /// strings would overwrite the other string.
+///
+/// The component is only used as a marker (via `fn() -> C`), so the parameters
+/// can be sent between threads even if the component can't.
 pub(crate) struct ComponentParameters<C, P: Default> {
     pub(crate) value: P,
-    pub(crate) _component: PhantomData<C>,
+    pub(crate) _component: PhantomData<fn() -> C>,
 }
 
 impl<C, P: Default> ComponentParameters<C, P> {
@@ -0,0 +1,293 @@
+//! Reusing provided services which are expensive to create
+
+use crate::{Component, HasComponent, Module, ModuleBuildContext, Provider, Scope};
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Clears the state a pooled service was left in by its previous user, before the service is
+/// handed out again by a [`ProviderPool`]. Implement it for the provided interface (ex.
+/// `impl Reset for dyn Buffer`).
+///
+/// [`ProviderPool`]: struct.ProviderPool.html
+pub trait Reset {
+    /// Clear the state of the service
+    fn reset(&mut self);
+}
+
+/// Parameters for [`ProviderPool`]
+///
+/// [`ProviderPool`]: struct.ProviderPool.html
+#[derive(Clone, Debug, Default)]
+pub struct ProviderPoolParameters {
+    /// The maximum number of idle services kept in the pool. Services which are returned to a
+    /// full pool are dropped. If not set, every returned service is kept.
+    pub max_idle: Option<usize>,
+}
+
+/// Keeps the services created by a [`PooledProvider`] after they are dropped, and hands them out
+/// again instead of creating new ones. This is useful for services which are expensive to create
+/// but cheap to reset, like large buffers or compiled templates.
+///
+/// The pool is a component, so its services are stored in the module. Add it to the module's
+/// components alongside the [`PooledProvider`] which uses it.
+///
+/// [`PooledProvider`]: struct.PooledProvider.html
+pub struct ProviderPool<I: ?Sized> {
+    shared: Arc<PoolShared<I>>,
+}
+
+/// The idle services, shared by the pool and the services it handed out
+struct PoolShared<I: ?Sized> {
+    idle: Mutex<Vec<Box<I>>>,
+    max_idle: Option<usize>,
+}
+
+impl<I: Reset + Send + ?Sized + 'static> ProviderPool<I> {
+    /// Create an empty pool which keeps up to `max_idle` idle services (or every returned service
+    /// if not set)
+    pub fn new(max_idle: Option<usize>) -> Self {
+        ProviderPool {
+            shared: Arc::new(PoolShared {
+                idle: Mutex::new(Vec::new()),
+                max_idle,
+            }),
+        }
+    }
+
+    /// Take an idle service from the pool, or create one via `provide` if the pool is empty.
+    ///
+    /// The service returns to the pool when it is dropped. If it is acquired within a [`Scope`],
+    /// it only returns to the pool once the scope has also ended, so services are not reused
+    /// while the request (or unit of work) which used them is still running.
+    ///
+    /// [`Scope`]: struct.Scope.html
+    pub fn acquire(
+        &self,
+        provide: impl FnOnce() -> Result<Box<I>, Box<dyn Error>>,
+    ) -> Result<Pooled<I>, Box<dyn Error>> {
+        let idle = lock(&self.shared.idle).pop();
+        let service = match idle {
+            Some(service) => service,
+            None => provide()?,
+        };
+
+        let scope_slot = Scope::current().map(|scope| {
+            let slot = Arc::new(Mutex::new(ScopeSlot::Leased));
+            let scope_end_slot = Arc::clone(&slot);
+            let shared = Arc::clone(&self.shared);
+
+            scope.on_drop(move || {
+                let state = mem::replace(&mut *lock(&scope_end_slot), ScopeSlot::Ended);
+
+                if let ScopeSlot::Returned(service) = state {
+                    shared.release(service);
+                }
+            });
+
+            slot
+        });
+
+        Ok(Pooled {
+            service: Some(service),
+            shared: Arc::clone(&self.shared),
+            scope_slot,
+        })
+    }
+
+    /// The number of idle services in the pool
+    pub fn idle(&self) -> usize {
+        lock(&self.shared.idle).len()
+    }
+
+    /// Drop the idle services in the pool
+    pub fn clear(&self) {
+        // Drop the services after releasing the lock
+        let idle: Vec<_> = lock(&self.shared.idle).drain(..).collect();
+        drop(idle);
+    }
+}
+
+impl<I: Reset + ?Sized> PoolShared<I> {
+    fn release(&self, mut service: Box<I>) {
+        service.reset();
+        let mut idle = lock(&self.idle);
+
+        if self.max_idle.map_or(true, |max_idle| idle.len() < max_idle) {
+            idle.push(service);
+        }
+    }
+}
+
+impl<I: Reset + Send + ?Sized + 'static> Default for ProviderPool<I> {
+    fn default() -> Self {
+        ProviderPool::new(None)
+    }
+}
+
+impl<I: ?Sized> Debug for ProviderPool<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProviderPool")
+            .field("idle", &lock(&self.shared.idle).len())
+            .field("max_idle", &self.shared.max_idle)
+            .finish()
+    }
+}
+
+impl<M: Module, I: Reset + Send + ?Sized + 'static> Component<M> for ProviderPool<I> {
+    type Interface = ProviderPool<I>;
+    type Parameters = ProviderPoolParameters;
+
+    fn build(_: &mut ModuleBuildContext<M>, params: Self::Parameters) -> Box<Self::Interface> {
+        Box::new(ProviderPool::new(params.max_idle))
+    }
+}
+
+/// Where a service acquired within a scope is while the scope is running
+enum ScopeSlot<I: ?Sized> {
+    /// The service is still in use
+    Leased,
+    /// The service was dropped, and returns to the pool when the scope ends
+    Returned(Box<I>),
+    /// The scope ended, so the service returns to the pool when it is dropped
+    Ended,
+}
+
+/// A service from a [`ProviderPool`], which returns to the pool (after being [`Reset`]) when
+/// dropped. Use [`into_inner`] to keep the service instead.
+///
+/// [`ProviderPool`]: struct.ProviderPool.html
+/// [`Reset`]: trait.Reset.html
+/// [`into_inner`]: #method.into_inner
+pub struct Pooled<I: Reset + ?Sized> {
+    service: Option<Box<I>>,
+    shared: Arc<PoolShared<I>>,
+    scope_slot: Option<Arc<Mutex<ScopeSlot<I>>>>,
+}
+
+impl<I: Reset + ?Sized> Pooled<I> {
+    /// Take the service out of the pool, so it is not returned when dropped
+    pub fn into_inner(mut self) -> Box<I> {
+        self.service.take().unwrap()
+    }
+}
+
+impl<I: Reset + ?Sized> Deref for Pooled<I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        self.service.as_ref().unwrap()
+    }
+}
+
+impl<I: Reset + ?Sized> DerefMut for Pooled<I> {
+    fn deref_mut(&mut self) -> &mut I {
+        self.service.as_mut().unwrap()
+    }
+}
+
+impl<I: Reset + ?Sized> Drop for Pooled<I> {
+    fn drop(&mut self) {
+        let service = match self.service.take() {
+            Some(service) => service,
+            None => return,
+        };
+
+        if let Some(scope_slot) = &self.scope_slot {
+            let mut state = lock(scope_slot);
+
+            if let ScopeSlot::Leased = *state {
+                *state = ScopeSlot::Returned(service);
+                return;
+            }
+        }
+
+        self.shared.release(service);
+    }
+}
+
+impl<I: Reset + Debug + ?Sized> Debug for Pooled<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Pooled").field(&&**self).finish()
+    }
+}
+
+/// Wraps a provider so its services are reused via the [`ProviderPool`] component of the
+/// provided interface, which the module must also contain. The provided service is a [`Pooled`]
+/// guard, which returns the service to the pool when dropped.
+///
+/// # Example
+/// ```
+/// use shaku::{module, HasProvider, Module, Pooled, PooledProvider, Provider, ProviderPool, Reset};
+/// use std::error::Error;
+///
+/// struct Buffer(Vec<u8>);
+///
+/// impl<M: Module> Provider<M> for Buffer {
+///     type Interface = Buffer;
+///
+///     fn provide(_: &M) -> Result<Box<Buffer>, Box<dyn Error>> {
+///         Ok(Box::new(Buffer(Vec::with_capacity(1024 * 1024))))
+///     }
+/// }
+///
+/// impl Reset for Buffer {
+///     fn reset(&mut self) {
+///         self.0.clear();
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [ProviderPool<Buffer>],
+///         providers = [PooledProvider<Buffer>]
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+///
+/// let mut buffer: Box<Pooled<Buffer>> = module.provide().unwrap();
+/// buffer.0.extend_from_slice(b"response");
+/// drop(buffer);
+///
+/// // The buffer is reused, but its contents were cleared
+/// let buffer: Box<Pooled<Buffer>> = module.provide().unwrap();
+/// assert!(buffer.0.is_empty());
+/// assert_eq!(buffer.0.capacity(), 1024 * 1024);
+/// # }
+/// ```
+///
+/// [`ProviderPool`]: struct.ProviderPool.html
+/// [`Pooled`]: struct.Pooled.html
+pub struct PooledProvider<P> {
+    _provider: PhantomData<P>,
+}
+
+impl<M, P> Provider<M> for PooledProvider<P>
+where
+    M: Module + HasComponent<ProviderPool<P::Interface>>,
+    P: Provider<M>,
+    P::Interface: Reset + Send,
+{
+    type Interface = Pooled<P::Interface>;
+
+    fn provide(module: &M) -> Result<Box<Self::Interface>, Box<dyn Error>> {
+        let pool: &ProviderPool<P::Interface> = module.resolve_ref();
+        pool.acquire(|| P::provide(module)).map(Box::new)
+    }
+}
+
+impl<P> Debug for PooledProvider<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PooledProvider").finish()
+    }
+}
+
+fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // Resetting or dropping a service can't panic while the lock is held, so ignore poisoning
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -0,0 +1,130 @@
+//! Values from the caller's context which are available while providing a service
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+thread_local! {
+    // A const initializer would require Rust 1.59, which is above the MSRV
+    #[allow(clippy::thread_local_initializer_can_be_made_const)]
+    static CURRENT_CONTEXTS: RefCell<Vec<ProvideContext>> = RefCell::new(Vec::new());
+}
+
+/// Values from the caller's context (ex. the current request's `tracing::Span`) which providers
+/// can use while creating a service. The context is passed in via
+/// [`HasProvider::provide_with_context`], and providers read it via [`ProvideContext::current`].
+///
+/// The context is current for the whole provide call, so it is also available to providers which
+/// are called while creating the service's dependencies. The web framework integrations (with
+/// their `tracing` feature enabled) pass the request's span in the context.
+///
+/// # Example
+/// ```
+/// use shaku::{module, HasProvider, ProvideContext, Provider};
+/// use std::error::Error;
+///
+/// struct RequestId(u64);
+///
+/// trait Client {
+///     fn request_id(&self) -> Option<u64>;
+/// }
+///
+/// struct ClientImpl {
+///     request_id: Option<u64>,
+/// }
+/// impl Client for ClientImpl {
+///     fn request_id(&self) -> Option<u64> {
+///         self.request_id
+///     }
+/// }
+///
+/// impl<M: shaku::Module> Provider<M> for ClientImpl {
+///     type Interface = dyn Client;
+///
+///     fn provide(_: &M) -> Result<Box<dyn Client>, Box<dyn Error>> {
+///         let request_id = ProvideContext::current()
+///             .and_then(|context| context.get::<RequestId>().map(|id| id.0));
+///
+///         Ok(Box::new(ClientImpl { request_id }))
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [],
+///         providers = [ClientImpl]
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let context = ProvideContext::new().with(RequestId(7));
+///
+/// let client: Box<dyn Client> = module.provide_with_context(&context).unwrap();
+/// assert_eq!(client.request_id(), Some(7));
+///
+/// let client: Box<dyn Client> = module.provide().unwrap();
+/// assert_eq!(client.request_id(), None);
+/// # }
+/// ```
+///
+/// [`HasProvider::provide_with_context`]: trait.HasProvider.html#method.provide_with_context
+/// [`ProvideContext::current`]: struct.ProvideContext.html#method.current
+#[derive(Clone, Debug, Default)]
+pub struct ProvideContext {
+    values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl ProvideContext {
+    /// Create an empty context
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a value to the context, replacing any previous value of the same type
+    pub fn with<T: Any + Send + Sync>(mut self, value: T) -> Self {
+        self.insert(value);
+        self
+    }
+
+    /// Add a value to the context, replacing any previous value of the same type
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) {
+        self.values.insert(TypeId::of::<T>(), Arc::new(value));
+    }
+
+    /// Get a value from the context by its type
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Get the context of the innermost [`HasProvider::provide_with_context`] call on this
+    /// thread, if any.
+    ///
+    /// [`HasProvider::provide_with_context`]: trait.HasProvider.html#method.provide_with_context
+    pub fn current() -> Option<ProvideContext> {
+        CURRENT_CONTEXTS.with(|contexts| contexts.borrow().last().cloned())
+    }
+
+    /// Make this context current until the returned guard is dropped
+    pub(crate) fn enter(&self) -> ProvideContextGuard {
+        CURRENT_CONTEXTS.with(|contexts| contexts.borrow_mut().push(self.clone()));
+        ProvideContextGuard { _private: () }
+    }
+}
+
+/// Removes the context entered via [`ProvideContext::enter`] when dropped (including during a
+/// panic).
+pub(crate) struct ProvideContextGuard {
+    _private: (),
+}
+
+impl Drop for ProvideContextGuard {
+    fn drop(&mut self) {
+        CURRENT_CONTEXTS.with(|contexts| {
+            contexts.borrow_mut().pop();
+        });
+    }
+}
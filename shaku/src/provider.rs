@@ -1,8 +1,14 @@
 //! This module contains trait definitions for provided services and interfaces
 
 use crate::module::ModuleInterface;
-use crate::Module;
-use std::error::Error;
+#[cfg(feature = "std")]
+use crate::ProvideContext;
+use crate::{Error, Interface, Module};
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::{type_name, Any};
+use core::marker::PhantomData;
 
 /// Like [`Component`]s, providers provide a service by implementing an interface.
 ///
@@ -17,13 +23,33 @@ use std::error::Error;
 /// [provider getting started guide]: guide/provider/index.html
 pub trait Provider<M: Module>: 'static {
     /// The trait/interface which this provider implements
-    type Interface: ?Sized;
+    type Interface: ProvidedInterface + ?Sized;
 
     /// Provides the service, possibly resolving other components/providers
     /// to do so.
     fn provide(module: &M) -> Result<Box<Self::Interface>, Box<dyn Error>>;
+
+    /// The type names of the interfaces which this provider resolves or provides (its injected
+    /// components and provided services). This is used to find services which are unreachable from
+    /// the module interface. The default implementation returns no dependencies.
+    fn dependencies() -> Vec<&'static str> {
+        Vec::new()
+    }
 }
 
+trait_alias!(
+    /// Provided interfaces must be `'static` (hence the `Any` requirement). Unlike [`Interface`],
+    /// they never need to be [`ThreadSafe`], since each provided service is owned by the caller
+    /// instead of being shared by the module.
+    ///
+    /// Interface traits do not need to inherit this trait, since all `'static` trait objects
+    /// implement it.
+    ///
+    /// [`Interface`]: trait.Interface.html
+    /// [`ThreadSafe`]: trait.ThreadSafe.html
+    pub ProvidedInterface = Any
+);
+
 /// The type signature of [`Provider::provide`]. This is used when overriding a
 /// provider via [`ModuleBuilder::with_provider_override`]
 ///
@@ -39,6 +65,50 @@ pub type ProviderFn<M, I> = Box<dyn (Fn(&M) -> Result<Box<I>, Box<dyn Error>>)>;
 #[cfg(feature = "thread_safe")]
 pub type ProviderFn<M, I> = Box<dyn (Fn(&M) -> Result<Box<I>, Box<dyn Error>>) + Send + Sync>;
 
+/// A plain function pointer to [`Provider::provide`]
+///
+/// [`Provider::provide`]: trait.Provider.html#tymethod.provide
+#[doc(hidden)]
+pub type ProviderFnPtr<M, I> = fn(&M) -> Result<Box<I>, Box<dyn Error>>;
+
+/// The provider function stored by modules created by the [`module`] macro. Providers which were
+/// not overridden are stored as a plain function pointer to [`Provider::provide`], so they don't
+/// need an allocation (or an `Arc` clone when the module is cloned). Only overrides are stored
+/// as a shared [`ProviderFn`].
+///
+/// [`module`]: macro.module.html
+/// [`Provider::provide`]: trait.Provider.html#tymethod.provide
+/// [`ProviderFn`]: type.ProviderFn.html
+#[doc(hidden)]
+pub enum ModuleProviderFn<M, I: ?Sized> {
+    /// The provider's own `provide` function
+    Provide(ProviderFnPtr<M, I>),
+    /// A provider override set on the module builder
+    Override(Arc<ProviderFn<M, I>>),
+}
+
+impl<M, I: ?Sized> ModuleProviderFn<M, I> {
+    /// Create a service via the stored function
+    pub fn provide(&self, module: &M) -> Result<Box<I>, Box<dyn Error>> {
+        match self {
+            ModuleProviderFn::Provide(provide) => provide(module),
+            ModuleProviderFn::Override(provider_fn) => provider_fn(module),
+        }
+    }
+}
+
+// The Clone derive would require M and I to be Clone
+impl<M, I: ?Sized> Clone for ModuleProviderFn<M, I> {
+    fn clone(&self) -> Self {
+        match self {
+            ModuleProviderFn::Provide(provide) => ModuleProviderFn::Provide(*provide),
+            ModuleProviderFn::Override(provider_fn) => {
+                ModuleProviderFn::Override(Arc::clone(provider_fn))
+            }
+        }
+    }
+}
+
 /// Indicates that a module contains a provider which implements the interface.
 pub trait HasProvider<I: ?Sized>: ModuleInterface {
     /// Create a service using the provider registered with the interface `I`.
@@ -70,4 +140,247 @@ pub trait HasProvider<I: ?Sized>: ModuleInterface {
     /// # }
     /// ```
     fn provide(&self) -> Result<Box<I>, Box<dyn Error>>;
+
+    /// Create a service like [`provide`], but with the context available to providers via
+    /// [`ProvideContext::current`] while the service (and its provided dependencies) are created.
+    ///
+    /// See [`ProvideContext`] for an example.
+    ///
+    /// [`provide`]: #tymethod.provide
+    /// [`ProvideContext::current`]: struct.ProvideContext.html#method.current
+    /// [`ProvideContext`]: struct.ProvideContext.html
+    #[cfg(feature = "std")]
+    fn provide_with_context(&self, context: &ProvideContext) -> Result<Box<I>, Box<dyn Error>> {
+        let _guard = context.enter();
+        self.provide()
+    }
+
+    /// The type name of the interface (ex. `dyn my_crate::Foo`), for logging and error messages in
+    /// code which is generic over the module and interface. Call it via
+    /// `HasProvider::<I>::interface_name(&module)` if the module has several providers.
+    fn interface_name(&self) -> &'static str {
+        type_name::<I>()
+    }
+}
+
+/// Forwards to the shared module, so generic code over modules also accepts `Arc<M>`
+impl<I: ?Sized, M: HasProvider<I> + ?Sized> HasProvider<I> for Arc<M>
+where
+    Arc<M>: ModuleInterface,
+{
+    fn provide(&self) -> Result<Box<I>, Box<dyn Error>> {
+        M::provide(&**self)
+    }
+
+    #[cfg(feature = "std")]
+    fn provide_with_context(&self, context: &ProvideContext) -> Result<Box<I>, Box<dyn Error>> {
+        M::provide_with_context(&**self, context)
+    }
+}
+
+/// Forwards to the boxed module, so generic code over modules also accepts `Box<M>`
+impl<I: ?Sized, M: HasProvider<I> + ?Sized> HasProvider<I> for Box<M>
+where
+    Box<M>: ModuleInterface,
+{
+    fn provide(&self) -> Result<Box<I>, Box<dyn Error>> {
+        M::provide(&**self)
+    }
+
+    #[cfg(feature = "std")]
+    fn provide_with_context(&self, context: &ProvideContext) -> Result<Box<I>, Box<dyn Error>> {
+        M::provide_with_context(&**self, context)
+    }
+}
+
+/// Create multiple services from a provider at once. This is useful for worker-pool scenarios,
+/// where a number of identical consumers need to be created with shared dependencies.
+///
+/// This trait is automatically implemented for every module which implements [`HasProvider`],
+/// including module interface trait objects (ex. `dyn MyModule`).
+///
+/// [`HasProvider`]: trait.HasProvider.html
+pub trait HasBatchProvider<I: ?Sized>: HasProvider<I> {
+    /// Create an iterator which provides `count` new instances of the service. Each instance is
+    /// created when the iterator is advanced, so providers may fail part way through.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, HasBatchProvider, Provider};
+    /// #
+    /// # trait Worker {}
+    /// #
+    /// # #[derive(Provider)]
+    /// # #[shaku(interface = Worker)]
+    /// # struct WorkerImpl;
+    /// # impl Worker for WorkerImpl {}
+    /// #
+    /// # module! {
+    /// #     TestModule {
+    /// #         components = [],
+    /// #         providers = [WorkerImpl]
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # let module = TestModule::builder().build();
+    /// #
+    /// for worker in HasBatchProvider::<dyn Worker>::provide_iter(&module, 4) {
+    ///     let worker: Box<dyn Worker> = worker.unwrap();
+    /// }
+    /// # }
+    /// ```
+    fn provide_iter(&self, count: usize) -> ProvideIter<'_, Self, I> {
+        ProvideIter {
+            module: self,
+            remaining: count,
+            _interface: PhantomData,
+        }
+    }
+
+    /// Create `count` new instances of the service. If any of the services fail to be created,
+    /// the first error is returned.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, HasBatchProvider, Provider};
+    /// #
+    /// # trait Worker {}
+    /// #
+    /// # #[derive(Provider)]
+    /// # #[shaku(interface = Worker)]
+    /// # struct WorkerImpl;
+    /// # impl Worker for WorkerImpl {}
+    /// #
+    /// # module! {
+    /// #     TestModule {
+    /// #         components = [],
+    /// #         providers = [WorkerImpl]
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # let module = TestModule::builder().build();
+    /// #
+    /// let workers: Vec<Box<dyn Worker>> = module.provide_batch(4).unwrap();
+    /// # }
+    /// ```
+    fn provide_batch(&self, count: usize) -> Result<Vec<Box<I>>, Box<dyn Error>> {
+        self.provide_iter(count).collect()
+    }
+}
+
+impl<I: ?Sized, M: HasProvider<I> + ?Sized> HasBatchProvider<I> for M {}
+
+/// An iterator which creates services from a provider. See [`HasBatchProvider::provide_iter`].
+///
+/// [`HasBatchProvider::provide_iter`]: trait.HasBatchProvider.html#method.provide_iter
+pub struct ProvideIter<'a, M: HasProvider<I> + ?Sized, I: ?Sized> {
+    module: &'a M,
+    remaining: usize,
+    _interface: PhantomData<fn() -> Box<I>>,
+}
+
+impl<'a, M: HasProvider<I> + ?Sized, I: ?Sized> Iterator for ProvideIter<'a, M, I> {
+    type Item = Result<Box<I>, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+        Some(self.module.provide())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, M: HasProvider<I> + ?Sized, I: ?Sized> ExactSizeIterator for ProvideIter<'a, M, I> {}
+
+/// Creates services on demand, like [`HasProvider::provide`]. Components which need to create
+/// provided services repeatedly can inject a factory (ex. `Arc<dyn Factory<dyn Foo>>`) instead of
+/// depending on the whole module.
+///
+/// The [`module`] macro registers a factory component for each service provided by a submodule.
+/// The module's own providers are not available as factory components, since components are built
+/// before the module exists. Once the module is built and shared, use [`ModuleFactory`] instead.
+///
+/// # Example
+/// ```
+/// # use shaku::{module, Component, Factory, HasComponent, Interface, Provider};
+/// # use std::sync::Arc;
+/// #
+/// trait Connection {}
+///
+/// #[derive(Provider)]
+/// #[shaku(interface = Connection)]
+/// struct ConnectionImpl;
+/// impl Connection for ConnectionImpl {}
+///
+/// trait Worker: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Worker)]
+/// struct WorkerImpl {
+///     #[shaku(inject)]
+///     connections: Arc<dyn Factory<dyn Connection>>,
+/// }
+/// impl Worker for WorkerImpl {}
+///
+/// module! {
+///     DatabaseModule {
+///         components = [],
+///         providers = [ConnectionImpl]
+///     }
+/// }
+///
+/// module! {
+///     WorkerModule {
+///         components = [WorkerImpl],
+///         providers = [],
+///
+///         use DatabaseModule {
+///             components = [],
+///             providers = [dyn Connection]
+///         }
+///     }
+/// }
+///
+/// # fn main() {
+/// let database_module = Arc::new(DatabaseModule::builder().build());
+/// let module = WorkerModule::builder(database_module).build();
+/// let connections: &dyn Factory<dyn Connection> = module.resolve_ref();
+/// let connection: Box<dyn Connection> = connections.create().unwrap();
+/// # }
+/// ```
+///
+/// [`HasProvider::provide`]: trait.HasProvider.html#tymethod.provide
+/// [`module`]: macro.module.html
+/// [`ModuleFactory`]: struct.ModuleFactory.html
+pub trait Factory<I: ?Sized>: Interface {
+    /// Create a new instance of the service
+    fn create(&self) -> Result<Box<I>, Box<dyn Error>>;
+}
+
+/// A [`Factory`] which creates services using the providers of a shared module.
+///
+/// [`Factory`]: trait.Factory.html
+pub struct ModuleFactory<M: ?Sized> {
+    module: Arc<M>,
+}
+
+impl<M: ?Sized> ModuleFactory<M> {
+    /// Create a factory which uses the module's providers
+    pub fn new(module: Arc<M>) -> Self {
+        ModuleFactory { module }
+    }
+}
+
+impl<I: ?Sized, M: HasProvider<I> + ?Sized> Factory<I> for ModuleFactory<M> {
+    fn create(&self) -> Result<Box<I>, Box<dyn Error>> {
+        self.module.provide()
+    }
 }
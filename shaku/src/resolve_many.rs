@@ -0,0 +1,90 @@
+//! Resolving several components in one call
+
+use crate::{HasComponent, Interface, ModuleInterface};
+use alloc::sync::Arc;
+
+/// A tuple of components which can be resolved from the module `M` via [`ResolveMany`].
+/// Implemented for tuples of up to 12 `Arc<I>`, where the module implements `HasComponent<I>` for
+/// each interface.
+///
+/// [`ResolveMany`]: trait.ResolveMany.html
+pub trait ResolveTuple<M: ?Sized> {
+    /// Resolve each component of the tuple
+    fn resolve_tuple(module: &M) -> Self;
+}
+
+/// Resolve several components in one call, ex. in a composition root which hands the components to
+/// the rest of the application. This trait is implemented for every module.
+///
+/// The components are given as a tuple of `Arc<I>` (ex. `(Arc<dyn Logger>, Arc<dyn Database>)`),
+/// which can often be inferred from the variables they are assigned to.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, Interface, ResolveMany};
+/// use std::sync::Arc;
+///
+/// trait Logger: Interface {}
+/// trait Database: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Logger)]
+/// struct LoggerImpl;
+/// impl Logger for LoggerImpl {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Database)]
+/// struct DatabaseImpl;
+/// impl Database for DatabaseImpl {}
+///
+/// module! {
+///     MyModule {
+///         components = [LoggerImpl, DatabaseImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+///
+/// let (logger, database) = module.resolve_many::<(Arc<dyn Logger>, Arc<dyn Database>)>();
+///
+/// // Or via the variable types
+/// let (logger, database): (Arc<dyn Logger>, Arc<dyn Database>) = module.resolve_many();
+/// # }
+/// ```
+pub trait ResolveMany {
+    /// Resolve each component of the tuple `T`
+    fn resolve_many<T: ResolveTuple<Self>>(&self) -> T {
+        T::resolve_tuple(self)
+    }
+}
+
+impl<M: ModuleInterface + ?Sized> ResolveMany for M {}
+
+macro_rules! impl_resolve_tuple {
+    ($($interface:ident),+) => {
+        impl<M, $($interface),+> ResolveTuple<M> for ($(Arc<$interface>,)+)
+        where
+            M: ?Sized $(+ HasComponent<$interface>)+,
+            $($interface: Interface + ?Sized),+
+        {
+            fn resolve_tuple(module: &M) -> Self {
+                ($(HasComponent::<$interface>::resolve(module),)+)
+            }
+        }
+    };
+}
+
+impl_resolve_tuple!(I1);
+impl_resolve_tuple!(I1, I2);
+impl_resolve_tuple!(I1, I2, I3);
+impl_resolve_tuple!(I1, I2, I3, I4);
+impl_resolve_tuple!(I1, I2, I3, I4, I5);
+impl_resolve_tuple!(I1, I2, I3, I4, I5, I6);
+impl_resolve_tuple!(I1, I2, I3, I4, I5, I6, I7);
+impl_resolve_tuple!(I1, I2, I3, I4, I5, I6, I7, I8);
+impl_resolve_tuple!(I1, I2, I3, I4, I5, I6, I7, I8, I9);
+impl_resolve_tuple!(I1, I2, I3, I4, I5, I6, I7, I8, I9, I10);
+impl_resolve_tuple!(I1, I2, I3, I4, I5, I6, I7, I8, I9, I10, I11);
+impl_resolve_tuple!(I1, I2, I3, I4, I5, I6, I7, I8, I9, I10, I11, I12);
@@ -0,0 +1,133 @@
+//! Retrying providers which fail with transient errors
+
+use crate::{Module, Provider};
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::thread;
+use std::time::Duration;
+
+/// How often, and with what delay, a failing provider is retried. The delay doubles after each
+/// failed attempt (exponential backoff), starting at `backoff`.
+///
+/// Retrying is set up via `#[shaku(retry(times = 3, backoff_ms = 100))]` on a derived provider, or
+/// via [`RetryProvider`] for other providers.
+///
+/// [`RetryProvider`]: struct.RetryProvider.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    retries: u32,
+    backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Create a policy which retries up to `retries` times (so the provider is called at most
+    /// `retries + 1` times), waiting `backoff` before the first retry.
+    pub const fn new(retries: u32, backoff: Duration) -> Self {
+        RetryPolicy { retries, backoff }
+    }
+
+    /// The maximum number of retries
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// The delay before the first retry
+    pub fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    /// The delay before the given retry (starting at 0)
+    pub fn delay(&self, retry: u32) -> Duration {
+        2u32.checked_pow(retry)
+            .and_then(|factor| self.backoff.checked_mul(factor))
+            .unwrap_or_else(|| Duration::from_secs(u64::max_value()))
+    }
+
+    /// Call `provide` until it succeeds or the retries run out. The last error is returned if
+    /// every attempt fails.
+    pub fn run<T>(
+        &self,
+        mut provide: impl FnMut() -> Result<T, Box<dyn Error>>,
+    ) -> Result<T, Box<dyn Error>> {
+        let mut retry = 0;
+
+        loop {
+            match provide() {
+                Ok(value) => return Ok(value),
+                Err(error) if retry >= self.retries => return Err(error),
+                Err(_) => {
+                    thread::sleep(self.delay(retry));
+                    retry += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Supplies the [`RetryPolicy`] of a [`RetryProvider`].
+///
+/// [`RetryPolicy`]: struct.RetryPolicy.html
+/// [`RetryProvider`]: struct.RetryProvider.html
+pub trait Retry: 'static {
+    /// The policy to retry with
+    const POLICY: RetryPolicy;
+}
+
+/// Wraps a provider so failures are retried according to the policy `R`. This is useful for
+/// manually implemented providers; derived providers can use
+/// `#[shaku(retry(times = 3, backoff_ms = 100))]` instead.
+///
+/// # Example
+/// ```
+/// use shaku::{module, HasProvider, Module, Provider, Retry, RetryPolicy, RetryProvider};
+/// use std::error::Error;
+/// use std::time::Duration;
+///
+/// struct Connection;
+///
+/// impl<M: Module> Provider<M> for Connection {
+///     type Interface = Connection;
+///
+///     fn provide(_: &M) -> Result<Box<Connection>, Box<dyn Error>> {
+///         // Connect to the database, which may fail temporarily
+///         Ok(Box::new(Connection))
+///     }
+/// }
+///
+/// struct ConnectionRetry;
+///
+/// impl Retry for ConnectionRetry {
+///     const POLICY: RetryPolicy = RetryPolicy::new(3, Duration::from_millis(100));
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [],
+///         providers = [RetryProvider<Connection, ConnectionRetry>]
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let connection: Box<Connection> = module.provide().unwrap();
+/// # }
+/// ```
+pub struct RetryProvider<P, R> {
+    _provider: PhantomData<P>,
+    _retry: PhantomData<R>,
+}
+
+impl<M: Module, P: Provider<M>, R: Retry> Provider<M> for RetryProvider<P, R> {
+    type Interface = P::Interface;
+
+    fn provide(module: &M) -> Result<Box<Self::Interface>, Box<dyn Error>> {
+        R::POLICY.run(|| P::provide(module))
+    }
+}
+
+impl<P, R> Debug for RetryProvider<P, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryProvider").finish()
+    }
+}
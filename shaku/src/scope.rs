@@ -0,0 +1,132 @@
+//! Cleanup callbacks for the services provided within a scope (ex. a request)
+
+use crate::ProvideContext;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+type DropCallback = Box<dyn FnOnce() + Send>;
+
+/// A scope (ex. a request or unit of work) which provided services can register cleanup callbacks
+/// with, via [`Scope::on_drop`]. When the scope ends (the last handle to it is dropped), the
+/// callbacks run in reverse order of registration, so services are cleaned up before the services
+/// they were created from (ex. a transaction is rolled back before its connection is released).
+///
+/// The scope is passed to providers in a [`ProvideContext`], and providers find it via
+/// [`Scope::current`]. Since the context holds a handle to the scope, the scope ends once both the
+/// caller's handle and the context are dropped.
+///
+/// If a callback panics, the callbacks registered before it are dropped without running.
+///
+/// # Example
+/// ```
+/// use shaku::{module, HasProvider, ProvideContext, Provider, Scope};
+/// use std::error::Error;
+/// use std::sync::{Arc, Mutex};
+///
+/// #[derive(Default)]
+/// struct Log(Mutex<Vec<String>>);
+///
+/// trait Transaction {}
+///
+/// struct TransactionImpl;
+/// impl Transaction for TransactionImpl {}
+///
+/// impl<M: shaku::Module> Provider<M> for TransactionImpl {
+///     type Interface = dyn Transaction;
+///
+///     fn provide(_: &M) -> Result<Box<dyn Transaction>, Box<dyn Error>> {
+///         let context = ProvideContext::current().unwrap();
+///         let log = Arc::clone(context.get::<Arc<Log>>().unwrap());
+///
+///         if let Some(scope) = Scope::current() {
+///             scope.on_drop(move || log.0.lock().unwrap().push("rollback".to_string()));
+///         }
+///
+///         Ok(Box::new(TransactionImpl))
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [],
+///         providers = [TransactionImpl]
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let log = Arc::new(Log::default());
+/// let scope = Scope::new();
+/// let context = ProvideContext::new().with(scope.clone()).with(Arc::clone(&log));
+///
+/// let _transaction: Box<dyn Transaction> = module.provide_with_context(&context).unwrap();
+/// assert!(log.0.lock().unwrap().is_empty());
+///
+/// // End the scope
+/// drop(context);
+/// drop(scope);
+/// assert_eq!(*log.0.lock().unwrap(), vec!["rollback"]);
+/// # }
+/// ```
+///
+/// [`Scope::on_drop`]: #method.on_drop
+/// [`Scope::current`]: #method.current
+/// [`ProvideContext`]: struct.ProvideContext.html
+#[derive(Clone, Default)]
+pub struct Scope {
+    callbacks: Arc<ScopeCallbacks>,
+}
+
+impl Scope {
+    /// Create a new scope
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the scope from the current [`ProvideContext`], if any
+    ///
+    /// [`ProvideContext`]: struct.ProvideContext.html
+    pub fn current() -> Option<Scope> {
+        ProvideContext::current().and_then(|context| context.get::<Scope>().cloned())
+    }
+
+    /// Register a callback to run when the scope ends. Callbacks run in reverse order of
+    /// registration.
+    pub fn on_drop<F: FnOnce() + Send + 'static>(&self, callback: F) {
+        self.callbacks.lock().push(Box::new(callback));
+    }
+
+    /// The number of callbacks which will run when the scope ends
+    pub fn pending_callbacks(&self) -> usize {
+        self.callbacks.lock().len()
+    }
+}
+
+impl fmt::Debug for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope")
+            .field("pending_callbacks", &self.pending_callbacks())
+            .finish()
+    }
+}
+
+/// The callbacks shared by the handles of a scope, which run when the last handle is dropped
+#[derive(Default)]
+struct ScopeCallbacks(Mutex<Vec<DropCallback>>);
+
+impl ScopeCallbacks {
+    fn lock(&self) -> MutexGuard<'_, Vec<DropCallback>> {
+        // A callback can't panic while the lock is held, so ignore poisoning
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for ScopeCallbacks {
+    fn drop(&mut self) {
+        let callbacks = self.0.get_mut().unwrap_or_else(|e| e.into_inner());
+
+        while let Some(callback) = callbacks.pop() {
+            callback();
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Storage for components which are not thread safe
+
+use crate::LocalInterface;
+use std::fmt;
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::thread::{self, ThreadId};
+
+/// Holds a service which can only be used on the thread which created it. This allows storing a
+/// component which is not `Send` or `Sync` (see [`LocalInterface`]) in a module, even when the
+/// `thread_safe` feature is turned on.
+///
+/// `ThreadBound` is used as the component's interface, and is usually generated by adding
+/// `thread_bound` to the derive attribute (ex. `#[shaku(interface = LocalThing, thread_bound)]`).
+/// The component is then resolved as `Arc<ThreadBound<dyn LocalThing>>`.
+///
+/// The component is bound to the thread which builds it (the thread which builds the module, or
+/// the thread which first resolves a lazy component). Accessing it from another thread returns
+/// `None` from [`get`], or panics when dereferencing. If the `ThreadBound` is dropped on another
+/// thread, the component is leaked instead of being dropped.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, HasComponent, LocalInterface, ThreadBound};
+/// use std::cell::Cell;
+/// use std::sync::Arc;
+///
+/// trait Counter: LocalInterface {
+///     fn increment(&self) -> usize;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Counter, thread_bound)]
+/// struct CounterImpl {
+///     #[shaku(default)]
+///     count: Cell<usize>,
+/// }
+///
+/// impl Counter for CounterImpl {
+///     fn increment(&self) -> usize {
+///         self.count.set(self.count.get() + 1);
+///         self.count.get()
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [CounterImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let counter: Arc<ThreadBound<dyn Counter>> = module.resolve();
+///
+/// assert_eq!(counter.increment(), 1);
+/// assert!(std::thread::spawn(move || counter.get().is_none()).join().unwrap());
+/// # }
+/// ```
+///
+/// [`LocalInterface`]: trait.LocalInterface.html
+/// [`get`]: #method.get
+pub struct ThreadBound<I: LocalInterface + ?Sized> {
+    value: ManuallyDrop<Box<I>>,
+    thread_id: ThreadId,
+}
+
+// The value is only accessed (including being dropped) on the thread which created it
+unsafe impl<I: LocalInterface + ?Sized> Send for ThreadBound<I> {}
+unsafe impl<I: LocalInterface + ?Sized> Sync for ThreadBound<I> {}
+
+impl<I: LocalInterface + ?Sized> ThreadBound<I> {
+    /// Bind the value to the current thread
+    pub fn new(value: Box<I>) -> Self {
+        ThreadBound {
+            value: ManuallyDrop::new(value),
+            thread_id: thread::current().id(),
+        }
+    }
+
+    /// Get the value if called from the thread which created it
+    pub fn get(&self) -> Option<&I> {
+        if self.is_owner_thread() {
+            Some(&**self.value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if called from the thread which created the value
+    pub fn is_owner_thread(&self) -> bool {
+        thread::current().id() == self.thread_id
+    }
+}
+
+impl<I: LocalInterface + ?Sized> Deref for ThreadBound<I> {
+    type Target = I;
+
+    fn deref(&self) -> &Self::Target {
+        match self.get() {
+            Some(value) => value,
+            None => panic!(
+                "ThreadBound<{}> was accessed from a thread other than the one which created it",
+                std::any::type_name::<I>()
+            ),
+        }
+    }
+}
+
+impl<I: LocalInterface + ?Sized> Drop for ThreadBound<I> {
+    fn drop(&mut self) {
+        // Dropping the value on another thread would not be safe, so it is leaked instead
+        if self.is_owner_thread() {
+            unsafe { ManuallyDrop::drop(&mut self.value) }
+        }
+    }
+}
+
+impl<I: LocalInterface + ?Sized> fmt::Debug for ThreadBound<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThreadBound")
+            .field("thread_id", &self.thread_id)
+            .finish()
+    }
+}
@@ -0,0 +1,48 @@
+//! Debug checks that modules which are not thread safe stay on one thread
+
+#[cfg(not(feature = "thread_safe"))]
+use std::thread::{self, ThreadId};
+
+/// Records the thread which built a module, so that components are only used on the thread they
+/// were created on. Components are created on the thread which builds the module (or which first
+/// resolves a lazy component, which is checked first).
+///
+/// The check is only done if the `thread_safe` feature is turned off. Otherwise this is a no-op.
+/// When `thread_safe` is turned off, modules are not `Send` or `Sync`, so this catches modules
+/// which were moved across threads via `unsafe` code.
+#[doc(hidden)]
+#[derive(Clone, Debug)]
+pub struct ThreadConfinement {
+    #[cfg(not(feature = "thread_safe"))]
+    owner: ThreadId,
+}
+
+impl ThreadConfinement {
+    /// Record the current thread as the owner
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        ThreadConfinement {
+            #[cfg(not(feature = "thread_safe"))]
+            owner: thread::current().id(),
+        }
+    }
+
+    /// Panic if the current thread is not the owner
+    #[cfg(not(feature = "thread_safe"))]
+    pub fn check(&self, service_name: &str) {
+        let current = thread::current().id();
+
+        if current != self.owner {
+            panic!(
+                "`{}` was created on thread {:?}, but used on thread {:?}. Modules are not \
+                 thread safe when the `thread_safe` feature is turned off.",
+                service_name, self.owner, current
+            );
+        }
+    }
+
+    /// Panic if the current thread is not the owner
+    #[cfg(feature = "thread_safe")]
+    #[inline(always)]
+    pub fn check(&self, _service_name: &str) {}
+}
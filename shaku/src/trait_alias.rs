@@ -1,10 +1,13 @@
 // Adapted from https://stackoverflow.com/a/30293051/3267834
 // FIXME: Use real trait aliases when they are stabilized:
 //        https://github.com/rust-lang/rust/issues/41517
+//
+// The alias is implemented for unsized types too (ex. trait objects), and may
+// have no bounds at all (ex. `ThreadSafe` when the `thread_safe` feature is off).
 macro_rules! trait_alias {
-    ($(#[$attributes:meta])* $visibility:vis $name:ident = $base1:ident $(+ $base2:ident)*) => {
+    ($(#[$attributes:meta])* $visibility:vis $name:ident = $($bounds:tt)*) => {
         $(#[$attributes])*
-        $visibility trait $name: $base1 $(+ $base2)* { }
-        impl<T: $base1 $(+ $base2)*> $name for T { }
+        $visibility trait $name: $($bounds)* { }
+        impl<T: ?Sized + $($bounds)*> $name for T { }
     };
 }
@@ -0,0 +1,197 @@
+//! Runtime verification of a module's services
+
+use crate::{ModuleBuilder, ModuleServices, ServiceKind};
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Build the module and check that every service it exposes can be resolved or provided. This is
+/// mainly useful for manually implemented modules, which are not checked at compile time like
+/// modules created via the [`module`] macro.
+///
+/// The module is built with default parameters and no overrides. Use [`ModuleBuilder::verify`] to
+/// verify a customized module builder (ex. a module with submodules).
+///
+/// Note that panics are caught while verifying, but the panic hook will still run (which by
+/// default prints the panic message).
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, Interface};
+///
+/// trait Foo: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Foo)]
+/// struct FooImpl;
+/// impl Foo for FooImpl {}
+///
+/// module! {
+///     MyModule {
+///         components = [FooImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let report = shaku::verify::<MyModule>();
+/// assert!(report.is_ok(), "{}", report);
+/// # }
+/// ```
+///
+/// [`module`]: macro.module.html
+/// [`ModuleBuilder::verify`]: struct.ModuleBuilder.html#method.verify
+pub fn verify<M: ModuleServices>() -> VerificationReport
+where
+    M::Submodules: Default,
+{
+    ModuleBuilder::<M>::with_submodules(Default::default()).verify()
+}
+
+impl<M: ModuleServices> ModuleBuilder<M> {
+    /// Build the module and check that every service it exposes can be resolved or provided.
+    /// See [`verify`].
+    ///
+    /// [`verify`]: fn.verify.html
+    pub fn verify(self) -> VerificationReport {
+        verify_builder(self)
+    }
+}
+
+fn verify_builder<M: ModuleServices>(builder: ModuleBuilder<M>) -> VerificationReport {
+    let mut report = VerificationReport { issues: Vec::new() };
+
+    let module = match panic::catch_unwind(AssertUnwindSafe(|| builder.build())) {
+        Ok(module) => module,
+        Err(payload) => {
+            report.issues.push(VerificationIssue {
+                interface_name: None,
+                kind: None,
+                problem: VerificationProblem::BuildPanicked(panic_message(payload)),
+            });
+            return report;
+        }
+    };
+
+    for service in M::services().iter() {
+        let problem = match panic::catch_unwind(AssertUnwindSafe(|| service.check(&module))) {
+            Ok(Ok(())) => continue,
+            Ok(Err(error)) => VerificationProblem::ProviderFailed(error.to_string()),
+            Err(payload) => VerificationProblem::Panicked(panic_message(payload)),
+        };
+
+        report.issues.push(VerificationIssue {
+            interface_name: Some(service.interface_name()),
+            kind: Some(service.kind()),
+            problem,
+        });
+    }
+
+    report
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
+
+/// The result of verifying a module. See [`verify`].
+///
+/// [`verify`]: fn.verify.html
+#[derive(Debug)]
+pub struct VerificationReport {
+    issues: Vec<VerificationIssue>,
+}
+
+impl VerificationReport {
+    /// Check if no issues were found
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// The issues found while verifying the module
+    pub fn issues(&self) -> &[VerificationIssue] {
+        &self.issues
+    }
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.issues.is_empty() {
+            return write!(f, "No issues found");
+        }
+
+        write!(f, "Found {} issue(s):", self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "\n- {}", issue)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A single issue found while verifying a module
+#[derive(Debug)]
+pub struct VerificationIssue {
+    interface_name: Option<&'static str>,
+    kind: Option<ServiceKind>,
+    problem: VerificationProblem,
+}
+
+impl VerificationIssue {
+    /// The type name of the interface which failed. This is `None` if the module failed to build.
+    pub fn interface_name(&self) -> Option<&'static str> {
+        self.interface_name
+    }
+
+    /// The kind of service which failed. This is `None` if the module failed to build.
+    pub fn kind(&self) -> Option<ServiceKind> {
+        self.kind
+    }
+
+    /// What went wrong
+    pub fn problem(&self) -> &VerificationProblem {
+        &self.problem
+    }
+}
+
+impl fmt::Display for VerificationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.kind, self.interface_name) {
+            (Some(kind), Some(interface_name)) => {
+                write!(f, "{} {}: {}", kind, interface_name, self.problem)
+            }
+            _ => write!(f, "{}", self.problem),
+        }
+    }
+}
+
+/// The types of problems found while verifying a module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationProblem {
+    /// The module panicked while building
+    BuildPanicked(String),
+    /// Resolving or providing the service panicked
+    Panicked(String),
+    /// The provider returned an error
+    ProviderFailed(String),
+}
+
+impl fmt::Display for VerificationProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationProblem::BuildPanicked(message) => {
+                write!(f, "module build panicked: {}", message)
+            }
+            VerificationProblem::Panicked(message) => write!(f, "panicked: {}", message),
+            VerificationProblem::ProviderFailed(message) => {
+                write!(f, "provider failed: {}", message)
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Interfaces can have associated type bindings, ex. `dyn Repository<Entity = User>`
+
+use shaku::{module, module_interface, Component, HasComponent, HasProvider, Interface, Provider};
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct User(String);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Order(usize);
+
+trait Repository: Interface {
+    type Entity;
+
+    fn get(&self) -> Self::Entity;
+}
+
+trait Handler {
+    type Output;
+
+    fn handle(&self) -> Self::Output;
+}
+
+#[derive(Component)]
+#[shaku(interface = Repository<Entity = User>)]
+struct UserRepository;
+impl Repository for UserRepository {
+    type Entity = User;
+
+    fn get(&self) -> User {
+        User("admin".to_string())
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = dyn Repository<Entity = T>)]
+struct DefaultRepository<T: Interface + Clone + Default> {
+    #[shaku(default)]
+    value: T,
+}
+impl<T: Interface + Clone + Default> Repository for DefaultRepository<T> {
+    type Entity = T;
+
+    fn get(&self) -> T {
+        self.value.clone()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Handler<Output = String>)]
+struct UserHandler {
+    #[shaku(inject)]
+    users: Arc<dyn Repository<Entity = User>>,
+}
+impl Handler for UserHandler {
+    type Output = String;
+
+    fn handle(&self) -> String {
+        self.users.get().0
+    }
+}
+
+module_interface! {
+    trait RepositoryModule {
+        components = [dyn Repository<Entity = User>, dyn Repository<Entity = Order>],
+        providers = [dyn Handler<Output = String>]
+    }
+}
+
+module! {
+    TestModule: RepositoryModule {
+        components = [UserRepository, DefaultRepository<Order>],
+        providers = [UserHandler]
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [],
+
+        use dyn RepositoryModule {
+            components = [dyn Repository<Entity = User>],
+            providers = [dyn Handler<Output = String>]
+        }
+    }
+}
+
+/// Components are resolved by their interface, including the associated type
+#[test]
+fn resolve_by_associated_type() {
+    let module = TestModule::builder().build();
+    let users: Arc<dyn Repository<Entity = User>> = module.resolve();
+    let orders: Arc<dyn Repository<Entity = Order>> = module.resolve();
+
+    assert_eq!(users.get(), User("admin".to_string()));
+    assert_eq!(orders.get(), Order(0));
+}
+
+/// Providers can have associated type bindings and inject them
+#[test]
+fn provide_by_associated_type() {
+    let module = TestModule::builder().build();
+    let handler: Box<dyn Handler<Output = String>> = module.provide().unwrap();
+
+    assert_eq!(handler.handle(), "admin");
+}
+
+/// Submodules can export services with associated type bindings
+#[test]
+fn resolve_from_submodule() {
+    let submodule: Arc<dyn RepositoryModule> = Arc::new(TestModule::builder().build());
+    let module = RootModule::builder(submodule).build();
+    let users: &dyn Repository<Entity = User> = module.resolve_ref();
+    let handler: Box<dyn Handler<Output = String>> = module.provide().unwrap();
+
+    assert_eq!(users.get(), User("admin".to_string()));
+    assert_eq!(handler.handle(), "admin");
+}
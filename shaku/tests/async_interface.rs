@@ -0,0 +1,148 @@
+//! Traits with async methods can be used as interfaces via `async_interface!`
+
+use shaku::{async_interface, module, Component, HasComponent, Interface};
+use std::future::Future;
+use std::sync::Arc;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+async_interface! {
+    trait Database: Interface {
+        async fn find_user(&self, id: u32) -> Option<String>;
+
+        async fn rename(&mut self, id: u32, name: &str) -> Result<(), String>;
+    }
+
+    trait UserService: Interface {
+        async fn greet(&self, id: u32) -> Result<String, String>;
+
+        async fn greet_all(&self, (first, second): (u32, u32)) -> String {
+            let first = self.greet(first).await.unwrap_or_default();
+            let second = self.greet(second).await.unwrap_or_default();
+            format!("{} {}", first, second)
+        }
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct InMemoryDatabase {
+    #[shaku(default = vec!["Alice".to_string(), "Bob".to_string()])]
+    users: Vec<String>,
+}
+
+async_interface! {
+    impl Database for InMemoryDatabase {
+        async fn find_user(&self, id: u32) -> Option<String> {
+            self.users.get(id as usize).cloned()
+        }
+
+        async fn rename(&mut self, id: u32, name: &str) -> Result<(), String> {
+            let user = self
+                .users
+                .get_mut(id as usize)
+                .ok_or_else(|| format!("No user {}", id))?;
+            *user = name.to_string();
+            Ok(())
+        }
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = UserService)]
+struct UserServiceImpl {
+    #[shaku(inject)]
+    database: Arc<dyn Database>,
+}
+
+async_interface! {
+    impl UserService for UserServiceImpl {
+        async fn greet(&self, id: u32) -> Result<String, String> {
+            let name = self
+                .database
+                .find_user(id)
+                .await
+                .ok_or_else(|| format!("No user {}", id))?;
+            Ok(format!("Hello, {}!", name))
+        }
+    }
+}
+
+module! {
+    TestModule {
+        components = [InMemoryDatabase, UserServiceImpl],
+        providers = []
+    }
+}
+
+module! {
+    DatabaseModule {
+        components = [InMemoryDatabase],
+        providers = []
+    }
+}
+
+/// Poll a future to completion. The futures in these tests never wait, so the waker is unused.
+fn block_on<F: Future>(future: F) -> F::Output {
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn async_methods_resolve() {
+    let module = TestModule::builder().build();
+    let service: &dyn UserService = module.resolve_ref();
+
+    assert_eq!(block_on(service.greet(1)), Ok("Hello, Bob!".to_string()));
+    assert_eq!(block_on(service.greet(2)), Err("No user 2".to_string()));
+}
+
+#[test]
+fn default_async_methods() {
+    let module = TestModule::builder().build();
+    let service: &dyn UserService = module.resolve_ref();
+
+    assert_eq!(
+        block_on(service.greet_all((0, 1))),
+        "Hello, Alice! Hello, Bob!"
+    );
+}
+
+#[test]
+fn borrowed_arguments_and_mut_self() {
+    let mut module = DatabaseModule::builder().build();
+    let database: &mut dyn Database = module.resolve_mut().unwrap();
+
+    assert_eq!(block_on(database.rename(0, "Carol")), Ok(()));
+    assert_eq!(
+        block_on(database.rename(5, "Dave")),
+        Err("No user 5".to_string())
+    );
+    assert_eq!(block_on(database.find_user(0)), Some("Carol".to_string()));
+}
+
+#[test]
+#[cfg(feature = "thread_safe")]
+fn futures_are_send() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    let module = TestModule::builder().build();
+    let service: &dyn UserService = module.resolve_ref();
+    assert_send(&service.greet(0));
+}
@@ -0,0 +1,118 @@
+//! Providers can create multiple services at once
+
+use shaku::{
+    module, Component, HasBatchProvider, HasComponent, HasProvider, Interface, Module, Provider,
+};
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+trait Counter: Interface {
+    fn next(&self) -> usize;
+}
+
+trait Worker {
+    fn id(&self) -> usize;
+    fn counter(&self) -> &Arc<dyn Counter>;
+}
+
+#[derive(Component)]
+#[shaku(interface = Counter)]
+struct CounterImpl {
+    #[shaku(default)]
+    value: AtomicUsize,
+}
+
+impl Counter for CounterImpl {
+    fn next(&self) -> usize {
+        self.value.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+struct WorkerImpl {
+    id: usize,
+    counter: Arc<dyn Counter>,
+}
+
+impl Worker for WorkerImpl {
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn counter(&self) -> &Arc<dyn Counter> {
+        &self.counter
+    }
+}
+
+impl<M: Module + HasComponent<dyn Counter>> Provider<M> for WorkerImpl {
+    type Interface = dyn Worker;
+
+    fn provide(module: &M) -> Result<Box<dyn Worker>, Box<dyn Error>> {
+        let counter: Arc<dyn Counter> = module.resolve();
+        let id = counter.next();
+
+        if id >= 3 {
+            return Err("Too many workers".into());
+        }
+
+        Ok(Box::new(WorkerImpl { id, counter }))
+    }
+}
+
+trait WorkerModule: HasProvider<dyn Worker> {}
+
+module! {
+    TestModule: WorkerModule {
+        components = [CounterImpl],
+        providers = [WorkerImpl]
+    }
+}
+
+/// Each provided service is a new instance, sharing the same dependencies
+#[test]
+fn provide_batch_shares_dependencies() {
+    let module = TestModule::builder().build();
+    let workers: Vec<Box<dyn Worker>> = module.provide_batch(3).unwrap();
+
+    let ids: Vec<usize> = workers.iter().map(|worker| worker.id()).collect();
+    assert_eq!(ids, vec![0, 1, 2]);
+    assert!(Arc::ptr_eq(workers[0].counter(), workers[2].counter()));
+}
+
+/// The iterator creates services lazily and reports errors per item
+#[test]
+fn provide_iter_is_lazy() {
+    let module = TestModule::builder().build();
+    let mut workers = HasBatchProvider::<dyn Worker>::provide_iter(&module, 5);
+
+    assert_eq!(workers.len(), 5);
+    assert_eq!(workers.next().unwrap().unwrap().id(), 0);
+    assert_eq!(workers.len(), 4);
+
+    let results: Vec<_> = workers.collect();
+    assert_eq!(results.len(), 4);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_ok());
+    assert_eq!(
+        results[2].as_ref().err().unwrap().to_string(),
+        "Too many workers"
+    );
+}
+
+/// A failing provider fails the whole batch
+#[test]
+fn provide_batch_error() {
+    let module = TestModule::builder().build();
+    let result = HasBatchProvider::<dyn Worker>::provide_batch(&module, 4);
+
+    assert_eq!(result.err().unwrap().to_string(), "Too many workers");
+}
+
+/// Batches can be created through module interfaces
+#[test]
+fn module_interface_batch() {
+    let module: Box<dyn WorkerModule> = Box::new(TestModule::builder().build());
+    let workers = module.provide_batch(2).unwrap();
+
+    assert_eq!(workers.len(), 2);
+}
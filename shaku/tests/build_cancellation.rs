@@ -0,0 +1,123 @@
+//! Async module builds can be cancelled via a `CancellationToken`
+
+use shaku::{
+    module, BoxFuture, CancellationToken, Component, FetchError, Interface, ModuleBuildError,
+    ParameterSource,
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+static WAKES: AtomicUsize = AtomicUsize::new(0);
+
+trait Client: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Client)]
+struct ClientImpl {
+    #[shaku(from_source = "api_key")]
+    _api_key: String,
+    #[shaku(from_source = "slow_certificate")]
+    _certificate: String,
+}
+impl Client for ClientImpl {}
+
+module! {
+    TestModule {
+        components = [ClientImpl],
+        providers = []
+    }
+}
+
+/// A fetch which never completes, like a hanging network handshake
+struct Hanging;
+
+impl Future for Hanging {
+    type Output = Result<Option<String>, FetchError>;
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+        Poll::Pending
+    }
+}
+
+/// Keys starting with "slow" never finish fetching
+struct SlowSource;
+
+impl ParameterSource for SlowSource {
+    fn fetch(&self, key: &str) -> BoxFuture<'static, Result<Option<String>, FetchError>> {
+        if key.starts_with("slow") {
+            return Box::pin(Hanging);
+        }
+
+        let mut values = std::collections::HashMap::new();
+        values.insert(key.to_string(), "value".to_string());
+        values.fetch(key)
+    }
+}
+
+/// A waker which counts the wakes
+fn counting_waker() -> Waker {
+    fn raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            raw_waker()
+        }
+        fn wake(_: *const ()) {
+            WAKES.fetch_add(1, Ordering::SeqCst);
+        }
+        fn noop(_: *const ()) {}
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+
+    unsafe { Waker::from_raw(raw_waker()) }
+}
+
+/// Cancelling wakes the build, which reports the keys fetched so far
+#[test]
+fn cancel_pending_build() {
+    let token = CancellationToken::new();
+    let mut build = TestModule::builder()
+        .with_parameter_source(SlowSource)
+        .build_async()
+        .with_cancellation(token.clone());
+    let waker = counting_waker();
+    let mut context = Context::from_waker(&waker);
+
+    assert!(Pin::new(&mut build).poll(&mut context).is_pending());
+    let wakes = WAKES.load(Ordering::SeqCst);
+
+    token.cancel();
+    assert!(token.is_cancelled());
+    assert_eq!(WAKES.load(Ordering::SeqCst), wakes + 1);
+
+    match Pin::new(&mut build).poll(&mut context) {
+        Poll::Ready(Err(ModuleBuildError::Cancelled { completed })) => {
+            assert_eq!(completed, vec!["api_key"])
+        }
+        _ => panic!("Expected the build to be cancelled"),
+    }
+}
+
+/// Builds with a cancelled token are cancelled before fetching anything
+#[test]
+fn cancelled_before_build() {
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let mut build = TestModule::builder()
+        .with_parameter_source(SlowSource)
+        .build_async()
+        .with_cancellation(token);
+    let waker = counting_waker();
+    let mut context = Context::from_waker(&waker);
+
+    match Pin::new(&mut build).poll(&mut context) {
+        Poll::Ready(Err(error @ ModuleBuildError::Cancelled { .. })) => assert_eq!(
+            error.to_string(),
+            "The module build was cancelled after fetching 0 parameter(s)"
+        ),
+        _ => panic!("Expected the build to be cancelled"),
+    }
+}
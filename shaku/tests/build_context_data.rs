@@ -0,0 +1,73 @@
+//! Data passed to the module builder is available to every component build
+
+use shaku::{module, Component, HasComponent, Interface, Module, ModuleBuildContext};
+
+struct AppConfig {
+    name: String,
+}
+
+trait NameHolder: Interface {
+    fn name(&self) -> Option<String>;
+}
+
+struct NameHolderImpl {
+    name: Option<String>,
+}
+
+impl NameHolder for NameHolderImpl {
+    fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+}
+
+impl<M: Module> Component<M> for NameHolderImpl {
+    type Interface = dyn NameHolder;
+    type Parameters = ();
+
+    fn build(context: &mut ModuleBuildContext<M>, _: ()) -> Box<dyn NameHolder> {
+        Box::new(NameHolderImpl {
+            name: context
+                .data::<AppConfig>()
+                .map(|config| config.name.clone()),
+        })
+    }
+}
+
+module! {
+    TestModule {
+        components = [NameHolderImpl],
+        providers = []
+    }
+}
+
+/// Components can read the data during build
+#[test]
+fn data_available_during_build() {
+    let module = TestModule::builder()
+        .with_build_context_data(AppConfig {
+            name: "first".to_string(),
+        })
+        .build();
+    let holder: &dyn NameHolder = module.resolve_ref();
+
+    assert_eq!(holder.name(), Some("first".to_string()));
+}
+
+/// Setting data of the same type again replaces it, and missing data is None
+#[test]
+fn data_replaced_or_missing() {
+    let module = TestModule::builder()
+        .with_build_context_data(AppConfig {
+            name: "first".to_string(),
+        })
+        .with_build_context_data(AppConfig {
+            name: "second".to_string(),
+        })
+        .build();
+    let holder: &dyn NameHolder = module.resolve_ref();
+    assert_eq!(holder.name(), Some("second".to_string()));
+
+    let module = TestModule::builder().build();
+    let holder: &dyn NameHolder = module.resolve_ref();
+    assert_eq!(holder.name(), None);
+}
@@ -0,0 +1,97 @@
+//! Modules report the order in which their components are built, which can be
+//! pinned via `#[shaku(build_after = ?)]`
+
+use shaku::{module, Component, HasComponent, Interface, ModuleBuildOrder};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+static LOGGER_BUILDS: AtomicUsize = AtomicUsize::new(0);
+static METRICS_BUILDS: AtomicUsize = AtomicUsize::new(0);
+
+trait Logger: Interface {}
+trait Database: Interface {}
+trait Cache: Interface {}
+trait Metrics: Interface {}
+
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+impl<M: shaku::Module> Component<M> for LoggerImpl {
+    type Interface = dyn Logger;
+    type Parameters = ();
+
+    fn build(_: &mut shaku::ModuleBuildContext<M>, _: ()) -> Box<dyn Logger> {
+        LOGGER_BUILDS.fetch_add(1, Ordering::SeqCst);
+        Box::new(LoggerImpl)
+    }
+}
+
+struct MetricsImpl;
+impl Metrics for MetricsImpl {}
+
+impl<M: shaku::Module> Component<M> for MetricsImpl {
+    type Interface = dyn Metrics;
+    type Parameters = ();
+
+    fn build(_: &mut shaku::ModuleBuildContext<M>, _: ()) -> Box<dyn Metrics> {
+        METRICS_BUILDS.fetch_add(1, Ordering::SeqCst);
+        Box::new(MetricsImpl)
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Database, build_after = LoggerImpl)]
+struct DatabaseImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    cache: Arc<dyn Cache>,
+}
+impl Database for DatabaseImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl;
+impl Cache for CacheImpl {}
+
+module! {
+    TestModule {
+        components = [DatabaseImpl, CacheImpl, #[lazy] LoggerImpl, #[lazy] MetricsImpl],
+        providers = []
+    }
+}
+
+/// Dependencies and `build_after` components come first, and lazy components
+/// are not built with the module
+#[test]
+fn build_order_lists_dependencies_first() {
+    assert_eq!(
+        TestModule::build_order(),
+        [
+            "dyn build_order::Logger",
+            "dyn build_order::Cache",
+            "dyn build_order::Database",
+        ]
+    );
+}
+
+/// The lazy `build_after` component is built with the module even though nothing
+/// injects it, while other lazy components are built on first use
+#[test]
+fn build_after_builds_component() {
+    let module = TestModule::builder().build();
+    assert_eq!(LOGGER_BUILDS.load(Ordering::SeqCst), 1);
+    assert_eq!(METRICS_BUILDS.load(Ordering::SeqCst), 0);
+
+    let _: &dyn Metrics = module.resolve_ref();
+    assert_eq!(METRICS_BUILDS.load(Ordering::SeqCst), 1);
+}
+
+/// The derived component lists its dependencies
+#[test]
+fn component_dependencies() {
+    assert_eq!(
+        <DatabaseImpl as Component<TestModule>>::dependencies(),
+        ["dyn build_order::Logger", "dyn build_order::Cache"]
+    );
+    assert!(<CacheImpl as Component<TestModule>>::dependencies().is_empty());
+}
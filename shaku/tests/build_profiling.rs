@@ -0,0 +1,92 @@
+//! Module builds can be profiled
+
+use shaku::{module, Component, Interface, Module, ModuleBuildContext};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+trait Slow: Interface {}
+trait Service: Interface {}
+
+struct SlowImpl;
+impl Slow for SlowImpl {}
+impl<M: Module> Component<M> for SlowImpl {
+    type Interface = dyn Slow;
+    type Parameters = ();
+
+    fn build(_: &mut ModuleBuildContext<M>, _: Self::Parameters) -> Box<Self::Interface> {
+        thread::sleep(Duration::from_millis(20));
+        Box::new(SlowImpl)
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    slow: Arc<dyn Slow>,
+}
+impl Service for ServiceImpl {}
+
+module! {
+    TestModule {
+        components = [ServiceImpl, SlowImpl],
+        providers = []
+    }
+}
+
+/// Each component build is recorded, dependencies first
+#[test]
+fn records_component_builds() {
+    let (_module, report) = TestModule::builder().build_profiled();
+    let components = report.components();
+
+    assert_eq!(components.len(), 2);
+    assert_eq!(
+        components[0].component_type_name(),
+        "build_profiling::SlowImpl"
+    );
+    assert_eq!(
+        components[0].interface_type_name(),
+        "dyn build_profiling::Slow"
+    );
+    assert_eq!(components[0].depth(), 1);
+    assert_eq!(
+        components[1].component_type_name(),
+        "build_profiling::ServiceImpl"
+    );
+    assert_eq!(components[1].depth(), 0);
+    assert!(report.total_duration() >= components[1].duration());
+}
+
+/// The time spent building dependencies is excluded from the own duration
+#[test]
+fn own_duration_excludes_dependencies() {
+    let (_module, mut report) = TestModule::builder().build_profiled();
+
+    report.sort_by_own_duration();
+    let components = report.components();
+
+    assert_eq!(
+        components[0].component_type_name(),
+        "build_profiling::SlowImpl"
+    );
+    assert!(components[0].own_duration() >= Duration::from_millis(20));
+    assert!(components[1].duration() >= Duration::from_millis(20));
+    assert!(components[1].own_duration() < Duration::from_millis(20));
+}
+
+/// Overridden component instances are not built, so they are not recorded
+#[test]
+fn overridden_components_are_skipped() {
+    let (_module, report) = TestModule::builder()
+        .with_component_override::<dyn Slow>(Box::new(SlowImpl))
+        .build_profiled();
+
+    assert_eq!(report.components().len(), 1);
+    assert_eq!(
+        report.components()[0].component_type_name(),
+        "build_profiling::ServiceImpl"
+    );
+}
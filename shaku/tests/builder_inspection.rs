@@ -0,0 +1,79 @@
+//! The overrides and parameters set on a module builder can be inspected before the build
+
+use shaku::{module, Component, Interface, Provider, ProviderFn};
+
+trait Config: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Config)]
+struct ConfigImpl {
+    #[allow(dead_code)]
+    port: u16,
+}
+
+impl Config for ConfigImpl {}
+
+trait Clock: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Clock)]
+struct SystemClock;
+
+impl Clock for SystemClock {}
+
+trait Connection {}
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct ConnectionImpl;
+
+impl Connection for ConnectionImpl {}
+
+module! {
+    TestModule {
+        components = [ConfigImpl, SystemClock],
+        providers = [ConnectionImpl]
+    }
+}
+
+/// A new builder has no overrides or parameters
+#[test]
+fn empty_builder() {
+    let builder = TestModule::builder();
+
+    assert!(builder.overridden_interfaces().is_empty());
+    assert!(builder.parameterized_components().is_empty());
+}
+
+/// Component and provider overrides are listed by interface
+#[test]
+fn overridden_interfaces() {
+    let connection: ProviderFn<TestModule, dyn Connection> =
+        Box::new(|_| Ok(Box::new(ConnectionImpl)));
+    let builder = TestModule::builder()
+        .with_component_override::<dyn Clock>(Box::new(SystemClock))
+        .with_provider_override(connection);
+
+    assert_eq!(
+        builder.overridden_interfaces(),
+        vec![
+            "dyn builder_inspection::Clock",
+            "dyn builder_inspection::Connection"
+        ]
+    );
+    assert!(builder.parameterized_components().is_empty());
+}
+
+/// Components are listed once, even if their parameters are set again
+#[test]
+fn parameterized_components() {
+    let builder = TestModule::builder()
+        .with_component_parameters::<ConfigImpl>(ConfigImplParameters { port: 80 })
+        .with_component_parameters::<ConfigImpl>(ConfigImplParameters { port: 8080 });
+
+    assert_eq!(
+        builder.parameterized_components(),
+        vec!["builder_inspection::ConfigImpl"]
+    );
+    assert!(builder.overridden_interfaces().is_empty());
+}
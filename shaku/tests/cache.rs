@@ -0,0 +1,118 @@
+//! Components can share an in-process cache
+
+use shaku::extras::{InMemoryCache, InMemoryCacheParameters, TypedCache};
+use shaku::{module, Component, HasComponent, Interface};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+trait UserService: Interface {
+    fn user_name(&self, id: u32) -> Arc<String>;
+    fn lookups(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = UserService)]
+struct UserServiceImpl {
+    #[shaku(inject)]
+    cache: Arc<dyn TypedCache>,
+    #[shaku(default)]
+    lookups: AtomicUsize,
+}
+
+impl UserService for UserServiceImpl {
+    fn user_name(&self, id: u32) -> Arc<String> {
+        self.cache.get_or_insert_with(&id.to_string(), || {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            format!("user {}", id)
+        })
+    }
+
+    fn lookups(&self) -> usize {
+        self.lookups.load(Ordering::SeqCst)
+    }
+}
+
+module! {
+    TestModule {
+        components = [InMemoryCache, UserServiceImpl],
+        providers = []
+    }
+}
+
+fn build_module(params: InMemoryCacheParameters) -> TestModule {
+    TestModule::builder()
+        .with_component_parameters::<InMemoryCache>(params)
+        .build()
+}
+
+/// Components share the module's cache
+#[test]
+fn shared_cache() {
+    let module = build_module(InMemoryCacheParameters::default());
+    let service: &dyn UserService = module.resolve_ref();
+    let cache: &dyn TypedCache = module.resolve_ref();
+
+    assert_eq!(*service.user_name(1), "user 1");
+    assert_eq!(*service.user_name(1), "user 1");
+    assert_eq!(service.lookups(), 1);
+    assert_eq!(
+        cache.get::<String>("1").as_deref(),
+        Some(&"user 1".to_string())
+    );
+}
+
+/// Entries with the same key but different types don't conflict
+#[test]
+fn typed_entries() {
+    let module = build_module(InMemoryCacheParameters::default());
+    let cache: &dyn TypedCache = module.resolve_ref();
+
+    cache.insert("key", 1u32);
+    cache.insert("key", 2u64);
+
+    assert_eq!(cache.get::<u32>("key").as_deref(), Some(&1));
+    assert_eq!(cache.get::<u64>("key").as_deref(), Some(&2));
+    assert!(cache.get::<i32>("key").is_none());
+
+    assert!(cache.remove::<u32>("key"));
+    assert!(cache.get::<u32>("key").is_none());
+    assert_eq!(cache.len(), 1);
+}
+
+/// Entries expire after their TTL, or the default TTL
+#[test]
+fn expiration() {
+    let module = build_module(InMemoryCacheParameters {
+        default_ttl: Some(Duration::from_millis(20)),
+        max_entries: None,
+    });
+    let cache: &dyn TypedCache = module.resolve_ref();
+
+    cache.insert("default", 1u32);
+    cache.insert_with_ttl("long", 2u32, Duration::from_secs(60));
+    thread::sleep(Duration::from_millis(30));
+
+    assert!(cache.get::<u32>("default").is_none());
+    assert_eq!(cache.get::<u32>("long").as_deref(), Some(&2));
+}
+
+/// The oldest entry is evicted when the cache is full
+#[test]
+fn max_entries() {
+    let module = build_module(InMemoryCacheParameters {
+        default_ttl: None,
+        max_entries: Some(2),
+    });
+    let cache: &dyn TypedCache = module.resolve_ref();
+
+    cache.insert("a", 1u32);
+    cache.insert("b", 2u32);
+    cache.insert("a", 3u32);
+    cache.insert("c", 4u32);
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get::<u32>("b").is_none());
+    assert_eq!(cache.get::<u32>("c").as_deref(), Some(&4));
+}
@@ -0,0 +1,88 @@
+//! Circuit breakers make failing providers fail fast
+
+use shaku::{
+    module, CircuitBreaker, CircuitBreakerPolicy, CircuitBreakerProvider, CircuitOpenError,
+    HasComponent, HasProvider, Module, Provider,
+};
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+static AVAILABLE: AtomicBool = AtomicBool::new(false);
+
+/// Fails until it becomes available
+struct Connection;
+
+impl<M: Module> Provider<M> for Connection {
+    type Interface = Connection;
+
+    fn provide(_: &M) -> Result<Box<Connection>, Box<dyn Error>> {
+        ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+
+        if AVAILABLE.load(Ordering::SeqCst) {
+            Ok(Box::new(Connection))
+        } else {
+            Err("connection refused".into())
+        }
+    }
+}
+
+struct ConnectionBreaker;
+
+impl CircuitBreakerPolicy for ConnectionBreaker {
+    const FAILURE_THRESHOLD: u32 = 2;
+    const COOLDOWN: Duration = Duration::from_millis(50);
+}
+
+module! {
+    TestModule {
+        components = [CircuitBreaker<ConnectionBreaker>],
+        providers = [CircuitBreakerProvider<Connection, ConnectionBreaker>]
+    }
+}
+
+/// The breaker opens after consecutive failures, and closes again once the
+/// provider succeeds after the cooldown
+#[test]
+fn breaker_opens_and_closes() {
+    let module = TestModule::builder().build();
+    let provide = || HasProvider::<Connection>::provide(&module);
+    let breaker: &CircuitBreaker<ConnectionBreaker> = module.resolve_ref();
+
+    assert!(provide().is_err());
+    assert!(!breaker.is_open());
+    assert!(provide().is_err());
+    assert!(breaker.is_open());
+
+    // The provider is not called while the breaker is open
+    let error = provide().err().unwrap();
+    assert!(error.downcast_ref::<CircuitOpenError>().is_some());
+    assert_eq!(ATTEMPTS.load(Ordering::SeqCst), 2);
+
+    AVAILABLE.store(true, Ordering::SeqCst);
+    thread::sleep(ConnectionBreaker::COOLDOWN);
+
+    assert!(provide().is_ok());
+    assert!(!breaker.is_open());
+    assert_eq!(ATTEMPTS.load(Ordering::SeqCst), 3);
+}
+
+/// A success resets the count of consecutive failures
+#[test]
+fn success_resets_failures() {
+    struct LocalBreaker;
+    impl CircuitBreakerPolicy for LocalBreaker {
+        const FAILURE_THRESHOLD: u32 = 2;
+        const COOLDOWN: Duration = Duration::from_secs(60);
+    }
+
+    let breaker = CircuitBreaker::<LocalBreaker>::new();
+    let fail = || -> Result<(), Box<dyn Error>> { Err("failed".into()) };
+
+    assert!(breaker.call(fail).is_err());
+    assert!(breaker.call(|| Ok(())).is_ok());
+    assert!(breaker.call(fail).is_err());
+    assert!(!breaker.is_open());
+}
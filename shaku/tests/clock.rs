@@ -0,0 +1,72 @@
+//! Components can read the time from an injected `Clock`
+
+use shaku::extras::{Clock, SystemClock, TestClock, TestClockBuilder};
+use shaku::{module, Component, HasComponent, Interface};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+trait Timer: Interface {
+    fn start(&self);
+    fn elapsed(&self) -> Duration;
+}
+
+#[derive(Component)]
+#[shaku(interface = Timer)]
+struct TimerImpl {
+    #[shaku(inject)]
+    clock: Arc<dyn Clock>,
+    #[shaku(default)]
+    started: Mutex<Option<Instant>>,
+}
+
+impl Timer for TimerImpl {
+    fn start(&self) {
+        *self.started.lock().unwrap() = Some(self.clock.instant());
+    }
+
+    fn elapsed(&self) -> Duration {
+        let started = self.started.lock().unwrap().unwrap();
+        self.clock.instant() - started
+    }
+}
+
+module! {
+    TestModule {
+        components = [SystemClock, TimerImpl],
+        providers = []
+    }
+}
+
+/// The system clock reads the current time
+#[test]
+fn system_clock() {
+    let module = TestModule::builder().build();
+    let clock: &dyn Clock = module.resolve_ref();
+    let before = SystemTime::now();
+
+    assert!(clock.now() >= before);
+}
+
+/// The test clock only moves when advanced, and the module shares its time
+#[test]
+fn test_clock() {
+    let clock = TestClock::new();
+    let module = TestModule::builder().with_test_clock(&clock).build();
+    let timer: &dyn Timer = module.resolve_ref();
+
+    timer.start();
+    assert_eq!(timer.elapsed(), Duration::from_secs(0));
+
+    clock.advance(Duration::from_millis(1500));
+    assert_eq!(timer.elapsed(), Duration::from_millis(1500));
+}
+
+/// The test clock can start at a fixed time
+#[test]
+fn fixed_start() {
+    let clock = TestClock::starting_at(UNIX_EPOCH);
+    clock.advance(Duration::from_secs(60));
+
+    assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(60));
+    assert_eq!(clock.elapsed(), Duration::from_secs(60));
+}
@@ -0,0 +1,98 @@
+//! Components can be created from closures via `with_component_closure`
+
+use shaku::{closure_interface, module, Component, HasComponent, Interface};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[closure_interface]
+trait EventSink: Interface {
+    fn send(&self, name: &str, count: usize);
+}
+
+#[closure_interface]
+pub trait Formatter: Interface {
+    fn format(&self, value: &u32) -> String;
+
+    fn format_twice(&self, value: &u32) -> String {
+        format!("{}{}", self.format(value), self.format(value))
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = EventSink)]
+struct NoopSink;
+impl EventSink for NoopSink {
+    fn send(&self, _: &str, _: usize) {}
+}
+
+#[derive(Component)]
+#[shaku(interface = Formatter)]
+struct DecimalFormatter;
+impl Formatter for DecimalFormatter {
+    fn format(&self, value: &u32) -> String {
+        value.to_string()
+    }
+}
+
+trait Reporter: Interface {
+    fn report(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Reporter)]
+struct ReporterImpl {
+    #[shaku(inject)]
+    formatter: Arc<dyn Formatter>,
+    #[shaku(inject)]
+    sink: Arc<dyn EventSink>,
+}
+
+impl Reporter for ReporterImpl {
+    fn report(&self) -> String {
+        let report = self.formatter.format_twice(&7);
+        self.sink.send(&report, 1);
+        report
+    }
+}
+
+module! {
+    TestModule {
+        components = [NoopSink, DecimalFormatter, ReporterImpl],
+        providers = []
+    }
+}
+
+/// The closure is called with the method's arguments
+#[test]
+fn closure_is_called() {
+    let total = Arc::new(AtomicUsize::new(0));
+    let closure_total = Arc::clone(&total);
+    let module = TestModule::builder()
+        .with_component_closure::<dyn EventSink, _>(move |name: &str, count: usize| {
+            closure_total.fetch_add(name.len() * count, Ordering::SeqCst);
+        })
+        .build();
+    let sink: &dyn EventSink = module.resolve_ref();
+
+    sink.send("abc", 2);
+    assert_eq!(total.load(Ordering::SeqCst), 6);
+}
+
+/// Closure components are injected into other components, and keep the default methods
+#[test]
+fn inject_closure_component() {
+    let module = TestModule::builder()
+        .with_component_closure::<dyn Formatter, _>(|value: &u32| format!("<{}>", value))
+        .build();
+    let reporter: &dyn Reporter = module.resolve_ref();
+
+    assert_eq!(reporter.report(), "<7><7>");
+}
+
+/// The generated closure struct can also be used directly
+#[test]
+fn closure_struct() {
+    let formatter = FormatterClosure(|value: &u32| format!("{:x}", value));
+
+    assert_eq!(formatter.format_twice(&255), "ffff");
+}
@@ -0,0 +1,160 @@
+//! Tests related to closures passed as component parameters via NoDefault
+
+use shaku::{module, Component, HasComponent, Interface, Module, ModuleBuildContext, NoDefault};
+
+trait Formatter: Interface {
+    fn format(&self, value: i32) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Formatter)]
+struct FormatterImpl {
+    format_fn: NoDefault<Box<dyn Fn(i32) -> String + Send + Sync>>,
+}
+
+impl Formatter for FormatterImpl {
+    fn format(&self, value: i32) -> String {
+        (self.format_fn)(value)
+    }
+}
+
+trait Validator: Interface {
+    fn is_valid(&self, value: i32) -> bool;
+}
+
+struct ValidatorImpl {
+    validate_fn: Box<dyn Fn(i32) -> bool + Send + Sync>,
+}
+
+impl Validator for ValidatorImpl {
+    fn is_valid(&self, value: i32) -> bool {
+        (self.validate_fn)(value)
+    }
+}
+
+impl<M: Module> Component<M> for ValidatorImpl {
+    type Interface = dyn Validator;
+    type Parameters = NoDefault<Box<dyn Fn(i32) -> bool + Send + Sync>>;
+
+    fn build(_: &mut ModuleBuildContext<M>, params: Self::Parameters) -> Box<dyn Validator> {
+        Box::new(ValidatorImpl {
+            validate_fn: params.into_inner(),
+        })
+    }
+}
+
+module! {
+    TestModule {
+        components = [FormatterImpl, ValidatorImpl],
+        providers = []
+    }
+}
+
+module! {
+    #[shaku(strict_builder)]
+    StrictModule {
+        components = [FormatterImpl, ValidatorImpl],
+        providers = []
+    }
+}
+
+fn format_fn(prefix: String) -> FormatterImplParameters {
+    FormatterImplParameters {
+        format_fn: NoDefault(Box::new(move |value| format!("{}{}", prefix, value))),
+    }
+}
+
+/// Boxed closures can be passed as parameters of derived and manually implemented components
+#[test]
+fn closure_parameters() {
+    let module = TestModule::builder()
+        .with_component_parameters::<FormatterImpl>(format_fn("#".to_string()))
+        .with_component_parameters::<ValidatorImpl>(NoDefault(Box::new(|value| value > 0)))
+        .build();
+
+    let formatter: &dyn Formatter = module.resolve_ref();
+    let validator: &dyn Validator = module.resolve_ref();
+    assert_eq!(formatter.format(5), "#5");
+    assert!(validator.is_valid(5));
+    assert!(!validator.is_valid(-5));
+}
+
+/// Derived components name the missing field
+#[test]
+#[should_panic(expected = "There is no default value for `FormatterImpl::format_fn`")]
+fn derived_closure_parameter_missing() {
+    TestModule::builder()
+        .with_component_parameters::<ValidatorImpl>(NoDefault(Box::new(|value| value > 0)))
+        .build();
+}
+
+/// Manually implemented components name the missing parameter type
+#[test]
+#[should_panic(expected = "There is no default value for the parameter `alloc::boxed::Box<dyn")]
+fn manual_closure_parameter_missing() {
+    TestModule::builder()
+        .with_component_parameters::<FormatterImpl>(format_fn("#".to_string()))
+        .build();
+}
+
+/// The strict builder requires NoDefault parameters of manually implemented components
+#[test]
+fn strict_builder_requires_closure_parameters() {
+    let module = StrictModule::strict_builder()
+        .with_component_parameters::<ValidatorImpl>(NoDefault(Box::new(|value| value % 2 == 0)))
+        .with_component_parameters::<FormatterImpl>(format_fn("0x".to_string()))
+        .build();
+
+    let formatter: &dyn Formatter = module.resolve_ref();
+    let validator: &dyn Validator = module.resolve_ref();
+    assert_eq!(formatter.format(10), "0x10");
+    assert!(validator.is_valid(4));
+}
+
+/// Without thread_safe, closures don't need to be Send or Sync, so they can capture an Rc
+#[cfg(not(feature = "thread_safe"))]
+mod local {
+    use shaku::{module, Component, HasComponent, Interface, NoDefault};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    trait Counter: Interface {
+        fn count(&self);
+    }
+
+    #[derive(Component)]
+    #[shaku(interface = Counter)]
+    struct CounterImpl {
+        count_fn: NoDefault<Box<dyn Fn()>>,
+    }
+
+    impl Counter for CounterImpl {
+        fn count(&self) {
+            (self.count_fn)()
+        }
+    }
+
+    module! {
+        LocalModule {
+            components = [CounterImpl],
+            providers = []
+        }
+    }
+
+    /// The closure shares the Rc with the test
+    #[test]
+    fn non_send_closure_parameters() {
+        let counter = Rc::new(Cell::new(0));
+        let captured = Rc::clone(&counter);
+        let module = LocalModule::builder()
+            .with_component_parameters::<CounterImpl>(CounterImplParameters {
+                count_fn: NoDefault(Box::new(move || captured.set(captured.get() + 1))),
+            })
+            .build();
+
+        let component: &dyn Counter = module.resolve_ref();
+        component.count();
+        component.count();
+        assert_eq!(counter.get(), 2);
+    }
+}
@@ -0,0 +1,77 @@
+//! Components marked with `#[as_provider]` can also be provided
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, ModuleServices, ServiceKind};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+trait Counter: Interface {
+    fn increment(&self) -> usize;
+}
+
+impl<T: Counter + ?Sized> Counter for Arc<T> {
+    fn increment(&self) -> usize {
+        T::increment(self)
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Counter)]
+struct CounterImpl {
+    #[shaku(default)]
+    count: AtomicUsize,
+}
+
+impl Counter for CounterImpl {
+    fn increment(&self) -> usize {
+        self.count.fetch_add(1, Ordering::SeqCst) + 1
+    }
+}
+
+module! {
+    TestModule {
+        components = [#[as_provider] CounterImpl],
+        providers = []
+    }
+}
+
+module! {
+    LazyModule {
+        components = [#[lazy] #[as_provider] CounterImpl],
+        providers = []
+    }
+}
+
+/// Provided services share the component
+#[test]
+fn provided_component_is_shared() {
+    let module = TestModule::builder().build();
+
+    let provided: Box<dyn Counter> = module.provide().unwrap();
+    assert_eq!(provided.increment(), 1);
+
+    let provided: Box<dyn Counter> = module.provide().unwrap();
+    assert_eq!(provided.increment(), 2);
+
+    let component: &dyn Counter = module.resolve_ref();
+    assert_eq!(component.increment(), 3);
+}
+
+/// Lazy components are built when first provided
+#[test]
+fn lazy_component_as_provider() {
+    let module = LazyModule::builder().build();
+
+    let provided: Box<dyn Counter> = module.provide().unwrap();
+    assert_eq!(provided.increment(), 1);
+}
+
+/// The component is registered as both a component and a provider
+#[test]
+fn registered_as_both() {
+    let kinds: Vec<ServiceKind> = TestModule::services()
+        .iter()
+        .map(|service| service.kind())
+        .collect();
+
+    assert_eq!(kinds, [ServiceKind::Component, ServiceKind::Provider]);
+}
@@ -0,0 +1,136 @@
+//! Components can use their own type (including generic types) as their interface
+
+use shaku::{module, module_interface, Component, HasComponent, Interface};
+use std::sync::Arc;
+
+#[derive(Component)]
+#[shaku(interface = Self)]
+struct Config {
+    #[shaku(default = 3)]
+    value: usize,
+}
+
+#[derive(Component)]
+#[shaku(interface = Counter<T>)]
+struct Counter<T: Default + Interface> {
+    #[shaku(default)]
+    start: T,
+}
+
+trait Greeter: Interface {
+    fn greet(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct GreeterImpl {
+    #[shaku(inject)]
+    config: Arc<Config>,
+}
+
+impl Greeter for GreeterImpl {
+    fn greet(&self) -> String {
+        format!("Hello {}", self.config.value)
+    }
+}
+
+trait ValueSource: Interface {
+    fn value(&self) -> usize;
+}
+
+/// The adapter wraps the concrete component
+#[derive(Component)]
+#[shaku(interface = Settings, adapter(ValueSource as SettingsValueSource))]
+struct Settings {
+    #[shaku(default = 7)]
+    value: usize,
+}
+
+impl ValueSource for SettingsValueSource {
+    fn value(&self) -> usize {
+        self.value
+    }
+}
+
+module_interface! {
+    trait ConcreteModule {
+        components = [Config, Counter<u32>],
+        providers = []
+    }
+}
+
+module! {
+    ConcreteModuleImpl: ConcreteModule {
+        components = [
+            Config,
+            Counter<u32>,
+            GreeterImpl,
+            Settings,
+            SettingsValueSource
+        ],
+        providers = []
+    }
+}
+
+module! {
+    OuterModule {
+        components = [],
+        providers = [],
+
+        use ConcreteModuleImpl {
+            components = [Config],
+            providers = []
+        }
+    }
+}
+
+/// A derived component can use its own type as the interface via `Self`
+#[test]
+fn self_interface() {
+    let module = ConcreteModuleImpl::builder().build();
+    let config: Arc<Config> = module.resolve();
+
+    assert_eq!(config.value, 3);
+}
+
+/// Concrete components can be injected into other components
+#[test]
+fn inject_concrete() {
+    let module = ConcreteModuleImpl::builder()
+        .with_component_parameters::<Config>(ConfigParameters { value: 5 })
+        .build();
+    let greeter: &dyn Greeter = module.resolve_ref();
+
+    assert_eq!(greeter.greet(), "Hello 5");
+}
+
+/// A generic derived component can name its own type as the interface
+#[test]
+fn generic_self_interface() {
+    let module = ConcreteModuleImpl::builder().build();
+    let counter: &Counter<u32> = module.resolve_ref();
+
+    assert_eq!(counter.start, 0);
+}
+
+/// Adapters work on top of concrete interfaces
+#[test]
+fn adapter_of_concrete() {
+    let module = ConcreteModuleImpl::builder().build();
+    let source: &dyn ValueSource = module.resolve_ref();
+
+    assert_eq!(source.value(), 7);
+}
+
+/// Concrete components can be used via module interfaces and submodules
+#[test]
+fn module_interface_and_submodule() {
+    let submodule = Arc::new(ConcreteModuleImpl::builder().build());
+    let interface: Arc<dyn ConcreteModule> = submodule.clone();
+    let module = OuterModule::builder(submodule).build();
+    let counter: Arc<Counter<u32>> = interface.resolve();
+    let config: &Config = module.resolve_ref();
+
+    assert_eq!(counter.start, 0);
+    assert_eq!(config.value, 3);
+}
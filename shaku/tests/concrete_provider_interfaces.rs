@@ -0,0 +1,118 @@
+//! Providers can use concrete types (including generic types) as their interface
+
+use shaku::{module, module_interface, Component, HasProvider, Interface, Module, Provider};
+use std::error::Error;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+trait Config: Interface {
+    fn value(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = Config)]
+struct ConfigImpl {
+    #[shaku(default = 3)]
+    value: usize,
+}
+
+impl Config for ConfigImpl {
+    fn value(&self) -> usize {
+        self.value
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Self)]
+struct RequestContext {
+    #[shaku(inject)]
+    config: Arc<dyn Config>,
+}
+
+#[derive(Provider)]
+#[shaku(interface = Scoped<T>)]
+struct Scoped<T: Interface + ?Sized> {
+    #[shaku(inject)]
+    service: Arc<T>,
+}
+
+struct Connection<T> {
+    value: T,
+}
+
+struct ConnectionProvider<T>(PhantomData<T>);
+
+impl<M: Module, T: Default + 'static> Provider<M> for ConnectionProvider<T> {
+    type Interface = Connection<T>;
+
+    fn provide(_: &M) -> Result<Box<Connection<T>>, Box<dyn Error>> {
+        Ok(Box::new(Connection {
+            value: T::default(),
+        }))
+    }
+}
+
+module_interface! {
+    trait ConcreteModule {
+        components = [],
+        providers = [RequestContext, Connection<u32>]
+    }
+}
+
+module! {
+    ConcreteModuleImpl: ConcreteModule {
+        components = [ConfigImpl],
+        providers = [RequestContext, Scoped<dyn Config>, ConnectionProvider<u32>]
+    }
+}
+
+module! {
+    OuterModule {
+        components = [],
+        providers = [],
+
+        use ConcreteModuleImpl {
+            components = [],
+            providers = [RequestContext, Connection<u32>]
+        }
+    }
+}
+
+/// A derived provider can use its own type as the interface via `Self`
+#[test]
+fn self_interface() {
+    let module = ConcreteModuleImpl::builder().build();
+    let context: Box<RequestContext> = module.provide().unwrap();
+
+    assert_eq!(context.config.value(), 3);
+}
+
+/// A generic derived provider can name its own type as the interface
+#[test]
+fn generic_self_interface() {
+    let module = ConcreteModuleImpl::builder().build();
+    let scoped: Box<Scoped<dyn Config>> = module.provide().unwrap();
+
+    assert_eq!(scoped.service.value(), 3);
+}
+
+/// Generic concrete interfaces can be listed in module interfaces
+#[test]
+fn module_interface_generic_concrete() {
+    let module: Arc<dyn ConcreteModule> = Arc::new(ConcreteModuleImpl::builder().build());
+    let connection: Box<Connection<u32>> = module.provide().unwrap();
+
+    assert_eq!(connection.value, 0);
+}
+
+/// Concrete interfaces, including generic ones, can be used from submodules
+#[test]
+fn submodule_concrete() {
+    let submodule = Arc::new(ConcreteModuleImpl::builder().build());
+    let module = OuterModule::builder(submodule).build();
+    let context: Box<RequestContext> = module.provide().unwrap();
+    let connection: Box<Connection<u32>> = module.provide().unwrap();
+
+    assert_eq!(context.config.value(), 3);
+    assert_eq!(connection.value, 0);
+}
@@ -0,0 +1,84 @@
+//! Services can be registered conditionally via `#[cfg]` or feature groups
+
+use shaku::{module, Component, HasComponent, Interface, ModuleServices, Provider};
+
+trait Cache: Interface {
+    fn name(&self) -> &'static str;
+}
+trait Logger: Interface {}
+trait Request {}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+#[allow(dead_code)]
+struct SharedCache;
+impl Cache for SharedCache {
+    fn name(&self) -> &'static str {
+        "shared"
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+#[allow(dead_code)]
+struct LocalCache;
+impl Cache for LocalCache {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = Request)]
+#[allow(dead_code)]
+struct RequestImpl;
+impl Request for RequestImpl {}
+
+module! {
+    #[shaku(derive_clone)]
+    ConditionalModule {
+        components = [
+            #[cfg(all())] LoggerImpl,
+            "thread_safe" => [SharedCache],
+            #[cfg(not(feature = "thread_safe"))] LocalCache,
+        ],
+        providers = [#[cfg(any())] RequestImpl]
+    }
+}
+
+/// Only the active implementation of an interface is registered
+#[test]
+fn feature_group() {
+    let module = ConditionalModule::builder().build();
+    let cache: &dyn Cache = module.resolve_ref();
+
+    if cfg!(feature = "thread_safe") {
+        assert_eq!(cache.name(), "shared");
+    } else {
+        assert_eq!(cache.name(), "local");
+    }
+}
+
+/// Services with an inactive `#[cfg]` (including the provider) are not part of the module
+#[test]
+fn cfg_attributes() {
+    let module = ConditionalModule::builder().build().clone();
+    let _logger: &dyn Logger = module.resolve_ref();
+
+    let services: Vec<&str> = ConditionalModule::services()
+        .iter()
+        .map(|service| service.interface_name())
+        .collect();
+    assert_eq!(
+        services,
+        [
+            "dyn conditional_services::Logger",
+            "dyn conditional_services::Cache"
+        ]
+    );
+}
@@ -0,0 +1,87 @@
+//! Components can delegate their interface to a field, ex. a type from an
+//! external crate
+
+use shaku::{module, Component, HasComponent, Interface};
+use std::sync::Arc;
+
+/// Stands in for a client type from another crate
+#[derive(Default)]
+struct ExternalClient {
+    base_url: String,
+}
+
+impl ExternalClient {
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+trait HttpClient: Interface {
+    fn url(&self, path: &str) -> String;
+}
+
+impl HttpClient for ExternalClient {
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path)
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = HttpClient, delegate = client)]
+struct HttpClientImpl {
+    client: ExternalClient,
+}
+
+#[derive(Component)]
+#[shaku(interface = HttpClient, delegate = 0)]
+struct TupleHttpClientImpl(#[shaku(default)] ExternalClient);
+
+module! {
+    TestModule {
+        components = [HttpClientImpl],
+        providers = []
+    }
+}
+
+module! {
+    TupleModule {
+        components = [TupleHttpClientImpl],
+        providers = []
+    }
+}
+
+/// The module stores the delegate as the component
+#[test]
+fn resolve_delegate() {
+    let module = TestModule::builder()
+        .with_component_parameters::<HttpClientImpl>(HttpClientImplParameters {
+            client: ExternalClient {
+                base_url: "https://example.com".to_string(),
+            },
+        })
+        .build();
+    let client: Arc<dyn HttpClient> = module.resolve();
+
+    assert_eq!(client.url("users"), "https://example.com/users");
+}
+
+/// Tuple struct components delegate by index
+#[test]
+fn resolve_tuple_delegate() {
+    let module = TupleModule::builder().build();
+    let client: &dyn HttpClient = module.resolve_ref();
+
+    assert_eq!(client.url("users"), "/users");
+}
+
+/// The component derefs to the delegate
+#[test]
+fn deref_to_delegate() {
+    let component = HttpClientImpl {
+        client: ExternalClient {
+            base_url: "https://example.com".to_string(),
+        },
+    };
+
+    assert_eq!(component.base_url(), "https://example.com");
+}
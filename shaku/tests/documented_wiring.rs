@@ -0,0 +1,77 @@
+//! Doc comments on module services are forwarded to the generated impls
+
+#![deny(unused_doc_comments)]
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
+use std::sync::Arc;
+
+trait Database: Interface {
+    fn url(&self) -> &str;
+}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct PostgresDatabase;
+
+impl Database for PostgresDatabase {
+    fn url(&self) -> &str {
+        "postgres://localhost"
+    }
+}
+
+trait Repository {
+    fn database_url(&self) -> String;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Repository)]
+struct RepositoryImpl {
+    #[shaku(inject)]
+    database: Arc<dyn Database>,
+}
+
+impl Repository for RepositoryImpl {
+    fn database_url(&self) -> String {
+        self.database.url().to_string()
+    }
+}
+
+module! {
+    DatabaseModule {
+        components = [
+            /// The primary database
+            #[lazy]
+            PostgresDatabase
+        ],
+        providers = []
+    }
+}
+
+module! {
+    AppModule {
+        components = [],
+        providers = [
+            /// Builds a repository for each request
+            ///
+            /// Uses the database of the `DatabaseModule`
+            RepositoryImpl
+        ],
+
+        use DatabaseModule {
+            components = [dyn Database],
+            providers = []
+        }
+    }
+}
+
+/// Documented services are wired as usual
+#[test]
+fn documented_services_resolve() {
+    let database_module = Arc::new(DatabaseModule::builder().build());
+    let module = AppModule::builder(database_module).build();
+
+    let database: &dyn Database = module.resolve_ref();
+    let repository: Box<dyn Repository> = module.provide().unwrap();
+    assert_eq!(database.url(), "postgres://localhost");
+    assert_eq!(repository.database_url(), "postgres://localhost");
+}
@@ -0,0 +1,106 @@
+//! Components can be registered by their interface's type id, for generated wiring
+
+use shaku::{module, Component, DynRegistration, HasComponent, Interface};
+use std::any::{Any, TypeId};
+use std::sync::Arc;
+
+trait Config: Interface {
+    fn name(&self) -> &str;
+}
+
+#[derive(Component)]
+#[shaku(interface = Config)]
+struct ConfigImpl;
+
+impl Config for ConfigImpl {
+    fn name(&self) -> &str {
+        "config"
+    }
+}
+
+trait Greeter: Interface {
+    fn greet(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct DefaultGreeter;
+
+impl Greeter for DefaultGreeter {
+    fn greet(&self) -> String {
+        "default".to_string()
+    }
+}
+
+struct GeneratedGreeter {
+    config: Arc<dyn Config>,
+}
+
+impl Greeter for GeneratedGreeter {
+    fn greet(&self) -> String {
+        format!("generated with {}", self.config.name())
+    }
+}
+
+trait Unused: Interface {}
+
+module! {
+    TestModule {
+        components = [ConfigImpl, DefaultGreeter],
+        providers = []
+    }
+}
+
+fn greet(module: &TestModule) -> String {
+    let greeter: &dyn Greeter = module.resolve_ref();
+    greeter.greet()
+}
+
+/// Registrations replace the module's component, and can resolve dependencies
+#[test]
+fn registration_builds_component() {
+    let registration = DynRegistration::<TestModule>::new::<dyn Greeter>(Box::new(|context| {
+        Box::new(GeneratedGreeter {
+            config: TestModule::build_component(context),
+        })
+    }));
+    let module = TestModule::builder()
+        .with_registrations(vec![registration])
+        .build();
+
+    assert_eq!(greet(&module), "generated with config");
+}
+
+/// Components without a registration are built as usual
+#[test]
+fn no_registrations() {
+    let module = TestModule::builder().with_registrations(Vec::new()).build();
+
+    assert_eq!(greet(&module), "default");
+}
+
+/// Registrations for interfaces the module doesn't have are rejected
+#[test]
+#[should_panic(expected = "has no component with this interface")]
+fn unknown_interface() {
+    let registration = DynRegistration::<TestModule> {
+        interface: TypeId::of::<dyn Unused>(),
+        build: Box::new(|_| Box::new(()) as Box<dyn Any>),
+    };
+
+    TestModule::builder().with_registrations(vec![registration]);
+}
+
+/// Build functions which return the wrong type fail when the component is built
+#[test]
+#[should_panic(expected = "did not build a Box<dyn dyn_registration::Greeter>")]
+fn wrong_type() {
+    let registration = DynRegistration::<TestModule> {
+        interface: TypeId::of::<dyn Greeter>(),
+        build: Box::new(|_| Box::new(DefaultGreeter) as Box<dyn Any>),
+    };
+
+    TestModule::builder()
+        .with_registrations(vec![registration])
+        .build();
+}
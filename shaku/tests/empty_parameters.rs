@@ -0,0 +1,101 @@
+//! Components with empty parameters are built without looking up their parameters
+
+use shaku::{module, Component, HasComponent, Interface, Module, ModuleBuildContext};
+use std::sync::Arc;
+
+trait Logger: Interface {
+    fn prefix(&self) -> String;
+}
+trait Clock: Interface {}
+trait Greeter: Interface {
+    fn greet(&self) -> String;
+}
+
+struct LoggerImpl;
+impl Logger for LoggerImpl {
+    fn prefix(&self) -> String {
+        "[log]".to_string()
+    }
+}
+
+impl<M: Module> Component<M> for LoggerImpl {
+    type Interface = dyn Logger;
+    type Parameters = ();
+    const EMPTY_PARAMETERS: bool = true;
+
+    fn build(_: &mut ModuleBuildContext<M>, _: ()) -> Box<dyn Logger> {
+        Box::new(LoggerImpl)
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Clock)]
+struct ClockImpl;
+impl Clock for ClockImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct GreeterImpl {
+    #[shaku(inject)]
+    logger: Arc<dyn Logger>,
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    clock: Arc<dyn Clock>,
+    #[shaku(default = "Hello".to_string())]
+    greeting: String,
+}
+impl Greeter for GreeterImpl {
+    fn greet(&self) -> String {
+        format!("{} {}", self.logger.prefix(), self.greeting)
+    }
+}
+
+module! {
+    TestModule {
+        components = [LoggerImpl, ClockImpl, GreeterImpl],
+        providers = []
+    }
+}
+
+struct OverrideLogger;
+impl Logger for OverrideLogger {
+    fn prefix(&self) -> String {
+        "[override]".to_string()
+    }
+}
+
+/// Components with and without parameters are built together
+#[test]
+fn build_mixed_parameters() {
+    let module = TestModule::builder()
+        .with_component_parameters::<GreeterImpl>(GreeterImplParameters {
+            greeting: "Hi".to_string(),
+        })
+        .build();
+
+    let greeter: &dyn Greeter = module.resolve_ref();
+    assert_eq!(greeter.greet(), "[log] Hi");
+}
+
+/// Components with empty parameters can still be overridden by a function
+#[test]
+fn override_component_fn_without_parameters() {
+    let module = TestModule::builder()
+        .with_component_override_fn::<dyn Logger>(Box::new(|_| Box::new(OverrideLogger)))
+        .build();
+
+    let greeter: &dyn Greeter = module.resolve_ref();
+    assert_eq!(greeter.greet(), "[override] Hello");
+}
+
+/// Setting empty parameters has no effect
+#[test]
+fn set_empty_parameters() {
+    let module = TestModule::builder()
+        .with_component_parameters::<LoggerImpl>(())
+        .with_component_parameters::<ClockImpl>(ClockImplParameters {})
+        .build();
+
+    let logger: &dyn Logger = module.resolve_ref();
+    assert_eq!(logger.prefix(), "[log]");
+}
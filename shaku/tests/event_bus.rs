@@ -0,0 +1,150 @@
+//! Components can subscribe to events published on an `EventBus`
+
+use shaku::extras::{EventBus, Subscriber};
+use shaku::{module, Component, HasComponent, Interface, RebuildModule};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Debug, PartialEq)]
+enum AppEvent {
+    Started,
+    UserCreated(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Shutdown;
+
+trait AuditLog: Interface + Subscriber<AppEvent> + Subscriber<Shutdown> {
+    fn entries(&self) -> Vec<String>;
+}
+
+#[derive(Component)]
+#[shaku(interface = AuditLog, subscribe = AppEvent, subscribe = Shutdown)]
+struct AuditLogImpl {
+    #[shaku(default)]
+    entries: Mutex<Vec<String>>,
+}
+
+impl AuditLog for AuditLogImpl {
+    fn entries(&self) -> Vec<String> {
+        self.entries.lock().unwrap().clone()
+    }
+}
+
+impl Subscriber<AppEvent> for AuditLogImpl {
+    fn on(&self, event: &AppEvent) {
+        self.entries.lock().unwrap().push(format!("{:?}", event));
+    }
+}
+
+impl Subscriber<Shutdown> for AuditLogImpl {
+    fn on(&self, _: &Shutdown) {
+        self.entries.lock().unwrap().push("shutdown".to_string());
+    }
+}
+
+trait Counter: Interface + Subscriber<AppEvent> {
+    fn count(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = Counter, subscribe = AppEvent)]
+struct CounterImpl {
+    #[shaku(default)]
+    count: Mutex<usize>,
+}
+
+impl Counter for CounterImpl {
+    fn count(&self) -> usize {
+        *self.count.lock().unwrap()
+    }
+}
+
+impl Subscriber<AppEvent> for CounterImpl {
+    fn on(&self, _: &AppEvent) {
+        *self.count.lock().unwrap() += 1;
+    }
+}
+
+module! {
+    TestModule {
+        components = [AuditLogImpl, CounterImpl, EventBus<AppEvent>, EventBus<Shutdown>],
+        providers = []
+    }
+}
+
+/// Subscribers are wired to the bus during module build
+#[test]
+fn subscribers_receive_events() {
+    let module = TestModule::builder().build();
+    let bus: &EventBus<AppEvent> = module.resolve_ref();
+    let audit_log: &dyn AuditLog = module.resolve_ref();
+    let counter: &dyn Counter = module.resolve_ref();
+
+    assert_eq!(bus.subscriber_count(), 2);
+
+    bus.publish(&AppEvent::Started);
+    bus.publish(&AppEvent::UserCreated("alice".to_string()));
+
+    assert_eq!(
+        audit_log.entries(),
+        vec!["Started".to_string(), "UserCreated(\"alice\")".to_string()]
+    );
+    assert_eq!(counter.count(), 2);
+}
+
+/// A component can subscribe to multiple event types
+#[test]
+fn multiple_subscriptions() {
+    let module = TestModule::builder().build();
+    let shutdown_bus: &EventBus<Shutdown> = module.resolve_ref();
+    let audit_log: &dyn AuditLog = module.resolve_ref();
+
+    shutdown_bus.publish(&Shutdown);
+
+    assert_eq!(audit_log.entries(), vec!["shutdown".to_string()]);
+}
+
+/// Overridden components are not subscribed automatically
+#[test]
+fn overridden_components_are_not_subscribed() {
+    let module = TestModule::builder()
+        .with_component_override::<dyn Counter>(Box::new(CounterImpl {
+            count: Mutex::new(0),
+        }))
+        .build();
+    let bus: &EventBus<AppEvent> = module.resolve_ref();
+
+    assert_eq!(bus.subscriber_count(), 1);
+}
+
+/// Subscribers can also be added manually
+#[test]
+fn manual_subscription() {
+    let module = TestModule::builder().build();
+    let bus: Arc<EventBus<AppEvent>> = module.resolve();
+    let counter = Arc::new(CounterImpl {
+        count: Mutex::new(0),
+    });
+
+    bus.subscribe(counter.clone());
+    bus.publish(&AppEvent::Started);
+
+    assert_eq!(counter.count(), 1);
+}
+
+/// Rebuilding the module builds the buses and subscribers again, so each is subscribed once
+#[test]
+fn rebuild_resubscribes() {
+    let module = TestModule::builder().build();
+    let rebuilt = module.rebuild(TestModule::builder());
+    let bus: &EventBus<AppEvent> = rebuilt.resolve_ref();
+    let counter: &dyn Counter = rebuilt.resolve_ref();
+    let previous_counter: &dyn Counter = module.resolve_ref();
+
+    assert_eq!(bus.subscriber_count(), 2);
+
+    bus.publish(&AppEvent::Started);
+
+    assert_eq!(counter.count(), 1);
+    assert_eq!(previous_counter.count(), 0);
+}
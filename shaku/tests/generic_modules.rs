@@ -1,7 +1,7 @@
 //! Modules and services can be generic. Based off of issue #2:
 //! https://github.com/AzureMarker/shaku/issues/2
 
-use shaku::{module, Component, HasProvider, Interface, Provider};
+use shaku::{module, Component, HasProvider, Interface, ModuleServices, Provider};
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -35,6 +35,13 @@ module! {
     }
 }
 
+module! {
+    WhereModule<E> where E: Debug + Default + Interface {
+        components = [RegisterServiceImpl<E>],
+        providers = [RegisterProviderImpl<E>]
+    }
+}
+
 #[test]
 fn can_use_generic_service_impl() {
     let module = MyModule::<()>::builder().build();
@@ -45,3 +52,13 @@ fn can_use_generic_service_impl() {
         "RegisterProviderImpl { register_service: RegisterServiceImpl { executor: () } }"
     );
 }
+
+/// Generic modules implement ModuleServices with the module's own bounds
+#[test]
+fn generic_module_services() {
+    assert_eq!(MyModule::<()>::services().iter().count(), 2);
+    assert_eq!(WhereModule::<()>::services().iter().count(), 2);
+
+    let report = shaku::verify::<WhereModule<()>>();
+    assert!(report.is_ok(), "{}", report);
+}
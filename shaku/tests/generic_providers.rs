@@ -0,0 +1,92 @@
+//! Providers can be generic over types which are not services, with bounds and where clauses
+
+use shaku::{module, Component, HasProvider, Interface, Provider};
+use std::fmt::{Debug, Display};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+trait Export<T> {
+    fn export(&self, value: T) -> String;
+}
+
+trait Prefix: Interface {
+    fn prefix(&self) -> &str;
+}
+
+#[derive(Component)]
+#[shaku(interface = Prefix)]
+struct PrefixImpl;
+
+impl Prefix for PrefixImpl {
+    fn prefix(&self) -> &str {
+        "value: "
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Export<T>)]
+struct Exporter<T: Display>
+where
+    T: Clone,
+{
+    #[shaku(inject)]
+    prefix: Arc<dyn Prefix>,
+    #[shaku(default)]
+    marker: PhantomData<T>,
+}
+
+impl<T: Display> Export<T> for Exporter<T>
+where
+    T: Clone,
+{
+    fn export(&self, value: T) -> String {
+        format!("{}{}", self.prefix.prefix(), value)
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Export<T>)]
+struct DebugExporter<T: Debug = u32> {
+    #[shaku(default = PhantomData)]
+    marker: PhantomData<T>,
+}
+
+impl<T: Debug> Export<T> for DebugExporter<T> {
+    fn export(&self, value: T) -> String {
+        format!("{:?}", value)
+    }
+}
+
+module! {
+    TestModule {
+        components = [PrefixImpl],
+        providers = [Exporter<u32>, DebugExporter<&'static str>]
+    }
+}
+
+module! {
+    GenericModule<T: Display + Clone + 'static> {
+        components = [PrefixImpl],
+        providers = [Exporter<T>]
+    }
+}
+
+/// Generic providers are built with their injected dependencies and default fields
+#[test]
+fn provide_generic_service() {
+    let module = TestModule::builder().build();
+    let exporter: Box<dyn Export<u32>> = module.provide().unwrap();
+    let debug_exporter: Box<dyn Export<&'static str>> = module.provide().unwrap();
+
+    assert_eq!(exporter.export(3), "value: 3");
+    assert_eq!(debug_exporter.export("text"), "\"text\"");
+}
+
+/// Generic providers can use the generics of the module
+#[test]
+fn provide_from_generic_module() {
+    let module = GenericModule::<String>::builder().build();
+    let exporter: Box<dyn Export<String>> = module.provide().unwrap();
+
+    assert_eq!(exporter.export("text".to_string()), "value: text");
+}
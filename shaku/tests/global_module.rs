@@ -0,0 +1,66 @@
+//! A module can be stored in a static and built lazily via Global
+
+use shaku::{module, Component, Global, HasComponent, Interface, Provider};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+trait Logger: Interface {
+    fn name(&self) -> &str;
+}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {
+    fn name(&self) -> &str {
+        "logger"
+    }
+}
+
+trait Request {}
+
+#[derive(Provider)]
+#[shaku(interface = Request)]
+struct RequestImpl;
+impl Request for RequestImpl {}
+
+module! {
+    AppModule {
+        components = [LoggerImpl],
+        providers = [RequestImpl]
+    }
+}
+
+static BUILD_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+fn build_app() -> AppModule {
+    BUILD_COUNT.fetch_add(1, Ordering::SeqCst);
+    AppModule::builder().build()
+}
+
+static APP: Global<AppModule> = Global::new(build_app);
+
+/// The module is built once, even when first used by multiple threads
+#[test]
+fn built_once() {
+    let threads: Vec<_> = (0..4)
+        .map(|_| thread::spawn(|| APP.resolve::<dyn Logger>()))
+        .collect();
+
+    for thread in threads {
+        assert_eq!(thread.join().unwrap().name(), "logger");
+    }
+
+    assert_eq!(BUILD_COUNT.load(Ordering::SeqCst), 1);
+}
+
+/// Components and providers can be used through the global module
+#[test]
+fn resolve_and_provide() {
+    let logger: &'static dyn Logger = APP.resolve_ref();
+    let same_logger: Arc<dyn Logger> = APP.get().resolve();
+
+    assert_eq!(logger.name(), same_logger.name());
+    assert!(APP.provide::<dyn Request>().is_ok());
+}
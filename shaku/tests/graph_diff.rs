@@ -0,0 +1,163 @@
+//! Module graphs can be compared via shaku::debug::diff
+
+use shaku::debug::{self, GraphSource};
+use shaku::{module, Component, HasComponent, Interface, Provider, ServiceKind};
+use std::sync::Arc;
+
+trait Database: Interface {
+    fn url(&self) -> &str;
+}
+trait Cache: Interface {}
+trait Connection {}
+
+#[derive(Component)]
+#[shaku(interface = Database, parameters_derive(Debug))]
+struct PostgresDatabase {
+    url: String,
+}
+impl Database for PostgresDatabase {
+    fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct MemoryDatabase;
+impl Database for MemoryDatabase {
+    fn url(&self) -> &str {
+        "memory"
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl;
+impl Cache for CacheImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct ConnectionImpl;
+impl Connection for ConnectionImpl {}
+
+module! {
+    CacheModule {
+        components = [CacheImpl],
+        providers = []
+    }
+}
+
+module! {
+    BlueModule {
+        components = [PostgresDatabase],
+        providers = [ConnectionImpl],
+
+        use CacheModule {
+            components = [dyn Cache],
+            providers = []
+        }
+    }
+}
+
+module! {
+    GreenModule {
+        components = [MemoryDatabase],
+        providers = []
+    }
+}
+
+fn blue(url: &str) -> BlueModule {
+    BlueModule::builder(Arc::new(CacheModule::builder().build()))
+        .record_graph()
+        .with_component_parameters::<PostgresDatabase>(PostgresDatabaseParameters {
+            url: url.to_string(),
+        })
+        .build()
+}
+
+/// The graph records the implementation and parameters of each service
+#[test]
+fn module_graph() {
+    let module = blue("postgres://blue");
+    let graph = module.module_graph();
+    let database = graph
+        .service(
+            ServiceKind::Component,
+            std::any::type_name::<dyn Database>(),
+        )
+        .unwrap();
+    let cache = graph
+        .service(ServiceKind::Component, std::any::type_name::<dyn Cache>())
+        .unwrap();
+
+    assert_eq!(graph.services().len(), 3);
+    assert!(database.implementation.ends_with("PostgresDatabase"));
+    assert_eq!(
+        database.parameters.as_deref(),
+        Some("PostgresDatabaseParameters { url: \"postgres://blue\" }")
+    );
+    assert!(cache.from_submodule);
+    assert!(cache.implementation.ends_with("CacheModule"));
+
+    let database: &dyn Database = module.resolve_ref();
+    assert_eq!(database.url(), "postgres://blue");
+}
+
+/// Modules with the same wiring and configuration have no differences
+#[test]
+fn identical() {
+    assert!(debug::diff(&blue("postgres://blue"), &blue("postgres://blue")).is_empty());
+}
+
+/// Parameter changes are reported
+#[test]
+fn parameters_changed() {
+    let diff = debug::diff(&blue("postgres://blue"), &blue("postgres://green"));
+
+    assert!(diff.added.is_empty());
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.changed.len(), 1);
+    assert!(diff.changed[0].parameters_changed());
+    assert!(!diff.changed[0].implementation_changed());
+}
+
+/// Implementation changes and added/removed services are reported
+#[test]
+fn wiring_changed() {
+    let blue = blue("postgres://blue");
+    let green = GreenModule::builder().record_graph().build();
+    let diff = debug::diff(&blue, &green);
+
+    assert_eq!(diff.changed.len(), 1);
+    assert!(diff.changed[0].implementation_changed());
+    assert!(diff.added.is_empty());
+    assert_eq!(diff.removed.len(), 2);
+    assert_eq!(diff.removed[0].kind, ServiceKind::Provider);
+    assert_eq!(diff.removed[1].kind, ServiceKind::Component);
+
+    let reverse = debug::diff(&green, blue.module_graph());
+    assert_eq!(reverse.added.len(), 2);
+    assert!(reverse.to_string().starts_with("+ provider"));
+}
+
+/// Overridden services are reported as implementation changes
+#[test]
+fn overridden() {
+    let overridden = BlueModule::builder(Arc::new(CacheModule::builder().build()))
+        .record_graph()
+        .with_component_override::<dyn Database>(Box::new(MemoryDatabase))
+        .build();
+    let diff = debug::diff(&blue("postgres://blue"), &overridden);
+
+    assert_eq!(diff.changed.len(), 1);
+    assert!(diff.changed[0].after.overridden);
+    assert!(diff.changed[0].implementation_changed());
+}
+
+/// The graph is only recorded if requested
+#[test]
+#[should_panic(expected = "was not recorded")]
+fn graph_not_recorded() {
+    let green = GreenModule::builder().build();
+    debug::diff(&green, &green);
+}
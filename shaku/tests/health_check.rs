@@ -0,0 +1,104 @@
+//! Components can be registered with the module's `HealthRegistry`
+
+use shaku::extras::{HealthCheck, HealthRegistry, HealthStatus, ModuleHealth};
+use shaku::{module, Component, HasComponent, Interface};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+trait Database: Interface + HealthCheck {
+    fn disconnect(&self);
+}
+
+#[derive(Component)]
+#[shaku(interface = Database, health_check)]
+struct DatabaseImpl {
+    #[shaku(default)]
+    disconnected: AtomicBool,
+}
+
+impl Database for DatabaseImpl {
+    fn disconnect(&self) {
+        self.disconnected.store(true, Ordering::SeqCst);
+    }
+}
+
+impl HealthCheck for DatabaseImpl {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    fn check(&self) -> HealthStatus {
+        if self.disconnected.load(Ordering::SeqCst) {
+            HealthStatus::Unhealthy("disconnected".to_string())
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+}
+
+trait Cache: Interface + HealthCheck {}
+
+#[derive(Component)]
+#[shaku(interface = Cache, health_check)]
+struct CacheImpl;
+
+impl Cache for CacheImpl {}
+
+impl HealthCheck for CacheImpl {
+    fn check(&self) -> HealthStatus {
+        HealthStatus::Degraded("cold".to_string())
+    }
+}
+
+module! {
+    TestModule {
+        components = [HealthRegistry, DatabaseImpl, CacheImpl],
+        providers = []
+    }
+}
+
+/// Health check components are registered when the module is built
+#[test]
+fn checks_are_registered() {
+    let module = TestModule::builder().build();
+    let registry: Arc<HealthRegistry> = module.resolve();
+
+    assert_eq!(registry.check_count(), 2);
+}
+
+/// The report contains the status of every check, and degraded checks are
+/// still healthy
+#[test]
+fn report_includes_each_check() {
+    let module = TestModule::builder().build();
+    let report = module.health();
+
+    assert!(report.is_healthy());
+    assert_eq!(
+        report.checks(),
+        [
+            ("database".to_string(), HealthStatus::Healthy),
+            (
+                "health_check::CacheImpl".to_string(),
+                HealthStatus::Degraded("cold".to_string())
+            )
+        ]
+    );
+}
+
+/// A single unhealthy check makes the report unhealthy
+#[test]
+fn unhealthy_check() {
+    let module = TestModule::builder().build();
+    let database: &dyn Database = module.resolve_ref();
+    database.disconnect();
+
+    let report = module.health();
+    assert!(!report.is_healthy());
+    assert_eq!(
+        report.to_string(),
+        "unhealthy\n\
+         - database: unhealthy (disconnected)\n\
+         - health_check::CacheImpl: degraded (cold)"
+    );
+}
@@ -0,0 +1,110 @@
+//! Components and providers can share a pooled HTTP client, and provide clients
+//! which add credentials to each request
+
+use shaku::extras::{
+    AuthenticatedClient, Credentials, HttpClient, PooledHttpClient, PooledHttpClientParameters,
+    StaticCredentials, StaticCredentialsParameters,
+};
+use shaku::{module, Component, Error, HasComponent, HasProvider};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[derive(Component)]
+#[shaku(interface = Credentials)]
+struct RotatingCredentials {
+    #[shaku(default)]
+    refreshes: AtomicUsize,
+}
+
+impl Credentials for RotatingCredentials {
+    fn authorization(&self) -> Result<String, Box<dyn Error>> {
+        let token = self.refreshes.fetch_add(1, Ordering::SeqCst);
+        Ok(format!("Bearer token-{}", token))
+    }
+}
+
+module! {
+    StaticModule {
+        components = [PooledHttpClient, StaticCredentials],
+        providers = [AuthenticatedClient]
+    }
+}
+
+module! {
+    RotatingModule {
+        components = [PooledHttpClient, RotatingCredentials],
+        providers = [AuthenticatedClient]
+    }
+}
+
+fn authorization(client: &AuthenticatedClient) -> String {
+    let request = client.get("http://localhost/users").build().unwrap();
+    request.headers()["authorization"]
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Provided clients send the module's credentials
+#[test]
+fn static_credentials() {
+    let module = StaticModule::builder()
+        .with_component_parameters::<PooledHttpClient>(PooledHttpClientParameters {
+            timeout: Some(Duration::from_secs(5)),
+            pool_max_idle_per_host: Some(4),
+            user_agent: Some("shaku-test".to_string()),
+            ..Default::default()
+        })
+        .with_component_parameters::<StaticCredentials>(StaticCredentialsParameters {
+            authorization: "Bearer secret".to_string(),
+        })
+        .build();
+    let client: Box<AuthenticatedClient> = module.provide().unwrap();
+
+    assert_eq!(authorization(&client), "Bearer secret");
+    assert!(!format!("{:?}", client).contains("secret"));
+}
+
+/// The credentials are read each time a client is provided
+#[test]
+fn rotating_credentials() {
+    let module = RotatingModule::builder().build();
+    let first: Box<AuthenticatedClient> = module.provide().unwrap();
+    let second: Box<AuthenticatedClient> = module.provide().unwrap();
+
+    assert_eq!(authorization(&first), "Bearer token-0");
+    assert_eq!(authorization(&second), "Bearer token-1");
+}
+
+/// An existing client can be shared with the module
+#[test]
+fn existing_client() {
+    let client = reqwest::Client::new();
+    let module = StaticModule::builder()
+        .with_component_parameters::<PooledHttpClient>(PooledHttpClientParameters {
+            client: Some(client),
+            ..Default::default()
+        })
+        .build();
+    let http_client: &dyn HttpClient = module.resolve_ref();
+
+    let request = http_client
+        .client()
+        .get("http://localhost/")
+        .build()
+        .unwrap();
+    assert!(request.headers().get("authorization").is_none());
+}
+
+/// Invalid credentials are reported when the client is provided
+#[test]
+fn invalid_credentials() {
+    let module = StaticModule::builder()
+        .with_component_parameters::<StaticCredentials>(StaticCredentialsParameters {
+            authorization: "Bearer\nsecret".to_string(),
+        })
+        .build();
+    let client: Result<Box<AuthenticatedClient>, _> = module.provide();
+
+    assert!(client.is_err());
+}
@@ -0,0 +1,143 @@
+//! Rebuilding a module only rebuilds the components affected by the new parameters
+
+use shaku::{module, Component, HasComponent, Interface, RebuildModule};
+use std::sync::Arc;
+
+trait Config: Interface {
+    fn port(&self) -> u16;
+}
+
+#[derive(Component)]
+#[shaku(interface = Config)]
+struct ConfigImpl {
+    #[shaku(default = 80)]
+    port: u16,
+}
+
+impl Config for ConfigImpl {
+    fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+trait Server: Interface {
+    fn port(&self) -> u16;
+}
+
+#[derive(Component)]
+#[shaku(interface = Server)]
+struct ServerImpl {
+    #[shaku(inject)]
+    config: Arc<dyn Config>,
+}
+
+impl Server for ServerImpl {
+    fn port(&self) -> u16 {
+        self.config.port()
+    }
+}
+
+trait Logger: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+
+impl Logger for LoggerImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct OtherLogger;
+
+impl Logger for OtherLogger {}
+
+trait Cache: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl;
+
+impl Cache for CacheImpl {}
+
+module! {
+    TestModule {
+        components = [ConfigImpl, ServerImpl, LoggerImpl, #[lazy] CacheImpl],
+        providers = []
+    }
+}
+
+/// Components with new parameters and their dependents are rebuilt, the rest are reused
+#[test]
+fn rebuilds_changed_components() {
+    let module = TestModule::builder().build();
+    let rebuilt = module.rebuild(
+        TestModule::builder()
+            .with_component_parameters::<ConfigImpl>(ConfigImplParameters { port: 8080 }),
+    );
+
+    let server: &dyn Server = rebuilt.resolve_ref();
+    assert_eq!(server.port(), 8080);
+    assert!(!Arc::ptr_eq(
+        &HasComponent::<dyn Config>::resolve(&module),
+        &HasComponent::<dyn Config>::resolve(&rebuilt)
+    ));
+    assert!(!Arc::ptr_eq(
+        &HasComponent::<dyn Server>::resolve(&module),
+        &HasComponent::<dyn Server>::resolve(&rebuilt)
+    ));
+    assert!(Arc::ptr_eq(
+        &HasComponent::<dyn Logger>::resolve(&module),
+        &HasComponent::<dyn Logger>::resolve(&rebuilt)
+    ));
+}
+
+/// Without parameters or overrides, every component is reused
+#[test]
+fn reuses_unchanged_components() {
+    let module = TestModule::builder()
+        .with_component_parameters::<ConfigImpl>(ConfigImplParameters { port: 8080 })
+        .build();
+    let rebuilt = module.rebuild(TestModule::builder());
+
+    let server: &dyn Server = rebuilt.resolve_ref();
+    assert_eq!(server.port(), 8080);
+    assert!(Arc::ptr_eq(
+        &HasComponent::<dyn Server>::resolve(&module),
+        &HasComponent::<dyn Server>::resolve(&rebuilt)
+    ));
+}
+
+/// Overridden components are used instead of the previous build's component
+#[test]
+fn overrides_replace_components() {
+    let module = TestModule::builder().build();
+    let rebuilt = module.rebuild(
+        TestModule::builder().with_component_override::<dyn Logger>(Box::new(OtherLogger)),
+    );
+
+    assert!(!Arc::ptr_eq(
+        &HasComponent::<dyn Logger>::resolve(&module),
+        &HasComponent::<dyn Logger>::resolve(&rebuilt)
+    ));
+    assert!(Arc::ptr_eq(
+        &HasComponent::<dyn Server>::resolve(&module),
+        &HasComponent::<dyn Server>::resolve(&rebuilt)
+    ));
+}
+
+/// Lazy components are reused once they were built
+#[test]
+fn reuses_built_lazy_components() {
+    let module = TestModule::builder().build();
+    let rebuilt = module.rebuild(TestModule::builder());
+    assert!(!Arc::ptr_eq(
+        &HasComponent::<dyn Cache>::resolve(&module),
+        &HasComponent::<dyn Cache>::resolve(&rebuilt)
+    ));
+
+    let rebuilt_again = module.rebuild(TestModule::builder());
+    assert!(Arc::ptr_eq(
+        &HasComponent::<dyn Cache>::resolve(&module),
+        &HasComponent::<dyn Cache>::resolve(&rebuilt_again)
+    ));
+}
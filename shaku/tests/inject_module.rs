@@ -0,0 +1,105 @@
+//! Components can receive a handle to their module via `#[shaku(inject_module)]`
+
+use shaku::{module, Component, HasComponent, Interface, ModuleHandle, RebuildModule};
+use std::sync::Arc;
+
+trait Greeter: Interface {
+    fn greet(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct GreeterImpl;
+
+impl Greeter for GreeterImpl {
+    fn greet(&self) -> String {
+        "Hello".to_string()
+    }
+}
+
+trait Orchestrator: Interface {
+    fn run(&self) -> Option<String>;
+}
+
+#[derive(Component)]
+#[shaku(interface = Orchestrator)]
+struct OrchestratorImpl {
+    #[shaku(inject_module)]
+    module: ModuleHandle<dyn GreeterModule>,
+}
+
+impl Orchestrator for OrchestratorImpl {
+    fn run(&self) -> Option<String> {
+        let module = self.module.get()?;
+        let greeter: &dyn Greeter = module.resolve_ref();
+        Some(greeter.greet())
+    }
+}
+
+trait GreeterModule: HasComponent<dyn Greeter> {}
+
+module! {
+    TestModule: GreeterModule {
+        components = [GreeterImpl, OrchestratorImpl],
+        providers = []
+    }
+}
+
+module! {
+    LazyModule: GreeterModule {
+        components = [GreeterImpl, #[lazy] OrchestratorImpl],
+        providers = []
+    }
+}
+
+/// The handle is set when the module is built via `build_arc`
+#[test]
+fn module_handle_set_by_build_arc() {
+    let module = TestModule::builder().build_arc();
+    let orchestrator: &dyn Orchestrator = module.resolve_ref();
+
+    assert_eq!(orchestrator.run(), Some("Hello".to_string()));
+}
+
+/// The handle is not set if the module is built via `build`
+#[test]
+fn module_handle_unset_by_build() {
+    let module = TestModule::builder().build();
+    let orchestrator: &dyn Orchestrator = module.resolve_ref();
+
+    assert_eq!(orchestrator.run(), None);
+}
+
+/// The handle doesn't keep the module alive
+#[test]
+fn module_handle_is_weak() {
+    let module = TestModule::builder().build_arc();
+    let orchestrator: Arc<dyn Orchestrator> = module.resolve();
+    drop(module);
+
+    assert_eq!(orchestrator.run(), None);
+}
+
+/// Lazy components built after `build_arc` get the handle right away
+#[test]
+fn lazy_component_module_handle() {
+    let module = LazyModule::builder().build_arc();
+    let orchestrator: &dyn Orchestrator = module.resolve_ref();
+
+    assert_eq!(orchestrator.run(), Some("Hello".to_string()));
+}
+
+/// Rebuilding the module builds the component again, so its handle is set to the new module
+#[test]
+fn rebuild_arc_sets_module_handle() {
+    let module = TestModule::builder().build_arc();
+    let rebuilt = module.rebuild_arc(TestModule::builder());
+    assert!(Arc::ptr_eq(
+        &HasComponent::<dyn Greeter>::resolve(&*module),
+        &HasComponent::<dyn Greeter>::resolve(&*rebuilt)
+    ));
+    drop(module);
+
+    let orchestrator: &dyn Orchestrator = rebuilt.resolve_ref();
+    assert_eq!(orchestrator.run(), Some("Hello".to_string()));
+}
@@ -0,0 +1,211 @@
+//! Components marked with `#[instrument]` trace the method calls of their interface
+
+// shaku's dev-dependencies must build on the minimum supported Rust version, so tracing is used via
+// shaku's re-export
+use shaku::__tracing::field::{Field, Visit};
+use shaku::__tracing::span::{Attributes, Id, Record};
+use shaku::__tracing::{subscriber, Event, Metadata, Subscriber};
+use shaku::{
+    async_interface, instrument_interface, module, Component, HasComponent, Instrument, Interface,
+};
+use std::fmt::{Debug, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[instrument_interface]
+trait Greeter: Interface {
+    fn greet(&self, name: &str) -> String;
+
+    fn wave(&self) {}
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct GreeterImpl {
+    #[shaku(default = "Hello".to_string())]
+    greeting: String,
+}
+
+impl Greeter for GreeterImpl {
+    fn greet(&self, name: &str) -> String {
+        format!("{}, {}!", self.greeting, name)
+    }
+}
+
+async_interface! {
+    #[instrument_interface]
+    trait Mailer: Interface {
+        async fn send(&self, to: &str) -> bool;
+    }
+}
+
+struct MailerImpl;
+
+async_interface! {
+    impl Mailer for MailerImpl {
+        async fn send(&self, _to: &str) -> bool {
+            true
+        }
+    }
+}
+
+trait Receptionist: Interface {
+    fn welcome(&self, name: &str) -> String;
+    fn greeter(&self) -> Arc<dyn Greeter>;
+}
+
+#[derive(Component)]
+#[shaku(interface = Receptionist)]
+struct ReceptionistImpl {
+    #[shaku(inject)]
+    greeter: Arc<dyn Greeter>,
+}
+
+impl Receptionist for ReceptionistImpl {
+    fn welcome(&self, name: &str) -> String {
+        self.greeter.greet(name)
+    }
+
+    fn greeter(&self) -> Arc<dyn Greeter> {
+        Arc::clone(&self.greeter)
+    }
+}
+
+module! {
+    TestModule {
+        components = [#[instrument] GreeterImpl, ReceptionistImpl],
+        providers = []
+    }
+}
+
+/// Records the spans and events as text
+#[derive(Default)]
+struct Recorder {
+    records: Arc<Mutex<Vec<String>>>,
+    next_id: AtomicU64,
+}
+
+#[derive(Default)]
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        write!(self.0, " {}={:?}", field.name(), value).unwrap();
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields::default();
+        span.record(&mut fields);
+        self.records
+            .lock()
+            .unwrap()
+            .push(format!("span {}{}", span.metadata().name(), fields.0));
+
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        self.records
+            .lock()
+            .unwrap()
+            .push(format!("event{}", fields.0));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+/// Run the function with the recorder, and return the records
+fn record(function: impl FnOnce()) -> Vec<String> {
+    let recorder = Recorder::default();
+    let records = Arc::clone(&recorder.records);
+    subscriber::with_default(recorder, function);
+
+    let records = records.lock().unwrap();
+    records.clone()
+}
+
+/// Each method call runs in a span, with enter and exit events
+#[test]
+fn method_calls_are_traced() {
+    let module = TestModule::builder().build();
+    let greeter: &dyn Greeter = module.resolve_ref();
+
+    let records = record(|| {
+        assert_eq!(greeter.greet("Alice"), "Hello, Alice!");
+        greeter.wave();
+    });
+
+    assert_eq!(
+        records,
+        vec![
+            "span greet interface=\"Greeter\"",
+            "event message=enter",
+            "event message=exit",
+            "span wave interface=\"Greeter\"",
+            "event message=enter",
+            "event message=exit",
+        ]
+    );
+}
+
+/// Dependents are injected with the same wrapper, so their calls are traced too
+#[test]
+fn dependents_share_the_wrapper() {
+    let module = TestModule::builder().build();
+    let greeter: Arc<dyn Greeter> = module.resolve();
+    let receptionist: &dyn Receptionist = module.resolve_ref();
+
+    assert!(Arc::ptr_eq(&greeter, &receptionist.greeter()));
+
+    let records = record(|| {
+        assert_eq!(receptionist.welcome("Bob"), "Hello, Bob!");
+    });
+
+    assert_eq!(records[0], "span greet interface=\"Greeter\"");
+}
+
+/// The parameters are set on the wrapped component
+#[test]
+fn parameters_of_wrapped_component() {
+    let module = TestModule::builder()
+        .with_component_parameters::<GreeterImpl>(GreeterImplParameters {
+            greeting: "Hi".to_string(),
+        })
+        .build();
+    let greeter: &dyn Greeter = module.resolve_ref();
+
+    assert_eq!(greeter.greet("Carol"), "Hi, Carol!");
+}
+
+/// Async methods are traced until they return their future
+#[test]
+fn async_methods_are_traced() {
+    let mailer = <dyn Mailer>::instrument(Arc::new(MailerImpl));
+
+    let records = record(|| {
+        let _future = mailer.send("Dave");
+    });
+
+    assert_eq!(
+        records,
+        vec![
+            "span send interface=\"Mailer\"",
+            "event message=enter",
+            "event message=exit",
+        ]
+    );
+}
@@ -0,0 +1,75 @@
+//! Traits which don't inherit `Interface` can be used via `interface_adapter!`
+
+use shaku::{interface_adapter, module, Component, HasComponent};
+use std::sync::Arc;
+
+/// Stands in for a crate which doesn't know about shaku
+mod foreign {
+    pub trait Handler<Request> {
+        type Response;
+
+        fn handle(&self, request: Request) -> Self::Response;
+    }
+
+    pub fn call_twice(handler: &dyn Handler<u32, Response = u32>, request: u32) -> u32 {
+        handler.handle(handler.handle(request))
+    }
+}
+
+interface_adapter!(dyn foreign::Handler<u32, Response = u32> as DoublingHandler);
+
+#[derive(Component)]
+#[shaku(interface = DoublingHandler)]
+struct Doubler;
+
+impl foreign::Handler<u32> for Doubler {
+    type Response = u32;
+
+    fn handle(&self, request: u32) -> u32 {
+        request * 2
+    }
+}
+
+trait Calculator: shaku::Interface {
+    fn quadruple(&self, value: u32) -> u32;
+}
+
+#[derive(Component)]
+#[shaku(interface = Calculator)]
+struct CalculatorImpl {
+    #[shaku(inject)]
+    handler: Arc<dyn DoublingHandler>,
+}
+
+impl Calculator for CalculatorImpl {
+    fn quadruple(&self, value: u32) -> u32 {
+        foreign::call_twice(&**self.handler, value)
+    }
+}
+
+module! {
+    TestModule {
+        components = [Doubler, CalculatorImpl],
+        providers = []
+    }
+}
+
+/// The foreign trait's methods can be called on the adapter
+#[test]
+fn call_foreign_methods() {
+    let module = TestModule::builder().build();
+    let handler: &dyn DoublingHandler = module.resolve_ref();
+
+    assert_eq!(handler.handle(3), 6);
+}
+
+/// The adapter can be passed where the foreign trait object is expected
+#[test]
+fn pass_as_foreign_trait() {
+    let module = TestModule::builder().build();
+    let calculator: &dyn Calculator = module.resolve_ref();
+    let handler: &dyn DoublingHandler = module.resolve_ref();
+
+    assert_eq!(calculator.quadruple(3), 12);
+    assert_eq!(foreign::call_twice(handler.as_foreign(), 1), 4);
+}
@@ -0,0 +1,59 @@
+//! Generic code can get the interface type names of a module's services
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
+use std::sync::Arc;
+
+trait Logger: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+trait Session {}
+
+#[derive(Provider)]
+#[shaku(interface = Session)]
+struct SessionImpl;
+impl Session for SessionImpl {}
+
+trait AppModule: HasComponent<dyn Logger> + HasProvider<dyn Session> {}
+
+module! {
+    TestModule: AppModule {
+        components = [LoggerImpl],
+        providers = [SessionImpl]
+    }
+}
+
+fn component_name<I: Interface + ?Sized, M: HasComponent<I> + ?Sized>(module: &M) -> &'static str {
+    module.interface_name()
+}
+
+fn provider_name<I: ?Sized, M: HasProvider<I> + ?Sized>(module: &M) -> &'static str {
+    module.interface_name()
+}
+
+/// The names are the type names of the interfaces
+#[test]
+fn interface_names() {
+    let module = TestModule::builder().build();
+
+    assert_eq!(
+        component_name::<dyn Logger, _>(&module),
+        std::any::type_name::<dyn Logger>()
+    );
+    assert_eq!(
+        provider_name::<dyn Session, _>(&module),
+        std::any::type_name::<dyn Session>()
+    );
+}
+
+/// The names are available through module trait objects and smart pointers
+#[test]
+fn interface_names_dyn_module() {
+    let module: Arc<dyn AppModule> = TestModule::builder().build_arc();
+
+    assert!(HasComponent::<dyn Logger>::interface_name(&*module).ends_with("Logger"));
+    assert!(provider_name::<dyn Session, _>(&module).ends_with("Session"));
+}
@@ -0,0 +1,92 @@
+//! Two versions of an interface can be backed by one component via an adapter, and the old
+//! version can be deprecated in the module
+
+use shaku::{
+    module, set_deprecation_handler, Component, DeprecatedResolution, HasComponent, Interface,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+trait PaymentsV1: Interface {
+    fn pay(&self, cents: u32) -> String;
+}
+
+trait PaymentsV2: Interface {
+    fn charge(&self, amount: u64, currency: &str) -> String;
+    fn charge_count(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = PaymentsV2, adapter(PaymentsV1 as PaymentsV1Adapter))]
+struct PaymentsImpl {
+    #[shaku(default)]
+    charges: AtomicUsize,
+}
+
+impl PaymentsV2 for PaymentsImpl {
+    fn charge(&self, amount: u64, currency: &str) -> String {
+        self.charges.fetch_add(1, Ordering::SeqCst);
+        format!("{} {}", amount, currency)
+    }
+
+    fn charge_count(&self) -> usize {
+        self.charges.load(Ordering::SeqCst)
+    }
+}
+
+impl PaymentsV1 for PaymentsV1Adapter {
+    fn pay(&self, cents: u32) -> String {
+        self.charge(u64::from(cents), "USD")
+    }
+}
+
+module! {
+    VersionedModule {
+        components = [PaymentsImpl, PaymentsV1Adapter],
+        providers = []
+    }
+}
+
+module! {
+    DeprecatedModule {
+        components = [PaymentsImpl, #[deprecated = "use PaymentsV2"] PaymentsV1Adapter],
+        providers = []
+    }
+}
+
+static RESOLUTIONS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_resolution(resolution: &DeprecatedResolution) {
+    RESOLUTIONS.lock().unwrap().push(resolution.to_string());
+}
+
+/// Both versions of the interface use the same component
+#[test]
+fn adapter_shares_component() {
+    let module = VersionedModule::builder().build();
+    let payments_v1: Arc<dyn PaymentsV1> = module.resolve();
+    let payments_v2: Arc<dyn PaymentsV2> = module.resolve();
+
+    assert_eq!(payments_v1.pay(250), "250 USD");
+    assert_eq!(payments_v2.charge(3, "EUR"), "3 EUR");
+    assert_eq!(payments_v2.charge_count(), 2);
+}
+
+/// Resolving a deprecated component is reported once
+#[test]
+fn deprecated_resolution_reported() {
+    set_deprecation_handler(record_resolution);
+    let module = DeprecatedModule::builder().build();
+
+    let _: &dyn PaymentsV2 = module.resolve_ref();
+    assert!(RESOLUTIONS.lock().unwrap().is_empty());
+
+    let _: &dyn PaymentsV1 = module.resolve_ref();
+    let _: Arc<dyn PaymentsV1> = module.resolve();
+
+    let resolutions = RESOLUTIONS.lock().unwrap();
+    assert_eq!(resolutions.len(), 1);
+    assert!(resolutions[0].contains("PaymentsV1"));
+    assert!(resolutions[0].contains("DeprecatedModule"));
+    assert!(resolutions[0].ends_with("use PaymentsV2"));
+}
@@ -0,0 +1,155 @@
+//! Modules with #[shaku(last_wins)] use the last registration of a duplicate service
+
+use shaku::{
+    module, set_superseded_handler, Component, HasComponent, HasProvider, Interface,
+    ModuleBuildOrder, Provider, SupersededService,
+};
+use std::sync::{Arc, Mutex};
+
+trait Config: Interface {
+    fn name(&self) -> &str;
+}
+
+#[derive(Component)]
+#[shaku(interface = Config)]
+struct DefaultConfig;
+
+impl Config for DefaultConfig {
+    fn name(&self) -> &str {
+        "default"
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Config)]
+struct LocalConfig;
+
+impl Config for LocalConfig {
+    fn name(&self) -> &str {
+        "local"
+    }
+}
+
+trait Connection {
+    fn name(&self) -> &str;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct DefaultConnection;
+
+impl Connection for DefaultConnection {
+    fn name(&self) -> &str {
+        "default"
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct LocalConnection;
+
+impl Connection for LocalConnection {
+    fn name(&self) -> &str {
+        "local"
+    }
+}
+
+module! {
+    DefaultModule {
+        components = [DefaultConfig],
+        providers = [DefaultConnection]
+    }
+}
+
+module! {
+    LocalModule {
+        components = [LocalConfig],
+        providers = [LocalConnection]
+    }
+}
+
+module! {
+    #[shaku(last_wins)]
+    AppModule {
+        components = [],
+        providers = [],
+
+        use DefaultModule {
+            components = [dyn Config],
+            providers = [dyn Connection]
+        },
+
+        use LocalModule {
+            components = [dyn Config],
+            providers = [dyn Connection]
+        }
+    }
+}
+
+module! {
+    #[shaku(last_wins)]
+    ListedTwiceModule {
+        components = [DefaultConfig, #[lazy] DefaultConfig],
+        providers = [DefaultConnection, DefaultConnection]
+    }
+}
+
+module! {
+    #[shaku(last_wins)]
+    ReportedModule {
+        components = [DefaultConfig, DefaultConfig],
+        providers = []
+    }
+}
+
+static SUPERSEDED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_superseded(service: &SupersededService) {
+    SUPERSEDED.lock().unwrap().push(service.to_string());
+}
+
+/// The services of the last submodule are used
+#[test]
+fn last_submodule_wins() {
+    let module = AppModule::builder(
+        Arc::new(DefaultModule::builder().build()),
+        Arc::new(LocalModule::builder().build()),
+    )
+    .build();
+    let config: &dyn Config = module.resolve_ref();
+    let connection: Box<dyn Connection> = module.provide().unwrap();
+
+    assert_eq!(config.name(), "local");
+    assert_eq!(connection.name(), "local");
+}
+
+/// Services listed twice are registered once, with the last registration's attributes
+#[test]
+fn listed_twice() {
+    let module = ListedTwiceModule::builder().build();
+    let config: &dyn Config = module.resolve_ref();
+    let connection: Box<dyn Connection> = module.provide().unwrap();
+
+    assert_eq!(config.name(), "default");
+    assert_eq!(connection.name(), "default");
+    assert_eq!(ListedTwiceModule::build_order(), Vec::<&str>::new());
+}
+
+/// Superseded services are reported to the handler the first time the module is built
+#[test]
+fn superseded_services_reported() {
+    set_superseded_handler(record_superseded);
+    ReportedModule::builder().build();
+    ReportedModule::builder().build();
+
+    // Other tests may build their modules with the handler set
+    let superseded: Vec<String> = SUPERSEDED
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|service| service.contains("ReportedModule"))
+        .cloned()
+        .collect();
+    assert_eq!(superseded.len(), 1);
+    assert!(superseded[0].contains("`DefaultConfig`"));
+}
@@ -0,0 +1,188 @@
+//! Components can be started and shut down via the module's `LifecycleRegistry`
+
+use shaku::extras::{
+    shutdown_on, LifecycleFailure, LifecycleRegistry, LifecycleRegistryParameters, ModuleLifecycle,
+    Stoppable,
+};
+use shaku::{async_interface, module, Component, Error, HasComponent, Interface};
+use std::future::pending;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::runtime::{Builder, Runtime};
+
+trait EventLog: Interface {
+    fn push(&self, event: &str);
+    fn events(&self) -> Vec<String>;
+}
+
+#[derive(Component)]
+#[shaku(interface = EventLog)]
+struct EventLogImpl {
+    #[shaku(default)]
+    events: Mutex<Vec<String>>,
+}
+
+impl EventLog for EventLogImpl {
+    fn push(&self, event: &str) {
+        self.events.lock().unwrap().push(event.to_string());
+    }
+
+    fn events(&self) -> Vec<String> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+trait Database: Interface + Stoppable {}
+
+#[derive(Component)]
+#[shaku(interface = Database, lifecycle)]
+struct DatabaseImpl {
+    #[shaku(inject)]
+    log: Arc<dyn EventLog>,
+    #[shaku(default)]
+    fail_stop: bool,
+}
+
+impl Database for DatabaseImpl {}
+
+async_interface! {
+    impl Stoppable for DatabaseImpl {
+        async fn start(&self) -> Result<(), Box<dyn Error>> {
+            self.log.push("start database");
+            Ok(())
+        }
+
+        async fn stop(&self) -> Result<(), Box<dyn Error>> {
+            if self.fail_stop {
+                return Err("connections are still open".into());
+            }
+
+            self.log.push("stop database");
+            Ok(())
+        }
+    }
+}
+
+trait Server: Interface + Stoppable {}
+
+#[derive(Component)]
+#[shaku(interface = Server, lifecycle)]
+struct ServerImpl {
+    #[shaku(inject)]
+    log: Arc<dyn EventLog>,
+    #[shaku(inject)]
+    _database: Arc<dyn Database>,
+    #[shaku(default)]
+    hang_on_stop: bool,
+}
+
+impl Server for ServerImpl {}
+
+async_interface! {
+    impl Stoppable for ServerImpl {
+        async fn start(&self) -> Result<(), Box<dyn Error>> {
+            self.log.push("start server");
+            Ok(())
+        }
+
+        async fn stop(&self) -> Result<(), Box<dyn Error>> {
+            if self.hang_on_stop {
+                pending::<()>().await;
+            }
+
+            self.log.push("stop server");
+            Ok(())
+        }
+    }
+}
+
+module! {
+    TestModule {
+        components = [LifecycleRegistry, EventLogImpl, ServerImpl, DatabaseImpl],
+        providers = []
+    }
+}
+
+fn runtime() -> Runtime {
+    Builder::new_current_thread().enable_time().build().unwrap()
+}
+
+/// Lifecycle components are registered when the module is built
+#[test]
+fn components_are_registered() {
+    let module = TestModule::builder().build();
+    let registry: Arc<LifecycleRegistry> = module.resolve();
+
+    assert_eq!(registry.component_count(), 2);
+}
+
+/// Dependencies are started first and stopped last
+#[test]
+fn startup_and_shutdown_order() {
+    let module = TestModule::builder().build();
+
+    runtime().block_on(async {
+        module.startup().await.unwrap();
+        module.shutdown().await.unwrap();
+    });
+
+    let log: &dyn EventLog = module.resolve_ref();
+    assert_eq!(
+        log.events(),
+        vec![
+            "start database",
+            "start server",
+            "stop server",
+            "stop database"
+        ]
+    );
+}
+
+/// Every component is stopped, and the failures and timeouts are reported together
+#[test]
+fn shutdown_reports_failures() {
+    let timeout = Duration::from_millis(10);
+    let module = TestModule::builder()
+        .with_component_parameters::<LifecycleRegistry>(LifecycleRegistryParameters { timeout })
+        .with_component_parameters::<ServerImpl>(ServerImplParameters { hang_on_stop: true })
+        .with_component_parameters::<DatabaseImpl>(DatabaseImplParameters { fail_stop: true })
+        .build();
+
+    let error = runtime().block_on(module.shutdown()).unwrap_err();
+
+    assert_eq!(
+        error.failures(),
+        [
+            (
+                "lifecycle::ServerImpl".to_string(),
+                LifecycleFailure::TimedOut(timeout)
+            ),
+            (
+                "lifecycle::DatabaseImpl".to_string(),
+                LifecycleFailure::Failed("connections are still open".to_string())
+            ),
+        ]
+    );
+    assert_eq!(
+        error.to_string(),
+        "2 of the components failed to stop\n\
+         - lifecycle::ServerImpl: timed out after 10ms\n\
+         - lifecycle::DatabaseImpl: failed (connections are still open)"
+    );
+}
+
+/// The module is shut down once the signal finishes
+#[test]
+fn shutdown_on_signal() {
+    let module = TestModule::builder().build();
+
+    runtime()
+        .block_on(async {
+            let signal = tokio::time::sleep(Duration::from_millis(1));
+            shutdown_on(&module, signal).await
+        })
+        .unwrap();
+
+    let log: &dyn EventLog = module.resolve_ref();
+    assert_eq!(log.events(), vec!["stop server", "stop database"]);
+}
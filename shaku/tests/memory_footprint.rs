@@ -0,0 +1,102 @@
+//! Modules estimate the memory held by their components
+
+use shaku::{module, Component, HasComponent, Interface, MemoryFootprint, ModuleMemoryFootprint};
+
+trait Cache: Interface + MemoryFootprint {}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl {
+    name: String,
+    entries: Vec<u32>,
+}
+impl Cache for CacheImpl {}
+impl MemoryFootprint for CacheImpl {
+    fn heap_size(&self) -> usize {
+        self.name.heap_size() + self.entries.heap_size()
+    }
+}
+
+trait Clock: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Clock)]
+struct ClockImpl {
+    #[shaku(default = 0)]
+    _offset: u64,
+}
+impl Clock for ClockImpl {}
+
+trait Logger: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+module! {
+    #[shaku(memory_footprint)]
+    MyModule {
+        components = [CacheImpl, ClockImpl, #[lazy] LoggerImpl],
+        providers = []
+    }
+}
+
+fn build_module() -> MyModule {
+    MyModule::builder()
+        .with_component_parameters::<CacheImpl>(CacheImplParameters {
+            name: String::with_capacity(16),
+            entries: Vec::with_capacity(100),
+        })
+        .build()
+}
+
+/// Each component reports its size, and its heap usage if the interface implements
+/// MemoryFootprint
+#[test]
+fn component_footprints() {
+    let report = build_module().memory_footprint();
+    let components = report.components();
+
+    assert_eq!(components.len(), 3);
+
+    assert_eq!(
+        components[0].interface(),
+        std::any::type_name::<dyn Cache>()
+    );
+    assert_eq!(components[0].size(), Some(std::mem::size_of::<CacheImpl>()));
+    assert_eq!(components[0].heap_size(), Some(16 + 100 * 4));
+
+    assert_eq!(
+        components[1].interface(),
+        std::any::type_name::<dyn Clock>()
+    );
+    assert_eq!(components[1].size(), Some(8));
+    assert_eq!(components[1].heap_size(), None);
+}
+
+/// Lazy components are only measured once they are built
+#[test]
+fn lazy_components() {
+    let module = build_module();
+    let report = module.memory_footprint();
+    assert_eq!(report.components()[2].size(), None);
+
+    let _logger: &dyn Logger = module.resolve_ref();
+    let report = module.memory_footprint();
+    assert_eq!(report.components()[2].size(), Some(0));
+}
+
+/// The total adds up the components
+#[test]
+fn total() {
+    let report = build_module().memory_footprint();
+
+    assert_eq!(
+        report.total(),
+        std::mem::size_of::<CacheImpl>() + 16 + 100 * 4 + 8
+    );
+    assert!(report
+        .to_string()
+        .starts_with(&format!("{} bytes in 3 component(s):", report.total())));
+}
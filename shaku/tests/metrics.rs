@@ -0,0 +1,120 @@
+//! Modules count how often each service is resolved or provided
+
+use shaku::{
+    module, Component, HasComponent, HasProvider, Interface, Provider, ResolveMetrics, ServiceKind,
+};
+use std::sync::Arc;
+
+trait Logger: Interface {}
+trait Cache: Interface {}
+trait Service {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl;
+impl Cache for CacheImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    logger: Arc<dyn Logger>,
+}
+impl Service for ServiceImpl {}
+
+module! {
+    #[derive(Clone)]
+    AppModule {
+        components = [LoggerImpl, CacheImpl],
+        providers = [ServiceImpl]
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [],
+
+        use AppModule {
+            components = [dyn Logger],
+            providers = [dyn Service]
+        }
+    }
+}
+
+/// Every service starts at zero
+#[test]
+fn zero_after_build() {
+    let module = AppModule::builder().build();
+    let metrics = module.metrics();
+
+    assert_eq!(
+        metrics.services(),
+        [
+            ("dyn metrics::Logger", ServiceKind::Component, 0),
+            ("dyn metrics::Cache", ServiceKind::Component, 0),
+            ("dyn metrics::Service", ServiceKind::Provider, 0)
+        ]
+    );
+    assert_eq!(metrics.total(), 0);
+}
+
+/// Resolves and provides are counted per interface
+#[test]
+fn counts_resolves_and_provides() {
+    let module = AppModule::builder().build();
+    let _logger: Arc<dyn Logger> = module.resolve();
+    let _logger: &dyn Logger = module.resolve_ref();
+    let _service: Box<dyn Service> = module.provide().unwrap();
+    let metrics = module.metrics();
+
+    // Providing the service also resolves the logger
+    assert_eq!(metrics.count("dyn metrics::Logger"), 3);
+    assert_eq!(metrics.count("dyn metrics::Service"), 1);
+    assert_eq!(metrics.total(), 4);
+    assert_eq!(metrics.unused(), ["dyn metrics::Cache"]);
+}
+
+/// Clones of a module share their counts
+#[test]
+fn clones_share_counts() {
+    let module = AppModule::builder().build();
+    let clone = module.clone();
+    let _cache: &dyn Cache = clone.resolve_ref();
+
+    assert_eq!(module.metrics().count("dyn metrics::Cache"), 1);
+}
+
+/// Services from submodules are counted by the module which uses them
+#[test]
+fn submodule_services() {
+    let app = Arc::new(AppModule::builder().build());
+    let module = RootModule::builder(app.clone()).build();
+    let _logger: &dyn Logger = module.resolve_ref();
+
+    assert_eq!(module.metrics().count("dyn metrics::Logger"), 1);
+    assert_eq!(app.metrics().count("dyn metrics::Logger"), 1);
+}
+
+/// The metrics list the most used services first
+#[test]
+fn display() {
+    let module = AppModule::builder().build();
+    let _cache: &dyn Cache = module.resolve_ref();
+    let _cache: &dyn Cache = module.resolve_ref();
+    let _logger: &dyn Logger = module.resolve_ref();
+
+    assert_eq!(
+        module.metrics().to_string(),
+        "3 resolve(s):\n\
+         - component dyn metrics::Cache: 2\n\
+         - component dyn metrics::Logger: 1\n\
+         - provider dyn metrics::Service: 0"
+    );
+}
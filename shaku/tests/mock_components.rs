@@ -0,0 +1,91 @@
+//! With the `mock` feature, `#[shaku(mock)]` implements Component for the interface's mock under
+//! `cfg(test)`. The mocks are written by hand here, in the shape of mockall's `#[automock]`.
+
+use shaku::{module, Component, HasComponent, Interface};
+use std::sync::Arc;
+
+mod notifications {
+    use shaku::Interface;
+
+    pub trait Notifier: Interface {
+        fn notify(&self, message: &str) -> bool;
+    }
+
+    /// Stands in for the mock generated by `#[automock]`
+    #[derive(Default)]
+    pub struct MockNotifier {
+        pub accept: bool,
+    }
+
+    impl MockNotifier {
+        pub fn new() -> Self {
+            Self::default()
+        }
+    }
+
+    impl Notifier for MockNotifier {
+        fn notify(&self, _message: &str) -> bool {
+            self.accept
+        }
+    }
+}
+
+use notifications::{MockNotifier, Notifier};
+
+trait Alerts: Interface {
+    fn alert(&self) -> bool;
+}
+
+#[derive(Component)]
+#[shaku(interface = notifications::Notifier, mock)]
+#[allow(dead_code)]
+struct EmailNotifier;
+
+impl Notifier for EmailNotifier {
+    fn notify(&self, _message: &str) -> bool {
+        true
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Alerts)]
+struct AlertsImpl {
+    #[shaku(inject)]
+    notifier: Arc<dyn Notifier>,
+}
+
+impl Alerts for AlertsImpl {
+    fn alert(&self) -> bool {
+        self.notifier.notify("alert")
+    }
+}
+
+module! {
+    TestModule {
+        components = [MockNotifier, AlertsImpl],
+        providers = []
+    }
+}
+
+/// The mock is passed in via the component parameters
+#[test]
+fn mock_from_parameters() {
+    let mut mock = MockNotifier::new();
+    mock.accept = true;
+
+    let module = TestModule::builder()
+        .with_component_parameters::<MockNotifier>(mock)
+        .build();
+    let alerts: &dyn Alerts = module.resolve_ref();
+
+    assert!(alerts.alert());
+}
+
+/// Without parameters, the default mock is used
+#[test]
+fn default_mock() {
+    let module = TestModule::builder().build();
+    let alerts: &dyn Alerts = module.resolve_ref();
+
+    assert!(!alerts.alert());
+}
@@ -0,0 +1,48 @@
+//! Modules can be checked for services at compile time
+
+use shaku::{assert_module_has, assert_module_provides, module, Component, Interface, Provider};
+
+trait Logger: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+
+impl Logger for LoggerImpl {}
+
+trait Connection {}
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct ConnectionImpl;
+
+impl Connection for ConnectionImpl {}
+
+module! {
+    InnerModule {
+        components = [LoggerImpl],
+        providers = [ConnectionImpl]
+    }
+}
+
+module! {
+    OuterModule {
+        components = [],
+        providers = [],
+
+        use InnerModule {
+            components = [dyn Logger],
+            providers = [dyn Connection]
+        }
+    }
+}
+
+const _: fn() = assert_module_has::<InnerModule, dyn Logger>;
+const _: fn() = assert_module_provides::<InnerModule, dyn Connection>;
+
+/// Services of the module and its submodules can be asserted in tests
+#[test]
+fn assert_services() {
+    assert_module_has::<OuterModule, dyn Logger>();
+    assert_module_provides::<OuterModule, dyn Connection>();
+}
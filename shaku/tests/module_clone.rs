@@ -0,0 +1,93 @@
+//! Modules can implement Clone via `#[shaku(derive_clone)]`
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
+use std::sync::Arc;
+
+trait MyComponent: Interface {}
+trait MyProvider {}
+
+#[derive(Component)]
+#[shaku(interface = MyComponent)]
+struct MyComponentImpl;
+impl MyComponent for MyComponentImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = MyProvider)]
+struct MyProviderImpl;
+impl MyProvider for MyProviderImpl {}
+
+trait Holder<T: Interface>: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Holder<T>)]
+struct HolderImpl<T: Interface + Default> {
+    #[shaku(default)]
+    #[allow(dead_code)]
+    value: T,
+}
+impl<T: Interface + Default> Holder<T> for HolderImpl<T> {}
+
+module! {
+    /// A module which can be cloned
+    #[shaku(derive_clone)]
+    SubModule {
+        components = [MyComponentImpl],
+        providers = [MyProviderImpl]
+    }
+}
+
+module! {
+    #[shaku(derive_clone)]
+    RootModule<T: Interface + Default> {
+        components = [HolderImpl<T>],
+        providers = [],
+
+        use SubModule {
+            components = [MyComponent],
+            providers = [MyProvider]
+        }
+    }
+}
+
+/// A cloned module shares the same components
+#[test]
+fn clone_shares_components() {
+    let module = SubModule::builder().build();
+    let cloned = module.clone();
+
+    let component: Arc<dyn MyComponent> = module.resolve();
+    let cloned_component: Arc<dyn MyComponent> = cloned.resolve();
+    assert!(Arc::ptr_eq(&component, &cloned_component));
+
+    let _provided: Box<dyn MyProvider> = cloned.provide().unwrap();
+}
+
+/// Generic modules can be cloned, even if the generic type isn't Clone
+#[test]
+fn clone_generic_module_with_submodule() {
+    #[derive(Default)]
+    struct NotClone;
+
+    let submodule = Arc::new(SubModule::builder().build());
+    let module = RootModule::<NotClone>::builder(Arc::clone(&submodule)).build();
+    let cloned = module.clone();
+
+    let component: Arc<dyn MyComponent> = cloned.resolve();
+    let sub_component: Arc<dyn MyComponent> = submodule.resolve();
+    assert!(Arc::ptr_eq(&component, &sub_component));
+}
+
+/// A cloned module keeps the provider overrides
+#[test]
+fn clone_keeps_provider_override() {
+    let module = SubModule::builder()
+        .with_provider_override::<dyn MyProvider>(Box::new(|_| Err("overridden".into())))
+        .build();
+    let cloned = module.clone();
+
+    assert!(HasProvider::<dyn MyProvider>::provide(&module).is_err());
+    let error = HasProvider::<dyn MyProvider>::provide(&cloned)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "overridden");
+}
@@ -0,0 +1,175 @@
+//! Modules can extend an `#[shaku(extendable)]` module, re-exporting all of its services
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, ModuleServices, Provider};
+use std::sync::Arc;
+
+trait Logger: Interface {
+    fn log(&self, message: &str) -> String;
+}
+
+trait Config: Interface {
+    fn name(&self) -> String;
+}
+
+trait Greeter: Interface {
+    fn greet(&self) -> String;
+}
+
+trait Request {
+    fn user(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Config)]
+struct ConfigImpl {
+    #[shaku(default = "shaku".to_string())]
+    name: String,
+}
+impl Config for ConfigImpl {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl {
+    #[shaku(inject)]
+    config: Arc<dyn Config>,
+}
+impl Logger for LoggerImpl {
+    fn log(&self, message: &str) -> String {
+        format!("[{}] {}", self.config.name(), message)
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Request)]
+struct RequestImpl;
+impl Request for RequestImpl {
+    fn user(&self) -> String {
+        "admin".to_string()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct GreeterImpl {
+    #[shaku(inject)]
+    logger: Arc<dyn Logger>,
+}
+impl Greeter for GreeterImpl {
+    fn greet(&self) -> String {
+        self.logger.log("hello")
+    }
+}
+
+module! {
+    ConfigModule {
+        components = [ConfigImpl],
+        providers = []
+    }
+}
+
+module! {
+    #[shaku(extendable)]
+    BaseModule {
+        components = [LoggerImpl],
+        providers = [RequestImpl],
+
+        use ConfigModule {
+            components = [dyn Config],
+            providers = []
+        }
+    }
+}
+
+module! {
+    #[shaku(extendable)]
+    ExtendedModule extends BaseModule {
+        components = [GreeterImpl],
+        providers = []
+    }
+}
+
+fn base_module() -> Arc<BaseModule> {
+    let config_module = Arc::new(ConfigModule::builder().build());
+    Arc::new(BaseModule::builder(config_module).build())
+}
+
+/// The extending module's services can use the extended module's services
+#[test]
+fn inject_extended_service() {
+    let module = ExtendedModule::builder(base_module()).build();
+    let greeter: &dyn Greeter = module.resolve_ref();
+
+    assert_eq!(greeter.greet(), "[shaku] hello");
+}
+
+/// The extended module's services (including from its submodules) are re-exported
+#[test]
+fn resolve_extended_services() {
+    let base_module = base_module();
+    let module = ExtendedModule::builder(Arc::clone(&base_module)).build();
+    let logger: Arc<dyn Logger> = module.resolve();
+    let config: &dyn Config = module.resolve_ref();
+    let request: Box<dyn Request> = module.provide().unwrap();
+
+    assert!(Arc::ptr_eq(
+        &logger,
+        &HasComponent::<dyn Logger>::resolve(&*base_module)
+    ));
+    assert_eq!(config.name(), "shaku");
+    assert_eq!(request.user(), "admin");
+}
+
+/// The extending module lists its own and the extended module's services
+#[test]
+fn module_services() {
+    let names: Vec<&str> = ExtendedModule::services()
+        .iter()
+        .map(|service| service.interface_name())
+        .collect();
+
+    assert_eq!(names.len(), 4);
+    assert!(names[0].contains("Greeter"));
+}
+
+mod nested {
+    use super::*;
+
+    trait Farewell: Interface {
+        fn farewell(&self) -> String;
+    }
+
+    #[derive(Component)]
+    #[shaku(interface = Farewell)]
+    struct FarewellImpl {
+        #[shaku(inject)]
+        greeter: Arc<dyn Greeter>,
+    }
+    impl Farewell for FarewellImpl {
+        fn farewell(&self) -> String {
+            format!("{} and goodbye", self.greeter.greet())
+        }
+    }
+
+    module! {
+        TwiceExtendedModule extends super::ExtendedModule {
+            components = [FarewellImpl],
+            providers = []
+        }
+    }
+
+    /// Extended modules can also be extended, and can be referenced by path
+    #[test]
+    fn extend_twice() {
+        let extended_module = Arc::new(ExtendedModule::builder(base_module()).build());
+        let module = TwiceExtendedModule::builder(extended_module).build();
+        let farewell: &dyn Farewell = module.resolve_ref();
+        let logger: &dyn Logger = module.resolve_ref();
+
+        assert_eq!(farewell.farewell(), "[shaku] hello and goodbye");
+        assert_eq!(logger.log("test"), "[shaku] test");
+    }
+}
@@ -0,0 +1,131 @@
+//! Modules have a fingerprint which changes with their wiring and configuration
+
+use shaku::{module, Component, Interface, ModuleFingerprint, Provider};
+
+trait Database: Interface {}
+trait Cache: Interface {}
+trait Connection {}
+
+#[derive(Component)]
+#[shaku(interface = Database, parameters_derive(Hash))]
+struct DatabaseImpl {
+    #[shaku(default)]
+    #[allow(dead_code)]
+    url: String,
+}
+impl Database for DatabaseImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl {
+    #[shaku(default)]
+    #[allow(dead_code)]
+    capacity: usize,
+}
+impl Cache for CacheImpl {}
+
+struct MemoryCache;
+impl Cache for MemoryCache {}
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct ConnectionImpl;
+impl Connection for ConnectionImpl {}
+
+module! {
+    #[shaku(derive_clone)]
+    TestModule {
+        components = [DatabaseImpl, CacheImpl],
+        providers = [ConnectionImpl]
+    }
+}
+
+module! {
+    OtherModule {
+        components = [DatabaseImpl, CacheImpl],
+        providers = []
+    }
+}
+
+fn build_default() -> TestModule {
+    TestModule::builder().record_fingerprint().build()
+}
+
+fn build_with_url(url: &str) -> TestModule {
+    TestModule::builder()
+        .record_fingerprint()
+        .with_component_parameters::<DatabaseImpl>(DatabaseImplParameters {
+            url: url.to_string(),
+        })
+        .build()
+}
+
+#[test]
+fn same_configuration_same_fingerprint() {
+    let module = build_with_url("postgres://localhost");
+
+    assert_eq!(
+        module.fingerprint(),
+        build_with_url("postgres://localhost").fingerprint()
+    );
+}
+
+/// Parameters which implement `Hash` are part of the fingerprint
+#[test]
+fn hashable_parameters_change_fingerprint() {
+    let module = build_with_url("postgres://localhost");
+
+    assert_ne!(
+        module.fingerprint(),
+        build_with_url("postgres://remote").fingerprint()
+    );
+    assert_ne!(module.fingerprint(), build_default().fingerprint());
+}
+
+/// The cache parameters don't implement `Hash`, so they are ignored
+#[test]
+fn other_parameters_are_ignored() {
+    let module = TestModule::builder()
+        .record_fingerprint()
+        .with_component_parameters::<CacheImpl>(CacheImplParameters { capacity: 10 })
+        .build();
+
+    assert_eq!(module.fingerprint(), build_default().fingerprint());
+}
+
+#[test]
+fn overrides_change_fingerprint() {
+    let module = TestModule::builder()
+        .record_fingerprint()
+        .with_component_override::<dyn Cache>(Box::new(MemoryCache))
+        .build();
+
+    assert_ne!(module.fingerprint(), build_default().fingerprint());
+}
+
+#[test]
+fn services_change_fingerprint() {
+    assert_ne!(
+        OtherModule::builder()
+            .record_fingerprint()
+            .build()
+            .fingerprint(),
+        build_default().fingerprint()
+    );
+}
+
+#[test]
+fn clones_keep_fingerprint() {
+    let module = build_with_url("postgres://localhost");
+
+    assert_eq!(module.clone().fingerprint(), module.fingerprint());
+}
+
+/// The fingerprint is only recorded if requested
+#[test]
+fn fingerprint_not_recorded_by_default() {
+    assert_eq!(TestModule::builder().build().fingerprint(), None);
+    assert!(build_with_url("postgres://localhost")
+        .fingerprint()
+        .is_some());
+}
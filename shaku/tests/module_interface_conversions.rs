@@ -0,0 +1,116 @@
+//! Modules which implement a module interface can be converted into module trait objects
+
+use shaku::{module, Component, HasComponent, Interface};
+use std::sync::Arc;
+
+trait Greeter: Interface {
+    fn greet(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct EnglishGreeter;
+impl Greeter for EnglishGreeter {
+    fn greet(&self) -> String {
+        "Hello".to_string()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct FrenchGreeter;
+impl Greeter for FrenchGreeter {
+    fn greet(&self) -> String {
+        "Bonjour".to_string()
+    }
+}
+
+trait GreetingModule: HasComponent<dyn Greeter> {}
+
+module! {
+    EnglishModule: GreetingModule {
+        components = [EnglishGreeter],
+        providers = []
+    }
+}
+
+module! {
+    FrenchModule: GreetingModule {
+        components = [FrenchGreeter],
+        providers = []
+    }
+}
+
+trait Value<T: Interface>: Interface {
+    fn value(&self) -> &T;
+}
+
+#[derive(Component)]
+#[shaku(interface = Value<T>)]
+struct ValueImpl<T: Interface + Default> {
+    #[shaku(default)]
+    value: T,
+}
+impl<T: Interface + Default> Value<T> for ValueImpl<T> {
+    fn value(&self) -> &T {
+        &self.value
+    }
+}
+
+trait ValueModule<T: Interface>: HasComponent<dyn Value<T>> {}
+
+module! {
+    ValueModuleImpl<T: Interface + Default>: ValueModule<T> {
+        components = [ValueImpl<T>],
+        providers = []
+    }
+}
+
+fn create_module(french: bool) -> Arc<dyn GreetingModule> {
+    if french {
+        FrenchModule::builder().build().into()
+    } else {
+        EnglishModule::builder().build().into()
+    }
+}
+
+fn greeting(module: &dyn GreetingModule) -> String {
+    let greeter: &dyn Greeter = module.resolve_ref();
+    greeter.greet()
+}
+
+/// Modules can be converted into an Arc via Into
+#[test]
+fn into_arc() {
+    assert_eq!(greeting(&*create_module(false)), "Hello");
+    assert_eq!(greeting(&*create_module(true)), "Bonjour");
+}
+
+/// Modules can be converted into a Box via Into
+#[test]
+fn into_box() {
+    let module: Box<dyn GreetingModule> = FrenchModule::builder().build().into();
+
+    assert_eq!(greeting(&*module), "Bonjour");
+}
+
+/// Modules can be borrowed or converted via the generated methods
+#[test]
+fn conversion_methods() {
+    let english = EnglishModule::builder().build();
+    let french = FrenchModule::builder().build().into_module_interface();
+
+    assert_eq!(greeting(english.as_module_interface()), "Hello");
+    assert_eq!(greeting(&*french), "Bonjour");
+}
+
+/// Generic modules can be converted via the generated methods
+#[test]
+fn generic_conversion_methods() {
+    let module: Arc<dyn ValueModule<usize>> = ValueModuleImpl::<usize>::builder()
+        .build()
+        .into_module_interface();
+    let value: &dyn Value<usize> = module.resolve_ref();
+
+    assert_eq!(*value.value(), 0);
+}
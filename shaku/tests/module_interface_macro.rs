@@ -0,0 +1,163 @@
+//! The module_interface macro declares module interface traits
+
+use shaku::{module, module_interface, Component, HasProvider, Interface, Provider};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+trait AuthManager: Interface {
+    fn user(&self) -> String;
+}
+
+trait TokenStore: Interface {
+    fn token(&self) -> String;
+}
+
+trait SessionFactory {
+    fn session(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = AuthManager)]
+struct AuthManagerImpl;
+impl AuthManager for AuthManagerImpl {
+    fn user(&self) -> String {
+        "admin".to_string()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = TokenStore)]
+struct TokenStoreImpl;
+impl TokenStore for TokenStoreImpl {
+    fn token(&self) -> String {
+        "token".to_string()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = SessionFactory)]
+struct SessionFactoryImpl {
+    #[shaku(inject)]
+    auth_manager: Arc<dyn AuthManager>,
+    #[shaku(inject)]
+    token_store: Arc<dyn TokenStore>,
+}
+impl SessionFactory for SessionFactoryImpl {
+    fn session(&self) -> String {
+        format!("{}:{}", self.auth_manager.user(), self.token_store.token())
+    }
+}
+
+module_interface! {
+    /// The auth services
+    pub trait AuthModule {
+        components = [dyn AuthManager, dyn TokenStore],
+        providers = [dyn SessionFactory]
+    }
+}
+
+module! {
+    AuthModuleImpl: AuthModule {
+        components = [AuthManagerImpl, TokenStoreImpl],
+        providers = [SessionFactoryImpl]
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [],
+
+        use dyn AuthModule {
+            components = [dyn AuthManager],
+            providers = [dyn SessionFactory]
+        }
+    }
+}
+
+trait Value<T: Interface>: Interface {
+    fn value(&self) -> T;
+}
+
+#[derive(Component)]
+#[shaku(interface = Value<T>)]
+struct ValueImpl<T: Interface + Clone + Default> {
+    value: T,
+}
+impl<T: Interface + Clone + Default> Value<T> for ValueImpl<T> {
+    fn value(&self) -> T {
+        self.value.clone()
+    }
+}
+
+module_interface! {
+    trait ValueModule<T> where T: Interface + Debug {
+        components = [dyn Value<T>],
+        providers = []
+    }
+}
+
+module! {
+    ValueModuleImpl<T: Interface + Clone + Default + Debug>: ValueModule<T> {
+        components = [ValueImpl<T>],
+        providers = []
+    }
+}
+
+module_interface! {
+    trait EmptyModule {
+        components = [],
+        providers = []
+    }
+}
+
+module! {
+    EmptyModuleImpl: EmptyModule {
+        components = [],
+        providers = []
+    }
+}
+
+/// The module interface can be used as a trait object
+#[test]
+fn module_interface_trait_object() {
+    let module: Arc<dyn AuthModule> = Arc::new(AuthModuleImpl::builder().build());
+
+    let auth_manager: &dyn AuthManager = module.resolve_ref();
+    let token_store: &dyn TokenStore = module.resolve_ref();
+    let session_factory: Box<dyn SessionFactory> = module.provide().unwrap();
+
+    assert_eq!(auth_manager.user(), "admin");
+    assert_eq!(token_store.token(), "token");
+    assert_eq!(session_factory.session(), "admin:token");
+}
+
+/// The module interface can be used as a submodule
+#[test]
+fn module_interface_submodule() {
+    let module = RootModule::builder(Arc::new(AuthModuleImpl::builder().build())).build();
+
+    let session_factory: Box<dyn SessionFactory> = module.provide().unwrap();
+
+    assert_eq!(session_factory.session(), "admin:token");
+}
+
+/// Module interfaces can be generic
+#[test]
+fn generic_module_interface() {
+    let module: Arc<dyn ValueModule<usize>> = Arc::new(
+        ValueModuleImpl::<usize>::builder()
+            .with_component_parameters::<ValueImpl<usize>>(ValueImplParameters { value: 5 })
+            .build(),
+    );
+
+    let value: &dyn Value<usize> = module.resolve_ref();
+
+    assert_eq!(value.value(), 5);
+}
+
+/// Module interfaces can have no services
+#[test]
+fn empty_module_interface() {
+    let _module: Arc<dyn EmptyModule> = Arc::new(EmptyModuleImpl::builder().build());
+}
@@ -0,0 +1,112 @@
+//! Providers can be used through module interface trait objects
+
+use shaku::{
+    module, Component, HasBatchProvider, HasComponent, HasProvider, Interface, ProvideContext,
+    Provider,
+};
+use std::sync::Arc;
+
+trait Config: Interface {
+    fn prefix(&self) -> &str;
+}
+
+#[derive(Component)]
+#[shaku(interface = Config)]
+struct ConfigImpl;
+impl Config for ConfigImpl {
+    fn prefix(&self) -> &str {
+        "config"
+    }
+}
+
+trait Repository {
+    fn name(&self) -> String;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Repository)]
+struct RepositoryImpl {
+    #[shaku(inject)]
+    config: Arc<dyn Config>,
+}
+impl Repository for RepositoryImpl {
+    fn name(&self) -> String {
+        format!("{} repository", self.config.prefix())
+    }
+}
+
+trait Service {
+    fn name(&self) -> String;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(provide)]
+    repository: Box<dyn Repository>,
+}
+impl Service for ServiceImpl {
+    fn name(&self) -> String {
+        format!("service using {}", self.repository.name())
+    }
+}
+
+trait MyModule:
+    HasComponent<dyn Config> + HasProvider<dyn Repository> + HasProvider<dyn Service>
+{
+}
+
+module! {
+    MyModuleImpl: MyModule {
+        components = [ConfigImpl],
+        providers = [RepositoryImpl, ServiceImpl]
+    }
+}
+
+module! {
+    ParentModule {
+        components = [],
+        providers = [],
+
+        use MyModule {
+            components = [Config],
+            providers = [Service]
+        }
+    }
+}
+
+fn create_module() -> Arc<dyn MyModule> {
+    Arc::new(MyModuleImpl::builder().build())
+}
+
+/// Providers with provided and injected dependencies work through a module trait object
+#[test]
+fn provide_through_trait_object() {
+    let module = create_module();
+
+    let service: Box<dyn Service> = module.provide().unwrap();
+
+    assert_eq!(service.name(), "service using config repository");
+}
+
+/// The other provide methods work through a module trait object
+#[test]
+fn provide_variants_through_trait_object() {
+    let module = create_module();
+
+    let services: Vec<Box<dyn Service>> = module.provide_batch(2).unwrap();
+    let service: Box<dyn Service> = module.provide_with_context(&ProvideContext::new()).unwrap();
+
+    assert_eq!(services.len(), 2);
+    assert_eq!(service.name(), "service using config repository");
+}
+
+/// Module trait objects can be used as submodules which provide services
+#[test]
+fn provide_through_submodule_trait_object() {
+    let module = ParentModule::builder(create_module()).build();
+
+    let service: Box<dyn Service> = module.provide().unwrap();
+
+    assert_eq!(service.name(), "service using config repository");
+}
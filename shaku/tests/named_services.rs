@@ -0,0 +1,75 @@
+//! Services can be given a name via `#[name = "..."]`, which is used for the module field
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, ModuleServices, Provider};
+
+trait Database: Interface {
+    fn url(&self) -> &str;
+}
+trait Cache: Interface {}
+trait Connection {}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct DatabaseImpl {
+    #[shaku(default = "postgres://localhost".to_string())]
+    url: String,
+}
+
+impl Database for DatabaseImpl {
+    fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl;
+impl Cache for CacheImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct ConnectionImpl;
+impl Connection for ConnectionImpl {}
+
+module! {
+    #[shaku(derive_clone)]
+    NamedModule {
+        components = [#[name = "primary_database"] DatabaseImpl, CacheImpl],
+        providers = [#[name = "connections"] ConnectionImpl]
+    }
+}
+
+module! {
+    LazyNamedModule {
+        components = [#[lazy] #[name = "database"] DatabaseImpl],
+        providers = []
+    }
+}
+
+/// Named services are resolved like any other service
+#[test]
+fn resolve_named_services() {
+    let module = NamedModule::builder().build().clone();
+
+    let database: &dyn Database = module.resolve_ref();
+    let _cache: &dyn Cache = module.resolve_ref();
+    let connection: Result<Box<dyn Connection>, _> = module.provide();
+
+    assert_eq!(database.url(), "postgres://localhost");
+    assert!(connection.is_ok());
+
+    let module = LazyNamedModule::builder().build();
+    let database: &dyn Database = module.resolve_ref();
+    assert_eq!(database.url(), "postgres://localhost");
+}
+
+/// The names are available in the module's service descriptors
+#[test]
+fn service_descriptor_names() {
+    let names: Vec<_> = NamedModule::services()
+        .iter()
+        .map(|service| service.name())
+        .collect();
+
+    assert_eq!(names, [Some("primary_database"), None, Some("connections")]);
+}
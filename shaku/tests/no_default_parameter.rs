@@ -15,6 +15,17 @@ struct MyComponentImpl {
 }
 impl MyComponent for MyComponentImpl {}
 
+trait OtherComponent: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = OtherComponent)]
+struct OtherComponentImpl {
+    #[shaku(default)]
+    #[allow(dead_code)]
+    value: usize,
+}
+impl OtherComponent for OtherComponentImpl {}
+
 module! {
     TestModule {
         components = [MyComponentImpl],
@@ -22,6 +33,14 @@ module! {
     }
 }
 
+module! {
+    #[shaku(strict_builder)]
+    StrictModule {
+        components = [MyComponentImpl, OtherComponentImpl],
+        providers = []
+    }
+}
+
 /// Providing the parameter will allow module creation to succeed
 #[test]
 fn with_given_parameter() {
@@ -38,3 +57,14 @@ fn with_given_parameter() {
 fn without_given_parameter() {
     TestModule::builder().build();
 }
+
+/// The strict builder can build the module once the parameter without a default is given.
+/// Components whose parameters have defaults do not need to be given.
+#[test]
+fn strict_builder_with_given_parameter() {
+    StrictModule::strict_builder()
+        .with_component_parameters::<MyComponentImpl>(MyComponentImplParameters {
+            no_default: NoDefault,
+        })
+        .build();
+}
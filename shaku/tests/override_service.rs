@@ -1,6 +1,7 @@
 //! Tests related to overriding components/providers
 
-use shaku::{module, Component, HasProvider, Interface, Provider};
+use shaku::{module, AnyOverride, Component, HasProvider, Interface, Provider, ProviderFn};
+use std::any::TypeId;
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -75,3 +76,55 @@ fn override_provider() {
         "MySecondProviderImpl { my_provider: FakeProvider }"
     )
 }
+
+/// Components and providers can be overridden by their interface's type id
+#[test]
+fn dynamic_overrides() {
+    #[derive(Component, Debug)]
+    #[shaku(interface = MyComponent)]
+    struct FakeComponent;
+    impl MyComponent for FakeComponent {}
+
+    #[derive(Provider, Debug)]
+    #[shaku(interface = MySecondProvider)]
+    struct FakeSecondProvider;
+    impl MySecondProvider for FakeSecondProvider {}
+
+    let component: Arc<dyn MyComponent> = Arc::new(FakeComponent);
+    let provider_fn: ProviderFn<TestModule, dyn MySecondProvider> =
+        Box::new(FakeSecondProvider::provide);
+    let overrides: Vec<(TypeId, AnyOverride)> = vec![
+        (TypeId::of::<dyn MyComponent>(), Box::new(component)),
+        (TypeId::of::<dyn MySecondProvider>(), Box::new(provider_fn)),
+    ];
+
+    let module = TestModule::builder()
+        .with_dynamic_overrides(overrides)
+        .build();
+    let my_provider: Box<dyn MyProvider> = module.provide().unwrap();
+    let my_second_provider: Box<dyn MySecondProvider> = module.provide().unwrap();
+
+    assert_eq!(
+        format!("{:?}", my_provider),
+        "MyProviderImpl { my_component: FakeComponent }"
+    );
+    assert_eq!(format!("{:?}", my_second_provider), "FakeSecondProvider");
+}
+
+/// Dynamic overrides must be for a service of the module
+#[test]
+#[should_panic(expected = "has no service with this interface")]
+fn dynamic_override_unknown_interface() {
+    trait Unknown: Interface {}
+
+    let value: AnyOverride = Box::new(());
+    TestModule::builder().with_dynamic_overrides(vec![(TypeId::of::<dyn Unknown>(), value)]);
+}
+
+/// Dynamic overrides must have the service's type
+#[test]
+#[should_panic(expected = "Unable to override component dyn override_service::MyComponent")]
+fn dynamic_override_wrong_type() {
+    let value: AnyOverride = Box::new(Box::new(MyComponentImpl) as Box<dyn MyComponent>);
+    TestModule::builder().with_dynamic_overrides(vec![(TypeId::of::<dyn MyComponent>(), value)]);
+}
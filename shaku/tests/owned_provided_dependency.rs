@@ -0,0 +1,142 @@
+//! Components can own a service from a provider via `#[shaku(provide)]`. The provider is called
+//! once, when the (lazy) component is built.
+
+use shaku::{module, Component, HasComponent, Interface, Module, Provider};
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+trait Config: Interface {
+    fn separator(&self) -> char;
+}
+
+#[derive(Component)]
+#[shaku(interface = Config)]
+struct ConfigImpl {
+    #[shaku(default = ',')]
+    separator: char,
+}
+
+impl Config for ConfigImpl {
+    fn separator(&self) -> char {
+        self.separator
+    }
+}
+
+trait Counter: Interface {
+    fn increment(&self);
+    fn count(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = Counter)]
+struct CounterImpl {
+    #[shaku(default = AtomicUsize::new(0))]
+    count: AtomicUsize,
+}
+
+impl Counter for CounterImpl {
+    fn increment(&self) {
+        self.count.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+}
+
+trait Parser: Interface {
+    fn parse(&self, input: &str) -> Vec<String>;
+}
+
+struct ParserImpl {
+    separator: char,
+}
+
+impl Parser for ParserImpl {
+    fn parse(&self, input: &str) -> Vec<String> {
+        input.split(self.separator).map(str::to_string).collect()
+    }
+}
+
+impl<M: Module + HasComponent<dyn Config> + HasComponent<dyn Counter>> Provider<M> for ParserImpl {
+    type Interface = dyn Parser;
+
+    fn provide(module: &M) -> Result<Box<dyn Parser>, Box<dyn Error>> {
+        let counter: &dyn Counter = module.resolve_ref();
+        counter.increment();
+
+        let config: &dyn Config = module.resolve_ref();
+        Ok(Box::new(ParserImpl {
+            separator: config.separator(),
+        }))
+    }
+}
+
+trait Importer: Interface {
+    fn import(&self, input: &str) -> Vec<String>;
+}
+
+#[derive(Component)]
+#[shaku(interface = Importer)]
+struct ImporterImpl {
+    #[shaku(provide)]
+    parser: Box<dyn Parser>,
+}
+
+impl Importer for ImporterImpl {
+    fn import(&self, input: &str) -> Vec<String> {
+        self.parser.parse(input)
+    }
+}
+
+module! {
+    LazyModule {
+        components = [ConfigImpl, CounterImpl, #[lazy] ImporterImpl],
+        providers = [ParserImpl]
+    }
+}
+
+module! {
+    EagerModule {
+        components = [ConfigImpl, CounterImpl, ImporterImpl],
+        providers = [ParserImpl]
+    }
+}
+
+/// The provided service is owned by the component, and the provider is only called once
+#[test]
+fn provided_once() {
+    let module = LazyModule::builder().build_arc();
+    let importer: &dyn Importer = module.resolve_ref();
+    let counter: &dyn Counter = module.resolve_ref();
+
+    assert_eq!(importer.import("a,b"), vec!["a", "b"]);
+    assert_eq!(importer.import("c"), vec!["c"]);
+    assert_eq!(counter.count(), 1);
+}
+
+/// The provider uses the module's components, including overridden parameters
+#[test]
+fn provider_uses_module() {
+    let module = LazyModule::builder()
+        .with_component_parameters::<ConfigImpl>(ConfigImplParameters { separator: ';' })
+        .build_arc();
+    let importer: &dyn Importer = module.resolve_ref();
+
+    assert_eq!(importer.import("a;b"), vec!["a", "b"]);
+}
+
+/// Providers need the built module, so eager components can't use them
+#[test]
+#[should_panic(expected = "before the module is built")]
+fn eager_component_panics() {
+    EagerModule::builder().build_arc();
+}
+
+/// Lazy components of a module built via `build` can't use them either
+#[test]
+#[should_panic(expected = "before the module is built")]
+fn without_build_arc_panics() {
+    let module = LazyModule::builder().build();
+    let _: &dyn Importer = module.resolve_ref();
+}
@@ -0,0 +1,249 @@
+//! Parameters marked with `#[shaku(from_source = "key")]` are fetched from parameter sources by
+//! `ModuleBuilder::build_async`
+
+use shaku::{
+    module, BoxFuture, Component, FetchError, HasComponent, Interface, ModuleBuildError,
+    ParameterSource,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+trait Database: Interface {
+    fn describe(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct DatabaseImpl {
+    url: String,
+    #[shaku(from_source = "db_password")]
+    password: String,
+    #[shaku(from_source = "db_pool_size")]
+    pool_size: u32,
+}
+
+impl Database for DatabaseImpl {
+    fn describe(&self) -> String {
+        format!("{} {} {}", self.url, self.password, self.pool_size)
+    }
+}
+
+module! {
+    MyModule {
+        components = [DatabaseImpl],
+        providers = []
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+fn values(values: &[(&str, &str)]) -> HashMap<String, String> {
+    values
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+fn builder() -> shaku::ModuleBuilder<MyModule> {
+    MyModule::builder().with_component_parameters::<DatabaseImpl>(DatabaseImplParameters {
+        url: "postgres://localhost".to_string(),
+    })
+}
+
+fn describe(module: &MyModule) -> String {
+    let database: &dyn Database = module.resolve_ref();
+    database.describe()
+}
+
+/// A fetch which is pending once before it completes, like a network request
+struct Delayed {
+    polled: bool,
+    result: Option<Result<Option<String>, FetchError>>,
+}
+
+impl Future for Delayed {
+    type Output = Result<Option<String>, FetchError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if !self.polled {
+            self.polled = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        Poll::Ready(self.result.take().unwrap())
+    }
+}
+
+/// Counts the fetches, and fails for keys starting with "fail"
+struct CountingSource {
+    fetches: Arc<AtomicUsize>,
+    values: HashMap<String, String>,
+}
+
+impl ParameterSource for CountingSource {
+    fn fetch(&self, key: &str) -> BoxFuture<'static, Result<Option<String>, FetchError>> {
+        self.fetches.fetch_add(1, Ordering::SeqCst);
+        let result = if key.starts_with("fail") {
+            Err("access denied".into())
+        } else {
+            Ok(self.values.get(key).cloned())
+        };
+
+        Box::pin(Delayed {
+            polled: false,
+            result: Some(result),
+        })
+    }
+}
+
+/// The values are fetched and parsed into the fields
+#[test]
+fn fetches_values() {
+    let module = block_on(
+        builder()
+            .with_parameter_source(values(&[("db_password", "hunter2"), ("db_pool_size", "8")]))
+            .build_async(),
+    )
+    .unwrap();
+
+    assert_eq!(describe(&module), "postgres://localhost hunter2 8");
+}
+
+/// Sources are asked in order until one has the value
+#[test]
+fn sources_fall_through() {
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let module = block_on(
+        builder()
+            .with_parameter_source(CountingSource {
+                fetches: Arc::clone(&fetches),
+                values: values(&[("db_password", "from vault")]),
+            })
+            .with_parameter_source(values(&[("db_password", "ignored"), ("db_pool_size", "2")]))
+            .build_async(),
+    )
+    .unwrap();
+
+    assert_eq!(describe(&module), "postgres://localhost from vault 2");
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+}
+
+/// Values set on the builder are not fetched, and allow a synchronous build
+#[test]
+fn set_values() {
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let module = block_on(
+        builder()
+            .with_parameter_source(CountingSource {
+                fetches: Arc::clone(&fetches),
+                values: values(&[("db_password", "hunter2")]),
+            })
+            .with_parameter_value("db_pool_size", "4")
+            .build_async(),
+    )
+    .unwrap();
+
+    assert_eq!(describe(&module), "postgres://localhost hunter2 4");
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+    let module = builder()
+        .with_parameter_value("db_password", "test")
+        .with_parameter_value("db_pool_size", "1")
+        .build();
+    assert_eq!(describe(&module), "postgres://localhost test 1");
+}
+
+/// Missing values fail the build
+#[test]
+fn missing_value() {
+    let result = block_on(
+        builder()
+            .with_parameter_source(values(&[("db_password", "hunter2")]))
+            .build_async(),
+    );
+
+    match result {
+        Err(ModuleBuildError::MissingParameter(key)) => assert_eq!(key, "db_pool_size"),
+        _ => panic!("Expected a missing value"),
+    }
+}
+
+trait Token: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Token)]
+struct TokenImpl {
+    #[shaku(from_source = "fail_token")]
+    _token: String,
+}
+impl Token for TokenImpl {}
+
+module! {
+    TokenModule {
+        components = [TokenImpl],
+        providers = []
+    }
+}
+
+/// Source failures fail the build
+#[test]
+fn fetch_error() {
+    let result = block_on(
+        TokenModule::builder()
+            .with_parameter_source(CountingSource {
+                fetches: Arc::new(AtomicUsize::new(0)),
+                values: HashMap::new(),
+            })
+            .build_async(),
+    );
+
+    match result {
+        Err(error @ ModuleBuildError::FetchParameter { .. }) => assert_eq!(
+            error.to_string(),
+            "Failed to fetch the value of `fail_token`: access denied"
+        ),
+        _ => panic!("Expected a fetch error"),
+    }
+}
+
+/// Building synchronously without the values panics
+#[test]
+#[should_panic(expected = "The parameter `db_password` of")]
+fn build_without_values() {
+    builder().build();
+}
+
+/// Values which don't parse into the field's type panic
+#[test]
+#[should_panic(expected = "Failed to parse the parameter `db_pool_size`")]
+fn invalid_value() {
+    builder()
+        .with_parameter_value("db_password", "hunter2")
+        .with_parameter_value("db_pool_size", "many")
+        .build();
+}
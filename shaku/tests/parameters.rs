@@ -2,7 +2,7 @@
 
 #![allow(clippy::disallowed_names)]
 
-use shaku::{module, Component, HasComponent, Interface};
+use shaku::{module, Component, HasComponent, Interface, ModuleBuildError};
 use std::sync::Arc;
 
 trait Foo: Interface {
@@ -72,3 +72,72 @@ fn parameters_are_injected() {
     let foo: Arc<dyn Foo> = module.resolve();
     assert_eq!(foo.foo(), "Foo = 'foo value', Bar = 'bar value'");
 }
+
+/// Setting parameters again replaces the previous parameters by default
+#[test]
+fn parameters_are_overwritten() {
+    let module = TestModule::builder()
+        .with_component_parameters::<BarImpl>(BarImplParameters {
+            bar_value: "first".to_string(),
+        })
+        .with_component_parameters::<BarImpl>(BarImplParameters {
+            bar_value: "second".to_string(),
+        })
+        .build();
+
+    let bar: &dyn Bar = module.resolve_ref();
+    assert_eq!(bar.bar(), "second");
+}
+
+/// Overwriting parameters fails the build if it was denied
+#[test]
+fn deny_parameter_overwrites() {
+    let result = TestModule::builder()
+        .deny_parameter_overwrites()
+        .with_component_parameters::<BarImpl>(BarImplParameters {
+            bar_value: "first".to_string(),
+        })
+        .with_component_parameters::<BarImpl>(BarImplParameters {
+            bar_value: "second".to_string(),
+        })
+        .try_build();
+
+    match result {
+        Err(ModuleBuildError::ParametersOverwritten(overwritten)) => {
+            assert_eq!(overwritten, ["parameters::BarImpl"])
+        }
+        _ => panic!("Expected the overwrite to be denied"),
+    }
+}
+
+/// Building a module whose parameters were overwritten panics if it was denied
+#[test]
+#[should_panic(expected = "The parameters of `parameters::BarImpl` were set more than once")]
+fn deny_parameter_overwrites_build() {
+    TestModule::builder()
+        .with_component_parameters::<BarImpl>(BarImplParameters {
+            bar_value: "first".to_string(),
+        })
+        .with_component_parameters::<BarImpl>(BarImplParameters {
+            bar_value: "first".to_string(),
+        })
+        .deny_parameter_overwrites()
+        .build();
+}
+
+/// Parameters of different components don't conflict when overwrites are denied
+#[test]
+fn deny_parameter_overwrites_distinct_components() {
+    let module = TestModule::builder()
+        .deny_parameter_overwrites()
+        .with_component_parameters::<FooImpl>(FooImplParameters {
+            value: "foo value".to_string(),
+        })
+        .with_component_parameters::<BarImpl>(BarImplParameters {
+            bar_value: "bar value".to_string(),
+        })
+        .build();
+
+    let foo: &dyn Foo = module.resolve_ref();
+    assert_eq!(foo.foo(), "Foo = 'foo value', Bar = 'bar value'");
+}
@@ -0,0 +1,188 @@
+//! Component parameters can be snapshotted and restored via serde
+
+use serde::{Deserialize, Serialize};
+use shaku::{
+    module, Component, HasComponent, Interface, ModuleBuildError, ParametersSnapshot, SnapshotError,
+};
+
+trait Database: Interface {
+    fn url(&self) -> &str;
+    fn pool_size(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = Database, parameters_derive(Serialize, Deserialize))]
+struct DatabaseImpl {
+    url: String,
+    #[shaku(default = 4)]
+    pool_size: usize,
+}
+
+impl Database for DatabaseImpl {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn pool_size(&self) -> usize {
+        self.pool_size
+    }
+}
+
+trait Greeter: Interface {
+    fn greeting(&self) -> &str;
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter, parameters_derive(Serialize, Deserialize))]
+struct GreeterImpl {
+    #[shaku(default)]
+    greeting: String,
+}
+
+impl Greeter for GreeterImpl {
+    fn greeting(&self) -> &str {
+        &self.greeting
+    }
+}
+
+module! {
+    TestModule {
+        components = [DatabaseImpl, GreeterImpl],
+        providers = []
+    }
+}
+
+fn database_parameters() -> DatabaseImplParameters {
+    DatabaseImplParameters {
+        url: "postgres://localhost".to_string(),
+        pool_size: 16,
+    }
+}
+
+/// A snapshot can be restored into a new builder, which builds the same components
+#[test]
+fn snapshot_round_trip() {
+    let snapshot = TestModule::builder()
+        .with_snapshot_parameters::<DatabaseImpl>()
+        .with_snapshot_parameters::<GreeterImpl>()
+        .with_component_parameters::<DatabaseImpl>(database_parameters())
+        .with_component_parameters::<GreeterImpl>(GreeterImplParameters {
+            greeting: "Hello".to_string(),
+        })
+        .parameters_snapshot()
+        .unwrap();
+    assert_eq!(snapshot.len(), 2);
+
+    let module = TestModule::builder()
+        .with_snapshot_parameters::<DatabaseImpl>()
+        .with_snapshot_parameters::<GreeterImpl>()
+        .restore_parameters(snapshot)
+        .unwrap()
+        .build();
+    let database: &dyn Database = module.resolve_ref();
+    let greeter: &dyn Greeter = module.resolve_ref();
+
+    assert_eq!(database.url(), "postgres://localhost");
+    assert_eq!(database.pool_size(), 16);
+    assert_eq!(greeter.greeting(), "Hello");
+}
+
+/// Restoring a snapshot over parameters which were already set counts as an overwrite
+#[test]
+fn restore_denied_overwrite() {
+    let snapshot = TestModule::builder()
+        .with_snapshot_parameters::<DatabaseImpl>()
+        .with_component_parameters::<DatabaseImpl>(database_parameters())
+        .parameters_snapshot()
+        .unwrap();
+
+    let result = TestModule::builder()
+        .deny_parameter_overwrites()
+        .with_snapshot_parameters::<DatabaseImpl>()
+        .with_component_parameters::<DatabaseImpl>(database_parameters())
+        .restore_parameters(snapshot)
+        .unwrap()
+        .try_build();
+
+    match result {
+        Err(ModuleBuildError::ParametersOverwritten(overwritten)) => {
+            assert_eq!(overwritten, [std::any::type_name::<DatabaseImpl>()])
+        }
+        _ => panic!("Expected the overwrite to be denied"),
+    }
+}
+
+/// Snapshots can be serialized, ex. to dump the effective configuration to a file
+#[test]
+fn snapshot_serializes() {
+    let snapshot = TestModule::builder()
+        .with_snapshot_parameters::<DatabaseImpl>()
+        .with_component_parameters::<DatabaseImpl>(database_parameters())
+        .parameters_snapshot()
+        .unwrap();
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let deserialized: ParametersSnapshot = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(deserialized, snapshot);
+    assert_eq!(
+        snapshot.get(std::any::type_name::<DatabaseImpl>()),
+        Some(&serde_json::json!({ "url": "postgres://localhost", "pool_size": 16 }))
+    );
+}
+
+/// Only components registered for snapshots with parameters set are included
+#[test]
+fn snapshot_skips_unregistered_and_unset() {
+    let snapshot = TestModule::builder()
+        .with_snapshot_parameters::<DatabaseImpl>()
+        .with_component_parameters::<GreeterImpl>(GreeterImplParameters {
+            greeting: "Hello".to_string(),
+        })
+        .parameters_snapshot()
+        .unwrap();
+
+    assert!(snapshot.is_empty());
+}
+
+/// Restoring a snapshot with an unregistered component fails
+#[test]
+fn restore_unknown_component() {
+    let snapshot = TestModule::builder()
+        .with_snapshot_parameters::<GreeterImpl>()
+        .with_component_parameters::<GreeterImpl>(GreeterImplParameters {
+            greeting: "Hello".to_string(),
+        })
+        .parameters_snapshot()
+        .unwrap();
+
+    let result = TestModule::builder()
+        .with_snapshot_parameters::<DatabaseImpl>()
+        .restore_parameters(snapshot);
+
+    match result {
+        Err(SnapshotError::UnknownComponent(name)) => {
+            assert_eq!(name, std::any::type_name::<GreeterImpl>())
+        }
+        _ => panic!("Expected an unknown component error"),
+    }
+}
+
+/// Restoring a snapshot with parameters of the wrong shape fails
+#[test]
+fn restore_invalid_parameters() {
+    let json = format!(
+        r#"{{ "{}": {{ "greeting": 5 }} }}"#,
+        std::any::type_name::<GreeterImpl>()
+    );
+    let snapshot: ParametersSnapshot = serde_json::from_str(&json).unwrap();
+
+    let result = TestModule::builder()
+        .with_snapshot_parameters::<GreeterImpl>()
+        .restore_parameters(snapshot);
+
+    match result {
+        Err(SnapshotError::Deserialize { .. }) => {}
+        _ => panic!("Expected a deserialize error"),
+    }
+}
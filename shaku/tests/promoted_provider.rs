@@ -0,0 +1,112 @@
+//! Providers marked with `#[promote_to_component]` are built once and resolved as components
+
+use shaku::{module, HasComponent, HasProvider, Interface, ModuleServices, Provider, ServiceKind};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+static BUILDS: AtomicUsize = AtomicUsize::new(0);
+
+trait Formatter: Interface {
+    fn format(&self, value: usize) -> String;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Formatter)]
+struct FormatterImpl;
+
+impl Formatter for FormatterImpl {
+    fn format(&self, value: usize) -> String {
+        format!("#{}", value)
+    }
+}
+
+trait Report {
+    fn render(&self) -> String;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Report)]
+struct ReportImpl {
+    #[shaku(inject)]
+    formatter: Arc<dyn Formatter>,
+}
+
+impl Report for ReportImpl {
+    fn render(&self) -> String {
+        self.formatter.format(1)
+    }
+}
+
+trait Counted: Interface {}
+
+struct CountedImpl;
+impl Counted for CountedImpl {}
+
+impl<M: shaku::Module> Provider<M> for CountedImpl {
+    type Interface = dyn Counted;
+
+    fn provide(_: &M) -> Result<Box<dyn Counted>, Box<dyn std::error::Error>> {
+        BUILDS.fetch_add(1, Ordering::SeqCst);
+        Ok(Box::new(CountedImpl))
+    }
+}
+
+module! {
+    TestModule {
+        components = [],
+        providers = [#[promote_to_component] FormatterImpl, ReportImpl]
+    }
+}
+
+module! {
+    CountingModule {
+        components = [],
+        providers = [#[promote_to_component] CountedImpl]
+    }
+}
+
+/// The promoted service is resolved as a component
+#[test]
+fn resolve_promoted_provider() {
+    let module = TestModule::builder().build();
+
+    let formatter: Arc<dyn Formatter> = module.resolve();
+    assert_eq!(formatter.format(3), "#3");
+    let other: Arc<dyn Formatter> = module.resolve();
+    assert!(Arc::ptr_eq(&formatter, &other));
+
+    let provided: Box<dyn Formatter> = module.provide().unwrap();
+    assert_eq!(provided.format(4), "#4");
+}
+
+/// Providers can inject the promoted service as a component
+#[test]
+fn inject_promoted_provider() {
+    let module = TestModule::builder().build();
+    let report: Box<dyn Report> = module.provide().unwrap();
+
+    assert_eq!(report.render(), "#1");
+}
+
+/// The service is built once, during the module build
+#[test]
+fn promoted_provider_built_once() {
+    let before = BUILDS.load(Ordering::SeqCst);
+    let module = CountingModule::builder().build();
+    assert_eq!(BUILDS.load(Ordering::SeqCst), before + 1);
+
+    let _: &dyn Counted = module.resolve_ref();
+    let _: Arc<dyn Counted> = module.resolve();
+    assert_eq!(BUILDS.load(Ordering::SeqCst), before + 1);
+}
+
+/// The provider is registered as both a provider and a component
+#[test]
+fn registered_as_both() {
+    let kinds: Vec<ServiceKind> = CountingModule::services()
+        .iter()
+        .map(|service| service.kind())
+        .collect();
+
+    assert_eq!(kinds, [ServiceKind::Provider, ServiceKind::Component]);
+}
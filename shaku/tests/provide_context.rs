@@ -0,0 +1,130 @@
+//! Providers can read the caller's context via ProvideContext
+
+use shaku::{module, HasProvider, Module, ProvideContext, Provider};
+use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
+
+#[derive(Debug, PartialEq)]
+struct RequestId(u64);
+
+trait Client {
+    fn request_id(&self) -> Option<u64>;
+}
+
+struct ClientImpl {
+    request_id: Option<u64>,
+}
+
+impl Client for ClientImpl {
+    fn request_id(&self) -> Option<u64> {
+        self.request_id
+    }
+}
+
+impl<M: Module> Provider<M> for ClientImpl {
+    type Interface = dyn Client;
+
+    fn provide(_: &M) -> Result<Box<dyn Client>, Box<dyn Error>> {
+        let request_id =
+            ProvideContext::current().and_then(|context| context.get::<RequestId>().map(|id| id.0));
+
+        Ok(Box::new(ClientImpl { request_id }))
+    }
+}
+
+trait Service {
+    fn client(&self) -> &dyn Client;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(provide)]
+    client: Box<dyn Client>,
+}
+
+impl Service for ServiceImpl {
+    fn client(&self) -> &dyn Client {
+        &*self.client
+    }
+}
+
+trait Failing {}
+
+struct FailingImpl;
+impl Failing for FailingImpl {}
+
+impl<M: Module> Provider<M> for FailingImpl {
+    type Interface = dyn Failing;
+
+    fn provide(_: &M) -> Result<Box<dyn Failing>, Box<dyn Error>> {
+        panic!("Failed to provide")
+    }
+}
+
+module! {
+    TestModule {
+        components = [],
+        providers = [ClientImpl, ServiceImpl, FailingImpl]
+    }
+}
+
+/// The context is available to the provider
+#[test]
+fn context_available_to_provider() {
+    let module = TestModule::builder().build();
+    let context = ProvideContext::new().with(RequestId(1));
+
+    let client: Box<dyn Client> = module.provide_with_context(&context).unwrap();
+
+    assert_eq!(client.request_id(), Some(1));
+}
+
+/// The context is available to providers of provided dependencies
+#[test]
+fn context_available_to_dependencies() {
+    let module = TestModule::builder().build();
+    let context = ProvideContext::new().with(RequestId(2));
+
+    let service: Box<dyn Service> = module.provide_with_context(&context).unwrap();
+
+    assert_eq!(service.client().request_id(), Some(2));
+}
+
+/// The context is only current during the provide call
+#[test]
+fn context_not_current_after_provide() {
+    let module = TestModule::builder().build();
+    let context = ProvideContext::new().with(RequestId(3));
+
+    let _: Box<dyn Client> = module.provide_with_context(&context).unwrap();
+    let client: Box<dyn Client> = module.provide().unwrap();
+
+    assert!(ProvideContext::current().is_none());
+    assert_eq!(client.request_id(), None);
+}
+
+/// The context is no longer current if the provider panics
+#[test]
+fn context_removed_after_panic() {
+    let module = TestModule::builder().build();
+    let context = ProvideContext::new().with(RequestId(4));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _: Result<Box<dyn Failing>, _> = module.provide_with_context(&context);
+    }));
+
+    assert!(result.is_err());
+    assert!(ProvideContext::current().is_none());
+}
+
+/// Values can be replaced and looked up by type
+#[test]
+fn context_values() {
+    let mut context = ProvideContext::new().with(RequestId(5)).with("name");
+    context.insert(RequestId(6));
+
+    assert_eq!(context.get::<RequestId>(), Some(&RequestId(6)));
+    assert_eq!(context.get::<&str>(), Some(&"name"));
+    assert_eq!(context.get::<u32>(), None);
+}
@@ -0,0 +1,90 @@
+//! Submodule providers can be injected as factories, and shared modules can be used as factories
+
+use shaku::{module, Component, Factory, HasComponent, Interface, ModuleFactory, Provider};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+trait Connection {
+    fn id(&self) -> usize;
+}
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct ConnectionImpl;
+
+impl Connection for ConnectionImpl {
+    fn id(&self) -> usize {
+        NEXT_ID.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+trait ConnectionPool: Interface {
+    fn open(&self, count: usize) -> Vec<Box<dyn Connection>>;
+}
+
+#[derive(Component)]
+#[shaku(interface = ConnectionPool)]
+struct ConnectionPoolImpl {
+    #[shaku(inject)]
+    connections: Arc<dyn Factory<dyn Connection>>,
+}
+
+impl ConnectionPool for ConnectionPoolImpl {
+    fn open(&self, count: usize) -> Vec<Box<dyn Connection>> {
+        (0..count)
+            .map(|_| self.connections.create().unwrap())
+            .collect()
+    }
+}
+
+module! {
+    DatabaseModule {
+        components = [],
+        providers = [ConnectionImpl]
+    }
+}
+
+module! {
+    PoolModule {
+        components = [ConnectionPoolImpl],
+        providers = [],
+
+        use DatabaseModule {
+            components = [],
+            providers = [dyn Connection]
+        }
+    }
+}
+
+/// Components can inject a factory for a submodule's provider
+#[test]
+fn inject_submodule_factory() {
+    let database_module = Arc::new(DatabaseModule::builder().build());
+    let module = PoolModule::builder(database_module).build();
+    let pool: &dyn ConnectionPool = module.resolve_ref();
+
+    let connections = pool.open(3);
+    assert_eq!(connections.len(), 3);
+    assert_ne!(connections[0].id(), connections[1].id());
+}
+
+/// The factory can be resolved from the module like other components
+#[test]
+fn resolve_submodule_factory() {
+    let database_module = Arc::new(DatabaseModule::builder().build());
+    let module = PoolModule::builder(database_module).build();
+    let factory: Arc<dyn Factory<dyn Connection>> = module.resolve();
+
+    assert!(factory.create().is_ok());
+}
+
+/// A shared module can be used as a factory for its own providers
+#[test]
+fn module_factory() {
+    let module = Arc::new(DatabaseModule::builder().build());
+    let factory: Arc<dyn Factory<dyn Connection>> = Arc::new(ModuleFactory::new(module));
+
+    assert!(factory.create().is_ok());
+}
@@ -0,0 +1,154 @@
+//! Pooled providers reuse their services instead of creating new ones
+
+use shaku::{
+    module, HasComponent, HasProvider, Module, Pooled, PooledProvider, ProvideContext, Provider,
+    ProviderPool, ProviderPoolParameters, Reset, Scope,
+};
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+trait Buffer: Send {
+    fn write(&mut self, data: &str);
+    fn contents(&self) -> &str;
+    fn id(&self) -> usize;
+}
+
+impl Reset for dyn Buffer {
+    fn reset(&mut self) {
+        self.write("");
+    }
+}
+
+struct BufferImpl {
+    id: usize,
+    contents: String,
+}
+
+impl Buffer for BufferImpl {
+    fn write(&mut self, data: &str) {
+        self.contents = data.to_string();
+    }
+
+    fn contents(&self) -> &str {
+        &self.contents
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+}
+
+impl<M: Module> Provider<M> for BufferImpl {
+    type Interface = dyn Buffer;
+
+    fn provide(_: &M) -> Result<Box<dyn Buffer>, Box<dyn Error>> {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        Ok(Box::new(BufferImpl {
+            id: NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            contents: String::new(),
+        }))
+    }
+}
+
+module! {
+    TestModule {
+        components = [ProviderPool<dyn Buffer>],
+        providers = [PooledProvider<BufferImpl>]
+    }
+}
+
+fn provide(module: &TestModule) -> Box<Pooled<dyn Buffer>> {
+    module.provide().unwrap()
+}
+
+fn pool(module: &TestModule) -> &ProviderPool<dyn Buffer> {
+    module.resolve_ref()
+}
+
+/// Dropped services are reset and handed out again
+#[test]
+fn reuses_dropped_services() {
+    let module = TestModule::builder().build();
+
+    let mut buffer = provide(&module);
+    let id = buffer.id();
+    buffer.write("hello");
+    assert_eq!(pool(&module).idle(), 0);
+    drop(buffer);
+    assert_eq!(pool(&module).idle(), 1);
+
+    let buffer = provide(&module);
+    assert_eq!(buffer.id(), id);
+    assert_eq!(buffer.contents(), "");
+}
+
+/// Services which are in use are not handed out again
+#[test]
+fn provides_new_services_while_in_use() {
+    let module = TestModule::builder().build();
+
+    let first = provide(&module);
+    let second = provide(&module);
+    assert_ne!(first.id(), second.id());
+}
+
+/// The pool drops returned services once it is full
+#[test]
+fn max_idle() {
+    let module = TestModule::builder()
+        .with_component_parameters::<ProviderPool<dyn Buffer>>(ProviderPoolParameters {
+            max_idle: Some(1),
+        })
+        .build();
+
+    let first = provide(&module);
+    let second = provide(&module);
+    drop(first);
+    drop(second);
+    assert_eq!(pool(&module).idle(), 1);
+
+    pool(&module).clear();
+    assert_eq!(pool(&module).idle(), 0);
+}
+
+/// Services taken out of the guard are not returned to the pool
+#[test]
+fn into_inner() {
+    let module = TestModule::builder().build();
+
+    let buffer: Box<dyn Buffer> = provide(&module).into_inner();
+    drop(buffer);
+    assert_eq!(pool(&module).idle(), 0);
+}
+
+/// Services provided within a scope return to the pool once both the service is dropped and the
+/// scope has ended
+#[test]
+fn returns_to_pool_after_scope() {
+    let module = TestModule::builder().build();
+
+    // The service is dropped before the scope ends
+    let scope = Scope::new();
+    let context = ProvideContext::new().with(scope.clone());
+    let buffer: Box<Pooled<dyn Buffer>> = module.provide_with_context(&context).unwrap();
+    drop(buffer);
+    assert_eq!(pool(&module).idle(), 0);
+
+    drop(context);
+    drop(scope);
+    assert_eq!(pool(&module).idle(), 1);
+
+    // The scope ends before the service is dropped
+    let scope = Scope::new();
+    let context = ProvideContext::new().with(scope.clone());
+    let buffer: Box<Pooled<dyn Buffer>> = module.provide_with_context(&context).unwrap();
+    assert_eq!(pool(&module).idle(), 0);
+
+    drop(context);
+    drop(scope);
+    assert_eq!(pool(&module).idle(), 0);
+
+    drop(buffer);
+    assert_eq!(pool(&module).idle(), 1);
+}
@@ -0,0 +1,132 @@
+//! Providers can retry failures
+
+use shaku::{module, HasProvider, Module, Provider, Retry, RetryPolicy, RetryProvider};
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+static CONNECTION_ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+static FLAKY_ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+static BROKEN_ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Fails the first two attempts
+struct Connection;
+
+impl<M: Module> Provider<M> for Connection {
+    type Interface = Connection;
+
+    fn provide(_: &M) -> Result<Box<Connection>, Box<dyn Error>> {
+        if CONNECTION_ATTEMPTS.fetch_add(1, Ordering::SeqCst) < 2 {
+            return Err("connection refused".into());
+        }
+
+        Ok(Box::new(Connection))
+    }
+}
+
+struct ConnectionRetry;
+
+impl Retry for ConnectionRetry {
+    const POLICY: RetryPolicy = RetryPolicy::new(3, Duration::from_millis(1));
+}
+
+trait Service {}
+
+/// Fails the first attempt
+struct FlakyDependency;
+
+impl<M: Module> Provider<M> for FlakyDependency {
+    type Interface = FlakyDependency;
+
+    fn provide(_: &M) -> Result<Box<FlakyDependency>, Box<dyn Error>> {
+        if FLAKY_ATTEMPTS.fetch_add(1, Ordering::SeqCst) < 1 {
+            return Err("temporarily unavailable".into());
+        }
+
+        Ok(Box::new(FlakyDependency))
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Service, retry(times = 2, backoff_ms = 1))]
+struct ServiceImpl {
+    #[shaku(provide)]
+    #[allow(dead_code)]
+    dependency: Box<FlakyDependency>,
+}
+impl Service for ServiceImpl {}
+
+trait Broken {}
+
+/// Always fails
+struct BrokenDependency;
+
+impl<M: Module> Provider<M> for BrokenDependency {
+    type Interface = BrokenDependency;
+
+    fn provide(_: &M) -> Result<Box<BrokenDependency>, Box<dyn Error>> {
+        BROKEN_ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+        Err("down for maintenance".into())
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Broken, retry(times = 2))]
+struct BrokenImpl {
+    #[shaku(provide)]
+    #[allow(dead_code)]
+    dependency: Box<BrokenDependency>,
+}
+impl Broken for BrokenImpl {}
+
+module! {
+    TestModule {
+        components = [],
+        providers = [
+            RetryProvider<Connection, ConnectionRetry>,
+            FlakyDependency,
+            ServiceImpl,
+            BrokenDependency,
+            BrokenImpl
+        ]
+    }
+}
+
+/// RetryProvider retries the wrapped provider until it succeeds
+#[test]
+fn retry_provider() {
+    let module = TestModule::builder().build();
+    let connection: Result<Box<Connection>, _> = module.provide();
+
+    assert!(connection.is_ok());
+    assert_eq!(CONNECTION_ATTEMPTS.load(Ordering::SeqCst), 3);
+}
+
+/// The retry attribute retries failures of the provider's dependencies
+#[test]
+fn retry_attribute() {
+    let module = TestModule::builder().build();
+    let service: Result<Box<dyn Service>, _> = module.provide();
+
+    assert!(service.is_ok());
+    assert_eq!(FLAKY_ATTEMPTS.load(Ordering::SeqCst), 2);
+}
+
+/// The last error is returned once the retries run out
+#[test]
+fn retries_exhausted() {
+    let module = TestModule::builder().build();
+    let error = HasProvider::<dyn Broken>::provide(&module).err().unwrap();
+
+    assert_eq!(error.to_string(), "down for maintenance");
+    assert_eq!(BROKEN_ATTEMPTS.load(Ordering::SeqCst), 3);
+}
+
+/// The delay doubles after each retry
+#[test]
+fn exponential_backoff() {
+    let policy = RetryPolicy::new(5, Duration::from_millis(100));
+
+    assert_eq!(policy.delay(0), Duration::from_millis(100));
+    assert_eq!(policy.delay(3), Duration::from_millis(800));
+}
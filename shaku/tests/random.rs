@@ -0,0 +1,80 @@
+//! Components can inject ID and random number generators, which tests can
+//! replace with deterministic versions
+
+use shaku::extras::{
+    IdGenerator, RandomSource, SeededRandom, SequentialIdGenerator, ThreadRandom, UuidV4Generator,
+    UuidV7Generator,
+};
+use shaku::{module, Component, HasComponent, Interface};
+use std::sync::Arc;
+use uuid::Uuid;
+
+trait UserFactory: Interface {
+    fn create(&self) -> (Uuid, u32);
+}
+
+#[derive(Component)]
+#[shaku(interface = UserFactory)]
+struct UserFactoryImpl {
+    #[shaku(inject)]
+    ids: Arc<dyn IdGenerator>,
+    #[shaku(inject)]
+    random: Arc<dyn RandomSource>,
+}
+
+impl UserFactory for UserFactoryImpl {
+    fn create(&self) -> (Uuid, u32) {
+        (self.ids.generate(), self.random.next_u32() % 100)
+    }
+}
+
+module! {
+    TestModule {
+        components = [UuidV4Generator, ThreadRandom, UserFactoryImpl],
+        providers = []
+    }
+}
+
+/// The default generators create random IDs
+#[test]
+fn random_ids() {
+    let module = TestModule::builder().build();
+    let factory: &dyn UserFactory = module.resolve_ref();
+    let (first, _) = factory.create();
+    let (second, _) = factory.create();
+
+    assert_eq!(first.get_version_num(), 4);
+    assert_ne!(first, second);
+}
+
+/// Version 7 IDs start with the creation time
+#[test]
+fn time_ordered_ids() {
+    let first = UuidV7Generator.generate();
+    let second = UuidV7Generator.generate();
+
+    assert_eq!(first.get_version_num(), 7);
+    assert_ne!(first, second);
+    assert!(first.as_bytes()[..6] <= second.as_bytes()[..6]);
+}
+
+/// The deterministic generators can replace the defaults via component
+/// overrides
+#[test]
+fn deterministic_overrides() {
+    let build = || {
+        TestModule::builder()
+            .with_component_override::<dyn IdGenerator>(Box::new(SequentialIdGenerator::new()))
+            .with_component_override::<dyn RandomSource>(Box::new(SeededRandom::new(7)))
+            .build()
+    };
+    let first_module = build();
+    let second_module = build();
+    let first: &dyn UserFactory = first_module.resolve_ref();
+    let second: &dyn UserFactory = second_module.resolve_ref();
+
+    let (id, number) = first.create();
+    assert_eq!(id, Uuid::from_u128(1));
+    assert_eq!(second.create(), (id, number));
+    assert_eq!(first.create().0, Uuid::from_u128(2));
+}
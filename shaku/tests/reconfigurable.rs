@@ -0,0 +1,128 @@
+//! Reconfigurable components receive new parameters via module.apply_parameters
+
+use shaku::{module, Component, HasComponent, Interface, Reconfigurable, ReconfigurableModule};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+trait Greeter: Interface {
+    fn greet(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter, reconfigurable)]
+struct GreeterImpl {
+    #[shaku(default = RwLock::new("Hello".to_string()))]
+    greeting: RwLock<String>,
+}
+
+impl Greeter for GreeterImpl {
+    fn greet(&self) -> String {
+        format!("{}, world!", self.greeting.read().unwrap())
+    }
+}
+
+impl Reconfigurable<GreeterImplParameters> for GreeterImpl {
+    fn reconfigure(&self, params: GreeterImplParameters) {
+        *self.greeting.write().unwrap() = params.greeting.into_inner().unwrap();
+    }
+}
+
+trait Counter: Interface {
+    fn greet(&self) -> String;
+    fn limit(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = Counter, reconfigurable)]
+struct CounterImpl {
+    #[shaku(inject)]
+    greeter: Arc<dyn Greeter>,
+    limit: AtomicUsize,
+}
+
+impl Counter for CounterImpl {
+    fn greet(&self) -> String {
+        self.greeter.greet()
+    }
+
+    fn limit(&self) -> usize {
+        self.limit.load(Ordering::SeqCst)
+    }
+}
+
+impl Reconfigurable<CounterImplParameters> for CounterImpl {
+    fn reconfigure(&self, params: CounterImplParameters) {
+        self.limit
+            .store(params.limit.into_inner(), Ordering::SeqCst);
+    }
+}
+
+module! {
+    #[shaku(derive_clone)]
+    GreetingModule {
+        components = [GreeterImpl],
+        providers = []
+    }
+}
+
+module! {
+    LazyModule {
+        components = [GreeterImpl, #[lazy] CounterImpl],
+        providers = []
+    }
+}
+
+/// The live component receives the new parameters, and clones of the module share it
+#[test]
+fn apply_parameters() {
+    let module = GreetingModule::builder().build();
+    let clone = module.clone();
+    let greeter: Arc<dyn Greeter> = module.resolve();
+    assert_eq!(greeter.greet(), "Hello, world!");
+
+    assert!(
+        clone.apply_parameters::<GreeterImpl>(GreeterImplParameters {
+            greeting: RwLock::new("Goodbye".to_string()),
+        })
+    );
+
+    assert_eq!(greeter.greet(), "Goodbye, world!");
+    let greeter: &dyn Greeter = module.resolve_ref();
+    assert_eq!(greeter.greet(), "Goodbye, world!");
+}
+
+/// Lazy components receive parameters once they are resolved
+#[test]
+fn lazy_component() {
+    let module = LazyModule::builder()
+        .with_component_parameters::<CounterImpl>(CounterImplParameters {
+            limit: AtomicUsize::new(1),
+        })
+        .build();
+    let new_limit = || CounterImplParameters {
+        limit: AtomicUsize::new(5),
+    };
+
+    assert!(!module.apply_parameters::<CounterImpl>(new_limit()));
+
+    let counter: &dyn Counter = module.resolve_ref();
+    assert_eq!(counter.greet(), "Hello, world!");
+    assert_eq!(counter.limit(), 1);
+    assert!(module.apply_parameters::<CounterImpl>(new_limit()));
+    assert_eq!(counter.limit(), 5);
+}
+
+/// Overridden components are not reconfigured
+#[test]
+fn overridden_component() {
+    let module = GreetingModule::builder()
+        .with_component_override::<dyn Greeter>(Box::new(GreeterImpl {
+            greeting: RwLock::new("Hi".to_string()),
+        }))
+        .build();
+
+    assert!(!module.apply_parameters::<GreeterImpl>(GreeterImplParameters::default()));
+
+    let greeter: &dyn Greeter = module.resolve_ref();
+    assert_eq!(greeter.greet(), "Hi, world!");
+}
@@ -0,0 +1,87 @@
+//! Several components can be resolved in one call via ResolveMany
+
+use shaku::{module, Component, HasComponent, Interface, ResolveMany};
+use std::sync::Arc;
+
+trait Logger: Interface {
+    fn name(&self) -> &'static str;
+}
+trait Database: Interface {}
+trait Cache: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {
+    fn name(&self) -> &'static str {
+        "logger"
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct DatabaseImpl;
+impl Database for DatabaseImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl;
+impl Cache for CacheImpl {}
+
+module! {
+    SubModule {
+        components = [LoggerImpl],
+        providers = []
+    }
+}
+
+module! {
+    RootModule {
+        components = [DatabaseImpl, CacheImpl],
+        providers = [],
+
+        use SubModule {
+            components = [Logger],
+            providers = []
+        }
+    }
+}
+
+fn root_module() -> RootModule {
+    let submodule = Arc::new(SubModule::builder().build());
+    RootModule::builder(submodule).build()
+}
+
+/// The tuple can be given explicitly, and includes components from submodules
+#[test]
+fn resolve_many_explicit() {
+    let module = root_module();
+    let (logger, database, cache) =
+        module.resolve_many::<(Arc<dyn Logger>, Arc<dyn Database>, Arc<dyn Cache>)>();
+
+    assert_eq!(logger.name(), "logger");
+    assert!(Arc::ptr_eq(&database, &module.resolve()));
+    assert!(Arc::ptr_eq(&cache, &module.resolve()));
+}
+
+/// The tuple can be inferred from the variable types
+#[test]
+fn resolve_many_inferred() {
+    let module = root_module();
+    let (logger,): (Arc<dyn Logger>,) = module.resolve_many();
+
+    assert_eq!(logger.name(), "logger");
+}
+
+/// Module trait objects and shared modules can resolve many components
+#[test]
+fn resolve_many_from_dyn_and_arc() {
+    let module = Arc::new(root_module());
+    let (database, cache): (Arc<dyn Database>, Arc<dyn Cache>) = module.resolve_many();
+
+    let module_ref: &dyn HasComponent<dyn Database> = &*module;
+    let (same_database,): (Arc<dyn Database>,) = module_ref.resolve_many();
+
+    assert!(Arc::ptr_eq(&database, &same_database));
+    assert!(Arc::ptr_eq(&cache, &module.resolve()));
+}
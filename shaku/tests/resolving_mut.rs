@@ -0,0 +1,96 @@
+//! Components can be mutated through the module while it is their only owner
+
+use shaku::{module, Component, HasComponent, Interface};
+use std::sync::Arc;
+
+trait Counter: Interface {
+    fn get(&self) -> usize;
+    fn increment(&mut self);
+}
+
+#[derive(Component)]
+#[shaku(interface = Counter)]
+struct CounterImpl {
+    #[shaku(default)]
+    count: usize,
+}
+
+impl Counter for CounterImpl {
+    fn get(&self) -> usize {
+        self.count
+    }
+
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+
+module! {
+    TestModule {
+        components = [CounterImpl],
+        providers = []
+    }
+}
+
+module! {
+    LazyModule {
+        components = [#[lazy] CounterImpl],
+        providers = []
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [],
+
+        use TestModule {
+            components = [Counter],
+            providers = []
+        }
+    }
+}
+
+/// The component can be mutated when the module is its only owner
+#[test]
+fn resolve_mut_unique() {
+    let mut module = TestModule::builder().build();
+
+    HasComponent::<dyn Counter>::resolve_mut(&mut module)
+        .unwrap()
+        .increment();
+    let counter: &dyn Counter = module.resolve_ref();
+
+    assert_eq!(counter.get(), 1);
+}
+
+/// The component cannot be mutated while it is shared
+#[test]
+fn resolve_mut_shared() {
+    let mut module = TestModule::builder().build();
+    let _counter: Arc<dyn Counter> = module.resolve();
+
+    assert!(HasComponent::<dyn Counter>::resolve_mut(&mut module).is_none());
+}
+
+/// Lazy components cannot be mutated
+#[test]
+fn resolve_mut_lazy() {
+    let mut module = LazyModule::builder().build();
+
+    assert!(HasComponent::<dyn Counter>::resolve_mut(&mut module).is_none());
+}
+
+/// Submodule components can be mutated if the submodule is not shared
+#[test]
+fn resolve_mut_submodule() {
+    let submodule = Arc::new(TestModule::builder().build());
+    let mut module = RootModule::builder(submodule).build();
+
+    HasComponent::<dyn Counter>::resolve_mut(&mut module)
+        .unwrap()
+        .increment();
+    let counter: &dyn Counter = module.resolve_ref();
+
+    assert_eq!(counter.get(), 1);
+}
@@ -0,0 +1,87 @@
+//! Components can spawn tasks via the `RuntimeHandle` component
+
+use shaku::extras::{RuntimeHandle, SpawnedTask, Spawner};
+use shaku::{module, Component, HasComponent, Interface};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use tokio::runtime::{Builder, Runtime};
+
+trait Notifier: Interface {
+    fn notify(&self, sender: Sender<&'static str>);
+}
+
+#[derive(Component)]
+#[shaku(interface = Notifier)]
+struct NotifierImpl {
+    #[shaku(inject)]
+    spawner: Arc<dyn Spawner>,
+}
+
+impl Notifier for NotifierImpl {
+    fn notify(&self, sender: Sender<&'static str>) {
+        self.spawner.spawn(Box::pin(async move {
+            sender.send("notified").unwrap();
+        }));
+    }
+}
+
+module! {
+    TestModule {
+        components = [RuntimeHandle, NotifierImpl],
+        providers = []
+    }
+}
+
+/// Run the runtime until the message is received
+fn receive(runtime: &Runtime, receiver: Receiver<&'static str>) -> &'static str {
+    runtime.block_on(async {
+        loop {
+            if let Ok(message) = receiver.try_recv() {
+                return message;
+            }
+
+            tokio::task::yield_now().await;
+        }
+    })
+}
+
+/// The current runtime is used when the module is built within a tokio context
+#[test]
+fn current_runtime() {
+    let runtime = Builder::new_current_thread().build().unwrap();
+    let module = runtime.block_on(async { TestModule::builder().build() });
+    let notifier: &dyn Notifier = module.resolve_ref();
+    let (sender, receiver) = channel();
+
+    notifier.notify(sender);
+    assert_eq!(receive(&runtime, receiver), "notified");
+}
+
+/// Building outside of a tokio context without a handle panics
+#[test]
+#[should_panic(expected = "RuntimeHandle requires a tokio runtime")]
+fn no_runtime() {
+    TestModule::builder().build();
+}
+
+/// Tests can replace the spawner, ex. to check which tasks are spawned
+#[test]
+fn override_spawner() {
+    struct ManualSpawner(Arc<Mutex<Vec<SpawnedTask>>>);
+    impl Spawner for ManualSpawner {
+        fn spawn(&self, task: SpawnedTask) {
+            self.0.lock().unwrap().push(task);
+        }
+    }
+
+    let tasks = Arc::new(Mutex::new(Vec::new()));
+    let module = TestModule::builder()
+        .with_component_override::<dyn Spawner>(Box::new(ManualSpawner(Arc::clone(&tasks))))
+        .build();
+    let notifier: &dyn Notifier = module.resolve_ref();
+    let (sender, receiver) = channel();
+
+    notifier.notify(sender);
+    assert_eq!(tasks.lock().unwrap().len(), 1);
+    assert!(receiver.try_recv().is_err());
+}
@@ -0,0 +1,120 @@
+//! Provided services can register cleanup callbacks with the current scope
+
+use shaku::{module, HasProvider, Module, ProvideContext, Provider, Scope};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Log(Mutex<Vec<&'static str>>);
+
+impl Log {
+    fn push(&self, entry: &'static str) {
+        self.0.lock().unwrap().push(entry);
+    }
+
+    fn entries(&self) -> Vec<&'static str> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+fn on_scope_drop(entry: &'static str) {
+    let context = ProvideContext::current().unwrap();
+    let log = Arc::clone(context.get::<Arc<Log>>().unwrap());
+
+    if let Some(scope) = Scope::current() {
+        scope.on_drop(move || log.push(entry));
+    }
+}
+
+trait Connection {}
+
+struct ConnectionImpl;
+impl Connection for ConnectionImpl {}
+
+impl<M: Module> Provider<M> for ConnectionImpl {
+    type Interface = dyn Connection;
+
+    fn provide(_: &M) -> Result<Box<dyn Connection>, Box<dyn Error>> {
+        on_scope_drop("release connection");
+        Ok(Box::new(ConnectionImpl))
+    }
+}
+
+trait Transaction {}
+
+struct TransactionImpl {
+    _connection: Box<dyn Connection>,
+}
+impl Transaction for TransactionImpl {}
+
+impl<M: Module + HasProvider<dyn Connection>> Provider<M> for TransactionImpl {
+    type Interface = dyn Transaction;
+
+    fn provide(module: &M) -> Result<Box<dyn Transaction>, Box<dyn Error>> {
+        let connection = module.provide()?;
+        on_scope_drop("rollback transaction");
+
+        Ok(Box::new(TransactionImpl {
+            _connection: connection,
+        }))
+    }
+}
+
+module! {
+    MyModule {
+        components = [],
+        providers = [ConnectionImpl, TransactionImpl]
+    }
+}
+
+/// Callbacks run in reverse order when the scope ends
+#[test]
+fn callbacks_run_in_reverse_order() {
+    let module = MyModule::builder().build();
+    let log = Arc::new(Log::default());
+    let scope = Scope::new();
+    let context = ProvideContext::new()
+        .with(scope.clone())
+        .with(Arc::clone(&log));
+
+    let _transaction: Box<dyn Transaction> = module.provide_with_context(&context).unwrap();
+    assert_eq!(scope.pending_callbacks(), 2);
+    assert!(log.entries().is_empty());
+
+    drop(context);
+    assert!(log.entries().is_empty());
+
+    drop(scope);
+    assert_eq!(
+        log.entries(),
+        vec!["rollback transaction", "release connection"]
+    );
+}
+
+/// Without a scope in the context, no callbacks are registered
+#[test]
+fn no_scope() {
+    let module = MyModule::builder().build();
+    let log = Arc::new(Log::default());
+    let context = ProvideContext::new().with(Arc::clone(&log));
+
+    let _transaction: Box<dyn Transaction> = module.provide_with_context(&context).unwrap();
+    drop(context);
+
+    assert!(log.entries().is_empty());
+}
+
+/// Callbacks can be registered directly
+#[test]
+fn direct_callbacks() {
+    let log = Arc::new(Log::default());
+    let scope = Scope::new();
+
+    let first = Arc::clone(&log);
+    scope.on_drop(move || first.push("first"));
+    let second = Arc::clone(&log);
+    scope.on_drop(move || second.push("second"));
+    drop(scope);
+
+    assert_eq!(log.entries(), vec!["second", "first"]);
+}
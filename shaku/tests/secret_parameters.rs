@@ -0,0 +1,77 @@
+//! Secret parameters are redacted in the parameters struct's Debug output
+#![allow(dead_code)]
+
+use shaku::{Component, Interface};
+use std::fmt::Debug;
+
+trait Database: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct DatabaseImpl {
+    url: String,
+    #[shaku(secret)]
+    password: String,
+    #[shaku(default = 5)]
+    pool_size: usize,
+}
+impl Database for DatabaseImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Database, parameters_derive(Clone, Debug))]
+struct TupleDatabaseImpl(String, #[shaku(secret)] String);
+impl Database for TupleDatabaseImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct GenericDatabaseImpl<T: Interface + Default> {
+    #[shaku(default)]
+    options: T,
+    #[shaku(secret)]
+    #[shaku(default)]
+    token: String,
+}
+impl<T: Interface + Default> Database for GenericDatabaseImpl<T> {}
+
+/// Secret fields are printed as `***`
+#[test]
+fn redacts_secret_fields() {
+    let parameters = DatabaseImplParameters {
+        url: "postgres://localhost".to_string(),
+        password: "hunter2".to_string(),
+        pool_size: 5,
+    };
+
+    assert_eq!(
+        format!("{:?}", parameters),
+        r#"DatabaseImplParameters { url: "postgres://localhost", password: ***, pool_size: 5 }"#
+    );
+}
+
+/// `parameters_derive(Debug)` is replaced by the redacting impl, and other derives still apply
+#[test]
+fn tuple_struct_with_derives() {
+    let parameters = TupleDatabaseImplParameters("localhost".to_string(), "hunter2".to_string());
+    let debug = format!("{:?}", parameters.clone());
+
+    assert_eq!(debug, r#"TupleDatabaseImplParameters("localhost", ***)"#);
+    assert!(!debug.contains("hunter2"));
+}
+
+/// Generic parameters structs are Debug if the non-secret parameters are Debug
+#[test]
+fn generic_parameters() {
+    fn debug<T: Debug>(value: T) -> String {
+        format!("{:?}", value)
+    }
+
+    let parameters = GenericDatabaseImplParameters::<u8> {
+        options: 3,
+        token: "secret-token".to_string(),
+    };
+
+    assert_eq!(
+        debug(parameters),
+        "GenericDatabaseImplParameters { options: 3, token: *** }"
+    );
+}
@@ -0,0 +1,94 @@
+//! Components can be looked up by the TypeId of their interface via ServiceLocator
+
+use shaku::{module, Component, HasComponent, Interface, Provider, ServiceLocator};
+use std::any::TypeId;
+use std::sync::Arc;
+
+trait Logger: Interface {
+    fn prefix(&self) -> &str;
+}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+
+impl Logger for LoggerImpl {
+    fn prefix(&self) -> &str {
+        "log"
+    }
+}
+
+trait Reporter: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Reporter)]
+struct ReporterImpl;
+impl Reporter for ReporterImpl {}
+
+trait Connection {}
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct ConnectionImpl;
+impl Connection for ConnectionImpl {}
+
+module! {
+    LoggingModule {
+        components = [LoggerImpl],
+        providers = []
+    }
+}
+
+module! {
+    RootModule {
+        components = [ReporterImpl],
+        providers = [ConnectionImpl],
+
+        use LoggingModule {
+            components = [dyn Logger],
+            providers = []
+        }
+    }
+}
+
+fn root_module() -> RootModule {
+    RootModule::builder(Arc::new(LoggingModule::builder().build())).build()
+}
+
+/// Components of the module and its submodules are located as the module's instances
+#[test]
+fn locate_components() {
+    let module = root_module();
+    let locator: &dyn ServiceLocator = &module;
+
+    let logger = locator.get::<dyn Logger>().unwrap();
+    let reporter = locator.get::<dyn Reporter>().unwrap();
+
+    assert_eq!(logger.prefix(), "log");
+    assert!(Arc::ptr_eq(
+        &logger,
+        &HasComponent::<dyn Logger>::resolve(&module)
+    ));
+    assert!(Arc::ptr_eq(
+        &reporter,
+        &HasComponent::<dyn Reporter>::resolve(&module)
+    ));
+}
+
+/// The type-erased service is an Arc of the interface
+#[test]
+fn get_arc() {
+    let module = root_module();
+    let logger = module.get_arc(TypeId::of::<dyn Logger>()).unwrap();
+
+    assert!(logger.downcast_ref::<Arc<dyn Logger>>().is_some());
+}
+
+/// Providers and unknown interfaces are not located
+#[test]
+fn missing_services() {
+    let module = root_module();
+
+    assert!(module.get_arc(TypeId::of::<dyn Connection>()).is_none());
+    assert!(module.get_arc(TypeId::of::<String>()).is_none());
+}
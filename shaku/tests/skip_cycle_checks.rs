@@ -0,0 +1,64 @@
+//! Tests related to the `skip_cycle_checks` feature, which skips tracking the resolution chain in
+//! release builds
+
+use shaku::{module, Component, HasComponent, Interface};
+use std::sync::Arc;
+
+trait Logger: Interface {
+    fn prefix(&self) -> &str;
+}
+trait Service: Interface {
+    fn describe(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {
+    fn prefix(&self) -> &str {
+        "[log]"
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(inject)]
+    logger: Arc<dyn Logger>,
+}
+impl Service for ServiceImpl {
+    fn describe(&self) -> String {
+        format!("{} service", self.logger.prefix())
+    }
+}
+
+module! {
+    TestModule {
+        components = [ServiceImpl, LoggerImpl],
+        providers = []
+    }
+}
+
+/// Components are built with their dependencies
+#[test]
+fn build_with_dependencies() {
+    let module = TestModule::builder().build();
+    let service: &dyn Service = module.resolve_ref();
+
+    assert_eq!(service.describe(), "[log] service");
+}
+
+/// Profiled builds still track the resolution chain, so the depth of each build is known
+#[test]
+fn profiled_build_tracks_depth() {
+    let (_module, report) = TestModule::builder().build_profiled();
+    let components = report.components();
+
+    assert_eq!(components.len(), 2);
+    assert_eq!(
+        components[0].component_type_name(),
+        "skip_cycle_checks::LoggerImpl"
+    );
+    assert_eq!(components[0].depth(), 1);
+    assert_eq!(components[1].depth(), 0);
+}
@@ -0,0 +1,88 @@
+//! Generic code over modules also accepts modules behind an `Arc` or `Box`
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
+use std::sync::Arc;
+
+trait Counter: Interface {
+    fn count(&self) -> usize;
+}
+trait Connection {
+    fn id(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = Counter)]
+struct CounterImpl {
+    #[shaku(default = 3)]
+    value: usize,
+}
+
+impl Counter for CounterImpl {
+    fn count(&self) -> usize {
+        self.value
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct ConnectionImpl;
+
+impl Connection for ConnectionImpl {
+    fn id(&self) -> usize {
+        7
+    }
+}
+
+trait AppModuleInterface: HasComponent<dyn Counter> + HasProvider<dyn Connection> {}
+
+module! {
+    AppModule: AppModuleInterface {
+        components = [CounterImpl],
+        providers = [ConnectionImpl]
+    }
+}
+
+fn count<M: HasComponent<dyn Counter> + ?Sized>(module: &M) -> usize {
+    module.resolve_ref().count()
+}
+
+fn connection_id<M: HasProvider<dyn Connection> + ?Sized>(module: &M) -> usize {
+    module.provide().unwrap().id()
+}
+
+/// Arc<M> forwards to the module
+#[test]
+fn arc_module() {
+    let module = Arc::new(AppModule::builder().build());
+
+    assert_eq!(count(&module), 3);
+    assert_eq!(connection_id(&module), 7);
+}
+
+/// Arc<dyn ModuleInterface> forwards to the module
+#[test]
+fn arc_module_interface() {
+    let module: Arc<dyn AppModuleInterface> = Arc::new(AppModule::builder().build());
+
+    assert_eq!(count(&module), 3);
+    assert_eq!(connection_id(&module), 7);
+}
+
+/// Box<M> forwards to the module
+#[test]
+fn boxed_module() {
+    let module: Box<dyn AppModuleInterface> = Box::new(AppModule::builder().build());
+
+    assert_eq!(count(&module), 3);
+    assert_eq!(connection_id(&module), 7);
+}
+
+/// Mutable resolves are only possible if the Arc is not shared
+#[test]
+fn arc_resolve_mut() {
+    let mut module = Arc::new(AppModule::builder().build());
+    assert!(HasComponent::<dyn Counter>::resolve_mut(&mut module).is_some());
+
+    let _shared = Arc::clone(&module);
+    assert!(HasComponent::<dyn Counter>::resolve_mut(&mut module).is_none());
+}
@@ -0,0 +1,100 @@
+//! Submodules can be accessed by type from the build context
+
+use shaku::{module, Component, HasComponent, Interface, ModuleBuildContext};
+use std::sync::Arc;
+
+trait Auth: Interface {
+    fn user(&self) -> String;
+}
+
+trait Database: Interface {
+    fn name(&self) -> String;
+}
+
+trait Service: Interface {
+    fn describe(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Auth)]
+struct AuthImpl;
+impl Auth for AuthImpl {
+    fn user(&self) -> String {
+        "user".to_string()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct DatabaseImpl;
+impl Database for DatabaseImpl {
+    fn name(&self) -> String {
+        "production".to_string()
+    }
+}
+
+/// Manually implemented so it can use the submodules directly
+struct ServiceImpl {
+    auth: Arc<dyn Auth>,
+    database: Arc<dyn Database>,
+}
+
+impl Service for ServiceImpl {
+    fn describe(&self) -> String {
+        format!("{}@{}", self.auth.user(), self.database.name())
+    }
+}
+
+impl Component<RootModule> for ServiceImpl {
+    type Interface = dyn Service;
+    type Parameters = ();
+
+    fn build(context: &mut ModuleBuildContext<RootModule>, _: ()) -> Box<dyn Service> {
+        Box::new(ServiceImpl {
+            auth: context.submodule::<AuthModule>().resolve(),
+            database: context.submodule::<DatabaseModule>().resolve(),
+        })
+    }
+}
+
+module! {
+    AuthModule {
+        components = [AuthImpl],
+        providers = []
+    }
+}
+
+module! {
+    DatabaseModule {
+        components = [DatabaseImpl],
+        providers = []
+    }
+}
+
+module! {
+    RootModule {
+        components = [ServiceImpl],
+        providers = [],
+
+        use AuthModule {
+            components = [],
+            providers = []
+        },
+
+        use DatabaseModule {
+            components = [],
+            providers = []
+        }
+    }
+}
+
+/// Each submodule is found by its type, regardless of its position
+#[test]
+fn access_submodules_by_type() {
+    let auth_module = Arc::new(AuthModule::builder().build());
+    let database_module = Arc::new(DatabaseModule::builder().build());
+    let module = RootModule::builder(auth_module, database_module).build();
+    let service: &dyn Service = module.resolve_ref();
+
+    assert_eq!(service.describe(), "user@production");
+}
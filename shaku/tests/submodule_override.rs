@@ -0,0 +1,139 @@
+//! Submodules can be replaced via `with_submodule_override`
+
+use shaku::{module, module_interface, Component, HasComponent, Interface};
+use std::sync::Arc;
+
+trait Auth: Interface {
+    fn user(&self) -> String;
+}
+
+trait Database: Interface {
+    fn name(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Auth)]
+struct AuthImpl {
+    #[shaku(default = "user".to_string())]
+    user: String,
+}
+impl Auth for AuthImpl {
+    fn user(&self) -> String {
+        self.user.clone()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct DatabaseImpl {
+    #[shaku(default = "production".to_string())]
+    name: String,
+}
+impl Database for DatabaseImpl {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+module_interface! {
+    trait AuthModule {
+        components = [dyn Auth],
+        providers = []
+    }
+}
+
+module! {
+    AuthModuleImpl: AuthModule {
+        components = [AuthImpl],
+        providers = []
+    }
+}
+
+module! {
+    DatabaseModule {
+        components = [DatabaseImpl],
+        providers = []
+    }
+}
+
+module! {
+    AuthRootModule {
+        components = [],
+        providers = [],
+
+        use dyn AuthModule {
+            components = [dyn Auth],
+            providers = []
+        }
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [],
+
+        use dyn AuthModule {
+            components = [dyn Auth],
+            providers = []
+        },
+
+        use DatabaseModule {
+            components = [dyn Database],
+            providers = []
+        }
+    }
+}
+
+fn auth_module(user: &str) -> Arc<dyn AuthModule> {
+    Arc::new(
+        AuthModuleImpl::builder()
+            .with_component_parameters::<AuthImpl>(AuthImplParameters {
+                user: user.to_string(),
+            })
+            .build(),
+    )
+}
+
+fn database_module(name: &str) -> Arc<DatabaseModule> {
+    Arc::new(
+        DatabaseModule::builder()
+            .with_component_parameters::<DatabaseImpl>(DatabaseImplParameters {
+                name: name.to_string(),
+            })
+            .build(),
+    )
+}
+
+/// A module's only submodule can be replaced by its trait
+#[test]
+fn override_single_submodule() {
+    let module = AuthRootModule::builder(auth_module("user"))
+        .with_submodule_override::<dyn AuthModule>(auth_module("admin"))
+        .build();
+    let auth: &dyn Auth = module.resolve_ref();
+
+    assert_eq!(auth.user(), "admin");
+}
+
+/// Each submodule can be replaced, by its trait or concrete type
+#[test]
+fn override_multiple_submodules() {
+    let module = RootModule::builder(auth_module("user"), database_module("production"))
+        .with_submodule_override::<DatabaseModule>(database_module("test"))
+        .build();
+    let auth: &dyn Auth = module.resolve_ref();
+    let database: &dyn Database = module.resolve_ref();
+
+    assert_eq!(auth.user(), "user");
+    assert_eq!(database.name(), "test");
+
+    let module = RootModule::builder(auth_module("user"), database_module("production"))
+        .with_submodule_override::<dyn AuthModule>(auth_module("admin"))
+        .build();
+    let auth: &dyn Auth = module.resolve_ref();
+    let database: &dyn Database = module.resolve_ref();
+
+    assert_eq!(auth.user(), "admin");
+    assert_eq!(database.name(), "production");
+}
@@ -0,0 +1,118 @@
+//! Built modules can reach into their submodules by type
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider, SubmodulePath};
+use std::sync::Arc;
+
+trait TokenStore: Interface {
+    fn token(&self) -> String;
+}
+trait Session {}
+trait Logger: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = TokenStore)]
+struct TokenStoreImpl {
+    #[shaku(default = "secret".to_string())]
+    token: String,
+}
+
+impl TokenStore for TokenStoreImpl {
+    fn token(&self) -> String {
+        self.token.clone()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Session)]
+struct SessionImpl;
+impl Session for SessionImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+trait AuthModuleInterface: HasComponent<dyn TokenStore> {}
+
+module! {
+    AuthModule: AuthModuleInterface {
+        components = [TokenStoreImpl],
+        providers = [SessionImpl]
+    }
+}
+
+module! {
+    AppModule {
+        components = [LoggerImpl],
+        providers = [],
+
+        use dyn AuthModuleInterface {
+            components = [],
+            providers = []
+        }
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [],
+
+        use AppModule {
+            components = [dyn Logger],
+            providers = []
+        },
+
+        use AuthModule {
+            components = [],
+            providers = []
+        }
+    }
+}
+
+fn root_module() -> RootModule {
+    let auth_module = Arc::new(AuthModule::builder().build());
+    let app_module = Arc::new(AppModule::builder(auth_module.clone()).build());
+
+    RootModule::builder(app_module, auth_module).build()
+}
+
+/// Services which are not re-exported can be resolved via the submodule
+#[test]
+fn resolve_from_submodule() {
+    let module = root_module();
+    let token_store: Arc<dyn TokenStore> = module.submodule::<AuthModule>().resolve();
+
+    assert_eq!(token_store.token(), "secret");
+}
+
+/// Providers of the submodule can be used too
+#[test]
+fn provide_from_submodule() {
+    let module = root_module();
+    let session: Result<Box<dyn Session>, _> = module.submodule::<AuthModule>().provide();
+
+    assert!(session.is_ok());
+}
+
+/// Submodules of submodules are reached by chaining, including module interfaces
+#[test]
+fn nested_submodules() {
+    let module = root_module();
+    let token_store: &dyn TokenStore = module
+        .submodule::<AppModule>()
+        .submodule::<dyn AuthModuleInterface>()
+        .resolve_ref();
+
+    assert_eq!(token_store.token(), "secret");
+}
+
+/// The submodule is the same instance the module was built with
+#[test]
+fn same_instance() {
+    let auth_module = Arc::new(AuthModule::builder().build());
+    let app_module = Arc::new(AppModule::builder(auth_module.clone()).build());
+    let module = RootModule::builder(app_module, auth_module.clone()).build();
+
+    assert!(Arc::ptr_eq(module.submodule::<AuthModule>(), &auth_module));
+}
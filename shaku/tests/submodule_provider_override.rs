@@ -0,0 +1,119 @@
+//! Provider overrides also apply to the services a module provides via its submodules
+
+use shaku::{module, HasProvider, Provider, ProviderFn};
+use std::sync::Arc;
+
+trait Repository {
+    fn name(&self) -> String;
+}
+
+trait Service {
+    fn repository_name(&self) -> String;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Repository)]
+struct DatabaseRepository;
+
+impl Repository for DatabaseRepository {
+    fn name(&self) -> String {
+        "database".to_string()
+    }
+}
+
+struct FakeRepository;
+
+impl Repository for FakeRepository {
+    fn name(&self) -> String {
+        "fake".to_string()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(provide)]
+    repository: Box<dyn Repository>,
+}
+
+impl Service for ServiceImpl {
+    fn repository_name(&self) -> String {
+        self.repository.name()
+    }
+}
+
+module! {
+    DataModule {
+        components = [],
+        providers = [DatabaseRepository]
+    }
+}
+
+module! {
+    AppModule {
+        components = [],
+        providers = [ServiceImpl],
+
+        use DataModule {
+            components = [],
+            providers = [dyn Repository]
+        }
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [],
+
+        use AppModule {
+            components = [],
+            providers = [dyn Repository, dyn Service]
+        }
+    }
+}
+
+fn app_module(override_repository: bool) -> AppModule {
+    let builder = AppModule::builder(Arc::new(DataModule::builder().build()));
+
+    if override_repository {
+        let fake: ProviderFn<AppModule, dyn Repository> =
+            Box::new(|_| Ok(Box::new(FakeRepository)));
+        builder.with_provider_override(fake).build()
+    } else {
+        builder.build()
+    }
+}
+
+/// Without an override, the submodule's provider is used
+#[test]
+fn submodule_provider_used() {
+    let module = app_module(false);
+    let repository: Box<dyn Repository> = module.provide().unwrap();
+    let service: Box<dyn Service> = module.provide().unwrap();
+
+    assert_eq!(repository.name(), "database");
+    assert_eq!(service.repository_name(), "database");
+}
+
+/// The override replaces the submodule's provider, including for the module's own providers
+#[test]
+fn override_submodule_provider() {
+    let module = app_module(true);
+    let repository: Box<dyn Repository> = module.provide().unwrap();
+    let service: Box<dyn Service> = module.provide().unwrap();
+
+    assert_eq!(repository.name(), "fake");
+    assert_eq!(service.repository_name(), "fake");
+}
+
+/// Modules using the overridden module also get the overridden service
+#[test]
+fn override_applies_to_parent_modules() {
+    let module = RootModule::builder(Arc::new(app_module(true))).build();
+    let repository: Box<dyn Repository> = module.provide().unwrap();
+    let service: Box<dyn Service> = module.provide().unwrap();
+
+    assert_eq!(repository.name(), "fake");
+    assert_eq!(service.repository_name(), "fake");
+}
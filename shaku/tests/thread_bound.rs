@@ -0,0 +1,99 @@
+//! Components which are not thread safe can be stored via ThreadBound
+
+use shaku::{module, Component, HasComponent, Interface, LocalInterface, ThreadBound};
+use std::cell::Cell;
+use std::panic;
+use std::sync::Arc;
+use std::thread;
+
+trait Counter: LocalInterface {
+    fn increment(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = Counter, thread_bound)]
+struct CounterImpl {
+    #[shaku(default)]
+    count: Cell<usize>,
+}
+
+impl Counter for CounterImpl {
+    fn increment(&self) -> usize {
+        self.count.set(self.count.get() + 1);
+        self.count.get()
+    }
+}
+
+trait Service: Interface {
+    fn counter(&self) -> &ThreadBound<dyn Counter>;
+}
+
+#[derive(Component)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(inject)]
+    counter: Arc<ThreadBound<dyn Counter>>,
+}
+
+impl Service for ServiceImpl {
+    fn counter(&self) -> &ThreadBound<dyn Counter> {
+        &self.counter
+    }
+}
+
+module! {
+    TestModule {
+        components = [CounterImpl, ServiceImpl],
+        providers = []
+    }
+}
+
+/// The component can be used on the thread which built the module
+#[test]
+fn resolve_on_owner_thread() {
+    let module = TestModule::builder().build();
+    let counter: Arc<ThreadBound<dyn Counter>> = module.resolve();
+
+    assert!(counter.is_owner_thread());
+    assert_eq!(counter.increment(), 1);
+    assert_eq!(counter.get().unwrap().increment(), 2);
+}
+
+/// The component can't be used from another thread
+#[test]
+fn access_from_other_thread() {
+    let module = TestModule::builder().build();
+    let counter: Arc<ThreadBound<dyn Counter>> = module.resolve();
+
+    let result = thread::spawn(move || {
+        assert!(!counter.is_owner_thread());
+        assert!(counter.get().is_none());
+
+        panic::catch_unwind(panic::AssertUnwindSafe(|| counter.increment())).is_err()
+    })
+    .join()
+    .unwrap();
+
+    assert!(result);
+}
+
+/// Dropping the module on another thread doesn't drop (or panic on) the component
+#[test]
+fn drop_on_other_thread() {
+    let module = TestModule::builder().build();
+    let counter: Arc<ThreadBound<dyn Counter>> = module.resolve();
+    drop(counter);
+
+    thread::spawn(move || drop(module)).join().unwrap();
+}
+
+/// Thread-bound components can be injected into other components
+#[test]
+fn inject_thread_bound() {
+    let module = TestModule::builder().build();
+    let service: Arc<dyn Service> = module.resolve();
+    let counter: Arc<ThreadBound<dyn Counter>> = module.resolve();
+
+    assert_eq!(service.counter().increment(), 1);
+    assert_eq!(counter.increment(), 2);
+}
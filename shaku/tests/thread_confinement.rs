@@ -0,0 +1,50 @@
+//! Modules which are not thread safe panic when used on another thread, if the
+//! `thread_confinement` feature is turned on
+#![cfg(not(feature = "thread_safe"))]
+
+use shaku::{module, Component, HasComponent, Interface};
+use std::rc::Rc;
+use std::thread;
+
+trait Service: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(default)]
+    #[allow(dead_code)]
+    value: Rc<usize>,
+}
+impl Service for ServiceImpl {}
+
+module! {
+    TestModule {
+        components = [ServiceImpl],
+        providers = []
+    }
+}
+
+/// Simulates user code which (incorrectly) moves the module to another thread
+struct AssertSend(TestModule);
+unsafe impl Send for AssertSend {}
+
+/// The module can be used on the thread which built it
+#[test]
+fn same_thread() {
+    let module = TestModule::builder().build();
+    let _service: &dyn Service = module.resolve_ref();
+}
+
+/// Using the module on another thread panics
+#[test]
+fn other_thread() {
+    let module = AssertSend(TestModule::builder().build());
+
+    let result = thread::spawn(move || {
+        let module = module;
+        let _service: &dyn Service = module.0.resolve_ref();
+    })
+    .join();
+
+    assert!(result.is_err());
+}
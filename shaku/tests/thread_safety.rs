@@ -1,20 +1,40 @@
 //! Tests related to thread-safety
 #![cfg(feature = "thread_safe")]
 
-use shaku::{module, Component, Interface, ModuleInterface};
+use shaku::{
+    module, Component, HasProvider, Interface, ModuleInterface, ProvidedInterface, Provider,
+    ThreadSafe,
+};
+use std::rc::Rc;
+use std::sync::Arc;
 
 trait TestComponent: Interface {}
 trait TestSubmodule: ModuleInterface {}
+trait TestProvider {
+    fn value(&self) -> Rc<usize>;
+}
 
 #[derive(Component)]
 #[shaku(interface = TestComponent)]
 struct TestComponentImpl;
 impl TestComponent for TestComponentImpl {}
 
+struct TestSubmoduleImpl;
+impl TestSubmodule for TestSubmoduleImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = TestProvider)]
+struct TestProviderImpl;
+impl TestProvider for TestProviderImpl {
+    fn value(&self) -> Rc<usize> {
+        Rc::new(1)
+    }
+}
+
 module! {
     TestModule {
         components = [TestComponentImpl],
-        providers = [],
+        providers = [TestProviderImpl],
 
         use TestSubmodule {
             components = [],
@@ -26,6 +46,10 @@ module! {
 // A compile-time test to assert that something is thread-safe
 fn assert_threadsafe<T: Send + Sync + ?Sized>() {}
 
+// A compile-time test to assert that a type satisfies a trait alias
+fn assert_thread_safe_alias<T: ThreadSafe + ?Sized>() {}
+fn assert_provided_interface<T: ProvidedInterface + ?Sized>() {}
+
 #[test]
 fn components_are_threadsafe() {
     assert_threadsafe::<dyn TestComponent>();
@@ -41,3 +65,20 @@ fn modules_are_threadsafe() {
 fn modules_with_submodules_are_threadsafe() {
     assert_threadsafe::<TestModule>();
 }
+
+/// Component interfaces are ThreadSafe via Interface
+#[test]
+fn components_are_thread_safe_alias() {
+    assert_thread_safe_alias::<dyn TestComponent>();
+}
+
+/// Provided interfaces do not need to be thread-safe
+#[test]
+fn provided_interfaces_are_not_threadsafe() {
+    assert_provided_interface::<dyn TestProvider>();
+
+    let module = TestModule::builder(Arc::new(TestSubmoduleImpl)).build();
+    let provided: Box<dyn TestProvider> = module.provide().unwrap();
+
+    assert_eq!(*provided.value(), 1);
+}
@@ -0,0 +1,73 @@
+//! Providers can be looked up at runtime via TryProvide
+
+use shaku::{module, Module, Provider, TryProvide};
+use std::error::Error;
+use std::sync::Arc;
+
+trait Connection {
+    fn id(&self) -> usize;
+}
+trait Cache {}
+trait Broken {}
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct ConnectionImpl;
+
+impl Connection for ConnectionImpl {
+    fn id(&self) -> usize {
+        5
+    }
+}
+
+struct BrokenImpl;
+impl Broken for BrokenImpl {}
+
+impl<M: Module> Provider<M> for BrokenImpl {
+    type Interface = dyn Broken;
+
+    fn provide(_: &M) -> Result<Box<dyn Broken>, Box<dyn Error>> {
+        Err("broken".into())
+    }
+}
+
+module! {
+    DatabaseModule {
+        components = [],
+        providers = [ConnectionImpl]
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [BrokenImpl],
+
+        use DatabaseModule {
+            components = [],
+            providers = [dyn Connection]
+        }
+    }
+}
+
+/// Registered providers, including those imported from submodules, are found
+#[test]
+fn registered_provider() {
+    let database_module = Arc::new(DatabaseModule::builder().build());
+    let module = RootModule::builder(database_module).build();
+
+    let connection = module.try_provide::<dyn Connection>().unwrap().unwrap();
+    assert_eq!(connection.id(), 5);
+}
+
+/// Unregistered providers are None, and provider errors are passed through
+#[test]
+fn unregistered_or_failing_provider() {
+    let database_module = Arc::new(DatabaseModule::builder().build());
+    let module = RootModule::builder(database_module).build();
+
+    assert!(module.try_provide::<dyn Cache>().is_none());
+
+    let error = module.try_provide::<dyn Broken>().unwrap().err().unwrap();
+    assert_eq!(error.to_string(), "broken");
+}
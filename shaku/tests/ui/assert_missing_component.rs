@@ -0,0 +1,16 @@
+//! Asserting a component which the module doesn't have will fail to compile
+
+use shaku::{assert_module_has, module, Interface};
+
+trait Logger: Interface {}
+
+module! {
+    TestModule {
+        components = [],
+        providers = []
+    }
+}
+
+const _: fn() = assert_module_has::<TestModule, dyn Logger>;
+
+fn main() {}
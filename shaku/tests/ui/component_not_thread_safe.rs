@@ -0,0 +1,16 @@
+//! Components must be thread safe when the thread_safe feature is on
+
+use shaku::{Component, Interface};
+use std::rc::Rc;
+
+trait Counter: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Counter)]
+struct CounterImpl {
+    #[shaku(default)]
+    count: Rc<usize>,
+}
+impl Counter for CounterImpl {}
+
+fn main() {}
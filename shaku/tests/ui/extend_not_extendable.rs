@@ -0,0 +1,19 @@
+//! Only modules with #[shaku(extendable)] can be extended
+
+use shaku::module;
+
+module! {
+    BaseModule {
+        components = [],
+        providers = []
+    }
+}
+
+module! {
+    ExtendedModule extends BaseModule {
+        components = [],
+        providers = []
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+//! Component interfaces must inherit Interface so the trait objects are thread safe
+
+use shaku::Component;
+
+trait Logger {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+fn main() {}
@@ -0,0 +1,35 @@
+//! Only the module's submodules can be overridden
+
+use shaku::module;
+use std::sync::Arc;
+
+module! {
+    SubModule {
+        components = [],
+        providers = []
+    }
+}
+
+module! {
+    OtherModule {
+        components = [],
+        providers = []
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [],
+
+        use SubModule {
+            components = [],
+            providers = []
+        }
+    }
+}
+
+fn main() {
+    let _ = RootModule::builder(Arc::new(SubModule::builder().build()))
+        .with_submodule_override::<OtherModule>(Arc::new(OtherModule::builder().build()));
+}
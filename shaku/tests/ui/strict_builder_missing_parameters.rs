@@ -0,0 +1,25 @@
+//! The strict builder cannot build the module until parameters without defaults are given
+
+use shaku::{module, Component, Interface};
+
+trait ServiceTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ServiceTrait)]
+struct ServiceImpl {
+    #[allow(dead_code)]
+    url: String,
+}
+impl ServiceTrait for ServiceImpl {}
+
+module! {
+    #[shaku(strict_builder)]
+    TestModule {
+        components = [ServiceImpl],
+        providers = []
+    }
+}
+
+fn main() {
+    let _module = TestModule::strict_builder().build();
+}
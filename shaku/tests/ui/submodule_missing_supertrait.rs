@@ -0,0 +1,19 @@
+//! Submodule traits must inherit ModuleInterface (ex. via HasComponent)
+
+use shaku::module;
+
+trait Submodule {}
+
+module! {
+    TestModule {
+        components = [],
+        providers = [],
+
+        use dyn Submodule {
+            components = [],
+            providers = []
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,101 @@
+//! Modules with a module interface report the services which are not reachable
+//! from it, and can deny them via `#[shaku(deny_unused)]`
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
+use std::sync::Arc;
+
+trait Database: Interface {}
+trait Cache: Interface {}
+trait Metrics: Interface {}
+trait Repository {}
+trait Connection {}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct DatabaseImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    cache: Arc<dyn Cache>,
+}
+impl Database for DatabaseImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl;
+impl Cache for CacheImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Metrics)]
+struct MetricsImpl;
+impl Metrics for MetricsImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct ConnectionImpl;
+impl Connection for ConnectionImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = Repository)]
+struct RepositoryImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    database: Arc<dyn Database>,
+    #[shaku(provide)]
+    #[allow(dead_code)]
+    connection: Box<dyn Connection>,
+}
+impl Repository for RepositoryImpl {}
+
+trait RepositoryModule: HasProvider<dyn Repository> {}
+
+module! {
+    #[shaku(deny_unused)]
+    RepositoryModuleImpl: RepositoryModule {
+        components = [DatabaseImpl, CacheImpl],
+        providers = [RepositoryImpl, ConnectionImpl]
+    }
+}
+
+trait DatabaseModule: HasComponent<dyn Database> {}
+
+module! {
+    DatabaseModuleImpl: DatabaseModule {
+        components = [DatabaseImpl, CacheImpl, MetricsImpl],
+        providers = [ConnectionImpl]
+    }
+}
+
+#[test]
+fn dependencies_are_reachable() {
+    assert!(RepositoryModuleImpl::unreachable_services().is_empty());
+
+    // deny_unused doesn't panic
+    RepositoryModuleImpl::builder().build();
+}
+
+#[test]
+fn unreachable_services_are_listed() {
+    assert_eq!(
+        DatabaseModuleImpl::unreachable_services(),
+        vec![
+            std::any::type_name::<dyn Metrics>(),
+            std::any::type_name::<dyn Connection>(),
+        ]
+    );
+}
+
+trait MetricsModule: HasComponent<dyn Database> {}
+
+module! {
+    #[shaku(deny_unused)]
+    MetricsModuleImpl: MetricsModule {
+        components = [DatabaseImpl, CacheImpl, MetricsImpl],
+        providers = []
+    }
+}
+
+#[test]
+#[should_panic(expected = "has services which are not reachable from its module interface")]
+fn deny_unused_panics() {
+    MetricsModuleImpl::builder().build();
+}
@@ -0,0 +1,117 @@
+//! Modules can record which of their services are used
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
+use std::sync::Arc;
+
+trait Logger: Interface {}
+trait Repository: Interface {}
+trait Cache: Interface {}
+trait Service {}
+trait Report {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Repository)]
+struct RepositoryImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    logger: Arc<dyn Logger>,
+}
+impl Repository for RepositoryImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl;
+impl Cache for CacheImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    repository: Arc<dyn Repository>,
+}
+impl Service for ServiceImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = Report)]
+struct ReportImpl;
+impl Report for ReportImpl {}
+
+module! {
+    AppModule {
+        components = [LoggerImpl, RepositoryImpl, CacheImpl],
+        providers = [ServiceImpl, ReportImpl]
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [],
+
+        use AppModule {
+            components = [dyn Logger, dyn Cache],
+            providers = []
+        }
+    }
+}
+
+/// Nothing is used right after building
+#[test]
+fn unused_after_build() {
+    let (_module, usage) = AppModule::builder().build_with_usage_tracking();
+    let report = usage.report();
+
+    assert_eq!(
+        report.unused_components(),
+        [
+            "dyn usage_tracking::Logger",
+            "dyn usage_tracking::Repository",
+            "dyn usage_tracking::Cache"
+        ]
+    );
+    assert_eq!(
+        report.unused_providers(),
+        ["dyn usage_tracking::Service", "dyn usage_tracking::Report"]
+    );
+}
+
+/// Components injected into used components are also used
+#[test]
+fn dependencies_are_used() {
+    let (module, usage) = AppModule::builder().build_with_usage_tracking();
+    let _repository: &dyn Repository = module.resolve_ref();
+    let report = usage.report();
+
+    assert_eq!(report.unused_components(), ["dyn usage_tracking::Cache"]);
+}
+
+/// Calling a provider marks it (and the components it resolves) as used
+#[test]
+fn providers_are_used() {
+    let (module, usage) = AppModule::builder().build_with_usage_tracking();
+    let _service: Box<dyn Service> = module.provide().unwrap();
+    let _cache: Arc<dyn Cache> = module.resolve();
+    let report = usage.report();
+
+    assert!(report.unused_components().is_empty());
+    assert_eq!(report.unused_providers(), ["dyn usage_tracking::Report"]);
+}
+
+/// Services from submodules are tracked by the module which uses them
+#[test]
+fn submodule_services() {
+    let app = Arc::new(AppModule::builder().build());
+    let (module, usage) = RootModule::builder(app).build_with_usage_tracking();
+    let _logger: &dyn Logger = module.resolve_ref();
+
+    assert_eq!(
+        usage.report().unused_components(),
+        ["dyn usage_tracking::Cache"]
+    );
+}
@@ -0,0 +1,145 @@
+//! Runtime verification of module services
+
+use shaku::{
+    module, Component, HasComponent, HasProvider, Interface, Module, ModuleBuildContext,
+    ModuleBuilder, ModuleServices, Provider, ProviderFn, ServiceKind, ServiceRegistry,
+    VerificationProblem,
+};
+use std::error::Error;
+use std::sync::Arc;
+
+trait Dependency: Interface {}
+trait Service {}
+
+#[derive(Component)]
+#[shaku(interface = Dependency)]
+struct DependencyImpl;
+impl Dependency for DependencyImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    dependency: Arc<dyn Dependency>,
+}
+impl Service for ServiceImpl {}
+
+module! {
+    MacroModule {
+        components = [DependencyImpl],
+        providers = [ServiceImpl]
+    }
+}
+
+/// This module forgets to build the dependency, which is only noticed when
+/// it is resolved
+struct ManualModule {
+    service: Arc<ProviderFn<Self, dyn Service>>,
+}
+
+impl Module for ManualModule {
+    type Submodules = ();
+
+    fn build(context: ModuleBuildContext<Self>) -> Self {
+        Self {
+            service: context.provider_fn::<ServiceImpl>(),
+        }
+    }
+}
+
+impl HasComponent<dyn Dependency> for ManualModule {
+    fn build_component(context: &mut ModuleBuildContext<Self>) -> Arc<dyn Dependency> {
+        context.build_component::<DependencyImpl>()
+    }
+
+    fn resolve(&self) -> Arc<dyn Dependency> {
+        unimplemented!("dependency was not built")
+    }
+
+    fn resolve_ref(&self) -> &dyn Dependency {
+        unimplemented!("dependency was not built")
+    }
+}
+
+impl HasProvider<dyn Service> for ManualModule {
+    fn provide(&self) -> Result<Box<dyn Service>, Box<dyn Error>> {
+        (self.service)(self)
+    }
+}
+
+impl ModuleServices for ManualModule {
+    fn register_services(services: &mut ServiceRegistry<Self>) {
+        services.component::<dyn Dependency>();
+        services.provider::<dyn Service>();
+    }
+}
+
+/// The module macro registers all services
+#[test]
+fn macro_module_services() {
+    let services = MacroModule::services();
+    let kinds: Vec<ServiceKind> = services.iter().map(|service| service.kind()).collect();
+
+    assert_eq!(kinds, vec![ServiceKind::Component, ServiceKind::Provider]);
+    assert_eq!(
+        services.iter().next().unwrap().interface_name(),
+        "dyn verify::Dependency"
+    );
+}
+
+/// A valid module has no issues
+#[test]
+fn verify_ok() {
+    let report = shaku::verify::<MacroModule>();
+
+    assert!(report.is_ok(), "{}", report);
+}
+
+/// Broken services in a manual module are reported
+#[test]
+fn verify_manual_module() {
+    let report = shaku::verify::<ManualModule>();
+    let issues = report.issues();
+
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].kind(), Some(ServiceKind::Component));
+    assert_eq!(issues[0].interface_name(), Some("dyn verify::Dependency"));
+    assert_eq!(
+        issues[0].problem(),
+        &VerificationProblem::Panicked("not implemented: dependency was not built".to_string())
+    );
+    assert_eq!(issues[1].kind(), Some(ServiceKind::Provider));
+}
+
+/// Provider errors are reported
+#[test]
+fn verify_provider_error() {
+    let report = ModuleBuilder::<MacroModule>::with_submodules(())
+        .with_provider_override::<dyn Service>(Box::new(|_| Err("no connection".into())))
+        .verify();
+
+    assert_eq!(
+        report.issues()[0].problem(),
+        &VerificationProblem::ProviderFailed("no connection".to_string())
+    );
+    assert_eq!(
+        report.to_string(),
+        "Found 1 issue(s):\n- provider dyn verify::Service: provider failed: no connection"
+    );
+}
+
+/// A panic during module build is reported
+#[test]
+fn verify_build_panic() {
+    let report = ModuleBuilder::<MacroModule>::with_submodules(())
+        .with_component_override_fn::<dyn Dependency>(Box::new(|_| panic!("build failed")))
+        .verify();
+
+    assert_eq!(report.issues().len(), 1);
+    assert_eq!(report.issues()[0].interface_name(), None);
+    assert_eq!(
+        report.issues()[0].problem(),
+        &VerificationProblem::BuildPanicked("build failed".to_string())
+    );
+}
@@ -0,0 +1,117 @@
+use crate::PluginComponent;
+use abi_stable::std_types::{RBox, RString, RVec};
+use abi_stable::{DynTrait, StableAbi};
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::mem;
+
+/// The components a plugin registers, which the host adds to its module via
+/// [`PluginComponents::with_plugin_component`]. This type has a stable ABI, so it can be passed
+/// between the host and plugins, even if they were compiled separately.
+///
+/// The components are stored type-erased, along with the key of their [`PluginComponent`] impl.
+/// The host checks the key and the size of the component before using it, but otherwise relies on
+/// the host and plugin using the same interface crate.
+///
+/// [`PluginComponents::with_plugin_component`]: trait.PluginComponents.html#tymethod.with_plugin_component
+/// [`PluginComponent`]: trait.PluginComponent.html
+#[repr(C)]
+#[derive(StableAbi, Default)]
+pub struct RModuleBuilder {
+    components: RVec<RComponent>,
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(impl_InterfaceType(Send, Sync))]
+struct ComponentInterface;
+
+/// A type-erased component, registered by a plugin
+#[repr(C)]
+#[derive(StableAbi)]
+struct RComponent {
+    key: RString,
+    size: usize,
+    alignment: usize,
+    component: DynTrait<'static, RBox<()>, ComponentInterface>,
+}
+
+impl RModuleBuilder {
+    /// Create a builder without any components
+    pub fn new() -> Self {
+        RModuleBuilder::default()
+    }
+
+    /// Register a component, replacing any component previously registered with the same key
+    pub fn with_component<C: PluginComponent>(mut self, component: C) -> Self {
+        self.components
+            .retain(|registered| registered.key.as_str() != C::KEY);
+        self.components.push(RComponent {
+            key: C::KEY.into(),
+            size: mem::size_of::<C>(),
+            alignment: mem::align_of::<C>(),
+            component: DynTrait::from_value(component),
+        });
+        self
+    }
+
+    /// The keys of the registered components, in the order they were registered
+    pub fn keys(&self) -> Vec<&str> {
+        self.components
+            .iter()
+            .map(|component| component.key.as_str())
+            .collect()
+    }
+
+    /// Remove the component registered with the key of `C`, if there is one
+    pub(crate) fn take<C: PluginComponent>(&mut self) -> Result<Option<C>, PluginError> {
+        let index = match self
+            .components
+            .iter()
+            .position(|component| component.key.as_str() == C::KEY)
+        {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let component = self.components.remove(index);
+
+        if component.size != mem::size_of::<C>() || component.alignment != mem::align_of::<C>() {
+            return Err(PluginError::LayoutMismatch(C::KEY));
+        }
+
+        // Downcasting checks the type ID, which differs between dynamic libraries, so the key and
+        // layout are checked instead.
+        let component: RBox<C> = unsafe { component.component.unchecked_downcast_into::<C>() };
+        Ok(Some(RBox::into_inner(component)))
+    }
+}
+
+impl Debug for RModuleBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RModuleBuilder")
+            .field("components", &self.keys())
+            .finish()
+    }
+}
+
+/// An error which occurred while adding a plugin's components to the host module
+#[derive(Debug)]
+pub enum PluginError {
+    /// The component registered with the key has a different layout than the host expects. The
+    /// plugin was probably compiled against a different version of the interface crate.
+    LayoutMismatch(&'static str),
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginError::LayoutMismatch(key) => write!(
+                f,
+                "The plugin component `{}` has a different layout than the host expects",
+                key
+            ),
+        }
+    }
+}
+
+impl Error for PluginError {}
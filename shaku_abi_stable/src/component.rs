@@ -0,0 +1,44 @@
+use crate::{PluginError, RModuleBuilder};
+use abi_stable::StableAbi;
+use shaku::{HasComponent, Interface, Module, ModuleBuilder};
+
+/// Adapts an FFI-safe trait object (ex. `Greeter_TO<'static, RBox<()>>` from `#[sabi_trait]`) to
+/// the host module's interface, so plugins can register it in an [`RModuleBuilder`]. Implement this
+/// in the interface crate which the host and plugins share.
+///
+/// [`RModuleBuilder`]: struct.RModuleBuilder.html
+pub trait PluginComponent: StableAbi + Send + Sync + Sized + 'static {
+    /// The key the component is registered with. It must be unique among the plugin components,
+    /// and should change if the trait object's layout changes.
+    const KEY: &'static str;
+
+    /// The interface which the component implements in the host module
+    type Interface: Interface + ?Sized;
+
+    /// Wrap the trait object in an implementation of the host module's interface
+    fn into_interface(self) -> Box<Self::Interface>;
+}
+
+/// Adds the components registered by plugins to the host module. Components which the plugins
+/// didn't register are built by the module as usual.
+pub trait PluginComponents<M: Module>: Sized {
+    /// Use the component the plugin registered for `C`, if it registered one. The component is
+    /// removed from the plugin's builder.
+    fn with_plugin_component<C>(self, plugin: &mut RModuleBuilder) -> Result<Self, PluginError>
+    where
+        C: PluginComponent,
+        M: HasComponent<C::Interface>;
+}
+
+impl<M: Module> PluginComponents<M> for ModuleBuilder<M> {
+    fn with_plugin_component<C>(self, plugin: &mut RModuleBuilder) -> Result<Self, PluginError>
+    where
+        C: PluginComponent,
+        M: HasComponent<C::Interface>,
+    {
+        match plugin.take::<C>()? {
+            Some(component) => Ok(self.with_component_override(component.into_interface())),
+            None => Ok(self),
+        }
+    }
+}
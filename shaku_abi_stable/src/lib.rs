@@ -0,0 +1,102 @@
+//! This crate provides integration between the `shaku` and `abi_stable` crates, so plugins which
+//! are compiled separately (as `cdylib`s) can contribute components to a host module.
+//!
+//! Rust trait objects don't have a stable ABI, so plugins can't hand `Box<dyn Trait>` to the host.
+//! Instead, the interfaces are declared with `abi_stable`'s `#[sabi_trait]` in a crate which the
+//! host and plugins share. For each interface, implement [`PluginComponent`] on the FFI-safe trait
+//! object (ex. `Greeter_TO<'static, RBox<()>>`), which adapts it to the host module's interface.
+//! Plugins register their components in an [`RModuleBuilder`] (which crosses the ABI boundary, ex.
+//! as the return value of a function in the plugin's root module), and the host adds them to its
+//! module via [`PluginComponents::with_plugin_component`].
+//!
+//! # Example
+//! ```rust
+//! use abi_stable::sabi_trait;
+//! use abi_stable::std_types::{RBox, RString};
+//! use shaku::{module, Component, HasComponent, Interface};
+//! use shaku_abi_stable::{PluginComponent, PluginComponents, RModuleBuilder};
+//!
+//! // In the shared interface crate
+//! #[sabi_trait]
+//! pub trait RGreeter: Send + Sync {
+//!     fn greet(&self) -> RString;
+//! }
+//!
+//! pub type RGreeterBox = RGreeter_TO<'static, RBox<()>>;
+//!
+//! pub trait Greeter: Interface {
+//!     fn greet(&self) -> String;
+//! }
+//!
+//! /// Adapts the plugin's greeter to the host's interface
+//! struct GreeterAdapter(RGreeterBox);
+//!
+//! impl Greeter for GreeterAdapter {
+//!     fn greet(&self) -> String {
+//!         self.0.greet().into_string()
+//!     }
+//! }
+//!
+//! impl PluginComponent for RGreeterBox {
+//!     const KEY: &'static str = "greeter";
+//!     type Interface = dyn Greeter;
+//!
+//!     fn into_interface(self) -> Box<dyn Greeter> {
+//!         Box::new(GreeterAdapter(self))
+//!     }
+//! }
+//!
+//! // In the plugin
+//! struct PluginGreeter;
+//!
+//! impl RGreeter for PluginGreeter {
+//!     fn greet(&self) -> RString {
+//!         "Hello from the plugin".into()
+//!     }
+//! }
+//!
+//! extern "C" fn register_components() -> RModuleBuilder {
+//!     RModuleBuilder::new()
+//!         .with_component(RGreeterBox::from_value(PluginGreeter, sabi_trait::TD_Opaque))
+//! }
+//!
+//! // In the host
+//! #[derive(Component)]
+//! #[shaku(interface = Greeter)]
+//! struct DefaultGreeter;
+//!
+//! impl Greeter for DefaultGreeter {
+//!     fn greet(&self) -> String {
+//!         "Hello from the host".to_string()
+//!     }
+//! }
+//!
+//! module! {
+//!     HostModule {
+//!         components = [DefaultGreeter],
+//!         providers = []
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), shaku_abi_stable::PluginError> {
+//! // The plugin is normally loaded via abi_stable's `RootModule::load_from_file`
+//! let mut plugin = register_components();
+//! let module = HostModule::builder()
+//!     .with_plugin_component::<RGreeterBox>(&mut plugin)?
+//!     .build();
+//!
+//! let greeter: &dyn Greeter = module.resolve_ref();
+//! assert_eq!(greeter.greet(), "Hello from the plugin");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`PluginComponent`]: trait.PluginComponent.html
+//! [`RModuleBuilder`]: struct.RModuleBuilder.html
+//! [`PluginComponents::with_plugin_component`]: trait.PluginComponents.html#tymethod.with_plugin_component
+
+mod builder;
+mod component;
+
+pub use builder::{PluginError, RModuleBuilder};
+pub use component::{PluginComponent, PluginComponents};
@@ -0,0 +1,147 @@
+//! Components registered by plugins are added to the host module
+
+// The items generated by abi_stable's #[sabi_trait] have empty doc comments
+#![allow(clippy::empty_docs)]
+
+use abi_stable::sabi_trait;
+use abi_stable::sabi_trait::TD_Opaque;
+use abi_stable::std_types::{RBox, RString};
+use abi_stable::StableAbi;
+use shaku::{module, Component, HasComponent, Interface};
+use shaku_abi_stable::{PluginComponent, PluginComponents, PluginError, RModuleBuilder};
+
+#[sabi_trait]
+pub trait RGreeter: Send + Sync {
+    fn greet(&self) -> RString;
+}
+
+type RGreeterBox = RGreeter_TO<'static, RBox<()>>;
+
+pub trait Greeter: Interface {
+    fn greet(&self) -> String;
+}
+
+struct GreeterAdapter(RGreeterBox);
+
+impl Greeter for GreeterAdapter {
+    fn greet(&self) -> String {
+        self.0.greet().into_string()
+    }
+}
+
+impl PluginComponent for RGreeterBox {
+    const KEY: &'static str = "greeter";
+    type Interface = dyn Greeter;
+
+    fn into_interface(self) -> Box<dyn Greeter> {
+        Box::new(GreeterAdapter(self))
+    }
+}
+
+struct PluginGreeter(&'static str);
+
+impl RGreeter for PluginGreeter {
+    fn greet(&self) -> RString {
+        self.0.into()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct DefaultGreeter;
+
+impl Greeter for DefaultGreeter {
+    fn greet(&self) -> String {
+        "host".to_string()
+    }
+}
+
+module! {
+    HostModule {
+        components = [DefaultGreeter],
+        providers = []
+    }
+}
+
+fn plugin_greeter(greeting: &'static str) -> RGreeterBox {
+    RGreeterBox::from_value(PluginGreeter(greeting), TD_Opaque)
+}
+
+fn greet(module: &HostModule) -> String {
+    let greeter: &dyn Greeter = module.resolve_ref();
+    greeter.greet()
+}
+
+/// The plugin's component replaces the host's component
+#[test]
+fn plugin_component_used() {
+    let mut plugin = RModuleBuilder::new().with_component(plugin_greeter("plugin"));
+    let module = HostModule::builder()
+        .with_plugin_component::<RGreeterBox>(&mut plugin)
+        .unwrap()
+        .build();
+
+    assert_eq!(greet(&module), "plugin");
+    assert!(plugin.keys().is_empty());
+}
+
+/// Without a plugin component, the host's component is used
+#[test]
+fn host_component_fallback() {
+    let mut plugin = RModuleBuilder::new();
+    let module = HostModule::builder()
+        .with_plugin_component::<RGreeterBox>(&mut plugin)
+        .unwrap()
+        .build();
+
+    assert_eq!(greet(&module), "host");
+}
+
+/// Registering a component again replaces it
+#[test]
+fn last_registration_wins() {
+    let mut plugin = RModuleBuilder::new()
+        .with_component(plugin_greeter("first"))
+        .with_component(plugin_greeter("second"));
+    assert_eq!(plugin.keys(), vec!["greeter"]);
+
+    let module = HostModule::builder()
+        .with_plugin_component::<RGreeterBox>(&mut plugin)
+        .unwrap()
+        .build();
+
+    assert_eq!(greet(&module), "second");
+}
+
+/// A component registered by an incompatible version of the interface crate
+#[repr(C)]
+#[derive(StableAbi)]
+struct OldGreeter(u8);
+
+struct OldGreeterAdapter;
+
+impl Greeter for OldGreeterAdapter {
+    fn greet(&self) -> String {
+        "old".to_string()
+    }
+}
+
+impl PluginComponent for OldGreeter {
+    const KEY: &'static str = "greeter";
+    type Interface = dyn Greeter;
+
+    fn into_interface(self) -> Box<dyn Greeter> {
+        Box::new(OldGreeterAdapter)
+    }
+}
+
+/// Components with a different layout than the host expects are rejected
+#[test]
+fn layout_mismatch() {
+    let mut plugin = RModuleBuilder::new().with_component(OldGreeter(1));
+
+    match HostModule::builder().with_plugin_component::<RGreeterBox>(&mut plugin) {
+        Err(PluginError::LayoutMismatch(key)) => assert_eq!(key, "greeter"),
+        _ => panic!("Expected a layout mismatch"),
+    }
+}
@@ -0,0 +1,36 @@
+use crate::services::{AppModule, EnglishModule, FrenchModule, Greeter, Greeting};
+use actix_web::{web, App, HttpServer};
+use shaku_actix::{Inject, InjectProvided};
+use std::sync::Arc;
+
+mod services;
+
+async fn greeting(greeting: Inject<dyn AppModule, dyn Greeting>) -> String {
+    greeting.greeting().to_string()
+}
+
+async fn greet(
+    name: web::Path<String>,
+    greeter: InjectProvided<dyn AppModule, dyn Greeter>,
+) -> String {
+    greeter.greet(&name)
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    // Set GREETING_LANGUAGE=fr to use the French module
+    let module: Arc<dyn AppModule> = match std::env::var("GREETING_LANGUAGE").as_deref() {
+        Ok("fr") => FrenchModule::builder().build().into(),
+        _ => EnglishModule::builder().build().into(),
+    };
+
+    HttpServer::new(move || {
+        App::new()
+            .app_data(module.clone())
+            .route("/", web::get().to(greeting))
+            .route("/greet/{name}", web::get().to(greet))
+    })
+    .bind("127.0.0.1:8080")?
+    .run()
+    .await
+}
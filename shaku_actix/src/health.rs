@@ -0,0 +1,45 @@
+use crate::Inject;
+use actix_web::HttpResponse;
+use shaku::extras::HealthRegistry;
+use shaku::{HasComponent, ModuleInterface};
+
+/// A handler which runs the checks of the module's [`HealthRegistry`]. It responds with the report
+/// as text, with status `200 OK` if the module is healthy and `503 Service Unavailable` otherwise.
+///
+/// Like [`Inject`], the module must be stored in app data as `Arc<M>`.
+///
+/// # Example
+/// ```rust
+/// use actix_web::{web, App};
+/// use shaku::extras::HealthRegistry;
+/// use shaku::module;
+/// use std::sync::Arc;
+///
+/// module! {
+///     MyModule {
+///         components = [HealthRegistry],
+///         providers = []
+///     }
+/// }
+///
+/// let module = Arc::new(MyModule::builder().build());
+/// let app = App::new()
+///     .app_data(module)
+///     .route("/health", web::get().to(shaku_actix::health::<MyModule>));
+/// ```
+///
+/// [`HealthRegistry`]: ../shaku/extras/struct.HealthRegistry.html
+/// [`Inject`]: struct.Inject.html
+pub async fn health<M>(registry: Inject<M, HealthRegistry>) -> HttpResponse
+where
+    M: ModuleInterface + HasComponent<HealthRegistry> + ?Sized,
+{
+    let report = registry.check();
+    let mut response = if report.is_healthy() {
+        HttpResponse::Ok()
+    } else {
+        HttpResponse::ServiceUnavailable()
+    };
+
+    response.body(report.to_string())
+}
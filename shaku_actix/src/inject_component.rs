@@ -3,6 +3,7 @@ use actix_web::dev::Payload;
 use actix_web::{Error, FromRequest, HttpRequest};
 use futures_util::future;
 use shaku::{HasComponent, Interface, ModuleInterface};
+use shaku_inject_core::resolve_component;
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::sync::Arc;
@@ -71,16 +72,28 @@ impl<M: ModuleInterface + HasComponent<I> + ?Sized, I: Interface + ?Sized> FromR
     type Future = future::Ready<Result<Self, Error>>;
 
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
-        let module = match get_module_from_state::<M>(req) {
+        let module = match get_module_from_state::<M, I>(req) {
             Ok(module) => module,
             Err(e) => return future::err(e),
         };
-        let component = module.resolve();
+        let component = resolve_component(&*module);
 
         future::ok(Inject(component, PhantomData))
     }
 }
 
+impl<M: ModuleInterface + HasComponent<I> + ?Sized, I: Interface + ?Sized> Inject<M, I> {
+    /// Take the component out of the extractor, ex. to move it into a spawned task
+    pub fn into_arc(self) -> Arc<I> {
+        self.0
+    }
+
+    /// The shared component. Clone it to keep the component after the extractor is dropped.
+    pub fn as_arc(&self) -> &Arc<I> {
+        &self.0
+    }
+}
+
 impl<M: ModuleInterface + HasComponent<I> + ?Sized, I: Interface + ?Sized> Deref for Inject<M, I> {
     type Target = I;
 
@@ -0,0 +1,86 @@
+use crate::get_module_from_state;
+use actix_web::dev::Payload;
+use actix_web::{Error, FromRequest, HttpRequest};
+use futures_util::future;
+use shaku::ModuleInterface;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Used to retrieve the shaku `Module` itself, ex. to resolve services which are only known at
+/// runtime. The module should be stored in Actix's app data, wrapped in an `Arc`.
+/// Use this struct as an extractor.
+///
+/// Like [`Inject`] and [`InjectProvided`], the module is looked up in app data once per request
+/// and then cached in the request's extensions, so further extractors reuse the cached module.
+///
+/// # Example
+/// ```rust
+/// use actix_web::{web, App};
+/// use shaku::{module, Component, HasComponent, Interface};
+/// use shaku_actix::InjectModule;
+/// use std::sync::Arc;
+///
+/// trait HelloWorld: Interface {
+///     fn greet(&self) -> String;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = HelloWorld)]
+/// struct HelloWorldImpl;
+///
+/// impl HelloWorld for HelloWorldImpl {
+///     fn greet(&self) -> String {
+///         "Hello, world!".to_owned()
+///     }
+/// }
+///
+/// trait HelloModule: HasComponent<dyn HelloWorld> {}
+///
+/// module! {
+///     HelloModuleImpl: HelloModule {
+///         components = [HelloWorldImpl],
+///         providers = []
+///     }
+/// }
+///
+/// async fn hello(module: InjectModule<dyn HelloModule>) -> String {
+///     let hello_world: &dyn HelloWorld = module.resolve_ref();
+///     hello_world.greet()
+/// }
+///
+/// let module: Arc<dyn HelloModule> = Arc::new(HelloModuleImpl::builder().build());
+/// let app = App::new()
+///     .app_data(module)
+///     .route("/", web::get().to(hello));
+/// ```
+///
+/// [`Inject`]: struct.Inject.html
+/// [`InjectProvided`]: struct.InjectProvided.html
+pub struct InjectModule<M: ModuleInterface + ?Sized>(Arc<M>);
+
+impl<M: ModuleInterface + ?Sized> InjectModule<M> {
+    /// Get the `Arc` containing the module
+    pub fn into_inner(self) -> Arc<M> {
+        self.0
+    }
+}
+
+impl<M: ModuleInterface + ?Sized> FromRequest for InjectModule<M> {
+    type Error = Error;
+    type Future = future::Ready<Result<Self, Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        match get_module_from_state::<M, M>(req) {
+            Ok(module) => future::ok(InjectModule(module)),
+            Err(e) => future::err(e),
+        }
+    }
+}
+
+impl<M: ModuleInterface + ?Sized> Deref for InjectModule<M> {
+    type Target = M;
+
+    fn deref(&self) -> &Self::Target {
+        Arc::as_ref(&self.0)
+    }
+}
@@ -1,9 +1,10 @@
-use crate::get_module_from_state;
+use crate::{get_module_from_state, ActixRequest};
 use actix_web::dev::Payload;
 use actix_web::error::ErrorInternalServerError;
 use actix_web::{Error, FromRequest, HttpRequest};
 use futures_util::future;
 use shaku::{HasProvider, ModuleInterface};
+use shaku_inject_core::{provide_failed, provide_service};
 use std::marker::PhantomData;
 use std::ops::Deref;
 
@@ -11,6 +12,10 @@ use std::ops::Deref;
 /// The module should be stored in Actix's app data, wrapped in an `Arc`.
 /// Use this struct as an extractor.
 ///
+/// With the `tracing` feature enabled, the service is provided inside the request's span, which is
+/// also available to providers via `shaku::ProvideContext`. The span is read from the request
+/// extensions (insert a `tracing::Span` via middleware), falling back to the current span.
+///
 /// # Example
 /// ```rust
 /// use actix_web::{App, HttpServer, web};
@@ -69,13 +74,13 @@ impl<M: ModuleInterface + HasProvider<I> + ?Sized, I: ?Sized> FromRequest for In
     type Future = future::Ready<Result<Self, Error>>;
 
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
-        let module = match get_module_from_state::<M>(req) {
+        let module = match get_module_from_state::<M, I>(req) {
             Ok(module) => module,
             Err(e) => return future::err(e),
         };
-        let service = match module.provide() {
+        let service = match provide_service(&*module, &ActixRequest(req)) {
             Ok(service) => service,
-            Err(e) => return future::err(ErrorInternalServerError(e)),
+            Err(e) => return future::err(ErrorInternalServerError(provide_failed::<M, I>(&*e))),
         };
 
         future::ok(InjectProvided(service, PhantomData))
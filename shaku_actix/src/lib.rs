@@ -1,24 +1,70 @@
 //! This crate provides integration between the `shaku` and `actix-web` crates.
 //!
-//! See [`Inject`] and [`InjectProvided`] for details.
+//! See [`Inject`], [`InjectProvided`], and [`InjectModule`] for details.
+//!
+//! # Module lookups
+//! The extractors find the module in Actix's app data, which is stored per scope, so a lookup
+//! checks the app data of each scope (from the innermost out) and downcasts the match. The first
+//! extractor to run for a request caches the module's `Arc` in the request's extensions, which is a
+//! single map lookup, so every further extractor of that request skips the app data search. This
+//! matters most for handlers which inject several services: a handler with three injected services
+//! does one app data search instead of three, and the savings grow with the number of extractors
+//! and nested scopes. Since the cached module is used for the rest of the request, an extractor
+//! which runs in a middleware before routing will cache the module from the app's (not the
+//! scope's) app data.
 //!
 //! [`Inject`]: struct.Inject.html
 //! [`InjectProvided`]: struct.InjectProvided.html
+//! [`InjectModule`]: struct.InjectModule.html
 
+#[cfg(feature = "health")]
+mod health;
 mod inject_component;
+mod inject_module;
 mod inject_provided;
 
+#[cfg(feature = "health")]
+pub use health::health;
 pub use inject_component::Inject;
+pub use inject_module::InjectModule;
 pub use inject_provided::InjectProvided;
 
 use actix_web::error::ErrorInternalServerError;
-use actix_web::{Error, HttpRequest};
+use actix_web::{Error, HttpMessage, HttpRequest};
 use shaku::ModuleInterface;
+use shaku_inject_core::{module_not_found, InjectRequest};
 use std::sync::Arc;
 
-fn get_module_from_state<M: ModuleInterface + ?Sized>(request: &HttpRequest) -> Result<&M, Error> {
-    request
+/// The module found in app data, cached in the request's extensions
+struct CachedModule<M: ?Sized>(Arc<M>);
+
+/// Get the module from Actix's app data, while injecting the interface `I`. The module is cached
+/// in the request's extensions, so only the first lookup of a request searches the app data.
+fn get_module_from_state<M: ModuleInterface + ?Sized, I: ?Sized>(
+    request: &HttpRequest,
+) -> Result<Arc<M>, Error> {
+    if let Some(CachedModule(module)) = request.extensions().get::<CachedModule<M>>() {
+        return Ok(Arc::clone(module));
+    }
+
+    let module = request
         .app_data::<Arc<M>>()
-        .map(Arc::as_ref)
-        .ok_or_else(|| ErrorInternalServerError("Failed to retrieve module from state"))
+        .cloned()
+        .ok_or_else(|| ErrorInternalServerError(module_not_found::<M, I>()))?;
+    request
+        .extensions_mut()
+        .insert(CachedModule(Arc::clone(&module)));
+
+    Ok(module)
+}
+
+/// Exposes the actix request to the shared extractor logic
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+struct ActixRequest<'a>(&'a HttpRequest);
+
+impl InjectRequest for ActixRequest<'_> {
+    #[cfg(feature = "tracing")]
+    fn span(&self) -> Option<tracing::Span> {
+        self.0.extensions().get::<tracing::Span>().cloned()
+    }
 }
@@ -0,0 +1,82 @@
+//! The health handler responds with the module's health report
+
+use actix_web::http::StatusCode;
+use actix_web::test::{call_service, init_service, read_body, TestRequest};
+use actix_web::{web, App};
+use shaku::extras::{HealthCheck, HealthRegistry, HealthStatus};
+use shaku::{module, Component, Interface};
+use std::sync::Arc;
+
+trait Database: Interface + HealthCheck {}
+
+#[derive(Component)]
+#[shaku(interface = Database, health_check)]
+struct DatabaseImpl {
+    healthy: bool,
+}
+
+impl Database for DatabaseImpl {}
+
+impl HealthCheck for DatabaseImpl {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    fn check(&self) -> HealthStatus {
+        if self.healthy {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Unhealthy("disconnected".to_string())
+        }
+    }
+}
+
+module! {
+    MyModule {
+        components = [HealthRegistry, DatabaseImpl],
+        providers = []
+    }
+}
+
+fn build_module(healthy: bool) -> MyModule {
+    MyModule::builder()
+        .with_component_parameters::<DatabaseImpl>(DatabaseImplParameters { healthy })
+        .build()
+}
+
+async fn get_health(healthy: bool) -> (StatusCode, String) {
+    let app = init_service(
+        App::new()
+            .app_data(Arc::new(build_module(healthy)))
+            .route("/health", web::get().to(shaku_actix::health::<MyModule>)),
+    )
+    .await;
+
+    let request = TestRequest::get().uri("/health").to_request();
+    let response = call_service(&app, request).await;
+    let status = response.status();
+    let body = read_body(response).await;
+
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+/// A healthy module responds with 200 OK
+#[actix_web::test]
+async fn healthy() {
+    assert_eq!(
+        get_health(true).await,
+        (StatusCode::OK, "healthy\n- database: healthy".to_string())
+    );
+}
+
+/// An unhealthy module responds with 503 Service Unavailable
+#[actix_web::test]
+async fn unhealthy() {
+    assert_eq!(
+        get_health(false).await,
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "unhealthy\n- database: unhealthy (disconnected)".to_string()
+        )
+    );
+}
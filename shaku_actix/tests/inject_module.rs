@@ -0,0 +1,125 @@
+//! The module can be extracted with `InjectModule`, and the module lookup is shared by all
+//! extractors of a request.
+
+use actix_web::http::StatusCode;
+use actix_web::test::{call_and_read_body, call_service, init_service, TestRequest};
+use actix_web::{web, App};
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
+use shaku_actix::{Inject, InjectModule, InjectProvided};
+use std::sync::Arc;
+
+trait Greeting: Interface {
+    fn greeting(&self) -> String;
+}
+trait Name: Interface {
+    fn name(&self) -> String;
+}
+trait Message {
+    fn message(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeting)]
+struct GreetingImpl;
+impl Greeting for GreetingImpl {
+    fn greeting(&self) -> String {
+        "Hello".to_string()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Name)]
+struct NameImpl;
+impl Name for NameImpl {
+    fn name(&self) -> String {
+        "world".to_string()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Message)]
+struct MessageImpl {
+    #[shaku(inject)]
+    greeting: Arc<dyn Greeting>,
+    #[shaku(inject)]
+    name: Arc<dyn Name>,
+}
+impl Message for MessageImpl {
+    fn message(&self) -> String {
+        format!("{}, {}!", self.greeting.greeting(), self.name.name())
+    }
+}
+
+trait MyModule: HasComponent<dyn Greeting> + HasComponent<dyn Name> + HasProvider<dyn Message> {}
+
+module! {
+    MyModuleImpl: MyModule {
+        components = [GreetingImpl, NameImpl],
+        providers = [MessageImpl]
+    }
+}
+
+async fn many_services(
+    greeting: Inject<dyn MyModule, dyn Greeting>,
+    name: Inject<dyn MyModule, dyn Name>,
+    message: InjectProvided<dyn MyModule, dyn Message>,
+    module: InjectModule<dyn MyModule>,
+) -> String {
+    let module_name: &dyn Name = module.resolve_ref();
+    format!(
+        "{} {} {} {}",
+        greeting.greeting(),
+        name.name(),
+        message.message(),
+        module_name.name()
+    )
+}
+
+async fn module_only(module: InjectModule<dyn MyModule>) -> String {
+    let module: Arc<dyn MyModule> = module.into_inner();
+    let greeting: &dyn Greeting = module.resolve_ref();
+    greeting.greeting()
+}
+
+fn module() -> Arc<dyn MyModule> {
+    Arc::new(MyModuleImpl::builder().build())
+}
+
+/// Every extractor of a request gets the services from the same module
+#[actix_web::test]
+async fn many_extractors() {
+    let app = init_service(
+        App::new()
+            .app_data(module())
+            .route("/", web::get().to(many_services)),
+    )
+    .await;
+
+    let body = call_and_read_body(&app, TestRequest::get().uri("/").to_request()).await;
+    assert_eq!(body, "Hello world Hello, world! world");
+}
+
+/// The module is found in app data of nested scopes
+#[actix_web::test]
+async fn module_in_scope() {
+    let app = init_service(
+        App::new().service(
+            web::scope("/api")
+                .app_data(module())
+                .route("/", web::get().to(module_only)),
+        ),
+    )
+    .await;
+
+    let body = call_and_read_body(&app, TestRequest::get().uri("/api/").to_request()).await;
+    assert_eq!(body, "Hello");
+}
+
+/// A missing module fails the request
+#[actix_web::test]
+async fn missing_module() {
+    let app = init_service(App::new().route("/", web::get().to(module_only))).await;
+
+    let response = call_service(&app, TestRequest::get().uri("/").to_request()).await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
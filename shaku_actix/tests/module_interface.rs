@@ -1,21 +1,39 @@
 //! Module interfaces can be used with `Inject` and `InjectProvided`.
 //! The module itself would be stored in state as `Arc<dyn MyModule>`.
 
+use actix_web::test::{call_and_read_body, init_service, TestRequest};
+use actix_web::{web, App};
 use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
 use shaku_actix::{Inject, InjectProvided};
+use std::sync::Arc;
 
-trait MyComponent: Interface {}
-trait MyProvider {}
+trait MyComponent: Interface {
+    fn name(&self) -> String;
+}
+trait MyProvider {
+    fn name(&self) -> String;
+}
 
 #[derive(Component)]
 #[shaku(interface = MyComponent)]
 struct MyComponentImpl;
-impl MyComponent for MyComponentImpl {}
+impl MyComponent for MyComponentImpl {
+    fn name(&self) -> String {
+        "component".to_string()
+    }
+}
 
 #[derive(Provider)]
 #[shaku(interface = MyProvider)]
-struct MyProviderImpl;
-impl MyProvider for MyProviderImpl {}
+struct MyProviderImpl {
+    #[shaku(inject)]
+    component: Arc<dyn MyComponent>,
+}
+impl MyProvider for MyProviderImpl {
+    fn name(&self) -> String {
+        format!("provider using {}", self.component.name())
+    }
+}
 
 trait MyModule: HasComponent<dyn MyComponent> + HasProvider<dyn MyProvider> {}
 
@@ -33,5 +51,52 @@ async fn index(
 ) {
 }
 
+async fn component_name(component: Inject<dyn MyModule, dyn MyComponent>) -> String {
+    component.name()
+}
+
+async fn spawned_component_name(component: Inject<dyn MyModule, dyn MyComponent>) -> String {
+    let component = component.into_arc();
+    std::thread::spawn(move || component.name()).join().unwrap()
+}
+
+async fn provider_name(provider: InjectProvided<dyn MyModule, dyn MyProvider>) -> String {
+    provider.name()
+}
+
+async fn get_body(uri: &str) -> String {
+    let module: Arc<dyn MyModule> = Arc::new(MyModuleImpl::builder().build());
+    let app = init_service(
+        App::new()
+            .app_data(module)
+            .route("/component", web::get().to(component_name))
+            .route("/spawned", web::get().to(spawned_component_name))
+            .route("/provider", web::get().to(provider_name)),
+    )
+    .await;
+
+    let request = TestRequest::get().uri(uri).to_request();
+    let body = call_and_read_body(&app, request).await;
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
 #[test]
 fn compiles_ok() {}
+
+/// Components can be injected from a module trait object
+#[actix_web::test]
+async fn inject_component() {
+    assert_eq!(get_body("/component").await, "component");
+}
+
+/// Provided services can be injected from a module trait object
+#[actix_web::test]
+async fn inject_provided() {
+    assert_eq!(get_body("/provider").await, "provider using component");
+}
+
+/// Injected components can be moved out of the extractor as an Arc
+#[actix_web::test]
+async fn inject_component_arc() {
+    assert_eq!(get_body("/spawned").await, "component");
+}
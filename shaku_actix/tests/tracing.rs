@@ -0,0 +1,93 @@
+//! With the `tracing` feature, `InjectProvided` provides services inside the request's span
+
+use actix_web::dev::Payload;
+use actix_web::test::TestRequest;
+use actix_web::{FromRequest, HttpMessage, HttpRequest};
+use shaku::{module, HasProvider, Module, ProvideContext, Provider};
+use shaku_actix::InjectProvided;
+use std::error::Error;
+use std::sync::Arc;
+use tracing::span::Id;
+use tracing::Span;
+
+trait SpanRecorder {
+    fn context_span(&self) -> Option<Id>;
+    fn current_span(&self) -> Option<Id>;
+}
+
+struct SpanRecorderImpl {
+    context_span: Option<Id>,
+    current_span: Option<Id>,
+}
+
+impl SpanRecorder for SpanRecorderImpl {
+    fn context_span(&self) -> Option<Id> {
+        self.context_span.clone()
+    }
+
+    fn current_span(&self) -> Option<Id> {
+        self.current_span.clone()
+    }
+}
+
+impl<M: Module> Provider<M> for SpanRecorderImpl {
+    type Interface = dyn SpanRecorder;
+
+    fn provide(_: &M) -> Result<Box<dyn SpanRecorder>, Box<dyn Error>> {
+        Ok(Box::new(SpanRecorderImpl {
+            context_span: ProvideContext::current()
+                .and_then(|context| context.get::<Span>().and_then(Span::id)),
+            current_span: Span::current().id(),
+        }))
+    }
+}
+
+trait MyModule: HasProvider<dyn SpanRecorder> {}
+
+module! {
+    MyModuleImpl: MyModule {
+        components = [],
+        providers = [SpanRecorderImpl]
+    }
+}
+
+fn create_request() -> HttpRequest {
+    let module: Arc<dyn MyModule> = Arc::new(MyModuleImpl::builder().build());
+
+    TestRequest::default().app_data(module).to_http_request()
+}
+
+fn extract(request: &HttpRequest) -> InjectProvided<dyn MyModule, dyn SpanRecorder> {
+    InjectProvided::from_request(request, &mut Payload::None)
+        .into_inner()
+        .unwrap()
+}
+
+/// The span in the request extensions is used
+#[test]
+fn span_from_extensions() {
+    let _subscriber = tracing::subscriber::set_default(tracing_subscriber::registry());
+    let span = tracing::info_span!("request");
+    let request = create_request();
+    request.extensions_mut().insert(span.clone());
+
+    let recorder = extract(&request);
+
+    assert!(span.id().is_some());
+    assert_eq!(recorder.context_span(), span.id());
+    assert_eq!(recorder.current_span(), span.id());
+}
+
+/// The current span is used if the request extensions don't contain a span
+#[test]
+fn current_span_fallback() {
+    let _subscriber = tracing::subscriber::set_default(tracing_subscriber::registry());
+    let span = tracing::info_span!("outer");
+    let _entered = span.enter();
+
+    let recorder = extract(&create_request());
+
+    assert!(span.id().is_some());
+    assert_eq!(recorder.context_span(), span.id());
+    assert_eq!(recorder.current_span(), span.id());
+}
@@ -0,0 +1,38 @@
+use crate::services::{AppModule, EnglishModule, FrenchModule, Greeter, Greeting};
+use axum::extract::Path;
+use axum::{routing::get, Router};
+use shaku_axum::{Inject, InjectProvided};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+mod services;
+
+async fn greeting(greeting: Inject<dyn AppModule, dyn Greeting>) -> String {
+    greeting.greeting().to_string()
+}
+
+async fn greet(
+    Path(name): Path<String>,
+    greeter: InjectProvided<dyn AppModule, dyn Greeter>,
+) -> String {
+    greeter.greet(&name)
+}
+
+#[tokio::main]
+async fn main() {
+    // Set GREETING_LANGUAGE=fr to use the French module
+    let module: Arc<dyn AppModule> = match std::env::var("GREETING_LANGUAGE").as_deref() {
+        Ok("fr") => FrenchModule::builder().build().into(),
+        _ => EnglishModule::builder().build().into(),
+    };
+
+    let app = Router::new()
+        .route("/", get(greeting))
+        .route("/greet/:name", get(greet))
+        .with_state(module);
+
+    let listener = TcpListener::bind("127.0.0.1:8080").await.unwrap();
+    axum::serve(listener, app.into_make_service())
+        .await
+        .unwrap();
+}
@@ -0,0 +1,63 @@
+//! Services which handlers access through the `AppModule` trait instead of a concrete module, so
+//! the module implementation can be chosen at runtime.
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
+use std::sync::Arc;
+
+pub trait AppModule: HasComponent<dyn Greeting> + HasProvider<dyn Greeter> {}
+
+module! {
+    pub EnglishModule: AppModule {
+        components = [EnglishGreeting],
+        providers = [GreeterImpl]
+    }
+}
+
+module! {
+    pub FrenchModule: AppModule {
+        components = [FrenchGreeting],
+        providers = [GreeterImpl]
+    }
+}
+
+pub trait Greeting: Interface {
+    fn greeting(&self) -> &str;
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeting)]
+pub struct EnglishGreeting;
+
+impl Greeting for EnglishGreeting {
+    fn greeting(&self) -> &str {
+        "Hello"
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeting)]
+pub struct FrenchGreeting;
+
+impl Greeting for FrenchGreeting {
+    fn greeting(&self) -> &str {
+        "Bonjour"
+    }
+}
+
+// Axum handlers must be Send, so provided services used in handlers must be Send too
+pub trait Greeter: Send {
+    fn greet(&self, name: &str) -> String;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Greeter)]
+pub struct GreeterImpl {
+    #[shaku(inject)]
+    greeting: Arc<dyn Greeting>,
+}
+
+impl Greeter for GreeterImpl {
+    fn greet(&self, name: &str) -> String {
+        format!("{}, {}!", self.greeting.greeting(), name)
+    }
+}
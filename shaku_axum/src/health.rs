@@ -0,0 +1,45 @@
+use crate::Inject;
+use axum::http::StatusCode;
+use shaku::extras::HealthRegistry;
+use shaku::{HasComponent, ModuleInterface};
+
+/// A handler which runs the checks of the module's [`HealthRegistry`]. It responds with the report
+/// as text, with status `200 OK` if the module is healthy and `503 Service Unavailable` otherwise.
+///
+/// Like [`Inject`], the module must be stored in Axum state as `Arc<M>`.
+///
+/// # Example
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use shaku::extras::HealthRegistry;
+/// use shaku::module;
+/// use std::sync::Arc;
+///
+/// module! {
+///     MyModule {
+///         components = [HealthRegistry],
+///         providers = []
+///     }
+/// }
+///
+/// let module = Arc::new(MyModule::builder().build());
+/// let app: Router = Router::new()
+///     .route("/health", get(shaku_axum::health::<MyModule>))
+///     .with_state(module);
+/// ```
+///
+/// [`HealthRegistry`]: ../shaku/extras/struct.HealthRegistry.html
+/// [`Inject`]: struct.Inject.html
+pub async fn health<M>(registry: Inject<M, HealthRegistry>) -> (StatusCode, String)
+where
+    M: ModuleInterface + HasComponent<HealthRegistry> + ?Sized,
+{
+    let report = registry.check();
+    let status = if report.is_healthy() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, report.to_string())
+}
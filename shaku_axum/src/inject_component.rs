@@ -4,6 +4,7 @@ use axum::{
     http::{request::Parts, StatusCode},
 };
 use shaku::{HasComponent, Interface, ModuleInterface};
+use shaku_inject_core::resolve_component;
 
 use std::marker::PhantomData;
 use std::ops::Deref;
@@ -11,7 +12,8 @@ use std::sync::Arc;
 
 /// Used to retrieve a reference to a component from a shaku `Module`.
 /// The module should be stored in Axum state, wrapped in an `Arc` (`Arc<MyModule>`).
-/// This `Arc<MyModule>` must implement `FromRef<S>` where `S` is the Axum state type.
+/// This `Arc<MyModule>` must implement `FromRef<S>` where `S` is the Axum state type, which
+/// [`ShakuState`] does (see [`ShakuRouterExt::with_shaku`]).
 ///
 /// Use this struct as an extractor.
 ///
@@ -78,6 +80,9 @@ use std::sync::Arc;
 ///     # }
 /// }
 /// ```
+///
+/// [`ShakuState`]: struct.ShakuState.html
+/// [`ShakuRouterExt::with_shaku`]: trait.ShakuRouterExt.html#tymethod.with_shaku
 pub struct Inject<M: ModuleInterface + HasComponent<I> + ?Sized, I: Interface + ?Sized>(
     Arc<I>,
     PhantomData<M>,
@@ -94,12 +99,24 @@ where
     type Rejection = (StatusCode, String);
 
     async fn from_request_parts(_req: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
-        let component = Arc::<M>::from_ref(state).resolve();
+        let component = resolve_component(&*Arc::<M>::from_ref(state));
 
         Ok(Self(component, PhantomData))
     }
 }
 
+impl<M: ModuleInterface + HasComponent<I> + ?Sized, I: Interface + ?Sized> Inject<M, I> {
+    /// Take the component out of the extractor, ex. to move it into a spawned task
+    pub fn into_arc(self) -> Arc<I> {
+        self.0
+    }
+
+    /// The shared component. Clone it to keep the component after the extractor is dropped.
+    pub fn as_arc(&self) -> &Arc<I> {
+        &self.0
+    }
+}
+
 impl<M: ModuleInterface + HasComponent<I> + ?Sized, I: Interface + ?Sized> Deref for Inject<M, I> {
     type Target = I;
 
@@ -1,18 +1,28 @@
+use crate::AxumRequest;
 use axum::async_trait;
 use axum::extract::{FromRef, FromRequestParts};
 use axum::http::request::Parts;
 use axum::http::StatusCode;
 use shaku::{HasProvider, ModuleInterface};
+use shaku_inject_core::{provide_failed, provide_service};
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::sync::Arc;
 
 /// Used to create a provided service from a shaku `Module`.
 /// The module should be stored in Axum state, wrapped in an `Arc` (`Arc<MyModule>`).
-/// This `Arc<MyModule>` must implement `FromRef<S>` where `S` is the Axum state type.
+/// This `Arc<MyModule>` must implement `FromRef<S>` where `S` is the Axum state type, which
+/// [`ShakuState`] does (see [`ShakuRouterExt::with_shaku`]).
 ///
 /// Use this struct as an extractor.
 ///
+/// Axum handlers must be `Send`, so the provided service's interface must also be `Send`
+/// (ex. `trait HelloWorld: Send`).
+///
+/// With the `tracing` feature enabled, the service is provided inside the request's span, which is
+/// also available to providers via `shaku::ProvideContext`. The span is read from the request
+/// extensions (insert a `tracing::Span` via middleware), falling back to the current span.
+///
 /// # Example
 /// ```rust
 /// use axum::{routing::get, Router};
@@ -76,6 +86,9 @@ use std::sync::Arc;
 ///     }
 /// }
 /// ```
+///
+/// [`ShakuState`]: struct.ShakuState.html
+/// [`ShakuRouterExt::with_shaku`]: trait.ShakuRouterExt.html#tymethod.with_shaku
 pub struct InjectProvided<M: ModuleInterface + HasProvider<I> + ?Sized, I: ?Sized>(
     Box<I>,
     PhantomData<M>,
@@ -91,10 +104,14 @@ where
 {
     type Rejection = (StatusCode, String);
 
-    async fn from_request_parts(_req: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
-        let service = Arc::<M>::from_ref(state)
-            .provide()
-            .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    async fn from_request_parts(req: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let service =
+            provide_service(&*Arc::<M>::from_ref(state), &AxumRequest(req)).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    provide_failed::<M, I>(&*e),
+                )
+            })?;
 
         Ok(Self(service, PhantomData))
     }
@@ -1,12 +1,34 @@
 //! This crate provides integration between the `shaku` and `axum` crates.
 //!
-//! See [`Inject`] and [`InjectProvided`] for details.
+//! See [`Inject`] and [`InjectProvided`] for details. To use the extractors without writing any
+//! `FromRef` impls, store the module as the router's state via [`ShakuRouterExt::with_shaku`].
 //!
 //! [`Inject`]: struct.Inject.html
 //! [`InjectProvided`]: struct.InjectProvided.html
+//! [`ShakuRouterExt::with_shaku`]: trait.ShakuRouterExt.html#tymethod.with_shaku
 
+#[cfg(feature = "health")]
+mod health;
 mod inject_component;
 mod inject_provided;
+mod state;
 
+#[cfg(feature = "health")]
+pub use health::health;
 pub use inject_component::Inject;
 pub use inject_provided::InjectProvided;
+pub use state::{ShakuRouterExt, ShakuState};
+
+use axum::http::request::Parts;
+use shaku_inject_core::InjectRequest;
+
+/// Exposes the axum request to the shared extractor logic
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+struct AxumRequest<'a>(&'a Parts);
+
+impl InjectRequest for AxumRequest<'_> {
+    #[cfg(feature = "tracing")]
+    fn span(&self) -> Option<tracing::Span> {
+        self.0.extensions.get::<tracing::Span>().cloned()
+    }
+}
@@ -0,0 +1,85 @@
+use axum::extract::FromRef;
+use axum::Router;
+use shaku::ModuleInterface;
+use std::sync::Arc;
+
+/// Axum state which holds a shaku `Module`. The module's `Arc` implements `FromRef` for this
+/// state, so [`Inject`] and [`InjectProvided`] work without any manual `FromRef` impls.
+///
+/// The state is usually added via [`ShakuRouterExt::with_shaku`].
+///
+/// [`Inject`]: struct.Inject.html
+/// [`InjectProvided`]: struct.InjectProvided.html
+/// [`ShakuRouterExt::with_shaku`]: trait.ShakuRouterExt.html#tymethod.with_shaku
+pub struct ShakuState<M: ModuleInterface + ?Sized>(pub Arc<M>);
+
+impl<M: ModuleInterface + ?Sized> Clone for ShakuState<M> {
+    fn clone(&self) -> Self {
+        ShakuState(Arc::clone(&self.0))
+    }
+}
+
+impl<M: ModuleInterface + ?Sized> From<Arc<M>> for ShakuState<M> {
+    fn from(module: Arc<M>) -> Self {
+        ShakuState(module)
+    }
+}
+
+impl<M: ModuleInterface + ?Sized> FromRef<ShakuState<M>> for Arc<M> {
+    fn from_ref(state: &ShakuState<M>) -> Arc<M> {
+        Arc::clone(&state.0)
+    }
+}
+
+/// Adds a shaku `Module` to a `Router` as its state (see [`ShakuState`]).
+///
+/// # Example
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use shaku::{module, Component, Interface};
+/// use shaku_axum::{Inject, ShakuRouterExt};
+/// use std::sync::Arc;
+///
+/// trait HelloWorld: Interface {
+///     fn greet(&self) -> String;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = HelloWorld)]
+/// struct HelloWorldImpl;
+///
+/// impl HelloWorld for HelloWorldImpl {
+///     fn greet(&self) -> String {
+///         "Hello, world!".to_owned()
+///     }
+/// }
+///
+/// module! {
+///     HelloModule {
+///         components = [HelloWorldImpl],
+///         providers = []
+///     }
+/// }
+///
+/// async fn hello(hello_world: Inject<HelloModule, dyn HelloWorld>) -> String {
+///     hello_world.greet()
+/// }
+///
+/// let module = Arc::new(HelloModule::builder().build());
+/// let app: Router = Router::new()
+///     .route("/", get(hello))
+///     .with_shaku(module);
+/// ```
+///
+/// [`ShakuState`]: struct.ShakuState.html
+pub trait ShakuRouterExt<M: ModuleInterface + ?Sized> {
+    /// Provide the module as the router's state. Like `Router::with_state`, this returns a router
+    /// with any state type, ex. `Router<()>` so it can be served.
+    fn with_shaku<S>(self, module: Arc<M>) -> Router<S>;
+}
+
+impl<M: ModuleInterface + ?Sized> ShakuRouterExt<M> for Router<ShakuState<M>> {
+    fn with_shaku<S>(self, module: Arc<M>) -> Router<S> {
+        self.with_state(ShakuState(module))
+    }
+}
@@ -0,0 +1,86 @@
+//! The health handler responds with the module's health report
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use shaku::extras::{HealthCheck, HealthRegistry, HealthStatus};
+use shaku::{module, Component, Interface};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+trait Database: Interface + HealthCheck {}
+
+#[derive(Component)]
+#[shaku(interface = Database, health_check)]
+struct DatabaseImpl {
+    healthy: bool,
+}
+
+impl Database for DatabaseImpl {}
+
+impl HealthCheck for DatabaseImpl {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    fn check(&self) -> HealthStatus {
+        if self.healthy {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Unhealthy("disconnected".to_string())
+        }
+    }
+}
+
+module! {
+    MyModule {
+        components = [HealthRegistry, DatabaseImpl],
+        providers = []
+    }
+}
+
+fn build_module(healthy: bool) -> MyModule {
+    MyModule::builder()
+        .with_component_parameters::<DatabaseImpl>(DatabaseImplParameters { healthy })
+        .build()
+}
+
+async fn get_health(healthy: bool) -> (StatusCode, String) {
+    let app = Router::new()
+        .route("/health", get(shaku_axum::health::<MyModule>))
+        .with_state(Arc::new(build_module(healthy)));
+
+    let request = Request::builder()
+        .uri("/health")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+/// A healthy module responds with 200 OK
+#[tokio::test]
+async fn healthy() {
+    assert_eq!(
+        get_health(true).await,
+        (StatusCode::OK, "healthy\n- database: healthy".to_string())
+    );
+}
+
+/// An unhealthy module responds with 503 Service Unavailable
+#[tokio::test]
+async fn unhealthy() {
+    assert_eq!(
+        get_health(false).await,
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "unhealthy\n- database: unhealthy (disconnected)".to_string()
+        )
+    );
+}
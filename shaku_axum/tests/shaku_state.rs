@@ -0,0 +1,88 @@
+//! `ShakuRouterExt::with_shaku` stores the module as the router's state, so the extractors work
+//! without any `FromRef` impls.
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
+use shaku_axum::{Inject, InjectProvided, ShakuRouterExt};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+trait MyComponent: Interface {
+    fn name(&self) -> String;
+}
+trait MyProvider: Send {
+    fn name(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = MyComponent)]
+struct MyComponentImpl;
+impl MyComponent for MyComponentImpl {
+    fn name(&self) -> String {
+        "component".to_string()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = MyProvider)]
+struct MyProviderImpl {
+    #[shaku(inject)]
+    component: Arc<dyn MyComponent>,
+}
+impl MyProvider for MyProviderImpl {
+    fn name(&self) -> String {
+        format!("provider using {}", self.component.name())
+    }
+}
+
+trait MyModule: HasComponent<dyn MyComponent> + HasProvider<dyn MyProvider> {}
+
+module! {
+    MyModuleImpl: MyModule {
+        components = [MyComponentImpl],
+        providers = [MyProviderImpl]
+    }
+}
+
+async fn component_name(component: Inject<MyModuleImpl, dyn MyComponent>) -> String {
+    component.name()
+}
+
+async fn provider_name(provider: InjectProvided<dyn MyModule, dyn MyProvider>) -> String {
+    provider.name()
+}
+
+async fn get_body(app: Router, uri: &str) -> String {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+/// A concrete module can be used as the state
+#[tokio::test]
+async fn module_state() {
+    let app = Router::new()
+        .route("/", get(component_name))
+        .with_shaku(Arc::new(MyModuleImpl::builder().build()));
+
+    assert_eq!(get_body(app, "/").await, "component");
+}
+
+/// A module interface can be used as the state
+#[tokio::test]
+async fn module_interface_state() {
+    let module: Arc<dyn MyModule> = Arc::new(MyModuleImpl::builder().build());
+    let app = Router::new()
+        .route("/", get(provider_name))
+        .with_shaku(module);
+
+    assert_eq!(get_body(app, "/").await, "provider using component");
+}
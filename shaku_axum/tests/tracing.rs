@@ -0,0 +1,88 @@
+//! With the `tracing` feature, `InjectProvided` provides services inside the request's span
+
+use axum::extract::FromRequestParts;
+use axum::http::Request;
+use shaku::{module, HasProvider, Module, ProvideContext, Provider};
+use shaku_axum::InjectProvided;
+use std::error::Error;
+use std::sync::Arc;
+use tracing::span::Id;
+use tracing::{Instrument, Span};
+
+trait SpanRecorder {
+    fn context_span(&self) -> Option<Id>;
+    fn current_span(&self) -> Option<Id>;
+}
+
+struct SpanRecorderImpl {
+    context_span: Option<Id>,
+    current_span: Option<Id>,
+}
+
+impl SpanRecorder for SpanRecorderImpl {
+    fn context_span(&self) -> Option<Id> {
+        self.context_span.clone()
+    }
+
+    fn current_span(&self) -> Option<Id> {
+        self.current_span.clone()
+    }
+}
+
+impl<M: Module> Provider<M> for SpanRecorderImpl {
+    type Interface = dyn SpanRecorder;
+
+    fn provide(_: &M) -> Result<Box<dyn SpanRecorder>, Box<dyn Error>> {
+        Ok(Box::new(SpanRecorderImpl {
+            context_span: ProvideContext::current()
+                .and_then(|context| context.get::<Span>().and_then(Span::id)),
+            current_span: Span::current().id(),
+        }))
+    }
+}
+
+trait MyModule: HasProvider<dyn SpanRecorder> {}
+
+module! {
+    MyModuleImpl: MyModule {
+        components = [],
+        providers = [SpanRecorderImpl]
+    }
+}
+
+async fn extract(request: Request<()>) -> InjectProvided<dyn MyModule, dyn SpanRecorder> {
+    let module: Arc<dyn MyModule> = Arc::new(MyModuleImpl::builder().build());
+    let (mut parts, _) = request.into_parts();
+
+    InjectProvided::from_request_parts(&mut parts, &module)
+        .await
+        .unwrap()
+}
+
+/// The span in the request extensions is used
+#[tokio::test]
+async fn span_from_extensions() {
+    let _subscriber = tracing::subscriber::set_default(tracing_subscriber::registry());
+    let span = tracing::info_span!("request");
+    let mut request = Request::new(());
+    request.extensions_mut().insert(span.clone());
+
+    let recorder = extract(request).await;
+
+    assert!(span.id().is_some());
+    assert_eq!(recorder.context_span(), span.id());
+    assert_eq!(recorder.current_span(), span.id());
+}
+
+/// The current span is used if the request extensions don't contain a span
+#[tokio::test]
+async fn current_span_fallback() {
+    let _subscriber = tracing::subscriber::set_default(tracing_subscriber::registry());
+    let span = tracing::info_span!("outer");
+
+    let recorder = extract(Request::new(())).instrument(span.clone()).await;
+
+    assert!(span.id().is_some());
+    assert_eq!(recorder.context_span(), span.id());
+    assert_eq!(recorder.current_span(), span.id());
+}
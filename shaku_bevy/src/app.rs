@@ -0,0 +1,183 @@
+use crate::{ShakuComponent, ShakuModule};
+use bevy_app::{App, PreUpdate};
+use bevy_ecs::change_detection::DetectChanges;
+use bevy_ecs::system::{NonSend, NonSendMut, Res, ResMut};
+use shaku::{HasComponent, Interface, ModuleInterface};
+use std::any::type_name;
+use std::sync::Arc;
+
+/// Adds shaku modules and their components to a Bevy `App` as resources.
+///
+/// Each registered component is resolved when it is registered, and again whenever the module's
+/// [`ShakuModule`] resource is replaced (checked in `PreUpdate`), so systems always see the
+/// components of the current module. The module must be inserted before its components.
+///
+/// Modules which are not `Send + Sync` (ex. when shaku's `thread_safe` feature is off) are stored
+/// as non-send resources via the `non_send` methods, which also store their components as
+/// non-send resources.
+///
+/// # Example
+/// ```rust
+/// use bevy_app::{App, Update};
+/// use bevy_ecs::system::Res;
+/// use shaku::{module, Component, Interface};
+/// use shaku_bevy::{ShakuAppExt, ShakuComponent};
+/// use std::sync::Arc;
+///
+/// trait Scoring: Interface {
+///     fn points(&self, kills: u32) -> u32;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Scoring)]
+/// struct ScoringImpl {
+///     #[shaku(default = 10)]
+///     points_per_kill: u32,
+/// }
+///
+/// impl Scoring for ScoringImpl {
+///     fn points(&self, kills: u32) -> u32 {
+///         kills * self.points_per_kill
+///     }
+/// }
+///
+/// module! {
+///     GameModule {
+///         components = [ScoringImpl],
+///         providers = []
+///     }
+/// }
+///
+/// fn update_score(scoring: Res<ShakuComponent<dyn Scoring>>) {
+///     assert_eq!(scoring.points(3), 30);
+/// }
+///
+/// let mut app = App::new();
+/// app.insert_shaku_module(Arc::new(GameModule::builder().build()))
+///     .insert_shaku_component::<GameModule, dyn Scoring>()
+///     .add_systems(Update, update_score);
+/// app.update();
+/// ```
+///
+/// [`ShakuModule`]: struct.ShakuModule.html
+pub trait ShakuAppExt {
+    /// Insert the module as a [`ShakuModule`] resource
+    ///
+    /// [`ShakuModule`]: struct.ShakuModule.html
+    fn insert_shaku_module<M>(&mut self, module: Arc<M>) -> &mut Self
+    where
+        M: ModuleInterface + Send + Sync + ?Sized;
+
+    /// Insert the component `I` of the module's resource as a [`ShakuComponent`] resource, and
+    /// keep it in sync with the module
+    ///
+    /// [`ShakuComponent`]: struct.ShakuComponent.html
+    fn insert_shaku_component<M, I>(&mut self) -> &mut Self
+    where
+        M: HasComponent<I> + Send + Sync + ?Sized,
+        I: Interface + Send + Sync + ?Sized;
+
+    /// Insert the module as a non-send [`ShakuModule`] resource
+    ///
+    /// [`ShakuModule`]: struct.ShakuModule.html
+    fn insert_non_send_shaku_module<M>(&mut self, module: Arc<M>) -> &mut Self
+    where
+        M: ModuleInterface + ?Sized;
+
+    /// Insert the component `I` of the module's non-send resource as a non-send
+    /// [`ShakuComponent`] resource, and keep it in sync with the module
+    ///
+    /// [`ShakuComponent`]: struct.ShakuComponent.html
+    fn insert_non_send_shaku_component<M, I>(&mut self) -> &mut Self
+    where
+        M: HasComponent<I> + ?Sized,
+        I: Interface + ?Sized;
+}
+
+impl ShakuAppExt for App {
+    fn insert_shaku_module<M>(&mut self, module: Arc<M>) -> &mut Self
+    where
+        M: ModuleInterface + Send + Sync + ?Sized,
+    {
+        self.insert_resource(ShakuModule(module))
+    }
+
+    fn insert_shaku_component<M, I>(&mut self) -> &mut Self
+    where
+        M: HasComponent<I> + Send + Sync + ?Sized,
+        I: Interface + Send + Sync + ?Sized,
+    {
+        let module = self
+            .world()
+            .get_resource::<ShakuModule<M>>()
+            .unwrap_or_else(|| module_missing::<M, I>());
+        let component = ShakuComponent(HasComponent::<I>::resolve(&**module));
+
+        self.insert_resource(component)
+            .add_systems(PreUpdate, sync_component::<M, I>)
+    }
+
+    fn insert_non_send_shaku_module<M>(&mut self, module: Arc<M>) -> &mut Self
+    where
+        M: ModuleInterface + ?Sized,
+    {
+        self.insert_non_send_resource(ShakuModule(module))
+    }
+
+    fn insert_non_send_shaku_component<M, I>(&mut self) -> &mut Self
+    where
+        M: HasComponent<I> + ?Sized,
+        I: Interface + ?Sized,
+    {
+        let module = self
+            .world()
+            .get_non_send_resource::<ShakuModule<M>>()
+            .unwrap_or_else(|| module_missing::<M, I>());
+        let component = ShakuComponent(HasComponent::<I>::resolve(&**module));
+
+        self.insert_non_send_resource(component)
+            .add_systems(PreUpdate, sync_non_send_component::<M, I>)
+    }
+}
+
+fn module_missing<M: ?Sized, I: ?Sized>() -> ! {
+    panic!(
+        "The module {} must be inserted before its component {}",
+        type_name::<M>(),
+        type_name::<I>()
+    )
+}
+
+/// Resolve the component again if the module was replaced
+fn sync_component<M, I>(module: Res<ShakuModule<M>>, mut component: ResMut<ShakuComponent<I>>)
+where
+    M: HasComponent<I> + Send + Sync + ?Sized,
+    I: Interface + Send + Sync + ?Sized,
+{
+    if module.is_changed() {
+        let resolved = HasComponent::<I>::resolve(&**module);
+
+        // Only mark the component as changed if it is a different instance
+        if !Arc::ptr_eq(&component.0, &resolved) {
+            *component = ShakuComponent(resolved);
+        }
+    }
+}
+
+/// Resolve the non-send component again if the module was replaced
+fn sync_non_send_component<M, I>(
+    module: NonSend<ShakuModule<M>>,
+    mut component: NonSendMut<ShakuComponent<I>>,
+) where
+    M: HasComponent<I> + ?Sized,
+    I: Interface + ?Sized,
+{
+    if module.is_changed() {
+        let resolved = HasComponent::<I>::resolve(&**module);
+
+        // Only mark the component as changed if it is a different instance
+        if !Arc::ptr_eq(&component.0, &resolved) {
+            *component = ShakuComponent(resolved);
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! This crate provides integration between the `shaku` and `bevy` crates.
+//!
+//! Insert a module into the Bevy world via [`ShakuAppExt`] (or [`ShakuPlugin`]), along with the
+//! components which systems use. Each component is stored as a [`ShakuComponent`] resource (ex.
+//! `Res<ShakuComponent<dyn Scoring>>`), and is updated when the module's [`ShakuModule`] resource
+//! is replaced, ex. after reloading the game's settings.
+//!
+//! [`ShakuAppExt`]: trait.ShakuAppExt.html
+//! [`ShakuPlugin`]: struct.ShakuPlugin.html
+//! [`ShakuComponent`]: struct.ShakuComponent.html
+//! [`ShakuModule`]: struct.ShakuModule.html
+
+mod app;
+mod plugin;
+mod resources;
+
+pub use app::ShakuAppExt;
+pub use plugin::ShakuPlugin;
+pub use resources::{ShakuComponent, ShakuModule};
@@ -0,0 +1,79 @@
+use crate::ShakuAppExt;
+use bevy_app::{App, Plugin};
+use shaku::{HasComponent, Interface, ModuleInterface};
+use std::sync::Arc;
+
+/// Adds a module and the listed components to the app, via [`ShakuAppExt`]. The module must be
+/// `Send + Sync`; use the `non_send` methods of [`ShakuAppExt`] otherwise.
+///
+/// # Example
+/// ```rust
+/// use bevy_app::App;
+/// use shaku::{module, Component, Interface};
+/// use shaku_bevy::{ShakuComponent, ShakuPlugin};
+/// use std::sync::Arc;
+///
+/// trait Audio: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Audio)]
+/// struct AudioImpl;
+/// impl Audio for AudioImpl {}
+///
+/// module! {
+///     GameModule {
+///         components = [AudioImpl],
+///         providers = []
+///     }
+/// }
+///
+/// let module = Arc::new(GameModule::builder().build());
+/// let mut app = App::new();
+/// app.add_plugins(ShakuPlugin::new(module).with_component::<dyn Audio>());
+///
+/// assert!(app.world().contains_resource::<ShakuComponent<dyn Audio>>());
+/// ```
+///
+/// [`ShakuAppExt`]: trait.ShakuAppExt.html
+pub struct ShakuPlugin<M: ModuleInterface + Send + Sync + ?Sized> {
+    module: Arc<M>,
+    components: Vec<fn(&mut App)>,
+}
+
+impl<M: ModuleInterface + Send + Sync + ?Sized> ShakuPlugin<M> {
+    /// Create a plugin which adds the module as a resource
+    pub fn new(module: Arc<M>) -> Self {
+        ShakuPlugin {
+            module,
+            components: Vec::new(),
+        }
+    }
+
+    /// Also add the component `I` as a resource
+    pub fn with_component<I>(mut self) -> Self
+    where
+        M: HasComponent<I>,
+        I: Interface + Send + Sync + ?Sized,
+    {
+        self.components.push(insert_component::<M, I>);
+        self
+    }
+}
+
+impl<M: ModuleInterface + Send + Sync + ?Sized> Plugin for ShakuPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.insert_shaku_module(Arc::clone(&self.module));
+
+        for insert_component in &self.components {
+            insert_component(app);
+        }
+    }
+}
+
+fn insert_component<M, I>(app: &mut App)
+where
+    M: HasComponent<I> + Send + Sync + ?Sized,
+    I: Interface + Send + Sync + ?Sized,
+{
+    app.insert_shaku_component::<M, I>();
+}
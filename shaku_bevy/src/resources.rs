@@ -0,0 +1,65 @@
+use bevy_ecs::system::Resource;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Holds a shaku module in the Bevy world. It is a resource if the module is `Send + Sync`, and can
+/// otherwise be stored as a non-send resource.
+///
+/// Replacing the resource (ex. with a module built from a reloaded config) updates the
+/// [`ShakuComponent`] resources registered via [`ShakuAppExt`].
+///
+/// [`ShakuComponent`]: struct.ShakuComponent.html
+/// [`ShakuAppExt`]: trait.ShakuAppExt.html
+pub struct ShakuModule<M: ?Sized>(pub Arc<M>);
+
+impl<M: ?Sized + Send + Sync + 'static> Resource for ShakuModule<M> {}
+
+impl<M: ?Sized> Clone for ShakuModule<M> {
+    fn clone(&self) -> Self {
+        ShakuModule(Arc::clone(&self.0))
+    }
+}
+
+impl<M: ?Sized> From<Arc<M>> for ShakuModule<M> {
+    fn from(module: Arc<M>) -> Self {
+        ShakuModule(module)
+    }
+}
+
+impl<M: ?Sized> Deref for ShakuModule<M> {
+    type Target = M;
+
+    fn deref(&self) -> &M {
+        &self.0
+    }
+}
+
+/// A component resolved from a [`ShakuModule`], ex. `Res<ShakuComponent<dyn Logger>>` in a
+/// system. It is a resource if the interface is `Send + Sync`, and can otherwise be used as a
+/// non-send resource (ex. `NonSend<ShakuComponent<dyn Renderer>>`).
+///
+/// [`ShakuModule`]: struct.ShakuModule.html
+pub struct ShakuComponent<I: ?Sized>(pub Arc<I>);
+
+impl<I: ?Sized + Send + Sync + 'static> Resource for ShakuComponent<I> {}
+
+impl<I: ?Sized> ShakuComponent<I> {
+    /// Get a clone of the component's `Arc`, ex. to move it into a task
+    pub fn to_arc(&self) -> Arc<I> {
+        Arc::clone(&self.0)
+    }
+}
+
+impl<I: ?Sized> Clone for ShakuComponent<I> {
+    fn clone(&self) -> Self {
+        ShakuComponent(Arc::clone(&self.0))
+    }
+}
+
+impl<I: ?Sized> Deref for ShakuComponent<I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        &self.0
+    }
+}
@@ -0,0 +1,123 @@
+//! Modules and their components can be stored as Bevy resources
+
+use bevy_app::{App, Update};
+use bevy_ecs::system::{NonSend, Res, ResMut, Resource};
+use shaku::{module, Component, Interface};
+use shaku_bevy::{ShakuAppExt, ShakuComponent, ShakuModule, ShakuPlugin};
+use std::sync::Arc;
+
+trait Scoring: Interface {
+    fn points(&self, kills: u32) -> u32;
+}
+
+#[derive(Component)]
+#[shaku(interface = Scoring)]
+struct ScoringImpl {
+    #[shaku(default = 10)]
+    points_per_kill: u32,
+}
+
+impl Scoring for ScoringImpl {
+    fn points(&self, kills: u32) -> u32 {
+        kills * self.points_per_kill
+    }
+}
+
+module! {
+    GameModule {
+        components = [ScoringImpl],
+        providers = []
+    }
+}
+
+#[derive(Default, Resource)]
+struct Score(u32);
+
+fn update_score(scoring: Res<ShakuComponent<dyn Scoring>>, mut score: ResMut<Score>) {
+    score.0 = scoring.points(3);
+}
+
+fn game_module(points_per_kill: u32) -> Arc<GameModule> {
+    Arc::new(
+        GameModule::builder()
+            .with_component_parameters::<ScoringImpl>(ScoringImplParameters { points_per_kill })
+            .build(),
+    )
+}
+
+/// Systems can use the components of the module
+#[test]
+fn component_resource() {
+    let mut app = App::new();
+    app.init_resource::<Score>()
+        .insert_shaku_module(game_module(10))
+        .insert_shaku_component::<GameModule, dyn Scoring>()
+        .add_systems(Update, update_score);
+
+    app.update();
+    assert_eq!(app.world().resource::<Score>().0, 30);
+}
+
+/// Replacing the module updates its components before the next update
+#[test]
+fn replace_module() {
+    let mut app = App::new();
+    app.init_resource::<Score>()
+        .insert_shaku_module(game_module(10))
+        .insert_shaku_component::<GameModule, dyn Scoring>()
+        .add_systems(Update, update_score);
+    app.update();
+
+    app.insert_shaku_module(game_module(100));
+    app.update();
+    assert_eq!(app.world().resource::<Score>().0, 300);
+
+    let module = app.world().resource::<ShakuModule<GameModule>>();
+    let component = app.world().resource::<ShakuComponent<dyn Scoring>>();
+    assert!(Arc::ptr_eq(
+        &component.to_arc(),
+        &shaku::HasComponent::<dyn Scoring>::resolve(&**module)
+    ));
+}
+
+/// Modules and components can be stored as non-send resources
+#[test]
+fn non_send_resources() {
+    fn update_non_send_score(
+        scoring: NonSend<ShakuComponent<dyn Scoring>>,
+        mut score: ResMut<Score>,
+    ) {
+        score.0 = scoring.points(2);
+    }
+
+    let mut app = App::new();
+    app.init_resource::<Score>()
+        .insert_non_send_shaku_module(game_module(10))
+        .insert_non_send_shaku_component::<GameModule, dyn Scoring>()
+        .add_systems(Update, update_non_send_score);
+    app.update();
+    assert_eq!(app.world().resource::<Score>().0, 20);
+
+    app.insert_non_send_shaku_module(game_module(5));
+    app.update();
+    assert_eq!(app.world().resource::<Score>().0, 10);
+}
+
+/// The plugin adds the module and the listed components
+#[test]
+fn plugin() {
+    let mut app = App::new();
+    app.init_resource::<Score>()
+        .add_plugins(ShakuPlugin::new(game_module(7)).with_component::<dyn Scoring>())
+        .add_systems(Update, update_score);
+
+    app.update();
+    assert_eq!(app.world().resource::<Score>().0, 21);
+}
+
+/// Components can't be inserted before their module
+#[test]
+#[should_panic(expected = "must be inserted before its component")]
+fn component_without_module() {
+    App::new().insert_shaku_component::<GameModule, dyn Scoring>();
+}
@@ -1,6 +1,28 @@
 pub const ATTR_NAME: &str = "shaku";
 pub const INTERFACE_ATTR_NAME: &str = "interface";
 pub const INJECT_ATTR_NAME: &str = "inject";
+pub const INJECT_MODULE_ATTR_NAME: &str = "inject_module";
 pub const PROVIDE_ATTR_NAME: &str = "provide";
 pub const DEFAULT_ATTR_NAME: &str = "default";
+pub const SECRET_ATTR_NAME: &str = "secret";
+pub const FROM_SOURCE_ATTR_NAME: &str = "from_source";
+pub const SUBSCRIBE_ATTR_NAME: &str = "subscribe";
+pub const HEALTH_CHECK_ATTR_NAME: &str = "health_check";
+pub const LIFECYCLE_ATTR_NAME: &str = "lifecycle";
+pub const MOCK_ATTR_NAME: &str = "mock";
+pub const RECONFIGURABLE_ATTR_NAME: &str = "reconfigurable";
+pub const PARAMETERS_DERIVE_ATTR_NAME: &str = "parameters_derive";
+pub const THREAD_BOUND_ATTR_NAME: &str = "thread_bound";
+pub const BUILD_AFTER_ATTR_NAME: &str = "build_after";
+pub const DELEGATE_ATTR_NAME: &str = "delegate";
+pub const ADAPTER_ATTR_NAME: &str = "adapter";
+pub const RETRY_ATTR_NAME: &str = "retry";
+pub const RETRY_TIMES_ATTR_NAME: &str = "times";
+pub const RETRY_BACKOFF_ATTR_NAME: &str = "backoff_ms";
+pub const DERIVE_CLONE_ATTR_NAME: &str = "derive_clone";
+pub const EXTENDABLE_ATTR_NAME: &str = "extendable";
+pub const STRICT_BUILDER_ATTR_NAME: &str = "strict_builder";
+pub const DENY_UNUSED_ATTR_NAME: &str = "deny_unused";
+pub const LAST_WINS_ATTR_NAME: &str = "last_wins";
+pub const MEMORY_FOOTPRINT_ATTR_NAME: &str = "memory_footprint";
 pub const DEBUG_ENV_VAR: &str = "SHAKU_CODEGEN_DEBUG";
@@ -4,7 +4,7 @@ extern crate proc_macro;
 #[macro_use]
 extern crate quote;
 
-use crate::structures::module::ModuleData;
+use crate::structures::module::{ModuleData, ModuleInterfaceData};
 use proc_macro::TokenStream;
 
 mod consts;
@@ -37,12 +37,27 @@ pub fn provider(input: TokenStream) -> TokenStream {
 /// A `fn builder(submodules...) -> ModuleBuilder<Self>` associated function will be created to make
 /// instantiating the module convenient. The arguments are the submodules the module uses.
 ///
+/// ## Module services
+/// The module will also implement [`ModuleServices`], which lists the components and providers
+/// exposed by the module (including those imported from submodules).
+///
 /// ## Module interfaces
 /// After the module name, you can add `: MyModuleInterface` where `MyModuleInterface` is the trait
 /// that you want this module to implement (ex. `trait MyModuleInterface: HasComponent<MyComponent> {}`).
 /// The macro will implement this trait for the module automatically. That is, it is the same as
 /// manually adding the line: `impl MyModuleInterface for MyModule {}`. See `MyModuleImpl` in the
-/// example below. See also [`ModuleInterface`].
+/// example below. See also [`ModuleInterface`]. Module interface traits can be declared with the
+/// [`module_interface`] macro.
+///
+/// Module interfaces can also require `HasProvider` (ex.
+/// `trait MyModuleInterface: HasComponent<dyn MyComponent> + HasProvider<dyn MyProvider> {}`), and
+/// can be used as trait objects (ex. `Arc<dyn MyModuleInterface>`) to choose the module
+/// implementation at runtime. The web framework integrations accept module trait objects too, ex.
+/// `InjectProvided<dyn MyModuleInterface, dyn MyProvider>`.
+///
+/// The module also gets `as_module_interface(&self) -> &dyn MyModuleInterface` and
+/// `into_module_interface(self) -> Arc<dyn MyModuleInterface>` methods. Modules without generics
+/// can also be converted via `Into`, ex. `let module: Box<dyn MyModuleInterface> = module.into()`.
 ///
 /// ## Submodules
 /// A module can use components/providers from other modules by explicitly listing the interfaces
@@ -51,6 +66,63 @@ pub fn provider(input: TokenStream) -> TokenStream {
 ///
 /// See also the [submodules getting started guide].
 ///
+/// ## Extending modules
+/// A module annotated with `#[shaku(extendable)]` can be extended by other modules, including in
+/// other crates, via `extends` (ex. `ExtendedModule extends other_crate::BaseModule`). The
+/// extending module uses the extended module as a hidden submodule, which is passed in as the first
+/// argument of `builder`, and re-exports all of its services (including those imported from its
+/// submodules). This allows adding services to a module without changing its definition.
+///
+/// ```rust
+/// use shaku::{module, Component, HasComponent, Interface};
+/// use std::sync::Arc;
+///
+/// trait Logger: Interface {}
+/// trait Greeter: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Logger)]
+/// struct LoggerImpl;
+/// impl Logger for LoggerImpl {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Greeter)]
+/// struct GreeterImpl {
+///     #[shaku(inject)]
+///     logger: Arc<dyn Logger>,
+/// }
+/// impl Greeter for GreeterImpl {}
+///
+/// module! {
+///     #[shaku(extendable)]
+///     BaseModule {
+///         components = [LoggerImpl],
+///         providers = []
+///     }
+/// }
+///
+/// // ExtendedModule implements HasComponent<dyn Logger> and HasComponent<dyn Greeter>
+/// module! {
+///     ExtendedModule extends BaseModule {
+///         components = [GreeterImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let base_module = Arc::new(BaseModule::builder().build());
+/// let module = ExtendedModule::builder(base_module).build();
+/// let logger: &dyn Logger = module.resolve_ref();
+/// # }
+/// ```
+///
+/// `#[shaku(extendable)]` generates a hidden exported macro named after the module, so extendable
+/// modules in the same crate need unique names. A module in the same crate must be extended after
+/// it is declared (like a `macro_rules` macro), and a module in another crate must be referenced by
+/// its path from that crate (ex. `other_crate::BaseModule`). The extended module's components and
+/// providers must be as visible as the module. The `extends` clause comes before the `where` clause
+/// and module interface, ex. `ExtendedModule extends BaseModule: MyModuleInterface`.
+///
 /// ## Generics
 /// This macro supports generics at the module level:
 /// ```rust
@@ -107,6 +179,369 @@ pub fn provider(input: TokenStream) -> TokenStream {
 /// # fn main() {}
 /// ```
 ///
+/// ## Deprecated Components
+/// Components can be marked as deprecated with `#[deprecated]` or `#[deprecated = "note"]` in the
+/// module declaration, ex. an adapter for an old version of an interface during a migration. The
+/// first time the component is resolved, the handler set via `shaku::set_deprecation_handler` is
+/// called (nothing is reported until a handler is set). Injecting the component into other
+/// components is not reported.
+///
+/// ```rust
+/// use shaku::{module, Component, Interface};
+///
+/// trait ServiceV1: Interface {}
+/// trait ServiceV2: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = ServiceV2, adapter(ServiceV1 as ServiceV1Adapter))]
+/// struct ServiceImpl;
+/// impl ServiceV2 for ServiceImpl {}
+/// impl ServiceV1 for ServiceV1Adapter {}
+///
+/// module! {
+///     MyModule {
+///         components = [ServiceImpl, #[deprecated = "use ServiceV2"] ServiceV1Adapter],
+///         providers = []
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ## Instrumented components
+/// A component marked with `#[instrument]` is wrapped in the tracing wrapper of its interface,
+/// which is generated by the [`instrument_interface`] attribute (this requires the `instrument`
+/// feature). Its method calls are then traced, including those of the components it is injected
+/// into. See `shaku::Instrumented`.
+///
+/// ## Components as providers
+/// A component marked with `#[as_provider]` is also available via `HasProvider`, which provides
+/// the shared component instead of creating a new service each time. This helps when migrating a
+/// service from a component to a provider, since the callers can switch to `provide` before the
+/// service does. The interface must be implemented for `Arc<dyn Interface>`, usually by forwarding
+/// to the inner value.
+///
+/// ```rust
+/// use shaku::{module, Component, HasProvider, Interface};
+/// use std::sync::Arc;
+///
+/// trait Service: Interface {
+///     fn name(&self) -> String;
+/// }
+///
+/// impl<T: Service + ?Sized> Service for Arc<T> {
+///     fn name(&self) -> String {
+///         T::name(self)
+///     }
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Service)]
+/// struct ServiceImpl;
+/// impl Service for ServiceImpl {
+///     fn name(&self) -> String {
+///         "service".to_string()
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [#[as_provider] ServiceImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let service: Box<dyn Service> = module.provide().unwrap();
+/// assert_eq!(service.name(), "service");
+/// # }
+/// ```
+///
+/// ## Providers as components
+/// The reverse also works: a provider marked with `#[promote_to_component]` is also available via
+/// `HasComponent`. The provider is called once when the module is built, and the service is shared
+/// from then on. This helps when migrating providers which are actually stateless and shareable.
+/// Since the provider needs the finished module, the service can be injected into providers but not
+/// into components.
+///
+/// ```rust
+/// use shaku::{module, HasComponent, Interface, Provider};
+/// use std::sync::Arc;
+///
+/// trait Formatter: Interface {
+///     fn format(&self, value: usize) -> String;
+/// }
+///
+/// #[derive(Provider)]
+/// #[shaku(interface = Formatter)]
+/// struct FormatterImpl;
+/// impl Formatter for FormatterImpl {
+///     fn format(&self, value: usize) -> String {
+///         format!("#{}", value)
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [],
+///         providers = [#[promote_to_component] FormatterImpl]
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let formatter: Arc<dyn Formatter> = module.resolve();
+/// assert_eq!(formatter.format(1), "#1");
+/// # }
+/// ```
+///
+/// ## Named services
+/// The module stores each component and provider in a field with a generated name (ex.
+/// `__di_component_3`), which shows up in debuggers and compiler errors. Annotate a component or
+/// provider with `#[name = "field_name"]` to use that name instead. The name is also available at
+/// runtime via `ServiceDescriptor::name` (see `shaku::ModuleServices`).
+///
+/// ```rust
+/// use shaku::{module, Component, Interface, ModuleServices};
+///
+/// trait Database: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Database)]
+/// struct PostgresDatabase;
+/// impl Database for PostgresDatabase {}
+///
+/// module! {
+///     MyModule {
+///         components = [#[name = "primary_database"] PostgresDatabase],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let services = MyModule::services();
+/// let database = services.iter().next().unwrap();
+/// assert_eq!(database.name(), Some("primary_database"));
+/// # }
+/// ```
+///
+/// ## Documented wiring
+/// The generated `HasComponent` and `HasProvider` impls are documented with the component,
+/// provider, or submodule which the service comes from, so `cargo doc` shows the effective wiring
+/// of the module. Doc comments on a component or provider are added to the docs of its impl.
+///
+/// ```rust
+/// use shaku::{module, Component, Interface};
+///
+/// trait Database: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Database)]
+/// struct PostgresDatabase;
+/// impl Database for PostgresDatabase {}
+///
+/// module! {
+///     MyModule {
+///         components = [
+///             /// The primary database, configured by `DATABASE_URL`
+///             PostgresDatabase
+///         ],
+///         providers = []
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ## Conditional services
+/// Components and providers can be registered conditionally with `#[cfg(...)]`. To reduce the
+/// noise when several services depend on cargo features, they can be grouped by feature, ex.
+/// `"redis" => [RedisCache, RedisLock]`, which is the same as annotating each of them with
+/// `#[cfg(feature = "redis")]`. Modules with `#[shaku(strict_builder)]` or
+/// `#[shaku(extendable)]` cannot have conditional services.
+///
+/// ```rust
+/// use shaku::{module, Component, Interface};
+///
+/// trait Cache: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Cache)]
+/// struct RedisCache;
+/// impl Cache for RedisCache {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Cache)]
+/// struct InMemoryCache;
+/// impl Cache for InMemoryCache {}
+///
+/// module! {
+///     MyModule {
+///         components = [
+///             "redis" => [RedisCache],
+///             #[cfg(not(feature = "redis"))] InMemoryCache,
+///         ],
+///         providers = []
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ## Clone
+/// Annotate the module with `#[shaku(derive_clone)]` to implement `Clone`. Cloning the module is
+/// cheap, since the components, providers, and submodules are shared via `Arc`. Modules with lazy
+/// components cannot implement `Clone`.
+///
+/// ```rust
+/// use shaku::{module, Component, Interface};
+///
+/// trait Service: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Service)]
+/// struct ServiceImpl;
+/// impl Service for ServiceImpl {}
+///
+/// module! {
+///     #[shaku(derive_clone)]
+///     MyModule {
+///         components = [ServiceImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let cloned_module = module.clone();
+/// # }
+/// ```
+///
+/// ## Strict builder
+/// Annotate the module with `#[shaku(strict_builder)]` to generate a `strict_builder` function
+/// alongside `builder`. The strict builder only allows building the module once the parameters of
+/// every component with parameters that have no default have been set, so forgetting them is a
+/// compile time error instead of a panic. See `shaku::StrictModuleBuilder`.
+///
+/// ## Memory footprint
+/// Annotate the module with `#[shaku(memory_footprint)]` to implement
+/// `shaku::ModuleMemoryFootprint`, which estimates the memory held by the module's components. See
+/// `shaku::MemoryFootprint` for an example.
+///
+/// ## Unused services
+/// Modules with a module interface (ex. `MyModuleImpl: MyModule`) get an `unreachable_services`
+/// function, which lists the services that are neither exported by the module interface nor
+/// injected into a reachable service. These are likely dead wiring. Annotate the module with
+/// `#[shaku(deny_unused)]` to panic with the list when building the module instead.
+///
+/// ```rust,should_panic
+/// use shaku::{module, Component, HasComponent, Interface};
+///
+/// trait Service: Interface {}
+/// trait Metrics: Interface {}
+/// trait MyModule: HasComponent<dyn Service> {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Service)]
+/// struct ServiceImpl;
+/// impl Service for ServiceImpl {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Metrics)]
+/// struct MetricsImpl;
+/// impl Metrics for MetricsImpl {}
+///
+/// module! {
+///     #[shaku(deny_unused)]
+///     MyModuleImpl: MyModule {
+///         components = [ServiceImpl, MetricsImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// // Panics, since nothing uses the metrics component
+/// let module = MyModuleImpl::builder().build();
+/// # }
+/// ```
+///
+/// ## Superseded services
+/// A service which is registered more than once (ex. listed twice, or imported from two
+/// submodules) is a compile error. For generated registration lists which may contain duplicates,
+/// annotate the module with `#[shaku(last_wins)]` to use the last registration instead. The earlier
+/// registrations are dropped, and reported to the handler set via `shaku::set_superseded_handler`
+/// the first time the module is built. The handler can, for example, log a warning or panic in
+/// tests. Nothing is reported until a handler is set.
+/// Like the duplicate check, this compares the services as written, so two different components
+/// with the same interface still conflict.
+///
+/// ```rust
+/// use shaku::{module, Component, HasComponent, Interface};
+///
+/// trait Config: Interface {
+///     fn name(&self) -> &str;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Config)]
+/// struct DefaultConfig;
+/// impl Config for DefaultConfig {
+///     fn name(&self) -> &str {
+///         "default"
+///     }
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Config)]
+/// struct LocalConfig;
+/// impl Config for LocalConfig {
+///     fn name(&self) -> &str {
+///         "local"
+///     }
+/// }
+///
+/// module! {
+///     DefaultModule {
+///         components = [DefaultConfig],
+///         providers = []
+///     }
+/// }
+///
+/// module! {
+///     LocalModule {
+///         components = [LocalConfig],
+///         providers = []
+///     }
+/// }
+///
+/// module! {
+///     #[shaku(last_wins)]
+///     AppModule {
+///         components = [],
+///         providers = [],
+///
+///         use DefaultModule {
+///             components = [dyn Config],
+///             providers = []
+///         },
+///
+///         use LocalModule {
+///             components = [dyn Config],
+///             providers = []
+///         }
+///     }
+/// }
+///
+/// # fn main() {
+/// # use std::sync::Arc;
+/// let module = AppModule::builder(
+///     Arc::new(DefaultModule::builder().build()),
+///     Arc::new(LocalModule::builder().build()),
+/// )
+/// .build();
+/// let config: &dyn Config = module.resolve_ref();
+/// assert_eq!(config.name(), "local");
+/// # }
+/// ```
+///
 /// # Examples
 /// ```
 /// use shaku::{module, Component, Interface, HasComponent};
@@ -145,12 +580,227 @@ pub fn provider(input: TokenStream) -> TokenStream {
 ///
 /// [`Module`]: trait.Module.html
 /// [`ModuleInterface`]: trait.ModuleInterface.html
+/// [`module_interface`]: macro.module_interface.html
+/// [`instrument_interface`]: attr.instrument_interface.html
+/// [`ModuleServices`]: trait.ModuleServices.html
 /// [submodules getting started guide]: guide/submodules/index.html
 #[proc_macro]
 pub fn module(input: TokenStream) -> TokenStream {
+    let unparsed_input = input.clone().into();
     let module = syn::parse_macro_input!(input as ModuleData);
 
-    macros::module::expand_module_macro(module)
+    macros::module::expand_module_macro(module, unparsed_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Declare a module interface trait which requires the listed components and providers. This is
+/// the same as writing the `HasComponent`/`HasProvider` supertraits by hand, and is useful for
+/// splitting an application across crates: the interface crate declares the services and the module
+/// interface, and the implementation crate implements them via [`module`].
+///
+/// ```rust
+/// use shaku::{module, module_interface, Component, HasComponent, Interface};
+///
+/// trait Logger: Interface {}
+///
+/// // Expands to `pub trait LoggingModule: HasComponent<dyn Logger> {}`
+/// module_interface! {
+///     pub trait LoggingModule {
+///         components = [dyn Logger],
+///         providers = []
+///     }
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Logger)]
+/// struct LoggerImpl;
+/// impl Logger for LoggerImpl {}
+///
+/// module! {
+///     LoggingModuleImpl: LoggingModule {
+///         components = [LoggerImpl],
+///         providers = []
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The generated trait's documentation lists the required services (after any doc comments on the
+/// trait). Generics and where clauses are supported, ex.
+/// `pub trait MyModule<T> where T: Interface { ... }`. The services are listed the same way as in a
+/// [`module`]'s submodule, so the components and providers cannot have attributes.
+///
+/// See also the [multiple crates guide].
+///
+/// [`module`]: macro.module.html
+/// [multiple crates guide]: guide/multiple_crates/index.html
+#[proc_macro]
+pub fn module_interface(input: TokenStream) -> TokenStream {
+    let interface = syn::parse_macro_input!(input as ModuleInterfaceData);
+
+    macros::module_interface::expand_module_interface_macro(interface)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Allow components implementing the trait to be created from closures, via
+/// [`ModuleBuilder::with_component_closure`]. The trait must have a single method without a default
+/// implementation, which takes `&self`. Calling the method calls the closure with the method's
+/// arguments.
+///
+/// A `<Trait>Closure<F>` struct is generated which implements the trait, along with an
+/// implementation of [`FromClosure`] for `dyn Trait`.
+///
+/// # Example
+/// ```
+/// use shaku::{closure_interface, Interface};
+///
+/// #[closure_interface]
+/// trait Formatter: Interface {
+///     fn format(&self, value: u32) -> String;
+///
+///     fn format_twice(&self, value: u32) -> String {
+///         format!("{}{}", self.format(value), self.format(value))
+///     }
+/// }
+///
+/// # fn main() {
+/// let formatter = FormatterClosure(|value: u32| value.to_string());
+/// assert_eq!(formatter.format_twice(4), "44");
+/// # }
+/// ```
+///
+/// [`ModuleBuilder::with_component_closure`]: struct.ModuleBuilder.html#method.with_component_closure
+/// [`FromClosure`]: trait.FromClosure.html
+#[proc_macro_attribute]
+pub fn closure_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "closure_interface does not take any arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let item = syn::parse_macro_input!(item as syn::ItemTrait);
+
+    // Keep the trait on errors, so its uses don't cause more errors
+    macros::closure_interface::expand_closure_interface(&item)
+        .unwrap_or_else(|e| {
+            let mut output = e.to_compile_error();
+            quote::ToTokens::to_tokens(&item, &mut output);
+            output
+        })
+        .into()
+}
+
+/// Generate a wrapper for the trait which traces each method call with [tracing], and forwards it
+/// to an inner implementation. This requires the `instrument` feature of shaku.
+///
+/// An `Instrumented<Trait>` struct is generated which holds an `Arc<dyn Trait>`, along with an
+/// implementation of [`Instrument`] for `dyn Trait`. Each call runs in a `TRACE` span named after
+/// the method (with an `interface` field), which contains an `enter` and an `exit` event. Async
+/// methods (see [`async_interface!`]) are traced until they return their future. Mark a component
+/// with `#[instrument]` in a [`module`] to install the wrapper around it.
+///
+/// The methods must take `&self`, and the trait cannot be generic or have associated types.
+/// Methods with `where Self: Sized` are not forwarded. Other supertraits than `Interface` must be
+/// implemented for the wrapper by hand.
+///
+/// See [`Instrumented`] for an example.
+///
+/// [tracing]: https://crates.io/crates/tracing
+/// [`Instrument`]: trait.Instrument.html
+/// [`Instrumented`]: struct.Instrumented.html
+/// [`async_interface!`]: macro.async_interface.html
+/// [`module`]: macro.module.html
+#[proc_macro_attribute]
+pub fn instrument_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "instrument_interface does not take any arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let item = syn::parse_macro_input!(item as syn::ItemTrait);
+
+    // Keep the trait on errors, so its uses don't cause more errors
+    macros::instrument_interface::expand_instrument_interface(&item)
+        .unwrap_or_else(|e| {
+            let mut output = e.to_compile_error();
+            quote::ToTokens::to_tokens(&item, &mut output);
+            output
+        })
+        .into()
+}
+
+/// Declare an interface trait with `async` methods, and implement it. Each `async fn` is turned
+/// into a method which returns a boxed future ([`BoxFuture`]), so the trait can still be used as a
+/// trait object (ex. `Arc<dyn Trait>`) and injected into components. When the `thread_safe` feature
+/// is on, the futures are `Send`.
+///
+/// The macro accepts traits and impl blocks, and must wrap both the trait and its implementations.
+/// Async methods must take `&self` or `&mut self`. The future borrows `self` and the method's
+/// arguments, so elided lifetimes in the argument types must be either references (ex. `&str`) or
+/// written as `'_` (ex. `Cow<'_, str>`).
+///
+/// # Example
+/// ```
+/// use shaku::{async_interface, module, Component, HasComponent, Interface};
+/// use std::sync::Arc;
+///
+/// async_interface! {
+///     trait UserRepository: Interface {
+///         async fn name(&self, id: u32) -> Option<String>;
+///
+///         async fn greeting(&self, id: u32) -> String {
+///             match self.name(id).await {
+///                 Some(name) => format!("Hello, {}!", name),
+///                 None => "Hello, stranger!".to_string(),
+///             }
+///         }
+///     }
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = UserRepository)]
+/// struct InMemoryUserRepository;
+///
+/// async_interface! {
+///     impl UserRepository for InMemoryUserRepository {
+///         async fn name(&self, id: u32) -> Option<String> {
+///             if id == 1 {
+///                 Some("Alice".to_string())
+///             } else {
+///                 None
+///             }
+///         }
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [InMemoryUserRepository],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let repository: Arc<dyn UserRepository> = module.resolve();
+/// let greeting = repository.greeting(1); // A future, which can be awaited
+/// # }
+/// ```
+///
+/// [`BoxFuture`]: type.BoxFuture.html
+#[proc_macro]
+pub fn async_interface(input: TokenStream) -> TokenStream {
+    macros::async_interface::expand_async_interface(input.into())
         .unwrap_or_else(|e| e.to_compile_error())
         .into()
 }
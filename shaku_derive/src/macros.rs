@@ -1,6 +1,10 @@
 //! Implementations of the proc macros
 
+pub mod async_interface;
+pub mod closure_interface;
 mod common_output;
 pub mod component;
+pub mod instrument_interface;
 pub mod module;
+pub mod module_interface;
 pub mod provider;
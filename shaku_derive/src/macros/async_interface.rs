@@ -0,0 +1,202 @@
+//! Implementation of the `async_interface!` macro
+
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::parse::{ParseStream, Parser};
+use syn::spanned::Spanned;
+use syn::{
+    Block, Error, FnArg, GenericArgument, GenericParam, ImplItem, Item, Lifetime, LifetimeDef, Pat,
+    PatIdent, PathArguments, ReturnType, Signature, Stmt, TraitItem, Type,
+};
+
+pub fn expand_async_interface(input: TokenStream) -> syn::Result<TokenStream> {
+    let parser = |input: ParseStream| {
+        let mut items = Vec::new();
+        while !input.is_empty() {
+            items.push(input.parse::<Item>()?);
+        }
+        Ok(items)
+    };
+    let mut items = parser.parse2(input)?;
+
+    for item in &mut items {
+        match item {
+            Item::Trait(item_trait) => {
+                for trait_item in &mut item_trait.items {
+                    if let TraitItem::Method(method) = trait_item {
+                        if method.sig.asyncness.is_some() {
+                            expand_async_method(&mut method.sig, method.default.as_mut())?;
+                        }
+                    }
+                }
+            }
+            Item::Impl(item_impl) => {
+                for impl_item in &mut item_impl.items {
+                    if let ImplItem::Method(method) = impl_item {
+                        if method.sig.asyncness.is_some() {
+                            expand_async_method(&mut method.sig, Some(&mut method.block))?;
+                        }
+                    }
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    item.span(),
+                    "async_interface! only accepts traits and impl blocks",
+                ))
+            }
+        }
+    }
+
+    let mut output = TokenStream::new();
+    for item in items {
+        item.to_tokens(&mut output);
+    }
+
+    Ok(output)
+}
+
+/// Turn `async fn method(&self, ...) -> T` into
+/// `fn method<'async_interface>(&'async_interface self, ...) -> BoxFuture<'async_interface, T>`.
+/// The trait and impl blocks are expanded the same way, so their signatures match.
+fn expand_async_method(sig: &mut Signature, block: Option<&mut Block>) -> syn::Result<()> {
+    let lifetime = Lifetime::new("'async_interface", Span::call_site());
+
+    // The future holds on to everything the method borrows
+    let mut bounds: Vec<TokenStream> = Vec::new();
+    for param in &sig.generics.params {
+        match param {
+            GenericParam::Lifetime(lifetime_def) => {
+                let param_lifetime = &lifetime_def.lifetime;
+                bounds.push(quote! { #param_lifetime: #lifetime });
+            }
+            GenericParam::Type(type_param) => {
+                let ident = &type_param.ident;
+                bounds.push(quote! { #ident: #lifetime });
+            }
+            GenericParam::Const(_) => {}
+        }
+    }
+    let where_clause = sig.generics.make_where_clause();
+    for bound in bounds {
+        where_clause.predicates.push(syn::parse2(bound)?);
+    }
+    sig.generics.params.insert(
+        0,
+        GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())),
+    );
+
+    let span = sig.span();
+    match sig.inputs.iter_mut().next() {
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() => {
+            let (_, receiver_lifetime) = receiver.reference.as_mut().unwrap();
+            if receiver_lifetime.is_none() {
+                *receiver_lifetime = Some(lifetime.clone());
+            }
+        }
+        _ => {
+            return Err(Error::new(
+                span,
+                "Async interface methods must take `&self` or `&mut self`",
+            ))
+        }
+    }
+
+    // Move the arguments into the future, like an `async fn` does
+    let mut bindings: Vec<TokenStream> = Vec::new();
+    for (i, input) in sig.inputs.iter_mut().skip(1).enumerate() {
+        if let FnArg::Typed(pat_type) = input {
+            name_elided_lifetimes(&mut pat_type.ty, &lifetime);
+
+            match &mut *pat_type.pat {
+                Pat::Ident(pat_ident)
+                    if pat_ident.by_ref.is_none() && pat_ident.subpat.is_none() =>
+                {
+                    let mutability = pat_ident.mutability.take();
+                    let ident = &pat_ident.ident;
+                    bindings.push(quote! { let #mutability #ident = #ident; });
+                }
+                pat => {
+                    let ident = format_ident!("__shaku_arg_{}", i);
+                    bindings.push(quote! { let #pat = #ident; });
+                    *pat = Pat::Ident(PatIdent {
+                        attrs: Vec::new(),
+                        by_ref: None,
+                        mutability: None,
+                        ident,
+                        subpat: None,
+                    });
+                }
+            }
+        }
+    }
+
+    let output = match &mut sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => {
+            name_elided_lifetimes(ty, &lifetime);
+            ty.to_token_stream()
+        }
+    };
+    sig.output = syn::parse2(quote! { -> ::shaku::BoxFuture<#lifetime, #output> })?;
+    sig.asyncness = None;
+
+    if let Some(block) = block {
+        let stmts: Vec<Stmt> = block.stmts.drain(..).collect();
+        *block = syn::parse2(quote! {
+            {
+                ::shaku::__alloc::Box::pin(async move {
+                    #(#bindings)*
+                    let __shaku_output: #output = { #(#stmts)* };
+                    #[allow(unreachable_code)]
+                    __shaku_output
+                })
+            }
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Name the elided lifetimes of references and `'_` (ex. `&str` or `Cow<'_, str>`), so that they
+/// outlive the future. Lifetimes which are elided without `'_` (ex. `Cow<str>`) are not found.
+fn name_elided_lifetimes(ty: &mut Type, lifetime: &Lifetime) {
+    match ty {
+        Type::Reference(reference) => {
+            match &reference.lifetime {
+                Some(existing) if existing.ident != "_" => {}
+                _ => reference.lifetime = Some(lifetime.clone()),
+            }
+            name_elided_lifetimes(&mut reference.elem, lifetime);
+        }
+        Type::Path(type_path) => {
+            if let Some(qself) = &mut type_path.qself {
+                name_elided_lifetimes(&mut qself.ty, lifetime);
+            }
+
+            for segment in &mut type_path.path.segments {
+                if let PathArguments::AngleBracketed(arguments) = &mut segment.arguments {
+                    for argument in &mut arguments.args {
+                        match argument {
+                            GenericArgument::Lifetime(existing) if existing.ident == "_" => {
+                                *existing = lifetime.clone();
+                            }
+                            GenericArgument::Type(ty) => name_elided_lifetimes(ty, lifetime),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+        Type::Slice(slice) => name_elided_lifetimes(&mut slice.elem, lifetime),
+        Type::Array(array) => name_elided_lifetimes(&mut array.elem, lifetime),
+        Type::Paren(paren) => name_elided_lifetimes(&mut paren.elem, lifetime),
+        Type::Group(group) => name_elided_lifetimes(&mut group.elem, lifetime),
+        Type::Tuple(tuple) => {
+            for elem in &mut tuple.elems {
+                name_elided_lifetimes(elem, lifetime);
+            }
+        }
+        _ => {}
+    }
+}
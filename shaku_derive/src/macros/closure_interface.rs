@@ -0,0 +1,118 @@
+//! Implementation of the `#[closure_interface]` attribute macro
+
+use proc_macro2::{Span, TokenStream};
+use syn::spanned::Spanned;
+use syn::{Error, FnArg, Ident, ItemTrait, Pat, PatIdent, TraitItem, TraitItemMethod};
+
+pub fn expand_closure_interface(item: &ItemTrait) -> syn::Result<TokenStream> {
+    if !item.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &item.generics,
+            "Closure interfaces cannot be generic",
+        ));
+    }
+
+    let method = find_closure_method(item)?;
+
+    if !method.sig.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &method.sig.generics,
+            "The method of a closure interface cannot be generic",
+        ));
+    }
+
+    let mut inputs = method.sig.inputs.iter();
+    match inputs.next() {
+        Some(FnArg::Receiver(receiver))
+            if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+        _ => {
+            return Err(Error::new_spanned(
+                &method.sig,
+                "The method of a closure interface must take `&self`",
+            ))
+        }
+    }
+
+    // The arguments are renamed, since the closure is called with them
+    let mut signature = method.sig.clone();
+    let mut argument_names = Vec::new();
+    let mut argument_types = Vec::new();
+    for (i, input) in signature.inputs.iter_mut().skip(1).enumerate() {
+        if let FnArg::Typed(pat_type) = input {
+            let name = Ident::new(&format!("__shaku_arg_{}", i), Span::call_site());
+            pat_type.pat = Box::new(Pat::Ident(PatIdent {
+                attrs: Vec::new(),
+                by_ref: None,
+                mutability: None,
+                ident: name.clone(),
+                subpat: None,
+            }));
+            argument_names.push(name);
+            argument_types.push(pat_type.ty.clone());
+        }
+    }
+
+    let trait_name = &item.ident;
+    let visibility = &item.vis;
+    let closure_name = format_ident!("{}Closure", trait_name);
+    let closure_doc = format!(
+        " Implements `{}` via a closure. Created by `ModuleBuilder::with_component_closure`.",
+        trait_name
+    );
+    let output = &method.sig.output;
+    let closure_bound = quote! {
+        F: Fn(#(#argument_types),*) #output + ::shaku::Interface
+    };
+
+    Ok(quote! {
+        #item
+
+        #[doc = #closure_doc]
+        #visibility struct #closure_name<F>(F);
+
+        impl<F> #trait_name for #closure_name<F> where #closure_bound {
+            #signature {
+                (self.0)(#(#argument_names),*)
+            }
+        }
+
+        impl<F> ::shaku::FromClosure<F> for dyn #trait_name where #closure_bound {
+            fn from_closure(closure: F) -> ::shaku::__alloc::Box<Self> {
+                ::shaku::__alloc::Box::new(#closure_name(closure))
+            }
+        }
+    })
+}
+
+/// The closure implements the only method without a default implementation
+fn find_closure_method(item: &ItemTrait) -> syn::Result<&TraitItemMethod> {
+    let mut required_methods = item.items.iter().filter_map(|trait_item| match trait_item {
+        TraitItem::Method(method) if method.default.is_none() => Some(method),
+        _ => None,
+    });
+
+    let method = required_methods.next().ok_or_else(|| {
+        Error::new(
+            item.ident.span(),
+            "Closure interfaces must have a method without a default implementation",
+        )
+    })?;
+
+    if let Some(other) = required_methods.next() {
+        return Err(Error::new(
+            other.sig.span(),
+            "Closure interfaces can only have one method without a default implementation",
+        ));
+    }
+
+    for trait_item in &item.items {
+        if let TraitItem::Type(associated_type) = trait_item {
+            return Err(Error::new_spanned(
+                associated_type,
+                "Closure interfaces cannot have associated types",
+            ));
+        }
+    }
+
+    Ok(method)
+}
@@ -2,17 +2,76 @@
 
 use crate::structures::service::{Property, PropertyType};
 use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+use syn::{Generics, Ident, Type};
 
 pub fn create_dependency(property: &Property) -> Option<TokenStream> {
     let property_ty = &property.ty;
 
     match property.property_type {
-        PropertyType::Parameter => None,
+        PropertyType::Parameter | PropertyType::Source => None,
         PropertyType::Component => Some(quote! {
             ::shaku::HasComponent<#property_ty>
         }),
         PropertyType::Provided => Some(quote! {
             ::shaku::HasProvider<#property_ty>
         }),
+        // The module must implement the module trait, so it can be upcast into the trait object
+        PropertyType::Module => match property_ty {
+            Type::TraitObject(trait_object) => {
+                let bounds = &trait_object.bounds;
+                Some(quote! { #bounds })
+            }
+            _ => unreachable!("Checked while parsing the property"),
+        },
     }
 }
+
+/// The service's own type (`Self`, or the service's name) is used as a
+/// concrete interface. Anything else is a trait, used as `dyn #interface`.
+pub fn service_interface(interface: &Type, service_name: &Ident) -> TokenStream {
+    if is_own_type(interface, service_name) {
+        quote! { #interface }
+    } else {
+        quote! { dyn #interface }
+    }
+}
+
+/// Check if the interface is the service's own type (`Self`, or the service's name)
+pub fn is_own_type(interface: &Type, service_name: &Ident) -> bool {
+    if let Type::Path(type_path) = interface {
+        let is_named = type_path.qself.is_none()
+            && type_path.path.segments.len() == 1
+            && type_path.path.segments[0].ident == *service_name;
+
+        type_path.path.is_ident("Self") || is_named
+    } else {
+        false
+    }
+}
+
+/// Check that the interface trait is object safe, so an object-unsafe interface (ex. with generic
+/// methods or `async fn`) is reported at `#[shaku(interface = ?)]` before the errors from the
+/// generated impl. The check is an unused method taking the interface as a trait object. Concrete
+/// interfaces are not trait objects, so they are not checked.
+pub fn interface_object_safety_check(
+    interface: &Type,
+    service_name: &Ident,
+    generics: &Generics,
+) -> Option<TokenStream> {
+    if is_own_type(interface, service_name) {
+        return None;
+    }
+
+    let (generic_impls, generic_tys, generic_where) = generics.split_for_impl();
+    let check = quote_spanned! {interface.span()=>
+        #[allow(dead_code)]
+        fn __shaku_interface_must_be_object_safe(_: &dyn #interface) {}
+    };
+
+    Some(quote! {
+        impl #generic_impls #service_name #generic_tys #generic_where {
+            #check
+        }
+    })
+}
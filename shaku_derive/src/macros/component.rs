@@ -1,14 +1,121 @@
 //! Implementation of the `#[derive(Component)]` procedural macro
 
 use crate::debug::get_debug_level;
-use crate::macros::common_output::create_dependency;
-use crate::structures::service::{Property, PropertyDefault, ServiceData};
+use crate::macros::common_output::{
+    create_dependency, interface_object_safety_check, is_own_type, service_interface,
+};
+use crate::structures::service::{Adapter, Property, PropertyDefault, PropertyType, ServiceData};
 use proc_macro2::TokenStream;
-use syn::{DeriveInput, Ident, Visibility};
+use syn::{
+    DeriveInput, Error, GenericArgument, Generics, Ident, LitStr, Member, Path, PathArguments,
+    Type, TypePath, Visibility,
+};
 
 pub fn expand_derive_component(input: &DeriveInput) -> syn::Result<TokenStream> {
     let service = ServiceData::from_derive_input(input)?;
 
+    if let (Some(thread_bound), Some(_)) = (
+        &service.metadata.thread_bound,
+        service.metadata.subscriptions.first(),
+    ) {
+        return Err(Error::new(
+            thread_bound.span(),
+            "Thread-bound components cannot subscribe to events",
+        ));
+    }
+
+    if let (Some(thread_bound), Some(_)) = (
+        &service.metadata.thread_bound,
+        &service.metadata.health_check,
+    ) {
+        return Err(Error::new(
+            thread_bound.span(),
+            "Thread-bound components cannot be health checks",
+        ));
+    }
+
+    if let (Some(thread_bound), Some(_)) =
+        (&service.metadata.thread_bound, &service.metadata.lifecycle)
+    {
+        return Err(Error::new(
+            thread_bound.span(),
+            "Thread-bound components cannot have lifecycle hooks",
+        ));
+    }
+
+    if let (Some(thread_bound), Some(_)) = (
+        &service.metadata.thread_bound,
+        &service.metadata.reconfigurable,
+    ) {
+        return Err(Error::new(
+            thread_bound.span(),
+            "Thread-bound components cannot be reconfigured",
+        ));
+    }
+
+    if let (Some(reconfigurable), Some(_)) =
+        (&service.metadata.reconfigurable, &service.metadata.delegate)
+    {
+        return Err(Error::new(
+            reconfigurable.span(),
+            "Reconfigurable components cannot delegate, since the component itself is reconfigured",
+        ));
+    }
+
+    if let Some(retry) = &service.metadata.retry {
+        return Err(Error::new(
+            retry.key.span(),
+            "Only providers can be retried, since components are built with the module",
+        ));
+    }
+
+    if let Some(adapter) = service.metadata.adapters.first() {
+        if let Some(thread_bound) = &service.metadata.thread_bound {
+            return Err(Error::new(
+                thread_bound.span(),
+                "Thread-bound components cannot have adapters",
+            ));
+        }
+
+        if !service.metadata.generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                &adapter.identifier,
+                "Generic components cannot have adapters",
+            ));
+        }
+    }
+
+    let own_interface = is_own_type(&service.metadata.interface, &service.metadata.identifier);
+
+    let mock_impl = match &service.metadata.mock {
+        Some(mock) => {
+            if service.metadata.thread_bound.is_some()
+                || !service.metadata.generics.params.is_empty()
+                || own_interface
+            {
+                return Err(Error::new(
+                    mock.span(),
+                    "Only components of a non-generic, thread-safe trait interface can generate mocks",
+                ));
+            }
+
+            Some(create_mock_impl(mock, &service.metadata.interface)?)
+        }
+        None => None,
+    };
+
+    if let (true, Some(delegate)) = (own_interface, &service.metadata.delegate) {
+        return Err(Error::new_spanned(
+            delegate,
+            "Components cannot delegate when the component itself is the interface",
+        ));
+    }
+
+    let delegate_property = match &service.metadata.delegate {
+        Some(delegate) => Some(find_delegate_property(&service.properties, delegate)?),
+        None => None,
+    };
+
     let debug_level = get_debug_level();
     if debug_level > 1 {
         println!("Service data parsed from Component input: {:#?}", service);
@@ -20,11 +127,49 @@ pub fn expand_derive_component(input: &DeriveInput) -> syn::Result<TokenStream>
         .map(create_resolve_property)
         .collect();
 
-    let dependencies: Vec<TokenStream> = service
+    let mut dependencies: Vec<TokenStream> = service
         .properties
         .iter()
         .filter_map(create_dependency)
         .collect();
+    dependencies.extend(
+        service
+            .metadata
+            .subscriptions
+            .iter()
+            .map(create_event_bus_dependency),
+    );
+    if service.metadata.health_check.is_some() {
+        dependencies.push(quote! {
+            ::shaku::HasComponent<::shaku::extras::HealthRegistry>
+        });
+    }
+    if service.metadata.lifecycle.is_some() {
+        dependencies.push(quote! {
+            ::shaku::HasComponent<::shaku::extras::LifecycleRegistry>
+        });
+    }
+    let build_after = &service.metadata.build_after;
+    let reconfigurable = service.metadata.reconfigurable.is_some();
+    let parameters_name = format_ident!("{}Parameters", service.metadata.identifier);
+    let (_, generic_tys, _) = service.metadata.generics.split_for_impl();
+    let reconfigurable_parameters = if reconfigurable {
+        Some(quote! { #parameters_name #generic_tys })
+    } else {
+        None
+    };
+    let component_where = create_component_where(
+        &service.metadata.generics,
+        build_after,
+        reconfigurable_parameters,
+    );
+    let dependencies_fn = create_dependencies_fn(&service.properties, build_after);
+    let parameter_sources_fn = create_parameter_sources_fn(&service.properties);
+    let post_build = create_post_build(
+        &service.metadata.subscriptions,
+        service.metadata.health_check.is_some(),
+        service.metadata.lifecycle.is_some(),
+    );
 
     let visibility = &service.metadata.visibility;
     let parameters_properties: Vec<TokenStream> = service
@@ -41,32 +186,174 @@ pub fn expand_derive_component(input: &DeriveInput) -> syn::Result<TokenStream>
 
     // Component implementation
     let component_name = service.metadata.identifier;
-    let parameters_name = format_ident!("{}Parameters", component_name);
     let parameters_doc = format!(" Parameters for {}", component_name);
-    let interface = service.metadata.interface;
+    let has_secrets = service.properties.iter().any(|property| property.secret);
+    // Secret parameters are redacted by the generated Debug impl instead
+    let parameters_derives: Vec<&Path> = service
+        .metadata
+        .parameters_derives
+        .iter()
+        .filter(|path| !(has_secrets && is_debug_path(path)))
+        .collect();
+    let parameters_derive_attr = if parameters_derives.is_empty() {
+        None
+    } else {
+        Some(quote! { #[derive(#(#parameters_derives),*)] })
+    };
     let (generic_impls, generic_tys, generic_where) = service.metadata.generics.split_for_impl();
+    let parameters_struct = if service.is_tuple_struct {
+        quote! {
+            #visibility struct #parameters_name #generic_impls (
+                #(#parameters_properties),*
+            ) #generic_where;
+        }
+    } else {
+        quote! {
+            #visibility struct #parameters_name #generic_impls #generic_where {
+                #(#parameters_properties),*
+            }
+        }
+    };
+    let parameters_debug = if has_secrets {
+        Some(create_parameters_debug(
+            &service.properties,
+            &parameters_name,
+            &service.metadata.generics,
+            service.is_tuple_struct,
+        ))
+    } else {
+        None
+    };
+    let interface = service_interface(&service.metadata.interface, &component_name);
+    // Adapters are generated outside of the impl, where `Self` is not available
+    let adapted_interface = if own_interface {
+        quote! { #component_name }
+    } else {
+        interface.clone()
+    };
+    let component = match &service.metadata.delegate {
+        Some(delegate) => quote! { Self { #(#resolve_properties),* }.#delegate },
+        None => quote! { Self { #(#resolve_properties),* } },
+    };
+    let (interface_type, build_output) = if service.metadata.thread_bound.is_some() {
+        (
+            quote! { ::shaku::ThreadBound<#interface> },
+            quote! {
+                ::shaku::__alloc::Box::new(::shaku::ThreadBound::new(
+                    ::shaku::__alloc::Box::new(#component) as ::shaku::__alloc::Box<#interface>
+                ))
+            },
+        )
+    } else {
+        (interface, quote! { ::shaku::__alloc::Box::new(#component) })
+    };
+    let build_shared = if reconfigurable {
+        Some(quote! {
+            fn build_shared(context: &mut ::shaku::ModuleBuildContext<M>, params: Self::Parameters) -> ::shaku::__alloc::Arc<Self::Interface> {
+                #(
+                let _: ::shaku::__alloc::Arc<<#build_after as ::shaku::Component<M>>::Interface> =
+                    M::build_component(context);
+                )*
+                let component = ::shaku::__alloc::Arc::new(#component);
+                context.watch_parameters(&component);
+                component
+            }
+        })
+    } else {
+        None
+    };
+    let delegate_deref = delegate_property.map(|property| {
+        let member = &property.member;
+        let ty = &property.ty;
+
+        quote! {
+            impl #generic_impls ::core::ops::Deref for #component_name #generic_tys #generic_where {
+                type Target = #ty;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.#member
+                }
+            }
+        }
+    });
+    let parameters_required = service.properties.iter().any(|property| {
+        if let PropertyDefault::NoDefault = property.default {
+            property.is_parameter()
+        } else {
+            false
+        }
+    });
+    // Components without parameters are built without looking up their parameters
+    let empty_parameters = parameters_properties.is_empty();
+    // Components which are tied to the module they were built in are never reused by `rebuild`
+    let always_rebuild = reconfigurable
+        || !service.metadata.subscriptions.is_empty()
+        || service.metadata.health_check.is_some()
+        || service.metadata.lifecycle.is_some()
+        || service.properties.iter().any(Property::is_module);
+    let parameters_state = if parameters_required {
+        quote! { ::shaku::ParametersMissing }
+    } else {
+        quote! { ::shaku::ParametersSet }
+    };
+    let adapters: Vec<TokenStream> = service
+        .metadata
+        .adapters
+        .iter()
+        .map(|adapter| create_adapter(adapter, &component_name, &adapted_interface, visibility))
+        .collect();
+    let object_safety_check = interface_object_safety_check(
+        &service.metadata.interface,
+        &component_name,
+        &service.metadata.generics,
+    );
     let generic_impls_no_parens = &service.metadata.generics.params;
     let output = quote! {
+        #object_safety_check
+
         impl<
             M: ::shaku::Module #(+ #dependencies)*,
             #generic_impls_no_parens
-        > ::shaku::Component<M> for #component_name #generic_tys #generic_where {
-            type Interface = dyn #interface;
+        > ::shaku::Component<M> for #component_name #generic_tys #component_where {
+            type Interface = #interface_type;
             type Parameters = #parameters_name #generic_tys;
+            const EMPTY_PARAMETERS: bool = #empty_parameters;
+            const ALWAYS_REBUILD: bool = #always_rebuild;
 
-            fn build(context: &mut ::shaku::ModuleBuildContext<M>, params: Self::Parameters) -> Box<Self::Interface> {
-                Box::new(Self {
-                    #(#resolve_properties),*
-                })
+            fn build(context: &mut ::shaku::ModuleBuildContext<M>, params: Self::Parameters) -> ::shaku::__alloc::Box<Self::Interface> {
+                #(
+                let _: ::shaku::__alloc::Arc<<#build_after as ::shaku::Component<M>>::Interface> =
+                    M::build_component(context);
+                )*
+                #build_output
             }
+
+            #build_shared
+
+            #post_build
+
+            #dependencies_fn
+
+            #parameter_sources_fn
         }
 
+        #delegate_deref
+
+        #(#adapters)*
+
+        #mock_impl
+
         #[doc = #parameters_doc]
-        #visibility struct #parameters_name #generic_impls #generic_where {
-            #(#parameters_properties),*
+        #parameters_derive_attr
+        #parameters_struct
+
+        #parameters_debug
+
+        impl #generic_impls ::shaku::RequiredParameters for #parameters_name #generic_tys #generic_where {
+            type State = #parameters_state;
         }
 
-        impl #generic_impls ::std::default::Default for #parameters_name #generic_tys #generic_where {
+        impl #generic_impls ::core::default::Default for #parameters_name #generic_tys #generic_where {
             #[allow(unreachable_code)]
             fn default() -> Self {
                 Self {
@@ -83,57 +370,400 @@ pub fn expand_derive_component(input: &DeriveInput) -> syn::Result<TokenStream>
     Ok(output)
 }
 
+/// The delegate must be a parameter, since the external type is passed in
+/// when building the module (or via its default).
+fn find_delegate_property<'a>(
+    properties: &'a [Property],
+    delegate: &Member,
+) -> syn::Result<&'a Property> {
+    let property = properties
+        .iter()
+        .find(|property| property.member == *delegate)
+        .ok_or_else(|| {
+            Error::new_spanned(
+                delegate,
+                format!("Unknown delegate field: `{}`", quote!(#delegate)),
+            )
+        })?;
+
+    if !property.is_parameter() {
+        return Err(Error::new_spanned(
+            delegate,
+            "The delegate field must be a parameter, not a service or sourced value",
+        ));
+    }
+
+    Ok(property)
+}
+
 fn create_resolve_property(property: &Property) -> TokenStream {
-    let property_name = &property.property_name;
+    let member = &property.member;
 
-    if property.is_service() {
+    if let PropertyType::Module = property.property_type {
+        quote! {
+            #member: context.module_handle(|module| module)
+        }
+    } else if let Some(key) = &property.source_key {
+        quote! {
+            #member: ::shaku::source_parameter(
+                context,
+                ::core::any::type_name::<Self>(),
+                #key,
+            )
+        }
+    } else if let PropertyType::Provided = property.property_type {
         quote! {
-            #property_name: M::build_component(context)
+            #member: context.provide()
+        }
+    } else if property.is_service() {
+        quote! {
+            #member: M::build_component(context)
         }
     } else {
+        let parameters_member = &property.parameters_member;
+
         quote! {
-            #property_name: params.#property_name
+            #member: params.#parameters_member
+        }
+    }
+}
+
+/// The adapter is a component which wraps whichever component implements the
+/// service's interface, so it can implement the other interface on top of it.
+fn create_adapter(
+    adapter: &Adapter,
+    component_name: &Ident,
+    interface: &TokenStream,
+    vis: &Visibility,
+) -> TokenStream {
+    let adapter_name = &adapter.identifier;
+    let adapter_interface = &adapter.interface;
+    let adapter_doc = format!(
+        " Adapts `{}` (via `{}`) to `{}`",
+        component_name,
+        quote!(#interface),
+        quote!(#adapter_interface)
+    );
+
+    quote! {
+        #[doc = #adapter_doc]
+        #vis struct #adapter_name(#vis ::shaku::__alloc::Arc<#interface>);
+
+        impl<M: ::shaku::Module + ::shaku::HasComponent<#interface>> ::shaku::Component<M>
+            for #adapter_name
+        {
+            type Interface = dyn #adapter_interface;
+            type Parameters = ();
+            const EMPTY_PARAMETERS: bool = true;
+
+            fn build(context: &mut ::shaku::ModuleBuildContext<M>, _: ()) -> ::shaku::__alloc::Box<Self::Interface> {
+                ::shaku::__alloc::Box::new(#adapter_name(M::build_component(context)))
+            }
+
+            fn dependencies() -> ::shaku::__alloc::Vec<&'static str> {
+                ::shaku::__alloc::vec![::core::any::type_name::<#interface>()]
+            }
+        }
+
+        impl ::core::ops::Deref for #adapter_name {
+            type Target = #interface;
+
+            fn deref(&self) -> &Self::Target {
+                &*self.0
+            }
+        }
+    }
+}
+
+/// Implement Component for the interface's mock, which mockall's `#[automock]` names
+/// `Mock<Trait>` (ex. `MockLogger`). The mock is passed in as the component's parameters, so tests
+/// can set up its expectations before the module is built.
+fn create_mock_impl(mock: &Ident, interface: &Type) -> syn::Result<TokenStream> {
+    let invalid_interface_error = || {
+        Error::new(
+            mock.span(),
+            "Mocks can only be generated when the interface is a trait path (ex. `interface = Foo`)",
+        )
+    };
+    let mut mock_path = match interface {
+        Type::Path(TypePath { qself: None, path }) => path.clone(),
+        _ => return Err(invalid_interface_error()),
+    };
+    let last_segment = mock_path
+        .segments
+        .last_mut()
+        .ok_or_else(invalid_interface_error)?;
+
+    if let PathArguments::AngleBracketed(arguments) = &last_segment.arguments {
+        for argument in &arguments.args {
+            if let GenericArgument::Type(_) = argument {
+                continue;
+            }
+
+            return Err(invalid_interface_error());
+        }
+    }
+
+    last_segment.ident = format_ident!("Mock{}", last_segment.ident);
+
+    Ok(quote! {
+        #[cfg(test)]
+        impl<M: ::shaku::Module> ::shaku::Component<M> for #mock_path {
+            type Interface = dyn #interface;
+            type Parameters = Self;
+
+            fn build(_: &mut ::shaku::ModuleBuildContext<M>, mock: Self) -> ::shaku::__alloc::Box<Self::Interface> {
+                ::shaku::__alloc::Box::new(mock)
+            }
+        }
+
+        #[cfg(test)]
+        impl ::shaku::RequiredParameters for #mock_path {
+            type State = ::shaku::ParametersSet;
+        }
+    })
+}
+
+fn create_event_bus_dependency(event_ty: &Type) -> TokenStream {
+    quote! {
+        ::shaku::HasComponent<::shaku::extras::EventBus<#event_ty>>
+    }
+}
+
+/// The where clause of the component impl, which also requires the components
+/// set via `#[shaku(build_after = ?)]` to be in the module, and reconfigurable
+/// components to implement `Reconfigurable` for their parameters
+fn create_component_where(
+    generics: &Generics,
+    build_after: &[Type],
+    reconfigurable_parameters: Option<TokenStream>,
+) -> Option<syn::WhereClause> {
+    let mut generics = generics.clone();
+
+    if let Some(parameters) = reconfigurable_parameters {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote! { Self: ::shaku::Reconfigurable<#parameters> });
+    }
+
+    if !build_after.is_empty() {
+        let where_clause = generics.make_where_clause();
+
+        for component in build_after {
+            where_clause
+                .predicates
+                .push(syn::parse_quote! { #component: ::shaku::Component<M> });
+            where_clause.predicates.push(syn::parse_quote! {
+                M: ::shaku::HasComponent<<#component as ::shaku::Component<M>>::Interface>
+            });
+        }
+    }
+
+    generics.where_clause
+}
+
+/// List the injected components and the components set via
+/// `#[shaku(build_after = ?)]`, which are built before this component
+fn create_dependencies_fn(properties: &[Property], build_after: &[Type]) -> Option<TokenStream> {
+    let mut dependencies: Vec<TokenStream> = build_after
+        .iter()
+        .map(|component| quote! { <#component as ::shaku::Component<M>>::Interface })
+        .collect();
+    dependencies.extend(
+        properties
+            .iter()
+            .filter_map(|property| match property.property_type {
+                PropertyType::Component => {
+                    let ty = &property.ty;
+                    Some(quote! { #ty })
+                }
+                _ => None,
+            }),
+    );
+
+    if dependencies.is_empty() {
+        return None;
+    }
+
+    Some(quote! {
+        fn dependencies() -> ::shaku::__alloc::Vec<&'static str> {
+            ::shaku::__alloc::vec![#(::core::any::type_name::<#dependencies>()),*]
+        }
+    })
+}
+
+/// List the keys of the properties which are read from a parameter source
+fn create_parameter_sources_fn(properties: &[Property]) -> Option<TokenStream> {
+    let keys: Vec<&LitStr> = properties
+        .iter()
+        .filter_map(|property| property.source_key.as_ref())
+        .collect();
+
+    if keys.is_empty() {
+        return None;
+    }
+
+    Some(quote! {
+        fn parameter_sources() -> ::shaku::__alloc::Vec<&'static str> {
+            ::shaku::__alloc::vec![#(#keys),*]
         }
+    })
+}
+
+fn create_post_build(
+    subscriptions: &[Type],
+    health_check: bool,
+    lifecycle: bool,
+) -> Option<TokenStream> {
+    if subscriptions.is_empty() && !health_check && !lifecycle {
+        return None;
     }
+
+    let register_health_check = if health_check {
+        Some(quote! {
+            let health_registry: ::shaku::__alloc::Arc<::shaku::extras::HealthRegistry> =
+                M::build_component(context);
+            health_registry.register(::shaku::__alloc::Arc::new(::shaku::__alloc::Arc::clone(component)));
+        })
+    } else {
+        None
+    };
+    let register_lifecycle = if lifecycle {
+        Some(quote! {
+            let lifecycle_registry: ::shaku::__alloc::Arc<::shaku::extras::LifecycleRegistry> =
+                M::build_component(context);
+            lifecycle_registry.register(::shaku::__alloc::Arc::new(::shaku::__alloc::Arc::clone(component)));
+        })
+    } else {
+        None
+    };
+
+    Some(quote! {
+        fn post_build(
+            context: &mut ::shaku::ModuleBuildContext<M>,
+            component: &::shaku::__alloc::Arc<Self::Interface>
+        ) {
+            #(
+            let event_bus: ::shaku::__alloc::Arc<::shaku::extras::EventBus<#subscriptions>> =
+                M::build_component(context);
+            event_bus.subscribe(::shaku::__alloc::Arc::new(::shaku::__alloc::Arc::clone(component)));
+            )*
+            #register_health_check
+            #register_lifecycle
+        }
+    })
 }
 
 fn create_parameters_property(property: &Property, vis: &Visibility) -> Option<TokenStream> {
-    if property.is_service() {
+    if !property.is_parameter() {
         return None;
     }
 
-    let property_name = &property.property_name;
     let property_type = &property.ty;
     let doc_comment = &property.doc_comment;
 
-    Some(quote! {
-        #(#doc_comment)*
-        #vis #property_name: #property_type
-    })
+    match &property.parameters_member {
+        Member::Named(property_name) => Some(quote! {
+            #(#doc_comment)*
+            #vis #property_name: #property_type
+        }),
+        Member::Unnamed(_) => Some(quote! {
+            #(#doc_comment)*
+            #vis #property_type
+        }),
+    }
+}
+
+fn is_debug_path(path: &Path) -> bool {
+    path.segments
+        .last()
+        .map_or(false, |segment| segment.ident == "Debug")
+}
+
+/// Create a Debug impl for the parameters struct which redacts secret parameters
+fn create_parameters_debug(
+    properties: &[Property],
+    parameters_name: &Ident,
+    generics: &Generics,
+    is_tuple_struct: bool,
+) -> TokenStream {
+    let parameters: Vec<&Property> = properties
+        .iter()
+        .filter(|property| property.is_parameter())
+        .collect();
+    let mut generics = generics.clone();
+
+    if !generics.params.is_empty() {
+        let where_clause = generics.make_where_clause();
+
+        for property in parameters.iter().filter(|property| !property.secret) {
+            let ty = &property.ty;
+            where_clause
+                .predicates
+                .push(syn::parse_quote! { #ty: ::core::fmt::Debug });
+        }
+    }
+
+    let fields = parameters.iter().map(|property| {
+        let member = &property.parameters_member;
+        let value = if property.secret {
+            quote! { &format_args!("***") }
+        } else {
+            quote! { &self.#member }
+        };
+
+        match member {
+            Member::Named(name) => {
+                let name = name.to_string();
+                quote! { .field(#name, #value) }
+            }
+            Member::Unnamed(_) => quote! { .field(#value) },
+        }
+    });
+    let parameters_name_str = parameters_name.to_string();
+    let debug_builder = if is_tuple_struct {
+        quote! { debug_tuple }
+    } else {
+        quote! { debug_struct }
+    };
+    let (generic_impls, generic_tys, generic_where) = generics.split_for_impl();
+
+    quote! {
+        impl #generic_impls ::core::fmt::Debug for #parameters_name #generic_tys #generic_where {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.#debug_builder(#parameters_name_str)
+                    #(#fields)*
+                    .finish()
+            }
+        }
+    }
 }
 
 fn create_parameters_default(property: &Property, component_ident: &Ident) -> Option<TokenStream> {
-    if property.is_service() {
+    if !property.is_parameter() {
         return None;
     }
 
-    let property_name = &property.property_name;
+    let member = &property.member;
+    let parameters_member = &property.parameters_member;
 
     match &property.default {
         PropertyDefault::Provided(default_expr) => Some(quote! {
-            #property_name: #default_expr
+            #parameters_member: #default_expr
         }),
         PropertyDefault::NotProvided => Some(quote! {
-            #property_name: Default::default()
+            #parameters_member: Default::default()
         }),
         PropertyDefault::NoDefault => {
             let unreachable_msg = format!(
                 "There is no default value for `{}::{}`",
-                component_ident, property_name
+                component_ident,
+                quote!(#member)
             );
 
             Some(quote! {
-                #property_name: unreachable!(#unreachable_msg)
+                #parameters_member: unreachable!(#unreachable_msg)
             })
         }
     }
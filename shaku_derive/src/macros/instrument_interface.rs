@@ -0,0 +1,167 @@
+//! Implementation of the `#[instrument_interface]` attribute macro
+
+use proc_macro2::{Span, TokenStream};
+use syn::{
+    Error, FnArg, GenericParam, Ident, ItemTrait, Pat, PatIdent, ReturnType, TraitItem,
+    TraitItemMethod, Type, TypeParamBound, WherePredicate,
+};
+
+pub fn expand_instrument_interface(item: &ItemTrait) -> syn::Result<TokenStream> {
+    if !item.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &item.generics,
+            "Instrumented interfaces cannot be generic",
+        ));
+    }
+
+    let trait_name = &item.ident;
+    let visibility = &item.vis;
+    let interface = trait_name.to_string();
+    let mut methods = Vec::new();
+
+    for trait_item in &item.items {
+        match trait_item {
+            TraitItem::Method(method) if !is_sized_only(method) => {
+                methods.push(instrument_method(method, &interface)?);
+            }
+            TraitItem::Method(_) => {}
+            TraitItem::Type(associated_type) => {
+                return Err(Error::new_spanned(
+                    associated_type,
+                    "Instrumented interfaces cannot have associated types",
+                ))
+            }
+            TraitItem::Const(associated_const) => {
+                return Err(Error::new_spanned(
+                    associated_const,
+                    "Instrumented interfaces cannot have associated constants",
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    let instrumented_name = format_ident!("Instrumented{}", trait_name);
+    let instrumented_doc = format!(
+        " Traces the method calls of a `{}` implementation. Created by `Instrument::instrument`.",
+        trait_name
+    );
+
+    Ok(quote! {
+        #item
+
+        #[doc = #instrumented_doc]
+        #visibility struct #instrumented_name(#visibility ::shaku::__alloc::Arc<dyn #trait_name>);
+
+        impl #trait_name for #instrumented_name {
+            #(#methods)*
+        }
+
+        impl ::shaku::Instrument for dyn #trait_name {
+            fn instrument(
+                inner: ::shaku::__alloc::Arc<Self>,
+            ) -> ::shaku::__alloc::Box<Self> {
+                ::shaku::__alloc::Box::new(#instrumented_name(inner))
+            }
+        }
+    })
+}
+
+/// Trace the method call in a span named after the method, and forward it to the inner
+/// implementation
+fn instrument_method(method: &TraitItemMethod, interface: &str) -> syn::Result<TokenStream> {
+    let has_type_generics = method.sig.generics.params.iter().any(|param| match param {
+        GenericParam::Lifetime(_) => false,
+        GenericParam::Type(_) | GenericParam::Const(_) => true,
+    });
+    if has_type_generics {
+        return Err(Error::new_spanned(
+            &method.sig.generics,
+            "The methods of an instrumented interface cannot be generic",
+        ));
+    }
+
+    match method.sig.inputs.iter().next() {
+        Some(FnArg::Receiver(receiver))
+            if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+        _ => {
+            return Err(Error::new_spanned(
+                &method.sig,
+                "The methods of an instrumented interface must take `&self`, since the \
+                 implementation is shared",
+            ))
+        }
+    }
+
+    // The arguments are renamed, since they are forwarded
+    let mut signature = method.sig.clone();
+    let mut argument_names = Vec::new();
+    for (i, input) in signature.inputs.iter_mut().skip(1).enumerate() {
+        if let FnArg::Typed(pat_type) = input {
+            let name = Ident::new(&format!("__shaku_arg_{}", i), Span::call_site());
+            pat_type.pat = Box::new(Pat::Ident(PatIdent {
+                attrs: Vec::new(),
+                by_ref: None,
+                mutability: None,
+                ident: name.clone(),
+                subpat: None,
+            }));
+            argument_names.push(name);
+        }
+    }
+
+    let method_name = &method.sig.ident;
+    let span_name = method_name.to_string();
+    let mut call = quote! {
+        (*self.0).#method_name(#(#argument_names),*)
+    };
+    if method.sig.unsafety.is_some() {
+        call = quote! { unsafe { #call } };
+    }
+    let body = match &method.sig.output {
+        ReturnType::Default => quote! {
+            #call;
+            ::shaku::__tracing::trace!("exit");
+        },
+        ReturnType::Type(_, _) => quote! {
+            let output = #call;
+            ::shaku::__tracing::trace!("exit");
+            output
+        },
+    };
+
+    Ok(quote! {
+        #signature {
+            let span = ::shaku::__tracing::trace_span!(#span_name, interface = #interface);
+            let _entered = span.enter();
+            ::shaku::__tracing::trace!("enter");
+            #body
+        }
+    })
+}
+
+/// Methods with `where Self: Sized` can't be called on the trait object, so they are not
+/// forwarded
+fn is_sized_only(method: &TraitItemMethod) -> bool {
+    let where_clause = match &method.sig.generics.where_clause {
+        Some(where_clause) => where_clause,
+        None => return false,
+    };
+
+    where_clause.predicates.iter().any(|predicate| {
+        let predicate = match predicate {
+            WherePredicate::Type(predicate) => predicate,
+            _ => return false,
+        };
+        let is_self = match &predicate.bounded_ty {
+            Type::Path(path) => path.qself.is_none() && path.path.is_ident("Self"),
+            _ => false,
+        };
+
+        is_self
+            && predicate.bounds.iter().any(|bound| match bound {
+                TypeParamBound::Trait(bound) => bound.path.is_ident("Sized"),
+                TypeParamBound::Lifetime(_) => false,
+            })
+    })
+}
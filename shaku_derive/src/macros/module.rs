@@ -1,18 +1,54 @@
 //! Implementation of the `module` procedural macro
 
 use crate::debug::get_debug_level;
-use crate::structures::module::{ComponentItem, ModuleData, Submodule};
-use proc_macro2::{Ident, Span, TokenStream};
+use crate::parser::Parser;
+use crate::structures::module::{
+    ComponentItem, ModuleData, ModuleItem, ModuleServices, ProviderItem, Submodule,
+};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::ToTokens;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::mem;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::Type;
+use syn::{Attribute, Type};
 
-pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
+/// Expand the module. The input is the unparsed module, which is passed to the
+/// extended module's macro if the module extends another module.
+pub fn expand_module_macro(mut module: ModuleData, input: TokenStream) -> syn::Result<TokenStream> {
     let debug_level = get_debug_level();
     if debug_level > 1 {
         println!("Module data parsed from input: {:#?}", module);
     }
 
+    // The extended module's services are only known by its macro, which passes
+    // them back to this macro. Once they are known, the extended module is
+    // used as the first submodule.
+    if let Some(base) = &module.metadata.extends {
+        match module.extended_services.take() {
+            Some(extended) => module.submodules.insert(
+                0,
+                Submodule {
+                    ty: extended.base,
+                    services: extended.services,
+                },
+            ),
+            None => return extend_module(base, input),
+        }
+    }
+
+    // With #[shaku(last_wins)], the earlier registrations of a duplicate service are removed
+    // instead of reported, and the module warns about them when it is built
+    let superseded = if module.metadata.options.last_wins.is_some() {
+        remove_superseded_services(&mut module)
+    } else {
+        Vec::new()
+    };
+
+    check_duplicate_services(&module)?;
+    check_duplicate_names(&module)?;
+
     // Only capture the build context if there is a lazy component
     let capture_build_context = module
         .services
@@ -21,11 +57,75 @@ pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
         .iter()
         .any(ComponentItem::is_lazy);
 
+    if let Some(derive_clone) = &module.metadata.options.derive_clone {
+        if capture_build_context {
+            return Err(syn::Error::new(
+                derive_clone.span(),
+                "Modules with lazy components cannot implement Clone",
+            ));
+        }
+    }
+
+    // Reachability is checked against the module interface
+    if let Some(deny_unused) = &module.metadata.options.deny_unused {
+        if module.metadata.interface.is_none() {
+            return Err(syn::Error::new(
+                deny_unused.span(),
+                "Modules with #[shaku(deny_unused)] must have a module interface, ex. \
+                 `MyModuleImpl: MyModule { ... }`",
+            ));
+        }
+    }
+
+    // The strict builder and module extensions refer to the services by index
+    let first_cfg = module
+        .services
+        .components
+        .items
+        .iter()
+        .flat_map(|component| component.cfg.first())
+        .chain(
+            module
+                .services
+                .providers
+                .items
+                .iter()
+                .flat_map(|provider| provider.cfg.first()),
+        )
+        .next();
+    if let Some(cfg) = first_cfg {
+        let options = &module.metadata.options;
+
+        if let Some(option) = options
+            .strict_builder
+            .as_ref()
+            .or(options.extendable.as_ref())
+        {
+            return Err(syn::Error::new_spanned(
+                cfg,
+                format!(
+                    "Modules with #[shaku({})] cannot have conditional services",
+                    option
+                ),
+            ));
+        }
+    }
+
     // Build token streams
     let module_struct = module_struct(&module, capture_build_context);
+    let module_clone_impl = module_clone_impl(&module);
     let module_trait_impl = module_trait(&module);
     let module_builder = module_builder(&module);
-    let module_impl = module_impl(&module, capture_build_context);
+    let module_metrics_impl = module_metrics_impl(&module);
+    let module_fingerprint_impl = module_fingerprint_impl(&module);
+    let module_build_order_impl = module_build_order_impl(&module);
+    let module_memory_footprint_impl = module_memory_footprint_impl(&module);
+    let module_reconfigurable_impl = module_reconfigurable_impl(&module);
+    let module_rebuild_impl = module_rebuild_impl(&module);
+    let module_strict_builder = module_strict_builder(&module);
+    let module_impl = module_impl(&module, capture_build_context, &superseded);
+    let module_services_impl = module_services_impl(&module);
+    let module_extension = module_extension(&module);
 
     let has_component_impls: Vec<TokenStream> = module
         .services
@@ -42,7 +142,7 @@ pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
         .items
         .iter()
         .enumerate()
-        .map(|(i, provider)| has_provider_impl(i, &provider.ty, &module))
+        .map(|(i, provider)| has_provider_impl(i, provider, &module))
         .collect();
 
     let has_subcomponent_impls: Vec<TokenStream> = module
@@ -60,18 +160,21 @@ pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
         })
         .collect();
 
-    let has_subprovider_impls: Vec<TokenStream> = module
-        .submodules
-        .iter()
+    let has_subprovider_impls: Vec<TokenStream> = subproviders(&module)
+        .into_iter()
         .enumerate()
-        .flat_map(|(i, submodule)| {
-            submodule
-                .services
-                .providers
-                .items
-                .iter()
-                .map(|provider| has_subprovider_impl(i, submodule, &provider.ty, &module))
-                .collect::<Vec<_>>()
+        .map(|(i, (submodule_index, provider_ty))| {
+            has_subprovider_impl(i, submodule_index, provider_ty, &module)
+        })
+        .collect();
+
+    let has_submodule_impls = has_submodule_impls(&module);
+
+    let has_factory_impls: Vec<TokenStream> = submodule_factories(&module)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (submodule_index, provider_ty))| {
+            has_factory_impl(i, submodule_index, provider_ty, &module)
         })
         .collect();
 
@@ -80,11 +183,23 @@ pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
         #module_struct
         #module_trait_impl
         #module_builder
+        #module_metrics_impl
+        #module_fingerprint_impl
+        #module_build_order_impl
+        #module_memory_footprint_impl
+        #module_reconfigurable_impl
+        #module_rebuild_impl
+        #module_strict_builder
         #module_impl
+        #module_services_impl
+        #module_extension
+        #module_clone_impl
         #(#has_component_impls)*
         #(#has_provider_impls)*
         #(#has_subcomponent_impls)*
         #(#has_subprovider_impls)*
+        #(#has_submodule_impls)*
+        #(#has_factory_impls)*
     };
 
     if debug_level > 0 {
@@ -94,6 +209,242 @@ pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
     Ok(output)
 }
 
+/// Where a service of the module comes from, for duplicate service errors
+enum ServiceSource<'a> {
+    Module,
+    Submodule(&'a Type),
+}
+
+impl ServiceSource<'_> {
+    fn describe(&self) -> String {
+        match self {
+            ServiceSource::Module => "the module's own services".to_string(),
+            ServiceSource::Submodule(ty) => {
+                format!("the `use {}` block", ty.to_token_stream())
+            }
+        }
+    }
+}
+
+/// Check that no service is registered twice, which would otherwise fail with
+/// a confusing "conflicting implementations" error. The module's own services
+/// are listed by their implementation, so they are only compared by name (ex.
+/// a service listed twice, or a concrete component which is also imported).
+/// Conditional services are skipped, since they may be mutually exclusive.
+fn check_duplicate_services(module: &ModuleData) -> syn::Result<()> {
+    let components = module
+        .services
+        .components
+        .items
+        .iter()
+        .filter(|component| component.cfg.is_empty())
+        .map(|component| (&component.ty, ServiceSource::Module))
+        .chain(module.submodules.iter().flat_map(|submodule| {
+            submodule
+                .services
+                .components
+                .items
+                .iter()
+                .map(move |component| (&component.ty, ServiceSource::Submodule(&submodule.ty)))
+        }));
+    check_duplicates(components, "component")?;
+
+    let providers = module
+        .services
+        .providers
+        .items
+        .iter()
+        .filter(|provider| provider.cfg.is_empty())
+        .map(|provider| (&provider.ty, ServiceSource::Module))
+        .chain(module.submodules.iter().flat_map(|submodule| {
+            submodule
+                .services
+                .providers
+                .items
+                .iter()
+                .map(move |provider| (&provider.ty, ServiceSource::Submodule(&submodule.ty)))
+        }));
+    check_duplicates(providers, "provider")
+}
+
+fn check_duplicates<'a>(
+    services: impl Iterator<Item = (&'a Type, ServiceSource<'a>)>,
+    kind: &str,
+) -> syn::Result<()> {
+    let mut seen: Vec<(String, ServiceSource)> = Vec::new();
+
+    for (ty, source) in services {
+        let name = service_name(ty);
+
+        if let Some((_, first_source)) = seen.iter().find(|(seen_name, _)| *seen_name == name) {
+            let message = match (first_source, &source) {
+                (ServiceSource::Module, ServiceSource::Module) => format!(
+                    "The {} `{}` is listed more than once. Remove the duplicate.",
+                    kind,
+                    ty.to_token_stream()
+                ),
+                (ServiceSource::Submodule(first), ServiceSource::Submodule(second))
+                    if first.to_token_stream().to_string()
+                        == second.to_token_stream().to_string() =>
+                {
+                    format!(
+                        "The {} `{}` is imported more than once from {}. Remove the duplicate.",
+                        kind,
+                        ty.to_token_stream(),
+                        source.describe()
+                    )
+                }
+                _ => format!(
+                    "The {} `{}` is already registered by {}, so it cannot also come from {}. \
+                     Remove it from one of them, or expose one of the services via a \
+                     different interface.",
+                    kind,
+                    ty.to_token_stream(),
+                    first_source.describe(),
+                    source.describe()
+                ),
+            };
+
+            return Err(syn::Error::new_spanned(ty, message));
+        }
+
+        seen.push((name, source));
+    }
+
+    Ok(())
+}
+
+/// Remove the earlier registrations of services which are registered more than once, so the last
+/// registration is used. Like `check_duplicate_services`, the services are compared by name and
+/// conditional services are skipped. Returns a description of each removed registration.
+fn remove_superseded_services(module: &mut ModuleData) -> Vec<String> {
+    let ModuleData {
+        services,
+        submodules,
+        ..
+    } = module;
+    let mut components = vec![(ServiceSource::Module, &mut services.components.items)];
+    let mut providers = vec![(ServiceSource::Module, &mut services.providers.items)];
+
+    for submodule in submodules.iter_mut() {
+        let Submodule { ty, services } = submodule;
+        let ModuleServices {
+            components: submodule_components,
+            providers: submodule_providers,
+            ..
+        } = services;
+        components.push((
+            ServiceSource::Submodule(ty),
+            &mut submodule_components.items,
+        ));
+        providers.push((ServiceSource::Submodule(ty), &mut submodule_providers.items));
+    }
+
+    let mut superseded = remove_superseded(components, "component");
+    superseded.extend(remove_superseded(providers, "provider"));
+    superseded
+}
+
+fn remove_superseded<A: Eq + Hash>(
+    lists: Vec<(
+        ServiceSource,
+        &mut Punctuated<ModuleItem<A>, syn::Token![,]>,
+    )>,
+    kind: &str,
+) -> Vec<String>
+where
+    Attribute: Parser<A>,
+{
+    // The name, list index, and item index of each unconditional service
+    let registrations: Vec<(String, usize, usize)> = lists
+        .iter()
+        .enumerate()
+        .flat_map(|(list_index, (_, items))| {
+            items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| item.cfg.is_empty())
+                .map(move |(item_index, item)| (service_name(&item.ty), list_index, item_index))
+        })
+        .collect();
+    let mut removed = Vec::new();
+    let mut descriptions = Vec::new();
+
+    for (i, (name, list_index, item_index)) in registrations.iter().enumerate() {
+        let last = registrations[i + 1..]
+            .iter()
+            .rev()
+            .find(|(later_name, _, _)| later_name == name);
+
+        if let Some((_, last_list_index, _)) = last {
+            let (source, items) = &lists[*list_index];
+            descriptions.push(format!(
+                "The {} `{}` from {} is superseded by the registration from {}",
+                kind,
+                items[*item_index].ty.to_token_stream(),
+                source.describe(),
+                lists[*last_list_index].0.describe()
+            ));
+            removed.push((*list_index, *item_index));
+        }
+    }
+
+    for (list_index, (_, items)) in lists.into_iter().enumerate() {
+        let all_items = mem::replace(items, Punctuated::new());
+        *items = all_items
+            .into_iter()
+            .enumerate()
+            .filter(|(item_index, _)| !removed.contains(&(list_index, *item_index)))
+            .map(|(_, item)| item)
+            .collect();
+    }
+
+    descriptions
+}
+
+/// Check that no two services were given the same field name via `#[name = "..."]`
+fn check_duplicate_names(module: &ModuleData) -> syn::Result<()> {
+    let names = module
+        .services
+        .components
+        .items
+        .iter()
+        .filter_map(|component| component.name.as_ref())
+        .chain(
+            module
+                .services
+                .providers
+                .items
+                .iter()
+                .filter_map(|provider| provider.name.as_ref()),
+        );
+    let mut seen = HashSet::new();
+
+    for name in names {
+        if !seen.insert(name) {
+            return Err(syn::Error::new_spanned(
+                name,
+                format!("The name `{}` is used by more than one service", name),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// A name for the service type, ignoring `dyn` and whitespace
+fn service_name(ty: &Type) -> String {
+    let ty = match ty {
+        Type::TraitObject(trait_object) if trait_object.dyn_token.is_some() => {
+            let bounds = &trait_object.bounds;
+            quote! { #bounds }
+        }
+        ty => ty.to_token_stream(),
+    };
+
+    ty.to_string().split_whitespace().collect()
+}
+
 /// Create the module struct
 fn module_struct(module: &ModuleData, capture_build_context: bool) -> TokenStream {
     let component_properties: Vec<TokenStream> = module
@@ -111,7 +462,17 @@ fn module_struct(module: &ModuleData, capture_build_context: bool) -> TokenStrea
         .items
         .iter()
         .enumerate()
-        .map(|(i, provider)| provider_property(i, &provider.ty))
+        .map(|(i, provider)| provider_property(i, provider))
+        .collect();
+
+    let promoted_properties: Vec<TokenStream> = module
+        .services
+        .providers
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, provider)| provider.is_promoted())
+        .map(|(i, provider)| promoted_property(i, provider))
         .collect();
 
     let submodule_properties: Vec<TokenStream> = module
@@ -121,40 +482,108 @@ fn module_struct(module: &ModuleData, capture_build_context: bool) -> TokenStrea
         .map(|(i, sub)| submodule_property(i, sub))
         .collect();
 
+    let factory_properties: Vec<TokenStream> = submodule_factories(module)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (_, provider_ty))| factory_property(i, provider_ty))
+        .collect();
+
+    let subprovider_override_properties: Vec<TokenStream> = subproviders(module)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (_, provider_ty))| subprovider_override_property(i, provider_ty))
+        .collect();
+
+    let attributes = &module.metadata.attributes;
     let visibility = &module.metadata.visibility;
     let module_name = &module.metadata.identifier;
     let module_generics = &module.metadata.generics;
     let where_clause = &module.metadata.generics.where_clause;
 
     let build_context_property = if capture_build_context {
-        quote! { build_context: ::std::sync::Mutex<::shaku::ModuleBuildContext<Self>>, }
+        quote! { build_context: ::shaku::Lock<::shaku::ModuleBuildContext<Self>>, }
     } else {
         TokenStream::new()
     };
 
+    let bookkeeping = bookkeeping_fields();
+    let bookkeeping_names = bookkeeping.iter().map(|field| &field.0);
+    let bookkeeping_types = bookkeeping.iter().map(|field| &field.1);
+
     quote! {
+        #(#attributes)*
         #visibility struct #module_name #module_generics #where_clause {
             #(#component_properties,)*
             #(#provider_properties,)*
+            #(#promoted_properties,)*
             #(#submodule_properties,)*
+            #(#factory_properties,)*
+            #(#subprovider_override_properties,)*
+            #(#bookkeeping_names: #bookkeeping_types,)*
+            __di_config_watcher: ::shaku::ConfigWatcher,
+            __di_fingerprint: ::core::option::Option<u64>,
+            __di_graph: ::core::option::Option<::shaku::__alloc::Arc<::shaku::debug::ModuleGraph>>,
             #build_context_property
         }
     }
 }
 
-/// Create an `impl $module_trait for $module` if there is a module trait
+/// Create an `impl $module_trait for $module` if there is a module trait, along
+/// with helpers to convert the module into a module trait object
 fn module_trait(module: &ModuleData) -> Option<TokenStream> {
     let module_trait = module.metadata.interface.as_ref()?;
     let module_name = &module.metadata.identifier;
+    let visibility = &module.metadata.visibility;
     let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
 
+    // Implementing From for Arc (a foreign type) with generic parameters is
+    // rejected by the coherence rules before Rust 1.41, so generic modules only
+    // get the conversion methods.
+    let from_impls = if module.metadata.generics.params.is_empty() {
+        Some(quote! {
+            impl ::core::convert::From<#module_name> for ::shaku::__alloc::Arc<dyn #module_trait> {
+                fn from(module: #module_name) -> Self {
+                    ::shaku::__alloc::Arc::new(module)
+                }
+            }
+
+            impl ::core::convert::From<#module_name> for ::shaku::__alloc::Box<dyn #module_trait> {
+                fn from(module: #module_name) -> Self {
+                    ::shaku::__alloc::Box::new(module)
+                }
+            }
+        })
+    } else {
+        None
+    };
+
     Some(quote! {
         impl #impl_generics #module_trait for #module_name #ty_generics #where_clause {}
+
+        impl #impl_generics #module_name #ty_generics #where_clause {
+            /// Use this module through its module interface
+            #[allow(dead_code)]
+            #visibility fn as_module_interface(&self) -> &dyn #module_trait {
+                self
+            }
+
+            /// Convert this module into a shared module interface trait object
+            #[allow(dead_code)]
+            #visibility fn into_module_interface(self) -> ::shaku::__alloc::Arc<dyn #module_trait> {
+                ::shaku::__alloc::Arc::new(self)
+            }
+        }
+
+        #from_impls
     })
 }
 
 /// Create a Module impl
-fn module_impl(module: &ModuleData, capture_build_context: bool) -> TokenStream {
+fn module_impl(
+    module: &ModuleData,
+    capture_build_context: bool,
+    superseded: &[String],
+) -> TokenStream {
     let module_name = &module.metadata.identifier;
     let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
 
@@ -173,32 +602,571 @@ fn module_impl(module: &ModuleData, capture_build_context: bool) -> TokenStream
         .items
         .iter()
         .enumerate()
-        .map(|(i, provider)| provider_build(i, &provider.ty))
+        .map(|(i, provider)| provider_build(i, provider))
+        .collect();
+
+    let promoted_providers: Vec<(Ident, &ProviderItem)> = module
+        .services
+        .providers
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, provider)| provider.is_promoted())
+        .map(|(i, provider)| (promoted_property_name(i, provider), provider))
+        .collect();
+    let promoted_builders = promoted_providers.iter().map(|(property, provider)| {
+        let cfg = &provider.cfg;
+        quote! {
+            #(#cfg)*
+            #property: ::shaku::OnceCell::new()
+        }
+    });
+    let promoted_inits = module
+        .services
+        .providers
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, provider)| provider.is_promoted())
+        .map(|(i, provider)| {
+            let cfg = &provider.cfg;
+            let init = promoted_init(i, provider, &quote! { (&module) });
+            quote! {
+                #(#cfg)*
+                #init;
+            }
+        });
+
+    let factory_builders: Vec<TokenStream> = submodule_factories(module)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (submodule_index, provider_ty))| {
+            factory_build(i, submodule_index, provider_ty, module)
+        })
+        .collect();
+
+    let subprovider_override_builders: Vec<TokenStream> = subproviders(module)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (_, provider_ty))| {
+            let property = generate_name(i, "subprovider_override", provider_ty.span());
+            quote! {
+                #[allow(bare_trait_objects)]
+                #property: context.provider_override::<#provider_ty>()
+            }
+        })
         .collect();
 
+    let fingerprint = module_fingerprint(module);
+    let graph = module_graph(module);
+    let deny_unused = module.metadata.options.deny_unused.as_ref().map(|_| {
+        quote! {
+            ::shaku::deny_unreachable_services(
+                ::core::any::type_name::<Self>(),
+                &Self::unreachable_services(),
+            );
+        }
+    });
+    let report_superseded = if superseded.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            ::shaku::report_superseded_services(
+                ::core::any::type_name::<Self>(),
+                &[#(#superseded),*],
+            );
+        })
+    };
     let submodules_init = submodules_init(&module.submodules);
     let submodule_names = submodule_names(&module.submodules);
     let submodule_types: Vec<&Type> = module.submodules.iter().map(|sub| &sub.ty).collect();
     let build_context_init = if capture_build_context {
-        quote! { build_context: ::std::sync::Mutex::new(context), }
+        quote! { build_context: ::shaku::Lock::new(context), }
     } else {
         TokenStream::new()
     };
 
+    let bookkeeping = bookkeeping_fields();
+    let bookkeeping_names = bookkeeping.iter().map(|field| &field.0);
+    let bookkeeping_inits = bookkeeping.iter().map(|field| &field.2);
+
+    let parameter_sources = module.services.components.items.iter().map(|component| {
+        let component_ty = &component.ty;
+        let cfg = &component.cfg;
+
+        quote! {
+            #(#cfg)*
+            keys.extend(<#component_ty as ::shaku::Component<Self>>::parameter_sources());
+        }
+    });
+
     quote! {
         impl #impl_generics ::shaku::Module for #module_name #ty_generics #where_clause {
             #[allow(bare_trait_objects)]
-            type Submodules = (#(::std::sync::Arc<#submodule_types>),*);
+            type Submodules = (#(::shaku::__alloc::Arc<#submodule_types>),*);
 
             fn build(mut context: ::shaku::ModuleBuildContext<Self>) -> Self {
+                #deny_unused
+                #report_superseded
+                // The parameters are used up while building, so fingerprint the module first
+                let fingerprint = if context.is_recording_fingerprint() {
+                    ::core::option::Option::Some(#fingerprint)
+                } else {
+                    ::core::option::Option::None
+                };
+                #[allow(bare_trait_objects)]
+                let graph = if context.is_recording_graph() {
+                    ::core::option::Option::Some(::shaku::__alloc::Arc::new(#graph))
+                } else {
+                    ::core::option::Option::None
+                };
                 #submodules_init
 
-                Self {
+                let module = Self {
                     #(#component_builders,)*
                     #(#provider_builders,)*
+                    #(#promoted_builders,)*
+                    #(#factory_builders,)*
+                    #(#subprovider_override_builders,)*
                     #(#submodule_names,)*
+                    #(#bookkeeping_names: #bookkeeping_inits,)*
+                    __di_config_watcher: context.config_watcher(#capture_build_context),
+                    __di_fingerprint: fingerprint,
+                    __di_graph: graph,
                     #build_context_init
+                };
+
+                // Build the promoted providers now that the module exists
+                #(#promoted_inits)*
+
+                module
+            }
+
+            #[allow(unused_mut)]
+            fn parameter_sources() -> ::shaku::__alloc::Vec<&'static str> {
+                let mut keys: ::shaku::__alloc::Vec<&'static str> = ::shaku::__alloc::Vec::new();
+                #(#parameter_sources)*
+                keys
+            }
+        }
+
+        impl #impl_generics ::shaku::debug::GraphSource for #module_name #ty_generics #where_clause {
+            fn module_graph(&self) -> &::shaku::debug::ModuleGraph {
+                ::shaku::debug::recorded_graph(
+                    &self.__di_graph,
+                    ::core::any::type_name::<Self>(),
+                )
+            }
+        }
+    }
+}
+
+/// Create a ModuleServices impl, which registers every service the module exposes
+fn module_services_impl(module: &ModuleData) -> TokenStream {
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+
+    let component_registrations = module.services.components.items.iter().map(|component| {
+        let interface = interface_from_component(&component.ty);
+        let cfg = &component.cfg;
+        let named = component.name.as_ref().map(|name| {
+            let name = name.to_string();
+            quote! { .named(#name) }
+        });
+        let provided = if component.is_provided() {
+            Some(quote! {
+                #(#cfg)*
+                services.provider::<#interface>();
+            })
+        } else {
+            None
+        };
+        quote! {
+            #(#cfg)*
+            services.component::<#interface>()#named;
+            #provided
+        }
+    });
+    let provider_registrations = module.services.providers.items.iter().map(|provider| {
+        let interface = interface_from_provider(&provider.ty);
+        let cfg = &provider.cfg;
+        let named = provider.name.as_ref().map(|name| {
+            let name = name.to_string();
+            quote! { .named(#name) }
+        });
+        let promoted = if provider.is_promoted() {
+            Some(quote! {
+                #(#cfg)*
+                services.component::<#interface>();
+            })
+        } else {
+            None
+        };
+        quote! {
+            #(#cfg)*
+            services.provider::<#interface>()#named;
+            #promoted
+        }
+    });
+    let subcomponent_interfaces = module
+        .submodules
+        .iter()
+        .flat_map(|submodule| submodule.services.components.items.iter())
+        .map(|component| &component.ty);
+    let subprovider_interfaces = module
+        .submodules
+        .iter()
+        .flat_map(|submodule| submodule.services.providers.items.iter())
+        .map(|provider| &provider.ty);
+    let factory_interfaces = submodule_factories(module)
+        .into_iter()
+        .map(|(_, provider_ty)| provider_ty);
+
+    quote! {
+        impl #impl_generics ::shaku::ModuleServices for #module_name #ty_generics #where_clause {
+            #[allow(bare_trait_objects)]
+            fn register_services(services: &mut ::shaku::ServiceRegistry<Self>) {
+                #(#component_registrations)*
+                #(#provider_registrations)*
+                #(services.component::<#subcomponent_interfaces>();)*
+                #(services.provider::<#subprovider_interfaces>();)*
+                #(services.component::<dyn ::shaku::Factory<#factory_interfaces>>();)*
+            }
+        }
+    }
+}
+
+/// Pass the module to the extended module's macro (generated via
+/// `#[shaku(extendable)]`), which passes it back with the extended module's
+/// services. The macro is exported at the root of the extended module's crate.
+fn extend_module(base: &Type, input: TokenStream) -> syn::Result<TokenStream> {
+    let path = match base {
+        Type::Path(type_path) if type_path.qself.is_none() => &type_path.path,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                base,
+                "The extended module must be a path, ex. `other_crate::BaseModule`",
+            ))
+        }
+    };
+    let base_ident = &path.segments.last().unwrap().ident;
+    let macro_name = extension_macro_name(base_ident);
+
+    // Modules from the current crate are found via the macro's textual scope
+    let first_segment = &path.segments.first().unwrap().ident;
+    let macro_path = if path.segments.len() == 1
+        || first_segment == "crate"
+        || first_segment == "self"
+        || first_segment == "super"
+    {
+        quote! { #macro_name }
+    } else {
+        let leading_colon = &path.leading_colon;
+        quote! { #leading_colon #first_segment::#macro_name }
+    };
+
+    Ok(quote! {
+        #macro_path! { #base; #input }
+    })
+}
+
+/// Create the impls and macro which allow other modules to extend this module
+/// if it was annotated with `#[shaku(extendable)]`. The macro passes the
+/// services back to the `module` macro of the extending module (see
+/// `extend_module`). The services are named via the `Exported*` traits, since
+/// the implementations may not be visible to the extending module.
+fn module_extension(module: &ModuleData) -> Option<TokenStream> {
+    module.metadata.options.extendable.as_ref()?;
+
+    let module_name = &module.metadata.identifier;
+
+    // The interfaces are projected via the extending module, so they can be
+    // normalized while checking the extending module's impls for overlap
+    let extension = quote! { __ShakuExtension };
+    let exported_components: Vec<(TokenStream, Option<TokenStream>)> = module
+        .services
+        .components
+        .items
+        .iter()
+        .map(|component| {
+            let component_ty = &component.ty;
+            (
+                quote! { <#component_ty as ::shaku::Component<#extension>>::Interface },
+                Some(quote! { #component_ty: ::shaku::Component<#extension> }),
+            )
+        })
+        .chain(
+            module
+                .submodules
+                .iter()
+                .flat_map(|submodule| submodule.services.components.items.iter())
+                .map(|component| (component.ty.to_token_stream(), None)),
+        )
+        .collect();
+    let exported_providers: Vec<(TokenStream, Option<TokenStream>)> = module
+        .services
+        .providers
+        .items
+        .iter()
+        .map(|provider| {
+            let provider_ty = &provider.ty;
+            (
+                quote! { <#provider_ty as ::shaku::Provider<#extension>>::Interface },
+                Some(quote! { #provider_ty: ::shaku::Provider<#extension> }),
+            )
+        })
+        .chain(
+            module
+                .submodules
+                .iter()
+                .flat_map(|submodule| submodule.services.providers.items.iter())
+                .map(|provider| (provider.ty.to_token_stream(), None)),
+        )
+        .collect();
+
+    let component_indices: Vec<TokenStream> =
+        (0..exported_components.len()).map(type_index).collect();
+    let provider_indices: Vec<TokenStream> =
+        (0..exported_providers.len()).map(type_index).collect();
+    let component_impls =
+        exported_components
+            .iter()
+            .zip(&component_indices)
+            .map(|((interface, bound), index)| {
+                let trait_ty = quote! { ::shaku::ExportedComponent<#index, #extension> };
+                exported_service_impl(module, trait_ty, interface, bound.as_ref())
+            });
+    let provider_impls =
+        exported_providers
+            .iter()
+            .zip(&provider_indices)
+            .map(|((interface, bound), index)| {
+                let trait_ty = quote! { ::shaku::ExportedProvider<#index, #extension> };
+                exported_service_impl(module, trait_ty, interface, bound.as_ref())
+            });
+    let macro_name = extension_macro_name(module_name);
+
+    Some(quote! {
+        #(#component_impls)*
+        #(#provider_impls)*
+
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #macro_name {
+            ($base:ty; $($module:tt)*) => {
+                ::shaku::module! {
+                    @extends_services $base {
+                        components = [
+                            #(<$base as ::shaku::ExportedComponent<#component_indices, Self>>::Interface),*
+                        ],
+                        providers = [
+                            #(<$base as ::shaku::ExportedProvider<#provider_indices, Self>>::Interface),*
+                        ]
+                    }
+                    $($module)*
+                }
+            };
+        }
+    })
+}
+
+/// Create an `Exported*` impl for one of the module's services
+fn exported_service_impl(
+    module: &ModuleData,
+    trait_ty: TokenStream,
+    interface: &TokenStream,
+    bound: Option<&TokenStream>,
+) -> TokenStream {
+    let module_name = &module.metadata.identifier;
+    let (_, ty_generics, _) = module.metadata.generics.split_for_impl();
+    let generic_params = &module.metadata.generics.params;
+    let where_predicates = module
+        .metadata
+        .generics
+        .where_clause
+        .iter()
+        .flat_map(|where_clause| where_clause.predicates.iter());
+
+    quote! {
+        #[allow(bare_trait_objects)]
+        impl<__ShakuExtension: ::shaku::Module, #generic_params> #trait_ty for #module_name #ty_generics
+        where
+            #(#where_predicates,)*
+            #bound
+        {
+            type Interface = #interface;
+        }
+    }
+}
+
+/// Create a type which represents the index, ex. `[(); 2]`
+fn type_index(index: usize) -> TokenStream {
+    let index = Literal::usize_unsuffixed(index);
+    quote! { [(); #index] }
+}
+
+/// The name of the macro generated for a module with `#[shaku(extendable)]`
+fn extension_macro_name(module_name: &Ident) -> Ident {
+    format_ident!("__shaku_extend_{}", module_name)
+}
+
+/// Create a Clone impl if the module was annotated with `#[shaku(derive_clone)]`. All of the
+/// module's properties are `Arc`s, so the clone is cheap and shares the same services.
+fn module_clone_impl(module: &ModuleData) -> Option<TokenStream> {
+    module.metadata.options.derive_clone.as_ref()?;
+
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let no_cfg: &[syn::Attribute] = &[];
+    let properties: Vec<TokenStream> =
+        module
+            .services
+            .components
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, component)| {
+                let name = item_property(i, "component", component);
+                (name, component.cfg.as_slice())
+            })
+            .chain(
+                module
+                    .services
+                    .providers
+                    .items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, provider)| {
+                        let name = item_property(i, "provider", provider);
+                        (name, provider.cfg.as_slice())
+                    }),
+            )
+            .chain(
+                submodule_names(&module.submodules)
+                    .into_iter()
+                    .map(|name| (name, no_cfg)),
+            )
+            .chain(submodule_factories(module).into_iter().enumerate().map(
+                |(i, (_, provider_ty))| (generate_name(i, "factory", provider_ty.span()), no_cfg),
+            ))
+            .map(|(name, cfg)| {
+                quote! {
+                    #(#cfg)*
+                    #name: ::core::clone::Clone::clone(&self.#name),
+                }
+            })
+            .collect();
+    let promoted_properties = module
+        .services
+        .providers
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, provider)| provider.is_promoted())
+        .map(|(i, provider)| {
+            let name = promoted_property_name(i, provider);
+            let cfg = &provider.cfg;
+            quote! {
+                #(#cfg)*
+                #name: ::core::clone::Clone::clone(&self.#name),
+            }
+        });
+    let subprovider_override_properties =
+        subproviders(module)
+            .into_iter()
+            .enumerate()
+            .map(|(i, (_, provider_ty))| {
+                let name = generate_name(i, "subprovider_override", provider_ty.span());
+                quote! {
+                    #name: ::core::clone::Clone::clone(&self.#name),
                 }
+            });
+
+    let bookkeeping = bookkeeping_fields();
+    let bookkeeping_names: Vec<&Ident> = bookkeeping.iter().map(|field| &field.0).collect();
+
+    Some(quote! {
+        impl #impl_generics ::core::clone::Clone for #module_name #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                Self {
+                    #(#properties)*
+                    #(#promoted_properties)*
+                    #(#subprovider_override_properties)*
+                    #(#bookkeeping_names: ::core::clone::Clone::clone(&self.#bookkeeping_names),)*
+                    __di_config_watcher: ::core::clone::Clone::clone(&self.__di_config_watcher),
+                    __di_fingerprint: self.__di_fingerprint,
+                    __di_graph: ::core::clone::Clone::clone(&self.__di_graph),
+                }
+            }
+        }
+    })
+}
+
+/// Implement `ResolveMetrics` for the module if the `metrics` feature of shaku is turned on
+fn module_metrics_impl(module: &ModuleData) -> Option<TokenStream> {
+    if !cfg!(feature = "metrics") {
+        return None;
+    }
+
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+
+    Some(quote! {
+        impl #impl_generics ::shaku::ResolveMetrics for #module_name #ty_generics #where_clause {
+            fn metrics(&self) -> ::shaku::ModuleMetrics {
+                self.__di_metrics.metrics()
+            }
+        }
+    })
+}
+
+/// Implement `ModuleFingerprint` for the module
+fn module_fingerprint_impl(module: &ModuleData) -> TokenStream {
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::shaku::ModuleFingerprint for #module_name #ty_generics #where_clause {
+            fn fingerprint(&self) -> ::core::option::Option<u64> {
+                self.__di_fingerprint
+            }
+        }
+    }
+}
+
+/// Implement `ModuleBuildOrder` for the module
+fn module_build_order_impl(module: &ModuleData) -> TokenStream {
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let component_dependencies = component_dependencies(module);
+
+    quote! {
+        impl #impl_generics ::shaku::ModuleBuildOrder for #module_name #ty_generics #where_clause {
+            #[allow(unused_mut)]
+            fn build_order() -> ::shaku::__alloc::Vec<&'static str> {
+                let mut components: ::shaku::__alloc::Vec<::shaku::ComponentDependencies> =
+                    ::shaku::__alloc::Vec::new();
+                #(#component_dependencies)*
+                ::shaku::build_order(components)
+            }
+        }
+    }
+}
+
+/// Implement `ReconfigurableModule` for the module
+fn module_reconfigurable_impl(module: &ModuleData) -> TokenStream {
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::shaku::ReconfigurableModule for #module_name #ty_generics #where_clause {
+            // The generic is prefixed to avoid clashing with the module's generics
+            fn apply_parameters<__C>(&self, params: __C::Parameters) -> bool
+            where
+                __C: ::shaku::Component<Self>
+                    + ::shaku::Reconfigurable<<__C as ::shaku::Component<Self>>::Parameters>,
+            {
+                self.__di_config_watcher.apply::<Self, __C>(params)
             }
         }
     }
@@ -212,40 +1180,490 @@ fn module_builder(module: &ModuleData) -> TokenStream {
     let submodule_types: Vec<&Type> = module.submodules.iter().map(|s| &s.ty).collect();
     let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
 
-    quote! {
+    let unreachable_services = module_unreachable_services(module);
+
+    quote! {
+        impl #impl_generics #module_name #ty_generics #where_clause {
+            #[allow(bare_trait_objects)]
+            #visibility fn builder(
+                #(#submodule_names: ::shaku::__alloc::Arc<#submodule_types>),*
+            ) -> ::shaku::ModuleBuilder<Self> {
+                ::shaku::ModuleBuilder::with_submodules((#(#submodule_names),*))
+            }
+
+            #unreachable_services
+        }
+    }
+}
+
+/// Push each component's `ComponentDependencies` to `components`
+fn component_dependencies(module: &ModuleData) -> Vec<TokenStream> {
+    module
+        .services
+        .components
+        .items
+        .iter()
+        .map(|component| {
+            let component_ty = &component.ty;
+            let interface = interface_from_component(component_ty);
+            let lazy = component.is_lazy();
+            let cfg = &component.cfg;
+
+            quote! {
+                #(#cfg)*
+                components.push((
+                    ::core::any::type_name::<#interface>(),
+                    #lazy,
+                    <#component_ty as ::shaku::Component<Self>>::dependencies(),
+                ));
+            }
+        })
+        .collect()
+}
+
+/// Implement `ModuleMemoryFootprint` for the module if it was annotated with
+/// `#[shaku(memory_footprint)]`. This estimates the memory held by the components.
+fn module_memory_footprint_impl(module: &ModuleData) -> Option<TokenStream> {
+    module.metadata.options.memory_footprint.as_ref()?;
+
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let components = module
+        .services
+        .components
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, component)| {
+            let property = item_property(i, "component", component);
+            let interface = interface_from_component(&component.ty);
+            let cfg = &component.cfg;
+            let component_ref = if component.is_lazy() {
+                quote! { self.#property.get() }
+            } else {
+                quote! { ::core::option::Option::Some(&self.#property) }
+            };
+
+            quote! {
+                #(#cfg)*
+                {
+                    let component: ::core::option::Option<&::shaku::__alloc::Arc<#interface>> =
+                        #component_ref;
+                    let component: ::core::option::Option<&#interface> =
+                        component.map(|component| &**component);
+                    report.push(
+                        ::core::any::type_name::<#interface>(),
+                        component.map(::core::mem::size_of_val),
+                        component
+                            .and_then(|component| (&::shaku::FootprintProbe(component)).heap_size()),
+                    );
+                }
+            }
+        });
+
+    Some(quote! {
+        impl #impl_generics ::shaku::ModuleMemoryFootprint for #module_name #ty_generics #where_clause {
+            #[allow(unused_mut)]
+            fn memory_footprint(&self) -> ::shaku::MemoryReport {
+                #[allow(unused_imports)]
+                use ::shaku::{ReportHeapSize as _, UnknownHeapSize as _};
+                let mut report = ::shaku::MemoryReport::new();
+                #(#components)*
+                report
+            }
+        }
+    })
+}
+
+/// Implement `RebuildModule` for the module, which builds the module again while reusing the
+/// components which are not affected by the builder's parameters and overrides
+fn module_rebuild_impl(module: &ModuleData) -> TokenStream {
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let components = &module.services.components.items;
+    let component_dependencies = component_dependencies(module);
+    let changed = components.iter().map(|component| {
+        let component_ty = &component.ty;
+        let interface = interface_from_component(component_ty);
+        let cfg = &component.cfg;
+
+        quote! {
+            #(#cfg)*
+            {
+                if builder.is_component_configured::<#component_ty>()
+                    || <#component_ty as ::shaku::Component<Self>>::ALWAYS_REBUILD
+                {
+                    changed.push(::core::any::type_name::<#interface>());
+                }
+            }
+        }
+    });
+    let reused = components.iter().enumerate().map(|(i, component)| {
+        let property = item_property(i, "component", component);
+        let interface = interface_from_component(&component.ty);
+        let cfg = &component.cfg;
+        let component_ref = if component.is_lazy() {
+            quote! { self.#property.get() }
+        } else {
+            quote! { ::core::option::Option::Some(&self.#property) }
+        };
+
+        quote! {
+            #(#cfg)*
+            {
+                let component: ::core::option::Option<&::shaku::__alloc::Arc<#interface>> =
+                    #component_ref;
+                match component {
+                    ::core::option::Option::Some(component)
+                        if !rebuilt.contains(::core::any::type_name::<#interface>()) =>
+                    {
+                        builder = builder
+                            .reuse_component::<#interface>(::core::clone::Clone::clone(component));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::shaku::RebuildModule for #module_name #ty_generics #where_clause {
+            #[allow(unused_mut)]
+            fn reuse_components(
+                &self,
+                builder: ::shaku::ModuleBuilder<Self>,
+            ) -> ::shaku::ModuleBuilder<Self> {
+                let mut builder = builder;
+                let mut components: ::shaku::__alloc::Vec<::shaku::ComponentDependencies> =
+                    ::shaku::__alloc::Vec::new();
+                #(#component_dependencies)*
+                let mut changed: ::shaku::__alloc::Vec<&'static str> =
+                    ::shaku::__alloc::Vec::new();
+                #(#changed)*
+                let rebuilt = ::shaku::rebuilt_components(components, changed);
+                #(#reused)*
+                builder
+            }
+        }
+    }
+}
+
+/// Create the `unreachable_services` function if the module has a module interface. A service is
+/// reachable if the module interface exports it, or a reachable service depends on it.
+fn module_unreachable_services(module: &ModuleData) -> Option<TokenStream> {
+    let module_trait = module.metadata.interface.as_ref()?;
+    let visibility = &module.metadata.visibility;
+    let exported = |interface: &TokenStream| {
+        quote! {
+            {
+                let probe = &::shaku::ExportProbe::<dyn #module_trait, #interface>::new();
+                probe.exports_component() || probe.exports_provider()
+            }
+        }
+    };
+    let components = module.services.components.items.iter().map(|component| {
+        let component_ty = &component.ty;
+        let interface = interface_from_component(component_ty);
+        let exported = exported(&interface);
+        let cfg = &component.cfg;
+
+        quote! {
+            #(#cfg)*
+            services.push((
+                ::core::any::type_name::<#interface>(),
+                #exported,
+                <#component_ty as ::shaku::Component<Self>>::dependencies(),
+            ));
+        }
+    });
+    let providers = module.services.providers.items.iter().map(|provider| {
+        let provider_ty = &provider.ty;
+        let interface = interface_from_provider(provider_ty);
+        let exported = exported(&interface);
+        let cfg = &provider.cfg;
+
+        quote! {
+            #(#cfg)*
+            services.push((
+                ::core::any::type_name::<#interface>(),
+                #exported,
+                <#provider_ty as ::shaku::Provider<Self>>::dependencies(),
+            ));
+        }
+    });
+
+    Some(quote! {
+        /// The interface type names of the services which are not reachable from the module
+        /// interface: they are not exported by it, and no reachable service depends on them.
+        /// Services which are only resolved dynamically (ex. via a `ModuleHandle`) are not
+        /// tracked.
+        #[allow(dead_code, unused_mut)]
+        #visibility fn unreachable_services() -> ::shaku::__alloc::Vec<&'static str> {
+            #[allow(unused_imports)]
+            use ::shaku::{ExportsComponent as _, ExportsProvider as _, NotExported as _};
+            let mut services: ::shaku::__alloc::Vec<::shaku::ServiceReachability> =
+                ::shaku::__alloc::Vec::new();
+            #(#components)*
+            #(#providers)*
+            ::shaku::unreachable_services(services)
+        }
+    })
+}
+
+/// Create the `strict_builder` function and the parameter state transitions if
+/// the module was annotated with `#[shaku(strict_builder)]`. The state is a
+/// list of the form `(S0, (S1, ()))` with one entry per component.
+fn module_strict_builder(module: &ModuleData) -> Option<TokenStream> {
+    module.metadata.options.strict_builder.as_ref()?;
+
+    let module_name = &module.metadata.identifier;
+    let visibility = &module.metadata.visibility;
+    let submodule_names = submodule_names(&module.submodules);
+    let submodule_types: Vec<&Type> = module.submodules.iter().map(|s| &s.ty).collect();
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let generic_params = &module.metadata.generics.params;
+    let where_predicates: Vec<_> = module
+        .metadata
+        .generics
+        .where_clause
+        .iter()
+        .flat_map(|where_clause| where_clause.predicates.iter())
+        .collect();
+    let components = &module.services.components.items;
+
+    let state_list = |states: Vec<TokenStream>| {
+        states
+            .into_iter()
+            .rev()
+            .fold(quote! { () }, |list, state| quote! { (#state, #list) })
+    };
+    let initial_state = state_list(
+        components
+            .iter()
+            .map(|component| {
+                let component_ty = &component.ty;
+                quote! {
+                    <<#component_ty as ::shaku::Component<#module_name #ty_generics>>::Parameters
+                        as ::shaku::RequiredParameters>::State
+                }
+            })
+            .collect(),
+    );
+    let state_params: Vec<Ident> = (0..components.len())
+        .map(|i| format_ident!("__ShakuState{}", i))
+        .collect();
+    let current_state = state_list(state_params.iter().map(|p| quote! { #p }).collect());
+    let transitions = components.iter().enumerate().map(|(i, component)| {
+        let component_ty = &component.ty;
+        let next_state = state_list(
+            state_params
+                .iter()
+                .enumerate()
+                .map(|(j, param)| {
+                    if i == j {
+                        quote! { ::shaku::ParametersSet }
+                    } else {
+                        quote! { #param }
+                    }
+                })
+                .collect(),
+        );
+
+        quote! {
+            impl<#(#state_params,)* #generic_params> ::shaku::SetComponentParameters<#component_ty>
+                for ::shaku::StrictModuleBuilder<#module_name #ty_generics, #current_state>
+            where
+                #(#where_predicates,)*
+            {
+                type State = #next_state;
+            }
+        }
+    });
+
+    Some(quote! {
         impl #impl_generics #module_name #ty_generics #where_clause {
+            /// Create a builder which requires the parameters of each component without
+            /// defaults to be set before the module can be built
             #[allow(bare_trait_objects)]
-            #visibility fn builder(
-                #(#submodule_names: ::std::sync::Arc<#submodule_types>),*
-            ) -> ::shaku::ModuleBuilder<Self> {
-                ::shaku::ModuleBuilder::with_submodules((#(#submodule_names),*))
+            #visibility fn strict_builder(
+                #(#submodule_names: ::shaku::__alloc::Arc<#submodule_types>),*
+            ) -> ::shaku::StrictModuleBuilder<Self, #initial_state> {
+                ::shaku::StrictModuleBuilder::new(Self::builder(#(#submodule_names),*))
             }
         }
-    }
+
+        #(#transitions)*
+    })
 }
 
 /// Create a property initializer for the component during module build
 fn component_build(index: usize, component: &ComponentItem) -> TokenStream {
-    let property = generate_name(index, "component", component.ty.span());
+    let property = item_property(index, "component", component);
     let interface = interface_from_component(&component.ty);
+    let cfg = &component.cfg;
 
     if component.is_lazy() {
         quote! {
+            #(#cfg)*
             #property: ::shaku::OnceCell::new()
         }
     } else {
         quote! {
+            #(#cfg)*
             #property: <Self as ::shaku::HasComponent<#interface>>::build_component(&mut context)
         }
     }
 }
 
 /// Create a property initializer for the provider during module build
-fn provider_build(index: usize, provider_ty: &Type) -> TokenStream {
-    let property = generate_name(index, "provider", provider_ty.span());
+fn provider_build(index: usize, provider: &ProviderItem) -> TokenStream {
+    let provider_ty = &provider.ty;
+    let property = item_property(index, "provider", provider);
+    let cfg = &provider.cfg;
+
+    quote! {
+        #(#cfg)*
+        #property: context.module_provider_fn::<#provider_ty>()
+    }
+}
+
+/// Create an expression which hashes the module's services and component parameters (see
+/// `FingerprintHasher`). This must run before the components are built, since building a component
+/// removes its parameters from the build context.
+fn module_fingerprint(module: &ModuleData) -> TokenStream {
+    let components = module.services.components.items.iter().map(|component| {
+        let component_ty = &component.ty;
+        let interface = interface_from_component(component_ty);
+        let cfg = &component.cfg;
+
+        quote! {
+            #(#cfg)*
+            {
+                hasher.write_service(
+                    ::core::any::type_name::<#component_ty>(),
+                    context.is_component_overridden::<#interface>(),
+                );
+                (&::shaku::ParametersFingerprint(context.parameters::<#component_ty>()))
+                    .hash_parameters(&mut hasher);
+            }
+        }
+    });
+    let providers = module.services.providers.items.iter().map(|provider| {
+        let provider_ty = &provider.ty;
+        let interface = interface_from_provider(provider_ty);
+        let cfg = &provider.cfg;
+
+        quote! {
+            #(#cfg)*
+            hasher.write_service(
+                ::core::any::type_name::<#provider_ty>(),
+                context.is_provider_overridden::<#interface>(),
+            );
+        }
+    });
+
+    quote! {
+        {
+            #[allow(unused_imports)]
+            use ::shaku::{HashParameters as _, SkipParameters as _};
+            #[allow(unused_mut)]
+            let mut hasher = ::shaku::FingerprintHasher::new();
+            #(#components)*
+            #(#providers)*
+            ::core::hash::Hasher::finish(&hasher)
+        }
+    }
+}
+
+/// Create an expression which records the module's graph (see `shaku::debug`) while the module
+/// is built, if requested via `ModuleBuilder::record_graph`. Like the fingerprint, this must
+/// happen before the parameters are used up.
+fn module_graph(module: &ModuleData) -> TokenStream {
+    let components = module.services.components.items.iter().map(|component| {
+        let component_ty = &component.ty;
+        let interface = interface_from_component(component_ty);
+        let cfg = &component.cfg;
+
+        quote! {
+            #(#cfg)*
+            graph.push(::shaku::debug::GraphService {
+                interface: ::core::any::type_name::<#interface>(),
+                kind: ::shaku::ServiceKind::Component,
+                implementation: ::core::any::type_name::<#component_ty>(),
+                from_submodule: false,
+                overridden: context.is_component_overridden::<#interface>(),
+                parameters: (&::shaku::debug::ParametersDebug(
+                    context.parameters::<#component_ty>(),
+                ))
+                    .debug_parameters(),
+            });
+        }
+    });
+    let providers = module.services.providers.items.iter().map(|provider| {
+        let provider_ty = &provider.ty;
+        let interface = interface_from_provider(provider_ty);
+        let cfg = &provider.cfg;
+
+        quote! {
+            #(#cfg)*
+            graph.push(::shaku::debug::GraphService {
+                interface: ::core::any::type_name::<#interface>(),
+                kind: ::shaku::ServiceKind::Provider,
+                implementation: ::core::any::type_name::<#provider_ty>(),
+                from_submodule: false,
+                overridden: context.is_provider_overridden::<#interface>(),
+                parameters: ::core::option::Option::None,
+            });
+        }
+    });
+    let subservices = module.submodules.iter().map(|submodule| {
+        let submodule_ty = &submodule.ty;
+        let components = submodule.services.components.items.iter().map(|component| {
+            let component_ty = &component.ty;
+
+            quote! {
+                graph.push(::shaku::debug::GraphService {
+                    interface: ::core::any::type_name::<#component_ty>(),
+                    kind: ::shaku::ServiceKind::Component,
+                    implementation: ::core::any::type_name::<#submodule_ty>(),
+                    from_submodule: true,
+                    overridden: false,
+                    parameters: ::core::option::Option::None,
+                });
+            }
+        });
+        let providers = submodule.services.providers.items.iter().map(|provider| {
+            let provider_ty = &provider.ty;
+
+            quote! {
+                graph.push(::shaku::debug::GraphService {
+                    interface: ::core::any::type_name::<#provider_ty>(),
+                    kind: ::shaku::ServiceKind::Provider,
+                    implementation: ::core::any::type_name::<#submodule_ty>(),
+                    from_submodule: true,
+                    overridden: context.is_provider_overridden::<#provider_ty>(),
+                    parameters: ::core::option::Option::None,
+                });
+            }
+        });
+
+        quote! {
+            #(#components)*
+            #(#providers)*
+        }
+    });
 
     quote! {
-        #property: context.provider_fn::<#provider_ty>()
+        {
+            #[allow(unused_imports)]
+            use ::shaku::debug::{DebugParameters as _, SkipDebugParameters as _};
+            #[allow(unused_mut)]
+            let mut graph = ::shaku::debug::ModuleGraph::new();
+            #(#components)*
+            #(#providers)*
+            #(#subservices)*
+            graph
+        }
     }
 }
 
@@ -260,34 +1678,51 @@ fn submodules_init(submodules: &Punctuated<Submodule, syn::Token![,]>) -> TokenS
     quote! {
         let (#(#names),*) = context.submodules();
         #(
-        let #names = ::std::sync::Arc::clone(#names);
+        let #names = ::shaku::__alloc::Arc::clone(#names);
         )*
     }
 }
 
 /// Create the property which holds a component instance
 fn component_property(index: usize, component: &ComponentItem) -> TokenStream {
-    let property = generate_name(index, "component", component.ty.span());
+    let property = item_property(index, "component", component);
     let interface = interface_from_component(&component.ty);
+    let cfg = &component.cfg;
 
     if component.is_lazy() {
         quote! {
-            #property: ::shaku::OnceCell<::std::sync::Arc<#interface>>
+            #(#cfg)*
+            #property: ::shaku::OnceCell<::shaku::__alloc::Arc<#interface>>
         }
     } else {
         quote! {
-            #property: ::std::sync::Arc<#interface>
+            #(#cfg)*
+            #property: ::shaku::__alloc::Arc<#interface>
         }
     }
 }
 
 /// Create the property which holds a provider function
-fn provider_property(index: usize, provider_ty: &Type) -> TokenStream {
-    let property = generate_name(index, "provider", provider_ty.span());
-    let interface = interface_from_provider(provider_ty);
+fn provider_property(index: usize, provider: &ProviderItem) -> TokenStream {
+    let property = item_property(index, "provider", provider);
+    let interface = interface_from_provider(&provider.ty);
+    let cfg = &provider.cfg;
+
+    quote! {
+        #(#cfg)*
+        #property: ::shaku::ModuleProviderFn<Self, #interface>
+    }
+}
+
+/// Create the property which holds the service of a provider marked with `#[promote_to_component]`
+fn promoted_property(index: usize, provider: &ProviderItem) -> TokenStream {
+    let property = promoted_property_name(index, provider);
+    let interface = interface_from_provider(&provider.ty);
+    let cfg = &provider.cfg;
 
     quote! {
-        #property: ::std::sync::Arc<::shaku::ProviderFn<Self, #interface>>
+        #(#cfg)*
+        #property: ::shaku::OnceCell<::shaku::__alloc::Arc<#interface>>
     }
 }
 
@@ -298,22 +1733,23 @@ fn submodule_property(index: usize, submodule: &Submodule) -> TokenStream {
 
     quote! {
         #[allow(bare_trait_objects)]
-        #property: ::std::sync::Arc<#submodule_ty>
+        #property: ::shaku::__alloc::Arc<#submodule_ty>
     }
 }
 
 /// Create a HasComponent impl
 fn has_component_impl(index: usize, component: &ComponentItem, module: &ModuleData) -> TokenStream {
     let component_ty = &component.ty;
-    let property = generate_name(index, "component", component_ty.span());
+    let property = item_property(index, "component", component);
     let interface = interface_from_component(component_ty);
     let module_name = &module.metadata.identifier;
     let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let cfg = &component.cfg;
 
     let get_ref_code = if component.is_lazy() {
         quote! {
             let component = self.#property.get_or_init(|| {
-                let mut context = self.build_context.lock().unwrap();
+                let mut context = self.build_context.lock();
                 <Self as ::shaku::HasComponent<#interface>>::build_component(&mut *context)
             });
         }
@@ -321,46 +1757,254 @@ fn has_component_impl(index: usize, component: &ComponentItem, module: &ModuleDa
         quote! { let component = &self.#property; }
     };
 
+    let report_deprecated = component.deprecation().map(|note| {
+        let module_name = module_name.to_string();
+        let note = match note {
+            Some(note) => quote! { Some(#note) },
+            None => quote! { None },
+        };
+
+        quote! {
+            ::shaku::report_deprecated_resolution(
+                #module_name,
+                ::core::any::type_name::<#interface>(),
+                #note,
+            );
+        }
+    });
+
+    let thread_check = thread_check(&interface);
+    let record_use = record_use(&interface);
+    let provider_bridge = if component.is_provided() {
+        Some(component_provider_bridge(component, module))
+    } else {
+        None
+    };
+    let resolve_mut_code = if component.is_lazy() {
+        None
+    } else {
+        Some(quote! {
+            fn resolve_mut(&mut self) -> ::core::option::Option<&mut #interface> {
+                #thread_check
+                #record_use
+                #report_deprecated
+                ::shaku::__alloc::Arc::get_mut(&mut self.#property)
+            }
+        })
+    };
+
+    let built_component_ty = if component.is_instrumented() {
+        quote! { ::shaku::Instrumented<#component_ty> }
+    } else {
+        quote! { #component_ty }
+    };
+
+    let lazy_note = if component.is_lazy() {
+        " It is built when it is first resolved."
+    } else {
+        ""
+    };
+    let instrument_note = if component.is_instrumented() {
+        " Its method calls are traced."
+    } else {
+        ""
+    };
+    let docs = service_docs(
+        format!(
+            "Resolved from the component `{}`.{}{}",
+            service_name(component_ty),
+            lazy_note,
+            instrument_note
+        ),
+        &component.docs,
+    );
+
     quote! {
+        #(#cfg)*
+        #docs
         impl #impl_generics ::shaku::HasComponent<#interface> for #module_name #ty_generics #where_clause {
             fn build_component(
                 context: &mut ::shaku::ModuleBuildContext<Self>
-            ) -> ::std::sync::Arc<#interface> {
-                context.build_component::<#component_ty>()
+            ) -> ::shaku::__alloc::Arc<#interface> {
+                context.build_component::<#built_component_ty>()
             }
 
-            fn resolve(&self) -> ::std::sync::Arc<#interface> {
+            fn resolve(&self) -> ::shaku::__alloc::Arc<#interface> {
+                #thread_check
+                #record_use
+                #report_deprecated
                 #get_ref_code
-                ::std::sync::Arc::clone(component)
+                ::shaku::__alloc::Arc::clone(component)
             }
 
             fn resolve_ref(&self) -> &#interface {
+                #thread_check
+                #record_use
+                #report_deprecated
                 #get_ref_code
-                ::std::sync::Arc::as_ref(component)
+                ::shaku::__alloc::Arc::as_ref(component)
+            }
+
+            #resolve_mut_code
+        }
+
+        #provider_bridge
+    }
+}
+
+/// Create a HasProvider impl for a component marked with `#[as_provider]`, which provides the
+/// shared component. This requires the interface to be implemented for `Arc<dyn Interface>`.
+fn component_provider_bridge(component: &ComponentItem, module: &ModuleData) -> TokenStream {
+    let interface = interface_from_component(&component.ty);
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let cfg = &component.cfg;
+    let component_ty = &component.ty;
+    let provided = quote_spanned! {component_ty.span()=>
+        ::shaku::__alloc::Box::new(component)
+    };
+
+    let docs = service_docs(
+        format!(
+            "Provides the shared component `{}`.",
+            service_name(component_ty)
+        ),
+        &component.docs,
+    );
+
+    quote! {
+        #(#cfg)*
+        #docs
+        impl #impl_generics ::shaku::HasProvider<#interface> for #module_name #ty_generics #where_clause {
+            fn provide(&self) -> ::core::result::Result<
+                ::shaku::__alloc::Box<#interface>,
+                ::shaku::__alloc::Box<dyn ::shaku::Error>
+            > {
+                let component = <Self as ::shaku::HasComponent<#interface>>::resolve(self);
+                ::core::result::Result::Ok(#provided)
             }
         }
     }
 }
 
 /// Create a HasProvider impl
-fn has_provider_impl(index: usize, provider_ty: &Type, module: &ModuleData) -> TokenStream {
-    let property = generate_name(index, "provider", provider_ty.span());
-    let interface = interface_from_provider(provider_ty);
+fn has_provider_impl(index: usize, provider: &ProviderItem, module: &ModuleData) -> TokenStream {
+    let property = item_property(index, "provider", provider);
+    let interface = interface_from_provider(&provider.ty);
+    let cfg = &provider.cfg;
     let module_name = &module.metadata.identifier;
     let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let thread_check = thread_check(&interface);
+    let record_use = record_use(&interface);
+    let component_bridge = if provider.is_promoted() {
+        Some(promoted_component_impl(index, provider, module))
+    } else {
+        None
+    };
+
+    let docs = service_docs(
+        format!("Provided by the provider `{}`.", service_name(&provider.ty)),
+        &provider.docs,
+    );
 
     quote! {
+        #(#cfg)*
+        #docs
         impl #impl_generics ::shaku::HasProvider<#interface> for #module_name #ty_generics #where_clause {
-            fn provide(&self) -> ::std::result::Result<
-                ::std::boxed::Box<#interface>,
-                ::std::boxed::Box<dyn ::std::error::Error>
+            fn provide(&self) -> ::core::result::Result<
+                ::shaku::__alloc::Box<#interface>,
+                ::shaku::__alloc::Box<dyn ::shaku::Error>
             > {
-                (self.#property)(self)
+                #thread_check
+                #record_use
+                self.#property.provide(self)
+            }
+        }
+
+        #component_bridge
+    }
+}
+
+/// Create a HasComponent impl for a provider marked with `#[promote_to_component]`, which resolves
+/// the service built once by the provider. The provider needs the finished module, so the service
+/// is built at the end of the module build and cannot be injected into components.
+fn promoted_component_impl(
+    index: usize,
+    provider: &ProviderItem,
+    module: &ModuleData,
+) -> TokenStream {
+    let property = promoted_property_name(index, provider);
+    let interface = interface_from_provider(&provider.ty);
+    let cfg = &provider.cfg;
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let thread_check = thread_check(&interface);
+    let record_use = record_use(&interface);
+    let init = promoted_init(index, provider, &quote! { self });
+    let docs = service_docs(
+        format!(
+            "Resolved from the provider `{}`, which is promoted to a component.",
+            service_name(&provider.ty)
+        ),
+        &provider.docs,
+    );
+
+    quote! {
+        #(#cfg)*
+        #docs
+        impl #impl_generics ::shaku::HasComponent<#interface> for #module_name #ty_generics #where_clause {
+            fn build_component(
+                _context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::shaku::__alloc::Arc<#interface> {
+                panic!(
+                    "{} is a provider promoted to a component, which is built after the module \
+                     and cannot be injected into components. Inject it into a provider instead.",
+                    ::core::any::type_name::<#interface>()
+                )
+            }
+
+            fn resolve(&self) -> ::shaku::__alloc::Arc<#interface> {
+                #thread_check
+                #record_use
+                ::shaku::__alloc::Arc::clone(#init)
+            }
+
+            fn resolve_ref(&self) -> &#interface {
+                #thread_check
+                #record_use
+                ::shaku::__alloc::Arc::as_ref(#init)
+            }
+
+            fn resolve_mut(&mut self) -> ::core::option::Option<&mut #interface> {
+                #thread_check
+                #record_use
+                ::shaku::__alloc::Arc::get_mut(self.#property.get_mut()?)
             }
         }
     }
 }
 
+/// Get the service of a promoted provider (as `&Arc<Interface>`), building it via the provider if
+/// it was not built yet. The receiver is a reference to the module.
+fn promoted_init(index: usize, provider: &ProviderItem, receiver: &TokenStream) -> TokenStream {
+    let property = promoted_property_name(index, provider);
+    let provider_property = item_property(index, "provider", provider);
+    let provider_ty = &provider.ty;
+
+    quote! {
+        #receiver.#property.get_or_init(|| {
+            match #receiver.#provider_property.provide(#receiver) {
+                ::core::result::Result::Ok(service) => ::shaku::__alloc::Arc::from(service),
+                ::core::result::Result::Err(error) => panic!(
+                    "Unable to promote {} to a component: {}",
+                    stringify!(#provider_ty),
+                    error
+                ),
+            }
+        })
+    }
+}
+
 /// Create a HasComponent impl for a subcomponent
 fn has_subcomponent_impl(
     submodule_index: usize,
@@ -373,53 +2017,352 @@ fn has_subcomponent_impl(
     let submodule_names = submodule_names(&module.submodules);
     let submodule_name = generate_name(submodule_index, "submodule", submodule_ty.span());
     let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let record_use = record_use(&quote! { #component_ty });
+    let docs = service_docs(
+        format!(
+            "Resolved from the submodule `{}`.",
+            service_name(submodule_ty)
+        ),
+        &[],
+    );
 
     quote! {
+        #docs
         #[allow(bare_trait_objects)]
         impl #impl_generics ::shaku::HasComponent<#component_ty> for #module_name #ty_generics #where_clause {
             fn build_component(
                 context: &mut ::shaku::ModuleBuildContext<Self>
-            ) -> ::std::sync::Arc<#component_ty> {
+            ) -> ::shaku::__alloc::Arc<#component_ty> {
+                context.record_dependency::<#component_ty>();
                 let (#(#submodule_names),*) = context.submodules();
                 #submodule_name.resolve()
             }
 
-            fn resolve(&self) -> ::std::sync::Arc<#component_ty> {
+            fn resolve(&self) -> ::shaku::__alloc::Arc<#component_ty> {
+                #record_use
                 self.#submodule_name.resolve()
             }
 
             fn resolve_ref(&self) -> &#component_ty {
+                #record_use
                 self.#submodule_name.resolve_ref()
             }
+
+            fn resolve_mut(&mut self) -> ::core::option::Option<&mut #component_ty> {
+                #record_use
+                ::shaku::__alloc::Arc::get_mut(&mut self.#submodule_name)?.resolve_mut()
+            }
         }
     }
 }
 
-/// Create a HasProvider impl for a subprovider
+/// Create a HasProvider impl for a subprovider. A provider override set on this module's builder
+/// is used instead of the submodule's provider.
 fn has_subprovider_impl(
+    index: usize,
     submodule_index: usize,
-    submodule: &Submodule,
     provider_ty: &Type,
     module: &ModuleData,
 ) -> TokenStream {
     let module_name = &module.metadata.identifier;
-    let submodule_ty = &submodule.ty;
+    let submodule_ty = &module.submodules[submodule_index].ty;
     let submodule_name = generate_name(submodule_index, "submodule", submodule_ty.span());
+    let override_property = generate_name(index, "subprovider_override", provider_ty.span());
     let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let record_use = record_use(&quote! { #provider_ty });
+    let docs = service_docs(
+        format!(
+            "Provided by the submodule `{}`.",
+            service_name(submodule_ty)
+        ),
+        &[],
+    );
 
     quote! {
+        #docs
         #[allow(bare_trait_objects)]
         impl #impl_generics ::shaku::HasProvider<#provider_ty> for #module_name #ty_generics #where_clause {
-            fn provide(&self) -> ::std::result::Result<
-                ::std::boxed::Box<#provider_ty>,
-                ::std::boxed::Box<dyn ::std::error::Error>
+            fn provide(&self) -> ::core::result::Result<
+                ::shaku::__alloc::Box<#provider_ty>,
+                ::shaku::__alloc::Box<dyn ::shaku::Error>
             > {
-                ::shaku::HasProvider::provide(::std::sync::Arc::as_ref(&self.#submodule_name))
+                #record_use
+                if let ::core::option::Option::Some(provider_fn) = &self.#override_property {
+                    return provider_fn(self);
+                }
+                ::shaku::HasProvider::provide(::shaku::__alloc::Arc::as_ref(&self.#submodule_name))
+            }
+        }
+    }
+}
+
+/// Get the providers of every submodule, along with the index of the submodule
+fn subproviders(module: &ModuleData) -> Vec<(usize, &Type)> {
+    module
+        .submodules
+        .iter()
+        .enumerate()
+        .flat_map(|(i, submodule)| {
+            submodule
+                .services
+                .providers
+                .items
+                .iter()
+                .map(move |provider| (i, &provider.ty))
+        })
+        .collect()
+}
+
+/// Create the property which holds the override of a subprovider, if one was set on the builder
+fn subprovider_override_property(index: usize, provider_ty: &Type) -> TokenStream {
+    let property = generate_name(index, "subprovider_override", provider_ty.span());
+
+    quote! {
+        #[allow(bare_trait_objects)]
+        #property: ::core::option::Option<
+            ::shaku::__alloc::Arc<::shaku::ProviderFn<Self, #provider_ty>>
+        >
+    }
+}
+
+/// Get the providers of each submodule, which are also exposed as factory
+/// components, along with the index of the submodule. The extended module's
+/// services are exposed as the module's own services, so it is skipped.
+fn submodule_factories(module: &ModuleData) -> Vec<(usize, &Type)> {
+    let skip = if module.metadata.extends.is_some() {
+        1
+    } else {
+        0
+    };
+
+    module
+        .submodules
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .flat_map(|(i, submodule)| {
+            submodule
+                .services
+                .providers
+                .items
+                .iter()
+                .map(move |provider| (i, &provider.ty))
+        })
+        .collect()
+}
+
+/// Create the property which holds a factory for a subprovider
+fn factory_property(index: usize, provider_ty: &Type) -> TokenStream {
+    let property = generate_name(index, "factory", provider_ty.span());
+
+    quote! {
+        #[allow(bare_trait_objects)]
+        #property: ::shaku::__alloc::Arc<dyn ::shaku::Factory<#provider_ty>>
+    }
+}
+
+/// Create a property initializer for a subprovider factory during module build
+fn factory_build(
+    index: usize,
+    submodule_index: usize,
+    provider_ty: &Type,
+    module: &ModuleData,
+) -> TokenStream {
+    let property = generate_name(index, "factory", provider_ty.span());
+    let submodule_ty = &module.submodules[submodule_index].ty;
+    let submodule_name = generate_name(submodule_index, "submodule", submodule_ty.span());
+
+    quote! {
+        #property: ::shaku::__alloc::Arc::new(::shaku::ModuleFactory::new(
+            ::shaku::__alloc::Arc::clone(&#submodule_name)
+        ))
+    }
+}
+
+/// Create a HasComponent impl for a subprovider factory
+fn has_factory_impl(
+    index: usize,
+    submodule_index: usize,
+    provider_ty: &Type,
+    module: &ModuleData,
+) -> TokenStream {
+    let module_name = &module.metadata.identifier;
+    let property = generate_name(index, "factory", provider_ty.span());
+    let submodule_names = submodule_names(&module.submodules);
+    let submodule_name = &submodule_names[submodule_index];
+    let interface = quote! { dyn ::shaku::Factory<#provider_ty> };
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let record_use = record_use(&interface);
+
+    quote! {
+        #[allow(bare_trait_objects)]
+        impl #impl_generics ::shaku::HasComponent<#interface> for #module_name #ty_generics #where_clause {
+            fn build_component(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::shaku::__alloc::Arc<#interface> {
+                context.record_dependency::<#interface>();
+                let (#(#submodule_names),*) = context.submodules();
+                ::shaku::__alloc::Arc::new(::shaku::ModuleFactory::new(
+                    ::shaku::__alloc::Arc::clone(#submodule_name)
+                ))
+            }
+
+            fn resolve(&self) -> ::shaku::__alloc::Arc<#interface> {
+                #record_use
+                ::shaku::__alloc::Arc::clone(&self.#property)
+            }
+
+            fn resolve_ref(&self) -> &#interface {
+                #record_use
+                ::shaku::__alloc::Arc::as_ref(&self.#property)
             }
         }
     }
 }
 
+/// Create a HasSubmodule impl for each submodule. Submodule types which are
+/// used multiple times are skipped, since the impls would conflict.
+fn has_submodule_impls(module: &ModuleData) -> Vec<TokenStream> {
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let submodule_count = module.submodules.len();
+    let type_names: Vec<String> = module
+        .submodules
+        .iter()
+        .map(|submodule| submodule.ty.to_token_stream().to_string())
+        .collect();
+
+    module
+        .submodules
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| {
+            type_names
+                .iter()
+                .filter(|type_name| **type_name == type_names[*i])
+                .count()
+                == 1
+        })
+        .map(|(i, submodule)| {
+            let submodule_ty = &submodule.ty;
+
+            let submodule_name = generate_name(i, "submodule", submodule_ty.span());
+
+            // A single submodule is not stored in a tuple
+            let (submodule_access, submodule_access_mut) = if submodule_count == 1 {
+                (quote! { submodules }, quote! { submodules })
+            } else {
+                let index = syn::Index::from(i);
+                (
+                    quote! { &submodules.#index },
+                    quote! { &mut submodules.#index },
+                )
+            };
+
+            quote! {
+                #[allow(bare_trait_objects)]
+                impl #impl_generics ::shaku::HasSubmodule<#submodule_ty> for #module_name #ty_generics #where_clause {
+                    fn submodule(
+                        submodules: &Self::Submodules
+                    ) -> &::shaku::__alloc::Arc<#submodule_ty> {
+                        #submodule_access
+                    }
+
+                    fn submodule_mut(
+                        submodules: &mut Self::Submodules
+                    ) -> &mut ::shaku::__alloc::Arc<#submodule_ty> {
+                        #submodule_access_mut
+                    }
+
+                    fn resolve_submodule(&self) -> &::shaku::__alloc::Arc<#submodule_ty> {
+                        &self.#submodule_name
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+/// Record that the service was used, for `ModuleBuilder::build_with_usage_tracking` (only if
+/// the `usage_tracking` feature of shaku is turned on) and `ResolveMetrics` (only if the
+/// `metrics` feature of shaku is turned on)
+fn record_use(interface: &TokenStream) -> TokenStream {
+    let usage = if cfg!(feature = "usage_tracking") {
+        quote! { self.__di_usage.record_use(::core::any::type_name::<#interface>()); }
+    } else {
+        TokenStream::new()
+    };
+    let metrics = if cfg!(feature = "metrics") {
+        quote! { self.__di_metrics.record(::core::any::type_name::<#interface>()); }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        #usage
+        #metrics
+    }
+}
+
+/// The fields which the module only needs for the optional features of shaku, as
+/// (name, type, initializer) triples. They are left out of the module if the feature is off.
+fn bookkeeping_fields() -> Vec<(Ident, TokenStream, TokenStream)> {
+    let mut fields = Vec::new();
+
+    if cfg!(feature = "thread_confinement") {
+        fields.push((
+            Ident::new("__di_thread_confinement", Span::call_site()),
+            quote! { ::shaku::ThreadConfinement },
+            quote! { ::shaku::ThreadConfinement::new() },
+        ));
+    }
+
+    if cfg!(feature = "metrics") {
+        fields.push((
+            Ident::new("__di_metrics", Span::call_site()),
+            quote! { ::shaku::ResolveCounter },
+            quote! { ::shaku::ResolveCounter::new::<Self>() },
+        ));
+    }
+
+    if cfg!(feature = "usage_tracking") {
+        fields.push((
+            Ident::new("__di_usage", Span::call_site()),
+            quote! { ::shaku::UsageTracker },
+            quote! { context.usage_tracker() },
+        ));
+    }
+
+    fields
+}
+
+/// Check that the service is used on the thread which built the module (only
+/// if the `thread_confinement` feature of shaku is turned on)
+fn thread_check(interface: &TokenStream) -> TokenStream {
+    if !cfg!(feature = "thread_confinement") {
+        return TokenStream::new();
+    }
+
+    quote! {
+        self.__di_thread_confinement.check(::core::any::type_name::<#interface>());
+    }
+}
+
+/// Create the docs of a generated `HasComponent`/`HasProvider` impl, which say where the service
+/// comes from, followed by the service's own doc comments. This shows the effective wiring of the
+/// module in rustdoc.
+fn service_docs(summary: String, docs: &[Attribute]) -> TokenStream {
+    if docs.is_empty() {
+        return quote! { #[doc = #summary] };
+    }
+
+    quote! {
+        #[doc = #summary]
+        #[doc = ""]
+        #(#docs)*
+    }
+}
+
 /// Get the interface type of a component via projection
 fn interface_from_component(component_ty: &Type) -> TokenStream {
     quote! {
@@ -443,6 +2386,22 @@ fn submodule_names(submodules: &Punctuated<Submodule, syn::Token![,]>) -> Vec<Id
         .collect()
 }
 
+/// The identifier of the module property which holds the component/provider, which is either set
+/// via `#[name = "..."]` or generated.
+fn item_property<A: Eq + Hash>(index: usize, category: &str, item: &ModuleItem<A>) -> Ident
+where
+    Attribute: Parser<A>,
+{
+    item.name
+        .clone()
+        .unwrap_or_else(|| generate_name(index, category, item.ty.span()))
+}
+
+/// The identifier of the module property which holds the service of a promoted provider
+fn promoted_property_name(index: usize, provider: &ProviderItem) -> Ident {
+    generate_name(index, "promoted_provider", provider.ty.span())
+}
+
 /// Generate an identifier for a module property.
 fn generate_name(index: usize, category: &str, span: Span) -> Ident {
     syn::Ident::new(&format!("__di_{}_{}", category, index), span)
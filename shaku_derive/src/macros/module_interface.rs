@@ -0,0 +1,89 @@
+//! Implementation of the `module_interface!` procedural macro
+
+use crate::debug::get_debug_level;
+use crate::structures::module::ModuleInterfaceData;
+use proc_macro2::TokenStream;
+use syn::Type;
+
+pub fn expand_module_interface_macro(interface: ModuleInterfaceData) -> syn::Result<TokenStream> {
+    let debug_level = get_debug_level();
+    if debug_level > 1 {
+        println!("Module interface data parsed from input: {:#?}", interface);
+    }
+
+    let components: Vec<&Type> = interface
+        .services
+        .components
+        .items
+        .iter()
+        .map(|item| &item.ty)
+        .collect();
+    let providers: Vec<&Type> = interface
+        .services
+        .providers
+        .items
+        .iter()
+        .map(|item| &item.ty)
+        .collect();
+
+    let services_doc = services_doc(&components, &providers);
+    let attributes = &interface.attributes;
+    let visibility = &interface.visibility;
+    let identifier = &interface.identifier;
+    let generics = &interface.generics;
+    let where_clause = &interface.generics.where_clause;
+
+    let output = quote! {
+        #(#attributes)*
+        #(#[doc = #services_doc])*
+        #[allow(bare_trait_objects)]
+        #visibility trait #identifier #generics:
+            ::shaku::ModuleInterface
+            #(+ ::shaku::HasComponent<#components>)*
+            #(+ ::shaku::HasProvider<#providers>)*
+        #where_clause
+        {}
+    };
+
+    if debug_level > 0 {
+        println!("{}", output);
+    }
+
+    Ok(output)
+}
+
+/// Create the lines of documentation which list the services required by the
+/// module interface
+fn services_doc(components: &[&Type], providers: &[&Type]) -> Vec<String> {
+    if components.is_empty() && providers.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec![String::new(), " # Services".to_string()];
+
+    for (kind, services) in &[("Components", components), ("Providers", providers)] {
+        if services.is_empty() {
+            continue;
+        }
+
+        lines.push(format!(" {}:", kind));
+        lines.extend(
+            services
+                .iter()
+                .map(|ty| format!(" - `{}`", type_to_string(ty))),
+        );
+    }
+
+    lines
+}
+
+/// Format the type for documentation (ex. without the spaces around generics)
+fn type_to_string(ty: &Type) -> String {
+    quote!(#ty)
+        .to_string()
+        .replace(" < ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+}
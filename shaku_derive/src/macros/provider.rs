@@ -1,12 +1,21 @@
 //! Implementation of the `#[derive(Provider)]` procedural macro
 
 use crate::debug::get_debug_level;
-use crate::macros::common_output::create_dependency;
-use crate::structures::service::{Property, PropertyType, ServiceData};
-use proc_macro2::TokenStream;
-use syn::{DeriveInput, Error};
+use crate::macros::common_output::{
+    create_dependency, interface_object_safety_check, service_interface,
+};
+use crate::structures::service::{Property, PropertyDefault, PropertyType, ServiceData};
+use proc_macro2::{Ident, TokenStream};
+use syn::{DeriveInput, Error, GenericParam, Generics, Type};
 
 pub fn expand_derive_provider(input: &DeriveInput) -> syn::Result<TokenStream> {
+    if !cfg!(feature = "providers") {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "#[derive(Provider)] requires the `providers` feature of shaku",
+        ));
+    }
+
     let service = ServiceData::from_derive_input(input)?;
 
     let debug_level = get_debug_level();
@@ -14,6 +23,76 @@ pub fn expand_derive_provider(input: &DeriveInput) -> syn::Result<TokenStream> {
         println!("Service data parsed from Provider input: {:#?}", service);
     }
 
+    if let Some(event_ty) = service.metadata.subscriptions.first() {
+        return Err(Error::new_spanned(
+            event_ty,
+            "Providers cannot subscribe to events",
+        ));
+    }
+
+    if let Some(health_check) = &service.metadata.health_check {
+        return Err(Error::new(
+            health_check.span(),
+            "Providers cannot be health checks, since they are not stored in the module",
+        ));
+    }
+
+    if let Some(lifecycle) = &service.metadata.lifecycle {
+        return Err(Error::new(
+            lifecycle.span(),
+            "Providers cannot have lifecycle hooks, since they are not stored in the module",
+        ));
+    }
+
+    if let Some(reconfigurable) = &service.metadata.reconfigurable {
+        return Err(Error::new(
+            reconfigurable.span(),
+            "Providers cannot be reconfigured, since they are not stored in the module",
+        ));
+    }
+
+    if let Some(mock) = &service.metadata.mock {
+        return Err(Error::new(
+            mock.span(),
+            "Providers cannot generate mocks, since each provided service would be a new mock",
+        ));
+    }
+
+    if let Some(build_after) = service.metadata.build_after.first() {
+        return Err(Error::new_spanned(
+            build_after,
+            "Providers cannot set a build order, since they are not built with the module",
+        ));
+    }
+
+    if let Some(derive_path) = service.metadata.parameters_derives.first() {
+        return Err(Error::new_spanned(
+            derive_path,
+            "Providers do not have a parameters struct to derive traits on",
+        ));
+    }
+
+    if let Some(thread_bound) = &service.metadata.thread_bound {
+        return Err(Error::new(
+            thread_bound.span(),
+            "Providers do not need to be thread-bound, since provided services are not shared",
+        ));
+    }
+
+    if let Some(delegate) = &service.metadata.delegate {
+        return Err(Error::new_spanned(
+            delegate,
+            "Providers cannot delegate the interface to a field",
+        ));
+    }
+
+    if let Some(adapter) = service.metadata.adapters.first() {
+        return Err(Error::new_spanned(
+            &adapter.identifier,
+            "Providers cannot have adapters",
+        ));
+    }
+
     let resolve_properties: Vec<TokenStream> = service
         .properties
         .iter()
@@ -28,24 +107,54 @@ pub fn expand_derive_provider(input: &DeriveInput) -> syn::Result<TokenStream> {
 
     // Provider implementation
     let provider_name = service.metadata.identifier;
-    let interface = service.metadata.interface;
-    let (_, generic_tys, generic_where) = service.metadata.generics.split_for_impl();
-    let generic_impls_no_parens = &service.metadata.generics.params;
+    let provide_body = quote! {
+        ::core::result::Result::Ok(::shaku::__alloc::Box::new(Self {
+            #(#resolve_properties),*
+        }))
+    };
+    let provide_body = match &service.metadata.retry {
+        Some(retry) => {
+            let times = retry.times;
+            let backoff_ms = retry.backoff_ms;
+
+            quote! {
+                ::shaku::RetryPolicy::new(
+                    #times,
+                    ::core::time::Duration::from_millis(#backoff_ms)
+                ).run(|| -> ::core::result::Result<
+                    ::shaku::__alloc::Box<Self::Interface>,
+                    ::shaku::__alloc::Box<dyn ::shaku::Error>
+                > {
+                    #provide_body
+                })
+            }
+        }
+        None => provide_body,
+    };
+    let dependencies_fn = create_dependencies_fn(&service.properties);
+    let interface = service_interface(&service.metadata.interface, &provider_name);
+    let impl_generics = create_provider_generics(&service.metadata.generics, &dependencies);
+    let (generic_impls, _, generic_where) = impl_generics.split_for_impl();
+    let (_, generic_tys, _) = service.metadata.generics.split_for_impl();
+    let object_safety_check = interface_object_safety_check(
+        &service.metadata.interface,
+        &provider_name,
+        &service.metadata.generics,
+    );
     let output = quote! {
-        impl<
-            M: ::shaku::Module #(+ #dependencies)*,
-            #generic_impls_no_parens
-        > ::shaku::Provider<M> for #provider_name #generic_tys #generic_where {
-            type Interface = dyn #interface;
-
-            fn provide(module: &M) -> ::std::result::Result<
-                Box<Self::Interface>,
-                Box<dyn ::std::error::Error>
+        #object_safety_check
+
+        impl #generic_impls ::shaku::Provider<M> for #provider_name #generic_tys #generic_where {
+            type Interface = #interface;
+
+            fn provide(module: &M) -> ::core::result::Result<
+                ::shaku::__alloc::Box<Self::Interface>,
+                ::shaku::__alloc::Box<dyn ::shaku::Error>
             > {
-                Ok(Box::new(Self {
-                    #(#resolve_properties),*
-                }))
+                #provide_body
             }
+
+            #dependencies_fn
         }
     };
 
@@ -56,19 +165,88 @@ pub fn expand_derive_provider(input: &DeriveInput) -> syn::Result<TokenStream> {
     Ok(output)
 }
 
+/// The generics of the provider impl: the provider's generics (without defaults, which split_for_impl
+/// removes), plus the module type `M` after any lifetimes. Providers must be `'static`, so the
+/// type parameters are required to be `'static` as well.
+fn create_provider_generics(generics: &Generics, dependencies: &[TokenStream]) -> Generics {
+    let mut impl_generics = generics.clone();
+    let type_params: Vec<Ident> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+
+    if !type_params.is_empty() {
+        let where_clause = impl_generics.make_where_clause();
+
+        for param in type_params {
+            where_clause
+                .predicates
+                .push(syn::parse_quote! { #param: 'static });
+        }
+    }
+
+    let module_param: GenericParam = syn::parse_quote! {
+        M: ::shaku::Module #(+ #dependencies)*
+    };
+    let lifetimes = generics.lifetimes().count();
+    impl_generics.params.insert(lifetimes, module_param);
+
+    impl_generics
+}
+
+/// List the injected components and provided services
+fn create_dependencies_fn(properties: &[Property]) -> Option<TokenStream> {
+    let dependencies: Vec<&Type> = properties
+        .iter()
+        .filter(|property| match property.property_type {
+            PropertyType::Component | PropertyType::Provided => true,
+            PropertyType::Parameter | PropertyType::Module | PropertyType::Source => false,
+        })
+        .map(|property| &property.ty)
+        .collect();
+
+    if dependencies.is_empty() {
+        return None;
+    }
+
+    Some(quote! {
+        fn dependencies() -> ::shaku::__alloc::Vec<&'static str> {
+            ::shaku::__alloc::vec![#(::core::any::type_name::<#dependencies>()),*]
+        }
+    })
+}
+
 fn create_property_assignment(property: &Property) -> syn::Result<TokenStream> {
-    let property_name = &property.property_name;
+    let member = &property.member;
 
     match property.property_type {
         PropertyType::Component => Ok(quote! {
-            #property_name: module.resolve()
+            #member: module.resolve()
         }),
         PropertyType::Provided => Ok(quote! {
-            #property_name: module.provide()?
+            #member: module.provide()?
         }),
-        PropertyType::Parameter => Err(Error::new(
-            property.property_name.span(),
-            "Parameters are not allowed in Providers",
+        PropertyType::Parameter => match &property.default {
+            // Fields with a default (ex. `PhantomData<T>` markers) are set to the default each time
+            PropertyDefault::Provided(default_expr) => Ok(quote! {
+                #member: #default_expr
+            }),
+            PropertyDefault::NotProvided => Ok(quote! {
+                #member: ::core::default::Default::default()
+            }),
+            PropertyDefault::NoDefault => Err(Error::new_spanned(
+                member,
+                "Parameters are not allowed in Providers. Fields which are not injected or \
+                 provided must have a default, ex. #[shaku(default)]",
+            )),
+        },
+        PropertyType::Module => Err(Error::new_spanned(
+            member,
+            "Only components can inject the module",
+        )),
+        PropertyType::Source => Err(Error::new_spanned(
+            member,
+            "Only components can read values from a parameter source",
         )),
     }
 }
@@ -15,7 +15,7 @@ pub trait Parser<T: Sized> {
     fn parse_as(&self) -> syn::Result<T>;
 }
 
-/// Find the #[shaku(...)] attribute
-fn get_shaku_attribute(attrs: &[Attribute]) -> Option<&Attribute> {
-    attrs.iter().find(|a| a.path.is_ident(consts::ATTR_NAME))
+/// Find the #[shaku(...)] attributes
+fn get_shaku_attributes(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|a| a.path.is_ident(consts::ATTR_NAME))
 }
@@ -1,27 +1,178 @@
 use crate::consts;
-use crate::parser::{get_shaku_attribute, KeyValue, Parser};
-use crate::structures::service::MetaData;
-use syn::spanned::Spanned;
-use syn::{DeriveInput, Error, Type};
+use crate::parser::{get_shaku_attributes, KeyValue, Parser};
+use crate::structures::service::{Adapter, MetaData, Retry};
+use quote::ToTokens;
+use std::fmt::Display;
+use std::str::FromStr;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{DeriveInput, Error, Ident, LitInt, Member, Path, Type};
 
-impl Parser<MetaData> for DeriveInput {
-    fn parse_as(&self) -> syn::Result<MetaData> {
-        // Find the shaku(interface = ?) attribute
-        let shaku_attribute = get_shaku_attribute(&self.attrs).ok_or_else(|| {
-            Error::new(
-                self.ident.span(),
+/// A single entry of the `#[shaku(...)]` attribute. Most entries are key/values
+/// (ex. `interface = ?`), but some take a list (ex. `parameters_derive(?, ?)`) or
+/// are a flag (ex. `thread_bound`). The delegate takes a field name or index
+/// instead of a type, adapters take a list of `Interface as AdapterName`, and
+/// retry takes a list of integer options (ex. `retry(times = 3)`).
+enum MetaDataItem {
+    KeyValue(KeyValue<Type>),
+    Delegate(KeyValue<Member>),
+    List(Ident, Punctuated<Path, syn::Token![,]>),
+    Adapters(Punctuated<Adapter, syn::Token![,]>),
+    Retry(Ident, Punctuated<KeyValue<LitInt>, syn::Token![,]>),
+    Flag(Ident),
+}
+
+impl Parse for MetaDataItem {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(Ident) && input.peek2(syn::token::Paren) {
+            let key: Ident = input.parse()?;
+            let content;
+            syn::parenthesized!(content in input);
+
+            if key == consts::ADAPTER_ATTR_NAME {
+                return content
+                    .parse_terminated(Adapter::parse)
+                    .map(MetaDataItem::Adapters);
+            }
+
+            if key == consts::RETRY_ATTR_NAME {
+                return Ok(MetaDataItem::Retry(
+                    key,
+                    content.parse_terminated(KeyValue::parse)?,
+                ));
+            }
+
+            Ok(MetaDataItem::List(
+                key,
+                content.parse_terminated(Path::parse)?,
+            ))
+        } else if input.peek(Ident) && (input.peek2(syn::Token![,]) || is_last_item(input)) {
+            input.parse().map(MetaDataItem::Flag)
+        } else if input
+            .fork()
+            .parse::<Ident>()
+            .ok()
+            .map_or(false, |key| key == consts::DELEGATE_ATTR_NAME)
+        {
+            input.parse().map(MetaDataItem::Delegate)
+        } else {
+            input.parse().map(MetaDataItem::KeyValue)
+        }
+    }
+}
+
+impl Parse for Adapter {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let interface = strip_dyn(input.parse()?);
+        input.parse::<syn::Token![as]>()?;
+
+        Ok(Adapter {
+            interface,
+            identifier: input.parse()?,
+        })
+    }
+}
+
+/// Read the options of `retry(times = ?, backoff_ms = ?)`. The number of
+/// retries is required, while the backoff defaults to zero.
+fn parse_retry(
+    key: Ident,
+    options: Punctuated<KeyValue<LitInt>, syn::Token![,]>,
+) -> syn::Result<Retry> {
+    let mut times = None;
+    let mut backoff_ms = None;
+
+    for option in options {
+        let value = if option.key == consts::RETRY_TIMES_ATTR_NAME {
+            &mut times
+        } else if option.key == consts::RETRY_BACKOFF_ATTR_NAME {
+            &mut backoff_ms
+        } else {
+            return Err(Error::new_spanned(
+                &option.key,
                 format!(
-                    "Unable to find interface. Please add a '#[{}({} = <your trait>)]'",
-                    consts::ATTR_NAME,
-                    consts::INTERFACE_ATTR_NAME
+                    "Unknown retry option: '{}'. Expected '{}' or '{}'",
+                    option.key,
+                    consts::RETRY_TIMES_ATTR_NAME,
+                    consts::RETRY_BACKOFF_ATTR_NAME
                 ),
-            )
-        })?;
+            ));
+        };
+
+        if value.is_some() {
+            return Err(Error::new_spanned(option.key, "Duplicate attribute"));
+        }
+
+        *value = Some(option.value);
+    }
+
+    let times = times.ok_or_else(|| {
+        Error::new_spanned(
+            &key,
+            format!(
+                "Missing retry option: '{}'. Example: {}({} = 3, {} = 100)",
+                consts::RETRY_TIMES_ATTR_NAME,
+                consts::RETRY_ATTR_NAME,
+                consts::RETRY_TIMES_ATTR_NAME,
+                consts::RETRY_BACKOFF_ATTR_NAME
+            ),
+        )
+    })?;
+
+    Ok(Retry {
+        times: parse_int(&times)?,
+        backoff_ms: match backoff_ms {
+            Some(backoff_ms) => parse_int(&backoff_ms)?,
+            None => 0,
+        },
+        key,
+    })
+}
+
+/// Parse an integer literal. `LitInt::base10_parse` is not available in the
+/// minimum supported syn version.
+fn parse_int<N>(lit: &LitInt) -> syn::Result<N>
+where
+    N: FromStr,
+    N::Err: Display,
+{
+    lit.base10_digits()
+        .parse()
+        .map_err(|e| Error::new(lit.span(), e))
+}
+
+/// Check if the next item is a single token at the end of the attribute
+fn is_last_item(input: ParseStream<'_>) -> bool {
+    let fork = input.fork();
+    fork.parse::<Ident>().is_ok() && fork.is_empty()
+}
 
-        // Get the interface key/value
-        let interface_kv: KeyValue<Type> = shaku_attribute.parse_args().map_err(|_| {
-            Error::new(
-                shaku_attribute.span(),
+/// The interface is used as `dyn #interface`, so allow (and remove) a leading
+/// `dyn`, ex. `interface = dyn Repository<Entity = User>`
+fn strip_dyn(interface: Type) -> Type {
+    match interface {
+        Type::TraitObject(mut trait_object) => {
+            trait_object.dyn_token = None;
+            Type::TraitObject(trait_object)
+        }
+        interface => interface,
+    }
+}
+
+impl Parser<MetaData> for DeriveInput {
+    fn parse_as(&self) -> syn::Result<MetaData> {
+        let missing_interface_error = Error::new(
+            self.ident.span(),
+            format!(
+                "Unable to find interface. Please add a '#[{}({} = <your trait>)]'",
+                consts::ATTR_NAME,
+                consts::INTERFACE_ATTR_NAME
+            ),
+        );
+
+        let invalid_format_error = |tokens: &dyn ToTokens| {
+            Error::new_spanned(
+                tokens,
                 format!(
                     "Invalid attribute format. The attribute must be in name-value form. \
                      Example: #[{}({} = <your trait>)]",
@@ -29,24 +180,162 @@ impl Parser<MetaData> for DeriveInput {
                     consts::INTERFACE_ATTR_NAME
                 ),
             )
-        })?;
+        };
+        let unknown_key_error = |key: &Ident| {
+            // Point out the name-value form if the interface was used incorrectly
+            if key == consts::INTERFACE_ATTR_NAME {
+                invalid_format_error(key)
+            } else {
+                Error::new_spanned(key, format!("Unknown shaku attribute: '{}'", key))
+            }
+        };
 
-        if interface_kv.key != consts::INTERFACE_ATTR_NAME {
-            return Err(Error::new(
-                self.ident.span(),
-                format!(
-                    "Unable to find interface. Please add a '#[{}({} = <your trait>)]'",
-                    consts::ATTR_NAME,
-                    consts::INTERFACE_ATTR_NAME
-                ),
-            ));
+        let mut interface = None;
+        let mut subscriptions = Vec::new();
+        let mut parameters_derives = Vec::new();
+        let mut thread_bound = None;
+        let mut health_check = None;
+        let mut lifecycle = None;
+        let mut reconfigurable = None;
+        let mut delegate = None;
+        let mut adapters = Vec::new();
+        let mut retry = None;
+        let mut mock = None;
+        let mut build_after = Vec::new();
+
+        // Get the items from each shaku attribute, ex. interface = ?, subscribe = ?,
+        // parameters_derive(?), thread_bound, health_check, lifecycle, reconfigurable, mock, delegate = ?,
+        // adapter(? as ?), retry(?), build_after = ?
+        for shaku_attribute in get_shaku_attributes(&self.attrs) {
+            let items = shaku_attribute
+                .parse_args_with(Punctuated::<MetaDataItem, syn::Token![,]>::parse_terminated)
+                .map_err(|_| invalid_format_error(shaku_attribute))?;
+
+            for item in items {
+                let key_value = match item {
+                    MetaDataItem::KeyValue(key_value) => key_value,
+                    MetaDataItem::Delegate(key_value) => {
+                        if delegate.is_some() {
+                            return Err(Error::new_spanned(key_value.key, "Duplicate attribute"));
+                        }
+
+                        delegate = Some(key_value.value);
+                        continue;
+                    }
+                    MetaDataItem::List(key, paths) => {
+                        if key == consts::PARAMETERS_DERIVE_ATTR_NAME {
+                            parameters_derives.extend(paths);
+                            continue;
+                        }
+
+                        return Err(unknown_key_error(&key));
+                    }
+                    MetaDataItem::Adapters(items) => {
+                        adapters.extend(items);
+                        continue;
+                    }
+                    MetaDataItem::Retry(key, options) => {
+                        if retry.is_some() {
+                            return Err(Error::new_spanned(key, "Duplicate attribute"));
+                        }
+
+                        retry = Some(parse_retry(key, options)?);
+                        continue;
+                    }
+                    MetaDataItem::Flag(key) => {
+                        if key == consts::THREAD_BOUND_ATTR_NAME {
+                            if thread_bound.is_some() {
+                                return Err(Error::new_spanned(key, "Duplicate attribute"));
+                            }
+
+                            thread_bound = Some(key);
+                            continue;
+                        }
+
+                        if key == consts::HEALTH_CHECK_ATTR_NAME {
+                            if health_check.is_some() {
+                                return Err(Error::new_spanned(key, "Duplicate attribute"));
+                            }
+
+                            health_check = Some(key);
+                            continue;
+                        }
+
+                        if key == consts::LIFECYCLE_ATTR_NAME {
+                            if lifecycle.is_some() {
+                                return Err(Error::new_spanned(key, "Duplicate attribute"));
+                            }
+
+                            lifecycle = Some(key);
+                            continue;
+                        }
+
+                        if key == consts::RECONFIGURABLE_ATTR_NAME {
+                            if reconfigurable.is_some() {
+                                return Err(Error::new_spanned(key, "Duplicate attribute"));
+                            }
+
+                            reconfigurable = Some(key);
+                            continue;
+                        }
+
+                        if key == consts::MOCK_ATTR_NAME {
+                            if !cfg!(feature = "mock") {
+                                return Err(Error::new_spanned(
+                                    key,
+                                    "#[shaku(mock)] requires the `mock` feature of shaku",
+                                ));
+                            }
+
+                            if mock.is_some() {
+                                return Err(Error::new_spanned(key, "Duplicate attribute"));
+                            }
+
+                            mock = Some(key);
+                            continue;
+                        }
+
+                        return Err(unknown_key_error(&key));
+                    }
+                };
+
+                if key_value.key == consts::INTERFACE_ATTR_NAME {
+                    if interface.is_some() {
+                        return Err(Error::new_spanned(
+                            &key_value.key,
+                            "The interface can only be specified once",
+                        ));
+                    }
+
+                    interface = Some(strip_dyn(key_value.value));
+                } else if key_value.key == consts::SUBSCRIBE_ATTR_NAME {
+                    subscriptions.push(key_value.value);
+                } else if key_value.key == consts::BUILD_AFTER_ATTR_NAME {
+                    build_after.push(key_value.value);
+                } else {
+                    return Err(unknown_key_error(&key_value.key));
+                }
+            }
         }
 
+        let interface = interface.ok_or(missing_interface_error)?;
+
         Ok(MetaData {
             identifier: self.ident.clone(),
             generics: self.generics.clone(),
-            interface: interface_kv.value,
+            interface,
             visibility: self.vis.clone(),
+            subscriptions,
+            health_check,
+            lifecycle,
+            reconfigurable,
+            parameters_derives,
+            thread_bound,
+            delegate,
+            adapters,
+            retry,
+            mock,
+            build_after,
         })
     }
 }
@@ -1,17 +1,36 @@
+use crate::consts;
 use crate::parser::Parser;
 use crate::structures::module::{
-    ComponentAttribute, ModuleData, ModuleItem, ModuleItems, ModuleMetadata, ModuleServices,
-    ProviderAttribute, Submodule,
+    ComponentAttribute, ExtendedServices, ModuleData, ModuleInterfaceData, ModuleItem, ModuleItems,
+    ModuleMetadata, ModuleOptions, ModuleServices, ProviderAttribute, Submodule,
 };
 use std::collections::HashSet;
 use std::hash::Hash;
-use syn::parse::{Parse, ParseStream};
-use syn::spanned::Spanned;
-use syn::{Attribute, Error, Generics};
+use std::mem;
+use syn::parse::{Parse, ParseStream, Parser as _};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Error, Generics, Ident, Lit, LitStr, Meta, MetaNameValue, Token};
+
+mod kw {
+    syn::custom_keyword!(extends);
+    syn::custom_keyword!(extends_services);
+}
 
 impl Parse for ModuleData {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let metadata = input.parse()?;
+        let extended_services: Option<ExtendedServices> = if input.peek(syn::Token![@]) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let metadata: ModuleMetadata = input.parse()?;
+
+        if let (Some(extended_services), None) = (&extended_services, &metadata.extends) {
+            return Err(Error::new_spanned(
+                &extended_services.base,
+                "Extended services were given, but the module does not extend another module",
+            ));
+        }
 
         let content;
         syn::braced!(content in input);
@@ -25,6 +44,7 @@ impl Parse for ModuleData {
         let submodules = content.parse_terminated(Submodule::parse)?;
 
         Ok(ModuleData {
+            extended_services,
             metadata,
             services,
             submodules,
@@ -34,9 +54,27 @@ impl Parse for ModuleData {
 
 impl Parse for ModuleMetadata {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attributes = input.call(Attribute::parse_outer)?;
+        let options = attributes
+            .iter()
+            .filter(|attr| attr.path.is_ident(consts::ATTR_NAME))
+            .map(Parser::<ModuleOptions>::parse_as)
+            .try_fold(ModuleOptions::default(), |options, parsed| {
+                parsed.and_then(|parsed| options.merge(parsed))
+            })?;
+        attributes.retain(|attr| !attr.path.is_ident(consts::ATTR_NAME));
+
         let visibility = input.parse()?;
         let identifier = input.parse()?;
         let mut generics: Generics = input.parse()?;
+
+        let extends = if input.peek(kw::extends) {
+            input.parse::<kw::extends>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
         generics.where_clause = input.parse()?;
 
         let interface = if input.peek(syn::Token![:]) {
@@ -47,14 +85,109 @@ impl Parse for ModuleMetadata {
         };
 
         Ok(ModuleMetadata {
+            attributes,
+            options,
             visibility,
             identifier,
             generics,
+            extends,
             interface,
         })
     }
 }
 
+impl Parse for ExtendedServices {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<syn::Token![@]>()?;
+        input.parse::<kw::extends_services>()?;
+        let base = input.parse()?;
+
+        let content;
+        syn::braced!(content in input);
+        let services: ModuleServices = content.parse()?;
+
+        if !content.is_empty() {
+            return Err(content.error("expected end of input"));
+        }
+
+        Ok(ExtendedServices { base, services })
+    }
+}
+
+impl Parser<ModuleOptions> for Attribute {
+    fn parse_as(&self) -> syn::Result<ModuleOptions> {
+        let flags = self.parse_args_with(Punctuated::<Ident, syn::Token![,]>::parse_terminated)?;
+        let mut options = ModuleOptions::default();
+
+        for flag in flags {
+            let merged = if flag == consts::DERIVE_CLONE_ATTR_NAME {
+                ModuleOptions {
+                    derive_clone: Some(flag),
+                    ..ModuleOptions::default()
+                }
+            } else if flag == consts::EXTENDABLE_ATTR_NAME {
+                ModuleOptions {
+                    extendable: Some(flag),
+                    ..ModuleOptions::default()
+                }
+            } else if flag == consts::STRICT_BUILDER_ATTR_NAME {
+                ModuleOptions {
+                    strict_builder: Some(flag),
+                    ..ModuleOptions::default()
+                }
+            } else if flag == consts::DENY_UNUSED_ATTR_NAME {
+                ModuleOptions {
+                    deny_unused: Some(flag),
+                    ..ModuleOptions::default()
+                }
+            } else if flag == consts::LAST_WINS_ATTR_NAME {
+                ModuleOptions {
+                    last_wins: Some(flag),
+                    ..ModuleOptions::default()
+                }
+            } else if flag == consts::MEMORY_FOOTPRINT_ATTR_NAME {
+                ModuleOptions {
+                    memory_footprint: Some(flag),
+                    ..ModuleOptions::default()
+                }
+            } else {
+                return Err(Error::new(
+                    flag.span(),
+                    format!("Unknown module option: '{}'", flag),
+                ));
+            };
+
+            options = options.merge(merged)?;
+        }
+
+        Ok(options)
+    }
+}
+
+impl ModuleOptions {
+    /// Combine two sets of options, erroring if an option is set twice
+    fn merge(self, other: ModuleOptions) -> syn::Result<ModuleOptions> {
+        Ok(ModuleOptions {
+            derive_clone: merge_flag(self.derive_clone, other.derive_clone)?,
+            extendable: merge_flag(self.extendable, other.extendable)?,
+            strict_builder: merge_flag(self.strict_builder, other.strict_builder)?,
+            deny_unused: merge_flag(self.deny_unused, other.deny_unused)?,
+            last_wins: merge_flag(self.last_wins, other.last_wins)?,
+            memory_footprint: merge_flag(self.memory_footprint, other.memory_footprint)?,
+        })
+    }
+}
+
+fn merge_flag(left: Option<Ident>, right: Option<Ident>) -> syn::Result<Option<Ident>> {
+    match (left, right) {
+        (Some(_), Some(duplicate)) => Err(Error::new(
+            duplicate.span(),
+            format!("Duplicate module option: '{}'", duplicate),
+        )),
+        (left, right) => Ok(left.or(right)),
+    }
+}
+
 impl Parse for Submodule {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         input.parse::<syn::Token![use]>()?;
@@ -68,38 +201,101 @@ impl Parse for Submodule {
             return Err(content.error("expected end of input"));
         }
 
-        // Make sure components don't use attributes
-        for component in &services.components.items {
-            if !component.attributes.is_empty() {
-                return Err(syn::Error::new(
-                    component.ty.span(),
-                    "Submodule components cannot have attributes",
+        services.check_no_attributes("Submodule")?;
+
+        Ok(Submodule { ty, services })
+    }
+}
+
+impl Parse for ModuleInterfaceData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attributes = input.call(Attribute::parse_outer)?;
+        let visibility = input.parse()?;
+        input.parse::<syn::Token![trait]>()?;
+        let identifier = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+        generics.where_clause = input.parse()?;
+
+        let content;
+        syn::braced!(content in input);
+        let services: ModuleServices = content.parse()?;
+
+        if !content.is_empty() {
+            return Err(content.error("expected end of input"));
+        }
+
+        services.check_no_attributes("Module interface")?;
+
+        Ok(ModuleInterfaceData {
+            attributes,
+            visibility,
+            identifier,
+            generics,
+            services,
+        })
+    }
+}
+
+impl ModuleServices {
+    /// Make sure the components and providers don't use attributes. The kind of
+    /// service list (ex. "Submodule") is used in the error message.
+    fn check_no_attributes(&self, kind: &str) -> syn::Result<()> {
+        for component in &self.components.items {
+            if let Some(attribute) = component.unparsed_attributes.first() {
+                return Err(syn::Error::new_spanned(
+                    attribute,
+                    format!("{} components cannot have attributes", kind),
                 ));
             }
         }
 
-        // Make sure providers don't use attributes
-        for provider in &services.providers.items {
-            if !provider.attributes.is_empty() {
-                return Err(syn::Error::new(
-                    provider.ty.span(),
-                    "Submodule providers cannot have attributes",
+        for provider in &self.providers.items {
+            if let Some(attribute) = provider.unparsed_attributes.first() {
+                return Err(syn::Error::new_spanned(
+                    attribute,
+                    format!("{} providers cannot have attributes", kind),
                 ));
             }
         }
 
-        Ok(Submodule { ty, services })
+        Ok(())
+    }
+
+    /// Make sure no providers are used if the `providers` feature of shaku is disabled
+    fn check_providers_enabled(&self) -> syn::Result<()> {
+        if cfg!(feature = "providers") {
+            return Ok(());
+        }
+
+        if let Some(provider) = self.providers.items.first() {
+            return Err(syn::Error::new_spanned(
+                &provider.ty,
+                "Providers require the `providers` feature of shaku",
+            ));
+        }
+
+        if let Some(component) = self.components.items.iter().find(|c| c.is_provided()) {
+            return Err(syn::Error::new_spanned(
+                &component.ty,
+                "#[as_provider] requires the `providers` feature of shaku",
+            ));
+        }
+
+        Ok(())
     }
 }
 
 impl Parse for ModuleServices {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        Ok(ModuleServices {
+        let services = ModuleServices {
             components: input.parse()?,
             comma_token: input.parse()?,
             providers: input.parse()?,
             trailing_comma: input.parse()?,
-        })
+        };
+        services.check_providers_enabled()?;
+
+        Ok(services)
     }
 }
 
@@ -113,11 +309,50 @@ where
             keyword_token: input.parse()?,
             eq_token: input.parse()?,
             bracket_token: syn::bracketed!(content in input),
-            items: content.parse_terminated(ModuleItem::parse)?,
+            items: parse_items(&content)?,
         })
     }
 }
 
+/// Parse a list of items, where items can be grouped by cargo feature, ex.
+/// `LoggerImpl, "redis" => [RedisCache, RedisLock]`. The items of a group get
+/// a `#[cfg(feature = "...")]` attribute.
+fn parse_items<A: Eq + Hash>(
+    input: ParseStream,
+) -> syn::Result<Punctuated<ModuleItem<A>, Token![,]>>
+where
+    Attribute: Parser<A>,
+{
+    let mut items = Punctuated::new();
+
+    while !input.is_empty() {
+        if input.peek(LitStr) {
+            let feature: LitStr = input.parse()?;
+            input.parse::<Token![=>]>()?;
+            let group;
+            syn::bracketed!(group in input);
+            let cfg = Attribute::parse_outer.parse2(quote_spanned! {feature.span()=>
+                #[cfg(feature = #feature)]
+            })?;
+
+            for mut item in group.parse_terminated::<_, Token![,]>(ModuleItem::parse)? {
+                item.unparsed_attributes.extend(cfg.iter().cloned());
+                item.cfg.extend(cfg.iter().cloned());
+                items.push(item);
+            }
+        } else {
+            items.push(input.parse()?);
+        }
+
+        if input.is_empty() {
+            break;
+        }
+        input.parse::<Token![,]>()?;
+    }
+
+    Ok(items)
+}
+
 impl<A: Eq + Hash> Parse for ModuleItem<A>
 where
     Attribute: Parser<A>,
@@ -125,13 +360,44 @@ where
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let unparsed_attrs = input.call(Attribute::parse_outer)?;
         let mut attributes = HashSet::with_capacity(unparsed_attrs.len());
+        let mut cfg = Vec::new();
+        let mut docs = Vec::new();
+        let mut name = None;
 
         // Parse attributes and check for duplicates
-        for unparsed_attr in unparsed_attrs {
+        for unparsed_attr in &unparsed_attrs {
+            if unparsed_attr.path.is_ident("cfg") {
+                cfg.push(unparsed_attr.clone());
+                continue;
+            }
+
+            if unparsed_attr.path.is_ident("doc") {
+                docs.push(unparsed_attr.clone());
+                continue;
+            }
+
+            if unparsed_attr.path.is_ident("name") {
+                if name.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        unparsed_attr,
+                        "Duplicate attribute",
+                    ));
+                }
+
+                name = Some(parse_name(unparsed_attr)?);
+                continue;
+            }
+
             let attr = unparsed_attr.parse_as()?;
 
-            if attributes.contains(&attr) {
-                return Err(syn::Error::new(unparsed_attr.span(), "Duplicate attribute"));
+            if attributes
+                .iter()
+                .any(|existing| mem::discriminant(existing) == mem::discriminant(&attr))
+            {
+                return Err(syn::Error::new_spanned(
+                    unparsed_attr,
+                    "Duplicate attribute",
+                ));
             }
 
             attributes.insert(attr);
@@ -139,23 +405,75 @@ where
 
         Ok(ModuleItem {
             attributes,
+            unparsed_attributes: unparsed_attrs,
+            cfg,
+            docs,
+            name,
             ty: input.parse()?,
         })
     }
 }
 
+/// Parse `#[name = "..."]` into the name of the service's module field
+fn parse_name(attr: &Attribute) -> syn::Result<Ident> {
+    let name = match attr.parse_meta()? {
+        Meta::NameValue(MetaNameValue {
+            lit: Lit::Str(name),
+            ..
+        }) => name,
+        _ => {
+            return Err(Error::new_spanned(
+                attr,
+                "Expected #[name = \"field_name\"]",
+            ))
+        }
+    };
+    let ident: Ident = name
+        .parse()
+        .map_err(|_| Error::new_spanned(&name, "The name must be a valid identifier"))?;
+
+    if ident.to_string().starts_with("__di_") || ident == "build_context" {
+        return Err(Error::new_spanned(
+            &name,
+            "The name is reserved for the module's generated fields",
+        ));
+    }
+
+    Ok(ident)
+}
+
 impl Parser<ComponentAttribute> for Attribute {
     fn parse_as(&self) -> syn::Result<ComponentAttribute> {
         if self.path.is_ident("lazy") && self.tokens.is_empty() {
             Ok(ComponentAttribute::Lazy)
+        } else if self.path.is_ident("as_provider") && self.tokens.is_empty() {
+            Ok(ComponentAttribute::AsProvider)
+        } else if self.path.is_ident("instrument") && self.tokens.is_empty() {
+            Ok(ComponentAttribute::Instrument)
+        } else if self.path.is_ident("deprecated") {
+            match self.parse_meta()? {
+                Meta::Path(_) => Ok(ComponentAttribute::Deprecated(None)),
+                Meta::NameValue(MetaNameValue {
+                    lit: Lit::Str(note),
+                    ..
+                }) => Ok(ComponentAttribute::Deprecated(Some(note.value()))),
+                _ => Err(Error::new_spanned(
+                    self,
+                    "Expected #[deprecated] or #[deprecated = \"note\"]",
+                )),
+            }
         } else {
-            Err(Error::new(self.span(), "Unknown attribute".to_string()))
+            Err(Error::new_spanned(self, "Unknown attribute"))
         }
     }
 }
 
 impl Parser<ProviderAttribute> for Attribute {
     fn parse_as(&self) -> syn::Result<ProviderAttribute> {
-        Err(Error::new(self.span(), "Providers cannot have attributes"))
+        if self.path.is_ident("promote_to_component") && self.tokens.is_empty() {
+            Ok(ProviderAttribute::PromoteToComponent)
+        } else {
+            Err(Error::new_spanned(self, "Unknown attribute"))
+        }
     }
 }
@@ -1,11 +1,23 @@
 use crate::parser::Parser;
 use crate::structures::service::Property;
-use syn::{Data, DeriveInput, Error, Field};
+use syn::{Data, DeriveInput, Error, Field, Fields, Index, Member};
 
 impl Parser<Vec<Property>> for DeriveInput {
     fn parse_as(&self) -> syn::Result<Vec<Property>> {
         match &self.data {
-            Data::Struct(data) => data.fields.iter().map(Field::parse_as).collect(),
+            Data::Struct(data) => {
+                let mut properties = data
+                    .fields
+                    .iter()
+                    .map(Field::parse_as)
+                    .collect::<syn::Result<Vec<Property>>>()?;
+
+                if let Fields::Unnamed(_) = data.fields {
+                    number_tuple_properties(&mut properties);
+                }
+
+                Ok(properties)
+            }
             _ => Err(Error::new(
                 self.ident.span(),
                 "Only structs are currently supported".to_string(),
@@ -13,3 +25,29 @@ impl Parser<Vec<Property>> for DeriveInput {
         }
     }
 }
+
+/// Set the positional members of a tuple struct's properties. The parameters
+/// are numbered separately, since only they are in the parameters struct.
+fn number_tuple_properties(properties: &mut [Property]) {
+    let mut parameter_index = 0;
+
+    for (index, property) in properties.iter_mut().enumerate() {
+        let span = match &property.member {
+            Member::Unnamed(member) => member.span,
+            Member::Named(ident) => ident.span(),
+        };
+
+        property.member = Member::Unnamed(Index {
+            index: index as u32,
+            span,
+        });
+
+        if property.is_parameter() {
+            property.parameters_member = Member::Unnamed(Index {
+                index: parameter_index,
+                span,
+            });
+            parameter_index += 1;
+        }
+    }
+}
@@ -1,27 +1,117 @@
 use crate::consts;
-use crate::parser::{get_shaku_attribute, KeyValue, Parser};
+use crate::parser::{get_shaku_attributes, KeyValue, Parser};
 use crate::structures::service::{Property, PropertyDefault, PropertyType};
+use quote::ToTokens;
 use syn::spanned::Spanned;
-use syn::{Attribute, Error, Expr, Field, GenericArgument, Path, PathArguments, Type};
-
-fn check_for_attr(attr_name: &str, attrs: &[Attribute]) -> bool {
-    attrs.iter().any(|a| {
-        a.path.is_ident(consts::ATTR_NAME)
-            && a.parse_args::<Path>()
-                .map(|p| p.is_ident(attr_name))
-                .unwrap_or(false)
-    })
+use syn::{
+    Attribute, Error, Expr, ExprLit, Field, GenericArgument, Index, Lit, LitStr, Member, Path,
+    PathArguments, Type,
+};
+
+/// A `#[shaku(...)]` attribute on a struct field
+enum FieldAttribute {
+    Inject,
+    Provide,
+    InjectModule,
+    Default(PropertyDefault),
+    Secret,
+    FromSource(LitStr),
+}
+
+impl Parser<FieldAttribute> for Attribute {
+    fn parse_as(&self) -> syn::Result<FieldAttribute> {
+        if let Ok(path) = self.parse_args::<Path>() {
+            if path.is_ident(consts::INJECT_ATTR_NAME) {
+                return Ok(FieldAttribute::Inject);
+            } else if path.is_ident(consts::PROVIDE_ATTR_NAME) {
+                return Ok(FieldAttribute::Provide);
+            } else if path.is_ident(consts::INJECT_MODULE_ATTR_NAME) {
+                return Ok(FieldAttribute::InjectModule);
+            } else if path.is_ident(consts::DEFAULT_ATTR_NAME) {
+                return Ok(FieldAttribute::Default(PropertyDefault::NotProvided));
+            } else if path.is_ident(consts::SECRET_ATTR_NAME) {
+                return Ok(FieldAttribute::Secret);
+            }
+
+            return Err(Error::new_spanned(
+                &path,
+                format!("Unknown shaku attribute: '{}'", path.to_token_stream()),
+            ));
+        }
+
+        match self.parse_args::<KeyValue<Expr>>() {
+            Ok(inner) if inner.key == consts::DEFAULT_ATTR_NAME => Ok(FieldAttribute::Default(
+                PropertyDefault::Provided(Box::new(inner.value)),
+            )),
+            Ok(inner) if inner.key == consts::FROM_SOURCE_ATTR_NAME => match inner.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(key), ..
+                }) => Ok(FieldAttribute::FromSource(key)),
+                value => Err(Error::new_spanned(
+                    value,
+                    "Expected the key as a string, ex. `from_source = \"db_password\"`",
+                )),
+            },
+            Ok(inner) => Err(Error::new_spanned(
+                &inner.key,
+                format!("Unknown shaku attribute: '{}'", inner.key),
+            )),
+            Err(_) => Err(Error::new_spanned(
+                self,
+                format!("Unknown attribute: 'shaku{}'", self.tokens),
+            )),
+        }
+    }
 }
 
 impl Parser<Property> for Field {
     fn parse_as(&self) -> syn::Result<Property> {
-        let is_injected = check_for_attr(consts::INJECT_ATTR_NAME, &self.attrs);
-        let is_provided = check_for_attr(consts::PROVIDE_ATTR_NAME, &self.attrs);
-        let has_default = check_for_attr(consts::DEFAULT_ATTR_NAME, &self.attrs);
+        let mut inject_attr = None;
+        let mut provide_attr = None;
+        let mut inject_module_attr = None;
+        let mut default = None;
+        let mut secret_attr = None;
+        let mut source_attr = None;
+
+        for attr in get_shaku_attributes(&self.attrs) {
+            let already_set = match attr.parse_as()? {
+                FieldAttribute::Inject => inject_attr.replace(attr).is_some(),
+                FieldAttribute::Provide => provide_attr.replace(attr).is_some(),
+                FieldAttribute::InjectModule => inject_module_attr.replace(attr).is_some(),
+                FieldAttribute::Default(property_default) => {
+                    default.replace((attr, property_default)).is_some()
+                }
+                FieldAttribute::Secret => secret_attr.replace(attr).is_some(),
+                FieldAttribute::FromSource(key) => source_attr.replace((attr, key)).is_some(),
+            };
 
-        let property_name = self.ident.clone().ok_or_else(|| {
-            Error::new(self.span(), "Struct properties must be named".to_string())
-        })?;
+            if already_set {
+                return Err(Error::new_spanned(attr, "Duplicate attribute"));
+            }
+
+            if inject_attr.is_some() && provide_attr.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "Cannot inject and provide the same property",
+                ));
+            }
+
+            if inject_module_attr.is_some() && (inject_attr.is_some() || provide_attr.is_some()) {
+                return Err(Error::new_spanned(
+                    attr,
+                    "Cannot inject the module and a service into the same property",
+                ));
+            }
+        }
+
+        // Tuple struct properties are numbered once all of the fields are parsed
+        let member = match &self.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index {
+                index: 0,
+                span: self.ty.span(),
+            }),
+        };
         let doc_comment = self
             .attrs
             .iter()
@@ -29,52 +119,85 @@ impl Parser<Property> for Field {
             .cloned()
             .collect();
 
-        let property_type = match (is_injected, is_provided) {
-            (false, false) => {
-                let property_default = get_shaku_attribute(&self.attrs)
-                    .map(|attr| match attr.parse_args::<KeyValue<Expr>>().ok() {
-                        Some(inner) => {
-                            if inner.key == consts::DEFAULT_ATTR_NAME {
-                                Ok(PropertyDefault::Provided(Box::new(inner.value)))
-                            } else {
-                                Err(Error::new(
-                                    inner.key.span(),
-                                    format!("Unknown shaku attribute: '{}'", inner.key),
-                                ))
-                            }
-                        }
-                        None => {
-                            if has_default {
-                                Ok(PropertyDefault::NotProvided)
-                            } else {
-                                Err(Error::new(
-                                    attr.span(),
-                                    format!("Unknown attribute: 'shaku{}'", attr.tokens),
-                                ))
-                            }
-                        }
-                    })
-                    .transpose()?
-                    .unwrap_or(PropertyDefault::NoDefault);
+        if let Some((attr, key)) = source_attr {
+            if inject_attr.is_some() || provide_attr.is_some() || inject_module_attr.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "Injected and provided properties cannot be read from a parameter source",
+                ));
+            }
+
+            if let Some((default_attr, _)) = default {
+                return Err(Error::new_spanned(
+                    default_attr,
+                    "Properties read from a parameter source cannot have a default",
+                ));
+            }
+
+            if let Some(secret_attr) = secret_attr {
+                return Err(Error::new_spanned(
+                    secret_attr,
+                    "Properties read from a parameter source are not parameters, so they are \
+                     never printed and cannot be secret",
+                ));
+            }
+
+            return Ok(Property {
+                member: member.clone(),
+                parameters_member: member,
+                ty: self.ty.clone(),
+                property_type: PropertyType::Source,
+                default: PropertyDefault::NoDefault,
+                secret: false,
+                source_key: Some(key),
+                doc_comment,
+            });
+        }
+
+        let property_type = match (inject_attr, provide_attr, inject_module_attr) {
+            (None, None, None) => {
+                if let Some((default_attr, _)) = &default {
+                    if is_no_default_wrapper(&self.ty) {
+                        return Err(Error::new_spanned(
+                            default_attr,
+                            "NoDefault parameters cannot have a default",
+                        ));
+                    }
+                }
 
                 return Ok(Property {
-                    property_name,
+                    member: member.clone(),
+                    parameters_member: member,
                     ty: self.ty.clone(),
                     property_type: PropertyType::Parameter,
-                    default: property_default,
+                    default: default
+                        .map(|(_, property_default)| property_default)
+                        .unwrap_or(PropertyDefault::NoDefault),
+                    secret: secret_attr.is_some(),
+                    source_key: None,
                     doc_comment,
                 });
             }
-            (false, true) => PropertyType::Provided,
-            (true, false) => PropertyType::Component,
-            (true, true) => {
-                return Err(Error::new(
-                    property_name.span(),
-                    "Cannot inject and provide the same property",
-                ))
-            }
+            (None, Some(_), None) => PropertyType::Provided,
+            (Some(_), None, None) => PropertyType::Component,
+            (None, None, Some(_)) => PropertyType::Module,
+            _ => unreachable!("Checked while parsing the attributes"),
         };
 
+        if let Some((default_attr, _)) = default {
+            return Err(Error::new_spanned(
+                default_attr,
+                "Injected and provided properties cannot have a default",
+            ));
+        }
+
+        if let Some(secret_attr) = secret_attr {
+            return Err(Error::new_spanned(
+                secret_attr,
+                "Only parameters can be secret",
+            ));
+        }
+
         match &self.ty {
             Type::Path(path)
                 if {
@@ -83,7 +206,8 @@ impl Parser<Property> for Field {
                     match property_type {
                         PropertyType::Component => name == "Arc",
                         PropertyType::Provided => name == "Box",
-                        PropertyType::Parameter => unreachable!(),
+                        PropertyType::Module => name == "ModuleHandle",
+                        PropertyType::Parameter | PropertyType::Source => unreachable!(),
                     }
                 } =>
             {
@@ -109,39 +233,76 @@ impl Parser<Property> for Field {
                             _ => None
                         }
                     })
-                    .ok_or_else(|| Error::new(path.span(), format!(
+                    .ok_or_else(|| Error::new_spanned(path, format!(
                         "Failed to find interface trait in {}. Make sure the type is Arc<dyn Trait>",
-                        property_name
+                        quote!(#member)
                     )))?;
 
+                // The module is upcast into the trait object
+                match (property_type, interface_type) {
+                    (PropertyType::Module, Type::TraitObject(_)) => {}
+                    (PropertyType::Module, _) => {
+                        return Err(Error::new_spanned(
+                            interface_type,
+                            "Expected a module trait object, ex. ModuleHandle<dyn MyModule>",
+                        ))
+                    }
+                    _ => {}
+                }
+
                 Ok(Property {
-                    property_name,
+                    member: member.clone(),
+                    parameters_member: member,
                     ty: (*interface_type).clone(),
                     property_type,
                     default: PropertyDefault::NotProvided,
+                    secret: false,
+                    source_key: None,
                     doc_comment,
                 })
             }
 
             _ => match property_type {
-                PropertyType::Component => Err(Error::new(
-                    property_name.span(),
+                PropertyType::Component => Err(Error::new_spanned(
+                    &self.ty,
                     format!(
                         "Found non-Arc type annotated with #[{}({})]",
                         consts::ATTR_NAME,
                         consts::INJECT_ATTR_NAME
                     ),
                 )),
-                PropertyType::Provided => Err(Error::new(
-                    property_name.span(),
+                PropertyType::Provided => Err(Error::new_spanned(
+                    &self.ty,
                     format!(
                         "Found non-Box type annotated with #[{}({})]",
                         consts::ATTR_NAME,
                         consts::PROVIDE_ATTR_NAME
                     ),
                 )),
-                PropertyType::Parameter => unreachable!(),
+                PropertyType::Module => Err(Error::new_spanned(
+                    &self.ty,
+                    format!(
+                        "Found non-ModuleHandle type annotated with #[{}({})]",
+                        consts::ATTR_NAME,
+                        consts::INJECT_MODULE_ATTR_NAME
+                    ),
+                )),
+                PropertyType::Parameter | PropertyType::Source => unreachable!(),
             },
         }
     }
 }
+
+/// Check if the type is the `NoDefault` parameter wrapper, ex. `NoDefault<Box<dyn Fn()>>` or
+/// `shaku::NoDefault<...>`
+fn is_no_default_wrapper(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "NoDefault")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
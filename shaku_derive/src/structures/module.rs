@@ -8,6 +8,7 @@ use syn::punctuated::Punctuated;
 use syn::{token, Attribute, Generics, Ident, Type, Visibility};
 
 pub type ComponentItem = ModuleItem<ComponentAttribute>;
+pub type ProviderItem = ModuleItem<ProviderAttribute>;
 
 mod kw {
     syn::custom_keyword!(components);
@@ -17,6 +18,8 @@ mod kw {
 /// The main module data structure, parsed from the macro input
 #[derive(Debug)]
 pub struct ModuleData {
+    /// The services of the extended module, once they are known
+    pub extended_services: Option<ExtendedServices>,
     pub metadata: ModuleMetadata,
     pub services: ModuleServices,
     pub submodules: Punctuated<Submodule, syn::Token![,]>,
@@ -25,12 +28,56 @@ pub struct ModuleData {
 /// Metadata about the module
 #[derive(Debug)]
 pub struct ModuleMetadata {
+    /// Attributes which are forwarded to the module struct (ex. doc comments)
+    pub attributes: Vec<Attribute>,
+    pub options: ModuleOptions,
     pub visibility: Visibility,
     pub identifier: Ident,
     pub generics: Generics,
+    /// The module this module extends via `extends BaseModule`
+    pub extends: Option<Type>,
     pub interface: Option<Type>,
 }
 
+/// Options set on the module via `#[shaku(...)]`
+#[derive(Debug, Default)]
+pub struct ModuleOptions {
+    /// `#[shaku(derive_clone)]`: Implement `Clone` for the module
+    pub derive_clone: Option<Ident>,
+    /// `#[shaku(extendable)]`: Allow other modules (including in other crates) to extend the module
+    pub extendable: Option<Ident>,
+    /// `#[shaku(strict_builder)]`: Generate a builder which requires parameters without defaults
+    pub strict_builder: Option<Ident>,
+    /// `#[shaku(deny_unused)]`: Panic when building the module if it has services which are
+    /// unreachable from the module interface
+    pub deny_unused: Option<Ident>,
+    /// `#[shaku(last_wins)]`: Use the last registration of a service which is registered more
+    /// than once, instead of failing to compile
+    pub last_wins: Option<Ident>,
+    /// `#[shaku(memory_footprint)]`: Implement `ModuleMemoryFootprint` for the module
+    pub memory_footprint: Option<Ident>,
+}
+
+/// The services of an extended module. These are passed back to the `module` macro by the macro
+/// which `#[shaku(extendable)]` generates for the extended module, ex.
+/// `@extends_services BaseModule { components = [...], providers = [...] }`.
+#[derive(Debug)]
+pub struct ExtendedServices {
+    pub base: Type,
+    pub services: ModuleServices,
+}
+
+/// A module interface trait, parsed from the `module_interface` macro input
+#[derive(Debug)]
+pub struct ModuleInterfaceData {
+    /// Attributes which are forwarded to the trait (ex. doc comments)
+    pub attributes: Vec<Attribute>,
+    pub visibility: Visibility,
+    pub identifier: Ident,
+    pub generics: Generics,
+    pub services: ModuleServices,
+}
+
 /// A submodule dependency
 #[derive(Debug)]
 pub struct Submodule {
@@ -68,6 +115,18 @@ where
     Attribute: Parser<A>,
 {
     pub attributes: HashSet<A>,
+    /// The attributes as written, used for error spans
+    pub unparsed_attributes: Vec<Attribute>,
+    /// `#[cfg(...)]` attributes, including those of a feature group (ex.
+    /// `"redis" => [RedisCache]`). They are forwarded to the code generated
+    /// for the item, so it is only registered if the configuration is active.
+    pub cfg: Vec<Attribute>,
+    /// Doc comments, which are added to the docs of the generated `HasComponent`/`HasProvider`
+    /// impl
+    pub docs: Vec<Attribute>,
+    /// `#[name = "..."]`: The name of the module field which holds the service, instead of a
+    /// generated name (ex. `__di_component_3`)
+    pub name: Option<Ident>,
     pub ty: Type,
 }
 
@@ -76,16 +135,54 @@ impl ModuleItem<ComponentAttribute> {
     pub fn is_lazy(&self) -> bool {
         self.attributes.contains(&ComponentAttribute::Lazy)
     }
+
+    /// Check if a component is marked with `#[as_provider]`
+    pub fn is_provided(&self) -> bool {
+        self.attributes.contains(&ComponentAttribute::AsProvider)
+    }
+
+    /// Check if a component is marked with `#[instrument]`
+    pub fn is_instrumented(&self) -> bool {
+        self.attributes.contains(&ComponentAttribute::Instrument)
+    }
+
+    /// Get the note of a component marked with `#[deprecated]` (which is `None` if there is no
+    /// note), or `None` if the component is not deprecated
+    pub fn deprecation(&self) -> Option<Option<&str>> {
+        self.attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                ComponentAttribute::Deprecated(note) => Some(note.as_ref().map(String::as_str)),
+                _ => None,
+            })
+    }
 }
 
 /// Valid component attributes
 #[derive(Debug, Eq, PartialEq, Hash)]
 pub enum ComponentAttribute {
     Lazy,
+    /// `#[deprecated]` or `#[deprecated = "note"]`
+    Deprecated(Option<String>),
+    /// `#[as_provider]`: The component is also provided via `HasProvider`
+    AsProvider,
+    /// `#[instrument]`: The component is wrapped in its interface's tracing wrapper (see
+    /// `shaku::Instrumented`)
+    Instrument,
+}
+
+impl ModuleItem<ProviderAttribute> {
+    /// Check if a provider is marked with `#[promote_to_component]`
+    pub fn is_promoted(&self) -> bool {
+        self.attributes
+            .contains(&ProviderAttribute::PromoteToComponent)
+    }
 }
 
 /// Valid provider attributes
 #[derive(Debug, Eq, PartialEq, Hash)]
 pub enum ProviderAttribute {
-    // None currently
+    /// `#[promote_to_component]`: The provided service is built once and also resolved via
+    /// `HasComponent`
+    PromoteToComponent,
 }
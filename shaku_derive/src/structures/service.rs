@@ -1,7 +1,9 @@
 //! Structures to hold useful service data parsed from syn::DeriveInput
 
 use crate::parser::Parser;
-use syn::{Attribute, DeriveInput, Expr, Generics, Ident, Type, Visibility};
+use syn::{
+    Attribute, Data, DeriveInput, Expr, Generics, Ident, LitStr, Member, Path, Type, Visibility,
+};
 
 /// The main data structure, representing the data required to implement
 /// Component or Provider.
@@ -9,6 +11,9 @@ use syn::{Attribute, DeriveInput, Expr, Generics, Ident, Type, Visibility};
 pub struct ServiceData {
     pub metadata: MetaData,
     pub properties: Vec<Property>,
+    /// If the service is a tuple struct, so its properties (and parameters)
+    /// are positional
+    pub is_tuple_struct: bool,
 }
 
 impl ServiceData {
@@ -16,6 +21,10 @@ impl ServiceData {
         Ok(ServiceData {
             metadata: input.parse_as()?,
             properties: input.parse_as()?,
+            is_tuple_struct: match &input.data {
+                Data::Struct(data) => data.fields.iter().any(|field| field.ident.is_none()),
+                _ => false,
+            },
         })
     }
 }
@@ -27,6 +36,52 @@ pub struct MetaData {
     pub interface: Type,
     pub generics: Generics,
     pub visibility: Visibility,
+    /// Event types the service subscribes to via `#[shaku(subscribe = ?)]`
+    pub subscriptions: Vec<Type>,
+    /// Set via `#[shaku(health_check)]` if the component is registered with
+    /// the module's `HealthRegistry`
+    pub health_check: Option<Ident>,
+    /// Set via `#[shaku(lifecycle)]` if the component is registered with
+    /// the module's `LifecycleRegistry`
+    pub lifecycle: Option<Ident>,
+    /// Set via `#[shaku(reconfigurable)]` if the component is registered with
+    /// the module's `ConfigWatcher`
+    pub reconfigurable: Option<Ident>,
+    /// Traits to derive on the generated parameters struct via
+    /// `#[shaku(parameters_derive(?))]`
+    pub parameters_derives: Vec<Path>,
+    /// Set via `#[shaku(thread_bound)]` if the component is stored in a `ThreadBound`
+    pub thread_bound: Option<Ident>,
+    /// The field which implements the interface, set via `#[shaku(delegate = ?)]`
+    pub delegate: Option<Member>,
+    /// Adapters to other versions of the interface, set via
+    /// `#[shaku(adapter(? as ?))]`
+    pub adapters: Vec<Adapter>,
+    /// How a provider retries failures, set via
+    /// `#[shaku(retry(times = ?, backoff_ms = ?))]`
+    pub retry: Option<Retry>,
+    /// Set via `#[shaku(mock)]` if a component impl is generated for the
+    /// interface's mockall mock (ex. `MockLogger`) under `cfg(test)`
+    pub mock: Option<Ident>,
+    /// Components which are built before this one, set via
+    /// `#[shaku(build_after = ?)]`
+    pub build_after: Vec<Type>,
+}
+
+/// A generated component which implements another interface (ex. an older
+/// version) on top of the service's interface
+#[derive(Clone, Debug)]
+pub struct Adapter {
+    pub interface: Type,
+    pub identifier: Ident,
+}
+
+/// The retry policy of a provider
+#[derive(Clone, Debug)]
+pub struct Retry {
+    pub key: Ident,
+    pub times: u32,
+    pub backoff_ms: u64,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -34,25 +89,61 @@ pub enum PropertyType {
     Parameter,
     Component,
     Provided,
+    /// A handle to the module, set via `#[shaku(inject_module)]`
+    Module,
+    /// A value fetched from a parameter source, set via `#[shaku(from_source = "key")]`
+    Source,
 }
 
 /// Holds information about a service property.
 #[derive(Clone, Debug)]
 pub struct Property {
-    pub property_name: Ident,
+    /// The field name, or index for tuple structs
+    pub member: Member,
+    /// The field name or index in the parameters struct. Only parameters are
+    /// in the parameters struct, so the indices differ for tuple structs.
+    pub parameters_member: Member,
     /// The full type if not a service.
-    /// Otherwise, the interface type (the type inside the Arc or Box).
+    /// Otherwise, the interface type (the type inside the Arc, Box, or ModuleHandle).
     pub ty: Type,
     pub property_type: PropertyType,
     pub default: PropertyDefault,
+    /// Set via `#[shaku(secret)]` if the parameter is redacted in the
+    /// parameters struct's `Debug` impl
+    pub secret: bool,
+    /// The key of a value read from a parameter source, set via
+    /// `#[shaku(from_source = "key")]`
+    pub source_key: Option<LitStr>,
     pub doc_comment: Vec<Attribute>,
 }
 
 impl Property {
     pub fn is_service(&self) -> bool {
         match self.property_type {
-            PropertyType::Component | PropertyType::Provided => true,
-            PropertyType::Parameter => false,
+            PropertyType::Component | PropertyType::Provided | PropertyType::Module => true,
+            PropertyType::Parameter | PropertyType::Source => false,
+        }
+    }
+
+    /// Check if the property is part of the parameters struct
+    pub fn is_parameter(&self) -> bool {
+        match self.property_type {
+            PropertyType::Parameter => true,
+            PropertyType::Component
+            | PropertyType::Provided
+            | PropertyType::Module
+            | PropertyType::Source => false,
+        }
+    }
+
+    /// Check if the property is a module handle (`#[shaku(inject_module)]`)
+    pub fn is_module(&self) -> bool {
+        match self.property_type {
+            PropertyType::Module => true,
+            PropertyType::Component
+            | PropertyType::Provided
+            | PropertyType::Parameter
+            | PropertyType::Source => false,
         }
     }
 }
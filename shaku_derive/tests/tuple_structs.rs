@@ -0,0 +1,92 @@
+//! Tuple structs and unit structs can be derived
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
+use std::sync::Arc;
+
+trait Dependency: Interface {
+    fn value(&self) -> usize;
+}
+
+trait Service: Interface {
+    fn total(&self) -> usize;
+}
+
+trait Request {
+    fn total(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = Dependency)]
+struct UnitDependency;
+impl Dependency for UnitDependency {
+    fn value(&self) -> usize {
+        1
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Service)]
+struct TupleService(
+    #[shaku(default)] usize,
+    #[shaku(inject)] Arc<dyn Dependency>,
+    #[shaku(default = 100)] usize,
+);
+impl Service for TupleService {
+    fn total(&self) -> usize {
+        self.0 + self.1.value() + self.2
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Request)]
+struct TupleRequest(#[shaku(inject)] Arc<dyn Service>);
+impl Request for TupleRequest {
+    fn total(&self) -> usize {
+        self.0.total()
+    }
+}
+
+module! {
+    TestModule {
+        components = [UnitDependency, TupleService],
+        providers = [TupleRequest]
+    }
+}
+
+/// Tuple struct parameters are positional, skipping the injected fields
+#[test]
+fn tuple_parameters() {
+    let parameters = TupleServiceParameters(10, 20);
+
+    assert_eq!(parameters.0, 10);
+    assert_eq!(parameters.1, 20);
+}
+
+/// Tuple struct parameters use the field defaults
+#[test]
+fn tuple_parameters_default() {
+    let parameters = TupleServiceParameters::default();
+
+    assert_eq!(parameters.0, 0);
+    assert_eq!(parameters.1, 100);
+}
+
+/// Tuple struct components are built from their parameters and dependencies
+#[test]
+fn resolve_tuple_component() {
+    let module = TestModule::builder()
+        .with_component_parameters::<TupleService>(TupleServiceParameters(10, 20))
+        .build();
+    let service: Arc<dyn Service> = module.resolve();
+
+    assert_eq!(service.total(), 31);
+}
+
+/// Tuple struct providers are built from their dependencies
+#[test]
+fn provide_tuple_provider() {
+    let module = TestModule::builder().build();
+    let request: Box<dyn Request> = module.provide().unwrap();
+
+    assert_eq!(request.total(), 101);
+}
@@ -0,0 +1,31 @@
+//! Closure interfaces need exactly one method without a default implementation, which takes &self
+
+use shaku::{closure_interface, Interface};
+
+#[closure_interface]
+trait NoMethods: Interface {
+    fn provided(&self) {}
+}
+
+#[closure_interface]
+trait TwoMethods: Interface {
+    fn first(&self);
+    fn second(&self);
+}
+
+#[closure_interface]
+trait MutableSelf: Interface {
+    fn update(&mut self, value: usize);
+}
+
+#[closure_interface]
+trait GenericMethod: Interface {
+    fn handle<T>(&self, value: T);
+}
+
+#[closure_interface]
+trait GenericTrait<T>: Interface {
+    fn handle(&self, value: T);
+}
+
+fn main() {}
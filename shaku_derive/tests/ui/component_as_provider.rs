@@ -0,0 +1,19 @@
+//! Components can only be provided if the interface is implemented for `Arc<dyn Interface>`
+
+use shaku::{module, Component, Interface};
+
+trait Logger: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+module! {
+    TestModule {
+        components = [#[as_provider] LoggerImpl],
+        providers = []
+    }
+}
+
+fn main() {}
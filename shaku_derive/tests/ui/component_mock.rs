@@ -0,0 +1,12 @@
+//! Generating a mock bridge requires the `mock` feature
+
+use shaku::{Component, Interface};
+
+trait Logger: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Logger, mock)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+fn main() {}
@@ -0,0 +1,12 @@
+//! Components cannot be retried
+
+use shaku::Component;
+
+trait ComponentTrait {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait, retry(times = 3))]
+struct ComponentImpl;
+impl ComponentTrait for ComponentImpl {}
+
+fn main() {}
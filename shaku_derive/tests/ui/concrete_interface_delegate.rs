@@ -0,0 +1,11 @@
+//! Components which are their own interface cannot delegate it to a field
+
+use shaku::Component;
+
+#[derive(Component)]
+#[shaku(interface = Self, delegate = value)]
+struct DelegatingConfig {
+    value: usize,
+}
+
+fn main() {}
@@ -0,0 +1,47 @@
+//! Conditional services are not supported by strict builders, extendable modules, or submodules
+
+use shaku::{module, Component, Interface};
+
+trait Logger: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+module! {
+    #[shaku(strict_builder)]
+    StrictModule {
+        components = [#[cfg(all())] LoggerImpl],
+        providers = []
+    }
+}
+
+module! {
+    #[shaku(extendable)]
+    ExtendableModule {
+        components = ["some_feature" => [LoggerImpl]],
+        providers = []
+    }
+}
+
+module! {
+    BaseModule {
+        components = [LoggerImpl],
+        providers = []
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [],
+
+        use BaseModule {
+            components = ["some_feature" => [dyn Logger]],
+            providers = []
+        }
+    }
+}
+
+fn main() {}
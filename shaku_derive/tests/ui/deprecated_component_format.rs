@@ -0,0 +1,26 @@
+//! Deprecated components take an optional note
+
+use shaku::{module, Component, Interface};
+
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl;
+impl ComponentTrait for ComponentImpl {}
+
+module! {
+    TestModule {
+        components = [#[deprecated(since = "0.6")] ComponentImpl],
+        providers = []
+    }
+}
+
+module! {
+    DuplicateModule {
+        components = [#[deprecated] #[deprecated = "note"] ComponentImpl],
+        providers = []
+    }
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+//! Modules with lazy components cannot implement Clone
+
+use shaku::{module, Component, Interface};
+
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl;
+impl ComponentTrait for ComponentImpl {}
+
+module! {
+    #[shaku(derive_clone)]
+    TestModule {
+        components = [#[lazy] ComponentImpl],
+        providers = []
+    }
+}
+
+fn main() {}
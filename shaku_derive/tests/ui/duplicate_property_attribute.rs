@@ -0,0 +1,27 @@
+//! Property attributes cannot be repeated
+
+use shaku::{Component, Interface};
+use std::sync::Arc;
+
+trait DependencyTrait: Interface {}
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl1 {
+    #[shaku(inject)]
+    #[shaku(inject)]
+    dependency: Arc<dyn DependencyTrait>,
+}
+impl ComponentTrait for ComponentImpl1 {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl2 {
+    #[shaku(default = 1)]
+    #[shaku(default)]
+    value: usize,
+}
+impl ComponentTrait for ComponentImpl2 {}
+
+fn main() {}
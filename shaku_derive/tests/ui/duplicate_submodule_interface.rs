@@ -0,0 +1,78 @@
+//! A service can only be registered once, whether by the module or by one of its submodules
+
+use shaku::{module, Component, Interface};
+
+trait Logger: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Self)]
+struct Config;
+
+module! {
+    AuthModule {
+        components = [LoggerImpl, Config],
+        providers = []
+    }
+}
+
+module! {
+    StorageModule {
+        components = [LoggerImpl],
+        providers = []
+    }
+}
+
+module! {
+    SameBlockModule {
+        components = [],
+        providers = [],
+
+        use AuthModule {
+            components = [dyn Logger, dyn Logger],
+            providers = []
+        }
+    }
+}
+
+module! {
+    TwoSubmodulesModule {
+        components = [],
+        providers = [],
+
+        use AuthModule {
+            components = [dyn Logger],
+            providers = []
+        },
+
+        use StorageModule {
+            components = [dyn  Logger],
+            providers = []
+        }
+    }
+}
+
+module! {
+    OwnAndSubmoduleModule {
+        components = [Config],
+        providers = [],
+
+        use AuthModule {
+            components = [Config],
+            providers = []
+        }
+    }
+}
+
+module! {
+    OwnDuplicateModule {
+        components = [LoggerImpl, LoggerImpl],
+        providers = []
+    }
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+//! The module can only be injected into components, as a module trait object
+
+use shaku::{Component, HasComponent, Interface, ModuleHandle, Provider};
+use std::sync::Arc;
+
+trait ComponentTrait: Interface {}
+trait ProviderTrait {}
+trait ModuleTrait: HasComponent<dyn ComponentTrait> {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct NonHandleComponent {
+    #[shaku(inject_module)]
+    module: Arc<dyn ModuleTrait>,
+}
+impl ComponentTrait for NonHandleComponent {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ConcreteModuleComponent {
+    #[shaku(inject_module)]
+    module: ModuleHandle<String>,
+}
+impl ComponentTrait for ConcreteModuleComponent {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct InjectedModuleComponent {
+    #[shaku(inject)]
+    #[shaku(inject_module)]
+    module: ModuleHandle<dyn ModuleTrait>,
+}
+impl ComponentTrait for InjectedModuleComponent {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait)]
+struct ProviderImpl {
+    #[shaku(inject_module)]
+    module: ModuleHandle<dyn ModuleTrait>,
+}
+impl ProviderTrait for ProviderImpl {}
+
+fn main() {}
@@ -0,0 +1,18 @@
+//! Only parameters can be secret
+
+use shaku::{Component, Interface};
+use std::sync::Arc;
+
+trait DependencyTrait: Interface {}
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl {
+    #[shaku(inject)]
+    #[shaku(secret)]
+    dependency: Arc<dyn DependencyTrait>,
+}
+impl ComponentTrait for ComponentImpl {}
+
+fn main() {}
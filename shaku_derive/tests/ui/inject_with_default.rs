@@ -0,0 +1,18 @@
+//! Injected and provided properties cannot have a default
+
+use shaku::{Component, Interface};
+use std::sync::Arc;
+
+trait DependencyTrait: Interface {}
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl {
+    #[shaku(inject)]
+    #[shaku(default)]
+    dependency: Arc<dyn DependencyTrait>,
+}
+impl ComponentTrait for ComponentImpl {}
+
+fn main() {}
@@ -0,0 +1,28 @@
+//! Instrumented interfaces need `&self` methods, and cannot be generic or have associated items
+
+use shaku::Interface;
+use shaku_derive::instrument_interface;
+
+#[instrument_interface]
+trait MutableSelf: Interface {
+    fn update(&mut self, value: usize);
+}
+
+#[instrument_interface]
+trait GenericMethod: Interface {
+    fn handle<T>(&self, value: T);
+}
+
+#[instrument_interface]
+trait GenericTrait<T>: Interface {
+    fn handle(&self, value: T);
+}
+
+#[instrument_interface]
+trait AssociatedType: Interface {
+    type Output;
+
+    fn handle(&self) -> Self::Output;
+}
+
+fn main() {}
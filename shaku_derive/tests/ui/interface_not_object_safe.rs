@@ -0,0 +1,19 @@
+//! Object-unsafe interfaces are reported at the interface attribute
+
+use shaku::{Component, Interface};
+
+trait Serializer: Interface {
+    fn serialize<T>(&self, value: T) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Serializer)]
+struct SerializerImpl;
+
+impl Serializer for SerializerImpl {
+    fn serialize<T>(&self, _: T) -> String {
+        String::new()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+//! Module interface services cannot have attributes
+
+use shaku::{module_interface, Interface};
+
+trait Service: Interface {}
+
+module_interface! {
+    trait MyModule {
+        components = [#[lazy] dyn Service],
+        providers = []
+    }
+}
+
+fn main() {}
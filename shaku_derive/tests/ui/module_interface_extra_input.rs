@@ -0,0 +1,20 @@
+//! Module interfaces cannot use submodules
+
+use shaku::{module_interface, Interface};
+
+trait Service: Interface {}
+trait OtherModule {}
+
+module_interface! {
+    trait MyModule {
+        components = [dyn Service],
+        providers = [],
+
+        use dyn OtherModule {
+            components = [],
+            providers = []
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,65 @@
+//! Service names must be unique identifiers, and can only be used for the module's own services
+
+use shaku::{module, Component, Interface};
+
+trait Logger: Interface {}
+trait Cache: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl;
+impl Cache for CacheImpl {}
+
+module! {
+    DuplicateNameModule {
+        components = [#[name = "service"] LoggerImpl, #[name = "service"] CacheImpl],
+        providers = []
+    }
+}
+
+module! {
+    InvalidNameModule {
+        components = [#[name = "not an identifier"] LoggerImpl],
+        providers = []
+    }
+}
+
+module! {
+    ReservedNameModule {
+        components = [#[name = "__di_component_1"] LoggerImpl],
+        providers = []
+    }
+}
+
+module! {
+    NotNameValueModule {
+        components = [#[name(logger)] LoggerImpl],
+        providers = []
+    }
+}
+
+module! {
+    BaseModule {
+        components = [LoggerImpl],
+        providers = []
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [],
+
+        use BaseModule {
+            components = [#[name = "logger"] dyn Logger],
+            providers = []
+        }
+    }
+}
+
+fn main() {}
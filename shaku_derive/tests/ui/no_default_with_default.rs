@@ -0,0 +1,15 @@
+//! NoDefault parameters cannot have a default
+
+use shaku::{Component, Interface, NoDefault};
+
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl {
+    #[shaku(default = NoDefault(Box::new(|| 1)))]
+    value_fn: NoDefault<Box<dyn Fn() -> i32 + Send + Sync>>,
+}
+impl ComponentTrait for ComponentImpl {}
+
+fn main() {}
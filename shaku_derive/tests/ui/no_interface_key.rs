@@ -1,4 +1,4 @@
-//! "interface" must be used in the name-value notation for setting the interface
+//! Misspelled service attribute keys (ex. "interface") are reported
 
 use shaku::{Component, Interface, Provider};
 
@@ -0,0 +1,13 @@
+//! Providers cannot have adapters
+
+use shaku::Provider;
+
+trait ProviderTrait {}
+trait OldProviderTrait {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait, adapter(OldProviderTrait as OldProviderAdapter))]
+struct ProviderImpl;
+impl ProviderTrait for ProviderImpl {}
+
+fn main() {}
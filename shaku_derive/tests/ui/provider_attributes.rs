@@ -1,4 +1,4 @@
-//! Providers cannot have module attributes
+//! Providers only accept the #[promote_to_component] module attribute
 
 use shaku::{module, Provider};
 
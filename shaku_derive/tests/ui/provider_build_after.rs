@@ -0,0 +1,17 @@
+//! Providers cannot set a build order
+
+use shaku::Provider;
+
+trait ProviderTrait {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait)]
+struct OtherProviderImpl;
+impl ProviderTrait for OtherProviderImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait, build_after = OtherProviderImpl)]
+struct ProviderImpl;
+impl ProviderTrait for ProviderImpl {}
+
+fn main() {}
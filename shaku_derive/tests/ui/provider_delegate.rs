@@ -0,0 +1,12 @@
+//! Providers cannot delegate their interface
+
+use shaku::Provider;
+
+trait ProviderTrait {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait, delegate = inner)]
+struct ProviderImpl;
+impl ProviderTrait for ProviderImpl {}
+
+fn main() {}
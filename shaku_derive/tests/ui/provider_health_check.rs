@@ -0,0 +1,12 @@
+//! Providers cannot be health checks
+
+use shaku::Provider;
+
+trait ProviderTrait {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait, health_check)]
+struct ProviderImpl;
+impl ProviderTrait for ProviderImpl {}
+
+fn main() {}
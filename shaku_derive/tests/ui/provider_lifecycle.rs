@@ -0,0 +1,12 @@
+//! Providers cannot have lifecycle hooks
+
+use shaku::Provider;
+
+trait ProviderTrait {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait, lifecycle)]
+struct ProviderImpl;
+impl ProviderTrait for ProviderImpl {}
+
+fn main() {}
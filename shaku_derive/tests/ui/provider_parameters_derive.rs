@@ -0,0 +1,12 @@
+//! Providers do not have a parameters struct to derive traits on
+
+use shaku::Provider;
+
+trait ProviderTrait {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait, parameters_derive(Clone))]
+struct ProviderImpl;
+impl ProviderTrait for ProviderImpl {}
+
+fn main() {}
@@ -0,0 +1,18 @@
+//! The retry attribute requires the number of retries, and only knows its own
+//! options
+
+use shaku::Provider;
+
+trait ProviderTrait {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait, retry(backoff_ms = 100))]
+struct MissingTimes;
+impl ProviderTrait for MissingTimes {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait, retry(times = 3, jitter = 10))]
+struct UnknownOption;
+impl ProviderTrait for UnknownOption {}
+
+fn main() {}
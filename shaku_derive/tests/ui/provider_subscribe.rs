@@ -0,0 +1,14 @@
+//! Providers cannot subscribe to events
+
+use shaku::Provider;
+
+trait ProviderTrait {}
+
+struct MyEvent;
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait, subscribe = MyEvent)]
+struct ProviderImpl;
+impl ProviderTrait for ProviderImpl {}
+
+fn main() {}
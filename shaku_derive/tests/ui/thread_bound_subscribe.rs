@@ -0,0 +1,14 @@
+//! Thread-bound components cannot subscribe to events
+
+use shaku::{Component, LocalInterface};
+
+trait ComponentTrait: LocalInterface {}
+
+struct MyEvent;
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait, thread_bound, subscribe = MyEvent)]
+struct ComponentImpl;
+impl ComponentTrait for ComponentImpl {}
+
+fn main() {}
@@ -0,0 +1,14 @@
+//! Tuple struct providers can't have parameters either
+
+use shaku::{Interface, Provider};
+use std::sync::Arc;
+
+trait DependencyTrait: Interface {}
+trait ProviderTrait {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait)]
+struct ProviderImpl(#[shaku(inject)] Arc<dyn DependencyTrait>, usize);
+impl ProviderTrait for ProviderImpl {}
+
+fn main() {}
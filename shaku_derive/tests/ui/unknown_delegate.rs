@@ -0,0 +1,22 @@
+//! The delegate must be a parameter of the component
+
+use shaku::{Component, Interface};
+use std::sync::Arc;
+
+trait ComponentTrait: Interface {}
+trait DependencyTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait, delegate = missing)]
+struct UnknownDelegate {
+    value: usize,
+}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait, delegate = dependency)]
+struct ServiceDelegate {
+    #[shaku(inject)]
+    dependency: Arc<dyn DependencyTrait>,
+}
+
+fn main() {}
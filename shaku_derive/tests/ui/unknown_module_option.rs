@@ -0,0 +1,22 @@
+//! Only known options can be set on a module
+
+use shaku::module;
+
+module! {
+    #[shaku(derive_clone, unknown)]
+    TestModule {
+        components = [],
+        providers = []
+    }
+}
+
+module! {
+    #[shaku(derive_clone)]
+    #[shaku(derive_clone)]
+    TestModule2 {
+        components = [],
+        providers = []
+    }
+}
+
+fn main() {}
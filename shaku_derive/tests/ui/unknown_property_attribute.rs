@@ -0,0 +1,23 @@
+//! Unknown property attributes are reported
+
+use shaku::{Component, Interface};
+
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl1 {
+    #[shaku(injected)]
+    value: usize,
+}
+impl ComponentTrait for ComponentImpl1 {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl2 {
+    #[shaku(value = 5)]
+    value: usize,
+}
+impl ComponentTrait for ComponentImpl2 {}
+
+fn main() {}
@@ -0,0 +1,23 @@
+//! Unknown or repeated service attributes are reported
+
+use shaku::{Component, Interface};
+
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait, thread_bond)]
+struct ComponentImpl1;
+impl ComponentTrait for ComponentImpl1 {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait, parameter_derive(Clone))]
+struct ComponentImpl2;
+impl ComponentTrait for ComponentImpl2 {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl3;
+impl ComponentTrait for ComponentImpl3 {}
+
+fn main() {}
@@ -0,0 +1,48 @@
+use crate::DieselPool;
+use diesel::r2d2::{ConnectionManager, PooledConnection, R2D2Connection};
+use shaku::{HasComponent, Module, Provider};
+use std::error::Error;
+use std::ops::{Deref, DerefMut};
+
+/// A connection checked out of the module's [`DieselPool`]. The connection is returned to the
+/// pool when this is dropped.
+///
+/// This type dereferences to the diesel connection, so queries can be run via
+/// `query.execute(&mut *connection)` (or `&mut **connection` when the connection is boxed).
+///
+/// Providing this service fails if a connection could not be checked out before the pool's
+/// connection timeout.
+///
+/// [`DieselPool`]: trait.DieselPool.html
+pub struct DieselConnection<C: R2D2Connection + 'static> {
+    connection: PooledConnection<ConnectionManager<C>>,
+}
+
+impl<C: R2D2Connection + 'static> Deref for DieselConnection<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.connection
+    }
+}
+
+impl<C: R2D2Connection + 'static> DerefMut for DieselConnection<C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.connection
+    }
+}
+
+impl<M, C> Provider<M> for DieselConnection<C>
+where
+    M: Module + HasComponent<dyn DieselPool<C>>,
+    C: R2D2Connection + 'static,
+{
+    type Interface = DieselConnection<C>;
+
+    fn provide(module: &M) -> Result<Box<Self::Interface>, Box<dyn Error>> {
+        let pool: &dyn DieselPool<C> = module.resolve_ref();
+        let connection = pool.pool().get()?;
+
+        Ok(Box::new(DieselConnection { connection }))
+    }
+}
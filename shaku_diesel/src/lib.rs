@@ -0,0 +1,44 @@
+//! This crate provides integration between the `shaku` and `diesel` crates.
+//!
+//! Add [`DieselPoolImpl`] as a component and [`DieselConnection`] as a provider to your module,
+//! then use `HasProvider<DieselConnection<C>>` to check out a pooled connection wherever one is
+//! needed (for example via `InjectProvided` in a web handler, which gives each request its own
+//! connection).
+//!
+//! # Example
+//! ```rust
+//! use diesel::sqlite::SqliteConnection;
+//! use diesel::RunQueryDsl;
+//! use shaku::{module, HasProvider};
+//! use shaku_diesel::{DieselConnection, DieselPoolImpl, DieselPoolImplParameters};
+//!
+//! module! {
+//!     DatabaseModule {
+//!         components = [DieselPoolImpl<SqliteConnection>],
+//!         providers = [DieselConnection<SqliteConnection>]
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let module = DatabaseModule::builder()
+//!     .with_component_parameters::<DieselPoolImpl<SqliteConnection>>(DieselPoolImplParameters {
+//!         database_url: ":memory:".to_string(),
+//!         ..Default::default()
+//!     })
+//!     .build();
+//!
+//! let mut connection: Box<DieselConnection<SqliteConnection>> = module.provide().unwrap();
+//! diesel::sql_query("CREATE TABLE users (id INTEGER PRIMARY KEY)")
+//!     .execute(&mut **connection)
+//!     .unwrap();
+//! # }
+//! ```
+//!
+//! [`DieselPoolImpl`]: struct.DieselPoolImpl.html
+//! [`DieselConnection`]: struct.DieselConnection.html
+
+mod connection;
+mod pool;
+
+pub use connection::DieselConnection;
+pub use pool::{DieselPool, DieselPoolImpl, DieselPoolImplParameters};
@@ -0,0 +1,77 @@
+use diesel::r2d2::{ConnectionManager, Pool, R2D2Connection};
+use shaku::{Component, Interface, Module, ModuleBuildContext};
+use std::time::Duration;
+
+/// A pool of diesel connections. [`DieselConnection`] checks out connections from the module's
+/// implementation of this trait.
+///
+/// [`DieselConnection`]: struct.DieselConnection.html
+pub trait DieselPool<C: R2D2Connection + 'static>: Interface {
+    /// Get the underlying r2d2 pool
+    fn pool(&self) -> &Pool<ConnectionManager<C>>;
+}
+
+/// The default [`DieselPool`] implementation. The pool is configured via
+/// [`DieselPoolImplParameters`].
+///
+/// Connections are not opened while the module is built. They are opened in the background, and
+/// errors (ex. an unreachable database) are reported when a connection is checked out.
+///
+/// Use [`DieselPoolImpl::new`] with `ModuleBuilder::with_component_override` to use a pool with
+/// custom settings.
+///
+/// [`DieselPool`]: trait.DieselPool.html
+/// [`DieselPoolImplParameters`]: struct.DieselPoolImplParameters.html
+/// [`DieselPoolImpl::new`]: struct.DieselPoolImpl.html#method.new
+pub struct DieselPoolImpl<C: R2D2Connection + 'static> {
+    pool: Pool<ConnectionManager<C>>,
+}
+
+impl<C: R2D2Connection + 'static> DieselPoolImpl<C> {
+    /// Wrap an existing pool
+    pub fn new(pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselPoolImpl { pool }
+    }
+}
+
+impl<C: R2D2Connection + 'static> DieselPool<C> for DieselPoolImpl<C> {
+    fn pool(&self) -> &Pool<ConnectionManager<C>> {
+        &self.pool
+    }
+}
+
+/// Parameters for [`DieselPoolImpl`]
+///
+/// [`DieselPoolImpl`]: struct.DieselPoolImpl.html
+pub struct DieselPoolImplParameters {
+    /// The URL of the database to connect to
+    pub database_url: String,
+    /// The maximum number of connections in the pool (default: 10)
+    pub max_size: u32,
+    /// How long to wait for a connection before giving up (default: 30 seconds)
+    pub connection_timeout: Duration,
+}
+
+impl Default for DieselPoolImplParameters {
+    fn default() -> Self {
+        DieselPoolImplParameters {
+            database_url: String::new(),
+            max_size: 10,
+            connection_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl<M: Module, C: R2D2Connection + 'static> Component<M> for DieselPoolImpl<C> {
+    type Interface = dyn DieselPool<C>;
+    type Parameters = DieselPoolImplParameters;
+
+    fn build(_: &mut ModuleBuildContext<M>, params: Self::Parameters) -> Box<Self::Interface> {
+        let pool = Pool::builder()
+            .max_size(params.max_size)
+            .connection_timeout(params.connection_timeout)
+            .build_unchecked(ConnectionManager::new(params.database_url));
+
+        Box::new(DieselPoolImpl { pool })
+    }
+}
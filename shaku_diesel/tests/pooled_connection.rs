@@ -0,0 +1,83 @@
+//! Diesel connections can be provided from a pool component
+
+use diesel::sql_types::Integer;
+use diesel::sqlite::SqliteConnection;
+use diesel::{Connection, RunQueryDsl};
+use shaku::{module, HasProvider};
+use shaku_diesel::{DieselConnection, DieselPoolImpl, DieselPoolImplParameters};
+use std::time::Duration;
+
+module! {
+    DatabaseModule {
+        components = [DieselPoolImpl<SqliteConnection>],
+        providers = [DieselConnection<SqliteConnection>]
+    }
+}
+
+fn create_module(max_size: u32) -> DatabaseModule {
+    DatabaseModule::builder()
+        .with_component_parameters::<DieselPoolImpl<SqliteConnection>>(DieselPoolImplParameters {
+            database_url: ":memory:".to_string(),
+            max_size,
+            connection_timeout: Duration::from_millis(100),
+        })
+        .build()
+}
+
+/// A provided connection can run queries
+#[test]
+fn provided_connection_runs_queries() {
+    let module = create_module(1);
+    let mut connection: Box<DieselConnection<SqliteConnection>> = module.provide().unwrap();
+
+    let value: i32 = diesel::select(diesel::dsl::sql::<Integer>("1 + 1"))
+        .get_result(&mut **connection)
+        .unwrap();
+
+    assert_eq!(value, 2);
+}
+
+/// Connections are returned to the pool when dropped, and reused afterwards
+#[test]
+fn connections_are_pooled() {
+    let module = create_module(1);
+
+    {
+        let mut connection: Box<DieselConnection<SqliteConnection>> = module.provide().unwrap();
+        diesel::sql_query("CREATE TABLE pooled (id INTEGER PRIMARY KEY)")
+            .execute(&mut **connection)
+            .unwrap();
+    }
+
+    // The in-memory database only exists on the pool's single connection
+    let mut connection: Box<DieselConnection<SqliteConnection>> = module.provide().unwrap();
+    diesel::sql_query("INSERT INTO pooled (id) VALUES (1)")
+        .execute(&mut **connection)
+        .unwrap();
+}
+
+/// Providing a connection fails if the pool is exhausted
+#[test]
+fn exhausted_pool_fails() {
+    let module = create_module(1);
+    let _connection: Box<DieselConnection<SqliteConnection>> = module.provide().unwrap();
+
+    let result: Result<Box<DieselConnection<SqliteConnection>>, _> = module.provide();
+
+    assert!(result.is_err());
+}
+
+/// Transactions can be run on a provided connection
+#[test]
+fn provided_connection_transaction() {
+    let module = create_module(1);
+    let mut connection: Box<DieselConnection<SqliteConnection>> = module.provide().unwrap();
+
+    let value = connection
+        .transaction::<_, diesel::result::Error, _>(|connection| {
+            diesel::select(diesel::dsl::sql::<Integer>("5")).get_result::<i32>(connection)
+        })
+        .unwrap();
+
+    assert_eq!(value, 5);
+}
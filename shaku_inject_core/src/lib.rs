@@ -0,0 +1,179 @@
+//! This crate contains the framework-agnostic logic behind the `Inject` and `InjectProvided`
+//! extractors of the shaku web framework integrations.
+//!
+//! An integration wraps its framework's request type in a local struct, implements
+//! [`InjectRequest`] for it, and calls [`resolve_component`] or [`provide_service`] from its
+//! extractor once it has looked up the module. If the module can't be found, the integration
+//! should fail the request with the message from [`module_not_found`]. If a service can't be
+//! provided, it should fail the request with the message from [`provide_failed`].
+//!
+//! # Crate features
+//! - `tracing`: Provide services in the request's span, and log failed injections at error level
+//!   (target `shaku::inject`) with the module and interface type names.
+//! - `debug_responses`: In debug builds, include the module and interface type names in the
+//!   messages which requests are failed with. Release builds never include them.
+//!
+//! # Example
+//! ```rust
+//! use shaku::{module, HasProvider, Provider};
+//! use shaku_inject_core::{provide_service, InjectRequest};
+//!
+//! trait HelloWorld: Send + Sync {
+//!     fn greet(&self) -> String;
+//! }
+//!
+//! #[derive(Provider)]
+//! #[shaku(interface = HelloWorld)]
+//! struct HelloWorldImpl;
+//!
+//! impl HelloWorld for HelloWorldImpl {
+//!     fn greet(&self) -> String {
+//!         "Hello, world!".to_owned()
+//!     }
+//! }
+//!
+//! module! {
+//!     HelloModule {
+//!         components = [],
+//!         providers = [HelloWorldImpl]
+//!     }
+//! }
+//!
+//! /// The framework's request type would be wrapped here
+//! struct MyFrameworkRequest;
+//!
+//! impl InjectRequest for MyFrameworkRequest {}
+//!
+//! let module = HelloModule::builder().build();
+//! let service: Box<dyn HelloWorld> = provide_service(&module, &MyFrameworkRequest).unwrap();
+//!
+//! assert_eq!(service.greet(), "Hello, world!");
+//! ```
+//!
+//! [`InjectRequest`]: trait.InjectRequest.html
+//! [`resolve_component`]: fn.resolve_component.html
+//! [`provide_service`]: fn.provide_service.html
+//! [`module_not_found`]: fn.module_not_found.html
+//! [`provide_failed`]: fn.provide_failed.html
+
+use shaku::{HasComponent, HasProvider, Interface};
+use std::error::Error;
+use std::sync::Arc;
+
+/// The error message used when the module could not be found in the framework's state.
+pub const MODULE_NOT_FOUND: &str = "Failed to retrieve module from state";
+
+/// The message to fail the request with when the module `M` could not be found in the
+/// framework's state while injecting the interface `I`.
+///
+/// With the `tracing` feature, the failure is logged at error level (target `shaku::inject`). With
+/// the `debug_responses` feature, debug builds include the module and interface type names in the
+/// message. Otherwise the message is [`MODULE_NOT_FOUND`].
+///
+/// [`MODULE_NOT_FOUND`]: constant.MODULE_NOT_FOUND.html
+pub fn module_not_found<M: ?Sized, I: ?Sized>() -> String {
+    #[cfg(feature = "tracing")]
+    tracing::error!(
+        target: "shaku::inject",
+        module = std::any::type_name::<M>(),
+        interface = std::any::type_name::<I>(),
+        "{}",
+        MODULE_NOT_FOUND
+    );
+
+    response_message::<M, I>(MODULE_NOT_FOUND.to_string())
+}
+
+/// The message to fail the request with when the interface `I` could not be provided by the
+/// module `M`.
+///
+/// With the `tracing` feature, the failure is logged at error level (target `shaku::inject`). With
+/// the `debug_responses` feature, debug builds include the module and interface type names in the
+/// message. Otherwise the message is the provider's error message.
+pub fn provide_failed<M: ?Sized, I: ?Sized>(error: &dyn Error) -> String {
+    #[cfg(feature = "tracing")]
+    tracing::error!(
+        target: "shaku::inject",
+        module = std::any::type_name::<M>(),
+        interface = std::any::type_name::<I>(),
+        error = %error,
+        "Failed to provide service"
+    );
+
+    response_message::<M, I>(error.to_string())
+}
+
+/// Add the module and interface type names to the message, if the `debug_responses` feature is
+/// turned on in a debug build
+fn response_message<M: ?Sized, I: ?Sized>(message: String) -> String {
+    if cfg!(all(feature = "debug_responses", debug_assertions)) {
+        format!(
+            "{} (module: `{}`, interface: `{}`)",
+            message,
+            std::any::type_name::<M>(),
+            std::any::type_name::<I>()
+        )
+    } else {
+        message
+    }
+}
+
+/// Hooks which let an integration expose request-specific data to the shared extractor logic.
+///
+/// Every hook has a default, so an integration only needs to implement the hooks its framework
+/// supports.
+pub trait InjectRequest {
+    /// The span which services are provided in (and which is passed to providers via
+    /// `shaku::ProvideContext`). Defaults to the current span.
+    #[cfg(feature = "tracing")]
+    fn span(&self) -> Option<tracing::Span> {
+        None
+    }
+}
+
+/// Resolve a component for an `Inject` extractor.
+pub fn resolve_component<M, I>(module: &M) -> Arc<I>
+where
+    M: HasComponent<I> + ?Sized,
+    I: Interface + ?Sized,
+{
+    module.resolve()
+}
+
+/// Create a provided service for an `InjectProvided` extractor.
+///
+/// With the `tracing` feature enabled, the service is provided inside the request's span (see
+/// [`InjectRequest::span`]), which is also available to providers via `shaku::ProvideContext`.
+///
+/// [`InjectRequest::span`]: trait.InjectRequest.html#method.span
+pub fn provide_service<M, I, R>(module: &M, request: &R) -> Result<Box<I>, Box<dyn Error>>
+where
+    M: HasProvider<I> + ?Sized,
+    I: ?Sized,
+    R: InjectRequest + ?Sized,
+{
+    provide_in_span(module, request)
+}
+
+#[cfg(not(feature = "tracing"))]
+fn provide_in_span<M, I, R>(module: &M, _request: &R) -> Result<Box<I>, Box<dyn Error>>
+where
+    M: HasProvider<I> + ?Sized,
+    I: ?Sized,
+    R: InjectRequest + ?Sized,
+{
+    module.provide()
+}
+
+#[cfg(feature = "tracing")]
+fn provide_in_span<M, I, R>(module: &M, request: &R) -> Result<Box<I>, Box<dyn Error>>
+where
+    M: HasProvider<I> + ?Sized,
+    I: ?Sized,
+    R: InjectRequest + ?Sized,
+{
+    let span = request.span().unwrap_or_else(tracing::Span::current);
+    let context = shaku::ProvideContext::new().with(span.clone());
+
+    span.in_scope(|| module.provide_with_context(&context))
+}
@@ -0,0 +1,49 @@
+//! The messages which failed injections are reported with
+
+use shaku_inject_core::{module_not_found, provide_failed, MODULE_NOT_FOUND};
+use std::error::Error;
+use std::fmt;
+
+struct AppModule;
+trait Database {}
+
+#[derive(Debug)]
+struct ConnectionError;
+
+impl fmt::Display for ConnectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "connection refused")
+    }
+}
+
+impl Error for ConnectionError {}
+
+/// With `debug_responses`, debug builds name the module and interface
+fn with_details(message: &str) -> String {
+    if cfg!(all(feature = "debug_responses", debug_assertions)) {
+        format!(
+            "{} (module: `error_messages::AppModule`, interface: `dyn error_messages::Database`)",
+            message
+        )
+    } else {
+        message.to_string()
+    }
+}
+
+/// A missing module is reported with MODULE_NOT_FOUND
+#[test]
+fn missing_module_message() {
+    assert_eq!(
+        module_not_found::<AppModule, dyn Database>(),
+        with_details(MODULE_NOT_FOUND)
+    );
+}
+
+/// A provider failure is reported with the provider's error
+#[test]
+fn provide_failed_message() {
+    assert_eq!(
+        provide_failed::<AppModule, dyn Database>(&ConnectionError),
+        with_details("connection refused")
+    );
+}
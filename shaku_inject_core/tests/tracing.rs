@@ -0,0 +1,87 @@
+//! With the `tracing` feature, `provide_service` provides services inside the span from the
+//! `InjectRequest::span` hook
+
+use shaku::{module, HasProvider, Module, ProvideContext, Provider};
+use shaku_inject_core::{provide_service, InjectRequest};
+use std::error::Error;
+use tracing::span::Id;
+use tracing::Span;
+
+trait SpanRecorder {
+    fn context_span(&self) -> Option<Id>;
+    fn current_span(&self) -> Option<Id>;
+}
+
+struct SpanRecorderImpl {
+    context_span: Option<Id>,
+    current_span: Option<Id>,
+}
+
+impl SpanRecorder for SpanRecorderImpl {
+    fn context_span(&self) -> Option<Id> {
+        self.context_span.clone()
+    }
+
+    fn current_span(&self) -> Option<Id> {
+        self.current_span.clone()
+    }
+}
+
+impl<M: Module> Provider<M> for SpanRecorderImpl {
+    type Interface = dyn SpanRecorder;
+
+    fn provide(_: &M) -> Result<Box<dyn SpanRecorder>, Box<dyn Error>> {
+        Ok(Box::new(SpanRecorderImpl {
+            context_span: ProvideContext::current()
+                .and_then(|context| context.get::<Span>().and_then(Span::id)),
+            current_span: Span::current().id(),
+        }))
+    }
+}
+
+trait MyModule: HasProvider<dyn SpanRecorder> {}
+
+module! {
+    MyModuleImpl: MyModule {
+        components = [],
+        providers = [SpanRecorderImpl]
+    }
+}
+
+struct TestRequest(Option<Span>);
+
+impl InjectRequest for TestRequest {
+    fn span(&self) -> Option<Span> {
+        self.0.clone()
+    }
+}
+
+/// The span from the request hook is used
+#[test]
+fn span_from_hook() {
+    let _subscriber = tracing::subscriber::set_default(tracing_subscriber::registry());
+    let span = tracing::info_span!("request");
+    let module = MyModuleImpl::builder().build();
+
+    let recorder: Box<dyn SpanRecorder> =
+        provide_service(&module, &TestRequest(Some(span.clone()))).unwrap();
+
+    assert!(span.id().is_some());
+    assert_eq!(recorder.context_span(), span.id());
+    assert_eq!(recorder.current_span(), span.id());
+}
+
+/// The current span is used if the request hook doesn't provide a span
+#[test]
+fn current_span_fallback() {
+    let _subscriber = tracing::subscriber::set_default(tracing_subscriber::registry());
+    let span = tracing::info_span!("outer");
+    let _entered = span.enter();
+    let module = MyModuleImpl::builder().build();
+
+    let recorder: Box<dyn SpanRecorder> = provide_service(&module, &TestRequest(None)).unwrap();
+
+    assert!(span.id().is_some());
+    assert_eq!(recorder.context_span(), span.id());
+    assert_eq!(recorder.current_span(), span.id());
+}
@@ -0,0 +1,30 @@
+#[macro_use]
+extern crate rocket;
+
+use crate::services::{AppModule, EnglishModule, FrenchModule, Greeter, Greeting};
+use shaku_rocket::{Inject, InjectProvided};
+
+mod services;
+
+#[get("/")]
+fn greeting(greeting: Inject<dyn AppModule, dyn Greeting>) -> String {
+    greeting.greeting().to_string()
+}
+
+#[get("/greet/<name>")]
+fn greet(name: &str, greeter: InjectProvided<dyn AppModule, dyn Greeter>) -> String {
+    greeter.greet(name)
+}
+
+#[rocket::launch]
+async fn rocket() -> _ {
+    // Set GREETING_LANGUAGE=fr to use the French module
+    let module: Box<dyn AppModule> = match std::env::var("GREETING_LANGUAGE").as_deref() {
+        Ok("fr") => FrenchModule::builder().build().into(),
+        _ => EnglishModule::builder().build().into(),
+    };
+
+    rocket::build()
+        .manage(module)
+        .mount("/", routes![greeting, greet])
+}
@@ -0,0 +1,94 @@
+use rocket::figment::{self, Figment};
+use rocket::serde::de::DeserializeOwned;
+use shaku::{Component, HasComponent, Module, ModuleBuilder};
+
+/// Reads component parameters from Rocket's configuration (a [`Figment`]), so the module is
+/// configured via `Rocket.toml` and `ROCKET_` environment variables like the rest of the app.
+///
+/// The component's parameters struct must implement `Deserialize`, ex. via
+/// `#[shaku(interface = Database, parameters_derive(Deserialize))]`.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use serde::Deserialize;
+/// use shaku::{module, Component, Interface};
+/// use shaku_rocket::{FigmentParameters, Inject};
+///
+/// trait Database: Interface {
+///     fn url(&self) -> &str;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Database, parameters_derive(Deserialize))]
+/// struct DatabaseImpl {
+///     url: String,
+/// }
+///
+/// impl Database for DatabaseImpl {
+///     fn url(&self) -> &str {
+///         &self.url
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [DatabaseImpl],
+///         providers = []
+///     }
+/// }
+///
+/// #[get("/")]
+/// fn index(database: Inject<MyModule, dyn Database>) -> String {
+///     database.url().to_string()
+/// }
+///
+/// # fn main() -> Result<(), rocket::figment::Error> {
+/// // Rocket.toml:
+/// // [default.database]
+/// // url = "postgres://localhost"
+/// let rocket = rocket::build();
+/// let module = MyModule::builder()
+///     .with_figment_parameters::<DatabaseImpl>(rocket.figment(), "database")?
+///     .build();
+///
+/// let rocket = rocket.manage(Box::new(module)).mount("/", routes![index]);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Figment`]: https://docs.rs/figment/0.10/figment/struct.Figment.html
+pub trait FigmentParameters<M: Module>: Sized {
+    /// Set the parameters of the component `C` from the value at `key` in the figment (ex.
+    /// `"database"` for the `[default.database]` section of `Rocket.toml`). If the figment has no
+    /// value at the key, the parameters are left unset, so the defaults are used.
+    fn with_figment_parameters<C>(
+        self,
+        figment: &Figment,
+        key: &str,
+    ) -> Result<Self, figment::Error>
+    where
+        C: Component<M>,
+        C::Parameters: DeserializeOwned,
+        M: HasComponent<C::Interface>;
+}
+
+impl<M: Module> FigmentParameters<M> for ModuleBuilder<M> {
+    fn with_figment_parameters<C>(
+        self,
+        figment: &Figment,
+        key: &str,
+    ) -> Result<Self, figment::Error>
+    where
+        C: Component<M>,
+        C::Parameters: DeserializeOwned,
+        M: HasComponent<C::Interface>,
+    {
+        if !figment.contains(key) {
+            return Ok(self);
+        }
+
+        let parameters = figment.extract_inner::<C::Parameters>(key)?;
+        Ok(self.with_component_parameters::<C>(parameters))
+    }
+}
@@ -0,0 +1,55 @@
+use rocket::http::Status;
+use rocket::response::{self, Responder};
+use rocket::Request;
+use shaku::extras::HealthReport;
+
+/// Responds with a [`HealthReport`] as text, with status `200 OK` if the report is healthy and
+/// `503 Service Unavailable` otherwise.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use shaku::extras::HealthRegistry;
+/// use shaku::module;
+/// use shaku_rocket::{HealthResponse, Inject};
+///
+/// module! {
+///     MyModule {
+///         components = [HealthRegistry],
+///         providers = []
+///     }
+/// }
+///
+/// #[get("/health")]
+/// fn health(registry: Inject<MyModule, HealthRegistry>) -> HealthResponse {
+///     registry.check().into()
+/// }
+///
+/// # fn main() {
+/// let rocket = rocket::build()
+///     .manage(Box::new(MyModule::builder().build()))
+///     .mount("/", routes![health]);
+/// # }
+/// ```
+///
+/// [`HealthReport`]: ../shaku/extras/struct.HealthReport.html
+#[derive(Debug)]
+pub struct HealthResponse(pub HealthReport);
+
+impl From<HealthReport> for HealthResponse {
+    fn from(report: HealthReport) -> Self {
+        HealthResponse(report)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for HealthResponse {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let status = if self.0.is_healthy() {
+            Status::Ok
+        } else {
+            Status::ServiceUnavailable
+        };
+
+        (status, self.0.to_string()).respond_to(request)
+    }
+}
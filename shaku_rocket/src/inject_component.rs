@@ -5,10 +5,12 @@ use rocket::Request;
 use shaku::{HasComponent, Interface, ModuleInterface};
 use std::marker::PhantomData;
 use std::ops::Deref;
+use std::sync::Arc;
 
 /// Used to retrieve a reference to a component from a shaku `Module`.
-/// The module should be stored in Rocket's state, in a `Box` (It could be
-/// `Box<dyn MyModule>` if the module implementation changes at runtime).
+/// The module should be stored in Rocket's state, in a `Box` or `Arc` (It could be
+/// `Box<dyn MyModule>` if the module implementation changes at runtime). Use an `Arc` to share
+/// the module with other tasks.
 /// Use this `Inject` struct as a request guard.
 ///
 /// # Example
@@ -56,8 +58,8 @@ use std::ops::Deref;
 /// # }
 /// ```
 pub struct Inject<'r, M: ModuleInterface + HasComponent<I> + ?Sized, I: Interface + ?Sized>(
-    &'r I,
-    PhantomData<M>,
+    Arc<I>,
+    PhantomData<&'r M>,
 );
 
 #[rocket::async_trait]
@@ -67,20 +69,31 @@ impl<'r, M: ModuleInterface + HasComponent<I> + ?Sized, I: Interface + ?Sized> F
     type Error = String;
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let module: &'r rocket::State<Box<M>> =
-            try_outcome!(get_module_from_state::<M>(request).await);
-        let component: &'r I = module.inner().resolve_ref();
+        let module: &'r M = try_outcome!(get_module_from_state::<M, I>(request));
+        let component: Arc<I> = module.resolve();
 
         Outcome::Success(Inject(component, PhantomData))
     }
 }
 
+impl<'r, M: ModuleInterface + HasComponent<I> + ?Sized, I: Interface + ?Sized> Inject<'r, M, I> {
+    /// Take the component out of the request guard, ex. to move it into a spawned task
+    pub fn into_arc(self) -> Arc<I> {
+        self.0
+    }
+
+    /// The shared component. Clone it to keep the component after the request guard is dropped.
+    pub fn as_arc(&self) -> &Arc<I> {
+        &self.0
+    }
+}
+
 impl<'r, M: ModuleInterface + HasComponent<I> + ?Sized, I: Interface + ?Sized> Deref
     for Inject<'r, M, I>
 {
     type Target = I;
 
     fn deref(&self) -> &Self::Target {
-        self.0
+        Arc::as_ref(&self.0)
     }
 }
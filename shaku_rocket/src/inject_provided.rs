@@ -5,14 +5,21 @@ use rocket::outcome::try_outcome;
 use rocket::request::{FromRequest, Outcome};
 use rocket::{http::Status, Request};
 use shaku::{HasProvider, ModuleInterface};
+use shaku_inject_core::{provide_failed, provide_service};
 
-use crate::get_module_from_state;
+use crate::{get_module_from_state, RocketRequest};
 
 /// Used to create a provided service from a shaku `Module`.
-/// The module should be stored in Rocket's state, in a `Box` (It could be
-/// `Box<dyn MyModule>` if the module implementation changes at runtime).
+/// The module should be stored in Rocket's state, in a `Box` or `Arc` (It could be
+/// `Box<dyn MyModule>` if the module implementation changes at runtime). Use an `Arc` to share
+/// the module with other tasks.
 /// Use this `InjectProvided` struct as a request guard.
 ///
+/// With the `tracing` feature enabled, the service is provided inside the request's span, which is
+/// also available to providers via `shaku::ProvideContext`. The span is read from the request-local
+/// cache (cache a `tracing::Span` via `Request::local_cache`, ex. in a fairing), falling back to
+/// the current span.
+///
 /// # Example
 /// ```rust
 /// #[macro_use] extern crate rocket;
@@ -69,13 +76,13 @@ impl<'r, M: ModuleInterface + HasProvider<I> + ?Sized, I: ?Sized> FromRequest<'r
     type Error = String;
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let module = try_outcome!(get_module_from_state::<M>(request).await);
+        let module = try_outcome!(get_module_from_state::<M, I>(request));
 
-        let service_result = module.inner().provide();
+        let service_result = provide_service(module, &RocketRequest(request));
 
         match service_result {
             Ok(service) => Outcome::Success(InjectProvided(service, PhantomData)),
-            Err(e) => Outcome::Error((Status::InternalServerError, e.to_string())),
+            Err(e) => Outcome::Error((Status::InternalServerError, provide_failed::<M, I>(&*e))),
         }
     }
 }
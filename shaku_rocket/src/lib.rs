@@ -1,26 +1,55 @@
 //! This crate provides integration between the `shaku` and `rocket` crates.
 //!
-//! See [`Inject`] and [`InjectProvided`] for details.
+//! See [`Inject`] and [`InjectProvided`] for details. Component parameters can be read from
+//! Rocket's configuration via [`FigmentParameters`].
 //!
 //! [`Inject`]: struct.Inject.html
 //! [`InjectProvided`]: struct.InjectProvided.html
+//! [`FigmentParameters`]: trait.FigmentParameters.html
 
+mod config;
+#[cfg(feature = "health")]
+mod health;
 mod inject_component;
 mod inject_provided;
 
+pub use config::FigmentParameters;
+#[cfg(feature = "health")]
+pub use health::HealthResponse;
 pub use inject_component::Inject;
 pub use inject_provided::InjectProvided;
 
+use rocket::http::Status;
 use rocket::request::Outcome;
-use rocket::{Request, State};
+use rocket::Request;
 use shaku::ModuleInterface;
+use shaku_inject_core::{module_not_found, InjectRequest};
+use std::sync::Arc;
 
-#[allow(clippy::needless_lifetimes)] // false positive
-async fn get_module_from_state<'r, M: ModuleInterface + ?Sized>(
+/// Get the module from Rocket's state, where it was registered as either `Box<M>` or `Arc<M>`,
+/// while injecting the interface `I`
+fn get_module_from_state<'r, M: ModuleInterface + ?Sized, I: ?Sized>(
     request: &'r Request<'_>,
-) -> Outcome<&'r State<Box<M>>, String> {
-    request
-        .guard()
-        .await
-        .map_error(|f| (f.0, "Failed to retrieve module from state".to_string()))
+) -> Outcome<&'r M, String> {
+    let rocket = request.rocket();
+    let module = rocket
+        .state::<Box<M>>()
+        .map(|module| &**module)
+        .or_else(|| rocket.state::<Arc<M>>().map(|module| &**module));
+
+    match module {
+        Some(module) => Outcome::Success(module),
+        None => Outcome::Error((Status::InternalServerError, module_not_found::<M, I>())),
+    }
+}
+
+/// Exposes the rocket request to the shared extractor logic
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+struct RocketRequest<'a, 'r>(&'a Request<'r>);
+
+impl InjectRequest for RocketRequest<'_, '_> {
+    #[cfg(feature = "tracing")]
+    fn span(&self) -> Option<tracing::Span> {
+        Some(self.0.local_cache(tracing::Span::current).clone())
+    }
 }
@@ -0,0 +1,111 @@
+//! The module can be stored in state as `Arc<M>` instead of `Box<M>`, so it can be shared with
+//! other tasks.
+
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use shaku::{module, Component, HasComponent, Interface, Provider};
+use shaku_rocket::{Inject, InjectProvided};
+use std::sync::Arc;
+
+trait MyComponent: Interface {
+    fn name(&self) -> String;
+}
+trait MyProvider {
+    fn name(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = MyComponent)]
+struct MyComponentImpl;
+impl MyComponent for MyComponentImpl {
+    fn name(&self) -> String {
+        "component".to_string()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = MyProvider)]
+struct MyProviderImpl;
+impl MyProvider for MyProviderImpl {
+    fn name(&self) -> String {
+        "provider".to_string()
+    }
+}
+
+trait MyModule: HasComponent<dyn MyComponent> {}
+
+module! {
+    MyModuleImpl: MyModule {
+        components = [MyComponentImpl],
+        providers = [MyProviderImpl]
+    }
+}
+
+#[rocket::get("/component")]
+fn component_name(component: Inject<MyModuleImpl, dyn MyComponent>) -> String {
+    component.name()
+}
+
+#[rocket::get("/provider")]
+fn provider_name(provider: InjectProvided<MyModuleImpl, dyn MyProvider>) -> String {
+    provider.name()
+}
+
+#[rocket::get("/interface")]
+fn interface_component_name(component: Inject<dyn MyModule, dyn MyComponent>) -> String {
+    component.name()
+}
+
+fn client() -> Client {
+    let module = Arc::new(MyModuleImpl::builder().build());
+    let module_interface: Arc<dyn MyModule> = Arc::clone(&module) as Arc<dyn MyModule>;
+    let rocket = rocket::build()
+        .manage(module)
+        .manage(module_interface)
+        .mount(
+            "/",
+            rocket::routes![component_name, provider_name, interface_component_name],
+        );
+
+    Client::untracked(rocket).unwrap()
+}
+
+/// Components can be injected from a module stored in an `Arc`
+#[test]
+fn inject_component() {
+    let client = client();
+    let response = client.get("/component").dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "component");
+}
+
+/// Provided services can be injected from a module stored in an `Arc`
+#[test]
+fn inject_provided() {
+    let client = client();
+    let response = client.get("/provider").dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "provider");
+}
+
+/// Module trait objects can be stored in an `Arc`
+#[test]
+fn inject_from_module_interface() {
+    let client = client();
+    let response = client.get("/interface").dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "component");
+}
+
+/// A missing module fails the request
+#[test]
+fn missing_module() {
+    let rocket = rocket::build().mount("/", rocket::routes![component_name]);
+    let client = Client::untracked(rocket).unwrap();
+
+    let response = client.get("/component").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+}
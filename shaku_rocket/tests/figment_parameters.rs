@@ -0,0 +1,83 @@
+//! Component parameters can be read from Rocket's configuration
+
+use rocket::figment::Figment;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use serde::Deserialize;
+use shaku::{module, Component, Interface};
+use shaku_rocket::{FigmentParameters, Inject};
+
+trait Database: Interface {
+    fn describe(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Database, parameters_derive(Deserialize))]
+struct DatabaseImpl {
+    #[shaku(default = "sqlite::memory:".to_string())]
+    url: String,
+    #[shaku(default = 1)]
+    pool_size: u32,
+}
+
+impl Database for DatabaseImpl {
+    fn describe(&self) -> String {
+        format!("{} ({})", self.url, self.pool_size)
+    }
+}
+
+module! {
+    MyModule {
+        components = [DatabaseImpl],
+        providers = []
+    }
+}
+
+#[rocket::get("/")]
+fn describe(database: Inject<MyModule, dyn Database>) -> String {
+    database.describe()
+}
+
+fn client(figment: Figment) -> Result<Client, rocket::figment::Error> {
+    let rocket = rocket::custom(figment);
+    let module = MyModule::builder()
+        .with_figment_parameters::<DatabaseImpl>(rocket.figment(), "database")?
+        .build();
+    let rocket = rocket
+        .manage(Box::new(module))
+        .mount("/", rocket::routes![describe]);
+
+    Ok(Client::untracked(rocket).unwrap())
+}
+
+/// The parameters are read from the configuration section
+#[test]
+fn parameters_from_config() {
+    let figment = rocket::Config::figment()
+        .merge(("database.url", "postgres://localhost"))
+        .merge(("database.pool_size", 8));
+    let client = client(figment).unwrap();
+    let response = client.get("/").dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "postgres://localhost (8)");
+}
+
+/// Without a configuration section, the default parameters are used
+#[test]
+fn missing_section_uses_defaults() {
+    let client = client(rocket::Config::figment()).unwrap();
+    let response = client.get("/").dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "sqlite::memory: (1)");
+}
+
+/// Invalid configuration is reported
+#[test]
+fn invalid_parameters_error() {
+    let figment = rocket::Config::figment()
+        .merge(("database.url", "postgres://localhost"))
+        .merge(("database.pool_size", "many"));
+
+    assert!(client(figment).is_err());
+}
@@ -0,0 +1,80 @@
+//! `HealthResponse` responds with the module's health report
+
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use shaku::extras::{HealthCheck, HealthRegistry, HealthStatus};
+use shaku::{module, Component, Interface};
+use shaku_rocket::{HealthResponse, Inject};
+
+trait Database: Interface + HealthCheck {}
+
+#[derive(Component)]
+#[shaku(interface = Database, health_check)]
+struct DatabaseImpl {
+    healthy: bool,
+}
+
+impl Database for DatabaseImpl {}
+
+impl HealthCheck for DatabaseImpl {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    fn check(&self) -> HealthStatus {
+        if self.healthy {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Unhealthy("disconnected".to_string())
+        }
+    }
+}
+
+module! {
+    MyModule {
+        components = [HealthRegistry, DatabaseImpl],
+        providers = []
+    }
+}
+
+fn build_module(healthy: bool) -> MyModule {
+    MyModule::builder()
+        .with_component_parameters::<DatabaseImpl>(DatabaseImplParameters { healthy })
+        .build()
+}
+
+#[rocket::get("/health")]
+fn health(registry: Inject<MyModule, HealthRegistry>) -> HealthResponse {
+    registry.check().into()
+}
+
+fn get_health(healthy: bool) -> (Status, String) {
+    let rocket = rocket::build()
+        .manage(Box::new(build_module(healthy)))
+        .mount("/", rocket::routes![health]);
+    let client = Client::untracked(rocket).unwrap();
+    let response = client.get("/health").dispatch();
+
+    (response.status(), response.into_string().unwrap())
+}
+
+/// A healthy module responds with 200 OK
+#[test]
+fn healthy() {
+    assert_eq!(
+        get_health(true),
+        (Status::Ok, "healthy\n- database: healthy".to_string())
+    );
+}
+
+/// An unhealthy module responds with 503 Service Unavailable
+#[test]
+fn unhealthy() {
+    assert_eq!(
+        get_health(false),
+        (
+            Status::ServiceUnavailable,
+            "unhealthy\n- database: unhealthy (disconnected)".to_string()
+        )
+    );
+}
@@ -1,22 +1,39 @@
 //! Module interfaces can be used with `Inject` and `InjectProvided`.
 //! The module itself would be stored in state as `Box<dyn MyModule>`.
-#![allow(clippy::let_unit_value)]
 
+use rocket::http::Status;
+use rocket::local::blocking::Client;
 use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
 use shaku_rocket::{Inject, InjectProvided};
+use std::sync::Arc;
 
-trait MyComponent: Interface {}
-trait MyProvider {}
+trait MyComponent: Interface {
+    fn name(&self) -> String;
+}
+trait MyProvider {
+    fn name(&self) -> String;
+}
 
 #[derive(Component)]
 #[shaku(interface = MyComponent)]
 struct MyComponentImpl;
-impl MyComponent for MyComponentImpl {}
+impl MyComponent for MyComponentImpl {
+    fn name(&self) -> String {
+        "component".to_string()
+    }
+}
 
 #[derive(Provider)]
 #[shaku(interface = MyProvider)]
-struct MyProviderImpl;
-impl MyProvider for MyProviderImpl {}
+struct MyProviderImpl {
+    #[shaku(inject)]
+    component: Arc<dyn MyComponent>,
+}
+impl MyProvider for MyProviderImpl {
+    fn name(&self) -> String {
+        format!("provider using {}", self.component.name())
+    }
+}
 
 trait MyModule: HasComponent<dyn MyComponent> + HasProvider<dyn MyProvider> {}
 
@@ -35,5 +52,53 @@ fn index(
 ) {
 }
 
+#[rocket::get("/component")]
+fn component_name(component: Inject<dyn MyModule, dyn MyComponent>) -> String {
+    component.name()
+}
+
+#[rocket::get("/spawned")]
+fn spawned_component_name(component: Inject<dyn MyModule, dyn MyComponent>) -> String {
+    let component = component.into_arc();
+    std::thread::spawn(move || component.name()).join().unwrap()
+}
+
+#[rocket::get("/provider")]
+fn provider_name(provider: InjectProvided<dyn MyModule, dyn MyProvider>) -> String {
+    provider.name()
+}
+
+fn get_body(uri: &str) -> String {
+    let module: Box<dyn MyModule> = Box::new(MyModuleImpl::builder().build());
+    let rocket = rocket::build().manage(module).mount(
+        "/",
+        rocket::routes![component_name, spawned_component_name, provider_name],
+    );
+    let client = Client::untracked(rocket).unwrap();
+
+    let response = client.get(uri).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    response.into_string().unwrap()
+}
+
 #[test]
 fn compiles_ok() {}
+
+/// Components can be injected from a module trait object
+#[test]
+fn inject_component() {
+    assert_eq!(get_body("/component"), "component");
+}
+
+/// Provided services can be injected from a module trait object
+#[test]
+fn inject_provided() {
+    assert_eq!(get_body("/provider"), "provider using component");
+}
+
+/// Injected components can be moved out of the request guard as an Arc
+#[test]
+fn inject_component_arc() {
+    assert_eq!(get_body("/spawned"), "component");
+}
@@ -0,0 +1,95 @@
+//! With the `tracing` feature, `InjectProvided` provides services inside the request's span
+
+use rocket::local::asynchronous::{Client, LocalRequest};
+use rocket::request::FromRequest;
+use shaku::{module, HasProvider, Module, ProvideContext, Provider};
+use shaku_rocket::InjectProvided;
+use std::error::Error;
+use tracing::span::Id;
+use tracing::{Instrument, Span};
+
+trait SpanRecorder {
+    fn context_span(&self) -> Option<Id>;
+    fn current_span(&self) -> Option<Id>;
+}
+
+struct SpanRecorderImpl {
+    context_span: Option<Id>,
+    current_span: Option<Id>,
+}
+
+impl SpanRecorder for SpanRecorderImpl {
+    fn context_span(&self) -> Option<Id> {
+        self.context_span.clone()
+    }
+
+    fn current_span(&self) -> Option<Id> {
+        self.current_span.clone()
+    }
+}
+
+impl<M: Module> Provider<M> for SpanRecorderImpl {
+    type Interface = dyn SpanRecorder;
+
+    fn provide(_: &M) -> Result<Box<dyn SpanRecorder>, Box<dyn Error>> {
+        Ok(Box::new(SpanRecorderImpl {
+            context_span: ProvideContext::current()
+                .and_then(|context| context.get::<Span>().and_then(Span::id)),
+            current_span: Span::current().id(),
+        }))
+    }
+}
+
+trait MyModule: HasProvider<dyn SpanRecorder> {}
+
+module! {
+    MyModuleImpl: MyModule {
+        components = [],
+        providers = [SpanRecorderImpl]
+    }
+}
+
+async fn create_client() -> Client {
+    let module: Box<dyn MyModule> = Box::new(MyModuleImpl::builder().build());
+    let rocket = rocket::build().manage(module);
+
+    Client::untracked(rocket).await.unwrap()
+}
+
+async fn extract(request: &LocalRequest<'_>) -> InjectProvided<dyn MyModule, dyn SpanRecorder> {
+    InjectProvided::from_request(request.inner())
+        .await
+        .succeeded()
+        .unwrap()
+}
+
+/// The span in the request-local cache is used
+#[rocket::async_test]
+async fn span_from_local_cache() {
+    let _subscriber = tracing::subscriber::set_default(tracing_subscriber::registry());
+    let span = tracing::info_span!("request");
+    let client = create_client().await;
+    let request = client.get("/");
+    request.inner().local_cache(|| span.clone());
+
+    let recorder = extract(&request).await;
+
+    assert!(span.id().is_some());
+    assert_eq!(recorder.context_span(), span.id());
+    assert_eq!(recorder.current_span(), span.id());
+}
+
+/// The current span is used if the request-local cache doesn't contain a span
+#[rocket::async_test]
+async fn current_span_fallback() {
+    let _subscriber = tracing::subscriber::set_default(tracing_subscriber::registry());
+    let span = tracing::info_span!("outer");
+    let client = create_client().await;
+    let request = client.get("/");
+
+    let recorder = extract(&request).instrument(span.clone()).await;
+
+    assert!(span.id().is_some());
+    assert_eq!(recorder.context_span(), span.id());
+    assert_eq!(recorder.current_span(), span.id());
+}
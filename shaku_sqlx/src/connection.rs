@@ -0,0 +1,60 @@
+use crate::SqlxPool;
+use shaku::{HasComponent, Module, Provider};
+use sqlx::pool::PoolConnection;
+use sqlx::{Connection, Database, Pool, Transaction};
+use std::error::Error;
+
+/// A handle to a connection from the module's [`SqlxPool`]. Since acquiring a connection is
+/// asynchronous, the connection is acquired on first use (see [`acquire`]) and then kept until
+/// this handle is dropped, at which point it is returned to the pool.
+///
+/// [`SqlxPool`]: trait.SqlxPool.html
+/// [`acquire`]: #method.acquire
+pub struct SqlxConnection<DB: Database> {
+    pool: Pool<DB>,
+    connection: Option<PoolConnection<DB>>,
+}
+
+impl<DB: Database> SqlxConnection<DB> {
+    /// Get the connection, acquiring it from the pool if this is the first use
+    pub async fn acquire(&mut self) -> Result<&mut PoolConnection<DB>, sqlx::Error> {
+        if self.connection.is_none() {
+            self.connection = Some(self.pool.acquire().await?);
+        }
+
+        Ok(self.connection.as_mut().unwrap())
+    }
+
+    /// Begin a transaction on the connection, acquiring it from the pool if this is the first use
+    pub async fn begin(&mut self) -> Result<Transaction<'_, DB>, sqlx::Error> {
+        let connection = self.acquire().await?;
+        Connection::begin(&mut **connection).await
+    }
+
+    /// Returns true if a connection has been acquired from the pool
+    pub fn is_acquired(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    /// Get the pool which connections are acquired from
+    pub fn pool(&self) -> &Pool<DB> {
+        &self.pool
+    }
+}
+
+impl<M, DB> Provider<M> for SqlxConnection<DB>
+where
+    M: Module + HasComponent<dyn SqlxPool<DB>>,
+    DB: Database,
+{
+    type Interface = SqlxConnection<DB>;
+
+    fn provide(module: &M) -> Result<Box<Self::Interface>, Box<dyn Error>> {
+        let pool: &dyn SqlxPool<DB> = module.resolve_ref();
+
+        Ok(Box::new(SqlxConnection {
+            pool: pool.pool().clone(),
+            connection: None,
+        }))
+    }
+}
@@ -0,0 +1,47 @@
+//! This crate provides integration between the `shaku` and `sqlx` crates.
+//!
+//! Add [`SqlxPoolImpl`] as a component and [`SqlxConnection`] as a provider to your module, then
+//! use `HasProvider<SqlxConnection<DB>>` to get a connection handle wherever one is needed (for
+//! example via `InjectProvided` in a web handler, which gives each request its own connection).
+//!
+//! This crate does not enable any sqlx runtime or database features. Enable them on your own
+//! `sqlx` dependency.
+//!
+//! # Example
+//! ```rust
+//! use shaku::{module, HasProvider};
+//! use shaku_sqlx::{SqlxConnection, SqlxPoolImpl, SqlxPoolImplParameters};
+//! use sqlx::Sqlite;
+//!
+//! module! {
+//!     DatabaseModule {
+//!         components = [SqlxPoolImpl<Sqlite>],
+//!         providers = [SqlxConnection<Sqlite>]
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let module = DatabaseModule::builder()
+//!         .with_component_parameters::<SqlxPoolImpl<Sqlite>>(SqlxPoolImplParameters {
+//!             database_url: "sqlite::memory:".to_string(),
+//!             ..Default::default()
+//!         })
+//!         .build();
+//!
+//!     let mut connection: Box<SqlxConnection<Sqlite>> = module.provide().unwrap();
+//!     sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY)")
+//!         .execute(&mut **connection.acquire().await.unwrap())
+//!         .await
+//!         .unwrap();
+//! }
+//! ```
+//!
+//! [`SqlxPoolImpl`]: struct.SqlxPoolImpl.html
+//! [`SqlxConnection`]: struct.SqlxConnection.html
+
+mod connection;
+mod pool;
+
+pub use connection::SqlxConnection;
+pub use pool::{SqlxPool, SqlxPoolImpl, SqlxPoolImplParameters};
@@ -0,0 +1,80 @@
+use shaku::{Component, Interface, Module, ModuleBuildContext};
+use sqlx::pool::PoolOptions;
+use sqlx::{Database, Pool};
+use std::time::Duration;
+
+/// A pool of sqlx connections. [`SqlxConnection`] acquires connections from the module's
+/// implementation of this trait.
+///
+/// [`SqlxConnection`]: struct.SqlxConnection.html
+pub trait SqlxPool<DB: Database>: Interface {
+    /// Get the underlying sqlx pool
+    fn pool(&self) -> &Pool<DB>;
+}
+
+/// The default [`SqlxPool`] implementation. The pool is configured via
+/// [`SqlxPoolImplParameters`].
+///
+/// Connections are not opened while the module is built. They are opened when first acquired,
+/// which is also when errors such as an unreachable database are reported. The pool starts
+/// background maintenance tasks, so the module must be built inside the async runtime.
+///
+/// Building the module panics if the database URL can not be parsed. Use [`SqlxPoolImpl::new`]
+/// with `ModuleBuilder::with_component_override` to use a pool with custom settings.
+///
+/// [`SqlxPool`]: trait.SqlxPool.html
+/// [`SqlxPoolImplParameters`]: struct.SqlxPoolImplParameters.html
+/// [`SqlxPoolImpl::new`]: struct.SqlxPoolImpl.html#method.new
+pub struct SqlxPoolImpl<DB: Database> {
+    pool: Pool<DB>,
+}
+
+impl<DB: Database> SqlxPoolImpl<DB> {
+    /// Wrap an existing pool
+    pub fn new(pool: Pool<DB>) -> Self {
+        SqlxPoolImpl { pool }
+    }
+}
+
+impl<DB: Database> SqlxPool<DB> for SqlxPoolImpl<DB> {
+    fn pool(&self) -> &Pool<DB> {
+        &self.pool
+    }
+}
+
+/// Parameters for [`SqlxPoolImpl`]
+///
+/// [`SqlxPoolImpl`]: struct.SqlxPoolImpl.html
+pub struct SqlxPoolImplParameters {
+    /// The URL of the database to connect to
+    pub database_url: String,
+    /// The maximum number of connections in the pool (default: 10)
+    pub max_connections: u32,
+    /// How long to wait for a connection before giving up (default: 30 seconds)
+    pub acquire_timeout: Duration,
+}
+
+impl Default for SqlxPoolImplParameters {
+    fn default() -> Self {
+        SqlxPoolImplParameters {
+            database_url: String::new(),
+            max_connections: 10,
+            acquire_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl<M: Module, DB: Database> Component<M> for SqlxPoolImpl<DB> {
+    type Interface = dyn SqlxPool<DB>;
+    type Parameters = SqlxPoolImplParameters;
+
+    fn build(_: &mut ModuleBuildContext<M>, params: Self::Parameters) -> Box<Self::Interface> {
+        let pool = PoolOptions::<DB>::new()
+            .max_connections(params.max_connections)
+            .acquire_timeout(params.acquire_timeout)
+            .connect_lazy(&params.database_url)
+            .unwrap_or_else(|e| panic!("Invalid database URL for SqlxPoolImpl: {}", e));
+
+        Box::new(SqlxPoolImpl { pool })
+    }
+}
@@ -0,0 +1,82 @@
+//! Sqlx connections can be provided from a pool component
+
+use shaku::{module, HasComponent, HasProvider};
+use shaku_sqlx::{SqlxConnection, SqlxPool, SqlxPoolImpl, SqlxPoolImplParameters};
+use sqlx::{Row, Sqlite};
+use std::time::Duration;
+
+module! {
+    DatabaseModule {
+        components = [SqlxPoolImpl<Sqlite>],
+        providers = [SqlxConnection<Sqlite>]
+    }
+}
+
+fn create_module(max_connections: u32) -> DatabaseModule {
+    DatabaseModule::builder()
+        .with_component_parameters::<SqlxPoolImpl<Sqlite>>(SqlxPoolImplParameters {
+            database_url: "sqlite::memory:".to_string(),
+            max_connections,
+            acquire_timeout: Duration::from_millis(100),
+        })
+        .build()
+}
+
+/// A provided connection can run queries
+#[tokio::test]
+async fn provided_connection_runs_queries() {
+    let module = create_module(1);
+    let mut connection: Box<SqlxConnection<Sqlite>> = module.provide().unwrap();
+
+    let row = sqlx::query("SELECT 1 + 1")
+        .fetch_one(&mut **connection.acquire().await.unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(row.get::<i32, _>(0), 2);
+}
+
+/// The connection is only acquired on first use
+#[tokio::test]
+async fn connection_acquired_lazily() {
+    let module = create_module(1);
+    let mut connection: Box<SqlxConnection<Sqlite>> = module.provide().unwrap();
+    assert!(!connection.is_acquired());
+
+    connection.acquire().await.unwrap();
+    assert!(connection.is_acquired());
+}
+
+/// Connections are returned to the pool when the handle is dropped
+#[tokio::test]
+async fn connections_are_pooled() {
+    let module = create_module(1);
+
+    {
+        let mut connection: Box<SqlxConnection<Sqlite>> = module.provide().unwrap();
+        connection.acquire().await.unwrap();
+
+        let mut other: Box<SqlxConnection<Sqlite>> = module.provide().unwrap();
+        assert!(other.acquire().await.is_err());
+    }
+
+    let mut connection: Box<SqlxConnection<Sqlite>> = module.provide().unwrap();
+    assert!(connection.acquire().await.is_ok());
+
+    let pool: &dyn SqlxPool<Sqlite> = module.resolve_ref();
+    assert_eq!(pool.pool().size(), 1);
+}
+
+/// Transactions can be started on a provided connection
+#[tokio::test]
+async fn provided_connection_transaction() {
+    let module = create_module(1);
+    let mut connection: Box<SqlxConnection<Sqlite>> = module.provide().unwrap();
+
+    let mut transaction = connection.begin().await.unwrap();
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY)")
+        .execute(&mut *transaction)
+        .await
+        .unwrap();
+    transaction.rollback().await.unwrap();
+}
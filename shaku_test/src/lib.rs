@@ -0,0 +1,15 @@
+//! This crate provides a test harness and test helpers for shaku modules.
+//!
+//! - [`case`]: Turn a function into a test which builds a module and injects the test function's
+//!   parameters from it.
+//! - [`MemoizedProvider`]: Make a provider return the same instance on every call, via
+//!   [`MemoizedProviderExt::with_memoized_provider`].
+//!
+//! [`case`]: attr.case.html
+//! [`MemoizedProvider`]: struct.MemoizedProvider.html
+//! [`MemoizedProviderExt::with_memoized_provider`]: trait.MemoizedProviderExt.html#tymethod.with_memoized_provider
+
+mod memoized_provider;
+
+pub use crate::memoized_provider::*;
+pub use shaku_test_macros::case;
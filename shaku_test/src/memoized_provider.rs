@@ -0,0 +1,197 @@
+//! Providing the same service instance every time, for asserting on it in tests
+
+use shaku::{HasProvider, Module, ModuleBuilder, Provider, ThreadSafe};
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::sync::{Arc, Mutex};
+
+/// Makes a provider return the same instance of its service on every call, via
+/// [`MemoizedProviderExt::with_memoized_provider`]. The first call creates the instance using the
+/// provider, and every call (including the first) returns a new `Box` around a shared handle to
+/// it, created by the `share` function (ex. a wrapper struct holding an `Arc<dyn Interface>`).
+///
+/// This is meant for tests, which can then assert on the interactions with a provided service
+/// (ex. a per-request repository) through the [`instance`], without changing the code under test.
+///
+/// # Example
+/// ```
+/// use shaku::{module, HasProvider, Module, Provider};
+/// use shaku_test::{MemoizedProvider, MemoizedProviderExt};
+/// use std::error::Error;
+/// use std::sync::{Arc, Mutex};
+///
+/// trait Repository: Send + Sync {
+///     fn save(&self, value: &str);
+///     fn saved(&self) -> Vec<String>;
+/// }
+///
+/// #[derive(Default)]
+/// struct InMemoryRepository {
+///     values: Mutex<Vec<String>>,
+/// }
+///
+/// impl<M: Module> Provider<M> for InMemoryRepository {
+///     type Interface = dyn Repository;
+///
+///     fn provide(_: &M) -> Result<Box<dyn Repository>, Box<dyn Error>> {
+///         Ok(Box::<InMemoryRepository>::default())
+///     }
+/// }
+///
+/// impl Repository for InMemoryRepository {
+///     fn save(&self, value: &str) {
+///         self.values.lock().unwrap().push(value.to_string());
+///     }
+///
+///     fn saved(&self) -> Vec<String> {
+///         self.values.lock().unwrap().clone()
+///     }
+/// }
+///
+/// /// Forwards to the memoized repository
+/// struct SharedRepository(Arc<dyn Repository>);
+///
+/// impl Repository for SharedRepository {
+///     fn save(&self, value: &str) {
+///         self.0.save(value)
+///     }
+///
+///     fn saved(&self) -> Vec<String> {
+///         self.0.saved()
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [],
+///         providers = [InMemoryRepository]
+///     }
+/// }
+///
+/// # fn main() {
+/// let repository = MemoizedProvider::<dyn Repository>::new(|repository| {
+///     Box::new(SharedRepository(repository))
+/// });
+/// let module = MyModule::builder()
+///     .with_memoized_provider::<InMemoryRepository>(&repository)
+///     .build();
+///
+/// // Each request gets its own repository in the code under test
+/// let first: Box<dyn Repository> = module.provide().unwrap();
+/// first.save("first");
+/// let second: Box<dyn Repository> = module.provide().unwrap();
+/// second.save("second");
+///
+/// assert_eq!(repository.instance().unwrap().saved(), vec!["first", "second"]);
+/// # }
+/// ```
+///
+/// [`MemoizedProviderExt::with_memoized_provider`]: trait.MemoizedProviderExt.html#tymethod.with_memoized_provider
+/// [`instance`]: #method.instance
+pub struct MemoizedProvider<I: ?Sized> {
+    instance: Arc<Mutex<Option<Arc<I>>>>,
+    share: fn(Arc<I>) -> Box<I>,
+}
+
+impl<I: ThreadSafe + ?Sized> MemoizedProvider<I> {
+    /// Create a memoized provider which wraps the shared instance via `share`. The instance is
+    /// created by the first call to the provider.
+    pub fn new(share: fn(Arc<I>) -> Box<I>) -> Self {
+        MemoizedProvider {
+            instance: Arc::new(Mutex::new(None)),
+            share,
+        }
+    }
+
+    /// Create a memoized provider which always provides the given instance, without calling the
+    /// provider
+    pub fn with_instance(instance: Arc<I>, share: fn(Arc<I>) -> Box<I>) -> Self {
+        MemoizedProvider {
+            instance: Arc::new(Mutex::new(Some(instance))),
+            share,
+        }
+    }
+
+    /// The memoized instance, if it was created
+    pub fn instance(&self) -> Option<Arc<I>> {
+        self.instance.lock().unwrap().clone()
+    }
+
+    /// Forget the memoized instance, so the next call to the provider creates a new one
+    pub fn reset(&self) {
+        *self.instance.lock().unwrap() = None;
+    }
+
+    /// Share the memoized instance, or create it via `provide` if there is none yet
+    pub fn get_or_provide(
+        &self,
+        provide: impl FnOnce() -> Result<Box<I>, Box<dyn Error>>,
+    ) -> Result<Box<I>, Box<dyn Error>> {
+        let memoized = self.instance.lock().unwrap().clone();
+        let instance = match memoized {
+            Some(instance) => instance,
+            None => {
+                // The lock is not held while providing, since the provider may provide other
+                // memoized services. If the instance was created concurrently, the first one wins.
+                let created = Arc::from(provide()?);
+                Arc::clone(self.instance.lock().unwrap().get_or_insert(created))
+            }
+        };
+
+        Ok((self.share)(instance))
+    }
+}
+
+// The Clone derive would require I to be Clone
+impl<I: ?Sized> Clone for MemoizedProvider<I> {
+    fn clone(&self) -> Self {
+        MemoizedProvider {
+            instance: Arc::clone(&self.instance),
+            share: self.share,
+        }
+    }
+}
+
+impl<I: ?Sized> Debug for MemoizedProvider<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoizedProvider")
+            .field("memoized", &self.instance.lock().unwrap().is_some())
+            .finish()
+    }
+}
+
+/// Adds [`with_memoized_provider`] to the module builder
+///
+/// [`with_memoized_provider`]: #tymethod.with_memoized_provider
+pub trait MemoizedProviderExt<M: Module> {
+    /// Make the provider `P` return the same instance of its service on every call, shared via
+    /// the [`MemoizedProvider`]. The instance is created by `P` on the first call (unless the
+    /// memoized provider already has one). Like `with_provider_override`, this replaces any other
+    /// override of the provider.
+    ///
+    /// [`MemoizedProvider`]: struct.MemoizedProvider.html
+    fn with_memoized_provider<P: Provider<M>>(
+        self,
+        memoized: &MemoizedProvider<P::Interface>,
+    ) -> Self
+    where
+        M: HasProvider<P::Interface>,
+        P::Interface: ThreadSafe;
+}
+
+impl<M: Module> MemoizedProviderExt<M> for ModuleBuilder<M> {
+    fn with_memoized_provider<P: Provider<M>>(
+        self,
+        memoized: &MemoizedProvider<P::Interface>,
+    ) -> Self
+    where
+        M: HasProvider<P::Interface>,
+        P::Interface: ThreadSafe,
+    {
+        let memoized = memoized.clone();
+
+        self.with_provider_override::<P::Interface>(Box::new(move |module| {
+            memoized.get_or_provide(|| P::provide(module))
+        }))
+    }
+}
@@ -0,0 +1,137 @@
+//! Test cases can inject services from a module
+
+use shaku::{module, Component, HasComponent, Interface, Provider};
+use std::error::Error;
+use std::sync::Arc;
+
+trait Greeter: Interface {
+    fn greet(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct GreeterImpl {
+    #[shaku(default = "world".to_string())]
+    name: String,
+}
+
+impl Greeter for GreeterImpl {
+    fn greet(&self) -> String {
+        format!("Hello, {}!", self.name)
+    }
+}
+
+trait Session {
+    fn greeting(&self) -> String;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Session)]
+struct SessionImpl {
+    #[shaku(inject)]
+    greeter: Arc<dyn Greeter>,
+}
+
+impl Session for SessionImpl {
+    fn greeting(&self) -> String {
+        self.greeter.greet()
+    }
+}
+
+module! {
+    TestModule {
+        components = [GreeterImpl],
+        providers = [SessionImpl]
+    }
+}
+
+module! {
+    ParentModule {
+        components = [],
+        providers = [],
+
+        use TestModule {
+            components = [Greeter],
+            providers = []
+        }
+    }
+}
+
+/// Components are injected as Arc or references, providers as Box
+#[shaku_test::case(module = TestModule)]
+fn inject_services(
+    greeter: Arc<dyn Greeter>,
+    greeter_ref: &dyn Greeter,
+    session: Box<dyn Session>,
+) {
+    assert_eq!(greeter.greet(), "Hello, world!");
+    assert_eq!(greeter_ref.greet(), "Hello, world!");
+    assert_eq!(session.greeting(), "Hello, world!");
+}
+
+/// The module itself can be injected
+#[shaku_test::case(module = TestModule)]
+fn inject_module(module: &TestModule) {
+    let greeter: &dyn Greeter = module.resolve_ref();
+    assert_eq!(greeter.greet(), "Hello, world!");
+}
+
+/// Parameters can be set per test
+#[shaku_test::case(
+    module = TestModule,
+    parameters(GreeterImpl = GreeterImplParameters { name: "shaku".to_string() })
+)]
+fn with_parameters(session: Box<dyn Session>) {
+    assert_eq!(session.greeting(), "Hello, shaku!");
+}
+
+struct FakeGreeter;
+impl Greeter for FakeGreeter {
+    fn greet(&self) -> String {
+        "Hi".to_string()
+    }
+}
+
+struct FakeSession;
+impl Session for FakeSession {
+    fn greeting(&self) -> String {
+        "Fake".to_string()
+    }
+}
+
+/// Components and providers can be overridden per test
+#[shaku_test::case(
+    module = TestModule,
+    component_override(dyn Greeter = Box::new(FakeGreeter)),
+    provider_override(dyn Session = Box::new(|_| Ok(Box::new(FakeSession))))
+)]
+fn with_overrides(greeter: &dyn Greeter, session: Box<dyn Session>) {
+    assert_eq!(greeter.greet(), "Hi");
+    assert_eq!(session.greeting(), "Fake");
+}
+
+/// Modules with submodules use a custom builder
+#[shaku_test::case(
+    module = ParentModule,
+    builder = ParentModule::builder(Arc::new(TestModule::builder().build()))
+)]
+fn with_builder(greeter: Arc<dyn Greeter>) {
+    assert_eq!(greeter.greet(), "Hello, world!");
+}
+
+/// Tests can return a result
+#[shaku_test::case(module = TestModule)]
+fn returns_result(session: Box<dyn Session>) -> Result<(), Box<dyn Error>> {
+    if session.greeting() != "Hello, world!" {
+        return Err("Unexpected greeting".into());
+    }
+
+    Ok(())
+}
+
+/// Other test attributes are kept
+#[shaku_test::case(module = TestModule)]
+#[should_panic(expected = "boom")]
+fn keeps_attributes(_greeter: &dyn Greeter) {
+    panic!("boom");
+}
@@ -0,0 +1,135 @@
+//! Memoized providers return the same instance on every call
+
+use shaku::{module, HasProvider, Module, Provider};
+use shaku_test::{MemoizedProvider, MemoizedProviderExt};
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+trait Repository: Send + Sync {
+    fn save(&self, value: &str);
+    fn saved(&self) -> Vec<String>;
+}
+
+static CREATED: AtomicUsize = AtomicUsize::new(0);
+static UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+#[derive(Default)]
+struct RepositoryImpl {
+    values: Mutex<Vec<String>>,
+}
+
+impl Repository for RepositoryImpl {
+    fn save(&self, value: &str) {
+        self.values.lock().unwrap().push(value.to_string());
+    }
+
+    fn saved(&self) -> Vec<String> {
+        self.values.lock().unwrap().clone()
+    }
+}
+
+impl<M: Module> Provider<M> for RepositoryImpl {
+    type Interface = dyn Repository;
+
+    fn provide(_: &M) -> Result<Box<dyn Repository>, Box<dyn Error>> {
+        if UNAVAILABLE.load(Ordering::SeqCst) {
+            return Err("the database is down".into());
+        }
+
+        CREATED.fetch_add(1, Ordering::SeqCst);
+        Ok(Box::<RepositoryImpl>::default())
+    }
+}
+
+struct SharedRepository(Arc<dyn Repository>);
+
+impl Repository for SharedRepository {
+    fn save(&self, value: &str) {
+        self.0.save(value)
+    }
+
+    fn saved(&self) -> Vec<String> {
+        self.0.saved()
+    }
+}
+
+trait Service {
+    fn handle(&self, request: &str);
+}
+
+#[derive(Provider)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(provide)]
+    repository: Box<dyn Repository>,
+}
+
+impl Service for ServiceImpl {
+    fn handle(&self, request: &str) {
+        self.repository.save(request);
+    }
+}
+
+module! {
+    TestModule {
+        components = [],
+        providers = [RepositoryImpl, ServiceImpl]
+    }
+}
+
+fn memoized_repository() -> MemoizedProvider<dyn Repository> {
+    MemoizedProvider::new(|repository| Box::new(SharedRepository(repository)))
+}
+
+// The tests share the provider's statics, so they run in one test
+/// The provider creates the instance once, and services which depend on it share it
+#[test]
+fn memoized_provider() {
+    let repository = memoized_repository();
+    let module = TestModule::builder()
+        .with_memoized_provider::<RepositoryImpl>(&repository)
+        .build();
+    assert!(repository.instance().is_none());
+
+    let service: Box<dyn Service> = module.provide().unwrap();
+    service.handle("first");
+    let service: Box<dyn Service> = module.provide().unwrap();
+    service.handle("second");
+    let direct: Box<dyn Repository> = module.provide().unwrap();
+    direct.save("third");
+
+    assert_eq!(CREATED.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        repository.instance().unwrap().saved(),
+        vec!["first", "second", "third"]
+    );
+
+    // After a reset, the next call creates a new instance
+    repository.reset();
+    let service: Box<dyn Service> = module.provide().unwrap();
+    service.handle("fourth");
+    assert_eq!(CREATED.load(Ordering::SeqCst), 2);
+    assert_eq!(repository.instance().unwrap().saved(), vec!["fourth"]);
+
+    // Provider errors are returned, and nothing is memoized
+    repository.reset();
+    UNAVAILABLE.store(true, Ordering::SeqCst);
+    assert!(HasProvider::<dyn Repository>::provide(&module).is_err());
+    assert!(repository.instance().is_none());
+    UNAVAILABLE.store(false, Ordering::SeqCst);
+
+    // An existing instance is provided without calling the provider
+    let instance: Arc<dyn Repository> = Arc::new(RepositoryImpl::default());
+    let repository = MemoizedProvider::with_instance(Arc::clone(&instance), |repository| {
+        Box::new(SharedRepository(repository))
+    });
+    let module = TestModule::builder()
+        .with_memoized_provider::<RepositoryImpl>(&repository)
+        .build();
+
+    let service: Box<dyn Service> = module.provide().unwrap();
+    service.handle("existing");
+    assert_eq!(CREATED.load(Ordering::SeqCst), 2);
+    assert_eq!(instance.saved(), vec!["existing"]);
+}
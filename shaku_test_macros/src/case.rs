@@ -0,0 +1,253 @@
+//! Implementation of the `#[case]` attribute macro
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Error, Expr, FnArg, Ident, ItemFn, Pat, Type};
+
+/// The arguments given to the attribute
+pub struct CaseArgs {
+    module: Type,
+    builder: Option<Expr>,
+    parameters: Vec<TypeAssignment>,
+    component_overrides: Vec<TypeAssignment>,
+    provider_overrides: Vec<TypeAssignment>,
+}
+
+/// A single argument, such as `module = MyModule` or `parameters(Foo = ...)`
+enum CaseArg {
+    Module(Type),
+    Builder(Expr),
+    Parameters(TypeAssignment),
+    ComponentOverride(TypeAssignment),
+    ProviderOverride(TypeAssignment),
+}
+
+/// `Type = expr`, used to configure the module builder
+struct TypeAssignment {
+    ty: Type,
+    value: Expr,
+}
+
+impl Parse for CaseArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let args = Punctuated::<CaseArg, syn::Token![,]>::parse_terminated(input)?;
+        let mut module = None;
+        let mut builder = None;
+        let mut parameters = Vec::new();
+        let mut component_overrides = Vec::new();
+        let mut provider_overrides = Vec::new();
+
+        for arg in args {
+            match arg {
+                CaseArg::Module(ty) => {
+                    if module.is_some() {
+                        return Err(Error::new(ty.span(), "The module can only be set once"));
+                    }
+                    module = Some(ty);
+                }
+                CaseArg::Builder(expr) => {
+                    if builder.is_some() {
+                        return Err(Error::new(expr.span(), "The builder can only be set once"));
+                    }
+                    builder = Some(expr);
+                }
+                CaseArg::Parameters(assignment) => parameters.push(assignment),
+                CaseArg::ComponentOverride(assignment) => component_overrides.push(assignment),
+                CaseArg::ProviderOverride(assignment) => provider_overrides.push(assignment),
+            }
+        }
+
+        let module = module.ok_or_else(|| {
+            Error::new(
+                input.span(),
+                "Missing module. Example: #[shaku_test::case(module = MyModule)]",
+            )
+        })?;
+
+        Ok(CaseArgs {
+            module,
+            builder,
+            parameters,
+            component_overrides,
+            provider_overrides,
+        })
+    }
+}
+
+impl Parse for CaseArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+
+        if key == "module" || key == "builder" {
+            input.parse::<syn::Token![=]>()?;
+
+            return if key == "module" {
+                Ok(CaseArg::Module(input.parse()?))
+            } else {
+                Ok(CaseArg::Builder(input.parse()?))
+            };
+        }
+
+        let content;
+        syn::parenthesized!(content in input);
+        let assignment = content.parse()?;
+
+        if key == "parameters" {
+            Ok(CaseArg::Parameters(assignment))
+        } else if key == "component_override" {
+            Ok(CaseArg::ComponentOverride(assignment))
+        } else if key == "provider_override" {
+            Ok(CaseArg::ProviderOverride(assignment))
+        } else {
+            Err(Error::new(
+                key.span(),
+                format!("Unknown shaku_test argument: '{}'", key),
+            ))
+        }
+    }
+}
+
+impl Parse for TypeAssignment {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse()?;
+        input.parse::<syn::Token![=]>()?;
+        let value = input.parse()?;
+
+        Ok(TypeAssignment { ty, value })
+    }
+}
+
+pub fn expand_case(args: CaseArgs, function: ItemFn) -> syn::Result<TokenStream> {
+    let module_ty = &args.module;
+    let builder = match &args.builder {
+        Some(builder) => quote! { #builder },
+        None => quote! { <#module_ty>::builder() },
+    };
+
+    let parameters = args.parameters.iter().map(|TypeAssignment { ty, value }| {
+        quote! { .with_component_parameters::<#ty>(#value) }
+    });
+    let component_overrides =
+        args.component_overrides
+            .iter()
+            .map(|TypeAssignment { ty, value }| {
+                quote! { .with_component_override::<#ty>(#value) }
+            });
+    let provider_overrides = args
+        .provider_overrides
+        .iter()
+        .map(|TypeAssignment { ty, value }| {
+            quote! { .with_provider_override::<#ty>(#value) }
+        });
+
+    let injections = function
+        .sig
+        .inputs
+        .iter()
+        .map(|input| create_injection(input, module_ty))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let attrs = &function.attrs;
+    let vis = &function.vis;
+    let name = &function.sig.ident;
+    let output = &function.sig.output;
+    let body = &function.block;
+
+    Ok(quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #name() #output {
+            let __shaku_module: #module_ty = #builder
+                #(#parameters)*
+                #(#component_overrides)*
+                #(#provider_overrides)*
+                .build();
+
+            #(#injections)*
+
+            #body
+        }
+    })
+}
+
+/// Create the statement which injects a single test function parameter
+fn create_injection(input: &FnArg, module_ty: &Type) -> syn::Result<TokenStream> {
+    let arg = match input {
+        FnArg::Typed(arg) => arg,
+        FnArg::Receiver(receiver) => {
+            return Err(Error::new(
+                receiver.span(),
+                "Test cases cannot take a self parameter",
+            ))
+        }
+    };
+    let pat = match &*arg.pat {
+        Pat::Ident(pat) => pat,
+        pat => return Err(Error::new(pat.span(), "Expected a parameter name")),
+    };
+    let ty = &arg.ty;
+
+    let injection = match &**ty {
+        Type::Reference(reference) if is_same_type(&reference.elem, module_ty) => {
+            quote! { &__shaku_module }
+        }
+        Type::Reference(reference) => {
+            let interface = &reference.elem;
+            quote! { ::shaku::HasComponent::<#interface>::resolve_ref(&__shaku_module) }
+        }
+        Type::Path(path) => {
+            let wrapper = path.path.segments.last().map(|segment| &segment.ident);
+            let interface = wrapper_generic(ty).ok_or_else(|| unsupported_type(ty))?;
+
+            match wrapper {
+                Some(wrapper) if wrapper == "Arc" => quote! {
+                    ::shaku::HasComponent::<#interface>::resolve(&__shaku_module)
+                },
+                Some(wrapper) if wrapper == "Box" => {
+                    let message = format!("Failed to provide `{}`", pat.ident);
+                    quote! {
+                        ::shaku::HasProvider::<#interface>::provide(&__shaku_module)
+                            .expect(#message)
+                    }
+                }
+                _ => return Err(unsupported_type(ty)),
+            }
+        }
+        _ => return Err(unsupported_type(ty)),
+    };
+
+    Ok(quote! {
+        let #pat: #ty = #injection;
+    })
+}
+
+/// Get the type inside of an `Arc` or `Box`
+fn wrapper_generic(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(path) => path,
+        _ => return None,
+    };
+
+    match &path.path.segments.last()?.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_same_type(left: &Type, right: &Type) -> bool {
+    quote!(#left).to_string() == quote!(#right).to_string()
+}
+
+fn unsupported_type(ty: &Type) -> Error {
+    Error::new(
+        ty.span(),
+        "Unsupported parameter type. Expected Arc<dyn Trait>, &dyn Trait, Box<dyn Trait>, \
+         or a reference to the module",
+    )
+}
@@ -0,0 +1,78 @@
+//! This crate provides the test harness macros of `shaku_test`. Use them via the `shaku_test`
+//! crate, which re-exports them.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+mod case;
+
+/// Turn a function into a test which builds a shaku module and injects the
+/// test function's parameters from it.
+///
+/// Parameters are resolved by type:
+/// - `Arc<dyn Interface>` resolves a component
+/// - `&dyn Interface` resolves a reference to a component
+/// - `Box<dyn Interface>` creates a service via a provider (the test panics if
+///   the provider fails)
+/// - `&MyModule` (where `MyModule` is the module type) gives access to the
+///   module itself
+///
+/// The module is dropped after the test body finishes.
+///
+/// ## Attribute arguments
+/// - `module = MyModule` (required): The module to build.
+/// - `builder = <expr>`: Use a custom expression to create the module builder,
+///   for example if the module has submodules. Defaults to `MyModule::builder()`.
+/// - `parameters(MyComponent = <expr>)`: Set the parameters of a component.
+/// - `component_override(dyn Interface = <expr>)`: Override a component with
+///   a `Box<dyn Interface>`.
+/// - `provider_override(dyn Interface = <expr>)`: Override a provider with a
+///   `ProviderFn`.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, Interface};
+/// use std::sync::Arc;
+///
+/// trait Greeter: Interface {
+///     fn greet(&self) -> String;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Greeter)]
+/// struct GreeterImpl {
+///     name: String,
+/// }
+///
+/// impl Greeter for GreeterImpl {
+///     fn greet(&self) -> String {
+///         format!("Hello, {}!", self.name)
+///     }
+/// }
+///
+/// module! {
+///     TestModule {
+///         components = [GreeterImpl],
+///         providers = []
+///     }
+/// }
+///
+/// #[shaku_test::case(
+///     module = TestModule,
+///     parameters(GreeterImpl = GreeterImplParameters { name: "world".to_string() })
+/// )]
+/// fn greets_the_world(greeter: Arc<dyn Greeter>) {
+///     assert_eq!(greeter.greet(), "Hello, world!");
+/// }
+/// # fn main() {}
+/// ```
+#[proc_macro_attribute]
+pub fn case(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(args as case::CaseArgs);
+    let function = syn::parse_macro_input!(input as syn::ItemFn);
+
+    case::expand_case(args, function)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
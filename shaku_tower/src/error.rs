@@ -0,0 +1,41 @@
+use std::error::Error;
+use std::fmt;
+
+/// Returned by [`MakeProvidedService`] when the provider fails to create the service.
+///
+/// Provider errors are not `Send` or `Sync`, which tower servers require, so the error only keeps
+/// the provider error's message.
+///
+/// [`MakeProvidedService`]: struct.MakeProvidedService.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProvideError {
+    interface: &'static str,
+    message: String,
+}
+
+impl ProvideError {
+    pub(crate) fn new(interface: &'static str, error: Box<dyn Error>) -> Self {
+        ProvideError {
+            interface,
+            message: error.to_string(),
+        }
+    }
+
+    /// The type name of the provided service (ex. `dyn my_crate::Api`)
+    pub fn interface(&self) -> &'static str {
+        self.interface
+    }
+
+    /// The message of the provider's error
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ProvideError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to provide {}: {}", self.interface, self.message)
+    }
+}
+
+impl Error for ProvideError {}
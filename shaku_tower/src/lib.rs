@@ -0,0 +1,13 @@
+//! This crate provides integration between the `shaku` and `tower` crates.
+//!
+//! A [`MakeProvidedService`] is a tower `MakeService` which creates a new service for each
+//! connection (or other target) via the module's provider, so servers built on tower (ex. hyper)
+//! can get their services straight from a shaku module.
+//!
+//! [`MakeProvidedService`]: struct.MakeProvidedService.html
+
+mod error;
+mod make_service;
+
+pub use error::ProvideError;
+pub use make_service::MakeProvidedService;
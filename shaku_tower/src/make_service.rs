@@ -0,0 +1,136 @@
+use crate::ProvideError;
+use shaku::HasProvider;
+use std::any::type_name;
+use std::fmt::{self, Debug};
+use std::future::{ready, Ready};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// A tower `MakeService` which creates the service `S` via the module's provider. Each call (ex.
+/// for each connection accepted by a server) provides a new instance of the service, which is a
+/// tower `Service` itself if `S` is (`Box<S>` forwards to `S`).
+///
+/// The target (ex. the connection) is not passed to the provider. Providers which need
+/// per-connection data can get it from a `shaku::ProvideContext` instead.
+///
+/// Servers usually require the made services to be `Send`, so the provided service must also be
+/// `Send` (ex. `trait Api: Service<Request> + Send`).
+///
+/// # Example
+/// ```rust
+/// use shaku::{module, Module, Provider};
+/// use shaku_tower::MakeProvidedService;
+/// use std::convert::Infallible;
+/// use std::error::Error;
+/// use std::future::{ready, Ready};
+/// use std::sync::Arc;
+/// use std::task::{Context, Poll};
+/// use tower::{Service, ServiceExt};
+///
+/// /// Greets each request of a connection
+/// struct Greeter {
+///     greeting: String,
+/// }
+///
+/// impl Service<&'static str> for Greeter {
+///     type Response = String;
+///     type Error = Infallible;
+///     type Future = Ready<Result<String, Infallible>>;
+///
+///     fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+///         Poll::Ready(Ok(()))
+///     }
+///
+///     fn call(&mut self, name: &'static str) -> Self::Future {
+///         ready(Ok(format!("{}, {}!", self.greeting, name)))
+///     }
+/// }
+///
+/// impl<M: Module> Provider<M> for Greeter {
+///     type Interface = Greeter;
+///
+///     fn provide(_: &M) -> Result<Box<Greeter>, Box<dyn Error>> {
+///         Ok(Box::new(Greeter {
+///             greeting: "Hello".to_string(),
+///         }))
+///     }
+/// }
+///
+/// module! {
+///     GreeterModule {
+///         components = [],
+///         providers = [Greeter]
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let module = Arc::new(GreeterModule::builder().build());
+///     let make_service = MakeProvidedService::<_, Greeter>::new(module);
+///
+///     // A server would make a service for each connection
+///     let greeter = make_service.oneshot(()).await.unwrap();
+///     assert_eq!(greeter.oneshot("world").await.unwrap(), "Hello, world!");
+/// }
+/// ```
+pub struct MakeProvidedService<M: ?Sized, S: ?Sized> {
+    module: Arc<M>,
+    _service: PhantomData<fn() -> Box<S>>,
+}
+
+impl<M: HasProvider<S> + ?Sized, S: ?Sized> MakeProvidedService<M, S> {
+    /// Create a `MakeService` which provides the service `S` via the module
+    pub fn new(module: Arc<M>) -> Self {
+        MakeProvidedService {
+            module,
+            _service: PhantomData,
+        }
+    }
+
+    /// The module which provides the services
+    pub fn module(&self) -> &Arc<M> {
+        &self.module
+    }
+}
+
+impl<M, S, Target> Service<Target> for MakeProvidedService<M, S>
+where
+    M: HasProvider<S> + ?Sized,
+    S: ?Sized,
+{
+    type Response = Box<S>;
+    type Error = ProvideError;
+    type Future = Ready<Result<Box<S>, ProvideError>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), ProvideError>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _: Target) -> Self::Future {
+        ready(
+            self.module
+                .provide()
+                .map_err(|error| ProvideError::new(type_name::<S>(), error)),
+        )
+    }
+}
+
+// The Clone derive would require M and S to be Clone
+impl<M: ?Sized, S: ?Sized> Clone for MakeProvidedService<M, S> {
+    fn clone(&self) -> Self {
+        MakeProvidedService {
+            module: Arc::clone(&self.module),
+            _service: PhantomData,
+        }
+    }
+}
+
+impl<M: ?Sized, S: ?Sized> Debug for MakeProvidedService<M, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MakeProvidedService")
+            .field("service", &type_name::<S>())
+            .finish()
+    }
+}
@@ -0,0 +1,150 @@
+//! Modules can make tower services for each connection
+
+use shaku::{module, Component, HasComponent, Interface, Module, Provider};
+use shaku_tower::MakeProvidedService;
+use std::convert::Infallible;
+use std::error::Error;
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use tower_service::Service;
+
+trait ConnectionCounter: Interface {
+    fn next(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = ConnectionCounter)]
+struct ConnectionCounterImpl {
+    #[shaku(default)]
+    connections: AtomicUsize,
+}
+
+impl ConnectionCounter for ConnectionCounterImpl {
+    fn next(&self) -> usize {
+        self.connections.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+/// Numbers the requests of a connection
+struct ConnectionService {
+    connection: usize,
+    requests: usize,
+}
+
+impl Service<&'static str> for ConnectionService {
+    type Response = String;
+    type Error = Infallible;
+    type Future = Ready<Result<String, Infallible>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: &'static str) -> Self::Future {
+        self.requests += 1;
+        ready(Ok(format!(
+            "{} #{}.{}",
+            request, self.connection, self.requests
+        )))
+    }
+}
+
+impl<M: Module + HasComponent<dyn ConnectionCounter>> Provider<M> for ConnectionService {
+    type Interface = ConnectionService;
+
+    fn provide(module: &M) -> Result<Box<ConnectionService>, Box<dyn Error>> {
+        let counter: &dyn ConnectionCounter = module.resolve_ref();
+
+        Ok(Box::new(ConnectionService {
+            connection: counter.next(),
+            requests: 0,
+        }))
+    }
+}
+
+trait Unavailable {}
+
+struct UnavailableImpl;
+
+impl<M: Module> Provider<M> for UnavailableImpl {
+    type Interface = dyn Unavailable;
+
+    fn provide(_: &M) -> Result<Box<dyn Unavailable>, Box<dyn Error>> {
+        Err("the database is down".into())
+    }
+}
+
+module! {
+    TestModule {
+        components = [ConnectionCounterImpl],
+        providers = [ConnectionService, UnavailableImpl]
+    }
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// Poll a ready service and call it. The services in these tests never wait.
+fn call<S, R>(service: &mut S, request: R) -> Result<S::Response, S::Error>
+where
+    S: Service<R>,
+    S::Future: Unpin,
+{
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut context = Context::from_waker(&waker);
+
+    match service.poll_ready(&mut context) {
+        Poll::Ready(result) => result?,
+        Poll::Pending => panic!("The service is not ready"),
+    }
+
+    let mut future = service.call(request);
+    match Pin::new(&mut future).poll(&mut context) {
+        Poll::Ready(result) => result,
+        Poll::Pending => panic!("The service did not respond immediately"),
+    }
+}
+
+/// Each target gets a new service from the provider
+#[test]
+fn makes_service_per_target() {
+    let module = Arc::new(TestModule::builder().build());
+    let mut make_service = MakeProvidedService::<_, ConnectionService>::new(module);
+
+    let mut first = call(&mut make_service, ()).unwrap();
+    let mut second = call(&mut make_service.clone(), ()).unwrap();
+
+    assert_eq!(call(&mut first, "hello").unwrap(), "hello #0.1");
+    assert_eq!(call(&mut first, "hello").unwrap(), "hello #0.2");
+    assert_eq!(call(&mut second, "hello").unwrap(), "hello #1.1");
+}
+
+/// Provider errors are returned by the make service
+#[test]
+fn provider_error() {
+    let module = Arc::new(TestModule::builder().build());
+    let mut make_service = MakeProvidedService::<_, dyn Unavailable>::new(module);
+
+    let error = call(&mut make_service, ()).err().unwrap();
+    assert_eq!(error.interface(), "dyn make_service::Unavailable");
+    assert_eq!(error.message(), "the database is down");
+    assert_eq!(
+        error.to_string(),
+        "Failed to provide dyn make_service::Unavailable: the database is down"
+    );
+}
+
+/// The make service and its errors can be sent to the server's threads
+#[test]
+fn make_service_is_send() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<MakeProvidedService<TestModule, ConnectionService>>();
+    assert_send_sync::<shaku_tower::ProvideError>();
+}